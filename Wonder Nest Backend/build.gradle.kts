@@ -108,9 +108,15 @@ dependencies {
     // Validation
     implementation("am.ik.yavi:yavi:0.14.1")
     
+    // Allowlist HTML sanitization for formatted text
+    implementation("com.googlecode.owasp-java-html-sanitizer:owasp-java-html-sanitizer:20240325.1")
+    
     // Password hashing
     implementation("org.springframework.security:spring-security-crypto:6.3.6")
     
+    // TOTP for admin two-factor authentication
+    implementation("dev.samstevens.totp:totp:1.7.1")
+    
     // JSON Web Tokens
     implementation("com.auth0:java-jwt:4.4.0")
     
@@ -122,6 +128,7 @@ dependencies {
     
     // Email
     implementation("com.sendgrid:sendgrid-java:4.10.2")
+    implementation("org.eclipse.angus:angus-mail:2.0.3")
     
     // HTTP client for external APIs
     implementation("io.ktor:ktor-client-apache-jvm:$ktor_version")
@@ -159,6 +166,10 @@ dependencies {
 
 tasks.withType<Test> {
     useJUnitPlatform()
+    // Services that refuse to start without production secrets (signed URLs, secret cipher)
+    // are built by configureDependencyInjection() in route tests
+    environment("KTOR_ENV", "development")
+    environment("SIGNED_URL_SECRET", "dGVzdC1zaWduZWQtdXJsLXNlY3JldC0zMi1ieXRlcyE=")
 }
 
 // Configure Java compatibility to match Docker container
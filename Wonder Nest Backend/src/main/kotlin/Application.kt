@@ -1,5 +1,6 @@
 package com.wondernest
 
+import com.wondernest.config.ServerBinding
 import com.wondernest.config.configureAuthentication
 import com.wondernest.config.configureDatabase
 import com.wondernest.config.configureDependencyInjection
@@ -7,12 +8,16 @@ import com.wondernest.config.configureHTTP
 import com.wondernest.config.configureMonitoring
 import com.wondernest.config.configureOpenAPI
 import com.wondernest.config.configureRouting
+import com.wondernest.config.configureScheduledTasks
 import com.wondernest.config.configureSecurity
 import com.wondernest.config.configureSerialization
+import com.wondernest.config.configureShutdown
 import io.ktor.server.application.*
 
 fun main(args: Array<String>) {
-    io.ktor.server.netty.EngineMain.main(args)
+    // Fails here, before anything starts, if SERVER_HOST isn't an IP address
+    val host = ServerBinding.hostFromEnvironment()
+    io.ktor.server.netty.EngineMain.main(arrayOf("-host=${host.hostAddress}") + args)
 }
 
 fun Application.module() {
@@ -25,4 +30,6 @@ fun Application.module() {
     configureOpenAPI()
     configureMonitoring()
     configureRouting()
+    configureScheduledTasks()
+    configureShutdown()
 }
@@ -0,0 +1,109 @@
+package com.wondernest.api
+
+import com.wondernest.domain.repository.FamilyRepository
+import com.wondernest.server.utils.respondError
+import com.wondernest.services.coppa.DataCollectionConsentChecker
+import io.ktor.http.*
+import io.ktor.server.application.*
+import io.ktor.server.auth.*
+import io.ktor.server.auth.jwt.*
+import java.util.*
+
+/**
+ * Family scope of the authenticated user, resolved from the database rather than token claims
+ */
+data class FamilyContext(
+    val userId: UUID,
+    val familyId: UUID,
+    val memberRole: String,
+    val childIds: Set<UUID>,
+    val timezone: String = "UTC"
+) {
+    fun ownsChild(childId: UUID): Boolean = childId in childIds
+
+    fun isParent(): Boolean = memberRole.lowercase() in PARENT_ROLES
+
+    /**
+     * Throws [FamilyAccessDeniedException] unless the child belongs to this family
+     */
+    fun requireChild(childId: UUID) {
+        if (!ownsChild(childId)) throw FamilyAccessDeniedException("Child does not belong to your family")
+    }
+
+    companion object {
+        private val PARENT_ROLES = setOf("parent", "guardian")
+    }
+}
+
+class FamilyAccessDeniedException(message: String) : SecurityException(message)
+
+class FamilyContextResolver(
+    private val familyRepository: FamilyRepository
+) {
+    /**
+     * Returns null when the user does not belong to a family
+     */
+    suspend fun resolve(userId: UUID): FamilyContext? {
+        val family = familyRepository.getFamilyByUserId(userId) ?: return null
+        val member = familyRepository.getFamilyMembers(family.id).firstOrNull { it.userId == userId }
+        val children = familyRepository.getChildrenByFamily(family.id)
+
+        return FamilyContext(
+            userId = userId,
+            familyId = family.id,
+            memberRole = member?.role ?: "parent", // family creators aren't always listed as members
+            childIds = children.map { it.id }.toSet(),
+            timezone = family.timezone
+        )
+    }
+}
+
+/**
+ * Resolve the caller's family, or respond 403 and return null when they have none:
+ *
+ *     val family = call.requireFamilyContext(resolver) ?: return@get
+ *     if (!family.ownsChild(childId)) return@get call.respondError(HttpStatusCode.Forbidden, "...")
+ */
+suspend fun ApplicationCall.requireFamilyContext(resolver: FamilyContextResolver): FamilyContext? {
+    val userId = principal<JWTPrincipal>()?.payload?.getClaim("userId")?.asString()
+        ?.let { runCatching { UUID.fromString(it) }.getOrNull() }
+    if (userId == null) {
+        respondError(HttpStatusCode.Unauthorized, "Invalid user ID in token", "INVALID_TOKEN")
+        return null
+    }
+
+    val context = resolver.resolve(userId)
+    if (context == null) {
+        respondError(HttpStatusCode.Forbidden, "User does not belong to a family", "NO_FAMILY")
+    }
+    return context
+}
+
+/**
+ * Like [requireFamilyContext] but also requires [childId] to belong to the family (403 otherwise)
+ */
+suspend fun ApplicationCall.requireChildAccess(resolver: FamilyContextResolver, childId: UUID): FamilyContext? {
+    val context = requireFamilyContext(resolver) ?: return null
+    if (!context.ownsChild(childId)) {
+        respondError(HttpStatusCode.Forbidden, "Child does not belong to your family", "CHILD_ACCESS_DENIED")
+        return null
+    }
+    return context
+}
+
+/**
+ * Respond 403 and return false unless a parent has consented to data collection for [childId].
+ * Every route that stores data about a child goes through this before writing.
+ */
+suspend fun ApplicationCall.requireDataCollectionConsent(
+    checker: DataCollectionConsentChecker,
+    childId: UUID
+): Boolean {
+    if (checker.isDataCollectionAllowed(childId)) return true
+    respondError(
+        HttpStatusCode.Forbidden,
+        "Parental consent required before collecting data for this child",
+        "PARENTAL_CONSENT_REQUIRED"
+    )
+    return false
+}
@@ -2,15 +2,28 @@ package com.wondernest.api
 
 import com.wondernest.data.database.table.UserRole
 import com.wondernest.domain.model.FileCategory
+import com.wondernest.domain.model.UploadedFile
 import com.wondernest.domain.model.UploadedFileDto
 import com.wondernest.domain.model.User
 import com.wondernest.api.dto.*
+import com.wondernest.services.resilience.RedisUnavailableException
+import com.wondernest.services.storage.ByteRange
+import com.wondernest.services.storage.ByteRangeResult
+import com.wondernest.services.storage.ConditionalGet
+import com.wondernest.services.storage.FileReferenceService
 import com.wondernest.services.storage.FileUploadService
+import com.wondernest.services.storage.FileValidationException
+import com.wondernest.services.storage.FileValidationService
+import com.wondernest.services.storage.SignedUrlService
+import com.wondernest.services.storage.SignedUrlValidation
+import com.wondernest.services.storage.StorageDownload
+import com.wondernest.services.storage.UnknownFileCategoryException
 import io.ktor.http.*
 import io.ktor.http.content.*
 import io.ktor.server.application.*
 import io.ktor.server.auth.*
 import io.ktor.server.auth.jwt.*
+import io.ktor.server.plugins.*
 import io.ktor.server.request.*
 import io.ktor.server.response.*
 import io.ktor.server.routing.*
@@ -52,11 +65,103 @@ fun ApplicationCall.extractUser(): User {
     )
 }
 
+const val MAX_BATCH_FILES = 20
+
+// Links in file listings only need to outlive the page that shows them
+const val PRIVATE_LIST_URL_EXPIRY_SECONDS = 300L
+
+private class SignedLinkUsedException : Exception("Single-use link already used")
+
+// Same error codes the single-file upload responds with
+private fun batchUploadError(e: Exception): ErrorDetails = when (e) {
+    is FileValidationException -> ErrorDetails(code = e.code, message = e.message ?: "File rejected")
+    is IllegalArgumentException -> ErrorDetails(code = "VALIDATION_ERROR", message = e.message ?: "Validation failed")
+    else -> ErrorDetails(code = "UPLOAD_FAILED", message = "Failed to upload file")
+}
+
+/**
+ * scheme://host[:port] the client used, after X-Forwarded-* headers are applied
+ */
+fun ApplicationCall.requestBaseUrl(): String {
+    val origin = request.origin
+    val defaultPort = if (origin.scheme == "https") 443 else 80
+    val port = if (origin.serverPort == defaultPort) "" else ":${origin.serverPort}"
+    return "${origin.scheme}://${origin.serverHost}$port"
+}
+
+/**
+ * Streams [file], honouring a Range header, or responds 404 when it's missing.
+ * Responds 304 when the client's ETag or Last-Modified validator still matches.
+ * [open] opens the whole file (null) or an inclusive byte range.
+ */
+private suspend fun ApplicationCall.respondFileDownload(
+    file: UploadedFile?,
+    open: suspend (LongRange?) -> StorageDownload?
+) {
+    if (file != null) {
+        response.header(HttpHeaders.ETag, file.etag())
+        response.header(HttpHeaders.LastModified, ConditionalGet.formatHttpDate(file.uploadedAt))
+        val notModified = ConditionalGet.isNotModified(
+            request.headers[HttpHeaders.IfNoneMatch],
+            request.headers[HttpHeaders.IfModifiedSince],
+            file.etag(),
+            file.uploadedAt
+        )
+        if (notModified) {
+            respond(HttpStatusCode.NotModified)
+            return
+        }
+    }
+    
+    val range = file?.let { f ->
+        request.headers[HttpHeaders.Range]?.let { ByteRange.parse(it, f.fileSize) }
+    }
+    
+    if (file != null && range == ByteRangeResult.Unsatisfiable) {
+        response.header(HttpHeaders.ContentRange, "bytes */${file.fileSize}")
+        respond(HttpStatusCode.RequestedRangeNotSatisfiable)
+        return
+    }
+    
+    val partial = range as? ByteRangeResult.Satisfiable
+    val download = file?.let { open(partial?.let { it.start..it.end }) }
+    
+    if (file != null && download != null) {
+        response.header(HttpHeaders.AcceptRanges, "bytes")
+        response.header(
+            HttpHeaders.ContentDisposition, 
+            ContentDisposition.Attachment
+                .withParameter(ContentDisposition.Parameters.FileName, file.originalName)
+                .toString()
+        )
+        partial?.let { response.header(HttpHeaders.ContentRange, it.contentRange(file.fileSize)) }
+        // Stream in chunks so large videos aren't buffered in memory
+        respondOutputStream(
+            ContentType.parse(file.mimeType),
+            if (partial != null) HttpStatusCode.PartialContent else HttpStatusCode.OK,
+            download.size
+        ) {
+            download.stream.use { it.copyTo(this) }
+        }
+    } else {
+        respond(HttpStatusCode.NotFound, mapOf(
+            "success" to false,
+            "error" to mapOf(
+                "code" to "FILE_NOT_FOUND",
+                "message" to "File not found"
+            )
+        ))
+    }
+}
+
 /**
  * File upload routes
  */
 fun Route.fileUploadRoutes() {
     val fileUploadService by inject<FileUploadService>()
+    val fileValidationService by inject<FileValidationService>()
+    val fileReferenceService by inject<FileReferenceService>()
+    val signedUrlService by inject<SignedUrlService>()
     
     authenticate("auth-jwt") {
         route("/files") {
@@ -68,9 +173,9 @@ fun Route.fileUploadRoutes() {
                     val multipart = call.receiveMultipart()
                     
                     // Get query parameters
-                    val category = call.request.queryParameters["category"]?.let { 
-                        FileCategory.fromString(it) 
-                    } ?: FileCategory.CONTENT
+                    val category = fileValidationService.resolveCategory(
+                        call.request.queryParameters["category"]
+                    )
                     
                     val childId = call.request.queryParameters["childId"]?.let { 
                         UUID.fromString(it) 
@@ -78,6 +183,9 @@ fun Route.fileUploadRoutes() {
                     
                     val isPublic = call.request.queryParameters["isPublic"]?.toBoolean() ?: false
                     
+                    val familyId = call.principal<JWTPrincipal>()?.payload?.getClaim("familyId")?.asString()
+                        ?.let { runCatching { UUID.fromString(it) }.getOrNull() }
+                    
                     var uploadedFile: UploadedFileDto? = null
                     
                     multipart.forEachPart { part ->
@@ -94,19 +202,11 @@ fun Route.fileUploadRoutes() {
                                     inputStream = part.streamProvider(),
                                     category = category,
                                     childId = childId,
-                                    isPublic = isPublic
+                                    isPublic = isPublic,
+                                    familyId = familyId
                                 )
                                 
-                                uploadedFile = UploadedFileDto(
-                                    id = file.id.toString(),
-                                    originalName = file.originalName,
-                                    mimeType = file.mimeType,
-                                    fileSize = file.fileSize,
-                                    category = file.category.toDbValue(),
-                                    url = file.url,
-                                    uploadedAt = file.uploadedAt.toString(),
-                                    metadata = file.metadata
-                                )
+                                uploadedFile = file.toDto()
                             }
                             else -> {}
                         }
@@ -125,6 +225,25 @@ fun Route.fileUploadRoutes() {
                             )
                         ))
                     }
+                } catch (e: UnknownFileCategoryException) {
+                    call.respond(HttpStatusCode.BadRequest, FileErrorResponse(
+                        error = ErrorDetails(
+                            code = "INVALID_CATEGORY",
+                            message = e.message ?: "Unknown upload category"
+                        )
+                    ))
+                } catch (e: FileValidationException) {
+                    val status = if (e.code == FileValidationService.FILE_TOO_LARGE) {
+                        HttpStatusCode.PayloadTooLarge
+                    } else {
+                        HttpStatusCode.BadRequest
+                    }
+                    call.respond(status, FileErrorResponse(
+                        error = ErrorDetails(
+                            code = e.code,
+                            message = e.message ?: "File rejected"
+                        )
+                    ))
                 } catch (e: IllegalArgumentException) {
                     logger.error(e) { "File validation failed" }
                     call.respond(HttpStatusCode.BadRequest, FileErrorResponse(
@@ -144,46 +263,143 @@ fun Route.fileUploadRoutes() {
                 }
             }
             
+            // Upload several files at once; failures are reported per file instead of aborting the batch,
+            // but a batch over MAX_BATCH_FILES is refused outright
+            post("/upload-batch") {
+                try {
+                    val user = call.extractUser()
+                    val multipart = call.receiveMultipart()
+                    
+                    val category = fileValidationService.resolveCategory(
+                        call.request.queryParameters["category"]
+                    )
+                    val childId = call.request.queryParameters["childId"]?.let { 
+                        UUID.fromString(it) 
+                    }
+                    val isPublic = call.request.queryParameters["isPublic"]?.toBoolean() ?: false
+                    val familyId = call.principal<JWTPrincipal>()?.payload?.getClaim("familyId")?.asString()
+                        ?.let { runCatching { UUID.fromString(it) }.getOrNull() }
+                    
+                    val results = mutableListOf<BatchFileResult>()
+                    val uploaded = mutableListOf<UUID>()
+                    var limitExceeded = false
+                    
+                    // readPart rather than forEachPart so an oversized batch stops being read
+                    while (!limitExceeded) {
+                        val part = multipart.readPart() ?: break
+                        if (part is PartData.FileItem) {
+                            if (results.size >= MAX_BATCH_FILES) {
+                                limitExceeded = true
+                            } else {
+                                val fileName = part.originalFileName ?: "unknown"
+                                val contentType = part.contentType?.toString() ?: "application/octet-stream"
+                                
+                                results += try {
+                                    val file = fileUploadService.uploadFile(
+                                        user = user,
+                                        fileName = fileName,
+                                        contentType = contentType,
+                                        inputStream = part.streamProvider(),
+                                        category = category,
+                                        childId = childId,
+                                        isPublic = isPublic,
+                                        familyId = familyId
+                                    )
+                                    uploaded += file.id
+                                    BatchFileResult(fileName = fileName, status = "success", data = file.toDto())
+                                } catch (e: Exception) {
+                                    logger.warn(e) { "Batch upload of $fileName failed" }
+                                    BatchFileResult(fileName = fileName, status = "error", error = batchUploadError(e))
+                                }
+                            }
+                        }
+                        part.dispose()
+                    }
+                    
+                    if (limitExceeded) {
+                        // The batch is rejected as a whole, so nothing from it is kept
+                        uploaded.forEach { fileUploadService.deleteFile(it, user.id) }
+                        return@post call.respond(HttpStatusCode.PayloadTooLarge, FileErrorResponse(
+                            error = ErrorDetails(
+                                code = "BATCH_LIMIT_EXCEEDED",
+                                message = "Only $MAX_BATCH_FILES files can be uploaded per batch"
+                            )
+                        ))
+                    }
+                    
+                    if (results.isEmpty()) {
+                        return@post call.respond(HttpStatusCode.BadRequest, FileErrorResponse(
+                            error = ErrorDetails(
+                                code = "NO_FILE",
+                                message = "No files provided in the request"
+                            )
+                        ))
+                    }
+                    
+                    val succeeded = results.count { it.status == "success" }
+                    call.respond(HttpStatusCode.OK, FileBatchUploadResponse(
+                        succeeded = succeeded,
+                        failed = results.size - succeeded,
+                        data = results
+                    ))
+                } catch (e: UnknownFileCategoryException) {
+                    call.respond(HttpStatusCode.BadRequest, FileErrorResponse(
+                        error = ErrorDetails(
+                            code = "INVALID_CATEGORY",
+                            message = e.message ?: "Unknown upload category"
+                        )
+                    ))
+                } catch (e: IllegalArgumentException) {
+                    call.respond(HttpStatusCode.BadRequest, FileErrorResponse(
+                        error = ErrorDetails(
+                            code = "VALIDATION_ERROR",
+                            message = e.message ?: "Validation failed"
+                        )
+                    ))
+                } catch (e: Exception) {
+                    logger.error(e) { "Batch upload failed" }
+                    call.respond(HttpStatusCode.InternalServerError, FileErrorResponse(
+                        error = ErrorDetails(
+                            code = "UPLOAD_FAILED",
+                            message = "Failed to upload files"
+                        )
+                    ))
+                }
+            }
+            
             // Get file metadata
             get("/{fileId}") {
                 try {
                     val user = call.extractUser()
-                    val fileId = UUID.fromString(call.parameters["fileId"])
+                    val fileId = call.parameters["fileId"]?.let { runCatching { UUID.fromString(it) }.getOrNull() }
+                        ?: return@get call.respond(HttpStatusCode.BadRequest, FileErrorResponse(
+                            error = ErrorDetails(
+                                code = "INVALID_FILE_ID",
+                                message = "File ID must be a valid UUID"
+                            )
+                        ))
                     
-                    val file = fileUploadService.getFile(fileId, user.id)
+                    // Files the user can't view are reported as missing so their existence isn't leaked
+                    val file = fileUploadService.getViewableFile(fileId, user.id)
                     
                     if (file != null) {
-                        val dto = UploadedFileDto(
-                            id = file.id.toString(),
-                            originalName = file.originalName,
-                            mimeType = file.mimeType,
-                            fileSize = file.fileSize,
-                            category = file.category.toDbValue(),
-                            url = file.url,
-                            uploadedAt = file.uploadedAt.toString(),
-                            metadata = file.metadata
-                        )
-                        
-                        call.respond(HttpStatusCode.OK, mapOf(
-                            "success" to true,
-                            "data" to dto
+                        call.respond(HttpStatusCode.OK, FileUploadSuccessResponse(
+                            data = file.toDto()
                         ))
                     } else {
-                        call.respond(HttpStatusCode.NotFound, mapOf(
-                            "success" to false,
-                            "error" to mapOf(
-                                "code" to "FILE_NOT_FOUND",
-                                "message" to "File not found"
+                        call.respond(HttpStatusCode.NotFound, FileErrorResponse(
+                            error = ErrorDetails(
+                                code = "FILE_NOT_FOUND",
+                                message = "File not found"
                             )
                         ))
                     }
                 } catch (e: Exception) {
                     logger.error(e) { "Failed to get file" }
-                    call.respond(HttpStatusCode.InternalServerError, mapOf(
-                        "success" to false,
-                        "error" to mapOf(
-                            "code" to "GET_FAILED",
-                            "message" to "Failed to get file"
+                    call.respond(HttpStatusCode.InternalServerError, FileErrorResponse(
+                        error = ErrorDetails(
+                            code = "GET_FAILED",
+                            message = "Failed to get file"
                         )
                     ))
                 }
@@ -195,30 +411,8 @@ fun Route.fileUploadRoutes() {
                     val user = call.extractUser()
                     val fileId = UUID.fromString(call.parameters["fileId"])
                     
-                    val file = fileUploadService.getFile(fileId, user.id)
-                    val data = file?.let { fileUploadService.downloadFile(fileId, user.id) }
-                    
-                    if (file != null && data != null) {
-                        call.response.header(
-                            HttpHeaders.ContentDisposition, 
-                            ContentDisposition.Attachment
-                                .withParameter(ContentDisposition.Parameters.FileName, file.originalName)
-                                .toString()
-                        )
-                        call.respondBytes(
-                            data,
-                            ContentType.parse(file.mimeType),
-                            HttpStatusCode.OK
-                        )
-                    } else {
-                        call.respond(HttpStatusCode.NotFound, mapOf(
-                            "success" to false,
-                            "error" to mapOf(
-                                "code" to "FILE_NOT_FOUND",
-                                "message" to "File not found"
-                            )
-                        ))
-                    }
+                    val file = fileUploadService.getViewableFile(fileId, user.id)
+                    call.respondFileDownload(file) { range -> fileUploadService.downloadFile(fileId, user.id, range) }
                 } catch (e: Exception) {
                     logger.error(e) { "Failed to download file" }
                     call.respond(HttpStatusCode.InternalServerError, mapOf(
@@ -231,20 +425,58 @@ fun Route.fileUploadRoutes() {
                 }
             }
             
+            // Make a file public or private
+            patch("/{fileId}/visibility") {
+                try {
+                    val user = call.extractUser()
+                    val fileId = UUID.fromString(call.parameters["fileId"])
+                    val request = call.receive<FileVisibilityRequest>()
+                    
+                    if (fileUploadService.setVisibility(fileId, user.id, request.isPublic)) {
+                        call.respond(HttpStatusCode.OK, FileVisibilityResponse(isPublic = request.isPublic))
+                    } else {
+                        call.respond(HttpStatusCode.NotFound, FileErrorResponse(
+                            error = ErrorDetails(
+                                code = "FILE_NOT_FOUND",
+                                message = "File not found"
+                            )
+                        ))
+                    }
+                } catch (e: Exception) {
+                    logger.error(e) { "Failed to update file visibility" }
+                    call.respond(HttpStatusCode.InternalServerError, FileErrorResponse(
+                        error = ErrorDetails(
+                            code = "VISIBILITY_UPDATE_FAILED",
+                            message = "Failed to update file visibility"
+                        )
+                    ))
+                }
+            }
+            
             // Check file usage in stories
             get("/{fileId}/usage") {
                 try {
                     val user = call.extractUser()
                     val fileId = UUID.fromString(call.parameters["fileId"])
                     
-                    // TODO: Implement actual story usage checking
-                    // For now, return mock data
-                    val mockUsage = FileUsageResponse(
-                        isUsed = false,
-                        stories = emptyList()
-                    )
+                    if (fileUploadService.getFile(fileId, user.id) == null) {
+                        return@get call.respond(HttpStatusCode.NotFound, FileErrorResponse(
+                            error = ErrorDetails(
+                                code = "FILE_NOT_FOUND",
+                                message = "File not found"
+                            )
+                        ))
+                    }
                     
-                    call.respond(HttpStatusCode.OK, mockUsage)
+                    val references = fileReferenceService.getComprehensiveReferences(fileId)
+                    call.respond(HttpStatusCode.OK, references.toUsageResponse())
+                } catch (e: IllegalArgumentException) {
+                    call.respond(HttpStatusCode.BadRequest, FileErrorResponse(
+                        error = ErrorDetails(
+                            code = "INVALID_FILE_ID",
+                            message = "File ID must be a valid UUID"
+                        )
+                    ))
                 } catch (e: Exception) {
                     logger.error(e) { "Failed to check file usage" }
                     call.respond(HttpStatusCode.InternalServerError, FileErrorResponse(
@@ -293,7 +525,13 @@ fun Route.fileUploadRoutes() {
                     val user = call.extractUser()
                     
                     val category = call.request.queryParameters["category"]?.let { 
-                        FileCategory.fromString(it) 
+                        FileCategory.fromStringOrNull(it) 
+                            ?: return@get call.respond(HttpStatusCode.BadRequest, FileErrorResponse(
+                                error = ErrorDetails(
+                                    code = "INVALID_CATEGORY",
+                                    message = "Unknown file category '$it'"
+                                )
+                            ))
                     }
                     
                     val childId = call.request.queryParameters["childId"]?.let { 
@@ -311,21 +549,17 @@ fun Route.fileUploadRoutes() {
                         offset = offset
                     )
                     
-                    val dtos = files.map { file ->
-                        UploadedFileDto(
-                            id = file.id.toString(),
-                            originalName = file.originalName,
-                            mimeType = file.mimeType,
-                            fileSize = file.fileSize,
-                            category = file.category.toDbValue(),
-                            url = file.url,
-                            uploadedAt = file.uploadedAt.toString(),
-                            metadata = file.metadata
-                        )
-                    }
-                    
                     call.respond(HttpStatusCode.OK, FileListSuccessResponse(
-                        data = dtos
+                        // Private files get a short-lived link the client can use without the session
+                        data = files.map { file ->
+                            val dto = file.toDto()
+                            if (file.isPublic) dto
+                            else dto.copy(
+                                url = signedUrlService.generateSignedUrl(
+                                    file.id, PRIVATE_LIST_URL_EXPIRY_SECONDS, requestBaseUrl = call.requestBaseUrl()
+                                )
+                            )
+                        }
                     ))
                 } catch (e: Exception) {
                     logger.error(e) { "Failed to list files" }
@@ -338,5 +572,90 @@ fun Route.fileUploadRoutes() {
                 }
             }
         }
+    }    
+    // Signed links stand in for the session, so this route sits outside authenticate
+    get("/files/{fileId}/signed-download") {
+        try {
+            val fileId = call.parameters["fileId"]?.let { runCatching { UUID.fromString(it) }.getOrNull() }
+                ?: return@get call.respond(HttpStatusCode.BadRequest, FileErrorResponse(
+                    error = ErrorDetails(code = "INVALID_FILE_ID", message = "File ID must be a valid UUID")
+                ))
+            val nonce = call.request.queryParameters["nonce"]
+            val validation = signedUrlService.validateSignedUrl(
+                fileId,
+                call.request.queryParameters["expires"]?.toLongOrNull(),
+                call.request.queryParameters["signature"],
+                nonce,
+                consume = false
+            )
+            val rejection = when (validation) {
+                SignedUrlValidation.VALID -> null
+                SignedUrlValidation.EXPIRED -> ErrorDetails(code = "LINK_EXPIRED", message = "This link has expired")
+                SignedUrlValidation.ALREADY_USED -> ErrorDetails(
+                    code = "LINK_ALREADY_USED",
+                    message = "This link can only be used once and has already been used"
+                )
+                SignedUrlValidation.INVALID -> ErrorDetails(code = "INVALID_SIGNATURE", message = "Invalid download link")
+            }
+            if (rejection != null) {
+                return@get call.respond(HttpStatusCode.Forbidden, FileErrorResponse(error = rejection))
+            }
+            
+            // A partial request would use up a single-use link without delivering the whole file
+            if (nonce != null && call.request.headers[HttpHeaders.Range] != null) {
+                return@get call.respond(HttpStatusCode.BadRequest, FileErrorResponse(
+                    error = ErrorDetails(
+                        code = "RANGE_NOT_SUPPORTED",
+                        message = "Single-use links can only download the whole file"
+                    )
+                ))
+            }
+            
+            val file = fileUploadService.getStoredFile(fileId)
+            if (nonce == null) {
+                call.respondFileDownload(file) { range -> file?.let { fileUploadService.openFile(it, range) } }
+            } else {
+                // The link is only used up once the file has been found and opened
+                call.respondFileDownload(file) { _ ->
+                    file?.let { fileUploadService.openFile(it, null) }?.also { download ->
+                        if (!signedUrlService.consumeNonce(nonce)) {
+                            download.stream.close()
+                            throw SignedLinkUsedException()
+                        }
+                    }
+                }
+            }
+        } catch (e: SignedLinkUsedException) {
+            call.respond(HttpStatusCode.Forbidden, FileErrorResponse(
+                error = ErrorDetails(
+                    code = "LINK_ALREADY_USED",
+                    message = "This link can only be used once and has already been used"
+                )
+            ))
+        } catch (e: RedisUnavailableException) {
+            call.respond(HttpStatusCode.ServiceUnavailable, FileErrorResponse(
+                error = ErrorDetails(code = "SERVICE_UNAVAILABLE", message = "Please try again shortly")
+            ))
+        } catch (e: Exception) {
+            logger.error(e) { "Failed to download file from signed link" }
+            call.respond(HttpStatusCode.InternalServerError, FileErrorResponse(
+                error = ErrorDetails(code = "DOWNLOAD_FAILED", message = "Failed to download file")
+            ))
+        }
+    }
+}
+
+/**
+ * Public files by storage key, the links the local storage provider hands out. Only keys of
+ * public, undeleted uploads resolve, so private files and the .metadata/.tags sidecars stored
+ * next to them are never served.
+ */
+fun Route.publicFileRoutes() {
+    val fileUploadService by inject<FileUploadService>()
+    
+    get("/files/{key...}") {
+        val key = call.parameters.getAll("key")?.joinToString("/").orEmpty()
+        val file = fileUploadService.getPublicFileByKey(key)
+        call.respondFileDownload(file) { range -> file?.let { fileUploadService.openFile(it, range) } }
     }
-}
\ No newline at end of file
+}
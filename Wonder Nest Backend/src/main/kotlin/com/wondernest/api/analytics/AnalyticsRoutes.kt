@@ -1,6 +1,18 @@
 package com.wondernest.api.analytics
 
+import com.wondernest.api.FamilyContextResolver
+import com.wondernest.api.requireChildAccess
+import com.wondernest.api.requireFamilyContext
+import com.wondernest.api.requireDataCollectionConsent
 import com.wondernest.data.database.table.SimpleGameData
+import com.wondernest.services.analytics.AnalyticsBatchService
+import com.wondernest.services.analytics.AnalyticsBatchTooLargeException
+import com.wondernest.services.analytics.AnalyticsEventService
+import com.wondernest.services.analytics.MilestoneService
+import com.wondernest.services.analytics.ScreenTimeLimitRequest
+import com.wondernest.services.analytics.ScreenTimeService
+import com.wondernest.services.coppa.DataCollectionConsentChecker
+import com.wondernest.services.family.ChildPseudonymService
 import io.ktor.http.*
 import io.ktor.server.application.*
 import io.ktor.server.auth.*
@@ -15,6 +27,12 @@ import kotlinx.serialization.json.Json
 import org.jetbrains.exposed.sql.*
 import org.jetbrains.exposed.sql.SqlExpressionBuilder.eq
 import org.jetbrains.exposed.sql.transactions.transaction
+import org.koin.ktor.ext.inject
+import java.time.DayOfWeek
+import java.time.LocalDate
+import java.time.ZoneId
+import java.time.ZoneOffset
+import java.time.temporal.TemporalAdjusters
 import java.util.UUID
 
 // Database operations for game data storage
@@ -22,6 +40,9 @@ import java.util.UUID
 @Serializable
 data class MessageResponse(val message: String)
 
+@Serializable
+data class MarkMilestoneRequest(val milestoneKey: String)
+
 @Serializable
 data class DailyAnalytics(
     val date: String,
@@ -52,7 +73,9 @@ data class AnalyticsEvent(
     val contentId: String? = null,
     val duration: Int? = null,
     val eventData: Map<String, kotlinx.serialization.json.JsonElement> = emptyMap(),
-    val sessionId: String? = null
+    val sessionId: String? = null,
+    // Client-generated UUID; replaying an event with the same ID doesn't count it again
+    val clientEventId: String? = null
 )
 
 @Serializable
@@ -67,6 +90,14 @@ data class WeeklyOverview(
 )
 
 fun Route.analyticsRoutes() {
+    val analyticsEventService by inject<AnalyticsEventService>()
+    val childPseudonymService by inject<ChildPseudonymService>()
+    val consentChecker by inject<DataCollectionConsentChecker>()
+    val familyContextResolver by inject<FamilyContextResolver>()
+    val analyticsBatchService by inject<AnalyticsBatchService>()
+    val screenTimeService by inject<ScreenTimeService>()
+    val milestoneService by inject<MilestoneService>()
+    
     authenticate("auth-jwt") {
         route("/analytics") {
             // Daily analytics for a specific child (Flutter expects this)
@@ -74,10 +105,9 @@ fun Route.analyticsRoutes() {
                 try {
                     val childId = call.request.queryParameters["childId"]
                         ?: return@get call.respond(HttpStatusCode.BadRequest, MessageResponse("Child ID is required"))
-
-                    val principal = call.principal<JWTPrincipal>()
-                    val familyId = principal?.payload?.getClaim("familyId")?.asString()
-                        ?: return@get call.respond(HttpStatusCode.BadRequest, MessageResponse("No family context in token"))
+                    val internalId = childPseudonymService.resolve(childId)
+                        ?: return@get call.respond(HttpStatusCode.NotFound, MessageResponse("Unknown child ID"))
+                    call.requireChildAccess(familyContextResolver, internalId) ?: return@get
 
                     // TODO: PRODUCTION - Fetch real analytics from database
                     val mockAnalytics = generateMockDailyAnalytics(childId)
@@ -99,10 +129,9 @@ fun Route.analyticsRoutes() {
                 try {
                     val childId = call.parameters["childId"]
                         ?: return@get call.respond(HttpStatusCode.BadRequest, MessageResponse("Child ID is required"))
-
-                    val principal = call.principal<JWTPrincipal>()
-                    val familyId = principal?.payload?.getClaim("familyId")?.asString()
-                        ?: return@get call.respond(HttpStatusCode.BadRequest, MessageResponse("No family context in token"))
+                    val internalId = childPseudonymService.resolve(childId)
+                        ?: return@get call.respond(HttpStatusCode.NotFound, MessageResponse("Unknown child ID"))
+                    call.requireChildAccess(familyContextResolver, internalId) ?: return@get
 
                     // TODO: PRODUCTION - Generate real insights based on child's learning patterns
                     val mockInsights = generateMockChildInsights(childId)
@@ -118,17 +147,22 @@ fun Route.analyticsRoutes() {
             // Weekly overview for parent dashboard
             get("/weekly") {
                 try {
-                    val childId = call.request.queryParameters["childId"]
+                    val childReference = call.request.queryParameters["childId"]
                         ?: return@get call.respond(HttpStatusCode.BadRequest, MessageResponse("Child ID is required"))
+                    val childId = childPseudonymService.resolve(childReference)
+                        ?: return@get call.respond(HttpStatusCode.NotFound, MessageResponse("Unknown child ID"))
+                    val family = call.requireChildAccess(familyContextResolver, childId) ?: return@get
 
-                    val principal = call.principal<JWTPrincipal>()
-                    val familyId = principal?.payload?.getClaim("familyId")?.asString()
-                        ?: return@get call.respond(HttpStatusCode.BadRequest, MessageResponse("No family context in token"))
+                    // Weeks run Monday to Sunday in the family's timezone
+                    val zone = runCatching { ZoneId.of(family.timezone) }.getOrDefault(ZoneOffset.UTC)
+                    val weekStart = call.request.queryParameters["weekStart"]?.let {
+                        runCatching { LocalDate.parse(it) }.getOrNull()
+                            ?: return@get call.respond(HttpStatusCode.BadRequest, MessageResponse("weekStart must be a date like 2025-06-02"))
+                    } ?: LocalDate.now(zone).with(TemporalAdjusters.previousOrSame(DayOfWeek.MONDAY))
 
-                    // TODO: PRODUCTION - Calculate real weekly overview
-                    val mockWeekly = generateMockWeeklyOverview(childId)
-                    
-                    call.respond(HttpStatusCode.OK, mockWeekly)
+                    val weekly = analyticsEventService.weeklyOverview(childId.toString(), weekStart, zone)
+
+                    call.respond(HttpStatusCode.OK, weekly)
                     call.application.environment.log.info("Generated weekly overview for child: $childId")
                 } catch (e: Exception) {
                     call.application.environment.log.error("Error generating weekly overview", e)
@@ -136,31 +170,119 @@ fun Route.analyticsRoutes() {
                 }
             }
             
+            // Today's screen time against the child's daily limit
+            route("/screen-time/{childId}") {
+                get("/status") {
+                    try {
+                        val externalId = call.parameters["childId"].orEmpty()
+                        val childId = childPseudonymService.resolve(externalId)
+                            ?: return@get call.respond(HttpStatusCode.NotFound, MessageResponse("Unknown child ID"))
+                        call.requireChildAccess(familyContextResolver, childId) ?: return@get
+
+                        val status = screenTimeService.status(childId)
+                            ?: return@get call.respond(HttpStatusCode.NotFound, MessageResponse("Child not found"))
+                        call.respond(HttpStatusCode.OK, status.copy(childId = externalId))
+                    } catch (e: Exception) {
+                        call.application.environment.log.error("Error retrieving screen-time status", e)
+                        call.respond(HttpStatusCode.InternalServerError, MessageResponse("Failed to retrieve screen-time status"))
+                    }
+                }
+
+                put("/limit") {
+                    try {
+                        val externalId = call.parameters["childId"].orEmpty()
+                        val childId = childPseudonymService.resolve(externalId)
+                            ?: return@put call.respond(HttpStatusCode.NotFound, MessageResponse("Unknown child ID"))
+                        val family = call.requireChildAccess(familyContextResolver, childId) ?: return@put
+                        if (!family.isParent()) {
+                            return@put call.respond(HttpStatusCode.Forbidden, MessageResponse("Only parents can change screen-time limits"))
+                        }
+
+                        val request = call.receive<ScreenTimeLimitRequest>()
+                        if (!screenTimeService.setLimit(childId, request.dailyLimitMinutes)) {
+                            return@put call.respond(HttpStatusCode.NotFound, MessageResponse("Child not found"))
+                        }
+                        // A lowered limit may already be exceeded today
+                        val status = screenTimeService.checkLimit(childId)
+                            ?: return@put call.respond(HttpStatusCode.NotFound, MessageResponse("Child not found"))
+                        call.respond(HttpStatusCode.OK, status.copy(childId = externalId))
+                    } catch (e: IllegalArgumentException) {
+                        call.respond(HttpStatusCode.BadRequest, MessageResponse(e.message ?: "Invalid limit"))
+                    } catch (e: Exception) {
+                        call.application.environment.log.error("Error updating screen-time limit", e)
+                        call.respond(HttpStatusCode.InternalServerError, MessageResponse("Failed to update screen-time limit"))
+                    }
+                }
+
+                // Days the child went over their limit, newest last
+                get("/alerts") {
+                    try {
+                        val externalId = call.parameters["childId"].orEmpty()
+                        val childId = childPseudonymService.resolve(externalId)
+                            ?: return@get call.respond(HttpStatusCode.NotFound, MessageResponse("Unknown child ID"))
+                        call.requireChildAccess(familyContextResolver, childId) ?: return@get
+
+                        call.respond(HttpStatusCode.OK, screenTimeService.alerts(childId).map { it.copy(childId = externalId) })
+                    } catch (e: Exception) {
+                        call.application.environment.log.error("Error retrieving screen-time alerts", e)
+                        call.respond(HttpStatusCode.InternalServerError, MessageResponse("Failed to retrieve screen-time alerts"))
+                    }
+                }
+            }
+
+            route("/milestones/{childId}") {
+                get {
+                    try {
+                        val externalId = call.parameters["childId"].orEmpty()
+                        val childId = childPseudonymService.resolve(externalId)
+                            ?: return@get call.respond(HttpStatusCode.NotFound, MessageResponse("Unknown child ID"))
+                        call.requireChildAccess(familyContextResolver, childId) ?: return@get
+
+                        val milestones = milestoneService.milestonesFor(childId)
+                            ?: return@get call.respond(HttpStatusCode.NotFound, MessageResponse("Child not found"))
+                        call.respond(HttpStatusCode.OK, milestones.copy(childId = externalId))
+                    } catch (e: Exception) {
+                        call.application.environment.log.error("Error retrieving milestones", e)
+                        call.respond(HttpStatusCode.InternalServerError, MessageResponse("Failed to retrieve milestones"))
+                    }
+                }
+
+                // Marking the same milestone again keeps the original achievedAt
+                post {
+                    try {
+                        val childId = call.parameters["childId"]?.let { childPseudonymService.resolve(it) }
+                            ?: return@post call.respond(HttpStatusCode.NotFound, MessageResponse("Unknown child ID"))
+                        val family = call.requireChildAccess(familyContextResolver, childId) ?: return@post
+                        if (!family.isParent()) {
+                            return@post call.respond(HttpStatusCode.Forbidden, MessageResponse("Only parents can mark milestones"))
+                        }
+
+                        val request = call.receive<MarkMilestoneRequest>()
+                        val milestone = milestoneService.markAchieved(childId, request.milestoneKey)
+                            ?: return@post call.respond(HttpStatusCode.NotFound, MessageResponse("Child not found"))
+                        call.respond(HttpStatusCode.OK, milestone)
+                    } catch (e: IllegalArgumentException) {
+                        call.respond(HttpStatusCode.BadRequest, MessageResponse(e.message ?: "Invalid milestone"))
+                    } catch (e: Exception) {
+                        call.application.environment.log.error("Error marking milestone", e)
+                        call.respond(HttpStatusCode.InternalServerError, MessageResponse("Failed to mark milestone"))
+                    }
+                }
+            }
+
             get("/children/{childId}/milestones") {
                 try {
-                    val childId = call.parameters["childId"]
-                        ?: return@get call.respond(HttpStatusCode.BadRequest, MessageResponse("Child ID is required"))
+                    val externalId = call.parameters["childId"].orEmpty()
+                    val childId = childPseudonymService.resolve(externalId)
+                        ?: return@get call.respond(HttpStatusCode.NotFound, MessageResponse("Unknown child ID"))
+                    call.requireChildAccess(familyContextResolver, childId) ?: return@get
 
-                    // TODO: PRODUCTION - Track and return actual developmental milestones
-                    val mockMilestones = mapOf(
-                        "age" to 6,
-                        "milestones" to listOf(
-                            mapOf("category" to "math", "description" to "Can count to 100", "achieved" to true),
-                            mapOf("category" to "reading", "description" to "Recognizes sight words", "achieved" to true),
-                            mapOf("category" to "social", "description" to "Shares and takes turns", "achieved" to false)
-                        ),
-                        "nextGoals" to listOf(
-                            "Practice addition with objects",
-                            "Read simple sentences",
-                            "Practice conflict resolution"
-                        )
-                    )
-                    
-                    call.respond(HttpStatusCode.OK, mockMilestones)
-                    call.application.environment.log.info("Generated milestones for child: $childId")
+                    val milestones = milestoneService.milestonesFor(childId)
+                        ?: return@get call.respond(HttpStatusCode.NotFound, MessageResponse("Child not found"))
+                    call.respond(HttpStatusCode.OK, milestones.copy(childId = externalId))
                 } catch (e: Exception) {
-                    call.application.environment.log.error("Error generating milestones", e)
-                    call.respond(HttpStatusCode.InternalServerError, MessageResponse("Failed to generate milestones"))
+                    call.application.environment.log.error("Error retrieving milestones", e)
+                    call.respond(HttpStatusCode.InternalServerError, MessageResponse("Failed to retrieve milestones"))
                 }
             }
             
@@ -176,7 +298,9 @@ fun Route.analyticsRoutes() {
 
                     call.application.environment.log.info("Loading game data for child: $childIdParam")
                     
-                    val childId = UUID.fromString(childIdParam)
+                    val childId = childPseudonymService.resolve(childIdParam)
+                        ?: return@get call.respond(HttpStatusCode.NotFound, MessageResponse("Unknown child ID"))
+                    call.requireChildAccess(familyContextResolver, childId) ?: return@get
                     
                     // Get stored game data for this child from database
                     val gameDataList = transaction {
@@ -207,6 +331,22 @@ fun Route.analyticsRoutes() {
                 }
             }
             
+            // Events buffered offline, flushed together; safe to resend after a crash
+            post("/events/batch") {
+                // Malformed bodies are left to the central error handler
+                val events = call.receive<List<AnalyticsEvent>>()
+                try {
+                    val family = call.requireFamilyContext(familyContextResolver) ?: return@post
+
+                    call.respond(HttpStatusCode.OK, analyticsBatchService.ingest(family, events))
+                } catch (e: AnalyticsBatchTooLargeException) {
+                    call.respond(HttpStatusCode.PayloadTooLarge, MessageResponse(e.message ?: "Batch too large"))
+                } catch (e: Exception) {
+                    call.application.environment.log.error("Error ingesting analytics batch", e)
+                    call.respond(HttpStatusCode.InternalServerError, MessageResponse("Failed to ingest analytics batch"))
+                }
+            }
+
             post("/events") {
                 call.application.environment.log.info("=== ANALYTICS EVENT POST REQUEST START ===")
                 
@@ -270,6 +410,11 @@ fun Route.analyticsRoutes() {
                         return@post call.respond(HttpStatusCode.BadRequest, MessageResponse("No family context in token"))
                     }
 
+                    // Children are identified by their external (pseudonymous) ID; raw UUIDs and rotated IDs don't resolve
+                    val resolvedChildId = childPseudonymService.resolve(event.childId)
+                        ?: return@post call.respond(HttpStatusCode.NotFound, MessageResponse("Unknown child ID"))
+                    if (!call.requireDataCollectionConsent(consentChecker, resolvedChildId)) return@post
+
                     call.application.environment.log.info("All validation passed, processing event...")
                     
                     // Special handling for sticker book project saves
@@ -287,7 +432,7 @@ fun Route.analyticsRoutes() {
                         
                         if (projectId != null && fullProjectData != null) {
                             call.application.environment.log.info("🎨 Both projectId and fullProjectData are present - proceeding with save")
-                            val childId = UUID.fromString(event.childId)
+                            val childId = resolvedChildId
                             val dataKey = "sticker_project_$projectId"
                             
                             // Parse and validate the JSON data
@@ -370,7 +515,7 @@ fun Route.analyticsRoutes() {
                         
                         val projectId = event.eventData["projectId"]?.toString()
                         if (projectId != null) {
-                            val childId = UUID.fromString(event.childId)
+                            val childId = resolvedChildId
                             val dataKey = "sticker_project_$projectId"
                             
                             transaction {
@@ -397,14 +542,20 @@ fun Route.analyticsRoutes() {
                         }
                     }
                     
-                    // TODO: PRODUCTION - Store event in analytics database
-                    // This would include events like:
-                    // - content_started, content_completed, content_paused
-                    // - activity_started, activity_completed
-                    // - milestone_achieved, struggle_detected
-                    // - parent_intervention_needed
+                    // Aggregates are always updated; raw rows for high-volume types are sampled
+                    val recorded = analyticsEventService.record(
+                        childId = resolvedChildId.toString(),
+                        eventType = event.eventType,
+                        contentId = event.contentId,
+                        duration = event.duration,
+                        eventData = event.eventData,
+                        sessionId = event.sessionId,
+                        clientEventId = event.clientEventId
+                    )
                     
-                    val eventId = "event_${System.currentTimeMillis()}"
+                    if (!recorded.duplicate) screenTimeService.checkLimit(resolvedChildId)
+
+                    val eventId = recorded.eventId
                     val timestamp = System.currentTimeMillis().toString()
                     
                     val response = mapOf(
@@ -466,15 +617,3 @@ private fun generateMockChildInsights(childId: String): ChildInsights {
         )
     )
 }
-
-private fun generateMockWeeklyOverview(childId: String): WeeklyOverview {
-    return WeeklyOverview(
-        weekStart = "2024-08-11",
-        totalScreenTime = 315, // minutes for the week
-        educationalPercentage = 68.0,
-        averageDailyUsage = 45,
-        topCategories = listOf("educational", "stories", "music"),
-        completionRate = 0.82,
-        parentalInteraction = 12 // number of parent interactions
-    )
-}
\ No newline at end of file
@@ -1,16 +1,38 @@
 package com.wondernest.api.audio
 
+import com.wondernest.api.FamilyContextResolver
+import com.wondernest.api.auth.ValidationErrorResponse
+import com.wondernest.api.requireChildAccess
+import com.wondernest.api.requireDataCollectionConsent
+import com.wondernest.api.validation.AudioMetricsValidation
+import com.wondernest.services.audio.AudioMetricsRequest
+import com.wondernest.services.audio.AudioMetricsService
+import com.wondernest.services.audio.SpeechTrendPeriod
+import com.wondernest.services.coppa.DataCollectionConsentChecker
+import com.wondernest.services.family.ChildPseudonymService
 import io.ktor.http.*
 import io.ktor.server.application.*
 import io.ktor.server.auth.*
+import io.ktor.server.request.*
 import io.ktor.server.response.*
 import io.ktor.server.routing.*
 import kotlinx.serialization.Serializable
+import org.koin.ktor.ext.inject
+import java.time.ZoneId
+import java.time.ZoneOffset
 
 @Serializable
 data class MessageResponse(val message: String)
 
+@Serializable
+data class AudioMetricsRecorded(val metricsId: String, val recordedAt: String)
+
 fun Route.audioRoutes() {
+    val audioMetricsService by inject<AudioMetricsService>()
+    val childPseudonymService by inject<ChildPseudonymService>()
+    val consentChecker by inject<DataCollectionConsentChecker>()
+    val familyContextResolver by inject<FamilyContextResolver>()
+
     authenticate("auth-jwt") {
         route("/audio") {
             post("/sessions") {
@@ -22,7 +44,44 @@ fun Route.audioRoutes() {
             }
             
             post("/metrics") {
-                call.respond(HttpStatusCode.Created, MessageResponse("Upload audio metrics - TODO"))
+                val request = call.receive<AudioMetricsRequest>()
+                val validation = AudioMetricsValidation.validate(request)
+                if (!validation.isValid) {
+                    return@post call.respond(
+                        HttpStatusCode.BadRequest,
+                        ValidationErrorResponse("Validation failed: ${validation.errors.joinToString(", ")}", validation.fieldErrors)
+                    )
+                }
+                try {
+                    val childId = childPseudonymService.resolve(request.childId)
+                        ?: return@post call.respond(HttpStatusCode.NotFound, MessageResponse("Unknown child ID"))
+                    call.requireChildAccess(familyContextResolver, childId) ?: return@post
+                    if (!call.requireDataCollectionConsent(consentChecker, childId)) return@post
+
+                    val stored = audioMetricsService.record(childId, request)
+                    call.respond(HttpStatusCode.Created, AudioMetricsRecorded(stored.id, stored.recordedAt.toString()))
+                } catch (e: Exception) {
+                    call.application.environment.log.error("Error recording audio metrics", e)
+                    call.respond(HttpStatusCode.InternalServerError, MessageResponse("Failed to record audio metrics"))
+                }
+            }
+
+            // Speech development over time; ?period=week|month|quarter, month by default
+            get("/metrics/{childId}/trends") {
+                try {
+                    val externalId = call.parameters["childId"].orEmpty()
+                    val childId = childPseudonymService.resolve(externalId)
+                        ?: return@get call.respond(HttpStatusCode.NotFound, MessageResponse("Unknown child ID"))
+                    val family = call.requireChildAccess(familyContextResolver, childId) ?: return@get
+                    val period = SpeechTrendPeriod.parse(call.request.queryParameters["period"])
+                        ?: return@get call.respond(HttpStatusCode.BadRequest, MessageResponse("period must be week, month or quarter"))
+
+                    val zone = runCatching { ZoneId.of(family.timezone) }.getOrDefault(ZoneOffset.UTC)
+                    call.respond(HttpStatusCode.OK, audioMetricsService.trends(childId, period, zone).copy(childId = externalId))
+                } catch (e: Exception) {
+                    call.application.environment.log.error("Error generating speech trends", e)
+                    call.respond(HttpStatusCode.InternalServerError, MessageResponse("Failed to generate speech trends"))
+                }
             }
             
             get("/sessions/{sessionId}/status") {
@@ -0,0 +1,30 @@
+package com.wondernest.api.auth
+
+import com.wondernest.server.utils.respondError
+import com.wondernest.services.auth.AuthRateLimiter
+import io.ktor.http.*
+import io.ktor.server.application.*
+import io.ktor.server.plugins.*
+import io.ktor.server.response.*
+
+/**
+ * Rate limit keys for this request. The client IP comes from the socket rather than
+ * request.origin, which XForwardedHeaders fills from whatever X-Forwarded-For the client sends;
+ * the header is only believed when the peer is a configured proxy.
+ */
+fun ApplicationCall.authAttemptKeys(limiter: AuthRateLimiter, action: String, email: String? = null): List<String> =
+    AuthRateLimiter.keysFor(
+        action,
+        limiter.clientAddress(request.local.remoteHost, request.headers[HttpHeaders.XForwardedFor]),
+        email
+    )
+
+/**
+ * Count this attempt against [keys]; respond 429 with Retry-After and return true when they're over the limit
+ */
+suspend fun ApplicationCall.rejectIfThrottled(limiter: AuthRateLimiter, keys: List<String>): Boolean {
+    val retryAfter = limiter.acquire(keys) ?: return false
+    response.header(HttpHeaders.RetryAfter, retryAfter.toString())
+    respondError(HttpStatusCode.TooManyRequests, "Too many attempts, try again later", "RATE_LIMITED")
+    return true
+}
@@ -3,14 +3,22 @@ package com.wondernest.api.auth
 import com.wondernest.api.validation.AuthValidation
 import com.wondernest.api.validation.AuthValidationException
 import com.wondernest.api.validation.throwIfInvalid
+import com.wondernest.services.auth.AuthRateLimiter
 import com.wondernest.services.auth.AuthService
+import com.wondernest.services.auth.EmailVerificationResult
 import com.wondernest.services.auth.SignupRequest
 import com.wondernest.services.auth.LoginRequest
 import com.wondernest.services.auth.OAuthLoginRequest
+import com.wondernest.services.auth.ParentPinVerifier
+import com.wondernest.services.auth.PinVerificationOutcome
+import com.wondernest.utils.FieldError
+import com.wondernest.utils.ValidationUtils
 import io.ktor.http.*
+import io.ktor.http.auth.*
 import io.ktor.server.application.*
 import io.ktor.server.auth.*
 import io.ktor.server.auth.jwt.*
+import io.ktor.server.plugins.*
 import io.ktor.server.plugins.contentnegotiation.*
 import io.ktor.server.plugins.ratelimit.*
 import io.ktor.server.request.*
@@ -20,9 +28,6 @@ import kotlinx.serialization.Serializable
 import org.koin.ktor.ext.inject
 import java.util.*
 
-@Serializable
-data class VerifyEmailRequest(val userId: String)
-
 @Serializable
 data class PasswordResetRequest(val email: String)
 
@@ -38,6 +43,9 @@ data class RefreshTokenRequest(val refreshToken: String?)
 @Serializable
 data class PinVerificationRequest(val pin: String)
 
+@Serializable
+data class PinSetupResponse(val success: Boolean, val message: String)
+
 @Serializable  
 data class PinVerificationResponse(
     val verified: Boolean,
@@ -48,25 +56,41 @@ data class PinVerificationResponse(
 @Serializable
 data class MessageResponse(val message: String)
 
+/**
+ * Validation failure body. [message] keeps the flat summary; [errors] ties each failure to its form field.
+ */
+@Serializable
+data class ValidationErrorResponse(
+    val message: String,
+    val errors: List<FieldError> = emptyList()
+)
+
+@Serializable
+data class PasswordStrengthRequest(val password: String)
+
+private suspend fun ApplicationCall.respondValidationFailed(e: AuthValidationException) {
+    respond(HttpStatusCode.BadRequest, ValidationErrorResponse(e.message ?: "Validation failed", e.fieldErrors))
+}
+
 fun Route.authRoutes() {
     val authService by inject<AuthService>()
+    val authRateLimiter by inject<AuthRateLimiter>()
+    val parentPinVerifier by inject<ParentPinVerifier>()
 
     route("/auth") {
         
         rateLimit(RateLimitName("auth")) {
             // Parent-specific registration (Flutter expects this endpoint)
             post("/parent/register") {
+                // Every signup counts against the client's budget, successful or not
+                val attemptKeys = call.authAttemptKeys(authRateLimiter, "signup")
+                if (call.rejectIfThrottled(authRateLimiter, attemptKeys)) return@post
                 try {
                     val rawRequest = call.receive<SignupRequest>()
                     call.application.environment.log.info("Received parent signup request: email=${rawRequest.email}, name=${rawRequest.firstName} ${rawRequest.lastName}")
                     
                     // Validate request
-                    val validationResult = AuthValidation.validateSignupRequest(rawRequest)
-                    if (!validationResult.isValid) {
-                        call.application.environment.log.warn("Parent signup validation failed: ${validationResult.errors}")
-                        call.respond(HttpStatusCode.BadRequest, MessageResponse("Validation failed: ${validationResult.errors.joinToString(", ")}"))
-                        return@post
-                    }
+                    AuthValidation.validateSignupRequest(rawRequest).throwIfInvalid()
                     
                     // Sanitize request
                     val sanitizedRequest = AuthValidation.sanitizeSignupRequest(rawRequest)
@@ -78,7 +102,7 @@ fun Route.authRoutes() {
                     call.respond(HttpStatusCode.Created, response)
                 } catch (e: AuthValidationException) {
                     call.application.environment.log.warn("Parent signup validation exception: ${e.message}", e)
-                    call.respond(HttpStatusCode.BadRequest, MessageResponse(e.message ?: "Validation failed"))
+                    call.respondValidationFailed(e)
                 } catch (e: IllegalArgumentException) {
                     call.application.environment.log.warn("Parent signup illegal argument: ${e.message}", e)
                     call.respond(HttpStatusCode.BadRequest, MessageResponse(e.message ?: "Invalid input"))
@@ -93,6 +117,8 @@ fun Route.authRoutes() {
 
             // Parent-specific login (Flutter expects this endpoint)  
             post("/parent/login") {
+                // Successful logins are released, so signing in never uses up the budget
+                var attemptKeys = call.authAttemptKeys(authRateLimiter, "login")
                 try {
                     // Get the raw request body
                     val rawBody = call.receiveText()
@@ -108,6 +134,8 @@ fun Route.authRoutes() {
                     }
                     val rawRequest = json.decodeFromString<LoginRequest>(fixedBody)
                     call.application.environment.log.info("Received parent login request: email=${rawRequest.email}")
+                    attemptKeys = call.authAttemptKeys(authRateLimiter, "login", rawRequest.email)
+                    if (call.rejectIfThrottled(authRateLimiter, attemptKeys)) return@post
                     
                     // Validate request
                     AuthValidation.validateLoginRequest(rawRequest).throwIfInvalid()
@@ -118,9 +146,10 @@ fun Route.authRoutes() {
                     // Login with family context
                     val response = authService.loginParent(sanitizedRequest)
                     call.application.environment.log.info("Parent login successful for user: ${sanitizedRequest.email}")
+                    authRateLimiter.release(attemptKeys)
                     call.respond(HttpStatusCode.OK, response)
                 } catch (e: AuthValidationException) {
-                    call.respond(HttpStatusCode.BadRequest, MessageResponse(e.message ?: "Validation failed"))
+                    call.respondValidationFailed(e)
                 } catch (e: SecurityException) {
                     call.respond(HttpStatusCode.Unauthorized, MessageResponse("Invalid credentials"))
                 } catch (e: Exception) {
@@ -129,64 +158,106 @@ fun Route.authRoutes() {
                 }
             }
 
-            // PIN verification endpoint (Flutter expects this for parent mode switching)
-            post("/parent/verify-pin") {
-                try {
-                    val rawRequest = call.receive<PinVerificationRequest>()
-                    
-                    // Basic validation
-                    if (rawRequest.pin.isBlank() || rawRequest.pin.length != 4 || !rawRequest.pin.all { it.isDigit() }) {
-                        call.respond(HttpStatusCode.BadRequest, PinVerificationResponse(
-                            verified = false,
-                            message = "Invalid PIN format. Must be 4 digits."
-                        ))
-                        return@post
+            // PIN verification endpoint (Flutter expects this for parent mode switching).
+            // Only a signed-in parent can try a PIN, and attempts are tracked per user.
+            authenticate("auth-jwt") {
+                // First PIN for a parent account; an existing PIN is never replaced here
+                post("/parent/setup-pin") {
+                    try {
+                        val userId = call.principal<JWTPrincipal>()?.payload?.getClaim("userId")?.asString()
+                        if (userId.isNullOrBlank()) {
+                            return@post call.respond(HttpStatusCode.Unauthorized, MessageResponse("Invalid user ID in token"))
+                        }
+                        val rawRequest = call.receive<PinVerificationRequest>()
+
+                        if (parentPinVerifier.setupPin(UUID.fromString(userId), rawRequest.pin)) {
+                            call.respond(HttpStatusCode.OK, PinSetupResponse(success = true, message = "PIN set up"))
+                        } else {
+                            call.respond(HttpStatusCode.Conflict, PinSetupResponse(success = false, message = "A PIN is already set up"))
+                        }
+                    } catch (e: IllegalArgumentException) {
+                        call.respond(HttpStatusCode.BadRequest, PinSetupResponse(success = false, message = e.message ?: "Invalid PIN"))
+                    } catch (e: Exception) {
+                        call.application.environment.log.error("PIN setup error", e)
+                        call.respond(HttpStatusCode.InternalServerError, PinSetupResponse(success = false, message = "PIN setup failed"))
                     }
-                    
-                    // TODO: PRODUCTION - Implement proper PIN storage and verification
-                    // For now, we'll use a default PIN for development/demo
-                    val defaultPin = "1234" // TODO: Remove this and implement proper PIN management
-                    
-                    if (rawRequest.pin == defaultPin) {
-                        // Generate a temporary session token for parent mode
-                        // TODO: Implement proper parent mode session management
-                        val sessionToken = "parent_mode_${System.currentTimeMillis()}"
-                        
-                        call.respond(HttpStatusCode.OK, PinVerificationResponse(
-                            verified = true,
-                            message = "PIN verified successfully",
-                            sessionToken = sessionToken
-                        ))
-                        call.application.environment.log.info("PIN verification successful")
-                    } else {
-                        call.respond(HttpStatusCode.Unauthorized, PinVerificationResponse(
+                }
+
+                post("/parent/verify-pin") {
+                    try {
+                        val userId = call.principal<JWTPrincipal>()?.payload?.getClaim("userId")?.asString()
+                        if (userId.isNullOrBlank()) {
+                            return@post call.respond(HttpStatusCode.Unauthorized, PinVerificationResponse(
+                                verified = false,
+                                message = "Invalid user ID in token"
+                            ))
+                        }
+                        val rawRequest = call.receive<PinVerificationRequest>()
+
+                        when (val outcome = parentPinVerifier.verify(UUID.fromString(userId), rawRequest.pin)) {
+                            PinVerificationOutcome.Verified -> {
+                                // TODO: Implement proper parent mode session management
+                                val sessionToken = "parent_mode_${System.currentTimeMillis()}"
+                                call.respond(HttpStatusCode.OK, PinVerificationResponse(
+                                    verified = true,
+                                    message = "PIN verified successfully",
+                                    sessionToken = sessionToken
+                                ))
+                                call.application.environment.log.info("PIN verification successful")
+                            }
+                            PinVerificationOutcome.InvalidFormat -> {
+                                call.respond(HttpStatusCode.BadRequest, PinVerificationResponse(
+                                    verified = false,
+                                    message = "Invalid PIN format. Must be ${ParentPinVerifier.PIN_LENGTH.first} to ${ParentPinVerifier.PIN_LENGTH.last} digits."
+                                ))
+                            }
+                            is PinVerificationOutcome.Incorrect -> {
+                                call.respond(HttpStatusCode.Unauthorized, PinVerificationResponse(
+                                    verified = false,
+                                    message = "Invalid PIN. ${outcome.attemptsRemaining} attempts remaining."
+                                ))
+                                call.application.environment.log.warn("PIN verification failed")
+                            }
+                            is PinVerificationOutcome.LockedOut -> {
+                                call.response.header(HttpHeaders.RetryAfter, outcome.retryAfterSeconds.toString())
+                                call.respond(HttpStatusCode.TooManyRequests, PinVerificationResponse(
+                                    verified = false,
+                                    message = "Too many incorrect PINs. PIN entry is locked for ${outcome.retryAfterSeconds} seconds."
+                                ))
+                            }
+                            PinVerificationOutcome.NotSet -> {
+                                call.respond(HttpStatusCode.Conflict, PinVerificationResponse(
+                                    verified = false,
+                                    message = "No PIN has been set up yet"
+                                ))
+                            }
+                            PinVerificationOutcome.Unavailable -> {
+                                call.respond(HttpStatusCode.ServiceUnavailable, PinVerificationResponse(
+                                    verified = false,
+                                    message = "PIN verification is temporarily unavailable"
+                                ))
+                            }
+                        }
+                    } catch (e: Exception) {
+                        call.application.environment.log.error("PIN verification error", e)
+                        call.respond(HttpStatusCode.InternalServerError, PinVerificationResponse(
                             verified = false,
-                            message = "Invalid PIN"
+                            message = "PIN verification failed"
                         ))
-                        call.application.environment.log.warn("PIN verification failed")
                     }
-                } catch (e: Exception) {
-                    call.application.environment.log.error("PIN verification error", e)
-                    call.respond(HttpStatusCode.InternalServerError, PinVerificationResponse(
-                        verified = false,
-                        message = "PIN verification failed"
-                    ))
                 }
             }
 
             // Generic signup (keeping for backward compatibility)
             post("/signup") {
+                val attemptKeys = call.authAttemptKeys(authRateLimiter, "signup")
+                if (call.rejectIfThrottled(authRateLimiter, attemptKeys)) return@post
                 try {
                     val rawRequest = call.receive<SignupRequest>()
                     call.application.environment.log.info("Received signup request: email=${rawRequest.email}, firstName=${rawRequest.firstName}, lastName=${rawRequest.lastName}")
                     
                     // Validate request
-                    val validationResult = AuthValidation.validateSignupRequest(rawRequest)
-                    if (!validationResult.isValid) {
-                        call.application.environment.log.warn("Signup validation failed: ${validationResult.errors}")
-                        call.respond(HttpStatusCode.BadRequest, MessageResponse("Validation failed: ${validationResult.errors.joinToString(", ")}"))
-                        return@post
-                    }
+                    AuthValidation.validateSignupRequest(rawRequest).throwIfInvalid()
                     
                     // Sanitize request
                     val sanitizedRequest = AuthValidation.sanitizeSignupRequest(rawRequest)
@@ -197,7 +268,7 @@ fun Route.authRoutes() {
                     call.respond(HttpStatusCode.Created, response)
                 } catch (e: AuthValidationException) {
                     call.application.environment.log.warn("Signup validation exception: ${e.message}", e)
-                    call.respond(HttpStatusCode.BadRequest, MessageResponse(e.message ?: "Validation failed"))
+                    call.respondValidationFailed(e)
                 } catch (e: IllegalArgumentException) {
                     call.application.environment.log.warn("Signup illegal argument: ${e.message}", e)
                     call.respond(HttpStatusCode.BadRequest, MessageResponse(e.message ?: "Invalid input"))
@@ -212,8 +283,11 @@ fun Route.authRoutes() {
 
             // Login
             post("/login") {
+                var attemptKeys = call.authAttemptKeys(authRateLimiter, "login")
                 try {
                     val rawRequest = call.receive<LoginRequest>()
+                    attemptKeys = call.authAttemptKeys(authRateLimiter, "login", rawRequest.email)
+                    if (call.rejectIfThrottled(authRateLimiter, attemptKeys)) return@post
                     
                     // Validate request
                     AuthValidation.validateLoginRequest(rawRequest).throwIfInvalid()
@@ -222,9 +296,10 @@ fun Route.authRoutes() {
                     val sanitizedRequest = AuthValidation.sanitizeLoginRequest(rawRequest)
                     
                     val response = authService.login(sanitizedRequest)
+                    authRateLimiter.release(attemptKeys)
                     call.respond(HttpStatusCode.OK, response)
                 } catch (e: AuthValidationException) {
-                    call.respond(HttpStatusCode.BadRequest, MessageResponse(e.message ?: "Validation failed"))
+                    call.respondValidationFailed(e)
                 } catch (e: SecurityException) {
                     call.respond(HttpStatusCode.Unauthorized, MessageResponse("Invalid credentials"))
                 } catch (e: Exception) {
@@ -247,7 +322,7 @@ fun Route.authRoutes() {
                     val response = authService.oauthLogin(sanitizedRequest)
                     call.respond(HttpStatusCode.OK, response)
                 } catch (e: AuthValidationException) {
-                    call.respond(HttpStatusCode.BadRequest, MessageResponse(e.message ?: "Validation failed"))
+                    call.respondValidationFailed(e)
                 } catch (e: SecurityException) {
                     call.respond(HttpStatusCode.Unauthorized, MessageResponse("OAuth authentication failed"))
                 } catch (e: Exception) {
@@ -264,7 +339,7 @@ fun Route.authRoutes() {
                     // Validate request
                     AuthValidation.validatePasswordResetRequest(rawRequest).throwIfInvalid()
                     
-                    val success = authService.requestPasswordReset(rawRequest.email.trim().lowercase())
+                    val success = authService.requestPasswordReset(rawRequest.email)
                     if (success) {
                         call.respond(HttpStatusCode.OK, MessageResponse("Password reset email sent"))
                     } else {
@@ -272,7 +347,7 @@ fun Route.authRoutes() {
                         call.respond(HttpStatusCode.OK, MessageResponse("Password reset email sent"))
                     }
                 } catch (e: AuthValidationException) {
-                    call.respond(HttpStatusCode.BadRequest, MessageResponse(e.message ?: "Validation failed"))
+                    call.respondValidationFailed(e)
                 } catch (e: Exception) {
                     call.application.environment.log.error("Password reset request error", e)
                     call.respond(HttpStatusCode.InternalServerError, MessageResponse("Password reset request failed"))
@@ -294,7 +369,7 @@ fun Route.authRoutes() {
                         call.respond(HttpStatusCode.BadRequest, MessageResponse("Invalid or expired token"))
                     }
                 } catch (e: AuthValidationException) {
-                    call.respond(HttpStatusCode.BadRequest, MessageResponse(e.message ?: "Validation failed"))
+                    call.respondValidationFailed(e)
                 } catch (e: IllegalArgumentException) {
                     call.respond(HttpStatusCode.BadRequest, MessageResponse(e.message ?: "Invalid password"))
                 } catch (e: Exception) {
@@ -304,6 +379,19 @@ fun Route.authRoutes() {
             }
         }
 
+        // Live password strength meter; nothing is stored. Uses the general API limit
+        // since the client calls this as the user types.
+        rateLimit(RateLimitName("api")) {
+            post("/password-strength") {
+                try {
+                    val request = call.receive<PasswordStrengthRequest>()
+                    call.respond(HttpStatusCode.OK, ValidationUtils.scorePassword(request.password))
+                } catch (e: Exception) {
+                    call.respond(HttpStatusCode.BadRequest, MessageResponse("Invalid request"))
+                }
+            }
+        }
+
         // Refresh token endpoint for Flutter compatibility
         route("/session") {
             post("/refresh") {
@@ -342,13 +430,38 @@ fun Route.authRoutes() {
             }
         }
 
+        // Link from the verification email; the token is the proof, so no session is needed
+        rateLimit(RateLimitName("auth")) {
+            get("/verify-email") {
+                val token = call.request.queryParameters["token"]
+                if (token.isNullOrBlank()) {
+                    call.respond(HttpStatusCode.BadRequest, MessageResponse("Verification token is required"))
+                    return@get
+                }
+                try {
+                    val result = authService.confirmEmailVerification(token)
+                    val status = when (result) {
+                        EmailVerificationResult.VERIFIED -> HttpStatusCode.OK
+                        EmailVerificationResult.ALREADY_USED -> HttpStatusCode.Conflict
+                        EmailVerificationResult.EXPIRED -> HttpStatusCode.Gone
+                        EmailVerificationResult.INVALID -> HttpStatusCode.BadRequest
+                    }
+                    call.respond(status, MessageResponse(result.message))
+                } catch (e: Exception) {
+                    call.application.environment.log.error("Email verification error", e)
+                    call.respond(HttpStatusCode.InternalServerError, MessageResponse("Email verification failed"))
+                }
+            }
+        }
+
         // Protected routes (require authentication)
         authenticate("auth-jwt") {
             
             // Logout
             post("/logout") {
                 try {
-                    val token = call.principal<JWTPrincipal>()?.payload?.getClaim("sessionToken")?.asString()
+                    // The session is keyed by the access token itself, not a claim inside it
+                    val token = (call.request.parseAuthorizationHeader() as? HttpAuthHeader.Single)?.blob
                     if (token != null) {
                         val success = authService.logout(token)
                         call.respond(HttpStatusCode.OK, MessageResponse("Logged out successfully"))
@@ -361,25 +474,26 @@ fun Route.authRoutes() {
                 }
             }
 
-            // Verify email
-            post("/verify-email") {
+            // Email a new verification link to the signed-in user
+            post("/send-verification") {
                 try {
                     val principal = call.principal<JWTPrincipal>()
                     val userId = principal?.payload?.getClaim("userId")?.asString()
                     
                     if (userId != null) {
-                        val success = authService.verifyEmail(UUID.fromString(userId))
-                        if (success) {
-                            call.respond(HttpStatusCode.OK, MessageResponse("Email verified successfully"))
+                        if (authService.sendVerificationEmail(UUID.fromString(userId))) {
+                            call.respond(HttpStatusCode.OK, MessageResponse("Verification email sent"))
                         } else {
-                            call.respond(HttpStatusCode.BadRequest, MessageResponse("Email verification failed"))
+                            call.respond(HttpStatusCode.InternalServerError, MessageResponse("Verification email could not be sent"))
                         }
                     } else {
                         call.respond(HttpStatusCode.Unauthorized, MessageResponse("Invalid token"))
                     }
+                } catch (e: IllegalStateException) {
+                    call.respond(HttpStatusCode.Conflict, MessageResponse(e.message ?: "Email is already verified"))
                 } catch (e: Exception) {
-                    call.application.environment.log.error("Email verification error", e)
-                    call.respond(HttpStatusCode.InternalServerError, MessageResponse("Email verification failed"))
+                    call.application.environment.log.error("Send verification email error", e)
+                    call.respond(HttpStatusCode.InternalServerError, MessageResponse("Verification email could not be sent"))
                 }
             }
 
@@ -390,14 +504,13 @@ fun Route.authRoutes() {
                     val userId = principal?.payload?.getClaim("userId")?.asString()
                     
                     if (userId != null) {
-                        // Return user info from token or fetch from database
-                        val userInfo = mapOf(
-                            "id" to userId,
-                            "email" to principal.payload.getClaim("email").asString(),
-                            "role" to principal.payload.getClaim("role").asString(),
-                            "verified" to principal.payload.getClaim("verified").asBoolean()
-                        )
-                        call.respond(HttpStatusCode.OK, userInfo)
+                        // Read from the database so role and family changes don't wait for a new token
+                        val userInfo = authService.getCurrentUser(UUID.fromString(userId))
+                        if (userInfo != null) {
+                            call.respond(HttpStatusCode.OK, userInfo)
+                        } else {
+                            call.respond(HttpStatusCode.NotFound, MessageResponse("User not found"))
+                        }
                     } else {
                         call.respond(HttpStatusCode.Unauthorized, MessageResponse("Invalid token"))
                     }
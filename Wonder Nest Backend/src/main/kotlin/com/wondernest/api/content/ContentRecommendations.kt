@@ -0,0 +1,68 @@
+package com.wondernest.api.content
+
+import com.wondernest.domain.repository.FamilyRepository
+import kotlinx.datetime.Clock
+import kotlinx.datetime.Instant
+import kotlinx.datetime.TimeZone
+import kotlinx.datetime.toLocalDateTime
+import kotlinx.datetime.yearsUntil
+import kotlinx.serialization.Serializable
+import java.util.UUID
+
+@Serializable
+data class ContentRecommendationsResponse(
+    val childId: String,
+    val childAge: Int,
+    val recommendations: List<ContentItem>,
+    val reason: String,
+    val generatedAt: String
+)
+
+/**
+ * Picks content for a child from their age on the server, so a client can't ask for
+ * content outside the child's band. Items rated above the child's age are never returned.
+ */
+class ContentRecommendationService(
+    private val familyRepository: FamilyRepository,
+    private val catalog: () -> List<ContentItem> = { generateMockContent(null, null) },
+    private val clock: () -> Instant = { Clock.System.now() }
+) {
+    companion object {
+        // How far below the child's age content is still considered engaging
+        const val AGE_BAND_YEARS = 3
+        const val MAX_RECOMMENDATIONS = 10
+    }
+
+    /**
+     * Null when the child doesn't exist
+     */
+    suspend fun recommendationsFor(childId: UUID): ContentRecommendationsResponse? {
+        val child = familyRepository.getChildProfile(childId) ?: return null
+        val now = clock()
+        val age = ageInYears(child.birthDate, now)
+
+        return ContentRecommendationsResponse(
+            childId = childId.toString(),
+            childAge = age,
+            recommendations = recommendationsForAge(age),
+            reason = "Based on age-appropriate content for age $age",
+            generatedAt = now.toString()
+        )
+    }
+
+    fun recommendationsForAge(age: Int): List<ContentItem> = appropriateFor(age, catalog())
+
+    /**
+     * Closest to the child's age first
+     */
+    fun appropriateFor(age: Int, items: List<ContentItem>): List<ContentItem> =
+        items.filter { it.ageRating in (age - AGE_BAND_YEARS)..age }
+            .sortedByDescending { it.ageRating }
+            .take(MAX_RECOMMENDATIONS)
+
+    private fun ageInYears(birthDate: Instant, now: Instant): Int {
+        val born = birthDate.toLocalDateTime(TimeZone.UTC).date
+        val today = now.toLocalDateTime(TimeZone.UTC).date
+        return born.yearsUntil(today).coerceAtLeast(0)
+    }
+}
@@ -6,7 +6,11 @@ import io.ktor.server.auth.*
 import io.ktor.server.auth.jwt.*
 import io.ktor.server.response.*
 import io.ktor.server.routing.*
+import com.wondernest.api.FamilyContextResolver
+import com.wondernest.api.requireChildAccess
 import kotlinx.serialization.Serializable
+import org.koin.ktor.ext.inject
+import java.util.UUID
 
 @Serializable
 data class MessageResponse(val message: String)
@@ -48,6 +52,9 @@ data class ContentResponse(
 )
 
 fun Route.contentRoutes() {
+    val familyContextResolver by inject<FamilyContextResolver>()
+    val recommendationService by inject<ContentRecommendationService>()
+
     authenticate("auth-jwt") {
         route("/content") {
             // Get content library with filtering (Flutter calls this endpoint)
@@ -96,37 +103,24 @@ fun Route.contentRoutes() {
                 call.respond(HttpStatusCode.OK, MessageResponse("Use /content instead of /content/library"))
             }
             
-            get("/recommendations/{childId}") {
-                try {
-                    val childId = call.parameters["childId"] ?: return@get call.respond(
-                        HttpStatusCode.BadRequest, MessageResponse("Child ID is required")
-                    )
+            // Age-filtered recommendations; the child's age always comes from their profile
+            get("/recommendations") {
+                val childId = call.request.queryParameters["child_id"]?.let {
+                    try { UUID.fromString(it) }
+                    catch (e: IllegalArgumentException) { null }
+                } ?: return@get call.respond(HttpStatusCode.BadRequest, MessageResponse("Valid child_id query parameter required"))
+
+                call.respondWithRecommendations(childId, familyContextResolver, recommendationService)
+            }
 
-                    val principal = call.principal<JWTPrincipal>()
-                    val familyId = principal?.payload?.getClaim("familyId")?.asString()
-                        ?: return@get call.respond(HttpStatusCode.BadRequest, MessageResponse("No family context in token"))
+            // Older clients pass the child in the path
+            get("/recommendations/{childId}") {
+                val childId = call.parameters["childId"]?.let {
+                    try { UUID.fromString(it) }
+                    catch (e: IllegalArgumentException) { null }
+                } ?: return@get call.respond(HttpStatusCode.BadRequest, MessageResponse("Child ID is required"))
 
-                    // TODO: PRODUCTION - Implement personalized recommendations based on:
-                    // - Child's age and interests
-                    // - Previous content engagement
-                    // - Educational goals
-                    // - Content preferences
-                    
-                    // For now, return mock recommendations
-                    val recommendations = generateMockRecommendations(childId)
-                    
-                    call.respond(HttpStatusCode.OK, mapOf(
-                        "childId" to childId,
-                        "recommendations" to recommendations,
-                        "reason" to "Based on age-appropriate content and interests",
-                        "generatedAt" to System.currentTimeMillis()
-                    ))
-                    
-                    call.application.environment.log.info("Generated content recommendations for child: $childId")
-                } catch (e: Exception) {
-                    call.application.environment.log.error("Error generating content recommendations", e)
-                    call.respond(HttpStatusCode.InternalServerError, MessageResponse("Failed to generate recommendations"))
-                }
+                call.respondWithRecommendations(childId, familyContextResolver, recommendationService)
             }
             
             post("/engagement") {
@@ -174,8 +168,27 @@ fun Route.contentRoutes() {
     }
 }
 
+private suspend fun ApplicationCall.respondWithRecommendations(
+    childId: UUID,
+    familyContextResolver: FamilyContextResolver,
+    recommendationService: ContentRecommendationService
+) {
+    try {
+        requireChildAccess(familyContextResolver, childId) ?: return
+
+        val response = recommendationService.recommendationsFor(childId)
+            ?: return respond(HttpStatusCode.NotFound, MessageResponse("Child not found"))
+
+        respond(HttpStatusCode.OK, response)
+        application.environment.log.info("Generated content recommendations for child: $childId")
+    } catch (e: Exception) {
+        application.environment.log.error("Error generating content recommendations", e)
+        respond(HttpStatusCode.InternalServerError, MessageResponse("Failed to generate recommendations"))
+    }
+}
+
 // TODO: PRODUCTION - Replace these mock functions with real database queries
-private fun generateMockContent(ageGroup: Int?, category: String?): List<ContentItem> {
+internal fun generateMockContent(ageGroup: Int?, category: String?): List<ContentItem> {
     val baseContent = listOf(
         ContentItem(
             id = "content_1",
@@ -227,11 +240,6 @@ private fun generateMockContent(ageGroup: Int?, category: String?): List<Content
     }
 }
 
-private fun generateMockRecommendations(childId: String): List<ContentItem> {
-    // TODO: PRODUCTION - Implement actual recommendation algorithm
-    return generateMockContent(null, null).take(5)
-}
-
 private fun getMockCategories(): List<ContentCategory> {
     return listOf(
         ContentCategory("educational", "Educational", "Learn while you play", "🎓", "#4CAF50", 3, 12),
@@ -1,13 +1,24 @@
 package com.wondernest.api.coppa
 
+import com.wondernest.api.FamilyContextResolver
+import com.wondernest.api.requestBaseUrl
+import com.wondernest.api.requireChildAccess
+import com.wondernest.api.requireFamilyContext
+import com.wondernest.domain.model.ConsentVerificationStatus
+import com.wondernest.services.coppa.ChildDataDeletionService
+import com.wondernest.services.coppa.ChildDataExportResult
+import com.wondernest.services.coppa.ChildDataExportService
+import com.wondernest.services.coppa.ConsentService
+import com.wondernest.services.coppa.DataCollectionConsentChecker
 import io.ktor.http.*
 import io.ktor.server.application.*
 import io.ktor.server.auth.*
-import io.ktor.server.auth.jwt.*
 import io.ktor.server.request.*
 import io.ktor.server.response.*
 import io.ktor.server.routing.*
 import kotlinx.serialization.Serializable
+import org.koin.ktor.ext.inject
+import java.util.UUID
 
 @Serializable
 data class MessageResponse(val message: String, val warning: String? = null)
@@ -17,7 +28,7 @@ data class COPPAConsentRequest(
     val childId: String,
     val consentType: String, // "parental_notification", "parental_consent", "verifiable_consent"
     val permissions: Map<String, Boolean>, // specific permissions granted
-    val verificationMethod: String, // "credit_card", "digital_signature", "government_id", etc.
+    val verificationMethod: String, // "credit_card" or "phone"; email-only and checkbox consent are rejected
     val verificationData: Map<String, String>? = null
 )
 
@@ -51,26 +62,33 @@ data class COPPAConsentResponse(
  * DO NOT deploy to production without proper legal counsel and COPPA compliance review.
  */
 fun Route.coppaRoutes() {
+    val consentService by inject<ConsentService>()
+    val consentChecker by inject<DataCollectionConsentChecker>()
+    val childDataDeletionService by inject<ChildDataDeletionService>()
+    val childDataExportService by inject<ChildDataExportService>()
+    val familyContextResolver by inject<FamilyContextResolver>()
+    
     authenticate("auth-jwt") {
         route("/coppa") {
             
-            // Submit COPPA consent (Flutter app uses this endpoint)
+            // Consent and permission state of every child in the caller's family
+            get("/dashboard") {
+                try {
+                    val family = call.requireFamilyContext(familyContextResolver) ?: return@get
+                    call.respond(HttpStatusCode.OK, consentService.getDashboard(family.familyId))
+                } catch (e: Exception) {
+                    call.application.environment.log.error("Error building COPPA consent dashboard", e)
+                    call.respond(HttpStatusCode.InternalServerError, MessageResponse(
+                        message = "Failed to load consent dashboard"
+                    ))
+                }
+            }
+            
+            // Submit COPPA consent (Flutter app uses this endpoint).
+            // The parent is verified with the requested method; grants only take effect once verified.
             post("/consent") {
                 try {
-                    val principal = call.principal<JWTPrincipal>()
-                    val familyId = principal?.payload?.getClaim("familyId")?.asString()
-                        ?: return@post call.respond(HttpStatusCode.BadRequest, MessageResponse(
-                            message = "No family context in token"
-                        ))
-
                     val request = call.receive<COPPAConsentRequest>()
-                    
-                    // PRODUCTION WARNING - This is a mock implementation
-                    call.application.environment.log.warn(
-                        "COPPA CONSENT SUBMITTED - PRODUCTION WARNING: " +
-                        "This is a development mock. Real COPPA consent requires legal compliance review. " +
-                        "Child: ${request.childId}, Type: ${request.consentType}"
-                    )
 
                     // Basic validation
                     if (request.childId.isBlank() || request.consentType.isBlank()) {
@@ -79,89 +97,66 @@ fun Route.coppaRoutes() {
                         ))
                     }
 
-                    // TODO: PRODUCTION - Implement proper COPPA compliance:
-                    // 1. Verify parent identity using verifiable methods
-                    // 2. Store consent with proper audit trail
-                    // 3. Implement consent expiration and renewal
-                    // 4. Validate verification method meets COPPA standards
-                    // 5. Update child's data collection permissions
-                    // 6. Send confirmation to verified parent email
-                    // 7. Generate compliance documentation
+                    val childId = try {
+                        UUID.fromString(request.childId)
+                    } catch (e: IllegalArgumentException) {
+                        return@post call.respond(HttpStatusCode.BadRequest, MessageResponse(
+                            message = "Invalid child ID"
+                        ))
+                    }
+                    val family = call.requireChildAccess(familyContextResolver, childId) ?: return@post
+                    val submission = consentService.submitConsent(
+                        familyId = family.familyId,
+                        parentId = family.userId,
+                        childId = childId,
+                        permissions = request.permissions,
+                        verificationMethod = request.verificationMethod,
+                        verificationData = request.verificationData.orEmpty()
+                    )
+                    consentChecker.invalidate(childId)
+
+                    // TODO: PRODUCTION - Remaining COPPA work:
+                    // 1. Replace the stub verifiers with real payment and call-centre providers
+                    // 2. Add IP / user agent to the consent audit trail
+                    // 3. Send confirmation to verified parent email
+                    // 4. Generate compliance documentation
 
-                    val mockConsentResponse = COPPAConsentResponse(
-                        consentId = "consent_mock_${System.currentTimeMillis()}",
+                    val verified = submission.verificationStatus == ConsentVerificationStatus.VERIFIED
+                    val status = when (submission.verificationStatus) {
+                        ConsentVerificationStatus.VERIFIED -> HttpStatusCode.Created
+                        ConsentVerificationStatus.PENDING -> HttpStatusCode.Accepted
+                        ConsentVerificationStatus.FAILED -> HttpStatusCode.UnprocessableEntity
+                    }
+                    call.respond(status, COPPAConsentResponse(
+                        consentId = submission.records.first().id.toString(),
                         childId = request.childId,
                         consentType = request.consentType,
                         permissions = request.permissions,
-                        consentGranted = true, // Mock approval
-                        expiresAt = "2025-08-14T00:00:00Z", // Mock expiration
-                        verificationStatus = "PENDING_PRODUCTION_IMPLEMENTATION",
-                        complianceWarnings = listOf(
-                            "DEVELOPMENT MOCK - Not COPPA compliant",
-                            "Requires legal review before production use",
-                            "Verifiable parental consent not implemented",
-                            "Age verification not implemented",
-                            "Data collection audit trail not implemented"
-                        )
-                    )
-
-                    call.respond(HttpStatusCode.Created, mockConsentResponse)
+                        consentGranted = verified && submission.records.any { it.granted },
+                        expiresAt = submission.expiresAt?.toString(),
+                        verificationStatus = submission.verificationStatus.name,
+                        complianceWarnings = submission.complianceWarnings
+                    ))
+                } catch (e: IllegalArgumentException) {
+                    call.respond(HttpStatusCode.BadRequest, MessageResponse(
+                        message = e.message ?: "Invalid consent request"
+                    ))
                 } catch (e: Exception) {
                     call.application.environment.log.error("Error processing COPPA consent", e)
                     call.respond(HttpStatusCode.InternalServerError, MessageResponse(
-                        message = "Failed to process COPPA consent",
-                        warning = "COPPA compliance not implemented - development only"
+                        message = "Failed to process COPPA consent"
                     ))
                 }
             }
 
-            // Get COPPA consent status
-            get("/consent/{childId}") {
-                try {
-                    val childId = call.parameters["childId"]
-                        ?: return@get call.respond(HttpStatusCode.BadRequest, MessageResponse(
-                            message = "Child ID is required"
-                        ))
-
-                    val principal = call.principal<JWTPrincipal>()
-                    val familyId = principal?.payload?.getClaim("familyId")?.asString()
-                        ?: return@get call.respond(HttpStatusCode.BadRequest, MessageResponse(
-                            message = "No family context in token"
-                        ))
-
-                    // TODO: PRODUCTION - Query real COPPA consent status from database
-                    val mockStatus = mapOf(
-                        "childId" to childId,
-                        "consentStatus" to "DEVELOPMENT_MOCK",
-                        "consentRequired" to true,
-                        "verificationRequired" to true,
-                        "dataCollectionAllowed" to false,
-                        "warnings" to listOf(
-                            "COPPA compliance not implemented",
-                            "This is development data only",
-                            "Legal review required for production"
-                        ),
-                        "nextSteps" to listOf(
-                            "Implement verifiable parental consent system",
-                            "Add age verification mechanisms", 
-                            "Set up data collection limitations",
-                            "Create audit trail system",
-                            "Legal compliance review"
-                        )
-                    )
+            // Stored consent state for one child
+            get("/status/{childId}") {
+                call.respondWithConsentStatus(consentService, familyContextResolver)
+            }
 
-                    call.respond(HttpStatusCode.OK, mockStatus)
-                    call.application.environment.log.warn(
-                        "COPPA status requested for child: $childId - " +
-                        "PRODUCTION WARNING: Mock data returned, not COPPA compliant"
-                    )
-                } catch (e: Exception) {
-                    call.application.environment.log.error("Error retrieving COPPA consent status", e)
-                    call.respond(HttpStatusCode.InternalServerError, MessageResponse(
-                        message = "Failed to retrieve COPPA consent status",
-                        warning = "COPPA compliance not implemented"
-                    ))
-                }
+            // Older path for the same status, still used by the Flutter app
+            get("/consent/{childId}") {
+                call.respondWithConsentStatus(consentService, familyContextResolver)
             }
 
             // Update COPPA consent
@@ -218,6 +213,96 @@ fun Route.coppaRoutes() {
                 }
             }
 
+            // Delete everything stored about a child (COPPA right to deletion). Parents only.
+            delete("/child/{childId}/data") {
+                try {
+                    val childId = call.parameters["childId"]?.let { runCatching { UUID.fromString(it) }.getOrNull() }
+                        ?: return@delete call.respond(HttpStatusCode.BadRequest, MessageResponse(
+                            message = "Valid child ID is required"
+                        ))
+                    val family = call.requireChildAccess(familyContextResolver, childId) ?: return@delete
+                    if (!family.isParent()) {
+                        return@delete call.respond(HttpStatusCode.Forbidden, MessageResponse(
+                            message = "Only a parent or guardian can delete a child's data"
+                        ))
+                    }
+
+                    val summary = childDataDeletionService.deleteChildData(childId)
+                    consentChecker.invalidate(childId)
+                    call.application.environment.log.info("Child data deleted for $childId by parent ${family.userId}")
+                    call.respond(HttpStatusCode.OK, summary)
+                } catch (e: Exception) {
+                    call.application.environment.log.error("Error deleting child data", e)
+                    call.respond(HttpStatusCode.InternalServerError, MessageResponse(
+                        message = "Failed to delete child data"
+                    ))
+                }
+            }
+
+            // Everything stored about a child, for a parent to review (COPPA right to review).
+            // Large exports are built in the background; poll the job for a signed download URL.
+            get("/child/{childId}/export") {
+                try {
+                    val childId = call.parameters["childId"]?.let { runCatching { UUID.fromString(it) }.getOrNull() }
+                        ?: return@get call.respond(HttpStatusCode.BadRequest, MessageResponse(
+                            message = "Valid child ID is required"
+                        ))
+                    val family = call.requireChildAccess(familyContextResolver, childId) ?: return@get
+                    if (!family.isParent()) {
+                        return@get call.respond(HttpStatusCode.Forbidden, MessageResponse(
+                            message = "Only a parent or guardian can export a child's data"
+                        ))
+                    }
+
+                    when (val result = childDataExportService.requestExport(childId, family.userId)) {
+                        is ChildDataExportResult.Inline -> {
+                            call.response.header(
+                                HttpHeaders.ContentDisposition,
+                                ContentDisposition.Attachment
+                                    .withParameter(ContentDisposition.Parameters.FileName, "child-data-$childId.json")
+                                    .toString()
+                            )
+                            call.respond(HttpStatusCode.OK, result.export)
+                        }
+                        is ChildDataExportResult.Deferred -> call.respond(HttpStatusCode.Accepted, result.job)
+                        ChildDataExportResult.ChildNotFound -> call.respond(HttpStatusCode.NotFound, MessageResponse(
+                            message = "Child not found"
+                        ))
+                    }
+                } catch (e: Exception) {
+                    call.application.environment.log.error("Error exporting child data", e)
+                    call.respond(HttpStatusCode.InternalServerError, MessageResponse(
+                        message = "Failed to export child data"
+                    ))
+                }
+            }
+
+            get("/child/{childId}/export/{jobId}") {
+                try {
+                    val childId = call.parameters["childId"]?.let { runCatching { UUID.fromString(it) }.getOrNull() }
+                        ?: return@get call.respond(HttpStatusCode.BadRequest, MessageResponse(
+                            message = "Valid child ID is required"
+                        ))
+                    val family = call.requireChildAccess(familyContextResolver, childId) ?: return@get
+                    if (!family.isParent()) {
+                        return@get call.respond(HttpStatusCode.Forbidden, MessageResponse(
+                            message = "Only a parent or guardian can export a child's data"
+                        ))
+                    }
+
+                    val job = childDataExportService.getJob(childId, call.parameters["jobId"].orEmpty(), call.requestBaseUrl())
+                        ?: return@get call.respond(HttpStatusCode.NotFound, MessageResponse(
+                            message = "Export not found"
+                        ))
+                    call.respond(HttpStatusCode.OK, job)
+                } catch (e: Exception) {
+                    call.application.environment.log.error("Error retrieving child data export", e)
+                    call.respond(HttpStatusCode.InternalServerError, MessageResponse(
+                        message = "Failed to retrieve export"
+                    ))
+                }
+            }
+
             // Get COPPA compliance information
             get("/compliance-info") {
                 try {
@@ -260,4 +345,24 @@ fun Route.coppaRoutes() {
             }
         }
     }
-}
\ No newline at end of file
+}
+
+private suspend fun ApplicationCall.respondWithConsentStatus(
+    consentService: ConsentService,
+    familyContextResolver: FamilyContextResolver
+) {
+    try {
+        val childId = parameters["childId"]?.let { runCatching { UUID.fromString(it) }.getOrNull() }
+            ?: return respond(HttpStatusCode.BadRequest, MessageResponse(message = "Valid child ID is required"))
+        requireChildAccess(familyContextResolver, childId) ?: return
+
+        val status = consentService.getChildStatus(childId)
+            ?: return respond(HttpStatusCode.NotFound, MessageResponse(message = "Child not found"))
+        respond(HttpStatusCode.OK, status)
+    } catch (e: Exception) {
+        application.environment.log.error("Error retrieving COPPA consent status", e)
+        respond(HttpStatusCode.InternalServerError, MessageResponse(
+            message = "Failed to retrieve COPPA consent status"
+        ))
+    }
+}
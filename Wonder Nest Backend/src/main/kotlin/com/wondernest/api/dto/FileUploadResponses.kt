@@ -2,6 +2,7 @@ package com.wondernest.api.dto
 
 import kotlinx.serialization.Serializable
 import com.wondernest.domain.model.UploadedFileDto
+import com.wondernest.services.storage.FileReferenceSummary
 
 @Serializable
 data class FileUploadSuccessResponse(
@@ -15,6 +16,25 @@ data class FileListSuccessResponse(
     val data: List<UploadedFileDto>
 )
 
+/**
+ * Per-file outcome of a batch upload; exactly one of [data] and [error] is set
+ */
+@Serializable
+data class BatchFileResult(
+    val fileName: String,
+    val status: String,
+    val data: UploadedFileDto? = null,
+    val error: ErrorDetails? = null
+)
+
+@Serializable
+data class FileBatchUploadResponse(
+    val success: Boolean = true,
+    val succeeded: Int,
+    val failed: Int,
+    val data: List<BatchFileResult>
+)
+
 @Serializable
 data class FileErrorResponse(
     val success: Boolean = false,
@@ -33,10 +53,26 @@ data class FileDeleteSuccessResponse(
     val message: String
 )
 
+@Serializable
+data class FileVisibilityRequest(
+    val isPublic: Boolean
+)
+
+@Serializable
+data class FileVisibilityResponse(
+    val success: Boolean = true,
+    val isPublic: Boolean
+)
+
+/**
+ * [isUsed] is false only when nothing references the file, i.e. it is safe to delete
+ */
 @Serializable
 data class FileUsageResponse(
     val isUsed: Boolean,
-    val stories: List<StoryUsageInfo> = emptyList()
+    val referenceCount: Int = 0,
+    val stories: List<StoryUsageInfo> = emptyList(),
+    val otherReferences: List<ContentUsageInfo> = emptyList()
 )
 
 @Serializable
@@ -44,4 +80,19 @@ data class StoryUsageInfo(
     val id: String,
     val title: String,
     val pageCount: Int
+)
+
+@Serializable
+data class ContentUsageInfo(
+    val type: String,
+    val id: String
+)
+
+fun FileReferenceSummary.toUsageResponse() = FileUsageResponse(
+    isUsed = isUsed,
+    referenceCount = referenceCount,
+    stories = stories.map {
+        StoryUsageInfo(id = it.referenceId.toString(), title = it.title ?: "Untitled story", pageCount = it.pageCount ?: 0)
+    },
+    otherReferences = other.map { ContentUsageInfo(type = it.referenceType, id = it.referenceId.toString()) }
 )
\ No newline at end of file
@@ -1,5 +1,10 @@
 package com.wondernest.api.family
 
+import com.wondernest.api.FamilyContextResolver
+import com.wondernest.api.requireChildAccess
+import com.wondernest.services.family.ChildPseudonymResponse
+import com.wondernest.services.family.ChildPseudonymService
+import com.wondernest.services.family.FamilyDashboardService
 import com.wondernest.services.family.FamilyService
 import com.wondernest.services.family.CreateChildRequest
 import com.wondernest.services.family.UpdateChildRequest
@@ -41,6 +46,9 @@ data class ChildProfileResponse(
 
 fun Route.familyRoutes() {
     val familyService by inject<FamilyService>()
+    val familyContextResolver by inject<FamilyContextResolver>()
+    val childPseudonymService by inject<ChildPseudonymService>()
+    val familyDashboardService by inject<FamilyDashboardService>()
     
     authenticate("auth-jwt") {
         // Family profile endpoint (Flutter expects this path)
@@ -66,6 +74,25 @@ fun Route.familyRoutes() {
                 }
             }
 
+            // Children, their latest analytics and recommendations for the home screen in one call
+            get("/dashboard") {
+                try {
+                    val principal = call.principal<JWTPrincipal>()
+                    val familyId = principal?.payload?.getClaim("familyId")?.asString()
+                        ?: return@get call.respond(HttpStatusCode.BadRequest, ErrorResponse("auth_error", "No family context in token"))
+
+                    call.respond(HttpStatusCode.OK, ApiResponse(
+                        success = true,
+                        data = familyDashboardService.dashboardFor(UUID.fromString(familyId))
+                    ))
+                } catch (e: IllegalArgumentException) {
+                    call.respond(HttpStatusCode.BadRequest, ErrorResponse("invalid_family_id", e.message ?: "Invalid family ID"))
+                } catch (e: Exception) {
+                    call.application.environment.log.error("Error building family dashboard", e)
+                    call.respond(HttpStatusCode.InternalServerError, ErrorResponse("server_error", "Failed to build family dashboard"))
+                }
+            }
+
             // Children management endpoints
             route("/children") {
                 // Get all children for the family
@@ -75,7 +102,8 @@ fun Route.familyRoutes() {
                         val familyId = principal?.payload?.getClaim("familyId")?.asString()
                             ?: return@get call.respond(HttpStatusCode.BadRequest, ErrorResponse("auth_error", "No family context in token"))
 
-                        val children = familyService.getChildren(UUID.fromString(familyId))
+                        val includeArchived = call.request.queryParameters["includeArchived"]?.toBoolean() ?: false
+                        val children = familyService.getChildren(UUID.fromString(familyId), includeArchived)
                         call.respond(HttpStatusCode.OK, ChildrenResponse(
                             success = true,
                             data = children
@@ -180,6 +208,52 @@ fun Route.familyRoutes() {
                         }
                     }
 
+                    // Archive a child who no longer uses the app; their data is kept
+                    post("/archive") {
+                        try {
+                            val childId = call.parameters["childId"]?.let { UUID.fromString(it) }
+                                ?: return@post call.respond(HttpStatusCode.BadRequest, ErrorResponse("invalid_child_id", "Invalid child ID"))
+                            val familyId = call.principal<JWTPrincipal>()?.payload?.getClaim("familyId")?.asString()
+                                ?: return@post call.respond(HttpStatusCode.BadRequest, ErrorResponse("auth_error", "No family context in token"))
+
+                            val archived = familyService.archiveChild(UUID.fromString(familyId), childId)
+                                ?: return@post call.respond(HttpStatusCode.NotFound, ErrorResponse("child_not_found", "Child profile not found"))
+
+                            call.respond(HttpStatusCode.OK, ChildProfileResponse(
+                                success = true,
+                                data = archived
+                            ))
+                        } catch (e: IllegalArgumentException) {
+                            call.respond(HttpStatusCode.BadRequest, ErrorResponse("invalid_child_id", "Invalid child ID format"))
+                        } catch (e: Exception) {
+                            call.application.environment.log.error("Error archiving child profile", e)
+                            call.respond(HttpStatusCode.InternalServerError, ErrorResponse("server_error", "Failed to archive child profile"))
+                        }
+                    }
+
+                    // Restore an archived child
+                    post("/restore") {
+                        try {
+                            val childId = call.parameters["childId"]?.let { UUID.fromString(it) }
+                                ?: return@post call.respond(HttpStatusCode.BadRequest, ErrorResponse("invalid_child_id", "Invalid child ID"))
+                            val familyId = call.principal<JWTPrincipal>()?.payload?.getClaim("familyId")?.asString()
+                                ?: return@post call.respond(HttpStatusCode.BadRequest, ErrorResponse("auth_error", "No family context in token"))
+
+                            val restored = familyService.restoreChild(UUID.fromString(familyId), childId)
+                                ?: return@post call.respond(HttpStatusCode.NotFound, ErrorResponse("child_not_found", "Archived child profile not found"))
+
+                            call.respond(HttpStatusCode.OK, ChildProfileResponse(
+                                success = true,
+                                data = restored
+                            ))
+                        } catch (e: IllegalArgumentException) {
+                            call.respond(HttpStatusCode.BadRequest, ErrorResponse("invalid_child_id", "Invalid child ID format"))
+                        } catch (e: Exception) {
+                            call.application.environment.log.error("Error restoring child profile", e)
+                            call.respond(HttpStatusCode.InternalServerError, ErrorResponse("server_error", "Failed to restore child profile"))
+                        }
+                    }
+
                     // Select active child (for child session management)
                     post("/select") {
                         try {
@@ -212,6 +286,41 @@ fun Route.familyRoutes() {
                             call.respond(HttpStatusCode.InternalServerError, ErrorResponse("server_error", "Failed to select child"))
                         }
                     }
+
+                    // Current external (pseudonymous) ID used for this child outside the family
+                    get("/pseudonym") {
+                        try {
+                            val childId = call.parameters["childId"]?.let { UUID.fromString(it) }
+                                ?: return@get call.respond(HttpStatusCode.BadRequest, ErrorResponse("invalid_child_id", "Invalid child ID"))
+                            call.requireChildAccess(familyContextResolver, childId) ?: return@get
+
+                            call.respond(HttpStatusCode.OK, ChildPseudonymResponse(childPseudonymService.externalIdFor(childId)))
+                        } catch (e: IllegalArgumentException) {
+                            call.respond(HttpStatusCode.BadRequest, ErrorResponse("invalid_child_id", "Invalid child ID format"))
+                        } catch (e: Exception) {
+                            call.application.environment.log.error("Error retrieving child pseudonym", e)
+                            call.respond(HttpStatusCode.InternalServerError, ErrorResponse("server_error", "Failed to retrieve child pseudonym"))
+                        }
+                    }
+
+                    // Rotate the external ID; the old one stops resolving, data stays attached to the child
+                    post("/pseudonym/rotate") {
+                        try {
+                            val childId = call.parameters["childId"]?.let { UUID.fromString(it) }
+                                ?: return@post call.respond(HttpStatusCode.BadRequest, ErrorResponse("invalid_child_id", "Invalid child ID"))
+                            val family = call.requireChildAccess(familyContextResolver, childId) ?: return@post
+                            if (!family.isParent()) {
+                                return@post call.respond(HttpStatusCode.Forbidden, ErrorResponse("access_denied", "Only parents can rotate a child's ID"))
+                            }
+
+                            call.respond(HttpStatusCode.OK, childPseudonymService.rotate(childId))
+                        } catch (e: IllegalArgumentException) {
+                            call.respond(HttpStatusCode.BadRequest, ErrorResponse("invalid_child_id", "Invalid child ID format"))
+                        } catch (e: Exception) {
+                            call.application.environment.log.error("Error rotating child pseudonym", e)
+                            call.respond(HttpStatusCode.InternalServerError, ErrorResponse("server_error", "Failed to rotate child pseudonym"))
+                        }
+                    }
                 }
             }
         }
@@ -225,7 +334,8 @@ fun Route.familyRoutes() {
                     val familyId = principal?.payload?.getClaim("familyId")?.asString()
                         ?: return@get call.respond(HttpStatusCode.BadRequest, ErrorResponse("auth_error", "No family context in token"))
 
-                    val children = familyService.getChildren(UUID.fromString(familyId))
+                    val includeArchived = call.request.queryParameters["includeArchived"]?.toBoolean() ?: false
+                    val children = familyService.getChildren(UUID.fromString(familyId), includeArchived)
                     call.respond(HttpStatusCode.OK, ChildrenResponse(
                         success = true,
                         data = children
@@ -1,16 +1,23 @@
 package com.wondernest.api.games
 
+import com.wondernest.api.FamilyContextResolver
+import com.wondernest.api.requireChildAccess
+import com.wondernest.api.requireDataCollectionConsent
+import com.wondernest.api.requireFamilyContext
+import com.wondernest.services.coppa.DataCollectionConsentChecker
 import com.wondernest.services.games.*
 import com.wondernest.services.games.SaveGameDataRequest as ServiceSaveGameDataRequest
 import com.wondernest.services.games.UpdateGameDataRequest as ServiceUpdateGameDataRequest
 import io.ktor.http.*
 import io.ktor.server.application.*
 import io.ktor.server.auth.*
+import io.ktor.server.auth.jwt.*
 import io.ktor.server.request.*
 import io.ktor.server.response.*
 import io.ktor.server.routing.*
 import kotlinx.serialization.Serializable
 import kotlinx.serialization.json.JsonElement
+import org.koin.ktor.ext.inject
 import java.util.UUID
 
 /**
@@ -21,6 +28,9 @@ fun Route.enhancedGameRoutes() {
     val gameRegistryService = GameRegistryService()
     val childGameInstanceService = ChildGameInstanceService()
     val gameDataService = GameDataService()
+    val gameDataHistoryService = GameDataHistoryService(gameDataService)
+    val familyContextResolver by inject<FamilyContextResolver>()
+    val consentChecker by inject<DataCollectionConsentChecker>()
     
     route("/games") {
         authenticate("auth-jwt") {
@@ -156,6 +166,63 @@ fun Route.enhancedGameRoutes() {
                 }
             }
             
+            // =============================================================================
+            // PARENTAL GAME AVAILABILITY
+            // =============================================================================
+            
+            // List a child's game instances with their enabled state
+            get("/instances") {
+                val childId = call.request.queryParameters["child_id"]?.let {
+                    try { UUID.fromString(it) }
+                    catch (e: IllegalArgumentException) { null }
+                } ?: return@get call.respond(HttpStatusCode.BadRequest, "Valid child_id query parameter required")
+                
+                try {
+                    call.requireChildAccess(familyContextResolver, childId) ?: return@get
+                    
+                    val instances = childGameInstanceService.getInstancesForChild(childId)
+                    call.respond(InstancesListResponse(success = true, instances = instances))
+                } catch (e: Exception) {
+                    call.respond(HttpStatusCode.InternalServerError, "Failed to get instances: ${e.message}")
+                }
+            }
+            
+            // Enable or disable a game for a child
+            patch("/instances/{instanceId}") {
+                val instanceId = call.parameters["instanceId"]?.let {
+                    try { UUID.fromString(it) }
+                    catch (e: IllegalArgumentException) { null }
+                } ?: return@patch call.respond(HttpStatusCode.BadRequest, "Invalid instance ID format")
+                
+                val family = call.requireFamilyContext(familyContextResolver) ?: return@patch
+                
+                val request = try {
+                    call.receive<UpdateInstanceEnabledRequest>()
+                } catch (e: Exception) {
+                    return@patch call.respond(HttpStatusCode.BadRequest, "Invalid request body: ${e.message}")
+                }
+                
+                try {
+                    val instance = childGameInstanceService.getInstance(instanceId)
+                        ?: return@patch call.respond(HttpStatusCode.NotFound, "Game instance not found")
+                    
+                    // Report instances outside the family as missing rather than forbidden
+                    if (!family.ownsChild(UUID.fromString(instance.childId))) {
+                        return@patch call.respond(HttpStatusCode.NotFound, "Game instance not found")
+                    }
+                    
+                    childGameInstanceService.setInstanceEnabled(instanceId, request.isEnabled)
+                    val updated = childGameInstanceService.getInstance(instanceId) ?: instance.copy(isEnabled = request.isEnabled)
+                    call.respond(CreateInstanceResponse(
+                        success = true,
+                        message = if (request.isEnabled) "Game enabled" else "Game disabled",
+                        instance = updated
+                    ))
+                } catch (e: Exception) {
+                    call.respond(HttpStatusCode.InternalServerError, "Failed to update instance: ${e.message}")
+                }
+            }
+            
             // =============================================================================
             // GAME DATA ENDPOINTS (NEW PROPER ARCHITECTURE)
             // =============================================================================
@@ -174,23 +241,31 @@ fun Route.enhancedGameRoutes() {
                 }
                 
                 try {
+                    if (!call.requireDataCollectionConsent(consentChecker, childId)) return@put
+                    
                     // Use saveGameData which creates instance if needed, then updates
                     val result = gameDataService.saveGameData(
                         childId = childId,
                         gameKey = request.gameKey,
                         dataKey = request.dataKey,
-                        dataValue = request.dataValue
+                        dataValue = request.dataValue,
+                        expectedVersion = request.expectedVersion
                     )
                     
-                    if (result.success) {
-                        call.respond(HttpStatusCode.OK, EnhancedGameDataResponse(
-                            success = true,
+                    if (result.success || result.conflict) {
+                        // A conflict carries the server's current copy so the client can merge
+                        call.respond(if (result.success) HttpStatusCode.OK else HttpStatusCode.Conflict, EnhancedGameDataResponse(
+                            success = result.success,
                             message = result.message,
                             childId = childId.toString(),
                             gameKey = request.gameKey,
                             dataKey = request.dataKey,
                             data = result.data
                         ))
+                    } else if (result.gameDisabled) {
+                        call.respond(HttpStatusCode.Forbidden, result.message)
+                    } else if (result.heldForReview) {
+                        call.respond(HttpStatusCode.Accepted, result.message)
                     } else {
                         call.respond(HttpStatusCode.BadRequest, result.message)
                     }
@@ -304,6 +379,74 @@ fun Route.enhancedGameRoutes() {
                 }
             }
             
+            // =============================================================================
+            // GAME DATA HISTORY
+            // =============================================================================
+            
+            // Last N saved versions of one data key, newest first
+            get("/{gameKey}/data/{dataKey}/history") {
+                val gameKey = call.parameters["gameKey"] ?: return@get call.respond(HttpStatusCode.BadRequest, "Game key required")
+                val dataKey = call.parameters["dataKey"] ?: return@get call.respond(HttpStatusCode.BadRequest, "Data key required")
+                val childId = call.request.queryParameters["child_id"]?.let {
+                    try { UUID.fromString(it) }
+                    catch (e: IllegalArgumentException) { null }
+                } ?: return@get call.respond(HttpStatusCode.BadRequest, "Valid child_id query parameter required")
+                val limit = call.request.queryParameters["limit"]?.toIntOrNull() ?: GameDataHistoryService.DEFAULT_LIMIT
+                
+                try {
+                    call.requireChildAccess(familyContextResolver, childId) ?: return@get
+                    
+                    val history = gameDataHistoryService.getHistory(childId, gameKey, dataKey, limit)
+                    call.respond(GameDataHistoryResponse(
+                        success = true,
+                        childId = childId.toString(),
+                        gameKey = gameKey,
+                        dataKey = dataKey,
+                        history = history
+                    ))
+                } catch (e: Exception) {
+                    call.respond(HttpStatusCode.InternalServerError, "Failed to load game data history: ${e.message}")
+                }
+            }
+            
+            // Save an earlier version as the new current version
+            post("/{gameKey}/data/{dataKey}/restore/{version}") {
+                val gameKey = call.parameters["gameKey"] ?: return@post call.respond(HttpStatusCode.BadRequest, "Game key required")
+                val dataKey = call.parameters["dataKey"] ?: return@post call.respond(HttpStatusCode.BadRequest, "Data key required")
+                val version = call.parameters["version"]?.toIntOrNull()
+                    ?: return@post call.respond(HttpStatusCode.BadRequest, "Invalid version")
+                val childId = call.request.queryParameters["child_id"]?.let {
+                    try { UUID.fromString(it) }
+                    catch (e: IllegalArgumentException) { null }
+                } ?: return@post call.respond(HttpStatusCode.BadRequest, "Valid child_id query parameter required")
+                
+                try {
+                    call.requireChildAccess(familyContextResolver, childId) ?: return@post
+                    if (!call.requireDataCollectionConsent(consentChecker, childId)) return@post
+                    
+                    val result = gameDataHistoryService.restore(childId, gameKey, dataKey, version)
+                    
+                    if (result.success) {
+                        call.respond(EnhancedGameDataResponse(
+                            success = true,
+                            message = "Restored version $version",
+                            childId = childId.toString(),
+                            gameKey = gameKey,
+                            dataKey = dataKey,
+                            data = result.data
+                        ))
+                    } else if (result.gameDisabled) {
+                        call.respond(HttpStatusCode.Forbidden, result.message)
+                    } else if (result.heldForReview) {
+                        call.respond(HttpStatusCode.Accepted, result.message)
+                    } else {
+                        call.respond(HttpStatusCode.NotFound, result.message)
+                    }
+                } catch (e: Exception) {
+                    call.respond(HttpStatusCode.InternalServerError, "Failed to restore game data: ${e.message}")
+                }
+            }
+            
             // Get child's active games
             get("/children/{childId}/active") {
                 val childId = call.parameters["childId"]?.let { 
@@ -373,6 +516,15 @@ data class EnhancedGameDataResponse(
     val data: GameDataInfo?
 )
 
+@Serializable
+data class GameDataHistoryResponse(
+    val success: Boolean,
+    val childId: String,
+    val gameKey: String,
+    val dataKey: String,
+    val history: List<GameDataHistoryEntry>
+)
+
 @Serializable
 data class EnhancedLoadGameDataResponse(
     val success: Boolean,
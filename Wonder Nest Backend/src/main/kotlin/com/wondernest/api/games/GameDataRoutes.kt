@@ -10,21 +10,45 @@ import kotlinx.serialization.Serializable
 import kotlinx.serialization.Contextual
 import kotlinx.serialization.json.Json
 import kotlinx.serialization.json.JsonElement
+import kotlinx.serialization.json.JsonObject
 import kotlinx.serialization.json.jsonObject
 import kotlinx.datetime.Clock
 import java.util.UUID
+import com.wondernest.api.FamilyContextResolver
+import com.wondernest.api.requireChildAccess
+import com.wondernest.api.requireDataCollectionConsent
 import com.wondernest.data.database.table.*
+import com.wondernest.services.coppa.DataCollectionConsentChecker
+import com.wondernest.services.games.ChildGameInstanceService
+import com.wondernest.services.games.GameDataBatchItem
+import com.wondernest.services.games.GameDataBatchService
+import com.wondernest.services.games.GameDataValidator
+import com.wondernest.services.moderation.ChildDataPiiGuard
+import com.wondernest.services.moderation.HeldDataTarget
+import com.wondernest.services.moderation.PiiScreening
+import com.wondernest.services.resilience.IdempotencyKeyReusedException
+import com.wondernest.services.resilience.IdempotencyService
+import kotlinx.serialization.encodeToString
 import org.jetbrains.exposed.sql.*
 import org.jetbrains.exposed.sql.SqlExpressionBuilder.eq
 import org.jetbrains.exposed.sql.statements.api.ExposedBlob
 import org.jetbrains.exposed.sql.transactions.transaction
 import org.jetbrains.exposed.sql.upsert
+import org.koin.ktor.ext.inject
 
 /**
  * Simple game data persistence routes using the SimpleGameData table
  * Perfect for games like sticker books that need to save project data
  */
 fun Route.gameDataRoutes() {
+    val childGameInstanceService = ChildGameInstanceService()
+    val idempotencyService by inject<IdempotencyService>()
+    val batchService = GameDataBatchService()
+    val dataValidator = GameDataValidator()
+    val piiGuard = ChildDataPiiGuard()
+    val consentChecker by inject<DataCollectionConsentChecker>()
+    val familyContextResolver by inject<FamilyContextResolver>()
+    
     route("/games") {
         authenticate("auth-jwt") {
             
@@ -32,19 +56,32 @@ fun Route.gameDataRoutes() {
             // SAVE GAME DATA
             // =============================================================================
             
-            // Save or update game data for a child
+            // Save or update game data for a child.
+            // Retries carrying the same Idempotency-Key get the first response back instead of writing again.
             put("/children/{childId}/data") {
                 val childId = call.parameters["childId"]?.let { 
                     try { UUID.fromString(it) } 
                     catch (e: IllegalArgumentException) { null }
                 } ?: return@put call.respond(HttpStatusCode.BadRequest, "Invalid child ID format")
                 
+                val idempotencyKey = call.request.headers[IdempotencyService.HEADER]
+                if (idempotencyKey != null &&
+                    (idempotencyKey.isBlank() || idempotencyKey.length > IdempotencyService.MAX_KEY_LENGTH)
+                ) {
+                    return@put call.respond(HttpStatusCode.BadRequest, "Invalid ${IdempotencyService.HEADER} header")
+                }
+                
                 val request = try {
                     call.receive<SaveGameDataRequest>()
                 } catch (e: Exception) {
                     return@put call.respond(HttpStatusCode.BadRequest, "Invalid request body: ${e.message}")
                 }
                 
+                val validationErrors = dataValidator.validate(request.gameType, JsonObject(request.dataValue))
+                if (validationErrors.isNotEmpty()) {
+                    return@put call.respond(HttpStatusCode.BadRequest, "Invalid game data: ${validationErrors.joinToString("; ")}")
+                }
+                
                 try {
                     // Validate child exists
                     val childExists = transaction {
@@ -55,32 +92,156 @@ fun Route.gameDataRoutes() {
                         return@put call.respond(HttpStatusCode.NotFound, "Child not found")
                     }
                     
-                    val now = Clock.System.now()
+                    if (!call.requireDataCollectionConsent(consentChecker, childId)) return@put
                     
-                    // Insert or update game data using SimpleGameData for now
-                    val result = transaction {
-                        SimpleGameData.upsert(
-                            keys = arrayOf(SimpleGameData.childId, SimpleGameData.gameType, SimpleGameData.dataKey)
-                        ) {
-                            it[SimpleGameData.childId] = childId
-                            it[SimpleGameData.gameType] = request.gameType
-                            it[SimpleGameData.dataKey] = request.dataKey
-                            it[SimpleGameData.dataValue] = request.dataValue
-                            it[SimpleGameData.updatedAt] = now
-                            it[SimpleGameData.createdAt] = now // Will be ignored for updates due to ON CONFLICT
+                    if (!childGameInstanceService.isGameEnabled(childId, request.gameType)) {
+                        return@put call.respond(HttpStatusCode.Forbidden, "Game '${request.gameType}' has been disabled for this child")
+                    }
+                    
+                    when (val screening = piiGuard.screen(childId, request.gameType, request.dataKey, JsonObject(request.dataValue), HeldDataTarget.SIMPLE)) {
+                        PiiScreening.Clean -> Unit
+                        is PiiScreening.Held -> return@put call.respond(HttpStatusCode.Accepted, screening.blockedMessage()!!)
+                        is PiiScreening.Rejected -> return@put call.respond(HttpStatusCode.UnprocessableEntity, screening.blockedMessage()!!)
+                    }
+                    
+                    val save: suspend () -> GameDataResponse = {
+                        val now = Clock.System.now()
+                        
+                        // Insert or update game data using SimpleGameData for now
+                        transaction {
+                            SimpleGameData.upsert(
+                                keys = arrayOf(SimpleGameData.childId, SimpleGameData.gameType, SimpleGameData.dataKey)
+                            ) {
+                                it[SimpleGameData.childId] = childId
+                                it[SimpleGameData.gameType] = request.gameType
+                                it[SimpleGameData.dataKey] = request.dataKey
+                                it[SimpleGameData.dataValue] = request.dataValue
+                                it[SimpleGameData.updatedAt] = now
+                                it[SimpleGameData.createdAt] = now // Will be ignored for updates due to ON CONFLICT
+                            }
                         }
+                        
+                        GameDataResponse(
+                            success = true,
+                            message = "Game data saved successfully",
+                            childId = childId.toString(),
+                            gameType = request.gameType,
+                            dataKey = request.dataKey
+                        )
+                    }
+                    
+                    if (idempotencyKey == null) {
+                        return@put call.respond(HttpStatusCode.OK, save())
+                    }
+                    
+                    val result = idempotencyService.execute(
+                        scope = "game-data:$childId",
+                        key = idempotencyKey,
+                        requestBody = Json.encodeToString(request)
+                    ) {
+                        HttpStatusCode.OK.value to Json.encodeToString(save())
+                    }
+                    call.respondText(result.body, ContentType.Application.Json, HttpStatusCode.fromValue(result.status))
+                    
+                } catch (e: IdempotencyKeyReusedException) {
+                    call.respond(HttpStatusCode.UnprocessableEntity, e.message ?: "Idempotency key reused")
+                } catch (e: Exception) {
+                    call.respond(HttpStatusCode.InternalServerError, "Failed to save game data: ${e.message}")
+                }
+            }
+            
+            // Save many keys for one game in a single transaction; one bad item rejects the whole batch
+            post("/{gameType}/data/batch") {
+                val gameType = call.parameters["gameType"] ?: return@post call.respond(HttpStatusCode.BadRequest, "Game type required")
+                
+                val request = try {
+                    call.receive<BatchSaveGameDataRequest>()
+                } catch (e: Exception) {
+                    return@post call.respond(HttpStatusCode.BadRequest, "Invalid request body: ${e.message}")
+                }
+                
+                val childId = try {
+                    UUID.fromString(request.childId)
+                } catch (e: IllegalArgumentException) {
+                    return@post call.respond(HttpStatusCode.BadRequest, "Invalid child ID format")
+                }
+                
+                try {
+                    call.requireChildAccess(familyContextResolver, childId) ?: return@post
+                    
+                    
+                    if (!call.requireDataCollectionConsent(consentChecker, childId)) return@post
+                    
+                    if (!childGameInstanceService.isGameEnabled(childId, gameType)) {
+                        return@post call.respond(HttpStatusCode.Forbidden, "Game '$gameType' has been disabled for this child")
                     }
                     
-                    call.respond(HttpStatusCode.OK, GameDataResponse(
+                    val result = batchService.saveBatch(
+                        childId,
+                        gameType,
+                        request.items.map { GameDataBatchItem(it.gameType, it.dataKey, it.dataValue) }
+                    )
+                    
+                    call.respond(
+                        when {
+                            !result.success -> HttpStatusCode.BadRequest
+                            result.heldKeys.isNotEmpty() -> HttpStatusCode.Accepted
+                            else -> HttpStatusCode.OK
+                        },
+                        BatchSaveGameDataResponse(
+                            success = result.success,
+                            savedCount = result.savedCount,
+                            failedKey = result.failedKey,
+                            error = result.error,
+                            heldKeys = result.heldKeys
+                        )
+                    )
+                    
+                } catch (e: Exception) {
+                    call.respond(HttpStatusCode.InternalServerError, "Failed to save game data: ${e.message}")
+                }
+            }
+            
+            // Load only the requested keys for one game
+            post("/{gameType}/data/batch/load") {
+                val gameType = call.parameters["gameType"] ?: return@post call.respond(HttpStatusCode.BadRequest, "Game type required")
+                
+                val request = try {
+                    call.receive<BatchLoadGameDataRequest>()
+                } catch (e: Exception) {
+                    return@post call.respond(HttpStatusCode.BadRequest, "Invalid request body: ${e.message}")
+                }
+                
+                val childId = try {
+                    UUID.fromString(request.childId)
+                } catch (e: IllegalArgumentException) {
+                    return@post call.respond(HttpStatusCode.BadRequest, "Invalid child ID format")
+                }
+                
+                try {
+                    call.requireChildAccess(familyContextResolver, childId) ?: return@post
+                    
+                    val gameDataList = batchService.loadBatch(childId, gameType, request.dataKeys).map { record ->
+                        GameDataItem(
+                            id = record.id.toString(),
+                            childId = record.childId.toString(),
+                            gameType = record.gameType,
+                            dataKey = record.dataKey,
+                            dataValue = record.dataValue,
+                            createdAt = record.createdAt.toString(),
+                            updatedAt = record.updatedAt.toString()
+                        )
+                    }
+                    
+                    call.respond(LoadGameDataResponse(
                         success = true,
-                        message = "Game data saved successfully",
-                        childId = childId.toString(),
-                        gameType = request.gameType,
-                        dataKey = request.dataKey
+                        gameData = gameDataList
                     ))
                     
+                } catch (e: IllegalArgumentException) {
+                    call.respond(HttpStatusCode.BadRequest, e.message ?: "Invalid batch")
                 } catch (e: Exception) {
-                    call.respond(HttpStatusCode.InternalServerError, "Failed to save game data: ${e.message}")
+                    call.respond(HttpStatusCode.InternalServerError, "Failed to load game data: ${e.message}")
                 }
             }
             
@@ -258,6 +419,27 @@ data class SaveGameDataRequest(
     @Contextual val dataValue: Map<String, JsonElement>
 )
 
+@Serializable
+data class BatchSaveGameDataRequest(
+    val childId: String,
+    val items: List<SaveGameDataRequest>
+)
+
+@Serializable
+data class BatchSaveGameDataResponse(
+    val success: Boolean,
+    val savedCount: Int,
+    val failedKey: String? = null,
+    val error: String? = null,
+    val heldKeys: List<String> = emptyList() // held for PII review instead of being saved
+)
+
+@Serializable
+data class BatchLoadGameDataRequest(
+    val childId: String,
+    val dataKeys: List<String>
+)
+
 @Serializable
 data class GameDataResponse(
     val success: Boolean,
@@ -2,6 +2,9 @@ package com.wondernest.api.health
 
 import com.wondernest.data.cache.RedisCache
 import com.wondernest.data.database.DatabaseFactory
+import com.wondernest.data.database.MigrationService
+import com.wondernest.services.resilience.RedisGuard
+import com.wondernest.services.storage.StorageProvider
 import io.ktor.http.*
 import io.ktor.server.application.*
 import io.ktor.server.response.*
@@ -29,9 +32,19 @@ data class ServiceHealth(
     val responseTime: Long? = null
 )
 
+@Serializable
+data class MigrationHealth(
+    val status: String,
+    val appliedVersion: String?,
+    val expectedMinimumVersion: String,
+    val message: String
+)
+
 fun Route.healthRoutes() {
     val databaseFactory by inject<DatabaseFactory>()
     val redisCache by inject<RedisCache>()
+    val redisGuard by inject<RedisGuard>()
+    val storageProvider by inject<StorageProvider>()
 
     // Basic health check - minimal response for load balancers
     get("/health") {
@@ -79,7 +92,11 @@ fun Route.healthRoutes() {
 
             services["redis"] = ServiceHealth(
                 status = if (redisHealthy) "UP" else "DOWN",
-                message = if (redisHealthy) "Connected" else "Connection failed",
+                message = when {
+                    redisGuard.isDegraded() -> "Degraded mode active (circuit open)"
+                    redisHealthy -> "Connected"
+                    else -> "Connection failed"
+                },
                 responseTime = redisResponseTime
             )
 
@@ -115,33 +132,32 @@ fun Route.healthRoutes() {
     }
 
     // Readiness check - indicates the app is ready to receive traffic
-    get("/health/ready") {
-        try {
-            val dbHealthy = databaseFactory.isHealthy()
-            val redisHealthy = try {
-                redisCache.exists("readiness-check")
-                true
-            } catch (e: Exception) {
-                false
-            }
+    readinessRoute(ReadinessProbe.forDependencies(databaseFactory, storageProvider))
 
-            if (dbHealthy && redisHealthy) {
-                call.respond(HttpStatusCode.OK, mapOf("status" to "READY"))
-            } else {
-                call.respond(
-                    HttpStatusCode.ServiceUnavailable,
-                    mapOf(
-                        "status" to "NOT_READY",
-                        "database" to if (dbHealthy) "UP" else "DOWN",
-                        "redis" to if (redisHealthy) "UP" else "DOWN"
-                    )
+    // Migration check - flags deployments where the schema is behind the code
+    get("/health/migrations") {
+        try {
+            val check = databaseFactory.checkSchemaVersion()
+            val statusCode = if (check.healthy) HttpStatusCode.OK else HttpStatusCode.ServiceUnavailable
+            call.respond(
+                statusCode,
+                MigrationHealth(
+                    status = if (check.healthy) "UP" else "DOWN",
+                    appliedVersion = check.appliedVersion,
+                    expectedMinimumVersion = check.expectedMinimumVersion,
+                    message = check.message
                 )
-            }
+            )
         } catch (e: Exception) {
-            logger.error("Readiness check failed", e)
+            logger.error("Migration status check failed", e)
             call.respond(
-                HttpStatusCode.InternalServerError,
-                mapOf("status" to "ERROR", "message" to e.message)
+                HttpStatusCode.ServiceUnavailable,
+                MigrationHealth(
+                    status = "DOWN",
+                    appliedVersion = null,
+                    expectedMinimumVersion = MigrationService.EXPECTED_MIN_SCHEMA_VERSION,
+                    message = "Unable to read migration status: ${e.message}"
+                )
             )
         }
     }
@@ -178,4 +194,15 @@ fun Route.healthRoutes() {
             )
         }
     }
-}
\ No newline at end of file
+}
+
+/**
+ * 200 only when every dependency answers; 503 with per-dependency status otherwise
+ */
+fun Route.readinessRoute(probe: ReadinessProbe) {
+    get("/health/ready") {
+        val readiness = probe.check()
+        val statusCode = if (readiness.status == "READY") HttpStatusCode.OK else HttpStatusCode.ServiceUnavailable
+        call.respond(statusCode, readiness)
+    }
+}
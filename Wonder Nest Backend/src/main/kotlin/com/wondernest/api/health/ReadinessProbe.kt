@@ -0,0 +1,69 @@
+package com.wondernest.api.health
+
+import com.wondernest.data.database.DatabaseFactory
+import com.wondernest.services.resilience.RedisConnections
+import com.wondernest.services.storage.StorageProvider
+import kotlinx.coroutines.Dispatchers
+import kotlinx.coroutines.asExecutor
+import kotlinx.coroutines.future.await
+import kotlinx.coroutines.withTimeoutOrNull
+import kotlinx.serialization.Serializable
+import org.slf4j.LoggerFactory
+import java.util.concurrent.CompletableFuture
+
+private val logger = LoggerFactory.getLogger("ReadinessProbe")
+
+@Serializable
+data class ReadinessStatus(
+    val status: String,
+    val services: Map<String, ServiceHealth>
+)
+
+/**
+ * Runs each dependency check with its own timeout so one hung dependency can't hang the probe.
+ * A check passes when it returns true; false, an exception or a timeout marks it DOWN.
+ */
+class ReadinessProbe(
+    private val checks: Map<String, suspend () -> Boolean>,
+    private val timeoutMillis: Long = DEFAULT_TIMEOUT_MILLIS
+) {
+    suspend fun check(): ReadinessStatus {
+        val services = checks.mapValues { (name, check) -> run(name, check) }
+        val ready = services.values.all { it.status == "UP" }
+        return ReadinessStatus(if (ready) "READY" else "NOT_READY", services)
+    }
+
+    private suspend fun run(name: String, check: suspend () -> Boolean): ServiceHealth {
+        val start = System.currentTimeMillis()
+        val result = try {
+            withTimeoutOrNull(timeoutMillis) { if (check()) "Connected" else "Check failed" }
+                ?: "Timed out after ${timeoutMillis}ms"
+        } catch (e: Exception) {
+            logger.warn("Readiness check for $name failed", e)
+            "Connection failed: ${e.message}"
+        }
+        return ServiceHealth(
+            status = if (result == "Connected") "UP" else "DOWN",
+            message = result,
+            responseTime = System.currentTimeMillis() - start
+        )
+    }
+
+    companion object {
+        const val DEFAULT_TIMEOUT_MILLIS = 2_000L
+
+        /**
+         * SELECT 1 against the pool, PING against Redis and the storage provider's own health check
+         */
+        fun forDependencies(databaseFactory: DatabaseFactory, storageProvider: StorageProvider): ReadinessProbe {
+            val database: suspend () -> Boolean = { blocking { databaseFactory.isHealthy() } }
+            val redis: suspend () -> Boolean = { blocking { RedisConnections.shared }.async().ping().await() == "PONG" }
+            val storage: suspend () -> Boolean = { storageProvider.healthCheck() }
+            return ReadinessProbe(mapOf("database" to database, "redis" to redis, "storage" to storage))
+        }
+
+        // JDBC and the first Redis connect block; awaiting a future lets the timeout give up on them
+        private suspend fun <T> blocking(block: () -> T): T =
+            CompletableFuture.supplyAsync(block, Dispatchers.IO.asExecutor()).await()
+    }
+}
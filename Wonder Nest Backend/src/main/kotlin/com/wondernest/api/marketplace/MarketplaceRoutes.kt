@@ -1,6 +1,8 @@
 package com.wondernest.api.marketplace
 
+import com.wondernest.api.FamilyContextResolver
 import com.wondernest.api.extractUser
+import com.wondernest.api.requireFamilyContext
 import com.wondernest.domain.repository.FamilyRepository
 import com.wondernest.services.marketplace.MarketplaceService
 import com.wondernest.services.marketplace.CreatorService
@@ -14,6 +16,14 @@ import com.wondernest.services.marketplace.CreatorProfileUpdate
 import com.wondernest.services.marketplace.TimeRange
 import com.wondernest.services.marketplace.SearchFacets
 import com.wondernest.services.marketplace.ContentCategory
+import com.wondernest.services.marketplace.PublishingLimitExceededException
+import com.wondernest.services.marketplace.InvalidAgeRangeException
+import com.wondernest.services.marketplace.LocalizationIncompleteException
+import com.wondernest.services.marketplace.LocalizedContent
+import com.wondernest.services.marketplace.ContentPatchRequest
+import com.wondernest.services.marketplace.ContentVersionConflictException
+import com.wondernest.services.marketplace.toResponse
+import com.wondernest.services.security.CreatorTwoFactorService
 import io.ktor.http.*
 import io.ktor.server.application.*
 import io.ktor.server.auth.*
@@ -25,6 +35,8 @@ import kotlinx.serialization.Serializable
 import mu.KotlinLogging
 import org.koin.ktor.ext.inject
 import java.math.BigDecimal
+import java.time.Instant
+import java.time.format.DateTimeParseException
 import java.util.*
 
 private val logger = KotlinLogging.logger {}
@@ -33,6 +45,8 @@ fun Route.marketplaceRoutes() {
     val marketplaceService by inject<MarketplaceService>()
     val creatorService by inject<CreatorService>()
     val familyRepository by inject<FamilyRepository>()
+    val familyContextResolver by inject<FamilyContextResolver>()
+    val creatorTwoFactor by inject<CreatorTwoFactorService>()
     
     route("/api/v2/marketplace") {
         
@@ -119,6 +133,56 @@ fun Route.marketplaceRoutes() {
             }
         }
         
+        // Get version history with changelogs for a pack
+        get("/packs/{packId}/versions") {
+            try {
+                val packId = call.parameters["packId"]?.let { UUID.fromString(it) }
+                    ?: return@get call.respond(HttpStatusCode.BadRequest,
+                        ErrorResponse("Invalid pack ID"))
+
+                val history = creatorService.getVersionHistory(packId)
+
+                if (history != null) {
+                    call.respond(HttpStatusCode.OK, history)
+                } else {
+                    call.respond(HttpStatusCode.NotFound,
+                        ErrorResponse("Pack not found"))
+                }
+
+            } catch (e: IllegalArgumentException) {
+                call.respond(HttpStatusCode.BadRequest, ErrorResponse("Invalid pack ID"))
+            } catch (e: Exception) {
+                logger.error(e) { "Error getting pack version history" }
+                call.respond(HttpStatusCode.InternalServerError,
+                    ErrorResponse("Failed to get version history"))
+            }
+        }
+        
+        // Public creator profile shown alongside their packs
+        get("/creators/{creatorId}/profile") {
+            try {
+                val creatorId = call.parameters["creatorId"]?.let { UUID.fromString(it) }
+                    ?: return@get call.respond(HttpStatusCode.BadRequest,
+                        ErrorResponse("Invalid creator ID"))
+
+                val profile = creatorService.getPublicProfile(creatorId)
+
+                if (profile != null) {
+                    call.respond(HttpStatusCode.OK, profile)
+                } else {
+                    call.respond(HttpStatusCode.NotFound,
+                        ErrorResponse("Creator not found"))
+                }
+
+            } catch (e: IllegalArgumentException) {
+                call.respond(HttpStatusCode.BadRequest, ErrorResponse("Invalid creator ID"))
+            } catch (e: Exception) {
+                logger.error(e) { "Error getting creator profile" }
+                call.respond(HttpStatusCode.InternalServerError,
+                    ErrorResponse("Failed to get creator profile"))
+            }
+        }
+        
         // Authenticated endpoints
         authenticate("auth-jwt") {
             
@@ -204,6 +268,28 @@ fun Route.marketplaceRoutes() {
                 }
             }
             
+            // Report an item as inappropriate
+            post("/items/{itemId}/report") {
+                try {
+                    val itemId = call.parameters["itemId"]?.let { UUID.fromString(it) }
+                        ?: return@post call.respond(HttpStatusCode.BadRequest,
+                            ErrorResponse("Invalid item ID"))
+                    val request = call.receive<ReportItemRequest>()
+
+                    val family = call.requireFamilyContext(familyContextResolver) ?: return@post
+
+                    marketplaceService.reportItem(family.familyId, itemId, request.reason)
+                    call.respond(HttpStatusCode.Accepted, mapOf("message" to "Report received"))
+
+                } catch (e: IllegalArgumentException) {
+                    call.respond(HttpStatusCode.BadRequest, ErrorResponse(e.message ?: "Invalid request"))
+                } catch (e: Exception) {
+                    logger.error(e) { "Error reporting item" }
+                    call.respond(HttpStatusCode.InternalServerError,
+                        ErrorResponse("Failed to report item"))
+                }
+            }
+            
             // Creator endpoints
             route("/creator") {
                 
@@ -221,7 +307,8 @@ fun Route.marketplaceRoutes() {
                                 avatarUrl = request.avatarUrl,
                                 contentSpecialties = request.contentSpecialties,
                                 languagesSupported = request.languagesSupported,
-                                educatorCredentials = request.educatorCredentials
+                                educatorCredentials = request.educatorCredentials,
+                                socialLinks = request.socialLinks
                             )
                         )
                         
@@ -233,6 +320,47 @@ fun Route.marketplaceRoutes() {
                             ErrorResponse("Creator registration failed"))
                     }
                 }
+
+                // Start TOTP enrollment; 2FA stays off until /2fa/confirm succeeds
+                post("/2fa/enroll") {
+                    try {
+                        val user = call.extractUser()
+                        creatorService.findCreatorIdForUser(user.id)
+                            ?: return@post call.respond(HttpStatusCode.Forbidden,
+                                ErrorResponse("Only creators can enable two-factor authentication"))
+                        if (creatorTwoFactor.isEnabled(user.id)) {
+                            return@post call.respond(HttpStatusCode.Conflict,
+                                ErrorResponse("Two-factor authentication is already enabled"))
+                        }
+
+                        val enrollment = creatorTwoFactor.beginEnrollment(user.id, user.email)
+                        call.respond(HttpStatusCode.OK, enrollment)
+
+                    } catch (e: Exception) {
+                        logger.error(e) { "Error starting 2FA enrollment" }
+                        call.respond(HttpStatusCode.InternalServerError,
+                            ErrorResponse("Failed to start two-factor enrollment"))
+                    }
+                }
+
+                // Confirm enrollment with a code from the authenticator app
+                post("/2fa/confirm") {
+                    try {
+                        val user = call.extractUser()
+                        val request = call.receive<TwoFactorCodeRequest>()
+
+                        if (creatorTwoFactor.confirmEnrollment(user.id, request.code.trim())) {
+                            call.respond(HttpStatusCode.OK, mapOf("message" to "Two-factor authentication enabled"))
+                        } else {
+                            call.respond(HttpStatusCode.BadRequest, ErrorResponse("Invalid two-factor code"))
+                        }
+
+                    } catch (e: Exception) {
+                        logger.error(e) { "Error confirming 2FA enrollment" }
+                        call.respond(HttpStatusCode.InternalServerError,
+                            ErrorResponse("Failed to confirm two-factor enrollment"))
+                    }
+                }
                 
                 // Get creator profile
                 get("/profile/{creatorId}") {
@@ -279,41 +407,232 @@ fun Route.marketplaceRoutes() {
                     }
                 }
                 
+                // Save content as a draft
+                post("/drafts") {
+                    try {
+                        val user = call.extractUser()
+                        val request = call.receive<PublishContentDto>()
+
+                        val creatorId = creatorService.findCreatorIdForUser(user.id)
+                            ?: return@post call.respond(HttpStatusCode.Forbidden,
+                                ErrorResponse("A creator profile is required"))
+
+                        val result = creatorService.saveDraft(creatorId, request.toPublishContentRequest())
+                        call.respond(HttpStatusCode.Created, result)
+
+                    } catch (e: PublishingLimitExceededException) {
+                        call.respond(HttpStatusCode.TooManyRequests, e.toResponse())
+                    } catch (e: InvalidAgeRangeException) {
+                        call.respond(HttpStatusCode.UnprocessableEntity, e.toResponse())
+                    } catch (e: Exception) {
+                        logger.error(e) { "Error saving draft" }
+                        call.respond(HttpStatusCode.InternalServerError,
+                            ErrorResponse("Failed to save draft"))
+                    }
+                }
+
+                // Partially update a draft's content data with a JSON Patch (RFC 6902)
+                patch("/drafts/{itemId}/content") {
+                    try {
+                        val user = call.extractUser()
+                        val itemId = call.parameters["itemId"]?.let { UUID.fromString(it) }
+                            ?: return@patch call.respond(HttpStatusCode.BadRequest,
+                                ErrorResponse("Invalid item ID"))
+                        val request = call.receive<ContentPatchRequest>()
+
+                        val creatorId = creatorService.findCreatorIdForUser(user.id)
+                            ?: return@patch call.respond(HttpStatusCode.NotFound,
+                                ErrorResponse("Draft not found"))
+
+                        val result = creatorService.patchDraftContent(creatorId, itemId, request)
+                        call.respond(HttpStatusCode.OK, result)
+
+                    } catch (e: ContentVersionConflictException) {
+                        call.respond(HttpStatusCode.Conflict, ErrorResponse(e.message ?: "Content was modified concurrently"))
+                    } catch (e: NoSuchElementException) {
+                        call.respond(HttpStatusCode.NotFound, ErrorResponse(e.message ?: "Draft not found"))
+                    } catch (e: IllegalArgumentException) {
+                        call.respond(HttpStatusCode.BadRequest, ErrorResponse(e.message ?: "Invalid patch"))
+                    } catch (e: Exception) {
+                        logger.error(e) { "Error patching draft content" }
+                        call.respond(HttpStatusCode.InternalServerError,
+                            ErrorResponse("Failed to update draft"))
+                    }
+                }
+
+                // Hold a submission until a coordinated launch time
+                put("/submissions/{itemId}/embargo") {
+                    try {
+                        val user = call.extractUser()
+                        val itemId = call.parameters["itemId"]?.let { UUID.fromString(it) }
+                            ?: return@put call.respond(HttpStatusCode.BadRequest,
+                                ErrorResponse("Invalid item ID"))
+                        val request = call.receive<EmbargoRequest>()
+
+                        val creatorId = creatorService.findCreatorIdForUser(user.id)
+                            ?: return@put call.respond(HttpStatusCode.NotFound,
+                                ErrorResponse("Submission not found"))
+
+                        val result = creatorService.setEmbargo(creatorId, itemId, Instant.parse(request.embargoUntil))
+                        call.respond(HttpStatusCode.OK, result)
+
+                    } catch (e: DateTimeParseException) {
+                        call.respond(HttpStatusCode.BadRequest, ErrorResponse("embargoUntil must be an ISO-8601 instant"))
+                    } catch (e: NoSuchElementException) {
+                        call.respond(HttpStatusCode.NotFound, ErrorResponse(e.message ?: "Submission not found"))
+                    } catch (e: IllegalArgumentException) {
+                        call.respond(HttpStatusCode.BadRequest, ErrorResponse(e.message ?: "Invalid request"))
+                    } catch (e: Exception) {
+                        logger.error(e) { "Error setting embargo" }
+                        call.respond(HttpStatusCode.InternalServerError,
+                            ErrorResponse("Failed to set embargo"))
+                    }
+                }
+
+                // Lift an embargo early
+                delete("/submissions/{itemId}/embargo") {
+                    try {
+                        val user = call.extractUser()
+                        val itemId = call.parameters["itemId"]?.let { UUID.fromString(it) }
+                            ?: return@delete call.respond(HttpStatusCode.BadRequest,
+                                ErrorResponse("Invalid item ID"))
+
+                        val creatorId = creatorService.findCreatorIdForUser(user.id)
+                            ?: return@delete call.respond(HttpStatusCode.NotFound,
+                                ErrorResponse("Submission not found"))
+
+                        call.respond(HttpStatusCode.OK, creatorService.liftEmbargo(itemId, creatorId))
+
+                    } catch (e: NoSuchElementException) {
+                        call.respond(HttpStatusCode.NotFound, ErrorResponse(e.message ?: "Submission not found"))
+                    } catch (e: IllegalArgumentException) {
+                        call.respond(HttpStatusCode.BadRequest, ErrorResponse(e.message ?: "Invalid request"))
+                    } catch (e: Exception) {
+                        logger.error(e) { "Error lifting embargo" }
+                        call.respond(HttpStatusCode.InternalServerError,
+                            ErrorResponse("Failed to lift embargo"))
+                    }
+                }
+
+                // Withdraw a submission from review
+                post("/submissions/{itemId}/withdraw") {
+                    try {
+                        val user = call.extractUser()
+                        val itemId = call.parameters["itemId"]?.let { UUID.fromString(it) }
+                            ?: return@post call.respond(HttpStatusCode.BadRequest,
+                                ErrorResponse("Invalid item ID"))
+
+                        val creatorId = creatorService.findCreatorIdForUser(user.id)
+                            ?: return@post call.respond(HttpStatusCode.NotFound,
+                                ErrorResponse("Submission not found"))
+
+                        call.respond(HttpStatusCode.OK, creatorService.withdrawSubmission(creatorId, itemId))
+
+                    } catch (e: NoSuchElementException) {
+                        call.respond(HttpStatusCode.NotFound, ErrorResponse(e.message ?: "Submission not found"))
+                    } catch (e: IllegalArgumentException) {
+                        call.respond(HttpStatusCode.BadRequest, ErrorResponse(e.message ?: "Invalid request"))
+                    } catch (e: Exception) {
+                        logger.error(e) { "Error withdrawing submission" }
+                        call.respond(HttpStatusCode.InternalServerError,
+                            ErrorResponse("Failed to withdraw submission"))
+                    }
+                }
+
+                // Send a withdrawn submission, or one needing changes, back for review
+                post("/submissions/{itemId}/resubmit") {
+                    try {
+                        val user = call.extractUser()
+                        val itemId = call.parameters["itemId"]?.let { UUID.fromString(it) }
+                            ?: return@post call.respond(HttpStatusCode.BadRequest,
+                                ErrorResponse("Invalid item ID"))
+
+                        val creatorId = creatorService.findCreatorIdForUser(user.id)
+                            ?: return@post call.respond(HttpStatusCode.NotFound,
+                                ErrorResponse("Submission not found"))
+
+                        call.respond(HttpStatusCode.OK, creatorService.resubmitSubmission(creatorId, itemId))
+
+                    } catch (e: NoSuchElementException) {
+                        call.respond(HttpStatusCode.NotFound, ErrorResponse(e.message ?: "Submission not found"))
+                    } catch (e: IllegalArgumentException) {
+                        call.respond(HttpStatusCode.BadRequest, ErrorResponse(e.message ?: "Invalid request"))
+                    } catch (e: IllegalStateException) {
+                        call.respond(HttpStatusCode.Conflict, ErrorResponse(e.message ?: "Submission changed"))
+                    } catch (e: Exception) {
+                        logger.error(e) { "Error resubmitting submission" }
+                        call.respond(HttpStatusCode.InternalServerError,
+                            ErrorResponse("Failed to resubmit submission"))
+                    }
+                }
+
+                // Submit a draft for review
+                post("/drafts/{itemId}/submit") {
+                    try {
+                        val user = call.extractUser()
+                        val itemId = call.parameters["itemId"]?.let { UUID.fromString(it) }
+                            ?: return@post call.respond(HttpStatusCode.BadRequest,
+                                ErrorResponse("Invalid item ID"))
+
+                        val creatorId = creatorService.findCreatorIdForUser(user.id)
+                            ?: return@post call.respond(HttpStatusCode.NotFound,
+                                ErrorResponse("Draft not found"))
+
+                        val result = creatorService.submitForReview(creatorId, itemId)
+                        call.respond(HttpStatusCode.OK, result)
+
+                    } catch (e: PublishingLimitExceededException) {
+                        call.respond(HttpStatusCode.TooManyRequests, e.toResponse())
+                    } catch (e: LocalizationIncompleteException) {
+                        call.respond(HttpStatusCode.UnprocessableEntity, e.toResponse())
+                    } catch (e: NoSuchElementException) {
+                        call.respond(HttpStatusCode.NotFound, ErrorResponse(e.message ?: "Draft not found"))
+                    } catch (e: IllegalArgumentException) {
+                        call.respond(HttpStatusCode.BadRequest, ErrorResponse(e.message ?: "Invalid request"))
+                    } catch (e: IllegalStateException) {
+                        call.respond(HttpStatusCode.Conflict, ErrorResponse(e.message ?: "Draft changed"))
+                    } catch (e: Exception) {
+                        logger.error(e) { "Error submitting draft for review" }
+                        call.respond(HttpStatusCode.InternalServerError,
+                            ErrorResponse("Failed to submit draft"))
+                    }
+                }
+                
                 // Publish content
                 post("/publish") {
                     try {
                         val user = call.extractUser()
                         val request = call.receive<PublishContentDto>()
                         
-                        // TODO: Verify user is a creator
-                        val creatorId = UUID.randomUUID() // Get from user's creator profile
+                        val creatorId = creatorService.findCreatorIdForUser(user.id)
+                            ?: return@post call.respond(HttpStatusCode.Forbidden,
+                                ErrorResponse("A creator profile is required"))
                         
                         val result = creatorService.publishContent(
                             creatorId = creatorId,
-                            request = PublishContentRequest(
-                                title = request.title,
-                                description = request.description,
-                                contentType = try {
-                                    ContentType.valueOf(request.contentType.uppercase())
-                                } catch (e: IllegalArgumentException) {
-                                    ContentType.STORY // Default to STORY if invalid
-                                },
-                                ageRange = request.ageRange,
-                                price = BigDecimal(request.price),
-                                licensingModel = LicensingModel.valueOf(request.licensingModel.uppercase()),
-                                tags = request.tags,
-                                educationalGoals = request.educationalGoals,
-                                contentData = request.contentData
-                            )
+                            request = request.toPublishContentRequest()
                         )
                         
                         if (result.success) {
                             call.respond(HttpStatusCode.Created, result)
+                        } else if (result.validation != null) {
+                            // Failed the automated scan; the findings tell the creator what to fix
+                            call.respond(HttpStatusCode.UnprocessableEntity, result)
                         } else {
                             call.respond(HttpStatusCode.BadRequest, 
                                 ErrorResponse(result.message))
                         }
                         
+                    } catch (e: PublishingLimitExceededException) {
+                        call.respond(HttpStatusCode.TooManyRequests, e.toResponse())
+                    } catch (e: LocalizationIncompleteException) {
+                        call.respond(HttpStatusCode.UnprocessableEntity, e.toResponse())
+                    } catch (e: InvalidAgeRangeException) {
+                        call.respond(HttpStatusCode.UnprocessableEntity, e.toResponse())
+                    } catch (e: NoSuchElementException) {
+                        call.respond(HttpStatusCode.NotFound, ErrorResponse(e.message ?: "Content not found"))
+                    } catch (e: IllegalArgumentException) {
+                        call.respond(HttpStatusCode.BadRequest, ErrorResponse(e.message ?: "Invalid request"))
                     } catch (e: Exception) {
                         logger.error(e) { "Error publishing content" }
                         call.respond(HttpStatusCode.InternalServerError, 
@@ -455,7 +774,8 @@ data class CreatorRegistrationDto(
     val avatarUrl: String?,
     val contentSpecialties: List<String>,
     val languagesSupported: List<String>,
-    val educatorCredentials: Map<String, String>? = null
+    val educatorCredentials: Map<String, String>? = null,
+    val socialLinks: Map<String, String> = emptyMap()
 )
 
 @Serializable
@@ -468,7 +788,52 @@ data class PublishContentDto(
     val licensingModel: String,
     val tags: List<String>,
     val educationalGoals: List<String>,
-    val contentData: Map<String, String>
+    val contentData: Map<String, String>,
+    val itemId: String? = null,
+    val version: String? = null,
+    val changelog: String? = null,
+    val primaryLanguage: String = "en",
+    val languagesSupported: List<String> = emptyList(),
+    val localizations: Map<String, LocalizedContent> = emptyMap(),
+    val dropIncompleteLanguages: Boolean = false
+)
+
+private fun PublishContentDto.toPublishContentRequest() = PublishContentRequest(
+    title = title,
+    description = description,
+    contentType = try {
+        ContentType.valueOf(contentType.uppercase())
+    } catch (e: IllegalArgumentException) {
+        ContentType.STORY // Default to STORY if invalid
+    },
+    ageRange = ageRange,
+    price = BigDecimal(price),
+    licensingModel = LicensingModel.valueOf(licensingModel.uppercase()),
+    tags = tags,
+    educationalGoals = educationalGoals,
+    contentData = contentData,
+    itemId = itemId?.let { UUID.fromString(it) },
+    version = version,
+    changelog = changelog,
+    primaryLanguage = primaryLanguage,
+    languagesSupported = languagesSupported,
+    localizations = localizations,
+    dropIncompleteLanguages = dropIncompleteLanguages
+)
+
+@Serializable
+data class TwoFactorCodeRequest(
+    val code: String
+)
+
+@Serializable
+data class EmbargoRequest(
+    val embargoUntil: String // ISO-8601 instant
+)
+
+@Serializable
+data class ReportItemRequest(
+    val reason: String
 )
 
 @Serializable
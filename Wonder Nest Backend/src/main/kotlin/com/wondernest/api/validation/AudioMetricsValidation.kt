@@ -0,0 +1,50 @@
+package com.wondernest.api.validation
+
+import com.wondernest.services.audio.AudioMetricsRequest
+import com.wondernest.utils.ValidationResult
+import com.wondernest.utils.ValidationResults
+
+/**
+ * Bounds for uploaded speech metrics, so one bad client can't skew a child's trends
+ */
+object AudioMetricsValidation {
+    val SCORE_RANGE = 0.0..1.0
+    const val MAX_SESSION_SECONDS = 4 * 60 * 60
+    const val MAX_WORD_LENGTH = 50
+    const val MAX_VOCABULARY_SIZE = 2000
+    // Width of speech_session_metrics.session_id
+    const val MAX_SESSION_ID_LENGTH = 100
+
+    fun validate(request: AudioMetricsRequest): ValidationResults {
+        val validations = mutableListOf<ValidationResult>()
+
+        // NaN fails the range check too
+        if (request.speechClarity !in SCORE_RANGE) {
+            validations.add(ValidationResult.failure("Speech clarity must be between 0.0 and 1.0", "speechClarity"))
+        }
+        if (request.engagementLevel !in SCORE_RANGE) {
+            validations.add(ValidationResult.failure("Engagement level must be between 0.0 and 1.0", "engagementLevel"))
+        }
+        if (request.sessionDuration !in 0..MAX_SESSION_SECONDS) {
+            validations.add(
+                ValidationResult.failure("Session duration must be between 0 and $MAX_SESSION_SECONDS seconds", "sessionDuration")
+            )
+        }
+        if ((request.sessionId?.length ?: 0) > MAX_SESSION_ID_LENGTH) {
+            validations.add(
+                ValidationResult.failure("Session ID must be at most $MAX_SESSION_ID_LENGTH characters", "sessionId")
+            )
+        }
+        if (request.vocabularyUsed.size > MAX_VOCABULARY_SIZE) {
+            validations.add(
+                ValidationResult.failure("At most $MAX_VOCABULARY_SIZE words can be sent per session", "vocabularyUsed")
+            )
+        } else if (request.vocabularyUsed.any { it.isBlank() || it.trim().length > MAX_WORD_LENGTH }) {
+            validations.add(
+                ValidationResult.failure("Each word must be 1 to $MAX_WORD_LENGTH characters", "vocabularyUsed")
+            )
+        }
+
+        return ValidationResults.combine(*validations.toTypedArray())
+    }
+}
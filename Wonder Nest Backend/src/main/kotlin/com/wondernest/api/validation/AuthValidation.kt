@@ -5,6 +5,8 @@ import com.wondernest.services.auth.LoginRequest
 import com.wondernest.services.auth.OAuthLoginRequest
 import com.wondernest.api.auth.PasswordResetRequest
 import com.wondernest.api.auth.PasswordResetConfirmRequest
+import com.wondernest.utils.EmailNormalizer
+import com.wondernest.utils.FieldError
 import com.wondernest.utils.ValidationUtils
 import com.wondernest.utils.ValidationResult
 import com.wondernest.utils.ValidationResults
@@ -23,16 +25,16 @@ object AuthValidation {
         
         // Email validation
         if (!ValidationUtils.isValidEmail(request.email)) {
-            validations.add(ValidationResult.failure("Invalid email format"))
+            validations.add(ValidationResult.failure("Invalid email format", "email"))
         }
         
         // Check for security threats in email - temporarily disabled for debugging
         // if (ValidationUtils.containsSqlInjection(request.email) || ValidationUtils.containsXss(request.email)) {
-        //     validations.add(ValidationResult.failure("Invalid characters in email"))
+        //     validations.add(ValidationResult.failure("Invalid characters in email", "email"))
         // }
         
         // Password validation
-        validations.add(ValidationUtils.validatePassword(request.password))
+        validations.add(ValidationUtils.validatePassword(request.password).forField("password"))
         
         // First name validation (optional)
         if (request.firstName != null) {
@@ -43,7 +45,7 @@ object AuthValidation {
                     minLength = 1,
                     maxLength = 50,
                     required = false
-                )
+                ).forField("firstName")
             )
             
             // Temporarily disabled for debugging
@@ -61,7 +63,7 @@ object AuthValidation {
                     minLength = 1,
                     maxLength = 50,
                     required = false
-                )
+                ).forField("lastName")
             )
             
             // Temporarily disabled for debugging
@@ -72,12 +74,12 @@ object AuthValidation {
         
         // Timezone validation
         if (!ValidationUtils.isValidTimezone(request.timezone)) {
-            validations.add(ValidationResult.failure("Invalid timezone"))
+            validations.add(ValidationResult.failure("Invalid timezone", "timezone"))
         }
         
         // Language validation
         if (!ValidationUtils.isValidLanguageCode(request.language)) {
-            validations.add(ValidationResult.failure("Invalid language code"))
+            validations.add(ValidationResult.failure("Invalid language code", "language"))
         }
         
         return ValidationResults.combine(*validations.toTypedArray())
@@ -91,19 +93,19 @@ object AuthValidation {
         
         // Email validation
         if (!ValidationUtils.isValidEmail(request.email)) {
-            validations.add(ValidationResult.failure("Invalid email format"))
+            validations.add(ValidationResult.failure("Invalid email format", "email"))
         }
         
         // Check for security threats in email (for login, be more lenient than signup)
         if (ValidationUtils.containsXss(request.email)) {
-            validations.add(ValidationResult.failure("Invalid characters in email"))
+            validations.add(ValidationResult.failure("Invalid characters in email", "email"))
         }
         
         // Note: Don't check password for SQL injection as it may contain legitimate special characters
         
         // Password presence check (don't validate strength for login)
         if (request.password.isBlank()) {
-            validations.add(ValidationResult.failure("Password is required"))
+            validations.add(ValidationResult.failure("Password is required", "password"))
         }
         
         return ValidationResults.combine(*validations.toTypedArray())
@@ -118,19 +120,19 @@ object AuthValidation {
         // Provider validation
         val validProviders = setOf("google", "apple", "facebook")
         if (!validProviders.contains(request.provider.lowercase())) {
-            validations.add(ValidationResult.failure("Invalid OAuth provider. Supported: ${validProviders.joinToString(", ")}"))
+            validations.add(ValidationResult.failure("Invalid OAuth provider. Supported: ${validProviders.joinToString(", ")}", "provider"))
         }
         
         // ID token validation (basic checks)
         if (request.idToken.isBlank()) {
-            validations.add(ValidationResult.failure("ID token is required"))
+            validations.add(ValidationResult.failure("ID token is required", "idToken"))
         } else if (request.idToken.length > 2048) {
-            validations.add(ValidationResult.failure("ID token is too long"))
+            validations.add(ValidationResult.failure("ID token is too long", "idToken"))
         }
         
         // Email validation
         if (!ValidationUtils.isValidEmail(request.email)) {
-            validations.add(ValidationResult.failure("Invalid email format"))
+            validations.add(ValidationResult.failure("Invalid email format", "email"))
         }
         
         // Check for security threats in all fields
@@ -143,7 +145,7 @@ object AuthValidation {
         
         fieldsToCheck.forEach { (value, fieldName) ->
             if (value != null && (ValidationUtils.containsSqlInjection(value) || ValidationUtils.containsXss(value))) {
-                validations.add(ValidationResult.failure("Invalid characters in $fieldName"))
+                validations.add(ValidationResult.failure("Invalid characters in $fieldName", fieldName))
             }
         }
         
@@ -156,7 +158,7 @@ object AuthValidation {
                     minLength = 1,
                     maxLength = 50,
                     required = false
-                )
+                ).forField("firstName")
             )
         }
         
@@ -168,7 +170,7 @@ object AuthValidation {
                     minLength = 1,
                     maxLength = 50,
                     required = false
-                )
+                ).forField("lastName")
             )
         }
         
@@ -183,12 +185,12 @@ object AuthValidation {
         
         // Email validation
         if (!ValidationUtils.isValidEmail(request.email)) {
-            validations.add(ValidationResult.failure("Invalid email format"))
+            validations.add(ValidationResult.failure("Invalid email format", "email"))
         }
         
         // Check for security threats
         if (ValidationUtils.containsSqlInjection(request.email) || ValidationUtils.containsXss(request.email)) {
-            validations.add(ValidationResult.failure("Invalid characters in email"))
+            validations.add(ValidationResult.failure("Invalid characters in email", "email"))
         }
         
         return ValidationResults.combine(*validations.toTypedArray())
@@ -202,18 +204,18 @@ object AuthValidation {
         
         // Token validation (basic checks)
         if (request.token.isBlank()) {
-            validations.add(ValidationResult.failure("Reset token is required"))
+            validations.add(ValidationResult.failure("Reset token is required", "token"))
         } else if (request.token.length < 10 || request.token.length > 256) {
-            validations.add(ValidationResult.failure("Invalid reset token format"))
+            validations.add(ValidationResult.failure("Invalid reset token format", "token"))
         }
         
         // Check for security threats in token
         if (ValidationUtils.containsSqlInjection(request.token) || ValidationUtils.containsXss(request.token)) {
-            validations.add(ValidationResult.failure("Invalid characters in reset token"))
+            validations.add(ValidationResult.failure("Invalid characters in reset token", "token"))
         }
         
         // New password validation
-        validations.add(ValidationUtils.validatePassword(request.newPassword))
+        validations.add(ValidationUtils.validatePassword(request.newPassword).forField("newPassword"))
         
         return ValidationResults.combine(*validations.toTypedArray())
     }
@@ -223,7 +225,7 @@ object AuthValidation {
      */
     fun sanitizeSignupRequest(request: SignupRequest): SignupRequest {
         return request.copy(
-            email = request.email.trim().lowercase(),
+            email = EmailNormalizer.canonical(request.email),
             firstName = ValidationUtils.sanitizeString(request.firstName),
             lastName = ValidationUtils.sanitizeString(request.lastName),
             timezone = request.timezone.trim(),
@@ -236,7 +238,7 @@ object AuthValidation {
      */
     fun sanitizeLoginRequest(request: LoginRequest): LoginRequest {
         return request.copy(
-            email = request.email.trim().lowercase()
+            email = EmailNormalizer.canonical(request.email)
             // Note: Don't sanitize password as it might contain legitimate special characters
         )
     }
@@ -247,7 +249,7 @@ object AuthValidation {
     fun sanitizeOAuthLoginRequest(request: OAuthLoginRequest): OAuthLoginRequest {
         return request.copy(
             provider = request.provider.trim().lowercase(),
-            email = request.email.trim().lowercase(),
+            email = EmailNormalizer.canonical(request.email),
             firstName = ValidationUtils.sanitizeString(request.firstName),
             lastName = ValidationUtils.sanitizeString(request.lastName)
         )
@@ -259,7 +261,8 @@ object AuthValidation {
  */
 class AuthValidationException(
     message: String,
-    val validationErrors: List<String> = emptyList()
+    val validationErrors: List<String> = emptyList(),
+    val fieldErrors: List<FieldError> = emptyList()
 ) : IllegalArgumentException(message)
 
 /**
@@ -269,7 +272,8 @@ fun ValidationResults.throwIfInvalid() {
     if (!this.isValid) {
         throw AuthValidationException(
             message = "Validation failed: ${this.errors.joinToString(", ")}",
-            validationErrors = this.errors
+            validationErrors = this.errors,
+            fieldErrors = this.fieldErrors
         )
     }
 }
\ No newline at end of file
@@ -0,0 +1,78 @@
+package com.wondernest.api.web.admin
+
+import com.wondernest.domain.web.AdminRole
+import com.wondernest.domain.web.AuditLogFilter
+import com.wondernest.domain.web.AuditSeverity
+import com.wondernest.services.web.admin.AdminAuditService
+import io.ktor.http.*
+import io.ktor.server.application.*
+import io.ktor.server.auth.*
+import io.ktor.server.auth.jwt.*
+import io.ktor.server.response.*
+import io.ktor.server.routing.*
+import mu.KotlinLogging
+import org.koin.ktor.ext.inject
+import java.time.Instant
+import java.time.format.DateTimeParseException
+import java.util.*
+
+private val logger = KotlinLogging.logger {}
+
+/**
+ * Admin dashboard routes for reviewing the audit log
+ */
+fun Route.adminAuditRoutes() {
+    val adminAuditService by inject<AdminAuditService>()
+
+    authenticate("admin-jwt") {
+        route("/admin/dashboard") {
+
+            /**
+             * Audit log entries, newest first. Optional filters: adminId, action,
+             * severity (info|warning|error), from and to (ISO-8601), page and pageSize.
+             * GET /api/web/v1/admin/dashboard/audit-logs
+             */
+            get("/audit-logs") {
+                try {
+                    val callerRole = call.principal<JWTPrincipal>()?.payload?.getClaim("adminRole")?.asString()
+                        ?.let { name -> AdminRole.entries.firstOrNull { it.name == name } }
+                    val params = call.request.queryParameters
+
+                    val filter = AuditLogFilter(
+                        adminId = params["adminId"]?.let { UUID.fromString(it) },
+                        action = params["action"]?.takeIf { it.isNotBlank() },
+                        severity = params["severity"]?.let { AuditSeverity.fromDbValue(it) },
+                        from = params["from"]?.let { Instant.parse(it) },
+                        to = params["to"]?.let { Instant.parse(it) }
+                    )
+                    val page = params["page"]?.toIntOrNull() ?: 1
+                    val pageSize = params["pageSize"]?.toIntOrNull() ?: AdminAuditService.DEFAULT_PAGE_SIZE
+
+                    call.respond(HttpStatusCode.OK, adminAuditService.queryLogs(callerRole, filter, page, pageSize))
+
+                } catch (e: SecurityException) {
+                    call.respond(
+                        HttpStatusCode.Forbidden,
+                        ErrorResponse("insufficient_permissions", e.message)
+                    )
+                } catch (e: DateTimeParseException) {
+                    call.respond(
+                        HttpStatusCode.BadRequest,
+                        ErrorResponse("invalid_request", "from and to must be ISO-8601 instants")
+                    )
+                } catch (e: IllegalArgumentException) {
+                    call.respond(
+                        HttpStatusCode.BadRequest,
+                        ErrorResponse("invalid_request", e.message)
+                    )
+                } catch (e: Exception) {
+                    logger.error(e) { "Error querying audit logs" }
+                    call.respond(
+                        HttpStatusCode.InternalServerError,
+                        ErrorResponse("internal_error", "Failed to query audit logs")
+                    )
+                }
+            }
+        }
+    }
+}
@@ -1,8 +1,18 @@
 package com.wondernest.api.web.admin
 
+import com.wondernest.api.auth.authAttemptKeys
+import com.wondernest.api.auth.rejectIfThrottled
 import com.wondernest.domain.web.AdminLoginRequest
+import com.wondernest.domain.web.AdminSessionView
+import com.wondernest.domain.web.TwoFactorConfirmRequest
+import com.wondernest.domain.web.TwoFactorConfirmResponse
+import com.wondernest.domain.web.UpdateAdminProfileRequest
+import com.wondernest.services.auth.AuthRateLimiter
+import com.wondernest.services.web.admin.AdminCaller
 import com.wondernest.services.web.admin.AdminAuthService
 import com.wondernest.services.web.admin.AuthenticationException
+import com.wondernest.services.web.admin.InsufficientPermissionsException
+import com.wondernest.services.web.admin.InvalidTwoFactorCodeException
 import io.ktor.http.*
 import io.ktor.server.application.*
 import io.ktor.server.auth.*
@@ -29,17 +39,44 @@ data class ErrorResponse(
     val timestamp: Long = System.currentTimeMillis()
 )
 
+@Serializable
+data class AdminSessionListResponse(
+    val sessions: List<AdminSessionView>
+)
+
+@Serializable
+data class SessionsRevokedResponse(
+    val sessionsRevoked: Int
+)
+
 @Serializable
 data class SuccessResponse(
     val message: String,
     val timestamp: Long = System.currentTimeMillis()
 )
 
+private fun ApplicationCall.adminCaller(): AdminCaller? {
+    val payload = principal<JWTPrincipal>()?.payload ?: return null
+    val adminId = payload.getClaim("userId").asString()
+        ?.let { runCatching { UUID.fromString(it) }.getOrNull() }
+        ?: return null
+    return AdminCaller(
+        adminId = adminId,
+        sessionId = payload.getClaim("sessionId").asString()?.let { runCatching { UUID.fromString(it) }.getOrNull() },
+        permissions = payload.getClaim("permissions").asList(String::class.java) ?: emptyList(),
+        ipAddress = request.headers["X-Forwarded-For"]
+            ?: request.headers["X-Real-IP"]
+            ?: request.local.remoteHost,
+        userAgent = request.headers["User-Agent"]
+    )
+}
+
 /**
  * Admin authentication routes for the web platform
  */
 fun Route.adminAuthRoutes() {
     val adminAuthService by inject<AdminAuthService>()
+    val authRateLimiter by inject<AuthRateLimiter>()
 
     route("/admin/auth") {
         
@@ -48,8 +85,11 @@ fun Route.adminAuthRoutes() {
          * POST /api/web/v1/admin/auth/login
          */
         post("/login") {
+            var attemptKeys = call.authAttemptKeys(authRateLimiter, "admin-login")
             try {
                 val request = call.receive<AdminLoginRequest>()
+                attemptKeys = call.authAttemptKeys(authRateLimiter, "admin-login", request.email)
+                if (call.rejectIfThrottled(authRateLimiter, attemptKeys)) return@post
                 
                 // Validate input
                 if (request.email.isBlank() || request.password.isBlank()) {
@@ -74,6 +114,7 @@ fun Route.adminAuthRoutes() {
                     ipAddress = ipAddress,
                     userAgent = userAgent
                 )
+                authRateLimiter.release(attemptKeys)
                 
                 call.respond(HttpStatusCode.OK, response)
                 
@@ -143,17 +184,17 @@ fun Route.adminAuthRoutes() {
              */
             post("/logout") {
                 try {
-                    val token = call.request.headers["Authorization"]?.removePrefix("Bearer ")
+                    val sessionId = call.adminCaller()?.sessionId
                     
-                    if (token.isNullOrBlank()) {
+                    if (sessionId == null) {
                         call.respond(
                             HttpStatusCode.BadRequest,
-                            ErrorResponse("validation_error", "Authorization token required")
+                            ErrorResponse("validation_error", "Token is not tied to a session")
                         )
                         return@post
                     }
                     
-                    val success = adminAuthService.logoutAdmin(token)
+                    val success = adminAuthService.logoutAdmin(sessionId)
                     
                     if (success) {
                         call.respond(
@@ -194,12 +235,11 @@ fun Route.adminAuthRoutes() {
                     }
                     
                     val adminId = UUID.fromString(adminIdStr)
-                    val token = call.request.headers["Authorization"]?.removePrefix("Bearer ")
-                        ?: return@get
+                    val sessionId = call.adminCaller()?.sessionId
                     
-                    val adminUser = adminAuthService.validateSession(token)
+                    val adminUser = sessionId?.let { adminAuthService.validateSession(it) }
                     
-                    if (adminUser == null) {
+                    if (adminUser == null || adminUser.id != adminId) {
                         call.respond(
                             HttpStatusCode.Unauthorized,
                             ErrorResponse("invalid_session", "Session not valid")
@@ -219,32 +259,139 @@ fun Route.adminAuthRoutes() {
             }
             
             /**
-             * Get active sessions for current admin user
+             * Update the current admin's profile (name and phone number)
+             * PUT /api/web/v1/admin/auth/profile
+             */
+            put("/profile") {
+                try {
+                    val caller = call.adminCaller()
+                        ?: return@put call.respond(
+                            HttpStatusCode.Unauthorized,
+                            ErrorResponse("invalid_token", "Invalid user ID in token")
+                        )
+                    val request = call.receive<UpdateAdminProfileRequest>()
+                    
+                    val profile = adminAuthService.updateProfile(
+                        caller.adminId, request, caller.ipAddress, caller.userAgent
+                    )
+                    call.respond(HttpStatusCode.OK, profile)
+                    
+                } catch (e: AuthenticationException) {
+                    call.respond(
+                        HttpStatusCode.Unauthorized,
+                        ErrorResponse("authentication_failed", e.message)
+                    )
+                } catch (e: IllegalArgumentException) {
+                    call.respond(
+                        HttpStatusCode.BadRequest,
+                        ErrorResponse("validation_error", e.message)
+                    )
+                } catch (e: Exception) {
+                    logger.error(e) { "Error updating admin profile" }
+                    call.respond(
+                        HttpStatusCode.InternalServerError,
+                        ErrorResponse("internal_error", "Failed to update profile")
+                    )
+                }
+            }
+            
+            /**
+             * List active sessions for the current admin, or for another admin with
+             * the manage_security_settings permission (?adminId=)
              * GET /api/web/v1/admin/auth/sessions
              */
             get("/sessions") {
                 try {
-                    val principal = call.principal<JWTPrincipal>()
-                    val adminIdStr = principal?.payload?.getClaim("userId")?.asString()
+                    val caller = call.adminCaller()
+                        ?: return@get call.respond(
+                            HttpStatusCode.Unauthorized,
+                            ErrorResponse("invalid_token", "Invalid user ID in token")
+                        )
+                    val targetAdminId = call.request.queryParameters["adminId"]?.let { UUID.fromString(it) }
+                        ?: caller.adminId
                     
-                    if (adminIdStr.isNullOrBlank()) {
-                        call.respond(
+                    val sessions = adminAuthService.listSessions(caller, targetAdminId)
+                    call.respond(HttpStatusCode.OK, AdminSessionListResponse(sessions))
+                    
+                } catch (e: InsufficientPermissionsException) {
+                    call.respond(
+                        HttpStatusCode.Forbidden,
+                        ErrorResponse("insufficient_permissions", e.message)
+                    )
+                } catch (e: IllegalArgumentException) {
+                    call.respond(
+                        HttpStatusCode.BadRequest,
+                        ErrorResponse("invalid_request", "Invalid admin ID")
+                    )
+                } catch (e: Exception) {
+                    logger.error(e) { "Error getting admin sessions" }
+                    call.respond(
+                        HttpStatusCode.InternalServerError,
+                        ErrorResponse("internal_error", "Failed to get sessions")
+                    )
+                }
+            }
+            
+            /**
+             * Revoke a single session
+             * DELETE /api/web/v1/admin/auth/sessions/{sessionId}
+             */
+            delete("/sessions/{sessionId}") {
+                try {
+                    val caller = call.adminCaller()
+                        ?: return@delete call.respond(
                             HttpStatusCode.Unauthorized,
                             ErrorResponse("invalid_token", "Invalid user ID in token")
                         )
-                        return@get
+                    val sessionId = call.parameters["sessionId"]?.let { UUID.fromString(it) }
+                        ?: return@delete call.respond(
+                            HttpStatusCode.BadRequest,
+                            ErrorResponse("invalid_request", "Invalid session ID")
+                        )
+                    
+                    if (adminAuthService.revokeSession(caller, sessionId)) {
+                        call.respond(HttpStatusCode.OK, SuccessResponse("Session revoked"))
+                    } else {
+                        call.respond(
+                            HttpStatusCode.NotFound,
+                            ErrorResponse("session_not_found", "Session not found")
+                        )
                     }
                     
-                    val adminId = UUID.fromString(adminIdStr)
-                    val sessions = adminAuthService.getActiveSessions(adminId)
+                } catch (e: IllegalArgumentException) {
+                    call.respond(
+                        HttpStatusCode.BadRequest,
+                        ErrorResponse("invalid_request", "Invalid session ID")
+                    )
+                } catch (e: Exception) {
+                    logger.error(e) { "Error revoking admin session" }
+                    call.respond(
+                        HttpStatusCode.InternalServerError,
+                        ErrorResponse("internal_error", "Failed to revoke session")
+                    )
+                }
+            }
+            
+            /**
+             * Revoke every session of the current admin except this one
+             * DELETE /api/web/v1/admin/auth/sessions
+             */
+            delete("/sessions") {
+                try {
+                    val caller = call.adminCaller()
+                        ?: return@delete call.respond(
+                            HttpStatusCode.Unauthorized,
+                            ErrorResponse("invalid_token", "Invalid user ID in token")
+                        )
                     
-                    call.respond(HttpStatusCode.OK, mapOf("sessions" to sessions))
+                    val revoked = adminAuthService.revokeOtherSessions(caller)
+                    call.respond(HttpStatusCode.OK, SessionsRevokedResponse(revoked))
                     
                 } catch (e: Exception) {
-                    logger.error(e) { "Error getting admin sessions" }
+                    logger.error(e) { "Error revoking admin sessions" }
                     call.respond(
                         HttpStatusCode.InternalServerError,
-                        ErrorResponse("internal_error", "Failed to get sessions")
+                        ErrorResponse("internal_error", "Failed to revoke sessions")
                     )
                 }
             }
@@ -295,6 +442,94 @@ fun Route.adminAuthRoutes() {
                     )
                 }
             }
+            
+            /**
+             * Start two-factor enrollment for the current admin
+             * POST /api/web/v1/admin/auth/2fa/enroll
+             */
+            post("/2fa/enroll") {
+                try {
+                    val principal = call.principal<JWTPrincipal>()
+                    val adminIdStr = principal?.payload?.getClaim("userId")?.asString()
+                    
+                    if (adminIdStr.isNullOrBlank()) {
+                        call.respond(
+                            HttpStatusCode.Unauthorized,
+                            ErrorResponse("invalid_token", "Invalid user ID in token")
+                        )
+                        return@post
+                    }
+                    
+                    val enrollment = adminAuthService.beginTwoFactorEnrollment(UUID.fromString(adminIdStr))
+                    call.respond(HttpStatusCode.OK, enrollment)
+                    
+                } catch (e: AuthenticationException) {
+                    call.respond(
+                        HttpStatusCode.Unauthorized,
+                        ErrorResponse("authentication_failed", e.message)
+                    )
+                } catch (e: IllegalArgumentException) {
+                    call.respond(
+                        HttpStatusCode.Conflict,
+                        ErrorResponse("two_factor_enabled", e.message)
+                    )
+                } catch (e: Exception) {
+                    logger.error(e) { "Error starting two-factor enrollment" }
+                    call.respond(
+                        HttpStatusCode.InternalServerError,
+                        ErrorResponse("internal_error", "Failed to start two-factor enrollment")
+                    )
+                }
+            }
+            
+            /**
+             * Confirm two-factor enrollment with a code from the authenticator app
+             * POST /api/web/v1/admin/auth/2fa/confirm
+             */
+            post("/2fa/confirm") {
+                try {
+                    val principal = call.principal<JWTPrincipal>()
+                    val adminIdStr = principal?.payload?.getClaim("userId")?.asString()
+                    
+                    if (adminIdStr.isNullOrBlank()) {
+                        call.respond(
+                            HttpStatusCode.Unauthorized,
+                            ErrorResponse("invalid_token", "Invalid user ID in token")
+                        )
+                        return@post
+                    }
+                    
+                    val request = call.receive<TwoFactorConfirmRequest>()
+                    val backupCodes = adminAuthService.confirmTwoFactorEnrollment(
+                        UUID.fromString(adminIdStr),
+                        request.code
+                    )
+                    
+                    call.respond(HttpStatusCode.OK, TwoFactorConfirmResponse(twoFactorEnabled = true, backupCodes = backupCodes))
+                    
+                } catch (e: InvalidTwoFactorCodeException) {
+                    call.respond(
+                        HttpStatusCode.BadRequest,
+                        ErrorResponse("invalid_two_factor_code", e.message)
+                    )
+                } catch (e: AuthenticationException) {
+                    call.respond(
+                        HttpStatusCode.Unauthorized,
+                        ErrorResponse("authentication_failed", e.message)
+                    )
+                } catch (e: IllegalArgumentException) {
+                    call.respond(
+                        HttpStatusCode.BadRequest,
+                        ErrorResponse("invalid_request", e.message)
+                    )
+                } catch (e: Exception) {
+                    logger.error(e) { "Error confirming two-factor enrollment" }
+                    call.respond(
+                        HttpStatusCode.InternalServerError,
+                        ErrorResponse("internal_error", "Failed to confirm two-factor enrollment")
+                    )
+                }
+            }
         }
     }
 }
\ No newline at end of file
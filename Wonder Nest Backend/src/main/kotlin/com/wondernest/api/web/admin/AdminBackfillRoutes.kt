@@ -0,0 +1,135 @@
+package com.wondernest.api.web.admin
+
+import com.wondernest.domain.web.BackfillRunRequest
+import com.wondernest.services.web.admin.BackfillAlreadyRunningException
+import com.wondernest.services.web.admin.BackfillService
+import io.ktor.http.*
+import io.ktor.server.application.*
+import io.ktor.server.auth.*
+import io.ktor.server.auth.jwt.*
+import io.ktor.server.request.*
+import io.ktor.server.response.*
+import io.ktor.server.routing.*
+import mu.KotlinLogging
+import org.koin.ktor.ext.inject
+import java.util.*
+
+private val logger = KotlinLogging.logger {}
+
+/**
+ * Admin routes for running resumable data backfills
+ */
+fun Route.adminBackfillRoutes() {
+    val backfillService by inject<BackfillService>()
+
+    authenticate("admin-jwt") {
+        route("/admin/backfills") {
+
+            /**
+             * List available backfill jobs with their saved progress
+             * GET /api/web/v1/admin/backfills
+             */
+            get {
+                try {
+                    val permissions = call.principal<JWTPrincipal>()?.payload?.getClaim("permissions")
+                        ?.asList(String::class.java) ?: emptyList()
+
+                    call.respond(HttpStatusCode.OK, backfillService.listJobs(permissions))
+
+                } catch (e: SecurityException) {
+                    call.respond(
+                        HttpStatusCode.Forbidden,
+                        ErrorResponse("insufficient_permissions", e.message)
+                    )
+                }
+            }
+
+            /**
+             * Progress of a single backfill job
+             * GET /api/web/v1/admin/backfills/{job}
+             */
+            get("/{job}") {
+                try {
+                    val permissions = call.principal<JWTPrincipal>()?.payload?.getClaim("permissions")
+                        ?.asList(String::class.java) ?: emptyList()
+
+                    call.respond(HttpStatusCode.OK, backfillService.status(permissions, call.parameters["job"]!!))
+
+                } catch (e: SecurityException) {
+                    call.respond(
+                        HttpStatusCode.Forbidden,
+                        ErrorResponse("insufficient_permissions", e.message)
+                    )
+                } catch (e: NoSuchElementException) {
+                    call.respond(
+                        HttpStatusCode.NotFound,
+                        ErrorResponse("not_found", e.message)
+                    )
+                }
+            }
+
+            /**
+             * Start (or resume) a backfill job from its saved cursor in the background;
+             * poll GET /{job} for its progress
+             * POST /api/web/v1/admin/backfills/{job}/run
+             */
+            post("/{job}/run") {
+                try {
+                    val principal = call.principal<JWTPrincipal>()
+                    val adminIdStr = principal?.payload?.getClaim("userId")?.asString()
+                    if (adminIdStr.isNullOrBlank()) {
+                        call.respond(
+                            HttpStatusCode.Unauthorized,
+                            ErrorResponse("invalid_token", "Invalid user ID in token")
+                        )
+                        return@post
+                    }
+                    val permissions = principal?.payload?.getClaim("permissions")
+                        ?.asList(String::class.java) ?: emptyList()
+
+                    val request = call.receive<BackfillRunRequest>()
+                    val ipAddress = call.request.headers["X-Forwarded-For"]
+                        ?: call.request.headers["X-Real-IP"]
+                        ?: call.request.local.remoteHost
+
+                    val progress = backfillService.start(
+                        adminId = UUID.fromString(adminIdStr),
+                        permissions = permissions,
+                        jobName = call.parameters["job"]!!,
+                        request = request,
+                        ipAddress = ipAddress
+                    )
+
+                    call.respond(HttpStatusCode.Accepted, progress)
+
+                } catch (e: SecurityException) {
+                    call.respond(
+                        HttpStatusCode.Forbidden,
+                        ErrorResponse("insufficient_permissions", e.message)
+                    )
+                } catch (e: NoSuchElementException) {
+                    call.respond(
+                        HttpStatusCode.NotFound,
+                        ErrorResponse("not_found", e.message)
+                    )
+                } catch (e: BackfillAlreadyRunningException) {
+                    call.respond(
+                        HttpStatusCode.Conflict,
+                        ErrorResponse("backfill_running", e.message)
+                    )
+                } catch (e: IllegalArgumentException) {
+                    call.respond(
+                        HttpStatusCode.BadRequest,
+                        ErrorResponse("validation_error", e.message)
+                    )
+                } catch (e: Exception) {
+                    logger.error(e) { "Error starting backfill" }
+                    call.respond(
+                        HttpStatusCode.InternalServerError,
+                        ErrorResponse("internal_error", "Failed to start backfill")
+                    )
+                }
+            }
+        }
+    }
+}
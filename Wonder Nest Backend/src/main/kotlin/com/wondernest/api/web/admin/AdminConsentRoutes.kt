@@ -0,0 +1,91 @@
+package com.wondernest.api.web.admin
+
+import com.wondernest.domain.model.ConsentVerificationStatus
+import com.wondernest.domain.web.AdminPermission
+import com.wondernest.services.coppa.ConsentService
+import com.wondernest.services.coppa.DataCollectionConsentChecker
+import io.ktor.http.*
+import io.ktor.server.application.*
+import io.ktor.server.auth.*
+import io.ktor.server.auth.jwt.*
+import io.ktor.server.request.*
+import io.ktor.server.response.*
+import io.ktor.server.routing.*
+import kotlinx.serialization.Serializable
+import mu.KotlinLogging
+import org.koin.ktor.ext.inject
+import java.util.*
+
+private val logger = KotlinLogging.logger {}
+
+@Serializable
+data class ConsentVerificationOutcomeRequest(
+    val outcome: String // verified | failed
+)
+
+/**
+ * Admin routes for completing consent verification that happens outside the app,
+ * such as the staff call behind phone consent
+ */
+fun Route.adminConsentRoutes() {
+    val consentService by inject<ConsentService>()
+    val consentChecker by inject<DataCollectionConsentChecker>()
+
+    authenticate("admin-jwt") {
+        route("/admin/coppa/consent") {
+
+            /**
+             * Record the outcome of verifying a child's pending consent
+             * POST /api/web/v1/admin/coppa/consent/{childId}/verification
+             */
+            post("/{childId}/verification") {
+                try {
+                    val permissions = call.principal<JWTPrincipal>()?.payload?.getClaim("permissions")
+                        ?.asList(String::class.java) ?: emptyList()
+                    if (AdminPermission.MANAGE_USERS.code !in permissions) {
+                        throw SecurityException("Missing permissions: ${AdminPermission.MANAGE_USERS.code}")
+                    }
+
+                    val childId = UUID.fromString(call.parameters["childId"])
+                    val request = call.receive<ConsentVerificationOutcomeRequest>()
+                    val outcome = when (request.outcome.lowercase()) {
+                        "verified" -> ConsentVerificationStatus.VERIFIED
+                        "failed" -> ConsentVerificationStatus.FAILED
+                        else -> throw IllegalArgumentException("outcome must be verified or failed")
+                    }
+
+                    val records = consentService.completeVerification(childId, outcome)
+                    consentChecker.invalidate(childId)
+
+                    call.respond(HttpStatusCode.OK, mapOf(
+                        "childId" to childId.toString(),
+                        "verificationStatus" to outcome.name.lowercase(),
+                        "updatedRecords" to records.size.toString()
+                    ))
+
+                } catch (e: SecurityException) {
+                    call.respond(
+                        HttpStatusCode.Forbidden,
+                        ErrorResponse("insufficient_permissions", e.message)
+                    )
+                } catch (e: NoSuchElementException) {
+                    call.respond(
+                        HttpStatusCode.NotFound,
+                        ErrorResponse("not_found", e.message)
+                    )
+                } catch (e: IllegalArgumentException) {
+                    call.respond(
+                        HttpStatusCode.BadRequest,
+                        ErrorResponse("validation_error", e.message)
+                    )
+                } catch (e: Exception) {
+                    logger.error(e) { "Error completing consent verification" }
+                    call.respond(
+                        HttpStatusCode.InternalServerError,
+                        ErrorResponse("internal_error", "Failed to complete consent verification")
+                    )
+                }
+            }
+        }
+    }
+}
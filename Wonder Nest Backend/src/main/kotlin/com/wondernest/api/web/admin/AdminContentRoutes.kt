@@ -0,0 +1,514 @@
+package com.wondernest.api.web.admin
+
+import com.wondernest.domain.web.AdminPermission
+import com.wondernest.domain.web.BulkStatusTransitionRequest
+import com.wondernest.services.ContentPackServiceSimple
+import com.wondernest.services.marketplace.CreatorService
+import com.wondernest.services.marketplace.SubmissionClaimedException
+import com.wondernest.services.moderation.ModerationAnalyticsService
+import com.wondernest.services.moderation.ModerationDecisionRequest
+import com.wondernest.services.moderation.ModerationWebhookDeadLetterLog
+import com.wondernest.services.moderation.ModerationWebhookDispatcher
+import com.wondernest.services.moderation.RejectionReasonCategory
+import com.wondernest.services.web.admin.AdminContentService
+import io.ktor.http.*
+import io.ktor.server.application.*
+import io.ktor.server.auth.*
+import io.ktor.server.auth.jwt.*
+import io.ktor.server.request.*
+import io.ktor.server.response.*
+import io.ktor.server.routing.*
+import kotlinx.serialization.Serializable
+import mu.KotlinLogging
+import org.koin.ktor.ext.inject
+import java.time.Instant
+import java.time.LocalDate
+import java.time.ZoneOffset
+import java.time.format.DateTimeParseException
+import java.util.*
+
+private val logger = KotlinLogging.logger {}
+
+/**
+ * @param expiresAt ISO-8601 instant, or null to clear the expiry
+ */
+@Serializable
+data class PackExpiryRequest(
+    val expiresAt: String? = null
+)
+
+@Serializable
+data class DuplicateCheckRequest(
+    val title: String,
+    val body: String = ""
+)
+
+@Serializable
+data class WebhookDeadLetterResponse(
+    val id: String,
+    val endpoint: String,
+    val eventId: String,
+    val eventType: String,
+    val itemId: String,
+    val attempts: Int,
+    val lastError: String,
+    val failedAt: String
+)
+
+@Serializable
+data class WebhookReplayResponse(
+    val delivered: Int
+)
+
+/**
+ * Admin content management routes for the web platform
+ */
+fun Route.adminContentRoutes() {
+    val adminContentService by inject<AdminContentService>()
+    val creatorService by inject<CreatorService>()
+    val contentPackService by inject<ContentPackServiceSimple>()
+    val moderationAnalyticsService by inject<ModerationAnalyticsService>()
+    val webhookDispatcher by inject<ModerationWebhookDispatcher>()
+    val webhookDeadLetters by inject<ModerationWebhookDeadLetterLog>()
+
+    authenticate("admin-jwt") {
+        route("/admin/content") {
+
+            /**
+             * Apply a status transition to all content matching a filter
+             * POST /api/web/v1/admin/content/bulk-status
+             */
+            post("/bulk-status") {
+                try {
+                    val principal = call.principal<JWTPrincipal>()
+                    val adminIdStr = principal?.payload?.getClaim("userId")?.asString()
+                    if (adminIdStr.isNullOrBlank()) {
+                        call.respond(
+                            HttpStatusCode.Unauthorized,
+                            ErrorResponse("invalid_token", "Invalid user ID in token")
+                        )
+                        return@post
+                    }
+                    val permissions = principal?.payload?.getClaim("permissions")
+                        ?.asList(String::class.java) ?: emptyList()
+
+                    val request = call.receive<BulkStatusTransitionRequest>()
+                    val ipAddress = call.request.headers["X-Forwarded-For"]
+                        ?: call.request.headers["X-Real-IP"]
+                        ?: call.request.local.remoteHost
+
+                    val result = adminContentService.bulkTransition(
+                        adminId = UUID.fromString(adminIdStr),
+                        permissions = permissions,
+                        request = request,
+                        ipAddress = ipAddress
+                    )
+
+                    call.respond(HttpStatusCode.OK, result)
+
+                } catch (e: SecurityException) {
+                    call.respond(
+                        HttpStatusCode.Forbidden,
+                        ErrorResponse("insufficient_permissions", e.message)
+                    )
+                } catch (e: IllegalArgumentException) {
+                    call.respond(
+                        HttpStatusCode.BadRequest,
+                        ErrorResponse("validation_error", e.message)
+                    )
+                } catch (e: Exception) {
+                    logger.error(e) { "Error applying bulk content status transition" }
+                    call.respond(
+                        HttpStatusCode.InternalServerError,
+                        ErrorResponse("internal_error", "Failed to apply bulk status transition")
+                    )
+                }
+            }
+
+            /**
+             * Set or clear when a seasonal pack leaves family listings
+             * PUT /api/web/v1/admin/content/packs/{packId}/expiry
+             */
+            put("/packs/{packId}/expiry") {
+                try {
+                    val permissions = call.principal<JWTPrincipal>()?.payload?.getClaim("permissions")
+                        ?.asList(String::class.java) ?: emptyList()
+                    if (AdminPermission.PUBLISH_CONTENT.code !in permissions) {
+                        throw SecurityException("Missing permissions: ${AdminPermission.PUBLISH_CONTENT.code}")
+                    }
+
+                    val packId = UUID.fromString(call.parameters["packId"])
+                    val request = call.receive<PackExpiryRequest>()
+                    val expiresAt = request.expiresAt?.let { Instant.parse(it) }
+
+                    call.respond(HttpStatusCode.OK, contentPackService.setPackExpiry(packId, expiresAt))
+
+                } catch (e: SecurityException) {
+                    call.respond(
+                        HttpStatusCode.Forbidden,
+                        ErrorResponse("insufficient_permissions", e.message)
+                    )
+                } catch (e: NoSuchElementException) {
+                    call.respond(
+                        HttpStatusCode.NotFound,
+                        ErrorResponse("not_found", e.message)
+                    )
+                } catch (e: DateTimeParseException) {
+                    call.respond(
+                        HttpStatusCode.BadRequest,
+                        ErrorResponse("validation_error", "expiresAt must be an ISO-8601 instant")
+                    )
+                } catch (e: IllegalArgumentException) {
+                    call.respond(
+                        HttpStatusCode.BadRequest,
+                        ErrorResponse("validation_error", "Invalid pack ID")
+                    )
+                }
+            }
+        }
+
+        route("/admin/moderation") {
+
+            /**
+             * Rejection reason taxonomy for the moderator UI
+             * GET /api/web/v1/admin/moderation/rejection-reasons
+             */
+            get("/rejection-reasons") {
+                call.respond(HttpStatusCode.OK, RejectionReasonCategory.taxonomy())
+            }
+
+            /**
+             * Possible duplicates flagged when a submission entered review
+             * GET /api/web/v1/admin/moderation/submissions/{itemId}/duplicates
+             */
+            get("/submissions/{itemId}/duplicates") {
+                try {
+                    val itemId = UUID.fromString(call.parameters["itemId"])
+                    call.respond(HttpStatusCode.OK, creatorService.getPossibleDuplicates(itemId))
+                } catch (e: NoSuchElementException) {
+                    call.respond(
+                        HttpStatusCode.NotFound,
+                        ErrorResponse("not_found", e.message)
+                    )
+                } catch (e: IllegalArgumentException) {
+                    call.respond(
+                        HttpStatusCode.BadRequest,
+                        ErrorResponse("validation_error", "Invalid item ID")
+                    )
+                }
+            }
+
+            /**
+             * Check content against the catalog before importing it
+             * POST /api/web/v1/admin/moderation/duplicate-check
+             */
+            post("/duplicate-check") {
+                try {
+                    val request = call.receive<DuplicateCheckRequest>()
+                    require(request.title.isNotBlank()) { "Title is required" }
+                    call.respond(HttpStatusCode.OK, creatorService.checkForDuplicates(request.title, request.body))
+                } catch (e: IllegalArgumentException) {
+                    call.respond(
+                        HttpStatusCode.BadRequest,
+                        ErrorResponse("validation_error", e.message)
+                    )
+                } catch (e: Exception) {
+                    logger.error(e) { "Error checking for duplicate content" }
+                    call.respond(
+                        HttpStatusCode.InternalServerError,
+                        ErrorResponse("internal_error", "Failed to check for duplicates")
+                    )
+                }
+            }
+
+            /**
+             * Lift a creator's embargo early; approved content publishes immediately
+             * POST /api/web/v1/admin/moderation/submissions/{itemId}/embargo/lift
+             */
+            post("/submissions/{itemId}/embargo/lift") {
+                try {
+                    val permissions = call.principal<JWTPrincipal>()?.payload?.getClaim("permissions")
+                        ?.asList(String::class.java) ?: emptyList()
+                    if (AdminPermission.PUBLISH_CONTENT.code !in permissions) {
+                        throw SecurityException("Missing permissions: ${AdminPermission.PUBLISH_CONTENT.code}")
+                    }
+
+                    val itemId = UUID.fromString(call.parameters["itemId"])
+                    call.respond(HttpStatusCode.OK, creatorService.liftEmbargo(itemId))
+
+                } catch (e: SecurityException) {
+                    call.respond(
+                        HttpStatusCode.Forbidden,
+                        ErrorResponse("insufficient_permissions", e.message)
+                    )
+                } catch (e: NoSuchElementException) {
+                    call.respond(
+                        HttpStatusCode.NotFound,
+                        ErrorResponse("not_found", e.message)
+                    )
+                } catch (e: IllegalArgumentException) {
+                    call.respond(
+                        HttpStatusCode.BadRequest,
+                        ErrorResponse("validation_error", e.message)
+                    )
+                }
+            }
+
+            /**
+             * Webhook deliveries that ran out of retries and haven't been replayed, oldest first
+             * GET /api/web/v1/admin/moderation/webhooks/dead-letters?limit=100
+             */
+            get("/webhooks/dead-letters") {
+                try {
+                    call.requireSystemSettings()
+                    val limit = (call.request.queryParameters["limit"]?.toIntOrNull()
+                        ?: ModerationWebhookDispatcher.DEFAULT_REPLAY_LIMIT).coerceIn(1, 500)
+
+                    call.respond(HttpStatusCode.OK, webhookDeadLetters.pending(limit).map {
+                        WebhookDeadLetterResponse(
+                            id = it.id.toString(),
+                            endpoint = it.endpoint.toString(),
+                            eventId = it.event.id,
+                            eventType = it.event.type,
+                            itemId = it.event.itemId,
+                            attempts = it.attempts,
+                            lastError = it.lastError,
+                            failedAt = it.failedAt.toString()
+                        )
+                    })
+
+                } catch (e: SecurityException) {
+                    call.respond(
+                        HttpStatusCode.Forbidden,
+                        ErrorResponse("insufficient_permissions", e.message)
+                    )
+                } catch (e: Exception) {
+                    logger.error(e) { "Error listing moderation webhook dead letters" }
+                    call.respond(
+                        HttpStatusCode.InternalServerError,
+                        ErrorResponse("internal_error", "Failed to list webhook dead letters")
+                    )
+                }
+            }
+
+            /**
+             * Deliver the oldest dead letters again; ones that still fail stay in the log
+             * POST /api/web/v1/admin/moderation/webhooks/dead-letters/replay
+             */
+            post("/webhooks/dead-letters/replay") {
+                try {
+                    call.requireSystemSettings()
+                    call.respond(HttpStatusCode.OK, WebhookReplayResponse(webhookDispatcher.replayDeadLetters()))
+
+                } catch (e: SecurityException) {
+                    call.respond(
+                        HttpStatusCode.Forbidden,
+                        ErrorResponse("insufficient_permissions", e.message)
+                    )
+                } catch (e: Exception) {
+                    logger.error(e) { "Error replaying moderation webhook dead letters" }
+                    call.respond(
+                        HttpStatusCode.InternalServerError,
+                        ErrorResponse("internal_error", "Failed to replay webhook dead letters")
+                    )
+                }
+            }
+
+            /**
+             * Per-moderator throughput and decision quality
+             * GET /api/web/v1/admin/moderation/analytics?from=2025-01-01&to=2025-01-31
+             */
+            get("/analytics") {
+                try {
+                    val permissions = call.principal<JWTPrincipal>()?.payload?.getClaim("permissions")
+                        ?.asList(String::class.java) ?: emptyList()
+                    if (AdminPermission.VIEW_PLATFORM_ANALYTICS.code !in permissions) {
+                        throw SecurityException("Missing permissions: ${AdminPermission.VIEW_PLATFORM_ANALYTICS.code}")
+                    }
+
+                    val to = call.request.queryParameters["to"]?.let { LocalDate.parse(it) }
+                        ?: LocalDate.now(ZoneOffset.UTC)
+                    val from = call.request.queryParameters["from"]?.let { LocalDate.parse(it) }
+                        ?: to.minusDays(29)
+
+                    call.respond(HttpStatusCode.OK, moderationAnalyticsService.workload(from, to))
+
+                } catch (e: SecurityException) {
+                    call.respond(
+                        HttpStatusCode.Forbidden,
+                        ErrorResponse("insufficient_permissions", e.message)
+                    )
+                } catch (e: DateTimeParseException) {
+                    call.respond(
+                        HttpStatusCode.BadRequest,
+                        ErrorResponse("validation_error", "Dates must be formatted as YYYY-MM-DD")
+                    )
+                } catch (e: IllegalArgumentException) {
+                    call.respond(
+                        HttpStatusCode.BadRequest,
+                        ErrorResponse("validation_error", e.message)
+                    )
+                }
+            }
+
+            /**
+             * Submissions awaiting review, highest priority first
+             * GET /api/web/v1/admin/moderation/queue?limit=50
+             */
+            get("/queue") {
+                try {
+                    val moderatorId = call.requireModerator()
+                    val limit = call.request.queryParameters["limit"]?.toIntOrNull()?.coerceIn(1, 200) ?: 50
+                    call.respond(HttpStatusCode.OK, creatorService.getModerationQueue(moderatorId, limit))
+                } catch (e: SecurityException) {
+                    call.respond(
+                        HttpStatusCode.Forbidden,
+                        ErrorResponse("insufficient_permissions", e.message)
+                    )
+                }
+            }
+
+            /**
+             * Claim a submission so no other moderator reviews it at the same time
+             * POST /api/web/v1/admin/moderation/submissions/{itemId}/claim
+             */
+            post("/submissions/{itemId}/claim") {
+                try {
+                    val moderatorId = call.requireModerator()
+                    val itemId = UUID.fromString(call.parameters["itemId"])
+                    call.respond(HttpStatusCode.OK, creatorService.claimSubmission(moderatorId, itemId))
+                } catch (e: SecurityException) {
+                    call.respond(
+                        HttpStatusCode.Forbidden,
+                        ErrorResponse("insufficient_permissions", e.message)
+                    )
+                } catch (e: SubmissionClaimedException) {
+                    call.respond(
+                        HttpStatusCode.Conflict,
+                        ErrorResponse("already_claimed", e.message)
+                    )
+                } catch (e: NoSuchElementException) {
+                    call.respond(
+                        HttpStatusCode.NotFound,
+                        ErrorResponse("not_found", e.message)
+                    )
+                } catch (e: IllegalArgumentException) {
+                    call.respond(
+                        HttpStatusCode.BadRequest,
+                        ErrorResponse("validation_error", e.message)
+                    )
+                }
+            }
+
+            /**
+             * Hand a claimed submission back to the queue
+             * DELETE /api/web/v1/admin/moderation/submissions/{itemId}/claim
+             */
+            delete("/submissions/{itemId}/claim") {
+                try {
+                    val moderatorId = call.requireModerator()
+                    val itemId = UUID.fromString(call.parameters["itemId"])
+                    creatorService.releaseClaim(moderatorId, itemId)
+                    call.respond(HttpStatusCode.NoContent)
+                } catch (e: SecurityException) {
+                    call.respond(
+                        HttpStatusCode.Forbidden,
+                        ErrorResponse("insufficient_permissions", e.message)
+                    )
+                } catch (e: SubmissionClaimedException) {
+                    call.respond(
+                        HttpStatusCode.Conflict,
+                        ErrorResponse("already_claimed", e.message)
+                    )
+                } catch (e: NoSuchElementException) {
+                    call.respond(
+                        HttpStatusCode.NotFound,
+                        ErrorResponse("not_found", e.message)
+                    )
+                } catch (e: IllegalArgumentException) {
+                    call.respond(
+                        HttpStatusCode.BadRequest,
+                        ErrorResponse("validation_error", e.message)
+                    )
+                }
+            }
+
+            /**
+             * Approve or reject a marketplace submission
+             * POST /api/web/v1/admin/moderation/submissions/{itemId}/decision
+             */
+            post("/submissions/{itemId}/decision") {
+                try {
+                    val principal = call.principal<JWTPrincipal>()
+                    val adminIdStr = principal?.payload?.getClaim("userId")?.asString()
+                    if (adminIdStr.isNullOrBlank()) {
+                        call.respond(
+                            HttpStatusCode.Unauthorized,
+                            ErrorResponse("invalid_token", "Invalid user ID in token")
+                        )
+                        return@post
+                    }
+                    val permissions = principal?.payload?.getClaim("permissions")
+                        ?.asList(String::class.java) ?: emptyList()
+                    if (AdminPermission.MODERATE_CONTENT.code !in permissions) {
+                        throw SecurityException("Missing permissions: ${AdminPermission.MODERATE_CONTENT.code}")
+                    }
+
+                    val itemId = UUID.fromString(call.parameters["itemId"])
+                    val request = call.receive<ModerationDecisionRequest>()
+
+                    val result = creatorService.moderateSubmission(UUID.fromString(adminIdStr), itemId, request)
+                    call.respond(HttpStatusCode.OK, result)
+
+                } catch (e: SecurityException) {
+                    call.respond(
+                        HttpStatusCode.Forbidden,
+                        ErrorResponse("insufficient_permissions", e.message)
+                    )
+                } catch (e: SubmissionClaimedException) {
+                    call.respond(
+                        HttpStatusCode.Conflict,
+                        ErrorResponse("already_claimed", e.message)
+                    )
+                } catch (e: NoSuchElementException) {
+                    call.respond(
+                        HttpStatusCode.NotFound,
+                        ErrorResponse("not_found", e.message)
+                    )
+                } catch (e: IllegalArgumentException) {
+                    call.respond(
+                        HttpStatusCode.BadRequest,
+                        ErrorResponse("validation_error", e.message)
+                    )
+                } catch (e: Exception) {
+                    logger.error(e) { "Error applying moderation decision" }
+                    call.respond(
+                        HttpStatusCode.InternalServerError,
+                        ErrorResponse("internal_error", "Failed to apply moderation decision")
+                    )
+                }
+            }
+        }
+    }
+}
+
+/**
+ * The admin's id, provided they hold the content moderation permission
+ */
+private fun ApplicationCall.requireModerator(): UUID {
+    val principal = principal<JWTPrincipal>()
+    val permissions = principal?.payload?.getClaim("permissions")?.asList(String::class.java) ?: emptyList()
+    if (AdminPermission.MODERATE_CONTENT.code !in permissions) {
+        throw SecurityException("Missing permissions: ${AdminPermission.MODERATE_CONTENT.code}")
+    }
+    return principal?.payload?.getClaim("userId")?.asString()?.let { UUID.fromString(it) }
+        ?: throw SecurityException("Invalid user ID in token")
+}
+
+private fun ApplicationCall.requireSystemSettings() {
+    val permissions = principal<JWTPrincipal>()?.payload?.getClaim("permissions")
+        ?.asList(String::class.java) ?: emptyList()
+    if (AdminPermission.MANAGE_SYSTEM_SETTINGS.code !in permissions) {
+        throw SecurityException("Missing permissions: ${AdminPermission.MANAGE_SYSTEM_SETTINGS.code}")
+    }
+}
@@ -0,0 +1,149 @@
+package com.wondernest.api.web.admin
+
+import com.wondernest.domain.web.AdminPermission
+import com.wondernest.services.moderation.HeldChildData
+import com.wondernest.services.moderation.PiiReviewService
+import io.ktor.http.*
+import io.ktor.server.application.*
+import io.ktor.server.auth.*
+import io.ktor.server.auth.jwt.*
+import io.ktor.server.response.*
+import io.ktor.server.routing.*
+import kotlinx.serialization.Serializable
+import kotlinx.serialization.json.JsonElement
+import mu.KotlinLogging
+import org.koin.ktor.ext.inject
+import java.util.*
+
+private val logger = KotlinLogging.logger {}
+
+@Serializable
+data class PiiReviewItemResponse(
+    val id: String,
+    val childId: String,
+    val gameKey: String,
+    val dataKey: String,
+    val target: String, // simple | game_instance
+    val dataValue: JsonElement,
+    val flaggedPaths: List<String>,
+    val heldAt: String
+)
+
+/**
+ * Admin routes for the child data PII review queue, filled when GAME_DATA_PII_ACTION=queue
+ */
+fun Route.adminPiiReviewRoutes() {
+    val piiReviewService by inject<PiiReviewService>()
+
+    authenticate("admin-jwt") {
+        route("/admin/pii-review") {
+
+            /**
+             * Held saves waiting for review, oldest first
+             * GET /api/web/v1/admin/pii-review?limit=50
+             */
+            get {
+                try {
+                    call.requirePiiReviewPermission()
+                    val limit = call.request.queryParameters["limit"]?.toIntOrNull() ?: PiiReviewService.DEFAULT_PAGE_SIZE
+
+                    call.respond(HttpStatusCode.OK, piiReviewService.pending(limit).map { it.data.toResponse(it.id) })
+
+                } catch (e: SecurityException) {
+                    call.respond(
+                        HttpStatusCode.Forbidden,
+                        ErrorResponse("insufficient_permissions", e.message)
+                    )
+                } catch (e: Exception) {
+                    logger.error(e) { "Error listing PII review queue" }
+                    call.respond(
+                        HttpStatusCode.InternalServerError,
+                        ErrorResponse("internal_error", "Failed to list PII review queue")
+                    )
+                }
+            }
+
+            /**
+             * Write a held save where it was going
+             * POST /api/web/v1/admin/pii-review/{id}/release
+             */
+            post("/{id}/release") {
+                call.reviewHeldData("release") { id, reviewerId -> piiReviewService.release(id, reviewerId) }
+            }
+
+            /**
+             * Drop a held save without writing it
+             * POST /api/web/v1/admin/pii-review/{id}/discard
+             */
+            post("/{id}/discard") {
+                call.reviewHeldData("discard") { id, reviewerId -> piiReviewService.discard(id, reviewerId) }
+            }
+        }
+    }
+}
+
+private fun ApplicationCall.requirePiiReviewPermission(): UUID? {
+    val payload = principal<JWTPrincipal>()?.payload
+    val permissions = payload?.getClaim("permissions")?.asList(String::class.java) ?: emptyList()
+    if (AdminPermission.MODERATE_USER_CONTENT.code !in permissions) {
+        throw SecurityException("Missing permissions: ${AdminPermission.MODERATE_USER_CONTENT.code}")
+    }
+    return payload?.getClaim("userId")?.asString()?.let { runCatching { UUID.fromString(it) }.getOrNull() }
+}
+
+private suspend fun ApplicationCall.reviewHeldData(action: String, review: (UUID, UUID) -> HeldChildData?) {
+    try {
+        val reviewerId = requirePiiReviewPermission()
+        if (reviewerId == null) {
+            respond(
+                HttpStatusCode.Unauthorized,
+                ErrorResponse("invalid_token", "Invalid user ID in token")
+            )
+            return
+        }
+        val id = UUID.fromString(parameters["id"])
+
+        val held = review(id, reviewerId)
+            ?: throw NoSuchElementException("No pending review $id")
+        respond(HttpStatusCode.OK, held.toResponse(id))
+
+    } catch (e: SecurityException) {
+        respond(
+            HttpStatusCode.Forbidden,
+            ErrorResponse("insufficient_permissions", e.message)
+        )
+    } catch (e: NoSuchElementException) {
+        respond(
+            HttpStatusCode.NotFound,
+            ErrorResponse("not_found", e.message)
+        )
+    } catch (e: IllegalArgumentException) {
+        respond(
+            HttpStatusCode.BadRequest,
+            ErrorResponse("validation_error", e.message)
+        )
+    } catch (e: IllegalStateException) {
+        // The write was refused (e.g. the game has since been disabled); the entry stays pending
+        respond(
+            HttpStatusCode.Conflict,
+            ErrorResponse("release_failed", e.message)
+        )
+    } catch (e: Exception) {
+        logger.error(e) { "Error trying to $action held child data" }
+        respond(
+            HttpStatusCode.InternalServerError,
+            ErrorResponse("internal_error", "Failed to $action held child data")
+        )
+    }
+}
+
+private fun HeldChildData.toResponse(id: UUID) = PiiReviewItemResponse(
+    id = id.toString(),
+    childId = childId.toString(),
+    gameKey = gameKey,
+    dataKey = dataKey,
+    target = target.dbValue,
+    dataValue = dataValue,
+    flaggedPaths = flaggedPaths,
+    heldAt = heldAt.toString()
+)
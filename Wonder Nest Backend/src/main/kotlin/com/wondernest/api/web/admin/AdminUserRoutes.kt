@@ -0,0 +1,183 @@
+package com.wondernest.api.web.admin
+
+import com.wondernest.domain.web.AdminInvitationRequest
+import com.wondernest.domain.web.AllowedHours
+import com.wondernest.domain.web.CustomPermissions
+import com.wondernest.services.web.admin.AdminAccountService
+import io.ktor.http.*
+import io.ktor.server.application.*
+import io.ktor.server.auth.*
+import io.ktor.server.auth.jwt.*
+import io.ktor.server.request.*
+import io.ktor.server.response.*
+import io.ktor.server.routing.*
+import kotlinx.serialization.Serializable
+import mu.KotlinLogging
+import org.koin.ktor.ext.inject
+import java.util.*
+
+private val logger = KotlinLogging.logger {}
+
+@Serializable
+data class AllowedHoursRequest(
+    val allowedHours: AllowedHours? = null
+)
+
+/**
+ * Admin routes for managing other admin accounts
+ */
+fun Route.adminUserRoutes() {
+    val adminAccountService by inject<AdminAccountService>()
+
+    authenticate("admin-jwt") {
+        route("/admin/users") {
+
+            /**
+             * Invite a new admin; the invitee gets an email with a single-use link
+             * POST /api/web/v1/admin/users/invitations
+             */
+            post("/invitations") {
+                try {
+                    val principal = call.principal<JWTPrincipal>()
+                    val actorIdStr = principal?.payload?.getClaim("userId")?.asString()
+                    if (actorIdStr.isNullOrBlank()) {
+                        call.respond(
+                            HttpStatusCode.Unauthorized,
+                            ErrorResponse("invalid_token", "Invalid user ID in token")
+                        )
+                        return@post
+                    }
+                    val permissions = principal?.payload?.getClaim("permissions")
+                        ?.asList(String::class.java) ?: emptyList()
+                    val request = call.receive<AdminInvitationRequest>()
+
+                    val invitation = adminAccountService.inviteAdmin(permissions, UUID.fromString(actorIdStr), request)
+                    call.respond(HttpStatusCode.Created, invitation)
+
+                } catch (e: SecurityException) {
+                    call.respond(
+                        HttpStatusCode.Forbidden,
+                        ErrorResponse("insufficient_permissions", e.message)
+                    )
+                } catch (e: IllegalArgumentException) {
+                    call.respond(
+                        HttpStatusCode.BadRequest,
+                        ErrorResponse("invalid_request", e.message)
+                    )
+                } catch (e: Exception) {
+                    logger.error(e) { "Error inviting admin" }
+                    call.respond(
+                        HttpStatusCode.InternalServerError,
+                        ErrorResponse("internal_error", "Failed to send invitation")
+                    )
+                }
+            }
+
+            /**
+             * Replace an admin's custom permission grants and denials
+             * PUT /api/web/v1/admin/users/{adminId}/permissions
+             */
+            put("/{adminId}/permissions") {
+                try {
+                    val principal = call.principal<JWTPrincipal>()
+                    val actorIdStr = principal?.payload?.getClaim("userId")?.asString()
+                    if (actorIdStr.isNullOrBlank()) {
+                        call.respond(
+                            HttpStatusCode.Unauthorized,
+                            ErrorResponse("invalid_token", "Invalid user ID in token")
+                        )
+                        return@put
+                    }
+                    val permissions = principal?.payload?.getClaim("permissions")
+                        ?.asList(String::class.java) ?: emptyList()
+                    val adminId = call.parameters["adminId"]?.let { UUID.fromString(it) }
+                        ?: return@put call.respond(
+                            HttpStatusCode.BadRequest,
+                            ErrorResponse("invalid_request", "Invalid admin ID")
+                        )
+                    val request = call.receive<CustomPermissions>()
+
+                    val profile = adminAccountService.updateCustomPermissions(
+                        permissions, UUID.fromString(actorIdStr), adminId, request
+                    )
+                    call.respond(HttpStatusCode.OK, profile)
+
+                } catch (e: SecurityException) {
+                    call.respond(
+                        HttpStatusCode.Forbidden,
+                        ErrorResponse("insufficient_permissions", e.message)
+                    )
+                } catch (e: NoSuchElementException) {
+                    call.respond(
+                        HttpStatusCode.NotFound,
+                        ErrorResponse("not_found", e.message)
+                    )
+                } catch (e: IllegalArgumentException) {
+                    call.respond(
+                        HttpStatusCode.BadRequest,
+                        ErrorResponse("invalid_request", e.message)
+                    )
+                } catch (e: Exception) {
+                    logger.error(e) { "Error updating admin permissions" }
+                    call.respond(
+                        HttpStatusCode.InternalServerError,
+                        ErrorResponse("internal_error", "Failed to update permissions")
+                    )
+                }
+            }
+
+            /**
+             * Restrict when an admin may log in, or lift the restriction with {"allowedHours": null}
+             * PUT /api/web/v1/admin/users/{adminId}/allowed-hours
+             */
+            put("/{adminId}/allowed-hours") {
+                try {
+                    val principal = call.principal<JWTPrincipal>()
+                    val actorIdStr = principal?.payload?.getClaim("userId")?.asString()
+                    if (actorIdStr.isNullOrBlank()) {
+                        call.respond(
+                            HttpStatusCode.Unauthorized,
+                            ErrorResponse("invalid_token", "Invalid user ID in token")
+                        )
+                        return@put
+                    }
+                    val permissions = principal?.payload?.getClaim("permissions")
+                        ?.asList(String::class.java) ?: emptyList()
+                    val adminId = call.parameters["adminId"]?.let { UUID.fromString(it) }
+                        ?: return@put call.respond(
+                            HttpStatusCode.BadRequest,
+                            ErrorResponse("invalid_request", "Invalid admin ID")
+                        )
+                    val request = call.receive<AllowedHoursRequest>()
+
+                    val profile = adminAccountService.updateAllowedHours(
+                        permissions, UUID.fromString(actorIdStr), adminId, request.allowedHours
+                    )
+                    call.respond(HttpStatusCode.OK, profile)
+
+                } catch (e: SecurityException) {
+                    call.respond(
+                        HttpStatusCode.Forbidden,
+                        ErrorResponse("insufficient_permissions", e.message)
+                    )
+                } catch (e: NoSuchElementException) {
+                    call.respond(
+                        HttpStatusCode.NotFound,
+                        ErrorResponse("not_found", e.message)
+                    )
+                } catch (e: IllegalArgumentException) {
+                    call.respond(
+                        HttpStatusCode.BadRequest,
+                        ErrorResponse("invalid_request", e.message)
+                    )
+                } catch (e: Exception) {
+                    logger.error(e) { "Error updating admin allowed hours" }
+                    call.respond(
+                        HttpStatusCode.InternalServerError,
+                        ErrorResponse("internal_error", "Failed to update allowed hours")
+                    )
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,97 @@
+package com.wondernest.config
+
+import io.ktor.http.*
+import io.ktor.server.application.*
+import io.ktor.server.plugins.*
+import io.ktor.server.plugins.callid.*
+import io.ktor.server.response.*
+import kotlinx.serialization.SerialName
+import com.wondernest.services.resilience.RedisUnavailableException
+import kotlinx.serialization.Serializable
+import java.sql.SQLException
+import java.sql.SQLTransientConnectionException
+
+/**
+ * Stable, machine-readable error codes. Clients switch on [name], so entries must not be
+ * renamed once shipped.
+ */
+enum class AppErrorCode(val status: HttpStatusCode, val defaultMessage: String) {
+    VALIDATION_ERROR(HttpStatusCode.BadRequest, "Invalid input"),
+    MALFORMED_REQUEST(HttpStatusCode.BadRequest, "The request could not be read"),
+    UNAUTHORIZED(HttpStatusCode.Unauthorized, "Authentication required"),
+    TOKEN_REVOKED(HttpStatusCode.Unauthorized, "This token has been revoked, sign in again"),
+    SECURITY_ERROR(HttpStatusCode.Forbidden, "Access denied"),
+    NOT_FOUND(HttpStatusCode.NotFound, "The requested resource was not found"),
+    PAYLOAD_TOO_LARGE(HttpStatusCode.PayloadTooLarge, "The request body is too large"),
+    UNSUPPORTED_MEDIA_TYPE(HttpStatusCode.UnsupportedMediaType, "Unsupported content type"),
+    SERVICE_UNAVAILABLE(HttpStatusCode.ServiceUnavailable, "The service is busy, try again shortly"),
+    QUERY_TIMEOUT(HttpStatusCode.ServiceUnavailable, "The request took too long to process"),
+    INTERNAL_ERROR(HttpStatusCode.InternalServerError, "An unexpected error occurred")
+}
+
+/**
+ * An error with a known [code]. Throw it from a route and StatusPages renders it as an [ErrorResponse].
+ */
+class AppError(val code: AppErrorCode, message: String? = null, cause: Throwable? = null) :
+    RuntimeException(message ?: code.defaultMessage, cause) {
+
+    val status: HttpStatusCode get() = code.status
+
+    companion object {
+        private const val QUERY_CANCELED = "57014"
+
+        /**
+         * Classify any exception that reached StatusPages. Request body and parameter
+         * rejections from `call.receive` share the shape of every other error.
+         */
+        fun from(cause: Throwable): AppError = when {
+            cause is AppError -> cause
+            // The pool had no free connection within its acquire timeout; Exposed wraps this
+            generateSequence(cause) { it.cause }.any { it is SQLTransientConnectionException } ->
+                AppError(AppErrorCode.SERVICE_UNAVAILABLE, cause = cause)
+            // Postgres cancelled a statement that ran past statement_timeout
+            generateSequence(cause) { it.cause }.any { it is SQLException && it.sqlState == QUERY_CANCELED } ->
+                AppError(AppErrorCode.QUERY_TIMEOUT, cause = cause)
+            // A fail-closed Redis feature during an outage
+            cause is RedisUnavailableException -> AppError(AppErrorCode.SERVICE_UNAVAILABLE, cause = cause)
+            else -> classify(cause)
+        }
+
+        private fun classify(cause: Throwable): AppError = when (cause) {
+            is UnsupportedMediaTypeException -> AppError(AppErrorCode.UNSUPPORTED_MEDIA_TYPE, cause = cause)
+            is PayloadTooLargeException -> AppError(AppErrorCode.PAYLOAD_TOO_LARGE, cause = cause)
+            is ContentTransformationException -> AppError(AppErrorCode.MALFORMED_REQUEST, cause = cause)
+            is MissingRequestParameterException, is ParameterConversionException ->
+                AppError(AppErrorCode.MALFORMED_REQUEST, cause.message, cause)
+            // Body conversion failures; Ktor's message names internal classes, so it isn't echoed
+            is BadRequestException -> AppError(AppErrorCode.MALFORMED_REQUEST, cause = cause)
+            is NotFoundException -> AppError(AppErrorCode.NOT_FOUND, cause.message, cause)
+            is IllegalArgumentException -> AppError(AppErrorCode.VALIDATION_ERROR, cause.message, cause)
+            // Security failures never explain themselves
+            is SecurityException -> AppError(AppErrorCode.SECURITY_ERROR, cause = cause)
+            is NoSuchElementException -> AppError(AppErrorCode.NOT_FOUND, cause.message, cause)
+            else -> AppError(AppErrorCode.INTERNAL_ERROR, cause = cause)
+        }
+    }
+}
+
+@Serializable
+data class ErrorBody(
+    val code: String,
+    val message: String,
+    // Same value as the X-Request-Id response header, for matching client reports to logs
+    @SerialName("request_id") val requestId: String? = null
+)
+
+/**
+ * `{ "error": { "code": ..., "message": ..., "request_id": ... } }`
+ */
+@Serializable
+data class ErrorResponse(val error: ErrorBody)
+
+suspend fun ApplicationCall.respondAppError(error: AppError) {
+    respond(error.status, ErrorResponse(ErrorBody(error.code.name, error.message ?: error.code.defaultMessage, callId)))
+}
+
+suspend fun ApplicationCall.respondAppError(code: AppErrorCode, message: String? = null) =
+    respondAppError(AppError(code, message))
@@ -1,26 +1,57 @@
 package com.wondernest.config
 
-import com.auth0.jwt.JWT
-import com.auth0.jwt.algorithms.Algorithm
+import com.auth0.jwt.interfaces.JWTVerifier
+import com.auth0.jwt.interfaces.Payload
+import com.wondernest.data.database.repository.web.AdminSessionRepository
 import com.wondernest.services.auth.JwtService
+import com.wondernest.services.auth.TokenBlocklist
+import io.ktor.http.auth.*
 import io.ktor.server.application.*
 import io.ktor.server.auth.*
 import io.ktor.server.auth.jwt.*
+import io.ktor.util.*
 import org.koin.ktor.ext.inject
+import java.util.UUID
+
+private fun JwtService.bearerVerifier(header: HttpAuthHeader): JWTVerifier? =
+    (header as? HttpAuthHeader.Single)
+        ?.takeIf { it.authScheme.equals("Bearer", ignoreCase = true) }
+        ?.let { verifierFor(it.blob) }
+
+/** Set when authentication failed because the token was revoked, so the 401 can say so */
+val TokenRevokedKey = AttributeKey<Unit>("TokenRevoked")
+
+private suspend fun ApplicationCall.isRevoked(tokenBlocklist: TokenBlocklist, payload: Payload): Boolean =
+    tokenBlocklist.isRevoked(payload).also { revoked ->
+        if (revoked) attributes.put(TokenRevokedKey, Unit)
+    }
+
+/**
+ * Admin tokens carry the id of the session they were issued for; once that session is
+ * revoked, logged out or expired the token stops working even before it expires itself.
+ */
+private suspend fun ApplicationCall.hasActiveAdminSession(repository: AdminSessionRepository, payload: Payload): Boolean {
+    val sessionId = payload.getClaim("sessionId").asString()
+        ?.let { runCatching { UUID.fromString(it) }.getOrNull() }
+        ?: return false
+    val session = repository.findById(sessionId) ?: return false
+    val active = session.isActive && !session.isExpired() && session.adminUserId.toString() == payload.getClaim("userId").asString()
+    if (!active) attributes.put(TokenRevokedKey, Unit)
+    return active
+}
 
 fun Application.configureAuthentication() {
     val jwtService by inject<JwtService>()
+    val tokenBlocklist by inject<TokenBlocklist>()
+    val adminSessionRepository by inject<AdminSessionRepository>()
     
     install(Authentication) {
         jwt("auth-jwt") {
             realm = jwtService.realm
-            verifier(
-                JWT
-                    .require(Algorithm.HMAC256(jwtService.secret))
-                    .withIssuer(jwtService.issuer)
-                    .build()
-            )
+            // The token's kid header picks the key, so rotated secrets keep verifying
+            verifier { header -> jwtService.bearerVerifier(header) }
             validate { credential ->
+                if (isRevoked(tokenBlocklist, credential.payload)) return@validate null
                 if (credential.payload.getClaim("userId").asString() != "") {
                     JWTPrincipal(credential.payload)
                 } else {
@@ -31,13 +62,10 @@ fun Application.configureAuthentication() {
         
         jwt("admin-jwt") {
             realm = jwtService.realm
-            verifier(
-                JWT
-                    .require(Algorithm.HMAC256(jwtService.secret))
-                    .withIssuer(jwtService.issuer)
-                    .build()
-            )
+            verifier { header -> jwtService.bearerVerifier(header) }
             validate { credential ->
+                if (isRevoked(tokenBlocklist, credential.payload)) return@validate null
+                if (!hasActiveAdminSession(adminSessionRepository, credential.payload)) return@validate null
                 val role = credential.payload.getClaim("role").asString()
                 if (credential.payload.getClaim("userId").asString() != "" && role == "admin") {
                     JWTPrincipal(credential.payload)
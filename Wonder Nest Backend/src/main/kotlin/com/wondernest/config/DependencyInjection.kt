@@ -40,11 +40,32 @@ fun Application.configureDependencyInjection() {
 val databaseModule = module {
     single { DatabaseFactory() }
     single { RedisCache() }
+    single { com.wondernest.services.resilience.RedisGuard() }
+    single {
+        com.wondernest.services.resilience.IdempotencyService(
+            com.wondernest.services.resilience.RedisIdempotencyStore(),
+            get()
+        )
+    }
 }
 
 val repositoryModule = module {
     single<UserRepository> { UserRepositoryImpl() }
-    single<FamilyRepository> { FamilyRepositoryImpl() }
+    single<FamilyRepository> {
+        FamilyRepositoryImpl { userId -> get<com.wondernest.services.storage.FileAccessController>().invalidateUser(userId) }
+    }
+    single<com.wondernest.domain.repository.TransactionRunner> {
+        com.wondernest.data.database.repository.DatabaseTransactionRunner()
+    }
+    single<com.wondernest.domain.repository.AnalyticsRepository> {
+        com.wondernest.data.database.repository.AnalyticsRepositoryImpl()
+    }
+    single<com.wondernest.domain.repository.MilestoneRepository> {
+        com.wondernest.data.database.repository.MilestoneRepositoryImpl()
+    }
+    single<com.wondernest.domain.repository.ConsentRepository> {
+        com.wondernest.data.database.repository.ConsentRepositoryImpl()
+    }
     
     // Web admin repositories
     single<com.wondernest.data.database.repository.web.AdminUserRepository> { 
@@ -53,6 +74,18 @@ val repositoryModule = module {
     single<com.wondernest.data.database.repository.web.AdminSessionRepository> { 
         com.wondernest.data.database.repository.web.AdminSessionRepositoryImpl() 
     }
+    single<com.wondernest.data.database.repository.web.ContentItemRepository> {
+        com.wondernest.data.database.repository.web.ContentItemRepositoryImpl()
+    }
+    single<com.wondernest.data.database.repository.web.AdminAuditRepository> {
+        com.wondernest.data.database.repository.web.AdminAuditRepositoryImpl()
+    }
+    single<com.wondernest.data.database.repository.web.AdminInvitationRepository> {
+        com.wondernest.data.database.repository.web.AdminInvitationRepositoryImpl()
+    }
+    single<com.wondernest.data.database.repository.web.BackfillProgressRepository> {
+        com.wondernest.data.database.repository.web.BackfillProgressRepositoryImpl()
+    }
     
     // Marketplace repositories
     single<com.wondernest.data.database.repository.marketplace.MarketplaceRepository> {
@@ -89,28 +122,175 @@ val useCaseModule = module {
 
 val serviceModule = module {
     single { JwtService() }
-    single { AuthService(get(), get(), get(), get()) } // userRepository, familyRepository, jwtService, emailService
+    single {
+        AuthService(
+            get(), get(), get(), get(),
+            com.wondernest.services.auth.UserLockoutConfig.fromEnvironment(),
+            com.wondernest.services.auth.EmailVerificationConfig.fromEnvironment(),
+            get(),
+            get(),
+            get(),
+            get()
+        )
+    } // userRepository, familyRepository, jwtService, emailService, lockoutConfig, emailVerificationConfig, creatorTwoFactor, emailNormalizer, tokenBlocklist, transactionRunner
+    single {
+        com.wondernest.services.auth.TokenBlocklist(
+            com.wondernest.services.auth.RedisTokenBlocklistStore(),
+            get(),
+            get<JwtService>().expiresIn
+        )
+    } // store, redisGuard, accessTokenLifetimeMillis
+    single {
+        com.wondernest.utils.EmailNormalizer(com.wondernest.utils.EmailNormalizationConfig.fromEnvironment())
+    }
+    single {
+        com.wondernest.services.security.CreatorTwoFactorService(
+            get(),
+            com.wondernest.services.security.SecretCipher.fromEnvironment(),
+            com.wondernest.services.security.DatabaseCreatorTwoFactorStore
+        )
+    } // twoFactorService, cipher, store
+    single {
+        com.wondernest.services.auth.AuthRateLimiter(
+            com.wondernest.services.auth.AuthRateLimitConfig.fromEnvironment(),
+            com.wondernest.services.auth.RedisAuthAttemptStore(),
+            get()
+        )
+    } // config, store, redisGuard
+    single {
+        com.wondernest.services.auth.ParentPinVerifier(
+            get(),
+            com.wondernest.services.auth.PinLockoutConfig.fromEnvironment(),
+            com.wondernest.services.auth.RedisPinAttemptStore(),
+            get()
+        )
+    } // userRepository, config, store, redisGuard
     single { FamilyService(get()) } // familyRepository
+    single { com.wondernest.api.FamilyContextResolver(get()) } // familyRepository
+    single { com.wondernest.api.content.ContentRecommendationService(get()) } // familyRepository
+    single { com.wondernest.services.family.FamilyDashboardService(get(), get(), get()) } // familyRepository, analyticsRepository, recommendationService
+    single { com.wondernest.services.coppa.ConsentService(get(), get()) } // familyRepository, consentRepository
+    single {
+        com.wondernest.services.coppa.DataCollectionConsentChecker(
+            get(),
+            com.wondernest.services.coppa.RedisConsentCache(),
+            get()
+        )
+    } // consentRepository, cache, redisGuard
+    single { com.wondernest.services.family.ChildPseudonymService() }
     single { EmailService() }
     single { NotificationService() }
     // single { StorageService() }
     
     // File upload services
     single<com.wondernest.services.storage.StorageProvider> { 
-        com.wondernest.services.storage.LocalStorageProvider() 
+        com.wondernest.services.storage.StorageProviderFactory.create(get<Application>()) 
     }
     single { com.wondernest.services.storage.FileValidationService(get<Application>()) }
-    single { com.wondernest.services.storage.FileUploadService(get(), get()) }
+    single { com.wondernest.services.storage.FileAccessController() }
+    single { com.wondernest.services.storage.FileReferenceService() }
+    single { com.wondernest.services.storage.FileUploadService(get(), get(), get()) }
+    // Created at start so a malformed PUBLIC_BASE_URL fails the boot rather than the first request
+    single(createdAtStart = true) {
+        com.wondernest.services.storage.SignedUrlService.fromEnvironment(
+            com.wondernest.services.storage.RedisSignedUrlNonceStore(),
+            get()
+        )
+    } // nonceStore, redisGuard
     
     // Web admin services
-    single { com.wondernest.services.web.admin.AdminAuthService(get(), get(), get()) } // adminUserRepo, adminSessionRepo, jwtService
+    single { com.wondernest.services.security.TwoFactorService() }
+    single {
+        com.wondernest.services.web.admin.AdminAuthService(
+            get(), get(), get(), get(), get(),
+            com.wondernest.services.web.admin.AdminLockoutConfig.fromEnvironment(),
+            get()
+        )
+    } // adminUserRepo, adminSessionRepo, jwtService, twoFactorService, adminAuditRepo, lockoutConfig, emailNormalizer
+    single { com.wondernest.services.web.admin.AdminContentService(get(), get(), get()) } // contentItemRepo, adminAuditRepo, adminUserRepo
+    single {
+        com.wondernest.services.web.admin.BackfillService(
+            listOf(com.wondernest.services.web.admin.UploadedFileNameBackfill()),
+            get(),
+            get()
+        )
+    } // jobs, backfillProgressRepo, adminAuditRepo
+    single { com.wondernest.services.web.admin.AdminAuditService(get()) } // adminAuditRepo
+    single {
+        com.wondernest.services.web.admin.AdminAccountService(get(), get(), get(), get(), get(), get())
+    } // adminUserRepo, adminAuditRepo, adminInvitationRepo, emailService, adminSessionRepo, emailNormalizer
+    
+    // Analytics
+    single { com.wondernest.services.analytics.AnalyticsEventService() }
+    single {
+        com.wondernest.services.analytics.AnalyticsBatchService(
+            get(), get(), get(),
+            com.wondernest.services.analytics.AnalyticsBatchConfig.fromEnvironment(),
+            get()
+        )
+    } // analyticsEventService, childPseudonymService, consentChecker, config, screenTimeService
+    single { com.wondernest.services.analytics.ScreenTimeService(get(), get()) } // familyRepository, analyticsEventService
+    single { com.wondernest.services.analytics.MilestoneService(get(), get()) } // familyRepository, milestoneRepository
+    single { com.wondernest.services.audio.AudioMetricsService(store = com.wondernest.services.audio.DatabaseAudioMetricsStore) }
+    single {
+        com.wondernest.services.coppa.ChildDataDeletionService(get(), get(), audioMetricsService = get())
+    } // storageProvider, fileAccessController, audioMetricsService
+    single {
+        com.wondernest.services.coppa.ChildDataExportService(get(), get(), get(), audioMetricsService = get())
+    } // analyticsEventService, storageProvider, signedUrlService, audioMetricsService
+    single { com.wondernest.services.coppa.ChildDataExportExpiryTask(get()) } // childDataExportService
     
     // Marketplace services
-    single { com.wondernest.services.marketplace.MarketplaceService(get()) }
-    single { com.wondernest.services.marketplace.CreatorService() }
+    single<com.wondernest.services.moderation.ContentReportStore> { com.wondernest.services.moderation.DatabaseContentReportStore }
+    single { com.wondernest.services.moderation.ContentFlagService(store = get()) }
+    single { com.wondernest.services.marketplace.MarketplaceService(get(), get(), get()) } // marketplaceRepo, contentFlagService, creatorService
+    single {
+        com.wondernest.services.moderation.DuplicateDetector(
+            index = com.wondernest.services.moderation.DatabaseContentFingerprintIndex
+        )
+    }
+    single { com.wondernest.services.moderation.PiiReviewService() }
+    single<com.wondernest.services.moderation.ModerationDecisionLog> { com.wondernest.services.moderation.DatabaseModerationDecisionLog }
+    single { com.wondernest.services.moderation.ModerationAnalyticsService(get()) } // decisionLog
+    single<com.wondernest.services.moderation.ModerationWebhookDeadLetterLog> { com.wondernest.services.moderation.DatabaseModerationWebhookDeadLetterLog }
+    single {
+        com.wondernest.services.moderation.ModerationWebhookDispatcher(
+            com.wondernest.services.moderation.ModerationWebhookConfig.fromEnvironment(),
+            get()
+        )
+    } // deadLetters
+    single {
+        com.wondernest.services.moderation.AutomatedContentScanner(
+            com.wondernest.services.moderation.ContentScanConfig.fromEnvironment()
+        )
+    }
+    single<com.wondernest.services.marketplace.CreatorStore> { com.wondernest.services.marketplace.DatabaseCreatorStore }
+    single {
+        com.wondernest.services.marketplace.CreatorService(
+            duplicateDetector = get(),
+            decisionLog = get(),
+            webhooks = get(),
+            contentScanner = get(),
+            queueConfig = com.wondernest.services.marketplace.ModerationQueueConfig.fromEnvironment(),
+            store = get()
+        )
+    }
+    single { com.wondernest.services.marketplace.EmbargoReleaseTask.fromEnvironment(get()) } // creatorService
     
     // Content Pack services - using simplified version temporarily
-    single { ContentPackServiceSimple() }
+    single { ContentPackServiceSimple(get()) }
+    single { com.wondernest.services.ContentPackExpiryTask.fromEnvironment() }
+    single {
+        com.wondernest.services.games.GameDataHistoryPruneTask.fromEnvironment(
+            com.wondernest.services.games.GameDataHistoryService()
+        )
+    }
+    single {
+        com.wondernest.services.coppa.DataRetentionSweepTask(analyticsEventService = get(), audioMetricsService = get())
+    } // analyticsEventService, audioMetricsService
+    single { com.wondernest.services.ContentPackReviewService(get()) }
+    single { com.wondernest.services.ContentPackRatingService(get()) }
+    single { com.wondernest.services.ContentPackBundleService(get()) } // storageProvider
     
     // Game services - temporarily disabled
     // single<GameService> { GameServiceImpl(get(), get(), get(), get()) } // gameRegistryRepo, instanceRepo, dataRepo, sessionRepo
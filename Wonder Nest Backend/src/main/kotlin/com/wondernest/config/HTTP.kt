@@ -2,14 +2,56 @@ package com.wondernest.config
 
 import io.ktor.http.*
 import io.ktor.server.application.*
+import io.ktor.server.config.*
 import io.ktor.server.plugins.cachingheaders.*
 import io.ktor.server.plugins.compression.*
 import io.ktor.server.plugins.conditionalheaders.*
 import io.ktor.server.plugins.cors.routing.*
 import io.ktor.server.plugins.defaultheaders.*
 import io.ktor.server.plugins.forwardedheaders.*
+import java.net.URI
+import java.net.URISyntaxException
 
-fun Application.configureHTTP() {
+/**
+ * Browser origins allowed to call the API with credentials. Comes from CORS_ALLOWED_ORIGINS,
+ * then cors.allowed_origins, then [DEFAULT_ORIGINS]; an empty value counts as unset.
+ */
+data class CorsConfig(val allowedOrigins: List<URI> = DEFAULT_ORIGINS.map(::parseOrigin)) {
+    companion object {
+        val DEFAULT_ORIGINS = listOf("http://localhost:3000", "http://localhost:3001", "http://localhost:8080")
+
+        /**
+         * Throws [IllegalArgumentException] on a malformed origin so a bad deploy fails at startup
+         */
+        fun fromConfig(config: ApplicationConfig, env: Map<String, String> = System.getenv()): CorsConfig {
+            val raw = env["CORS_ALLOWED_ORIGINS"]?.takeIf { it.isNotBlank() }
+                ?: config.propertyOrNull("cors.allowed_origins")?.getString()
+            val origins = raw?.split(",")?.map { it.trim() }?.filter { it.isNotEmpty() }.orEmpty()
+            return if (origins.isEmpty()) CorsConfig() else CorsConfig(origins.map(::parseOrigin))
+        }
+
+        /**
+         * An origin is scheme://host[:port] with nothing after it, e.g. https://admin.wondernest.app
+         */
+        fun parseOrigin(origin: String): URI {
+            val uri = try {
+                URI(origin.trimEnd('/'))
+            } catch (e: URISyntaxException) {
+                throw IllegalArgumentException("Invalid CORS origin '$origin': ${e.message}")
+            }
+            require(uri.scheme == "http" || uri.scheme == "https") {
+                "Invalid CORS origin '$origin': scheme must be http or https"
+            }
+            require(!uri.host.isNullOrEmpty() && uri.userInfo == null) { "Invalid CORS origin '$origin': missing host" }
+            require(uri.rawPath.isNullOrEmpty() && uri.rawQuery == null && uri.rawFragment == null) {
+                "Invalid CORS origin '$origin': must not contain a path, query or fragment"
+            }
+            return uri
+        }
+    }
+}
+
+fun Application.configureHTTP(corsConfig: CorsConfig = CorsConfig.fromConfig(environment.config)) {
     install(CORS) {
         allowMethod(HttpMethod.Options)
         allowMethod(HttpMethod.Get)
@@ -21,9 +63,11 @@ fun Application.configureHTTP() {
         allowHeader(HttpHeaders.ContentType)
         allowHeader("X-Requested-With")
         
-        // Temporarily allow any host to debug the issue
-        anyHost()
-        this@configureHTTP.environment.log.info("CORS: Allowing any host (temporary for debugging)")
+        corsConfig.allowedOrigins.forEach { origin ->
+            val host = if (origin.port == -1) origin.host else "${origin.host}:${origin.port}"
+            allowHost(host, schemes = listOf(origin.scheme))
+        }
+        this@configureHTTP.environment.log.info("CORS: Allowing origins ${corsConfig.allowedOrigins.joinToString()}")
         
         allowCredentials = true
         maxAgeInSeconds = 86400 // 24 hours
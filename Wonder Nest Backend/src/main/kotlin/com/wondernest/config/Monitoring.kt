@@ -7,6 +7,7 @@ import io.ktor.server.plugins.callid.*
 import io.ktor.server.plugins.calllogging.*
 import io.ktor.server.request.*
 import io.ktor.util.*
+import io.micrometer.core.instrument.Metrics
 import io.micrometer.prometheus.PrometheusConfig
 import io.micrometer.prometheus.PrometheusMeterRegistry
 import org.slf4j.event.Level
@@ -21,13 +22,17 @@ fun Application.configureMonitoring() {
     
     install(CallId) {
         header(HttpHeaders.XRequestId)
+        replyToHeader(HttpHeaders.XRequestId)
         generate { UUID.randomUUID().toString() }
+        // Client-supplied ids end up in logs, so anything unusual is replaced with a generated one
         verify { callId: String ->
-            callId.isNotEmpty()
+            callId.length <= MAX_REQUEST_ID_LENGTH && callId.all { it.isLetterOrDigit() || it in "-_." }
         }
     }
     
     val appMicrometerRegistry = PrometheusMeterRegistry(PrometheusConfig.DEFAULT)
+    // Expose metrics recorded outside of routes (e.g. Redis degraded mode) on /metrics
+    Metrics.addRegistry(appMicrometerRegistry)
     
     install(MicrometerMetrics) {
         registry = appMicrometerRegistry
@@ -46,4 +51,6 @@ fun Application.configureMonitoring() {
     }
 }
 
+const val MAX_REQUEST_ID_LENGTH = 128
+
 val MicrometerRegistryKey = AttributeKey<PrometheusMeterRegistry>("MicrometerRegistry")
\ No newline at end of file
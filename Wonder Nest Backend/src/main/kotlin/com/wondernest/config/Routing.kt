@@ -8,20 +8,26 @@ import com.wondernest.api.content.contentRoutes
 import com.wondernest.api.coppa.coppaRoutes
 import com.wondernest.api.family.familyRoutes
 import com.wondernest.api.fileUploadRoutes
+import com.wondernest.api.publicFileRoutes
 import com.wondernest.server.api.fileRoutes
 import com.wondernest.api.games.gameDataRoutes
 import com.wondernest.api.games.enhancedGameRoutes
 import com.wondernest.api.games.storyAdventureRoutes
 import com.wondernest.api.health.healthRoutes
 import com.wondernest.api.marketplace.marketplaceRoutes
+import com.wondernest.api.web.admin.adminAuditRoutes
+import com.wondernest.api.web.admin.adminAuthRoutes
+import com.wondernest.api.web.admin.adminBackfillRoutes
+import com.wondernest.api.web.admin.adminConsentRoutes
+import com.wondernest.api.web.admin.adminPiiReviewRoutes
+import com.wondernest.api.web.admin.adminContentRoutes
+import com.wondernest.api.web.admin.adminUserRoutes
 import com.wondernest.routes.contentPackRoutes
 import io.ktor.http.*
 import io.ktor.server.application.*
-import io.ktor.server.http.content.*
 import io.ktor.server.response.*
 import io.ktor.server.routing.*
 import io.micrometer.prometheus.PrometheusMeterRegistry
-import java.io.File
 
 fun Application.configureRouting() {
     routing {
@@ -66,12 +72,8 @@ fun Application.configureRouting() {
             )
         }
         
-        // Static file serving for uploads
-        staticFiles("/files", File("uploads")) {
-            // Optional: Add CORS headers for file serving
-            default("index.html")
-            enableAutoHeadResponse()
-        }
+        // Public uploads stored locally; private files go through the authenticated or signed routes
+        publicFileRoutes()
         
         // Health and monitoring endpoints
         healthRoutes()
@@ -107,6 +109,17 @@ fun Application.configureRouting() {
             fileRoutes()                // Enhanced file routes with tagging
         }
         
+        // Web platform admin routes
+        route("/api/web/v1") {
+            adminAuthRoutes()
+            adminContentRoutes()
+            adminBackfillRoutes()
+            adminAuditRoutes()
+            adminUserRoutes()
+            adminConsentRoutes()
+            adminPiiReviewRoutes()
+        }
+        
         // AI story generation routes
         aiStoryRoutes()
         
@@ -0,0 +1,28 @@
+package com.wondernest.config
+
+import com.wondernest.services.ContentPackExpiryTask
+import com.wondernest.services.coppa.ChildDataExportExpiryTask
+import com.wondernest.services.coppa.DataRetentionSweepTask
+import com.wondernest.services.games.GameDataHistoryPruneTask
+import com.wondernest.services.marketplace.EmbargoReleaseTask
+import io.ktor.server.application.*
+import org.koin.ktor.ext.inject
+
+/**
+ * Background jobs run in the application scope, so they stop with the server
+ */
+fun Application.configureScheduledTasks() {
+    val contentPackExpiryTask by inject<ContentPackExpiryTask>()
+    val embargoReleaseTask by inject<EmbargoReleaseTask>()
+    val gameDataHistoryPruneTask by inject<GameDataHistoryPruneTask>()
+    val dataRetentionSweepTask by inject<DataRetentionSweepTask>()
+    val childDataExportExpiryTask by inject<ChildDataExportExpiryTask>()
+
+    environment.monitor.subscribe(ApplicationStarted) { application ->
+        contentPackExpiryTask.start(application)
+        embargoReleaseTask.start(application)
+        gameDataHistoryPruneTask.start(application)
+        dataRetentionSweepTask.start(application)
+        childDataExportExpiryTask.start(application)
+    }
+}
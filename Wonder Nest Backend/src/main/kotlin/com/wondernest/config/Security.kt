@@ -2,19 +2,13 @@ package com.wondernest.config
 
 import io.ktor.http.*
 import io.ktor.server.application.*
+import io.ktor.server.plugins.*
 import io.ktor.server.plugins.ratelimit.*
 import io.ktor.server.plugins.statuspages.*
+import io.ktor.server.request.*
 import io.ktor.server.response.*
-import kotlinx.serialization.Serializable
 import kotlin.time.Duration.Companion.seconds
 
-@Serializable
-data class ErrorResponse(
-    val error: String,
-    val message: String,
-    val timestamp: Long = System.currentTimeMillis()
-)
-
 fun Application.configureSecurity() {
     install(RateLimit) {
         // Default rate limit for all endpoints
@@ -22,9 +16,11 @@ fun Application.configureSecurity() {
             rateLimiter(limit = 100, refillPeriod = 60.seconds)
         }
         
-        // Stricter rate limit for authentication endpoints
+        // Coarse per-client cap on authentication endpoints. Login and signup attempts are
+        // additionally limited by AuthRateLimiter, which only counts failed logins.
         register(RateLimitName("auth")) {
-            rateLimiter(limit = 5, refillPeriod = 60.seconds)
+            rateLimiter(limit = 30, refillPeriod = 60.seconds)
+            requestKey { call -> call.request.origin.remoteHost }
         }
         
         // Rate limit for file uploads
@@ -35,47 +31,30 @@ fun Application.configureSecurity() {
 
     install(StatusPages) {
         exception<Throwable> { call, cause ->
-            when (cause) {
-                is IllegalArgumentException -> {
-                    call.respond(
-                        HttpStatusCode.BadRequest,
-                        ErrorResponse("VALIDATION_ERROR", cause.message ?: "Invalid input")
-                    )
-                }
-                is SecurityException -> {
-                    call.respond(
-                        HttpStatusCode.Forbidden,
-                        ErrorResponse("SECURITY_ERROR", "Access denied")
-                    )
-                }
-                is NoSuchElementException -> {
-                    call.respond(
-                        HttpStatusCode.NotFound,
-                        ErrorResponse("NOT_FOUND", cause.message ?: "Resource not found")
-                    )
-                }
-                else -> {
-                    call.application.environment.log.error("Unhandled exception", cause)
-                    call.respond(
-                        HttpStatusCode.InternalServerError,
-                        ErrorResponse("INTERNAL_ERROR", "An unexpected error occurred")
-                    )
-                }
+            val error = AppError.from(cause)
+            if (error.code == AppErrorCode.INTERNAL_ERROR) {
+                call.application.environment.log.error("Unhandled exception", cause)
+            }
+            if (error.code == AppErrorCode.SERVICE_UNAVAILABLE) {
+                call.application.environment.log.warn("Dependency unavailable: ${cause.message}")
+                call.response.header(HttpHeaders.RetryAfter, "1")
             }
+            if (error.code == AppErrorCode.QUERY_TIMEOUT) {
+                call.application.environment.log.warn("Query cancelled by statement_timeout on ${call.request.path()}", cause)
+            }
+            call.respondAppError(error)
         }
         
-        status(HttpStatusCode.NotFound) { call, status ->
-            call.respond(
-                status,
-                ErrorResponse("NOT_FOUND", "The requested resource was not found")
-            )
+        status(HttpStatusCode.NotFound) { call, _ ->
+            call.respondAppError(AppErrorCode.NOT_FOUND)
         }
         
-        status(HttpStatusCode.Unauthorized) { call, status ->
-            call.respond(
-                status,
-                ErrorResponse("UNAUTHORIZED", "Authentication required")
-            )
+        status(HttpStatusCode.Unauthorized) { call, _ ->
+            if (call.attributes.contains(TokenRevokedKey)) {
+                call.respondAppError(AppErrorCode.TOKEN_REVOKED)
+            } else {
+                call.respondAppError(AppErrorCode.UNAUTHORIZED)
+            }
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,40 @@
+package com.wondernest.config
+
+import java.net.InetAddress
+import java.net.InetSocketAddress
+
+/**
+ * Address the HTTP server listens on. The port still comes from ktor.deployment.port;
+ * SERVER_HOST only changes the interface, e.g. 127.0.0.1 behind a reverse proxy.
+ */
+data class ServerBinding(val host: InetAddress, val port: Int) {
+
+    val socketAddress: InetSocketAddress get() = InetSocketAddress(host, port)
+
+    companion object {
+        const val DEFAULT_HOST = "0.0.0.0"
+
+        private val IPV4 = Regex("""(25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)(\.(25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)){3}""")
+
+        /**
+         * Reads SERVER_HOST; an empty value counts as unset
+         */
+        fun hostFromEnvironment(env: Map<String, String> = System.getenv()): InetAddress =
+            parseHost(env["SERVER_HOST"]?.trim()?.takeIf { it.isNotEmpty() } ?: DEFAULT_HOST)
+
+        /**
+         * Only IP literals are accepted so startup never waits on DNS
+         */
+        fun parseHost(host: String): InetAddress {
+            val literal = host.removeSurrounding("[", "]")
+            require(IPV4.matches(literal) || ':' in literal) {
+                "Invalid SERVER_HOST '$host': expected an IPv4 or IPv6 address such as 0.0.0.0 or 127.0.0.1"
+            }
+            return try {
+                InetAddress.getByName(literal)
+            } catch (e: Exception) {
+                throw IllegalArgumentException("Invalid SERVER_HOST '$host': ${e.message}")
+            }
+        }
+    }
+}
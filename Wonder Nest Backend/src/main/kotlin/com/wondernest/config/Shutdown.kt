@@ -0,0 +1,48 @@
+package com.wondernest.config
+
+import com.wondernest.data.database.DatabaseFactory
+import com.wondernest.services.resilience.RedisConnections
+import io.ktor.server.application.*
+import org.koin.ktor.ext.inject
+
+/**
+ * The engine's shutdown hook stops accepting connections and drains in-flight requests
+ * (ktor.deployment.shutdownGracePeriod / shutdownTimeout); once it has, Redis and the
+ * database pool are closed so no transaction is cut off mid-flight.
+ */
+fun Application.configureShutdown() {
+    val databaseFactory by inject<DatabaseFactory>()
+
+    registerShutdownSequence(
+        listOf(
+            "Redis connection" to RedisConnections::close,
+            "database pool" to { databaseFactory.close() }
+        )
+    )
+}
+
+/**
+ * Logs each shutdown stage and runs [resources] in order after the application has stopped.
+ * A failing close is logged and doesn't stop the rest.
+ */
+fun Application.registerShutdownSequence(resources: List<Pair<String, () -> Unit>>) {
+    val log = environment.log
+
+    environment.monitor.subscribe(ApplicationStopPreparing) {
+        log.info("Shutdown requested: no longer accepting connections, draining in-flight requests")
+    }
+    environment.monitor.subscribe(ApplicationStopping) {
+        log.info("In-flight requests finished, stopping application")
+    }
+    environment.monitor.subscribe(ApplicationStopped) {
+        resources.forEach { (name, close) ->
+            try {
+                close()
+                log.info("Closed $name")
+            } catch (e: Exception) {
+                log.error("Failed to close $name", e)
+            }
+        }
+        log.info("Shutdown complete")
+    }
+}
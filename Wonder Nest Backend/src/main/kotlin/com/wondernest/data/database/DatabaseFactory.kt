@@ -7,6 +7,7 @@ import kotlinx.coroutines.Dispatchers
 import kotlinx.coroutines.delay
 import org.jetbrains.exposed.sql.Database
 import org.jetbrains.exposed.sql.SchemaUtils
+import org.jetbrains.exposed.sql.transactions.TransactionManager
 import org.jetbrains.exposed.sql.transactions.experimental.newSuspendedTransaction
 import org.jetbrains.exposed.sql.transactions.transaction
 import org.slf4j.LoggerFactory
@@ -30,13 +31,7 @@ class DatabaseFactory {
             
             username = System.getenv("DB_USERNAME") ?: "wondernest_app"
             password = System.getenv("DB_PASSWORD") ?: "wondernest_secure_password_dev"
-            maximumPoolSize = System.getenv("DB_MAX_POOL_SIZE")?.toIntOrNull() ?: 20
-            minimumIdle = System.getenv("DB_MIN_IDLE")?.toIntOrNull() ?: 5
-            
-            // Connection settings optimized for both Docker and local development
-            connectionTimeout = 30000  // 30 seconds
-            idleTimeout = 600000       // 10 minutes
-            maxLifetime = 1800000      // 30 minutes
+            DatabasePoolConfig.fromEnvironment().applyTo(this)
             leakDetectionThreshold = 60000 // 1 minute
             
             // Connection validation
@@ -65,12 +60,12 @@ class DatabaseFactory {
         // Always run migrations to ensure database is up to date
         val environment = System.getenv("KTOR_ENV") ?: "development"
         logger.info("Running database migrations in $environment mode")
-        runMigrations()
+        runMigrations(config)
         
         logger.info("Database connection initialized successfully")
         logger.info("JDBC URL: ${config.jdbcUrl}")
         logger.info("Username: ${config.username}")
-        logger.info("Max Pool Size: ${config.maximumPoolSize}")
+        logger.info("Max Pool Size: ${config.maximumPoolSize}, acquire timeout: ${config.connectionTimeout}ms")
     }
     
     private fun initializeWithRetry(config: HikariConfig, maxAttempts: Int = 10) {
@@ -158,10 +153,17 @@ class DatabaseFactory {
         }
     }
     
-    private fun runMigrations() {
+    private fun runMigrations(config: HikariConfig) {
         try {
-            val migrationService = MigrationService(dataSource)
-            val migrationsExecuted = migrationService.migrate()
+            // Migrations get their own short-lived pool without the per-request statement timeout
+            val migrationConfig = HikariConfig().also { config.copyStateTo(it) }.apply {
+                poolName = "wondernest-migrations"
+                // Overrides the statement_timeout startup option the copied data source properties carry
+                connectionInitSql = "SET statement_timeout = 0"
+                maximumPoolSize = 2
+                minimumIdle = 0
+            }
+            val migrationsExecuted = HikariDataSource(migrationConfig).use { MigrationService(it).migrate() }
             
             if (migrationsExecuted > 0) {
                 logger.info("Database migrations completed: $migrationsExecuted migrations applied")
@@ -182,8 +184,11 @@ class DatabaseFactory {
         }
     }
     
+    // Joins the caller's transaction when there is one (see TransactionRunner) rather than
+    // committing on its own
     suspend fun <T> dbQuery(block: suspend () -> T): T =
-        newSuspendedTransaction(Dispatchers.IO) { block() }
+        if (TransactionManager.currentOrNull() != null) block()
+        else newSuspendedTransaction(Dispatchers.IO) { block() }
     
     fun close() {
         if (::dataSource.isInitialized) {
@@ -196,6 +201,11 @@ class DatabaseFactory {
         }
     }
     
+    fun checkSchemaVersion(): SchemaVersionCheck {
+        check(::dataSource.isInitialized) { "Database not initialized" }
+        return MigrationService.checkSchemaVersion(MigrationService(dataSource).currentVersion())
+    }
+    
     fun isHealthy(): Boolean {
         return try {
             if (!::dataSource.isInitialized) return false
@@ -0,0 +1,75 @@
+package com.wondernest.data.database
+
+import com.zaxxer.hikari.HikariConfig
+import org.jetbrains.exposed.sql.Database
+import org.jetbrains.exposed.sql.Transaction
+import org.jetbrains.exposed.sql.transactions.transaction
+
+/**
+ * Connection pool sizing and timeouts. [acquireTimeoutMillis] bounds how long a request waits
+ * for a free connection before failing with a 503 instead of queueing behind a saturated pool.
+ * [statementTimeoutMillis] is Postgres' statement_timeout for every pooled connection, so a
+ * runaway query is cancelled server-side; 0 disables it. Known-heavy work such as exports runs
+ * under [heavyStatementTimeoutMillis] through [transactionWithStatementTimeout].
+ */
+data class DatabasePoolConfig(
+    val maxConnections: Int = 20,
+    val minConnections: Int = 5,
+    val acquireTimeoutMillis: Long = 5_000,
+    val idleTimeoutMillis: Long = 600_000,   // 10 minutes
+    val maxLifetimeMillis: Long = 1_800_000, // 30 minutes
+    val statementTimeoutMillis: Long = 5_000,
+    val heavyStatementTimeoutMillis: Long = 120_000
+) {
+    init {
+        require(maxConnections > 0) { "DB_MAX_POOL_SIZE must be positive" }
+        require(minConnections in 0..maxConnections) { "DB_MIN_IDLE must be between 0 and DB_MAX_POOL_SIZE" }
+        // HikariCP's own lower bound
+        require(acquireTimeoutMillis >= 250) { "DB_ACQUIRE_TIMEOUT_MS must be at least 250" }
+        require(statementTimeoutMillis >= 0 && heavyStatementTimeoutMillis >= 0) { "Statement timeouts can't be negative" }
+    }
+
+    fun applyTo(config: HikariConfig) {
+        config.maximumPoolSize = maxConnections
+        config.minimumIdle = minConnections
+        config.connectionTimeout = acquireTimeoutMillis
+        config.idleTimeout = idleTimeoutMillis
+        config.maxLifetime = maxLifetimeMillis
+        // Sent as a startup parameter, so the timeout is part of the connection rather than a
+        // statement Hikari runs after connecting
+        statementTimeoutMillis.takeIf { it > 0 }?.let {
+            config.addDataSourceProperty("options", "-c statement_timeout=$it")
+        }
+    }
+
+    companion object {
+        /**
+         * Reads DB_MAX_POOL_SIZE, DB_MIN_IDLE, DB_ACQUIRE_TIMEOUT_MS, DB_IDLE_TIMEOUT_MS,
+         * DB_MAX_LIFETIME_MS, DB_STATEMENT_TIMEOUT_MS and DB_HEAVY_STATEMENT_TIMEOUT_MS
+         */
+        fun fromEnvironment(env: Map<String, String> = System.getenv()): DatabasePoolConfig {
+            val defaults = DatabasePoolConfig()
+            return DatabasePoolConfig(
+                maxConnections = env["DB_MAX_POOL_SIZE"]?.toIntOrNull() ?: defaults.maxConnections,
+                minConnections = env["DB_MIN_IDLE"]?.toIntOrNull() ?: defaults.minConnections,
+                acquireTimeoutMillis = env["DB_ACQUIRE_TIMEOUT_MS"]?.toLongOrNull() ?: defaults.acquireTimeoutMillis,
+                idleTimeoutMillis = env["DB_IDLE_TIMEOUT_MS"]?.toLongOrNull() ?: defaults.idleTimeoutMillis,
+                maxLifetimeMillis = env["DB_MAX_LIFETIME_MS"]?.toLongOrNull() ?: defaults.maxLifetimeMillis,
+                statementTimeoutMillis = env["DB_STATEMENT_TIMEOUT_MS"]?.toLongOrNull() ?: defaults.statementTimeoutMillis,
+                heavyStatementTimeoutMillis = env["DB_HEAVY_STATEMENT_TIMEOUT_MS"]?.toLongOrNull()
+                    ?: defaults.heavyStatementTimeoutMillis
+            )
+        }
+    }
+}
+
+/**
+ * A transaction whose statements may run for up to [timeoutMillis] (0 for no limit) instead of
+ * the pool's default. SET LOCAL ends with the transaction, so the pooled connection keeps its
+ * normal timeout afterwards.
+ */
+fun <T> transactionWithStatementTimeout(timeoutMillis: Long, db: Database? = null, statement: Transaction.() -> T): T =
+    transaction(db) {
+        exec("SET LOCAL statement_timeout = $timeoutMillis")
+        statement()
+    }
@@ -1,6 +1,7 @@
 package com.wondernest.data.database
 
 import org.flywaydb.core.Flyway
+import org.flywaydb.core.api.MigrationVersion
 import org.flywaydb.core.api.configuration.FluentConfiguration
 import org.slf4j.LoggerFactory
 import javax.sql.DataSource
@@ -109,6 +110,14 @@ class MigrationService(private val dataSource: DataSource) {
         }
     }
     
+    /**
+     * Returns the version of the latest applied migration, or null if none have run
+     */
+    fun currentVersion(): String? {
+        val flyway = createFlyway()
+        return flyway.info().current()?.version?.version
+    }
+    
     fun validate(): Boolean {
         return try {
             val flyway = createFlyway()
@@ -153,4 +162,45 @@ class MigrationService(private val dataSource: DataSource) {
             throw e
         }
     }
-}
\ No newline at end of file
+
+    companion object {
+        /**
+         * Minimum schema version this build expects. Bump this whenever a
+         * migration is added that the code depends on.
+         */
+        const val EXPECTED_MIN_SCHEMA_VERSION = "66"
+
+        fun checkSchemaVersion(
+            appliedVersion: String?,
+            expectedMinimum: String = EXPECTED_MIN_SCHEMA_VERSION
+        ): SchemaVersionCheck {
+            if (appliedVersion == null) {
+                return SchemaVersionCheck(
+                    healthy = false,
+                    appliedVersion = null,
+                    expectedMinimumVersion = expectedMinimum,
+                    message = "No migrations have been applied"
+                )
+            }
+
+            val behind = MigrationVersion.fromVersion(appliedVersion) < MigrationVersion.fromVersion(expectedMinimum)
+            return SchemaVersionCheck(
+                healthy = !behind,
+                appliedVersion = appliedVersion,
+                expectedMinimumVersion = expectedMinimum,
+                message = if (behind) {
+                    "Database schema is behind: applied $appliedVersion, expected at least $expectedMinimum"
+                } else {
+                    "Database schema is up to date"
+                }
+            )
+        }
+    }
+}
+
+data class SchemaVersionCheck(
+    val healthy: Boolean,
+    val appliedVersion: String?,
+    val expectedMinimumVersion: String,
+    val message: String
+)
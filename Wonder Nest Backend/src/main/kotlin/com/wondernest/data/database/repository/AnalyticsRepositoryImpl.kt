@@ -0,0 +1,54 @@
+package com.wondernest.data.database.repository
+
+import com.wondernest.api.analytics.DailyAnalytics
+import com.wondernest.data.database.table.ChildProfiles
+import com.wondernest.data.database.table.DailyChildMetrics
+import com.wondernest.domain.repository.AnalyticsRepository
+import kotlinx.serialization.json.Json
+import org.jetbrains.exposed.sql.*
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.eq
+import org.jetbrains.exposed.sql.transactions.transaction
+import java.util.UUID
+
+class AnalyticsRepositoryImpl : AnalyticsRepository {
+
+    override suspend fun getLatestDailyAnalyticsByFamily(familyId: UUID): Map<UUID, DailyAnalytics> = transaction {
+        val latestDate = DailyChildMetrics.date.max().alias("latest_date")
+        val latest = DailyChildMetrics
+            .slice(DailyChildMetrics.childId, latestDate)
+            .selectAll()
+            .groupBy(DailyChildMetrics.childId)
+            .alias("latest")
+
+        DailyChildMetrics
+            .join(latest, JoinType.INNER, additionalConstraint = {
+                (DailyChildMetrics.childId eq latest[DailyChildMetrics.childId]) and
+                    (DailyChildMetrics.date eq latest[latestDate])
+            })
+            .join(ChildProfiles, JoinType.INNER, DailyChildMetrics.childId, ChildProfiles.id)
+            .select {
+                (ChildProfiles.familyId eq familyId) and (ChildProfiles.isActive eq true) and ChildProfiles.archivedAt.isNull()
+            }
+            .associate { row -> row[DailyChildMetrics.childId].value to row.toDailyAnalytics() }
+    }
+
+    private fun ResultRow.toDailyAnalytics(): DailyAnalytics {
+        val screenTime = this[DailyChildMetrics.totalScreenTimeMinutes]
+        val sessions = this[DailyChildMetrics.contentSessions]
+        val contentTypes = runCatching {
+            Json.decodeFromString<List<String>>(this[DailyChildMetrics.preferredContentTypes])
+        }.getOrDefault(emptyList())
+
+        return DailyAnalytics(
+            date = this[DailyChildMetrics.date].toString(),
+            childId = this[DailyChildMetrics.childId].value.toString(),
+            totalScreenTime = screenTime,
+            contentConsumed = sessions,
+            educationalTime = this[DailyChildMetrics.educationalContentMinutes],
+            averageSessionLength = if (sessions > 0) screenTime / sessions else 0,
+            mostEngagedCategory = contentTypes.firstOrNull() ?: "none",
+            completedActivities = this[DailyChildMetrics.completedContentCount],
+            learningProgress = this[DailyChildMetrics.engagementScore]?.toDouble() ?: 0.0
+        )
+    }
+}
@@ -0,0 +1,73 @@
+package com.wondernest.data.database.repository
+
+import com.wondernest.data.database.table.CoppaConsents
+import com.wondernest.domain.model.ConsentRecord
+import com.wondernest.domain.model.ConsentVerificationStatus
+import com.wondernest.domain.repository.ConsentRepository
+import kotlinx.datetime.Instant
+import mu.KotlinLogging
+import org.jetbrains.exposed.sql.*
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.eq
+import org.jetbrains.exposed.sql.transactions.transaction
+import java.util.UUID
+
+private val logger = KotlinLogging.logger {}
+
+class ConsentRepositoryImpl : ConsentRepository {
+
+    override suspend fun recordConsent(record: ConsentRecord): ConsentRecord = transaction {
+        CoppaConsents.insert {
+            it[id] = record.id
+            it[familyId] = record.familyId
+            it[childId] = record.childId
+            it[parentId] = record.parentId
+            it[consentType] = record.consentType
+            it[granted] = record.granted
+            it[consentText] = record.consentText
+            it[createdAt] = record.createdAt
+            it[revokedAt] = record.revokedAt
+            it[verificationMethod] = record.verificationMethod
+            it[verificationStatus] = record.verificationStatus.name.lowercase()
+            it[expiresAt] = record.expiresAt
+        }
+
+        logger.info { "Recorded ${record.consentType} consent (granted=${record.granted}) for child ${record.childId}" }
+        record
+    }
+
+    override suspend fun getConsentRecordsByFamily(familyId: UUID): List<ConsentRecord> = transaction {
+        CoppaConsents.select { CoppaConsents.familyId eq familyId }
+            .orderBy(CoppaConsents.createdAt)
+            .map { it.toConsentRecord() }
+    }
+
+    override suspend fun getConsentRecordsByChild(childId: UUID): List<ConsentRecord> = transaction {
+        CoppaConsents.select { CoppaConsents.childId eq childId }
+            .orderBy(CoppaConsents.createdAt)
+            .map { it.toConsentRecord() }
+    }
+
+    override suspend fun updateVerification(id: UUID, status: ConsentVerificationStatus, expiresAt: Instant?) {
+        transaction {
+            CoppaConsents.update({ CoppaConsents.id eq id }) {
+                it[verificationStatus] = status.name.lowercase()
+                it[CoppaConsents.expiresAt] = expiresAt
+            }
+        }
+    }
+
+    private fun ResultRow.toConsentRecord() = ConsentRecord(
+        id = this[CoppaConsents.id].value,
+        familyId = this[CoppaConsents.familyId].value,
+        childId = this[CoppaConsents.childId].value,
+        parentId = this[CoppaConsents.parentId].value,
+        consentType = this[CoppaConsents.consentType],
+        granted = this[CoppaConsents.granted],
+        consentText = this[CoppaConsents.consentText],
+        createdAt = this[CoppaConsents.createdAt],
+        revokedAt = this[CoppaConsents.revokedAt],
+        verificationMethod = this[CoppaConsents.verificationMethod],
+        verificationStatus = ConsentVerificationStatus.valueOf(this[CoppaConsents.verificationStatus].uppercase()),
+        expiresAt = this[CoppaConsents.expiresAt]
+    )
+}
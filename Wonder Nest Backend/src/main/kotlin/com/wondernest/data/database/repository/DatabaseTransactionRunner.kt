@@ -0,0 +1,10 @@
+package com.wondernest.data.database.repository
+
+import com.wondernest.domain.repository.TransactionRunner
+import kotlinx.coroutines.Dispatchers
+import org.jetbrains.exposed.sql.transactions.experimental.newSuspendedTransaction
+
+class DatabaseTransactionRunner : TransactionRunner {
+    override suspend fun <T> inTransaction(block: suspend () -> T): T =
+        newSuspendedTransaction(Dispatchers.IO) { block() }
+}
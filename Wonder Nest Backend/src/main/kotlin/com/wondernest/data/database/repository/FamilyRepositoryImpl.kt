@@ -13,7 +13,13 @@ import java.util.UUID
 
 private val logger = KotlinLogging.logger {}
 
-class FamilyRepositoryImpl : FamilyRepository {
+/**
+ * [onMembershipChanged] is called with each user whose family membership was added or removed,
+ * for caches keyed on it (file access decisions)
+ */
+class FamilyRepositoryImpl(
+    private val onMembershipChanged: (userId: UUID) -> Unit = {}
+) : FamilyRepository {
 
     override suspend fun createFamily(family: Family): Family = transaction {
         val familyId = Families.insertAndGetId {
@@ -78,9 +84,13 @@ class FamilyRepositoryImpl : FamilyRepository {
     }
 
     override suspend fun deleteFamily(id: UUID): Boolean = transaction {
+        val members = FamilyMembers.slice(FamilyMembers.userId)
+            .select { FamilyMembers.familyId eq id }
+            .map { it[FamilyMembers.userId].value }
         val deleted = Families.deleteWhere { Families.id eq id }
         if (deleted > 0) {
             logger.info { "Deleted family: $id" }
+            members.forEach(onMembershipChanged)
         }
         deleted > 0
     }
@@ -95,6 +105,7 @@ class FamilyRepositoryImpl : FamilyRepository {
         }
         
         logger.info { "Added family member: ${member.userId} to family ${member.familyId}" }
+        onMembershipChanged(member.userId)
         member
     }
 
@@ -121,6 +132,7 @@ class FamilyRepositoryImpl : FamilyRepository {
         
         if (deleted > 0) {
             logger.info { "Removed family member: $userId from family $familyId" }
+            onMembershipChanged(userId)
         }
         deleted > 0
     }
@@ -200,7 +212,7 @@ class FamilyRepositoryImpl : FamilyRepository {
                         educationalContentOnly = false
                     ),
                     timeRestrictions = TimeRestrictions(
-                        dailyScreenTimeMinutes = 60,
+                        dailyScreenTimeMinutes = row[ChildProfiles.dailyScreenTimeLimitMinutes] ?: 60,
                         bedtimeEnabled = true,
                         bedtimeStart = if (age < 8) "19:00" else "20:00",
                         bedtimeEnd = if (age < 8) "07:00" else "07:30"
@@ -223,9 +235,15 @@ class FamilyRepositoryImpl : FamilyRepository {
             }
     }
 
-    override suspend fun getChildrenByFamily(familyId: UUID): List<ChildProfile> = transaction {
+    override suspend fun getChildrenByFamily(familyId: UUID, includeArchived: Boolean): List<ChildProfile> = transaction {
         ChildProfiles.selectAll()
-            .where { (ChildProfiles.familyId eq familyId) and (ChildProfiles.isActive eq true) and ChildProfiles.archivedAt.isNull() }
+            .where {
+                if (includeArchived) {
+                    (ChildProfiles.familyId eq familyId) and ChildProfiles.deletedAt.isNull()
+                } else {
+                    (ChildProfiles.familyId eq familyId) and (ChildProfiles.isActive eq true) and ChildProfiles.archivedAt.isNull()
+                }
+            }
             .map { row ->
                 val birthDate = row[ChildProfiles.birthDate] // This is already kotlinx.datetime.LocalDate
                 val now = Clock.System.now().toLocalDateTime(TimeZone.UTC).date
@@ -263,7 +281,7 @@ class FamilyRepositoryImpl : FamilyRepository {
                         educationalContentOnly = false
                     ),
                     timeRestrictions = TimeRestrictions(
-                        dailyScreenTimeMinutes = 60,
+                        dailyScreenTimeMinutes = row[ChildProfiles.dailyScreenTimeLimitMinutes] ?: 60,
                         bedtimeEnabled = true,
                         bedtimeStart = if (age < 8) "19:00" else "20:00",
                         bedtimeEnd = if (age < 8) "07:00" else "07:30"
@@ -313,6 +331,33 @@ class FamilyRepositoryImpl : FamilyRepository {
         updated > 0
     }
 
+    override suspend fun getDailyScreenTimeLimit(childId: UUID): Int? = transaction {
+        ChildProfiles.slice(ChildProfiles.dailyScreenTimeLimitMinutes)
+            .select { ChildProfiles.id eq childId }
+            .singleOrNull()
+            ?.get(ChildProfiles.dailyScreenTimeLimitMinutes)
+    }
+
+    override suspend fun setDailyScreenTimeLimit(childId: UUID, minutes: Int?): Boolean = transaction {
+        val updated = ChildProfiles.update({ ChildProfiles.id eq childId }) {
+            it[dailyScreenTimeLimitMinutes] = minutes
+            it[updatedAt] = Clock.System.now()
+        }
+        updated > 0
+    }
+
+    override suspend fun restoreChildProfile(id: UUID): Boolean = transaction {
+        val updated = ChildProfiles.update({ (ChildProfiles.id eq id) and ChildProfiles.deletedAt.isNull() }) {
+            it[isActive] = true
+            it[archivedAt] = null
+        }
+
+        if (updated > 0) {
+            logger.info { "Restored child profile: $id" }
+        }
+        updated > 0
+    }
+
     override suspend fun deleteChildProfile(id: UUID): Boolean = transaction {
         val deleted = ChildProfiles.deleteWhere { ChildProfiles.id eq id }
         if (deleted > 0) {
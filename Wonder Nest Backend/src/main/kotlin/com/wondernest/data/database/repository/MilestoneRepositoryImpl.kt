@@ -0,0 +1,64 @@
+package com.wondernest.data.database.repository
+
+import com.wondernest.data.database.table.Milestones
+import com.wondernest.domain.model.AchievedMilestone
+import com.wondernest.domain.model.MilestoneDefinition
+import com.wondernest.domain.repository.MilestoneRepository
+import kotlinx.datetime.Instant
+import mu.KotlinLogging
+import org.jetbrains.exposed.sql.*
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.eq
+import org.jetbrains.exposed.sql.transactions.transaction
+import java.util.UUID
+
+private val logger = KotlinLogging.logger {}
+
+class MilestoneRepositoryImpl : MilestoneRepository {
+
+    override suspend fun getAchievedMilestones(childId: UUID): List<AchievedMilestone> = transaction {
+        Milestones.select { (Milestones.childId eq childId) and (Milestones.achieved eq true) }
+            .map { it.toAchievedMilestone() }
+    }
+
+    override suspend fun markAchieved(
+        childId: UUID,
+        milestone: MilestoneDefinition,
+        achievedAt: Instant,
+        childAgeMonths: Int
+    ): AchievedMilestone = transaction {
+        // The unique (child_id, milestone_key) index makes a concurrent second mark a no-op
+        Milestones.insertIgnore {
+            it[Milestones.childId] = childId
+            it[milestoneKey] = milestone.key
+            it[milestoneType] = milestone.category
+            it[milestoneName] = milestone.name
+            it[description] = milestone.description
+            it[typicalAgeMonthsMin] = milestone.ageMonthsMin
+            it[typicalAgeMonthsMax] = milestone.ageMonthsMax
+            it[achieved] = true
+            it[Milestones.achievedAt] = achievedAt
+            it[childAgeMonthsAtAchievement] = childAgeMonths
+            it[evidenceSource] = "parent_report"
+        }
+
+        val row = Milestones.select { (Milestones.childId eq childId) and (Milestones.milestoneKey eq milestone.key) }.single()
+        if (!row[Milestones.achieved]) {
+            Milestones.update({ Milestones.id eq row[Milestones.id] }) {
+                it[achieved] = true
+                it[Milestones.achievedAt] = achievedAt
+                it[childAgeMonthsAtAchievement] = childAgeMonths
+                it[updatedAt] = achievedAt
+            }
+            logger.info { "Milestone ${milestone.key} achieved for child $childId" }
+            return@transaction AchievedMilestone(childId, milestone.key, achievedAt, childAgeMonths)
+        }
+        row.toAchievedMilestone()
+    }
+
+    private fun ResultRow.toAchievedMilestone() = AchievedMilestone(
+        childId = this[Milestones.childId].value,
+        key = this[Milestones.milestoneKey],
+        achievedAt = this[Milestones.achievedAt] ?: this[Milestones.updatedAt],
+        childAgeMonths = this[Milestones.childAgeMonthsAtAchievement] ?: 0
+    )
+}
@@ -5,14 +5,17 @@ import com.wondernest.data.database.table.*
 import com.wondernest.domain.model.User
 import com.wondernest.domain.model.UserSession
 import com.wondernest.domain.model.PasswordResetToken
+import com.wondernest.domain.model.EmailVerificationToken
 import com.wondernest.domain.repository.UserRepository
 import org.jetbrains.exposed.sql.*
 import org.jetbrains.exposed.sql.SqlExpressionBuilder.eq
 import org.jetbrains.exposed.sql.SqlExpressionBuilder.greater
 import org.jetbrains.exposed.sql.SqlExpressionBuilder.less
 import org.jetbrains.exposed.sql.SqlExpressionBuilder.plus
+import org.jetbrains.exposed.sql.statements.StatementType
 import java.util.*
 import kotlinx.datetime.Clock
+import kotlinx.datetime.Instant
 import java.security.MessageDigest
 import kotlinx.serialization.json.Json
 import kotlinx.serialization.decodeFromString
@@ -119,6 +122,52 @@ class UserRepositoryImpl : UserRepository {
         } > 0
     }
 
+    override suspend fun updateFailedLoginAttempts(userId: UUID, attempts: Int): Boolean = db.dbQuery {
+        Users.update({ Users.id eq userId }) {
+            it[failedLoginAttempts] = attempts
+            if (attempts == 0) it[lockedUntil] = null
+        } > 0
+    }
+
+    override suspend fun incrementFailedLoginAttempts(userId: UUID, now: Instant): Int? = db.dbQuery {
+        // One statement, so concurrent failures each add to the stored count instead of all
+        // writing the same value read before them
+        exec(
+            """
+            UPDATE core.users SET
+                failed_login_attempts = CASE WHEN locked_until <= ? THEN 1 ELSE failed_login_attempts + 1 END,
+                locked_until = CASE WHEN locked_until <= ? THEN NULL ELSE locked_until END
+            WHERE id = ?
+            RETURNING failed_login_attempts
+            """.trimIndent(),
+            listOf(
+                Users.lockedUntil.columnType to now,
+                Users.lockedUntil.columnType to now,
+                UUIDColumnType() to userId
+            ),
+            explicitStatementType = StatementType.SELECT
+        ) { rs -> if (rs.next()) rs.getInt(1) else null }
+    }
+
+    override suspend fun lockUser(userId: UUID, lockedUntil: Instant): Boolean = db.dbQuery {
+        Users.update({ Users.id eq userId }) {
+            it[Users.lockedUntil] = lockedUntil
+        } > 0
+    }
+
+    override suspend fun getUserPinHash(userId: UUID): String? = db.dbQuery {
+        Users.select { Users.id eq userId and Users.isActive }
+            .map { it[Users.pinHash] }
+            .singleOrNull()
+    }
+
+    override suspend fun setUserPinIfUnset(userId: UUID, pinHash: String): Boolean = db.dbQuery {
+        Users.update({ (Users.id eq userId) and Users.pinHash.isNull() }) {
+            it[Users.pinHash] = pinHash
+            it[updatedAt] = Clock.System.now()
+        } > 0
+    }
+
     // Session management
     override suspend fun createSession(session: UserSession): UserSession = db.dbQuery {
         UserSessions.insert {
@@ -191,6 +240,43 @@ class UserRepositoryImpl : UserRepository {
         }
     }
 
+    override suspend fun createEmailVerificationToken(
+        userId: UUID,
+        token: String,
+        expiresAt: Instant
+    ): EmailVerificationToken = db.dbQuery {
+        val now = Clock.System.now()
+        val id = EmailVerificationTokens.insertAndGetId {
+            it[EmailVerificationTokens.userId] = userId
+            it[tokenHash] = hashToken(token)
+            it[EmailVerificationTokens.expiresAt] = expiresAt
+            it[createdAt] = now
+        }.value
+        EmailVerificationToken(id = id, userId = userId, expiresAt = expiresAt, createdAt = now)
+    }
+
+    override suspend fun getEmailVerificationToken(token: String): EmailVerificationToken? = db.dbQuery {
+        EmailVerificationTokens.select { EmailVerificationTokens.tokenHash eq hashToken(token) }
+            .map {
+                EmailVerificationToken(
+                    id = it[EmailVerificationTokens.id].value,
+                    userId = it[EmailVerificationTokens.userId].value,
+                    expiresAt = it[EmailVerificationTokens.expiresAt],
+                    usedAt = it[EmailVerificationTokens.usedAt],
+                    createdAt = it[EmailVerificationTokens.createdAt]
+                )
+            }
+            .singleOrNull()
+    }
+
+    override suspend fun markEmailVerificationTokenUsed(tokenId: UUID, usedAt: Instant): Boolean = db.dbQuery {
+        EmailVerificationTokens.update({
+            (EmailVerificationTokens.id eq tokenId) and EmailVerificationTokens.usedAt.isNull()
+        }) {
+            it[EmailVerificationTokens.usedAt] = usedAt
+        } > 0
+    }
+
     override suspend fun searchUsers(query: String, limit: Int): List<User> = db.dbQuery {
         Users.select { 
             (Users.email like "%$query%") or 
@@ -233,7 +319,9 @@ class UserRepositoryImpl : UserRepository {
         parentalConsentVerified = false,  // Default value
         parentalConsentMethod = null,  // Default value
         parentalConsentDate = null,  // Default value
-        deletedAt = null  // Using isActive instead of deletedAt
+        deletedAt = null,  // Using isActive instead of deletedAt
+        failedLoginAttempts = row[Users.failedLoginAttempts],
+        lockedUntil = row[Users.lockedUntil]
     )
 
     private fun rowToUserSession(row: ResultRow) = UserSession(
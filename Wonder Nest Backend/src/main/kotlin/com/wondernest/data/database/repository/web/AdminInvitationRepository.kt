@@ -0,0 +1,7 @@
+package com.wondernest.data.database.repository.web
+
+import com.wondernest.domain.web.AdminInvitation
+
+interface AdminInvitationRepository {
+    suspend fun create(invitation: AdminInvitation): AdminInvitation
+}
@@ -0,0 +1,25 @@
+package com.wondernest.data.database.repository.web
+
+import com.wondernest.data.database.table.web.AdminInvitations
+import com.wondernest.domain.web.AdminInvitation
+import kotlinx.coroutines.Dispatchers
+import kotlinx.datetime.toKotlinInstant
+import org.jetbrains.exposed.sql.insert
+import org.jetbrains.exposed.sql.transactions.experimental.newSuspendedTransaction
+
+class AdminInvitationRepositoryImpl : AdminInvitationRepository {
+
+    override suspend fun create(invitation: AdminInvitation): AdminInvitation = newSuspendedTransaction(Dispatchers.IO) {
+        AdminInvitations.insert {
+            it[id] = invitation.id
+            it[email] = invitation.email
+            it[role] = invitation.role.name.lowercase()
+            it[tokenHash] = invitation.tokenHash
+            it[invitedBy] = invitation.invitedBy
+            it[expiresAt] = invitation.expiresAt.toKotlinInstant()
+            it[acceptedAt] = invitation.acceptedAt?.toKotlinInstant()
+            it[createdAt] = invitation.createdAt.toKotlinInstant()
+        }
+        invitation
+    }
+}
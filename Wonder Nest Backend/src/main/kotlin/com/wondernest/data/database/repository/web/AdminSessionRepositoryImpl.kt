@@ -1,57 +1,101 @@
 package com.wondernest.data.database.repository.web
 
+import com.wondernest.data.database.table.web.AdminSessions
 import com.wondernest.domain.web.AdminSession
+import kotlinx.coroutines.Dispatchers
+import kotlinx.datetime.toJavaInstant
+import kotlinx.datetime.toKotlinInstant
+import org.jetbrains.exposed.sql.*
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.eq
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.less
+import org.jetbrains.exposed.sql.transactions.experimental.newSuspendedTransaction
 import java.time.Instant
 import java.util.*
 
 /**
- * Implementation of AdminSessionRepository
- * TODO: Implement actual database operations
+ * Admin sessions in web_admin.admin_sessions
  */
 class AdminSessionRepositoryImpl : AdminSessionRepository {
-    
-    override suspend fun create(session: AdminSession): AdminSession {
-        // TODO: Implement database insert
-        return session
-    }
-    
-    override suspend fun findById(id: UUID): AdminSession? {
-        // TODO: Implement database query
-        return null
-    }
-    
-    override suspend fun findByToken(token: String): AdminSession? {
-        // TODO: Implement database query
-        return null
-    }
-    
-    override suspend fun findActiveSessionsForUser(adminUserId: UUID): List<AdminSession> {
-        // TODO: Implement database query
-        return emptyList()
-    }
-    
-    override suspend fun updateLastActivity(id: UUID, lastActivity: Instant): Boolean {
-        // TODO: Implement database update
-        return true
-    }
-    
-    override suspend fun deactivateSession(id: UUID): Boolean {
-        // TODO: Implement database update
-        return true
-    }
-    
-    override suspend fun deactivateAllUserSessions(adminUserId: UUID): Int {
-        // TODO: Implement database update
-        return 0
-    }
-    
-    override suspend fun deleteExpiredSessions(): Int {
-        // TODO: Implement database delete
-        return 0
-    }
-    
-    override suspend fun findByAdminUserId(adminUserId: UUID): List<AdminSession> {
-        // TODO: Implement database query
-        return emptyList()
-    }
-}
\ No newline at end of file
+
+    override suspend fun create(session: AdminSession): AdminSession = newSuspendedTransaction(Dispatchers.IO) {
+        AdminSessions.insert {
+            it[id] = session.id
+            it[adminUserId] = session.adminUserId
+            it[sessionToken] = session.sessionToken
+            it[refreshToken] = session.refreshToken
+            it[ipAddress] = session.ipAddress
+            it[userAgent] = session.userAgent
+            it[deviceFingerprint] = session.deviceFingerprint
+            it[expiresAt] = session.expiresAt.toKotlinInstant()
+            it[lastActivity] = session.lastActivity.toKotlinInstant()
+            it[isActive] = session.isActive
+            it[createdAt] = session.createdAt.toKotlinInstant()
+        }
+        session
+    }
+
+    override suspend fun findById(id: UUID): AdminSession? = newSuspendedTransaction(Dispatchers.IO) {
+        AdminSessions.select { AdminSessions.id eq id }.singleOrNull()?.toAdminSession()
+    }
+
+    // Matches either token, so logout works with the access session token and refresh with the refresh token
+    override suspend fun findByToken(token: String): AdminSession? = newSuspendedTransaction(Dispatchers.IO) {
+        AdminSessions.select { (AdminSessions.sessionToken eq token) or (AdminSessions.refreshToken eq token) }
+            .singleOrNull()?.toAdminSession()
+    }
+
+    override suspend fun findByAdminUserId(adminUserId: UUID): List<AdminSession> = newSuspendedTransaction(Dispatchers.IO) {
+        AdminSessions.select { AdminSessions.adminUserId eq adminUserId }
+            .orderBy(AdminSessions.createdAt, SortOrder.DESC)
+            .map { it.toAdminSession() }
+    }
+
+    override suspend fun findActiveSessionsForUser(adminUserId: UUID): List<AdminSession> = newSuspendedTransaction(Dispatchers.IO) {
+        val now = Instant.now().toKotlinInstant()
+        AdminSessions.select {
+            (AdminSessions.adminUserId eq adminUserId) and
+                (AdminSessions.isActive eq true) and
+                (AdminSessions.expiresAt greater now)
+        }
+            .orderBy(AdminSessions.lastActivity, SortOrder.DESC)
+            .map { it.toAdminSession() }
+    }
+
+    override suspend fun updateLastActivity(id: UUID, lastActivity: Instant): Boolean = newSuspendedTransaction(Dispatchers.IO) {
+        AdminSessions.update({ AdminSessions.id eq id }) {
+            it[AdminSessions.lastActivity] = lastActivity.toKotlinInstant()
+        } > 0
+    }
+
+    // Only counts sessions that were still active, so revoking twice reports false the second time
+    override suspend fun deactivateSession(id: UUID): Boolean = newSuspendedTransaction(Dispatchers.IO) {
+        AdminSessions.update({ (AdminSessions.id eq id) and (AdminSessions.isActive eq true) }) {
+            it[isActive] = false
+        } > 0
+    }
+
+    override suspend fun deactivateAllUserSessions(adminUserId: UUID): Int = newSuspendedTransaction(Dispatchers.IO) {
+        AdminSessions.update({ (AdminSessions.adminUserId eq adminUserId) and (AdminSessions.isActive eq true) }) {
+            it[isActive] = false
+        }
+    }
+
+    override suspend fun deleteExpiredSessions(): Int = newSuspendedTransaction(Dispatchers.IO) {
+        val now = Instant.now().toKotlinInstant()
+        AdminSessions.deleteWhere { (AdminSessions.expiresAt less now) or (AdminSessions.isActive eq false) }
+    }
+
+    private fun ResultRow.toAdminSession() = AdminSession(
+        id = this[AdminSessions.id].value,
+        adminUserId = this[AdminSessions.adminUserId],
+        sessionToken = this[AdminSessions.sessionToken],
+        refreshToken = this[AdminSessions.refreshToken],
+        ipAddress = this[AdminSessions.ipAddress],
+        userAgent = this[AdminSessions.userAgent],
+        deviceFingerprint = this[AdminSessions.deviceFingerprint],
+        expiresAt = this[AdminSessions.expiresAt].toJavaInstant(),
+        lastActivity = this[AdminSessions.lastActivity].toJavaInstant(),
+        isActive = this[AdminSessions.isActive],
+        createdAt = this[AdminSessions.createdAt].toJavaInstant()
+    )
+}
@@ -16,7 +16,22 @@ interface AdminUserRepository {
     suspend fun update(adminUser: AdminUser): AdminUser
     suspend fun updateLastLogin(id: UUID, lastLoginAt: Instant): Boolean
     suspend fun updateFailedLoginAttempts(id: UUID, attempts: Int): Boolean
+    /**
+     * Add one failed attempt in the database and return the new count, starting again from one
+     * once an earlier lock has expired. Null when the admin doesn't exist.
+     */
+    suspend fun incrementFailedLoginAttempts(id: UUID, now: Instant): Int?
     suspend fun lockUser(id: UUID, lockedUntil: Instant): Boolean
+    /**
+     * Record [step] as the last accepted TOTP step. Returns false, changing nothing, when a
+     * code for this step or a later one was already accepted.
+     */
+    suspend fun recordTotpStep(id: UUID, step: Long): Boolean
+    /**
+     * Remove the backup code with [codeHash] and return how many codes are left, or null,
+     * changing nothing, when no unused code has that hash
+     */
+    suspend fun consumeBackupCode(id: UUID, codeHash: String, now: Instant): Int?
     suspend fun findByRole(role: AdminRole): List<AdminUser>
     suspend fun findActiveAdmins(): List<AdminUser>
 }
@@ -1,60 +1,169 @@
 package com.wondernest.data.database.repository.web
 
+import com.wondernest.data.database.table.web.AdminUsers
 import com.wondernest.domain.web.AdminUser
 import com.wondernest.domain.web.AdminRole
+import kotlinx.coroutines.Dispatchers
+import kotlinx.datetime.toJavaInstant
+import kotlinx.datetime.toKotlinInstant
+import org.jetbrains.exposed.sql.*
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.eq
+import org.jetbrains.exposed.sql.statements.StatementType
+import org.jetbrains.exposed.sql.statements.UpdateBuilder
+import org.jetbrains.exposed.sql.transactions.experimental.newSuspendedTransaction
 import java.time.Instant
 import java.util.*
 
 /**
- * Implementation of AdminUserRepository
- * TODO: Implement actual database operations
+ * Admin accounts in web_admin.admin_users
  */
 class AdminUserRepositoryImpl : AdminUserRepository {
-    
-    override suspend fun findById(id: UUID): AdminUser? {
-        // TODO: Implement database query
-        return null
-    }
-    
-    override suspend fun findByEmail(email: String): AdminUser? {
-        // TODO: Implement database query
-        // For now, return null to prevent authentication
-        return null
-    }
-    
-    override suspend fun create(user: AdminUser): AdminUser {
-        // TODO: Implement database insert
-        return user
-    }
-    
-    override suspend fun update(adminUser: AdminUser): AdminUser {
-        // TODO: Implement database update
-        return adminUser
-    }
-    
-    
-    override suspend fun updateLastLogin(id: UUID, lastLoginAt: Instant): Boolean {
-        // TODO: Implement database update
-        return true
-    }
-    
-    override suspend fun updateFailedLoginAttempts(id: UUID, attempts: Int): Boolean {
-        // TODO: Implement database update
-        return true
-    }
-    
-    override suspend fun lockUser(id: UUID, lockedUntil: Instant): Boolean {
-        // TODO: Implement database update
-        return true
-    }
-    
-    override suspend fun findByRole(role: AdminRole): List<AdminUser> {
-        // TODO: Implement database query
-        return emptyList()
-    }
-    
-    override suspend fun findActiveAdmins(): List<AdminUser> {
-        // TODO: Implement database query
-        return emptyList()
-    }
-}
\ No newline at end of file
+
+    override suspend fun findById(id: UUID): AdminUser? = newSuspendedTransaction(Dispatchers.IO) {
+        AdminUsers.select { AdminUsers.id eq id }.singleOrNull()?.toAdminUser()
+    }
+
+    override suspend fun findByEmail(email: String): AdminUser? = newSuspendedTransaction(Dispatchers.IO) {
+        AdminUsers.select { AdminUsers.email eq email }.singleOrNull()?.toAdminUser()
+    }
+
+    override suspend fun create(adminUser: AdminUser): AdminUser = newSuspendedTransaction(Dispatchers.IO) {
+        AdminUsers.insert {
+            it[id] = adminUser.id
+            it[createdBy] = adminUser.createdBy
+            it[createdAt] = adminUser.createdAt.toKotlinInstant()
+            it.writeFields(adminUser)
+        }
+        adminUser
+    }
+
+    // Returns the row as stored, so callers see exactly what was persisted
+    override suspend fun update(adminUser: AdminUser): AdminUser = newSuspendedTransaction(Dispatchers.IO) {
+        AdminUsers.update({ AdminUsers.id eq adminUser.id }) { it.writeFields(adminUser) }
+        AdminUsers.select { AdminUsers.id eq adminUser.id }.singleOrNull()?.toAdminUser()
+            ?: throw NoSuchElementException("Admin user ${adminUser.id} not found")
+    }
+
+    override suspend fun updateLastLogin(id: UUID, lastLoginAt: Instant): Boolean = newSuspendedTransaction(Dispatchers.IO) {
+        AdminUsers.update({ AdminUsers.id eq id }) {
+            it[AdminUsers.lastLoginAt] = lastLoginAt.toKotlinInstant()
+        } > 0
+    }
+
+    override suspend fun updateFailedLoginAttempts(id: UUID, attempts: Int): Boolean = newSuspendedTransaction(Dispatchers.IO) {
+        AdminUsers.update({ AdminUsers.id eq id }) {
+            it[failedLoginAttempts] = attempts
+        } > 0
+    }
+
+    // One statement, so concurrent failures each count instead of all writing the same stale value
+    override suspend fun incrementFailedLoginAttempts(id: UUID, now: Instant): Int? = newSuspendedTransaction(Dispatchers.IO) {
+        exec(
+            """
+            UPDATE web_admin.admin_users SET
+                failed_login_attempts = CASE WHEN locked_until <= ? THEN 1 ELSE COALESCE(failed_login_attempts, 0) + 1 END,
+                locked_until = CASE WHEN locked_until <= ? THEN NULL ELSE locked_until END
+            WHERE id = ?
+            RETURNING failed_login_attempts
+            """.trimIndent(),
+            listOf(
+                AdminUsers.lockedUntil.columnType to now.toKotlinInstant(),
+                AdminUsers.lockedUntil.columnType to now.toKotlinInstant(),
+                UUIDColumnType() to id
+            ),
+            explicitStatementType = StatementType.SELECT
+        ) { rs -> if (rs.next()) rs.getInt(1) else null }
+    }
+
+    override suspend fun lockUser(id: UUID, lockedUntil: Instant): Boolean = newSuspendedTransaction(Dispatchers.IO) {
+        AdminUsers.update({ AdminUsers.id eq id }) {
+            it[AdminUsers.lockedUntil] = lockedUntil.toKotlinInstant()
+        } > 0
+    }
+
+    // Conditional update, so two requests racing with the same code can't both succeed
+    override suspend fun recordTotpStep(id: UUID, step: Long): Boolean = newSuspendedTransaction(Dispatchers.IO) {
+        AdminUsers.update({
+            (AdminUsers.id eq id) and
+                (AdminUsers.twoFactorLastUsedStep.isNull() or (AdminUsers.twoFactorLastUsedStep less step))
+        }) {
+            it[twoFactorLastUsedStep] = step
+        } > 0
+    }
+
+    // Removes the hash only if it is still there, so a code can't be spent by two logins at once
+    override suspend fun consumeBackupCode(id: UUID, codeHash: String, now: Instant): Int? = newSuspendedTransaction(Dispatchers.IO) {
+        exec(
+            """
+            UPDATE web_admin.admin_users SET
+                two_fa_backup_codes = two_fa_backup_codes - ?::text,
+                updated_at = ?
+            WHERE id = ? AND two_fa_backup_codes @> jsonb_build_array(?::text)
+            RETURNING jsonb_array_length(two_fa_backup_codes)
+            """.trimIndent(),
+            listOf(
+                TextColumnType() to codeHash,
+                AdminUsers.updatedAt.columnType to now.toKotlinInstant(),
+                UUIDColumnType() to id,
+                TextColumnType() to codeHash
+            ),
+            explicitStatementType = StatementType.SELECT
+        ) { rs -> if (rs.next()) rs.getInt(1) else null }
+    }
+
+    override suspend fun findByRole(role: AdminRole): List<AdminUser> = newSuspendedTransaction(Dispatchers.IO) {
+        AdminUsers.select { AdminUsers.role eq role.name.lowercase() }.map { it.toAdminUser() }
+    }
+
+    override suspend fun findActiveAdmins(): List<AdminUser> = newSuspendedTransaction(Dispatchers.IO) {
+        AdminUsers.select { AdminUsers.isActive eq true }.map { it.toAdminUser() }
+    }
+
+    private fun UpdateBuilder<*>.writeFields(user: AdminUser) {
+        this[AdminUsers.email] = user.email
+        this[AdminUsers.passwordHash] = user.passwordHash
+        this[AdminUsers.salt] = user.salt
+        this[AdminUsers.firstName] = user.firstName
+        this[AdminUsers.lastName] = user.lastName
+        this[AdminUsers.phoneNumber] = user.phoneNumber
+        this[AdminUsers.role] = user.role.name.lowercase()
+        this[AdminUsers.permissions] = user.permissions
+        this[AdminUsers.customPermissions] = user.customPermissions
+        this[AdminUsers.allowedHours] = user.allowedHours
+        this[AdminUsers.twoFactorEnabled] = user.twoFactorEnabled
+        this[AdminUsers.twoFactorSecret] = user.twoFactorSecret
+        this[AdminUsers.twoFactorBackupCodes] = user.twoFactorBackupCodes
+        this[AdminUsers.isActive] = user.isActive
+        this[AdminUsers.emailVerified] = user.emailVerified
+        this[AdminUsers.lastLoginAt] = user.lastLoginAt?.toKotlinInstant()
+        this[AdminUsers.failedLoginAttempts] = user.failedLoginAttempts
+        this[AdminUsers.lockedUntil] = user.lockedUntil?.toKotlinInstant()
+        this[AdminUsers.updatedAt] = user.updatedAt.toKotlinInstant()
+    }
+
+    private fun ResultRow.toAdminUser() = AdminUser(
+        id = this[AdminUsers.id].value,
+        email = this[AdminUsers.email],
+        passwordHash = this[AdminUsers.passwordHash],
+        salt = this[AdminUsers.salt],
+        firstName = this[AdminUsers.firstName],
+        lastName = this[AdminUsers.lastName],
+        phoneNumber = this[AdminUsers.phoneNumber],
+        role = AdminRole.valueOf(this[AdminUsers.role].uppercase()),
+        permissions = this[AdminUsers.permissions],
+        customPermissions = this[AdminUsers.customPermissions],
+        allowedHours = this[AdminUsers.allowedHours],
+        twoFactorEnabled = this[AdminUsers.twoFactorEnabled],
+        twoFactorSecret = this[AdminUsers.twoFactorSecret],
+        twoFactorBackupCodes = this[AdminUsers.twoFactorBackupCodes],
+        twoFactorLastUsedStep = this[AdminUsers.twoFactorLastUsedStep],
+        isActive = this[AdminUsers.isActive],
+        emailVerified = this[AdminUsers.emailVerified],
+        lastLoginAt = this[AdminUsers.lastLoginAt]?.toJavaInstant(),
+        failedLoginAttempts = this[AdminUsers.failedLoginAttempts],
+        lockedUntil = this[AdminUsers.lockedUntil]?.toJavaInstant(),
+        createdBy = this[AdminUsers.createdBy],
+        createdAt = this[AdminUsers.createdAt].toJavaInstant(),
+        updatedAt = this[AdminUsers.updatedAt].toJavaInstant()
+    )
+}
@@ -0,0 +1,25 @@
+package com.wondernest.data.database.repository.web
+
+import com.wondernest.domain.web.BackfillProgress
+import java.time.Instant
+import java.util.*
+
+interface BackfillProgressRepository {
+    suspend fun find(jobName: String): BackfillProgress?
+
+    /**
+     * Mark the job as running under [runId] unless another run holds it. Returns the progress
+     * as it was before the claim, or null when the job is already running.
+     */
+    suspend fun claim(jobName: String, runId: UUID, now: Instant): BackfillProgress?
+
+    /**
+     * Save progress for the run that holds the job; false once another run has taken it over
+     */
+    suspend fun save(progress: BackfillProgress): Boolean
+
+    /**
+     * Mark runs that haven't saved progress since [staleBefore] as failed, so they can be resumed
+     */
+    suspend fun failStale(staleBefore: Instant, now: Instant): Int
+}
@@ -0,0 +1,89 @@
+package com.wondernest.data.database.repository.web
+
+import com.wondernest.data.database.table.web.BackfillProgressTable
+import com.wondernest.domain.web.BackfillProgress
+import com.wondernest.domain.web.BackfillStatus
+import kotlinx.coroutines.Dispatchers
+import kotlinx.datetime.toJavaInstant
+import kotlinx.datetime.toKotlinInstant
+import org.jetbrains.exposed.sql.*
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.eq
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.less
+import org.jetbrains.exposed.sql.transactions.experimental.newSuspendedTransaction
+import java.time.Instant
+import java.util.*
+
+class BackfillProgressRepositoryImpl : BackfillProgressRepository {
+
+    override suspend fun find(jobName: String): BackfillProgress? = newSuspendedTransaction(Dispatchers.IO) {
+        BackfillProgressTable.select { BackfillProgressTable.jobName eq jobName }
+            .singleOrNull()
+            ?.toProgress()
+    }
+
+    override suspend fun claim(jobName: String, runId: UUID, now: Instant): BackfillProgress? =
+        newSuspendedTransaction(Dispatchers.IO) {
+            BackfillProgressTable.insertIgnore {
+                it[BackfillProgressTable.jobName] = jobName
+                it[updatedAt] = now.toKotlinInstant()
+            }
+            // Locking the row makes concurrent claims from any instance see each other
+            val current = BackfillProgressTable.select { BackfillProgressTable.jobName eq jobName }
+                .forUpdate()
+                .single()
+                .toProgress()
+            if (current.status == BackfillStatus.RUNNING) return@newSuspendedTransaction null
+
+            BackfillProgressTable.update({ BackfillProgressTable.jobName eq jobName }) {
+                it[status] = BackfillStatus.RUNNING.dbValue
+                it[BackfillProgressTable.runId] = runId
+                it[updatedAt] = now.toKotlinInstant()
+            }
+            current
+        }
+
+    override suspend fun save(progress: BackfillProgress): Boolean = newSuspendedTransaction(Dispatchers.IO) {
+        val runId = progress.runId ?: return@newSuspendedTransaction false
+        BackfillProgressTable.update({
+            (BackfillProgressTable.jobName eq progress.jobName) and (BackfillProgressTable.runId eq runId)
+        }) {
+            it[cursor] = progress.cursor
+            it[status] = progress.status.dbValue
+            it[processed] = progress.processed
+            it[updated] = progress.updated
+            it[batches] = progress.batches
+            it[lastError] = progress.lastError
+            it[startedBy] = progress.startedBy
+            it[startedAt] = progress.startedAt?.toKotlinInstant()
+            it[updatedAt] = progress.updatedAt.toKotlinInstant()
+            it[completedAt] = progress.completedAt?.toKotlinInstant()
+        } > 0
+    }
+
+    override suspend fun failStale(staleBefore: Instant, now: Instant): Int = newSuspendedTransaction(Dispatchers.IO) {
+        BackfillProgressTable.update({
+            (BackfillProgressTable.status eq BackfillStatus.RUNNING.dbValue) and
+                (BackfillProgressTable.updatedAt less staleBefore.toKotlinInstant())
+        }) {
+            it[status] = BackfillStatus.FAILED.dbValue
+            it[lastError] = "Interrupted: no progress saved since before $staleBefore"
+            it[runId] = null
+            it[updatedAt] = now.toKotlinInstant()
+        }
+    }
+
+    private fun ResultRow.toProgress() = BackfillProgress(
+        jobName = this[BackfillProgressTable.jobName],
+        cursor = this[BackfillProgressTable.cursor],
+        status = BackfillStatus.fromDbValue(this[BackfillProgressTable.status]),
+        processed = this[BackfillProgressTable.processed],
+        updated = this[BackfillProgressTable.updated],
+        batches = this[BackfillProgressTable.batches],
+        lastError = this[BackfillProgressTable.lastError],
+        startedBy = this[BackfillProgressTable.startedBy],
+        startedAt = this[BackfillProgressTable.startedAt]?.toJavaInstant(),
+        updatedAt = this[BackfillProgressTable.updatedAt].toJavaInstant(),
+        completedAt = this[BackfillProgressTable.completedAt]?.toJavaInstant(),
+        runId = this[BackfillProgressTable.runId]
+    )
+}
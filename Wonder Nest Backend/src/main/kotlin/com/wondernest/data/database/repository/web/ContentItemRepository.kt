@@ -0,0 +1,49 @@
+package com.wondernest.data.database.repository.web
+
+import com.wondernest.domain.web.AuditLogEntry
+import com.wondernest.domain.web.AuditLogFilter
+import com.wondernest.domain.web.AuditSeverity
+import com.wondernest.domain.web.ContentItem
+import com.wondernest.domain.web.ContentItemFilter
+import com.wondernest.domain.web.ContentStatus
+import java.util.*
+
+/**
+ * Repository interface for admin content workflow items
+ */
+interface ContentItemRepository {
+    /**
+     * Keyset-paginated lookup ordered by id. Pass the last id of the previous page as [afterId].
+     */
+    suspend fun findByFilter(filter: ContentItemFilter, afterId: UUID? = null, limit: Int = 100): List<ContentItem>
+
+    /**
+     * Set the status of the given items in a single transaction. Returns the number of rows updated.
+     */
+    suspend fun updateStatusBatch(ids: List<UUID>, status: ContentStatus, actorId: UUID): Int
+}
+
+/**
+ * Admin audit trail entry (web_audit.audit_log)
+ */
+data class AdminAuditEntry(
+    val adminId: UUID,
+    val action: String,
+    val resourceType: String? = null,
+    val resourceId: UUID? = null,
+    val details: Map<String, String> = emptyMap(),
+    val success: Boolean = true,
+    val errorMessage: String? = null,
+    val ipAddress: String? = null,
+    val userAgent: String? = null,
+    val severity: AuditSeverity = AuditSeverity.INFO
+)
+
+interface AdminAuditRepository {
+    suspend fun record(entry: AdminAuditEntry)
+
+    /**
+     * Entries matching [filter], newest first
+     */
+    suspend fun query(filter: AuditLogFilter, limit: Int, offset: Long): List<AuditLogEntry>
+}
@@ -0,0 +1,139 @@
+package com.wondernest.data.database.repository.web
+
+import com.wondernest.data.database.table.web.AdminAuditLog
+import com.wondernest.data.database.table.web.ContentItems
+import com.wondernest.domain.web.AuditLogEntry
+import com.wondernest.domain.web.AuditLogFilter
+import com.wondernest.domain.web.AuditSeverity
+import com.wondernest.domain.web.ContentItem
+import com.wondernest.domain.web.ContentItemFilter
+import com.wondernest.domain.web.ContentStatus
+import kotlinx.coroutines.Dispatchers
+import kotlinx.datetime.Clock
+import kotlinx.datetime.toJavaInstant
+import kotlinx.datetime.toKotlinInstant
+import kotlinx.serialization.json.JsonObject
+import kotlinx.serialization.json.JsonPrimitive
+import kotlinx.serialization.json.contentOrNull
+import org.jetbrains.exposed.sql.*
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.eq
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.greater
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.greaterEq
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.inList
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.less
+import org.jetbrains.exposed.sql.transactions.experimental.newSuspendedTransaction
+import java.util.*
+
+class ContentItemRepositoryImpl : ContentItemRepository {
+
+    override suspend fun findByFilter(
+        filter: ContentItemFilter,
+        afterId: UUID?,
+        limit: Int
+    ): List<ContentItem> = newSuspendedTransaction(Dispatchers.IO) {
+        val query = ContentItems.selectAll()
+        filter.creatorId?.let { query.andWhere { ContentItems.creatorId eq it } }
+        filter.contentType?.let { query.andWhere { ContentItems.contentType eq it } }
+        filter.status?.let { query.andWhere { ContentItems.status eq it.dbValue } }
+        filter.createdAfter?.let { query.andWhere { ContentItems.createdAt greaterEq it.toKotlinInstant() } }
+        filter.createdBefore?.let { query.andWhere { ContentItems.createdAt less it.toKotlinInstant() } }
+        afterId?.let { query.andWhere { ContentItems.id greater it } }
+
+        query.orderBy(ContentItems.id to SortOrder.ASC)
+            .limit(limit)
+            .map { rowToContentItem(it) }
+    }
+
+    override suspend fun updateStatusBatch(
+        ids: List<UUID>,
+        status: ContentStatus,
+        actorId: UUID
+    ): Int = newSuspendedTransaction(Dispatchers.IO) {
+        if (ids.isEmpty()) return@newSuspendedTransaction 0
+        val now = Clock.System.now()
+        ContentItems.update({ ContentItems.id inList ids }) {
+            it[ContentItems.status] = status.dbValue
+            it[updatedAt] = now
+            if (status == ContentStatus.PUBLISHED) {
+                it[publishedAt] = now
+                it[publishedBy] = actorId
+            }
+        }
+    }
+
+    private fun rowToContentItem(row: ResultRow): ContentItem {
+        return ContentItem(
+            id = row[ContentItems.id].value,
+            contentType = row[ContentItems.contentType],
+            title = row[ContentItems.title],
+            description = row[ContentItems.description],
+            creatorId = row[ContentItems.creatorId],
+            status = ContentStatus.fromDbValue(row[ContentItems.status]),
+            publishedAt = row[ContentItems.publishedAt]?.toJavaInstant(),
+            publishedBy = row[ContentItems.publishedBy],
+            minAgeMonths = row[ContentItems.minAgeMonths],
+            maxAgeMonths = row[ContentItems.maxAgeMonths],
+            tags = row[ContentItems.tags],
+            languageCode = row[ContentItems.languageCode],
+            createdAt = row[ContentItems.createdAt].toJavaInstant(),
+            updatedAt = row[ContentItems.updatedAt].toJavaInstant()
+        )
+    }
+}
+
+class AdminAuditRepositoryImpl : AdminAuditRepository {
+
+    override suspend fun record(entry: AdminAuditEntry) {
+        newSuspendedTransaction(Dispatchers.IO) {
+            val data = entry.details.mapValues { JsonPrimitive(it.value) } +
+                listOfNotNull(entry.ipAddress?.let { "ipAddress" to JsonPrimitive(it) })
+
+            AdminAuditLog.insert {
+                it[userId] = entry.adminId
+                it[userType] = "admin"
+                it[action] = entry.action
+                it[resourceType] = entry.resourceType
+                it[resourceId] = entry.resourceId
+                it[actionData] = JsonObject(data)
+                it[userAgent] = entry.userAgent
+                it[success] = entry.success
+                it[errorMessage] = entry.errorMessage
+                it[severity] = entry.severity.dbValue
+                it[createdAt] = Clock.System.now()
+            }
+        }
+    }
+
+    override suspend fun query(
+        filter: AuditLogFilter,
+        limit: Int,
+        offset: Long
+    ): List<AuditLogEntry> = newSuspendedTransaction(Dispatchers.IO) {
+        val query = AdminAuditLog.selectAll()
+        filter.adminId?.let { query.andWhere { AdminAuditLog.userId eq it } }
+        filter.action?.let { query.andWhere { AdminAuditLog.action eq it } }
+        filter.severity?.let { query.andWhere { AdminAuditLog.severity eq it.dbValue } }
+        filter.from?.let { query.andWhere { AdminAuditLog.createdAt greaterEq it.toKotlinInstant() } }
+        filter.to?.let { query.andWhere { AdminAuditLog.createdAt less it.toKotlinInstant() } }
+
+        query.orderBy(AdminAuditLog.createdAt to SortOrder.DESC, AdminAuditLog.id to SortOrder.DESC)
+            .limit(limit, offset)
+            .map { row ->
+                AuditLogEntry(
+                    id = row[AdminAuditLog.id].value,
+                    adminId = row[AdminAuditLog.userId],
+                    action = row[AdminAuditLog.action],
+                    resourceType = row[AdminAuditLog.resourceType],
+                    resourceId = row[AdminAuditLog.resourceId],
+                    details = row[AdminAuditLog.actionData]
+                        .mapNotNull { (key, value) -> (value as? JsonPrimitive)?.contentOrNull?.let { key to it } }
+                        .toMap(),
+                    severity = AuditSeverity.fromDbValue(row[AdminAuditLog.severity]),
+                    success = row[AdminAuditLog.success],
+                    errorMessage = row[AdminAuditLog.errorMessage],
+                    userAgent = row[AdminAuditLog.userAgent],
+                    createdAt = row[AdminAuditLog.createdAt].toJavaInstant()
+                )
+            }
+    }
+}
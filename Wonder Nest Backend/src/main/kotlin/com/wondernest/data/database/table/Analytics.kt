@@ -28,7 +28,7 @@ data class EventProperties(
 )
 
 // Daily aggregated metrics per child
-object DailyChildMetrics : UUIDTable("daily_child_metrics") {
+object DailyChildMetrics : UUIDTable("analytics.daily_child_metrics") {
     val childId = reference("child_id", ChildProfiles)
     val date = date("date")
     
@@ -62,8 +62,10 @@ object DailyChildMetrics : UUIDTable("daily_child_metrics") {
 }
 
 // Development milestones tracking
-object Milestones : UUIDTable("milestones") {
+object Milestones : UUIDTable("core.milestones") {
     val childId = reference("child_id", ChildProfiles)
+    // Catalog key of the milestone (see MilestoneCatalog)
+    val milestoneKey = varchar("milestone_key", 100)
     
     val milestoneType = varchar("milestone_type", 100) // language, motor, social, cognitive
     val milestoneName = varchar("milestone_name", 200)
@@ -85,6 +87,10 @@ object Milestones : UUIDTable("milestones") {
     
     val createdAt = timestamp("created_at").defaultExpression(CurrentTimestamp())
     val updatedAt = timestamp("updated_at").defaultExpression(CurrentTimestamp())
+
+    init {
+        uniqueIndex(childId, milestoneKey)
+    }
 }
 
 // Usage analytics and events
@@ -106,4 +112,52 @@ object Events : UUIDTable("events") {
     // Privacy-safe location data
     val country = varchar("country", 2).nullable() // ISO country code only
     val timezone = varchar("timezone", 50).nullable()
-}
\ No newline at end of file
+}
+// Raw analytics events (created in V2 in the analytics schema)
+object AnalyticsEvents : UUIDTable("analytics.analytics_events") {
+    val userId = uuid("user_id").nullable()
+    val childId = uuid("child_id").nullable()
+    val eventType = varchar("event_type", 50)
+    val eventCategory = varchar("event_category", 50).nullable()
+    val eventData = jsonb<kotlinx.serialization.json.JsonElement>("event_data",
+        serialize = { Json.encodeToString(kotlinx.serialization.json.JsonElement.serializer(), it) },
+        deserialize = { Json.parseToJsonElement(it) }
+    )
+    val sessionId = varchar("session_id", 100).nullable()
+    val contentId = varchar("content_id", 255).nullable()
+    val duration = integer("duration").nullable()
+    val sampleRate = double("sample_rate").default(1.0)
+    val createdAt = timestamp("created_at").defaultExpression(CurrentTimestamp())
+}
+
+// Per child, event type and UTC day, counted before sampling (V58)
+object AnalyticsEventDailyTotals : Table("analytics.analytics_event_daily_totals") {
+    val childId = uuid("child_id")
+    val eventType = varchar("event_type", 50)
+    val eventDate = date("event_date")
+    val eventCount = long("event_count").default(0)
+    val totalDuration = long("total_duration").default(0)
+
+    override val primaryKey = PrimaryKey(childId, eventType, eventDate)
+}
+
+// Client event IDs already counted, so replays are recognised (V58)
+object AnalyticsClientEvents : Table("analytics.analytics_client_events") {
+    val childId = uuid("child_id")
+    val clientEventId = varchar("client_event_id", 100)
+    val eventId = uuid("event_id")
+    val recordedAt = timestamp("recorded_at").defaultExpression(CurrentTimestamp())
+
+    override val primaryKey = PrimaryKey(childId, clientEventId)
+}
+
+// Generated insights shown to parents (created in V2 in the analytics schema)
+object LearningInsights : UUIDTable("analytics.learning_insights") {
+    val childId = uuid("child_id")
+    val insightType = varchar("insight_type", 50).nullable()
+    val category = varchar("category", 50).nullable()
+    val title = varchar("title", 255).nullable()
+    val description = text("description").nullable()
+    val isRead = bool("is_read").default(false)
+    val createdAt = timestamp("created_at").defaultExpression(CurrentTimestamp())
+}
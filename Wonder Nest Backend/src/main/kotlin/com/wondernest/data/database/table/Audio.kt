@@ -1,6 +1,9 @@
 package com.wondernest.data.database.table
 
+import kotlinx.serialization.json.Json
 import org.jetbrains.exposed.dao.id.UUIDTable
+import org.jetbrains.exposed.sql.Table
+import org.jetbrains.exposed.sql.json.jsonb
 import org.jetbrains.exposed.sql.kotlin.datetime.CurrentTimestamp
 import org.jetbrains.exposed.sql.kotlin.datetime.timestamp
 import java.math.BigDecimal
@@ -72,4 +75,17 @@ object SpeechMetrics : UUIDTable("speech_metrics") {
     val excitementIndicators = integer("excitement_indicators").default(0) // laughing, exclamations
     
     val createdAt = timestamp("created_at").defaultExpression(CurrentTimestamp())
-}
\ No newline at end of file
+}
+// Per-session speech metrics uploaded by the device (V66)
+object SpeechSessionMetrics : Table("analytics.speech_session_metrics") {
+    val id = uuid("id")
+    val childId = uuid("child_id")
+    val sessionId = varchar("session_id", 100).nullable()
+    val speechClarity = double("speech_clarity")
+    val engagementLevel = double("engagement_level")
+    val sessionDuration = integer("session_duration")
+    val vocabulary = jsonb<List<String>>("vocabulary", Json.Default).default(emptyList())
+    val recordedAt = timestamp("recorded_at")
+
+    override val primaryKey = PrimaryKey(id)
+}
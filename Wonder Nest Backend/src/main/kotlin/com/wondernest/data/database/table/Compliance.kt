@@ -0,0 +1,57 @@
+package com.wondernest.data.database.table
+
+import kotlinx.serialization.encodeToString
+import kotlinx.serialization.json.Json
+import kotlinx.serialization.json.JsonElement
+import org.jetbrains.exposed.dao.id.UUIDTable
+import org.jetbrains.exposed.sql.json.jsonb
+import org.jetbrains.exposed.sql.kotlin.datetime.CurrentTimestamp
+import org.jetbrains.exposed.sql.kotlin.datetime.timestamp
+
+// COPPA consent records, one row per consent type decision
+object CoppaConsents : UUIDTable("compliance.coppa_consent") {
+    val familyId = reference("family_id", Families)
+    val childId = reference("child_id", ChildProfiles)
+    val parentId = reference("parent_id", Users)
+    val consentType = varchar("consent_type", 50) // data_collection, audio_monitoring, etc
+    val granted = bool("granted")
+    val userAgent = text("user_agent").nullable()
+    val consentText = text("consent_text").nullable()
+    val version = varchar("version", 20).nullable()
+    val createdAt = timestamp("created_at").defaultExpression(CurrentTimestamp())
+    val revokedAt = timestamp("revoked_at").nullable()
+    val verificationMethod = varchar("verification_method", 50).nullable() // credit_card, phone
+    val verificationStatus = varchar("verification_status", 20).default("pending") // pending, verified, failed
+    val expiresAt = timestamp("expires_at").nullable()
+}
+
+// Child game data held back because it looked like it contained personal information
+object PiiReviewQueue : UUIDTable("compliance.pii_review_queue") {
+    val childId = reference("child_id", ChildProfiles)
+    val gameKey = varchar("game_key", 100)
+    val dataKey = varchar("data_key", 200)
+    val dataValue = jsonb<JsonElement>("data_value",
+        serialize = { Json.encodeToString(it) },
+        deserialize = { Json.parseToJsonElement(it) }
+    )
+    val flaggedPaths = jsonb<List<String>>("flagged_paths",
+        serialize = { Json.encodeToString(it) },
+        deserialize = { Json.decodeFromString(it) }
+    )
+    val target = varchar("target", 20).default("simple") // simple, game_instance
+    val status = varchar("status", 20).default("pending") // pending, released, discarded
+    val createdAt = timestamp("created_at").defaultExpression(CurrentTimestamp())
+    val reviewedAt = timestamp("reviewed_at").nullable()
+    val reviewedBy = uuid("reviewed_by").nullable()
+}
+
+// Deferred child data exports and the stored archive they produced
+object ChildDataExportJobs : UUIDTable("compliance.child_data_export_jobs") {
+    val childId = reference("child_id", ChildProfiles)
+    val parentId = uuid("parent_id")
+    val status = varchar("status", 20).default("pending") // pending, ready, failed, expired
+    val fileKey = varchar("file_key", 500).nullable()
+    val requestedAt = timestamp("requested_at")
+    val completedAt = timestamp("completed_at").nullable()
+    val fileExpiresAt = timestamp("file_expires_at").nullable()
+}
@@ -63,6 +63,8 @@ object ContentPacksTable : UUIDTable("content_packs") {
     // Status and timestamps
     val status = varchar("status", 50).default("draft")
     val publishedAt = timestamp("published_at").nullable()
+    val expiresAt = timestamp("expires_at").nullable() // Seasonal packs leave listings after this
+    val featuredUntil = timestamp("featured_until").nullable()
     val createdAt = timestamp("created_at")
     val updatedAt = timestamp("updated_at")
     val createdBy = uuid("created_by").nullable()
@@ -194,6 +196,7 @@ object ContentPackCollectionItemsTable : UUIDTable("content_pack_collection_item
 object ContentPackReviewsTable : UUIDTable("content_pack_reviews") {
     val packId = reference("pack_id", ContentPacksTable)
     val userId = uuid("user_id") // Parent/guardian only
+    val familyId = uuid("family_id").nullable()
     
     // Review content
     val rating = integer("rating")
@@ -222,6 +225,37 @@ object ContentPackReviewsTable : UUIDTable("content_pack_reviews") {
     }
 }
 
+// One row per family per pack so re-installs don't inflate content_packs.download_count
+object ContentPackInstallsTable : Table("content_pack_installs") {
+    val packId = reference("pack_id", ContentPacksTable, onDelete = ReferenceOption.CASCADE)
+    val familyId = uuid("family_id")
+    val installedAt = timestamp("installed_at")
+    
+    override val primaryKey = PrimaryKey(packId, familyId)
+}
+
+// One rating per user per pack; resubmitting replaces it
+object ContentPackRatingsTable : Table("content_pack_ratings") {
+    val packId = reference("pack_id", ContentPacksTable, onDelete = ReferenceOption.CASCADE)
+    val userId = uuid("user_id")
+    val rating = short("rating")
+    val createdAt = timestamp("created_at")
+    val updatedAt = timestamp("updated_at")
+    
+    override val primaryKey = PrimaryKey(packId, userId)
+}
+
+object ContentPackReviewVotesTable : UUIDTable("content_pack_review_votes") {
+    val reviewId = reference("review_id", ContentPackReviewsTable, onDelete = ReferenceOption.CASCADE)
+    val userId = uuid("user_id")
+    val isHelpful = bool("is_helpful")
+    val createdAt = timestamp("created_at")
+    
+    init {
+        uniqueIndex(reviewId, userId)
+    }
+}
+
 // Enums for content pack system
 enum class ContentPackType {
     CHARACTER_BUNDLE,
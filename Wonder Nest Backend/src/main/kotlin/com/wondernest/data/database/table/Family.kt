@@ -1,6 +1,7 @@
 package com.wondernest.data.database.table
 
 import org.jetbrains.exposed.dao.id.UUIDTable
+import org.jetbrains.exposed.sql.Table
 import org.jetbrains.exposed.sql.kotlin.datetime.CurrentTimestamp
 import org.jetbrains.exposed.sql.kotlin.datetime.timestamp
 import org.jetbrains.exposed.sql.json.jsonb
@@ -55,8 +56,22 @@ object ChildProfiles : UUIDTable("family.child_profiles") {
     val avatarUrl = text("avatar_url").nullable()
     val interests = text("interests").nullable() // Store as JSON string
     val favoriteColors = text("favorite_colors").nullable() // Store as JSON string
+    // Parent-set daily limit; null falls back to the family's maxScreenTimeMinutes
+    val dailyScreenTimeLimitMinutes = integer("daily_screen_time_limit_minutes").nullable()
     val isActive = bool("is_active").default(true)
     val createdAt = timestamp("created_at").defaultExpression(CurrentTimestamp())
     val updatedAt = timestamp("updated_at").defaultExpression(CurrentTimestamp())
     val archivedAt = timestamp("archived_at").nullable()
+    // Set when a parent has the child's data deleted; such profiles are never listed or restored
+    val deletedAt = timestamp("deleted_at").nullable()
+}
+
+// The child's current external ID; rotating replaces the row's external_id
+object ChildPseudonyms : Table("family.child_pseudonyms") {
+    val childId = uuid("child_id")
+    val externalId = varchar("external_id", 40).uniqueIndex()
+    val createdAt = timestamp("created_at").defaultExpression(CurrentTimestamp())
+    val rotatedAt = timestamp("rotated_at").nullable()
+
+    override val primaryKey = PrimaryKey(childId)
 }
\ No newline at end of file
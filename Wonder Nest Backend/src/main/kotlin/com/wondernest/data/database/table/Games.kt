@@ -126,6 +126,7 @@ object ChildGameInstances : UUIDTable("games.child_game_instances") {
     val sessionCount = integer("session_count").default(0)
     
     // Status
+    val isEnabled = bool("is_enabled").default(true)
     val isFavorite = bool("is_favorite").default(false)
     val isCompleted = bool("is_completed").default(false)
     val completionPercentage = decimal("completion_percentage", 5, 2).default(java.math.BigDecimal.ZERO)
@@ -154,6 +155,22 @@ object ChildGameData : UUIDTable("games.child_game_data") {
     }
 }
 
+// Append-only copy of every child_game_data save, written by a database trigger
+object ChildGameDataHistory : UUIDTable("games.child_game_data_history") {
+    val childGameInstanceId = reference("child_game_instance_id", ChildGameInstances, onDelete = ReferenceOption.CASCADE)
+    val dataKey = varchar("data_key", 200)
+    val dataVersion = integer("data_version")
+    val dataValue = jsonb<Map<String, String>>("data_value",
+        serialize = { Json.encodeToString(it) },
+        deserialize = { Json.decodeFromString(it) }
+    )
+    val recordedAt = timestamp("recorded_at")
+    
+    init {
+        uniqueIndex(childGameInstanceId, dataKey, dataVersion)
+    }
+}
+
 // =============================================================================
 // GAME SESSIONS
 // =============================================================================
@@ -367,4 +384,21 @@ object SimpleGameData : UUIDTable("games.simple_game_data") {
     init {
         uniqueIndex(childId, gameType, dataKey)
     }
+}
+
+// Written by the V55 trigger on simple_game_data, one row per saved version
+object SimpleGameDataHistory : UUIDTable("games.simple_game_data_history") {
+    val childId = uuid("child_id")
+    val gameType = varchar("game_type", 100)
+    val dataKey = varchar("data_key", 200)
+    val dataVersion = integer("data_version")
+    val dataValue = jsonb<Map<String, kotlinx.serialization.json.JsonElement>>("data_value",
+        serialize = { Json.encodeToString(it) },
+        deserialize = { Json.decodeFromString(it) }
+    )
+    val recordedAt = timestamp("recorded_at")
+    
+    init {
+        uniqueIndex(childId, gameType, dataKey, dataVersion)
+    }
 }
\ No newline at end of file
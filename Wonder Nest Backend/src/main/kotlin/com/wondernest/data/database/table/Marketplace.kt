@@ -0,0 +1,101 @@
+package com.wondernest.data.database.table
+
+import com.wondernest.services.marketplace.LocalizedContent
+import com.wondernest.services.moderation.ContentScanFinding
+import com.wondernest.services.moderation.DuplicateMatch
+import kotlinx.serialization.json.Json
+import org.jetbrains.exposed.sql.Table
+import org.jetbrains.exposed.sql.json.jsonb
+import org.jetbrains.exposed.sql.kotlin.datetime.timestamp
+
+// Creator accounts (V25, content_specialties from V64)
+object CreatorProfiles : Table("marketplace.creator_profiles") {
+    val id = uuid("id")
+    val userId = uuid("user_id").uniqueIndex("unique_user_creator")
+    val displayName = varchar("display_name", 100)
+    val bio = text("bio").nullable()
+    val avatarUrl = text("avatar_url").nullable()
+    val socialLinks = jsonb<Map<String, String>>("social_links", Json.Default).default(emptyMap())
+    val contentSpecialties = jsonb<List<String>>("content_specialties", Json.Default).default(emptyList())
+    val tier = varchar("tier", 30).default("HOBBYIST")
+    val verified = bool("verified").default(false)
+    val totalSales = integer("total_sales").default(0)
+    val totalRevenue = decimal("total_revenue", 10, 2)
+    val averageRating = decimal("average_rating", 3, 2)
+    val contentCount = integer("content_count").default(0)
+    val followerCount = integer("follower_count").default(0)
+    val accountStatus = varchar("account_status", 30).default("PENDING_VERIFICATION")
+    val createdAt = timestamp("created_at")
+    val updatedAt = timestamp("updated_at")
+
+    override val primaryKey = PrimaryKey(id)
+}
+
+// Drafts and submissions moving through review (V64)
+object CreatorSubmissions : Table("marketplace.creator_submissions") {
+    val itemId = uuid("item_id")
+    val creatorId = uuid("creator_id").index()
+    val title = varchar("title", 200)
+    val status = varchar("status", 30)
+    val contentType = varchar("content_type", 50).nullable()
+    val submittedAt = timestamp("submitted_at").nullable()
+    val publishedAt = timestamp("published_at").nullable()
+    val embargoUntil = timestamp("embargo_until").nullable()
+    val claimedBy = uuid("claimed_by").nullable()
+    val claimedAt = timestamp("claimed_at").nullable()
+    val rejectionCategory = varchar("rejection_category", 50).nullable()
+    val rejectionGuidance = text("rejection_guidance").nullable()
+    val rejectionNotes = text("rejection_notes").nullable()
+    val rejectedBy = uuid("rejected_by").nullable()
+    val rejectedAt = timestamp("rejected_at").nullable()
+    val possibleDuplicates = jsonb<List<DuplicateMatch>>("possible_duplicates", Json.Default).default(emptyList())
+    val automatedFindings = jsonb<List<ContentScanFinding>>("automated_findings", Json.Default).default(emptyList())
+    val updatedAt = timestamp("updated_at")
+
+    override val primaryKey = PrimaryKey(itemId)
+}
+
+// Editable content of a draft (V64)
+object CreatorDrafts : Table("marketplace.creator_drafts") {
+    val itemId = uuid("item_id").references(CreatorSubmissions.itemId)
+    val revision = integer("revision")
+    val title = varchar("title", 200)
+    val description = text("description")
+    val contentType = varchar("content_type", 50)
+    val ageRange = varchar("age_range", 20)
+    val price = decimal("price", 8, 2)
+    val licensingModel = varchar("licensing_model", 30)
+    val tags = jsonb<List<String>>("tags", Json.Default).default(emptyList())
+    val educationalGoals = jsonb<List<String>>("educational_goals", Json.Default).default(emptyList())
+    val contentData = jsonb<Map<String, String>>("content_data", Json.Default).default(emptyMap())
+    val version = varchar("version", 50).nullable()
+    val changelog = text("changelog").nullable()
+    val primaryLanguage = varchar("primary_language", 10).default("en")
+    val languagesSupported = jsonb<List<String>>("languages_supported", Json.Default).default(emptyList())
+    val localizations = jsonb<Map<String, LocalizedContent>>("localizations", Json.Default).default(emptyMap())
+    val dropIncompleteLanguages = bool("drop_incomplete_languages").default(false)
+    val updatedAt = timestamp("updated_at")
+
+    override val primaryKey = PrimaryKey(itemId)
+}
+
+// Released versions of creator content (V64)
+object CreatorContentVersions : Table("marketplace.creator_content_versions") {
+    val itemId = uuid("item_id")
+    val version = varchar("version", 50)
+    val changelog = text("changelog")
+    val releasedAt = timestamp("released_at")
+
+    override val primaryKey = PrimaryKey(itemId, version)
+}
+
+// Duplicate-detection fingerprints of submitted content (V65)
+object ContentFingerprints : Table("marketplace.content_fingerprints") {
+    val itemId = uuid("item_id")
+    val normalizedTitle = text("normalized_title")
+    val contentHash = varchar("content_hash", 64)
+    val vocabulary = jsonb<List<String>>("vocabulary", Json.Default).default(emptyList())
+    val registeredAt = timestamp("registered_at")
+
+    override val primaryKey = PrimaryKey(itemId)
+}
@@ -0,0 +1,41 @@
+package com.wondernest.data.database.table
+
+import com.wondernest.services.moderation.ModerationWebhookEvent
+import kotlinx.serialization.json.Json
+import org.jetbrains.exposed.dao.id.UUIDTable
+import org.jetbrains.exposed.sql.Table
+import org.jetbrains.exposed.sql.json.jsonb
+import org.jetbrains.exposed.sql.kotlin.datetime.timestamp
+
+// Approve/reject decisions on creator submissions, read by moderator workload analytics
+object ModerationDecisions : UUIDTable("games.moderation_decisions") {
+    val itemId = uuid("item_id")
+    val moderatorId = uuid("moderator_id")
+    val outcome = varchar("outcome", 20) // approve, reject
+    val submittedAt = timestamp("submitted_at")
+    val decidedAt = timestamp("decided_at")
+    val appealed = bool("appealed").default(false)
+    val overturnedOnAppeal = bool("overturned_on_appeal").default(false)
+}
+
+// Moderation webhook deliveries that ran out of retries, kept until they're replayed
+object ModerationWebhookDeadLetters : UUIDTable("games.moderation_webhook_dead_letters") {
+    val endpoint = text("endpoint")
+    val event = jsonb<ModerationWebhookEvent>("event", Json.Default, ModerationWebhookEvent.serializer())
+    val attempts = integer("attempts")
+    val lastError = text("last_error")
+    val failedAt = timestamp("failed_at")
+    val replayedAt = timestamp("replayed_at").nullable()
+}
+
+// Family reports on marketplace items and content packs; one row per family and item
+object ContentReports : Table("games.content_reports") {
+    val contentId = uuid("content_id")
+    val reporterFamilyId = uuid("reporter_family_id")
+    val reason = text("reason")
+    val reportedAt = timestamp("reported_at")
+
+    init {
+        uniqueIndex("uq_content_reports_content_reporter", contentId, reporterFamilyId)
+    }
+}
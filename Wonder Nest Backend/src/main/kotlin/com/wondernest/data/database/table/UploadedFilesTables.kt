@@ -22,6 +22,7 @@ object UploadedFiles : UUIDTable("core.uploaded_files") {
     val originalName = varchar("original_name", 255)
     val mimeType = varchar("mime_type", 100)
     val fileSize = long("file_size")
+    val contentHash = varchar("content_hash", 64).nullable() // SHA-256 hex, added in V46
     val storageProvider = varchar("storage_provider", 50).default("local")
     
     // URL and access
@@ -44,4 +45,15 @@ object UploadedFiles : UUIDTable("core.uploaded_files") {
     val uploadedAt = timestamp("uploaded_at")
     val accessedAt = timestamp("accessed_at").nullable()
     val deletedAt = timestamp("deleted_at").nullable()
-}
\ No newline at end of file
+}
+
+/**
+ * Where uploaded files are used, so deletes can warn before breaking content
+ * (created in V23__Add_File_Soft_Delete.sql)
+ */
+object FileReferences : UUIDTable("content.file_references") {
+    val fileId: Column<UUID> = uuid("file_id").references(UploadedFiles.id, onDelete = ReferenceOption.CASCADE)
+    val referenceType = varchar("reference_type", 50)
+    val referenceId = uuid("reference_id")
+    val createdAt = timestamp("created_at")
+}
@@ -43,6 +43,9 @@ object Users : UUIDTable("core.users") {
     val lastName = varchar("last_name", 100).nullable()
     val phone = varchar("phone", 20).nullable()
     val isActive = bool("is_active").default(true)
+    val failedLoginAttempts = integer("failed_login_attempts").default(0)
+    val lockedUntil = timestamp("locked_until").nullable()
+    val pinHash = varchar("pin_hash", 255).nullable() // parent-mode PIN, BCrypt
     val createdAt = timestamp("created_at").defaultExpression(CurrentTimestamp())
     val updatedAt = timestamp("updated_at").defaultExpression(CurrentTimestamp())
 }
@@ -59,6 +62,29 @@ object UserSessions : UUIDTable("core.user_sessions") {
     ).nullable()
 }
 
+object EmailVerificationTokens : UUIDTable("core.email_verification_tokens") {
+    val userId = reference("user_id", Users)
+    val tokenHash = varchar("token_hash", 64).uniqueIndex()
+    val expiresAt = timestamp("expires_at")
+    val usedAt = timestamp("used_at").nullable()
+    val createdAt = timestamp("created_at").defaultExpression(CurrentTimestamp())
+}
+
+// TOTP for creator accounts; the secret is encrypted with SecretCipher, backup codes are SHA-256 hashes
+object CreatorTwoFactor : Table("core.creator_two_factor") {
+    val userId = reference("user_id", Users)
+    val encryptedSecret = text("encrypted_secret")
+    val backupCodeHashes = jsonb<List<String>>("backup_code_hashes",
+        serialize = { Json.encodeToString(it) },
+        deserialize = { Json.decodeFromString(it) }
+    )
+    val enabled = bool("enabled").default(false)
+    val createdAt = timestamp("created_at").defaultExpression(CurrentTimestamp())
+    val enabledAt = timestamp("enabled_at").nullable()
+
+    override val primaryKey = PrimaryKey(userId)
+}
+
 object PasswordResetTokens : UUIDTable("password_reset_tokens") {
     val userId = reference("user_id", Users)
     val token = varchar("token", 255).uniqueIndex()
@@ -0,0 +1,15 @@
+package com.wondernest.data.database.table.web
+
+import org.jetbrains.exposed.dao.id.UUIDTable
+import org.jetbrains.exposed.sql.kotlin.datetime.timestamp
+
+// Admin invitations (created in V45__Add_Admin_Invitations.sql)
+object AdminInvitations : UUIDTable("web_admin.admin_invitations") {
+    val email = varchar("email", 255)
+    val role = varchar("role", 50)
+    val tokenHash = varchar("token_hash", 64).uniqueIndex()
+    val invitedBy = uuid("invited_by")
+    val expiresAt = timestamp("expires_at")
+    val acceptedAt = timestamp("accepted_at").nullable()
+    val createdAt = timestamp("created_at")
+}
@@ -0,0 +1,71 @@
+package com.wondernest.data.database.table.web
+
+import com.wondernest.domain.web.AllowedHours
+import com.wondernest.domain.web.CustomPermissions
+import kotlinx.serialization.builtins.ListSerializer
+import kotlinx.serialization.builtins.serializer
+import kotlinx.serialization.json.Json
+import org.jetbrains.exposed.dao.id.UUIDTable
+import org.jetbrains.exposed.sql.Column
+import org.jetbrains.exposed.sql.ColumnType
+import org.jetbrains.exposed.sql.Table
+import org.jetbrains.exposed.sql.json.jsonb
+import org.jetbrains.exposed.sql.kotlin.datetime.timestamp
+import org.postgresql.util.PGobject
+
+// Admin accounts (created in V7__Add_Web_Platform_Tables.sql, extended by V31, V43, V44 and V52)
+object AdminUsers : UUIDTable("web_admin.admin_users") {
+    val email = varchar("email", 255).uniqueIndex()
+    val passwordHash = varchar("password_hash", 255)
+    val salt = varchar("salt", 255)
+    val firstName = varchar("first_name", 100)
+    val lastName = varchar("last_name", 100)
+    val phoneNumber = varchar("phone_number", 20).nullable()
+    val role = varchar("role", 50)
+    val permissions = jsonb("permissions", Json.Default, ListSerializer(String.serializer()))
+    val customPermissions = jsonb("custom_permissions", Json.Default, CustomPermissions.serializer())
+    val allowedHours = jsonb("allowed_hours", Json.Default, AllowedHours.serializer()).nullable()
+    val twoFactorEnabled = bool("two_fa_enabled").default(false)
+    val twoFactorSecret = varchar("two_fa_secret", 32).nullable()
+    val twoFactorBackupCodes = jsonb("two_fa_backup_codes", Json.Default, ListSerializer(String.serializer()))
+    val twoFactorLastUsedStep = long("two_fa_last_used_step").nullable()
+    val isActive = bool("is_active").default(true)
+    val emailVerified = bool("email_verified").default(false)
+    val lastLoginAt = timestamp("last_login_at").nullable()
+    val failedLoginAttempts = integer("failed_login_attempts").default(0)
+    val lockedUntil = timestamp("locked_until").nullable()
+    val createdBy = uuid("created_by").nullable()
+    val createdAt = timestamp("created_at")
+    val updatedAt = timestamp("updated_at")
+}
+
+// Admin login sessions (created in V7__Add_Web_Platform_Tables.sql). Tokens are stored hashed.
+object AdminSessions : UUIDTable("web_admin.admin_sessions") {
+    val adminUserId = uuid("admin_user_id")
+    val sessionToken = varchar("session_token", 255).uniqueIndex()
+    val refreshToken = varchar("refresh_token", 255).nullable()
+    val ipAddress = inet("ip_address")
+    val userAgent = text("user_agent").nullable()
+    val deviceFingerprint = varchar("device_fingerprint", 255).nullable()
+    val expiresAt = timestamp("expires_at")
+    val lastActivity = timestamp("last_activity")
+    val isActive = bool("is_active").default(true)
+    val createdAt = timestamp("created_at")
+}
+
+/**
+ * PostgreSQL INET, read and written as its text form. Only the first address of a
+ * forwarded-for list is stored.
+ */
+private class InetColumnType : ColumnType() {
+    override fun sqlType(): String = "INET"
+
+    override fun valueFromDB(value: Any): Any = (value as? PGobject)?.value ?: value.toString()
+
+    override fun notNullValueToDB(value: Any): Any = PGobject().apply {
+        type = "inet"
+        this.value = value.toString().substringBefore(',').trim()
+    }
+}
+
+private fun Table.inet(name: String): Column<String> = registerColumn(name, InetColumnType())
@@ -0,0 +1,22 @@
+package com.wondernest.data.database.table.web
+
+import org.jetbrains.exposed.sql.Table
+import org.jetbrains.exposed.sql.kotlin.datetime.timestamp
+
+// Backfill job progress (created in V30__Add_Backfill_Progress.sql, run_id added in V59)
+object BackfillProgressTable : Table("web_audit.backfill_progress") {
+    val jobName = varchar("job_name", 100)
+    val cursor = text("cursor").nullable()
+    val status = varchar("status", 20).default("pending")
+    val processed = long("processed").default(0)
+    val updated = long("updated").default(0)
+    val batches = integer("batches").default(0)
+    val lastError = text("last_error").nullable()
+    val startedBy = uuid("started_by").nullable()
+    val startedAt = timestamp("started_at").nullable()
+    val updatedAt = timestamp("updated_at")
+    val completedAt = timestamp("completed_at").nullable()
+    val runId = uuid("run_id").nullable()
+
+    override val primaryKey = PrimaryKey(jobName)
+}
@@ -0,0 +1,43 @@
+package com.wondernest.data.database.table.web
+
+import kotlinx.serialization.json.Json
+import kotlinx.serialization.json.JsonObject
+import org.jetbrains.exposed.dao.id.UUIDTable
+import org.jetbrains.exposed.sql.json.jsonb
+import org.jetbrains.exposed.sql.kotlin.datetime.timestamp
+
+// Admin content workflow (schema created in V7__Add_Web_Platform_Tables.sql)
+object ContentItems : UUIDTable("content_workflow.content_items") {
+    val contentType = varchar("content_type", 50)
+    val title = varchar("title", 255)
+    val description = text("description").nullable()
+    val creatorId = uuid("creator_id")
+    val status = varchar("status", 50).default("draft")
+    val version = integer("version").default(1)
+    val publishedAt = timestamp("published_at").nullable()
+    val publishedBy = uuid("published_by").nullable()
+    val minAgeMonths = integer("min_age_months").default(24)
+    val maxAgeMonths = integer("max_age_months").default(144)
+    val tags = jsonb<List<String>>("tags", Json.Default).default(emptyList())
+    val languageCode = varchar("language_code", 10).default("en-US")
+    val createdAt = timestamp("created_at")
+    val updatedAt = timestamp("updated_at")
+}
+
+// Admin audit trail (web_audit.audit_log)
+object AdminAuditLog : UUIDTable("web_audit.audit_log") {
+    val userId = uuid("user_id")
+    val userType = varchar("user_type", 50)
+    val userEmail = varchar("user_email", 255).nullable()
+    val action = varchar("action", 100)
+    val resourceType = varchar("resource_type", 50).nullable()
+    val resourceId = uuid("resource_id").nullable()
+    val actionData = jsonb<JsonObject>("action_data", Json.Default).default(JsonObject(emptyMap()))
+    // ip_address is INET; it is recorded inside action_data instead
+    val userAgent = text("user_agent").nullable()
+    val sessionId = uuid("session_id").nullable()
+    val success = bool("success")
+    val errorMessage = text("error_message").nullable()
+    val severity = varchar("severity", 20).default("info")
+    val createdAt = timestamp("created_at")
+}
@@ -0,0 +1,33 @@
+package com.wondernest.domain.model
+
+import kotlinx.datetime.Instant
+import java.util.UUID
+
+/**
+ * Whether the parent's identity behind a consent decision has been verified.
+ * Only verified decisions change what a child's data may be used for.
+ */
+enum class ConsentVerificationStatus {
+    PENDING,
+    VERIFIED,
+    FAILED
+}
+
+/**
+ * A parent's grant or refusal of one COPPA consent type for a child
+ */
+data class ConsentRecord(
+    val id: UUID,
+    val familyId: UUID,
+    val childId: UUID,
+    val parentId: UUID,
+    val consentType: String,
+    val granted: Boolean,
+    val consentText: String? = null,
+    val createdAt: Instant,
+    val revokedAt: Instant? = null,
+    val verificationMethod: String? = null,
+    // Nothing counts until a verifier says so
+    val verificationStatus: ConsentVerificationStatus = ConsentVerificationStatus.PENDING,
+    val expiresAt: Instant? = null
+)
@@ -0,0 +1,47 @@
+package com.wondernest.domain.model
+
+import kotlinx.datetime.Instant
+import kotlinx.serialization.Serializable
+import java.util.UUID
+
+/**
+ * A developmental milestone children typically reach between two ages
+ */
+data class MilestoneDefinition(
+    val key: String,
+    val category: String, // language, motor, social, cognitive
+    val name: String,
+    val description: String,
+    val ageMonthsMin: Int,
+    val ageMonthsMax: Int
+)
+
+/**
+ * A milestone a parent marked achieved; [achievedAt] is when it was first marked
+ */
+data class AchievedMilestone(
+    val childId: UUID,
+    val key: String,
+    val achievedAt: Instant,
+    val childAgeMonths: Int
+)
+
+@Serializable
+data class MilestoneItem(
+    val key: String,
+    val category: String,
+    val name: String,
+    val description: String,
+    val ageMonthsMin: Int,
+    val ageMonthsMax: Int,
+    val achieved: Boolean = false,
+    val achievedAt: Instant? = null
+)
+
+@Serializable
+data class ChildMilestones(
+    val childId: String,
+    val ageMonths: Int,
+    val milestones: List<MilestoneItem>,
+    val nextGoals: List<String> = emptyList()
+)
@@ -15,6 +15,7 @@ data class UploadedFile(
     val originalName: String,
     val mimeType: String,
     val fileSize: Long,
+    val contentHash: String? = null,
     val storageProvider: String,
     val url: String? = null,
     val isPublic: Boolean = false,
@@ -23,7 +24,31 @@ data class UploadedFile(
     val uploadedAt: Instant,
     val accessedAt: Instant? = null,
     val deletedAt: Instant? = null
-)
+) {
+    /**
+     * Public files use their storage URL. Private files are only reachable through the
+     * authenticated download route, since storage URLs are served without auth.
+     */
+    fun accessUrl(): String? = if (isPublic) url else "/api/v1/files/$id/download"
+
+    /**
+     * Strong ETag from the content hash. Files uploaded before hashes were recorded get a
+     * weak one from key, size and upload time, which is still stable since files are immutable.
+     */
+    fun etag(): String = contentHash?.let { "\"$it\"" }
+        ?: "W/\"${fileKey.hashCode().toUInt().toString(16)}-$fileSize-${uploadedAt.toEpochMilliseconds()}\""
+
+    fun toDto() = UploadedFileDto(
+        id = id.toString(),
+        originalName = originalName,
+        mimeType = mimeType,
+        fileSize = fileSize,
+        category = category.toDbValue(),
+        url = accessUrl(),
+        uploadedAt = uploadedAt.toString(),
+        metadata = metadata
+    )
+}
 
 /**
  * File category enumeration
@@ -33,17 +58,26 @@ enum class FileCategory {
     CONTENT,
     DOCUMENT,
     GAME_ASSET,
-    ARTWORK;
+    ARTWORK,
+    AUDIO;
     
     companion object {
         fun fromString(value: String): FileCategory {
-            return when (value.lowercase()) {
-                "profile_picture" -> PROFILE_PICTURE
+            return fromStringOrNull(value) ?: CONTENT
+        }
+
+        /**
+         * Strict parse used for validating client input; returns null for unknown categories
+         */
+        fun fromStringOrNull(value: String): FileCategory? {
+            return when (value.trim().lowercase()) {
+                "profile_picture", "avatar" -> PROFILE_PICTURE
                 "content" -> CONTENT
                 "document" -> DOCUMENT
                 "game_asset" -> GAME_ASSET
-                "artwork" -> ARTWORK
-                else -> CONTENT
+                "artwork", "child_artwork" -> ARTWORK
+                "audio" -> AUDIO
+                else -> null
             }
         }
     }
@@ -55,6 +89,7 @@ enum class FileCategory {
             DOCUMENT -> "document"
             GAME_ASSET -> "game_asset"
             ARTWORK -> "artwork"
+            AUDIO -> "audio"
         }
     }
 }
@@ -37,11 +37,14 @@ data class User(
     val parentalConsentVerified: Boolean = false,
     val parentalConsentMethod: String? = null,
     val parentalConsentDate: Instant? = null,
-    val deletedAt: Instant? = null
+    val deletedAt: Instant? = null,
+    val failedLoginAttempts: Int = 0,
+    val lockedUntil: Instant? = null
 ) {
     val isActive: Boolean get() = status == UserStatus.ACTIVE && deletedAt == null
     val isDeleted: Boolean get() = deletedAt != null
     val fullName: String get() = listOfNotNull(firstName, lastName).joinToString(" ")
+
     val displayName: String get() = fullName.ifBlank { email.substringBefore("@") }
 }
 
@@ -69,4 +72,15 @@ data class PasswordResetToken(
     val used: Boolean = false,
     val expiresAt: Instant,
     val createdAt: Instant
-)
\ No newline at end of file
+)
+
+/**
+ * Only a hash of the token is stored, so the token itself never comes back from the database
+ */
+data class EmailVerificationToken(
+    val id: UUID,
+    val userId: UUID,
+    val expiresAt: Instant,
+    val usedAt: Instant? = null,
+    val createdAt: Instant
+)
@@ -0,0 +1,9 @@
+package com.wondernest.domain.repository
+
+import com.wondernest.api.analytics.DailyAnalytics
+import java.util.UUID
+
+interface AnalyticsRepository {
+    // Each active child's most recent day of metrics in one query, keyed by child id
+    suspend fun getLatestDailyAnalyticsByFamily(familyId: UUID): Map<UUID, DailyAnalytics>
+}
@@ -0,0 +1,13 @@
+package com.wondernest.domain.repository
+
+import com.wondernest.domain.model.ConsentRecord
+import com.wondernest.domain.model.ConsentVerificationStatus
+import kotlinx.datetime.Instant
+import java.util.UUID
+
+interface ConsentRepository {
+    suspend fun recordConsent(record: ConsentRecord): ConsentRecord
+    suspend fun getConsentRecordsByFamily(familyId: UUID): List<ConsentRecord>
+    suspend fun getConsentRecordsByChild(childId: UUID): List<ConsentRecord>
+    suspend fun updateVerification(id: UUID, status: ConsentVerificationStatus, expiresAt: Instant?)
+}
@@ -21,8 +21,13 @@ interface FamilyRepository {
     // Child profile management
     suspend fun createChildProfile(profile: ChildProfile): ChildProfile
     suspend fun getChildProfile(id: UUID): ChildProfile?
-    suspend fun getChildrenByFamily(familyId: UUID): List<ChildProfile>
+    // Archived children are left out unless asked for
+    suspend fun getChildrenByFamily(familyId: UUID, includeArchived: Boolean = false): List<ChildProfile>
     suspend fun updateChildProfile(profile: ChildProfile): ChildProfile?
     suspend fun archiveChildProfile(id: UUID): Boolean
+    suspend fun restoreChildProfile(id: UUID): Boolean
+    // Null when the child has no limit of their own
+    suspend fun getDailyScreenTimeLimit(childId: UUID): Int?
+    suspend fun setDailyScreenTimeLimit(childId: UUID, minutes: Int?): Boolean
     suspend fun deleteChildProfile(id: UUID): Boolean
 }
\ No newline at end of file
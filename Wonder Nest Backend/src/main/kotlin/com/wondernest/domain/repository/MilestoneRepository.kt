@@ -0,0 +1,13 @@
+package com.wondernest.domain.repository
+
+import com.wondernest.domain.model.AchievedMilestone
+import com.wondernest.domain.model.MilestoneDefinition
+import kotlinx.datetime.Instant
+import java.util.UUID
+
+interface MilestoneRepository {
+    suspend fun getAchievedMilestones(childId: UUID): List<AchievedMilestone>
+
+    // Marking an already achieved milestone returns the original record unchanged
+    suspend fun markAchieved(childId: UUID, milestone: MilestoneDefinition, achievedAt: Instant, childAgeMonths: Int): AchievedMilestone
+}
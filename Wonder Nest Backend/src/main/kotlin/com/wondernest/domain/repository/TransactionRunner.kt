@@ -0,0 +1,9 @@
+package com.wondernest.domain.repository
+
+/**
+ * Makes multi-step writes atomic. Repository calls made inside [inTransaction] join its
+ * transaction, which commits when the block returns and rolls back if it throws.
+ */
+interface TransactionRunner {
+    suspend fun <T> inTransaction(block: suspend () -> T): T
+}
@@ -3,6 +3,8 @@ package com.wondernest.domain.repository
 import com.wondernest.domain.model.User
 import com.wondernest.domain.model.UserSession
 import com.wondernest.domain.model.PasswordResetToken
+import com.wondernest.domain.model.EmailVerificationToken
+import kotlinx.datetime.Instant
 import java.util.*
 
 interface UserRepository {
@@ -16,6 +18,19 @@ interface UserRepository {
     suspend fun updateUserPassword(userId: UUID, passwordHash: String): Boolean
     suspend fun verifyUserEmail(userId: UUID): Boolean
     suspend fun updateLastLogin(userId: UUID): Boolean
+    suspend fun updateFailedLoginAttempts(userId: UUID, attempts: Int): Boolean
+    /**
+     * Count a failed login in the database and return the new count, or null for an unknown user.
+     * A lock that ran out before [now] is cleared and the count starts again at 1.
+     */
+    suspend fun incrementFailedLoginAttempts(userId: UUID, now: Instant): Int?
+    suspend fun lockUser(userId: UUID, lockedUntil: Instant): Boolean
+    suspend fun getUserPinHash(userId: UUID): String?
+    /**
+     * Store the parent-mode PIN hash unless the user already has one. Returns false, changing
+     * nothing, when a PIN is already set.
+     */
+    suspend fun setUserPinIfUnset(userId: UUID, pinHash: String): Boolean
     
     // Session management
     suspend fun createSession(session: UserSession): UserSession
@@ -29,6 +44,12 @@ interface UserRepository {
     suspend fun getPasswordResetToken(token: String): PasswordResetToken?
     suspend fun markPasswordResetTokenUsed(tokenId: UUID): Boolean
     suspend fun deleteExpiredPasswordResetTokens(): Int
+
+    // Email verification; tokens are stored hashed and looked up by the raw token
+    suspend fun createEmailVerificationToken(userId: UUID, token: String, expiresAt: Instant): EmailVerificationToken
+    suspend fun getEmailVerificationToken(token: String): EmailVerificationToken?
+    /** Returns false when the token was already used, so two concurrent confirmations can't both succeed */
+    suspend fun markEmailVerificationTokenUsed(tokenId: UUID, usedAt: Instant): Boolean
     
     // User search and listing
     suspend fun searchUsers(query: String, limit: Int = 50): List<User>
@@ -0,0 +1,45 @@
+package com.wondernest.domain.web
+
+import kotlinx.serialization.Serializable
+import java.time.Instant
+import java.util.*
+
+/**
+ * Pending invitation for a new admin. Only the SHA-256 hash of the emailed token is kept.
+ */
+data class AdminInvitation(
+    val id: UUID,
+    val email: String,
+    val role: AdminRole,
+    val tokenHash: String,
+    val invitedBy: UUID,
+    val expiresAt: Instant,
+    val acceptedAt: Instant? = null,
+    val createdAt: Instant
+) {
+    fun toResponse(emailSent: Boolean) = AdminInvitationResponse(
+        id = id.toString(),
+        email = email,
+        role = role.name.lowercase(),
+        expiresAt = expiresAt.toString(),
+        emailSent = emailSent
+    )
+}
+
+@Serializable
+data class AdminInvitationRequest(
+    val email: String,
+    val role: String
+)
+
+/**
+ * [emailSent] is false when the invitation was created but the email could not be delivered
+ */
+@Serializable
+data class AdminInvitationResponse(
+    val id: String,
+    val email: String,
+    val role: String,
+    val expiresAt: String,
+    val emailSent: Boolean
+)
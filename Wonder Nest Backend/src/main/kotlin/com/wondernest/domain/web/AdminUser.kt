@@ -1,7 +1,11 @@
 package com.wondernest.domain.web
 
 import kotlinx.serialization.Serializable
+import java.time.DayOfWeek
 import java.time.Instant
+import java.time.LocalTime
+import java.time.ZoneId
+import java.time.format.DateTimeParseException
 import java.util.*
 
 /**
@@ -17,8 +21,16 @@ data class AdminUser(
     val phoneNumber: String? = null,
     val role: AdminRole,
     val permissions: List<String>,
+    // Per-account overrides on top of the role's permissions
+    val customPermissions: CustomPermissions = CustomPermissions(),
+    // When set, logins and token refreshes are only accepted inside these windows
+    val allowedHours: AllowedHours? = null,
     val twoFactorEnabled: Boolean = false,
     val twoFactorSecret: String? = null,
+    // SHA-256 hashes of unused backup codes
+    val twoFactorBackupCodes: List<String> = emptyList(),
+    // TOTP time step of the last accepted code; codes for this step or earlier are refused
+    val twoFactorLastUsedStep: Long? = null,
     val isActive: Boolean = true,
     val emailVerified: Boolean = false,
     val lastLoginAt: Instant? = null,
@@ -35,16 +47,111 @@ data class AdminUser(
             firstName = firstName,
             lastName = lastName,
             role = role.name.lowercase(),
-            permissions = permissions,
+            permissions = effectivePermissions(),
             twoFactorEnabled = twoFactorEnabled
         )
     }
     
+    /**
+     * Role permissions plus stored and custom grants, minus custom denials.
+     * A denial always wins, even over a permission the role includes.
+     */
+    fun effectivePermissions(): List<String> {
+        val granted = AdminPermission.getRolePermissions(role).map { it.code } +
+            permissions + customPermissions.granted
+        return granted.distinct().filterNot { it in customPermissions.denied }
+    }
+    
     fun isLocked(): Boolean {
         return lockedUntil?.isAfter(Instant.now()) == true
     }
 }
 
+/**
+ * Permission codes granted to or denied from a single admin account, beyond their role
+ */
+@Serializable
+data class CustomPermissions(
+    val granted: List<String> = emptyList(),
+    val denied: List<String> = emptyList()
+) {
+    /**
+     * Codes that are not in the [AdminPermission] catalog
+     */
+    fun unknownCodes(): List<String> {
+        val known = AdminPermission.values().map { it.code }.toSet()
+        return (granted + denied).distinct().filterNot { it in known }
+    }
+}
+
+/**
+ * Weekly windows in which an admin may use the console, in the admin's own timezone:
+ *
+ * {"timezone": "Europe/London", "windows": [{"days": ["MONDAY", "FRIDAY"], "start": "22:00", "end": "06:00"}]}
+ *
+ * A window whose end is not after its start crosses midnight and runs into the following
+ * day, so the example covers Monday 22:00 to Tuesday 06:00 and Friday 22:00 to Saturday 06:00.
+ */
+@Serializable
+data class AllowedHours(
+    val timezone: String,
+    val windows: List<AllowedHoursWindow>
+) {
+    /**
+     * Throws [IllegalArgumentException] describing the first problem found
+     */
+    fun validate() {
+        try {
+            ZoneId.of(timezone)
+        } catch (e: Exception) {
+            throw IllegalArgumentException("Unknown timezone: $timezone")
+        }
+        require(windows.isNotEmpty()) { "At least one window is required" }
+        windows.forEach { it.validate() }
+    }
+
+    fun allows(instant: Instant): Boolean {
+        val local = instant.atZone(ZoneId.of(timezone))
+        return windows.any { it.contains(local.dayOfWeek, local.toLocalTime()) }
+    }
+}
+
+/**
+ * [days] are day names (MONDAY..SUNDAY) on which the window starts; [start] and [end] are HH:mm
+ */
+@Serializable
+data class AllowedHoursWindow(
+    val days: List<String>,
+    val start: String,
+    val end: String
+) {
+    fun validate() {
+        require(days.isNotEmpty()) { "A window needs at least one day" }
+        days.forEach { day ->
+            require(DayOfWeek.values().any { it.name == day.uppercase() }) { "Unknown day: $day" }
+        }
+        val startTime = parseTime(start)
+        require(startTime != parseTime(end)) { "A window's start and end must differ" }
+    }
+
+    fun contains(day: DayOfWeek, time: LocalTime): Boolean {
+        val startDays = days.map { DayOfWeek.valueOf(it.uppercase()) }.toSet()
+        val startTime = parseTime(start)
+        val endTime = parseTime(end)
+        return if (startTime < endTime) {
+            day in startDays && time >= startTime && time < endTime
+        } else {
+            (day in startDays && time >= startTime) || (day.minus(1) in startDays && time < endTime)
+        }
+    }
+
+    private fun parseTime(value: String): LocalTime = try {
+        LocalTime.parse(value)
+    } catch (e: DateTimeParseException) {
+        throw IllegalArgumentException("Times must be HH:mm, got $value")
+    }
+}
+
 /**
  * Admin roles with different permission levels
  */
@@ -77,8 +184,32 @@ data class AdminSession(
     val createdAt: Instant
 ) {
     fun isExpired(): Boolean = expiresAt.isBefore(Instant.now())
+
+    fun toView(currentSessionId: UUID?): AdminSessionView {
+        return AdminSessionView(
+            id = id.toString(),
+            ipAddress = ipAddress,
+            userAgent = userAgent,
+            createdAt = createdAt.toString(),
+            lastAccessedAt = lastActivity.toString(),
+            current = id == currentSessionId
+        )
+    }
 }
 
+/**
+ * Session as listed to admins; [current] marks the session the request was made with
+ */
+@Serializable
+data class AdminSessionView(
+    val id: String,
+    val ipAddress: String,
+    val userAgent: String? = null,
+    val createdAt: String,
+    val lastAccessedAt: String,
+    val current: Boolean
+)
+
 /**
  * Serializable admin user profile for API responses
  */
@@ -93,6 +224,16 @@ data class AdminUserProfile(
     val twoFactorEnabled: Boolean
 )
 
+/**
+ * Editable profile fields; null leaves a field unchanged and a blank phone number clears it
+ */
+@Serializable
+data class UpdateAdminProfileRequest(
+    val firstName: String? = null,
+    val lastName: String? = null,
+    val phoneNumber: String? = null
+)
+
 /**
  * Admin login request model
  */
@@ -103,6 +244,23 @@ data class AdminLoginRequest(
     val twoFactorCode: String? = null
 )
 
+/**
+ * Confirms two-factor enrollment with a code from the authenticator app
+ */
+@Serializable
+data class TwoFactorConfirmRequest(
+    val code: String
+)
+
+/**
+ * Backup codes are returned once, when two-factor authentication is enabled
+ */
+@Serializable
+data class TwoFactorConfirmResponse(
+    val twoFactorEnabled: Boolean,
+    val backupCodes: List<String>
+)
+
 /**
  * Admin login response model
  */
@@ -0,0 +1,79 @@
+package com.wondernest.domain.web
+
+import kotlinx.serialization.Serializable
+import java.time.Instant
+import java.util.*
+
+enum class AuditSeverity(val dbValue: String) {
+    INFO("info"),
+    WARNING("warning"),
+    ERROR("error");
+
+    companion object {
+        fun fromDbValue(value: String): AuditSeverity =
+            entries.firstOrNull { it.dbValue == value.lowercase() }
+                ?: throw IllegalArgumentException("Unknown audit severity: $value")
+    }
+}
+
+/**
+ * Audit-log filters; every field is optional. [from] is inclusive and [to] exclusive.
+ */
+data class AuditLogFilter(
+    val adminId: UUID? = null,
+    val action: String? = null,
+    val severity: AuditSeverity? = null,
+    val from: Instant? = null,
+    val to: Instant? = null
+)
+
+data class AuditLogEntry(
+    val id: UUID,
+    val adminId: UUID,
+    val action: String,
+    val resourceType: String?,
+    val resourceId: UUID?,
+    val details: Map<String, String>,
+    val severity: AuditSeverity,
+    val success: Boolean,
+    val errorMessage: String?,
+    val userAgent: String?,
+    val createdAt: Instant
+) {
+    fun toResponse() = AuditLogEntryResponse(
+        id = id.toString(),
+        adminId = adminId.toString(),
+        action = action,
+        resourceType = resourceType,
+        resourceId = resourceId?.toString(),
+        details = details,
+        severity = severity.dbValue,
+        success = success,
+        errorMessage = errorMessage,
+        userAgent = userAgent,
+        createdAt = createdAt.toString()
+    )
+}
+
+@Serializable
+data class AuditLogEntryResponse(
+    val id: String,
+    val adminId: String,
+    val action: String,
+    val resourceType: String? = null,
+    val resourceId: String? = null,
+    val details: Map<String, String> = emptyMap(),
+    val severity: String,
+    val success: Boolean,
+    val errorMessage: String? = null,
+    val userAgent: String? = null,
+    val createdAt: String
+)
+
+@Serializable
+data class AuditLogPage(
+    val entries: List<AuditLogEntryResponse>,
+    val page: Int,
+    val pageSize: Int,
+    val hasMore: Boolean
+)
@@ -0,0 +1,84 @@
+package com.wondernest.domain.web
+
+import kotlinx.serialization.Serializable
+import java.time.Instant
+import java.util.*
+
+enum class BackfillStatus(val dbValue: String) {
+    PENDING("pending"),
+    RUNNING("running"),
+    PAUSED("paused"),
+    COMPLETED("completed"),
+    FAILED("failed");
+
+    companion object {
+        fun fromDbValue(value: String): BackfillStatus =
+            entries.firstOrNull { it.dbValue == value.lowercase() }
+                ?: throw IllegalArgumentException("Unknown backfill status: $value")
+    }
+}
+
+/**
+ * Saved progress of a named backfill job. [cursor] is the id of the last row in the
+ * last completed batch; a resumed run continues strictly after it. [runId] identifies
+ * the run currently holding the job.
+ */
+data class BackfillProgress(
+    val jobName: String,
+    val cursor: String? = null,
+    val status: BackfillStatus = BackfillStatus.PENDING,
+    val processed: Long = 0,
+    val updated: Long = 0,
+    val batches: Int = 0,
+    val lastError: String? = null,
+    val startedBy: UUID? = null,
+    val startedAt: Instant? = null,
+    val updatedAt: Instant = Instant.now(),
+    val completedAt: Instant? = null,
+    val runId: UUID? = null
+) {
+    fun toResponse() = BackfillProgressResponse(
+        jobName = jobName,
+        status = status.dbValue,
+        cursor = cursor,
+        processed = processed,
+        updated = updated,
+        batches = batches,
+        lastError = lastError,
+        startedAt = startedAt?.toString(),
+        updatedAt = updatedAt.toString(),
+        completedAt = completedAt?.toString()
+    )
+}
+
+/**
+ * @param maxBatches stop (paused) after this many batches; the next run resumes from the cursor
+ * @param restart discard saved progress and start from the beginning
+ */
+@Serializable
+data class BackfillRunRequest(
+    val batchSize: Int = 100,
+    val maxBatches: Int? = null,
+    val restart: Boolean = false
+)
+
+@Serializable
+data class BackfillProgressResponse(
+    val jobName: String,
+    val status: String,
+    val cursor: String?,
+    val processed: Long,
+    val updated: Long,
+    val batches: Int,
+    val lastError: String?,
+    val startedAt: String?,
+    val updatedAt: String,
+    val completedAt: String?
+)
+
+@Serializable
+data class BackfillJobInfo(
+    val name: String,
+    val description: String,
+    val progress: BackfillProgressResponse? = null
+)
@@ -0,0 +1,99 @@
+package com.wondernest.domain.web
+
+import kotlinx.serialization.Serializable
+import java.time.Instant
+import java.util.*
+
+/**
+ * Content item managed through the admin content workflow
+ */
+data class ContentItem(
+    val id: UUID,
+    val contentType: String,
+    val title: String,
+    val description: String? = null,
+    val creatorId: UUID,
+    val status: ContentStatus,
+    val publishedAt: Instant? = null,
+    val publishedBy: UUID? = null,
+    val minAgeMonths: Int = 24,
+    val maxAgeMonths: Int = 144,
+    val tags: List<String> = emptyList(),
+    val languageCode: String = "en-US",
+    val createdAt: Instant,
+    val updatedAt: Instant
+)
+
+/**
+ * Workflow status of a content item (matches content_workflow.content_items.status)
+ */
+enum class ContentStatus(val dbValue: String) {
+    DRAFT("draft"),
+    IN_REVIEW("in_review"),
+    NEEDS_REVISION("needs_revision"),
+    APPROVED("approved"),
+    PUBLISHED("published"),
+    ARCHIVED("archived"),
+    DELETED("deleted");
+
+    fun canTransitionTo(target: ContentStatus): Boolean {
+        if (this == target) return false
+        return when (this) {
+            DRAFT -> target in setOf(IN_REVIEW, ARCHIVED, DELETED)
+            IN_REVIEW -> target in setOf(NEEDS_REVISION, APPROVED, DRAFT, ARCHIVED, DELETED)
+            NEEDS_REVISION -> target in setOf(DRAFT, IN_REVIEW, ARCHIVED, DELETED)
+            APPROVED -> target in setOf(PUBLISHED, NEEDS_REVISION, ARCHIVED, DELETED)
+            PUBLISHED -> target in setOf(ARCHIVED, DELETED)
+            ARCHIVED -> target in setOf(DRAFT, PUBLISHED, DELETED)
+            DELETED -> false
+        }
+    }
+
+    companion object {
+        fun fromDbValue(value: String): ContentStatus =
+            entries.firstOrNull { it.dbValue == value.lowercase() }
+                ?: throw IllegalArgumentException("Unknown content status: $value")
+    }
+}
+
+/**
+ * Filter for selecting content items in admin bulk operations
+ */
+data class ContentItemFilter(
+    val creatorId: UUID? = null,
+    val contentType: String? = null,
+    val status: ContentStatus? = null,
+    val createdAfter: Instant? = null,
+    val createdBefore: Instant? = null
+) {
+    fun isEmpty(): Boolean =
+        creatorId == null && contentType == null && status == null && createdAfter == null && createdBefore == null
+}
+
+@Serializable
+data class BulkStatusTransitionRequest(
+    val targetStatus: String,
+    val creatorId: String? = null,
+    val contentType: String? = null,
+    val currentStatus: String? = null,
+    val createdAfter: String? = null,
+    val createdBefore: String? = null,
+    val batchSize: Int = 100,
+    val reason: String? = null
+)
+
+@Serializable
+data class BulkTransitionFailure(
+    val contentId: String,
+    val reason: String
+)
+
+@Serializable
+data class BulkStatusTransitionResult(
+    val targetStatus: String,
+    val matched: Int,
+    val updated: Int,
+    val failed: Int,
+    val batches: Int,
+    val failures: List<BulkTransitionFailure>
+)
@@ -61,6 +61,8 @@ data class ContentPack(
     // Status and timestamps
     val status: String = "draft",
     @Contextual val publishedAt: Instant? = null,
+    @Contextual val expiresAt: Instant? = null, // Hidden from listings once passed; owners keep access
+    @Contextual val featuredUntil: Instant? = null, // End of the featured window; null keeps it featured
     @Contextual val createdAt: Instant,
     @Contextual val updatedAt: Instant,
     @Contextual val createdBy: UUID? = null,
@@ -76,7 +78,12 @@ data class ContentPack(
     val assets: List<ContentPackAsset> = emptyList(),
     val userOwnership: UserPackOwnership? = null,
     val userReview: ContentPackReview? = null
-)
+) {
+    fun isExpired(now: Instant = Instant.now()): Boolean = expiresAt?.let { !it.isAfter(now) } ?: false
+
+    fun isCurrentlyFeatured(now: Instant = Instant.now()): Boolean =
+        isFeatured && (featuredUntil?.isAfter(now) ?: true)
+}
 
 @Serializable
 data class ContentPackAsset(
@@ -195,6 +202,7 @@ data class ContentPackReview(
     @Contextual val id: UUID,
     @Contextual val packId: UUID,
     @Contextual val userId: UUID,
+    @Contextual val familyId: UUID? = null,
     
     // Review content
     val rating: Int,
@@ -220,6 +228,55 @@ data class ContentPackReview(
 )
 
 // Request/Response DTOs
+@Serializable
+data class ContentPackReviewRequest(
+    val rating: Int,
+    val reviewText: String? = null,
+    val reviewTitle: String? = null,
+    val childAgeRange: String? = null
+)
+
+/**
+ * [firstInstall] is false when the family had already installed the pack, in which case
+ * the download count is unchanged
+ */
+@Serializable
+data class PackInstallResponse(
+    @Contextual val packId: UUID,
+    val downloadCount: Long,
+    val firstInstall: Boolean
+)
+
+@Serializable
+data class ContentPackRatingRequest(
+    val rating: Int
+)
+
+/**
+ * Aggregate rating for a pack, computed from every stored rating
+ */
+@Serializable
+data class ContentPackRatingSummary(
+    @Contextual val packId: UUID,
+    @Contextual val ratingAverage: BigDecimal,
+    val ratingCount: Int,
+    val userRating: Int? = null
+)
+
+@Serializable
+data class ContentPackReviewVoteRequest(
+    val helpful: Boolean
+)
+
+@Serializable
+data class ContentPackReviewListResponse(
+    val reviews: List<ContentPackReview>,
+    val total: Long,
+    val page: Int,
+    val size: Int,
+    val hasNext: Boolean
+)
+
 @Serializable
 data class ContentPackSearchRequest(
     val query: String? = null,
@@ -243,7 +300,18 @@ data class ContentPackSearchResponse(
     val total: Long,
     val page: Int,
     val size: Int,
-    val hasNext: Boolean
+    val hasNext: Boolean,
+    val facets: ContentPackSearchFacets = ContentPackSearchFacets()
+)
+
+/**
+ * Pack counts per facet value over the filtered results
+ */
+@Serializable
+data class ContentPackSearchFacets(
+    val category: Map<String, Int> = emptyMap(),
+    val packType: Map<String, Int> = emptyMap(),
+    val isFree: Map<String, Int> = emptyMap()
 )
 
 @Serializable
@@ -32,4 +32,14 @@ data class AssetsData(
 @Serializable
 data class MessageData(
     val message: String
-)
\ No newline at end of file
+)
+
+@Serializable
+data class ReviewData(
+    val review: ContentPackReview
+)
+
+@Serializable
+data class RatingData(
+    val rating: ContentPackRatingSummary
+)
@@ -1,7 +1,12 @@
 package com.wondernest.routes
 
 import com.wondernest.models.*
+import com.wondernest.services.ContentPackBundleService
+import com.wondernest.services.ContentPackRatingService
+import com.wondernest.services.ContentPackReviewService
 import com.wondernest.services.ContentPackServiceSimple
+import com.wondernest.services.ReviewAlreadyExistsException
+import com.wondernest.services.ReviewRejectedException
 import io.ktor.http.*
 import io.ktor.server.application.*
 import io.ktor.server.auth.*
@@ -14,6 +19,9 @@ import java.util.UUID
 
 fun Route.contentPackRoutes() {
     val contentPackService by inject<ContentPackServiceSimple>()
+    val reviewService by inject<ContentPackReviewService>()
+    val ratingService by inject<ContentPackRatingService>()
+    val bundleService by inject<ContentPackBundleService>()
 
     route("/content-packs") {
         authenticate("auth-jwt") {
@@ -248,6 +256,39 @@ fun Route.contentPackRoutes() {
                 }
             }
 
+            // Record that the family installed a pack; only the first install per family counts as a download
+            post("/{packId}/install") {
+                try {
+                    val familyId = call.principal<JWTPrincipal>()?.payload?.getClaim("familyId")?.asString()
+                        ?.let { UUID.fromString(it) }
+                        ?: throw IllegalArgumentException("No family context in token")
+
+                    val packId = call.parameters["packId"]?.let { UUID.fromString(it) }
+                        ?: throw IllegalArgumentException("Invalid pack ID")
+
+                    call.respond(
+                        HttpStatusCode.OK,
+                        ContentPackResponse(
+                            success = true,
+                            data = contentPackService.recordInstall(familyId, packId)
+                        )
+                    )
+                } catch (e: NoSuchElementException) {
+                    call.respond(
+                        HttpStatusCode.NotFound,
+                        ContentPackResponse<PackInstallResponse>(success = false, error = e.message)
+                    )
+                } catch (e: Exception) {
+                    call.respond(
+                        HttpStatusCode.BadRequest,
+                        ContentPackResponse<PackInstallResponse>(
+                            success = false,
+                            error = "Failed to record install: ${e.message}"
+                        )
+                    )
+                }
+            }
+
             // Update download status
             patch("/{packId}/download") {
                 try {
@@ -339,6 +380,231 @@ fun Route.contentPackRoutes() {
                 }
             }
 
+            // Download an owned pack as a ZIP with a manifest, streamed straight from storage
+            get("/{packId}/bundle") {
+                val userId = call.principal<JWTPrincipal>()?.payload?.getClaim("userId")?.asString()
+                    ?.let { runCatching { UUID.fromString(it) }.getOrNull() }
+                    ?: return@get call.respond(
+                        HttpStatusCode.Unauthorized,
+                        ContentPackResponse<MessageData>(success = false, error = "User ID not found in token")
+                    )
+                val packId = call.parameters["packId"]?.let { runCatching { UUID.fromString(it) }.getOrNull() }
+                    ?: return@get call.respond(
+                        HttpStatusCode.BadRequest,
+                        ContentPackResponse<MessageData>(success = false, error = "Invalid pack ID")
+                    )
+
+                val pack = contentPackService.getPackById(packId, userId)
+                    ?.takeIf { contentPackService.ownsPack(userId, packId) }
+                    ?: return@get call.respond(
+                        HttpStatusCode.Forbidden,
+                        ContentPackResponse<MessageData>(success = false, error = "Pack not owned or not found")
+                    )
+                val assets = contentPackService.getPackAssets(packId, userId).orEmpty()
+
+                call.response.header(
+                    HttpHeaders.ContentDisposition,
+                    ContentDisposition.Attachment
+                        .withParameter(ContentDisposition.Parameters.FileName, "pack-$packId.zip")
+                        .toString()
+                )
+                call.respondOutputStream(ContentType.Application.Zip) {
+                    bundleService.writeBundle(pack, assets, this)
+                }
+            }
+
+            // List published reviews for a pack
+            get("/{packId}/reviews") {
+                try {
+                    val packId = call.parameters["packId"]?.let { UUID.fromString(it) }
+                        ?: throw IllegalArgumentException("Invalid pack ID")
+                    val page = call.request.queryParameters["page"]?.toIntOrNull() ?: 0
+                    val size = call.request.queryParameters["size"]?.toIntOrNull() ?: 20
+
+                    call.respond(
+                        HttpStatusCode.OK,
+                        ContentPackResponse(
+                            success = true,
+                            data = reviewService.listReviews(packId, page, size)
+                        )
+                    )
+                } catch (e: Exception) {
+                    call.respond(
+                        HttpStatusCode.BadRequest,
+                        ContentPackResponse<ContentPackReviewListResponse>(
+                            success = false,
+                            error = "Failed to fetch reviews: ${e.message}"
+                        )
+                    )
+                }
+            }
+
+            // Submit a review (one per family, pack must be owned)
+            post("/{packId}/reviews") {
+                try {
+                    val principal = call.principal<JWTPrincipal>()
+                    val userId = principal?.payload?.getClaim("userId")?.asString()
+                        ?.let { UUID.fromString(it) }
+                        ?: throw IllegalArgumentException("User ID not found in token")
+                    val familyId = principal.payload.getClaim("familyId")?.asString()
+                        ?.let { UUID.fromString(it) }
+                        ?: throw IllegalArgumentException("No family context in token")
+
+                    val packId = call.parameters["packId"]?.let { UUID.fromString(it) }
+                        ?: throw IllegalArgumentException("Invalid pack ID")
+
+                    val request = call.receive<ContentPackReviewRequest>()
+                    val review = reviewService.submitReview(userId, familyId, packId, request)
+
+                    call.respond(
+                        HttpStatusCode.Created,
+                        ContentPackResponse(success = true, data = ReviewData(review))
+                    )
+                } catch (e: SecurityException) {
+                    call.respond(
+                        HttpStatusCode.Forbidden,
+                        ContentPackResponse<ReviewData>(success = false, error = e.message)
+                    )
+                } catch (e: ReviewAlreadyExistsException) {
+                    call.respond(
+                        HttpStatusCode.Conflict,
+                        ContentPackResponse<ReviewData>(success = false, error = e.message)
+                    )
+                } catch (e: ReviewRejectedException) {
+                    call.respond(
+                        HttpStatusCode.UnprocessableEntity,
+                        ContentPackResponse<ReviewData>(success = false, error = e.message)
+                    )
+                } catch (e: Exception) {
+                    call.respond(
+                        HttpStatusCode.BadRequest,
+                        ContentPackResponse<ReviewData>(
+                            success = false,
+                            error = "Failed to submit review: ${e.message}"
+                        )
+                    )
+                }
+            }
+
+            // Rate a pack 1-5; rating again replaces the parent's previous rating
+            post("/{packId}/rating") {
+                try {
+                    val principal = call.principal<JWTPrincipal>()
+                    val userId = principal?.payload?.getClaim("userId")?.asString()
+                        ?.let { UUID.fromString(it) }
+                        ?: throw IllegalArgumentException("User ID not found in token")
+                    val role = principal.payload.getClaim("role")?.asString()
+                    if (role != null && !role.equals("parent", ignoreCase = true)) {
+                        throw SecurityException("Only parents can rate content packs")
+                    }
+
+                    val packId = call.parameters["packId"]?.let { UUID.fromString(it) }
+                        ?: throw IllegalArgumentException("Invalid pack ID")
+
+                    val request = call.receive<ContentPackRatingRequest>()
+                    val summary = ratingService.submitRating(userId, packId, request.rating)
+
+                    call.respond(
+                        HttpStatusCode.OK,
+                        ContentPackResponse(success = true, data = RatingData(summary))
+                    )
+                } catch (e: SecurityException) {
+                    call.respond(
+                        HttpStatusCode.Forbidden,
+                        ContentPackResponse<RatingData>(success = false, error = e.message)
+                    )
+                } catch (e: NoSuchElementException) {
+                    call.respond(
+                        HttpStatusCode.NotFound,
+                        ContentPackResponse<RatingData>(success = false, error = e.message)
+                    )
+                } catch (e: Exception) {
+                    call.respond(
+                        HttpStatusCode.BadRequest,
+                        ContentPackResponse<RatingData>(
+                            success = false,
+                            error = "Failed to submit rating: ${e.message}"
+                        )
+                    )
+                }
+            }
+
+            // Edit the family's review
+            put("/{packId}/reviews/{reviewId}") {
+                try {
+                    val familyId = call.principal<JWTPrincipal>()?.payload?.getClaim("familyId")?.asString()
+                        ?.let { UUID.fromString(it) }
+                        ?: throw IllegalArgumentException("No family context in token")
+
+                    val reviewId = call.parameters["reviewId"]?.let { UUID.fromString(it) }
+                        ?: throw IllegalArgumentException("Invalid review ID")
+
+                    val request = call.receive<ContentPackReviewRequest>()
+                    val review = reviewService.updateReview(familyId, reviewId, request)
+
+                    call.respond(
+                        HttpStatusCode.OK,
+                        ContentPackResponse(success = true, data = ReviewData(review))
+                    )
+                } catch (e: SecurityException) {
+                    call.respond(
+                        HttpStatusCode.Forbidden,
+                        ContentPackResponse<ReviewData>(success = false, error = e.message)
+                    )
+                } catch (e: NoSuchElementException) {
+                    call.respond(
+                        HttpStatusCode.NotFound,
+                        ContentPackResponse<ReviewData>(success = false, error = e.message)
+                    )
+                } catch (e: ReviewRejectedException) {
+                    call.respond(
+                        HttpStatusCode.UnprocessableEntity,
+                        ContentPackResponse<ReviewData>(success = false, error = e.message)
+                    )
+                } catch (e: Exception) {
+                    call.respond(
+                        HttpStatusCode.BadRequest,
+                        ContentPackResponse<ReviewData>(
+                            success = false,
+                            error = "Failed to update review: ${e.message}"
+                        )
+                    )
+                }
+            }
+
+            // Mark a review as helpful / not helpful
+            post("/reviews/{reviewId}/vote") {
+                try {
+                    val userId = call.principal<JWTPrincipal>()?.payload?.getClaim("userId")?.asString()
+                        ?.let { UUID.fromString(it) }
+                        ?: throw IllegalArgumentException("User ID not found in token")
+
+                    val reviewId = call.parameters["reviewId"]?.let { UUID.fromString(it) }
+                        ?: throw IllegalArgumentException("Invalid review ID")
+
+                    val request = call.receive<ContentPackReviewVoteRequest>()
+                    val review = reviewService.voteOnReview(reviewId, userId, request.helpful)
+
+                    call.respond(
+                        HttpStatusCode.OK,
+                        ContentPackResponse(success = true, data = ReviewData(review))
+                    )
+                } catch (e: NoSuchElementException) {
+                    call.respond(
+                        HttpStatusCode.NotFound,
+                        ContentPackResponse<ReviewData>(success = false, error = e.message)
+                    )
+                } catch (e: Exception) {
+                    call.respond(
+                        HttpStatusCode.BadRequest,
+                        ContentPackResponse<ReviewData>(
+                            success = false,
+                            error = "Failed to record vote: ${e.message}"
+                        )
+                    )
+                }
+            }
+
             // Record pack usage
             post("/usage") {
                 try {
@@ -1,5 +1,6 @@
 package com.wondernest.server.api
 
+import com.wondernest.data.database.table.FileReferences
 import com.wondernest.data.database.table.UploadedFiles
 import com.wondernest.data.database.table.Users
 import com.wondernest.data.database.table.ChildProfiles
@@ -8,6 +9,9 @@ import com.wondernest.server.domain.model.*
 import com.wondernest.server.service.FileTagService
 import com.wondernest.server.utils.respondError
 import com.wondernest.server.utils.respondSuccess
+import com.wondernest.services.storage.FileReferenceService
+import com.wondernest.services.storage.FileValidationService
+import com.wondernest.services.storage.UnknownFileCategoryException
 import io.ktor.http.*
 import io.ktor.http.content.*
 import io.ktor.server.application.*
@@ -21,6 +25,7 @@ import kotlinx.serialization.Serializable
 import org.jetbrains.exposed.sql.*
 import org.jetbrains.exposed.sql.SqlExpressionBuilder.eq
 import org.jetbrains.exposed.sql.transactions.transaction
+import org.koin.ktor.ext.inject
 import org.slf4j.LoggerFactory
 import java.io.File
 import java.util.*
@@ -29,6 +34,9 @@ private val logger = LoggerFactory.getLogger("FileRoutes")
 private val fileTagService = FileTagService()
 
 fun Route.fileRoutes() {
+    val fileValidationService by inject<FileValidationService>()
+    val fileReferenceService by inject<FileReferenceService>()
+
     authenticate("auth-jwt") {
         route("/api/v2/files") {
             // Upload file with tags
@@ -68,6 +76,13 @@ fun Route.fileRoutes() {
                     part.dispose()
                 }
 
+                // Validate category against the configured allow-list
+                category = try {
+                    fileValidationService.resolveCategory(category).toDbValue()
+                } catch (e: UnknownFileCategoryException) {
+                    return@post call.respondError(HttpStatusCode.BadRequest, e.message ?: "Unknown category", "INVALID_CATEGORY")
+                }
+
                 // Validate tags
                 val validationResult = fileTagService.validateTags(tags, isSystemImage = false)
                 if (!validationResult.isValid) {
@@ -177,31 +192,28 @@ fun Route.fileRoutes() {
                 val fileId = call.parameters["fileId"]?.let { UUID.fromString(it) }
                     ?: return@get call.respondError(HttpStatusCode.BadRequest, "Invalid file ID")
 
-                val usage = transaction {
+                val owned = transaction {
                     // Check if file exists and belongs to user
-                    val file = UploadedFiles
+                    UploadedFiles
                         .select { 
                             (UploadedFiles.id eq fileId) and
                             (UploadedFiles.userId eq userId)
                         }
-                        .firstOrNull()
-                        ?: return@transaction null
-
-                    // Get stories using this file
-                    val usedInStories = getStoriesUsingFile(fileId)
-                    
-                    FileUsageResponse(
-                        fileId = fileId.toString(),
-                        usageCount = usedInStories.size,
-                        usedInStories = usedInStories
-                    )
+                        .any()
                 }
 
-                if (usage == null) {
+                if (!owned) {
                     return@get call.respondError(HttpStatusCode.NotFound, "File not found")
                 }
 
-                call.respondSuccess(usage)
+                val references = fileReferenceService.getComprehensiveReferences(fileId)
+                call.respondSuccess(
+                    FileUsageResponse(
+                        fileId = fileId.toString(),
+                        usageCount = references.referenceCount,
+                        usedInStories = references.stories.map { it.referenceId.toString() }
+                    )
+                )
             }
 
             // Delete file with soft delete option
@@ -283,19 +295,9 @@ fun Route.fileRoutes() {
     }
 }
 
-// Helper function to get file usage count in stories
-private fun getFileUsageCount(fileId: UUID): Int {
-    // This would query the stories tables to count usage
-    // For now, return 0 as stories table integration is pending
-    return 0
-}
-
-// Helper function to get stories using a file
-private fun getStoriesUsingFile(fileId: UUID): List<String> {
-    // This would query the stories tables to find usage
-    // For now, return empty list as stories table integration is pending
-    return emptyList()
-}
+// Counts stories and other content referencing a file; must run inside a transaction
+private fun getFileUsageCount(fileId: UUID): Int =
+    FileReferences.select { FileReferences.fileId eq fileId }.count().toInt()
 
 @Serializable
 data class FileUsageResponse(
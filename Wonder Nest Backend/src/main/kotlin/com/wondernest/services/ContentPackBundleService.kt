@@ -0,0 +1,100 @@
+package com.wondernest.services
+
+import com.wondernest.models.ContentPack
+import com.wondernest.models.ContentPackAsset
+import com.wondernest.services.storage.StorageProvider
+import kotlinx.serialization.Serializable
+import kotlinx.serialization.encodeToString
+import kotlinx.serialization.json.Json
+import mu.KotlinLogging
+import java.io.OutputStream
+import java.time.Instant
+import java.util.zip.ZipEntry
+import java.util.zip.ZipOutputStream
+
+private val logger = KotlinLogging.logger {}
+
+@Serializable
+data class BundleManifest(
+    val packId: String,
+    val name: String,
+    val packType: String,
+    val generatedAt: String,
+    val assets: List<BundleManifestAsset>
+)
+
+/**
+ * [path] is the entry inside the archive; it's null for assets that live outside our storage
+ * (or are missing from it), in which case [sourceUrl] tells the client where to fetch them.
+ */
+@Serializable
+data class BundleManifestAsset(
+    val id: String,
+    val name: String,
+    val assetType: String,
+    val fileFormat: String?,
+    val path: String?,
+    val sourceUrl: String?
+)
+
+/**
+ * Writes a content pack as a ZIP: manifest.json first, then each stored asset under assets/.
+ * Asset bytes are copied straight from the storage stream into the archive, so a pack is never
+ * held in memory. An asset's fileUrl is treated as a storage key unless it's an absolute URL.
+ */
+class ContentPackBundleService(
+    private val storageProvider: StorageProvider,
+    private val clock: () -> Instant = { Instant.now() }
+) {
+    private val json = Json { prettyPrint = true }
+
+    suspend fun writeBundle(pack: ContentPack, assets: List<ContentPackAsset>, out: OutputStream) {
+        // Resolve entries up front so the manifest is accurate before any asset bytes are written
+        val entries = assets.map { asset -> asset to entryPath(asset)?.takeIf { storageProvider.exists(asset.fileUrl) } }
+        val manifest = BundleManifest(
+            packId = pack.id.toString(),
+            name = pack.name,
+            packType = pack.packType,
+            generatedAt = clock().toString(),
+            assets = entries.map { (asset, path) ->
+                BundleManifestAsset(
+                    id = asset.id.toString(),
+                    name = asset.name,
+                    assetType = asset.assetType,
+                    fileFormat = asset.fileFormat,
+                    path = path,
+                    sourceUrl = if (path == null) asset.fileUrl else null
+                )
+            }
+        )
+
+        val zip = ZipOutputStream(out)
+        zip.putNextEntry(ZipEntry(MANIFEST_ENTRY))
+        zip.write(json.encodeToString(manifest).toByteArray())
+        zip.closeEntry()
+
+        for ((asset, path) in entries) {
+            if (path == null) continue
+            val download = storageProvider.downloadStream(asset.fileUrl)
+            if (download == null) {
+                // Deleted between the manifest and now; the archive just lacks the entry
+                logger.warn { "Asset ${asset.id} of pack ${pack.id} disappeared while bundling" }
+                continue
+            }
+            zip.putNextEntry(ZipEntry(path))
+            download.stream.use { it.copyTo(zip) }
+            zip.closeEntry()
+        }
+        zip.finish()
+    }
+
+    private fun entryPath(asset: ContentPackAsset): String? {
+        if (asset.fileUrl.contains("://")) return null
+        val extension = asset.fileFormat?.takeIf { it.isNotBlank() }?.let { ".$it" } ?: ""
+        return "assets/${asset.id}$extension"
+    }
+
+    companion object {
+        const val MANIFEST_ENTRY = "manifest.json"
+    }
+}
@@ -0,0 +1,68 @@
+package com.wondernest.services
+
+import com.wondernest.data.database.table.ContentPacksTable
+import kotlinx.coroutines.CancellationException
+import kotlinx.coroutines.CoroutineScope
+import kotlinx.coroutines.Dispatchers
+import kotlinx.coroutines.Job
+import kotlinx.coroutines.delay
+import kotlinx.coroutines.isActive
+import kotlinx.coroutines.launch
+import kotlinx.datetime.toKotlinInstant
+import mu.KotlinLogging
+import org.jetbrains.exposed.sql.and
+import org.jetbrains.exposed.sql.or
+import org.jetbrains.exposed.sql.transactions.experimental.newSuspendedTransaction
+import org.jetbrains.exposed.sql.update
+import java.time.Instant
+
+private val logger = KotlinLogging.logger {}
+
+/**
+ * Periodically demotes content packs whose expiry or featured window has passed so they stop
+ * being featured. Listings and search also filter on both, so this only tidies up promotion flags;
+ * ownership is untouched and families who acquired a pack keep it.
+ */
+class ContentPackExpiryTask(
+    private val intervalMillis: Long = DEFAULT_INTERVAL_MILLIS,
+    private val demoteExpired: suspend (Instant) -> Int = ::demoteExpiredPacks,
+    private val clock: () -> Instant = Instant::now
+) {
+
+    suspend fun runOnce(): Int {
+        val demoted = demoteExpired(clock())
+        if (demoted > 0) logger.info { "Demoted $demoted expired content packs" }
+        return demoted
+    }
+
+    fun start(scope: CoroutineScope): Job = scope.launch {
+        while (isActive) {
+            try {
+                runOnce()
+            } catch (e: CancellationException) {
+                throw e
+            } catch (e: Exception) {
+                logger.warn(e) { "Content pack expiry run failed, retrying next interval" }
+            }
+            delay(intervalMillis)
+        }
+    }
+
+    companion object {
+        const val DEFAULT_INTERVAL_MILLIS = 15 * 60 * 1000L
+
+        fun fromEnvironment(env: Map<String, String> = System.getenv()) = ContentPackExpiryTask(
+            intervalMillis = env["CONTENT_EXPIRY_INTERVAL_MINUTES"]?.toLongOrNull()?.takeIf { it > 0 }
+                ?.let { it * 60_000 } ?: DEFAULT_INTERVAL_MILLIS
+        )
+    }
+}
+
+private suspend fun demoteExpiredPacks(now: Instant): Int = newSuspendedTransaction(Dispatchers.IO) {
+    val cutoff = now.toKotlinInstant()
+    val stale = (ContentPacksTable.expiresAt lessEq cutoff) or (ContentPacksTable.featuredUntil lessEq cutoff)
+    ContentPacksTable.update({ stale and (ContentPacksTable.isFeatured eq true) }) {
+        it[isFeatured] = false
+        it[updatedAt] = cutoff
+    }
+}
@@ -0,0 +1,49 @@
+package com.wondernest.services
+
+import com.wondernest.models.ContentPackRatingSummary
+import java.math.BigDecimal
+import java.time.Instant
+import java.util.UUID
+
+/**
+ * Star ratings for content packs, one per parent per pack, stored in content_pack_ratings.
+ * The pack's aggregate is recomputed from all stored ratings on every submission rather
+ * than adjusted incrementally.
+ */
+class ContentPackRatingService(
+    private val contentPackService: ContentPackServiceSimple,
+    private val store: ContentPackStatsStore = DatabaseContentPackStatsStore,
+    private val clock: () -> Instant = Instant::now
+) {
+
+    /**
+     * Store or replace [userId]'s rating for [packId] and return the updated aggregate
+     */
+    fun submitRating(userId: UUID, packId: UUID, rating: Int): ContentPackRatingSummary {
+        require(rating in MIN_RATING..MAX_RATING) { "Rating must be between $MIN_RATING and $MAX_RATING" }
+        if (!contentPackService.packExists(packId)) throw NoSuchElementException("Pack not found")
+
+        val stats = store.upsertRating(packId, userId, rating, clock())
+        return ContentPackRatingSummary(
+            packId = packId,
+            ratingAverage = stats.ratingAverage,
+            ratingCount = stats.ratingCount,
+            userRating = rating
+        )
+    }
+
+    fun summarize(packId: UUID, userId: UUID? = null): ContentPackRatingSummary {
+        val stats = store.stats(listOf(packId))[packId]
+        return ContentPackRatingSummary(
+            packId = packId,
+            ratingAverage = stats?.ratingAverage ?: BigDecimal.ZERO.setScale(2),
+            ratingCount = stats?.ratingCount ?: 0,
+            userRating = userId?.let { store.findRating(packId, it) }
+        )
+    }
+
+    companion object {
+        const val MIN_RATING = 1
+        const val MAX_RATING = 5
+    }
+}
@@ -0,0 +1,123 @@
+package com.wondernest.services
+
+import com.wondernest.models.*
+import com.wondernest.utils.ContentModeration
+import java.time.Instant
+import java.util.UUID
+
+/**
+ * Family reviews for content packs, stored in content_pack_reviews with helpful votes in
+ * content_pack_review_votes. Reviews are moderated for profanity/PII before publishing and
+ * limited to one per family per pack.
+ */
+class ContentPackReviewService(
+    private val contentPackService: ContentPackServiceSimple,
+    private val store: ContentPackReviewStore = DatabaseContentPackReviewStore,
+    private val clock: () -> Instant = Instant::now
+) {
+
+    fun submitReview(
+        userId: UUID,
+        familyId: UUID,
+        packId: UUID,
+        request: ContentPackReviewRequest
+    ): ContentPackReview {
+        validateReview(request)
+
+        if (!contentPackService.ownsPack(userId, packId)) {
+            throw SecurityException("Only families who own this pack can review it")
+        }
+
+        val now = clock()
+        val review = ContentPackReview(
+            id = UUID.randomUUID(),
+            packId = packId,
+            userId = userId,
+            familyId = familyId,
+            rating = request.rating,
+            reviewText = request.reviewText?.trim(),
+            reviewTitle = request.reviewTitle?.trim(),
+            childAgeRange = request.childAgeRange,
+            isApproved = true,
+            moderatedAt = now,
+            createdAt = now,
+            updatedAt = now
+        )
+        if (!store.insert(review)) {
+            throw ReviewAlreadyExistsException("Your family has already reviewed this pack")
+        }
+        return review
+    }
+
+    fun updateReview(
+        familyId: UUID,
+        reviewId: UUID,
+        request: ContentPackReviewRequest
+    ): ContentPackReview {
+        validateReview(request)
+
+        val existing = store.find(reviewId) ?: throw NoSuchElementException("Review not found")
+        if (existing.familyId != familyId) {
+            throw SecurityException("You can only edit your own family's review")
+        }
+
+        val now = clock()
+        val updated = existing.copy(
+            rating = request.rating,
+            reviewText = request.reviewText?.trim(),
+            reviewTitle = request.reviewTitle?.trim(),
+            childAgeRange = request.childAgeRange ?: existing.childAgeRange,
+            moderatedAt = now,
+            updatedAt = now
+        )
+        store.update(updated)
+        return updated
+    }
+
+    fun listReviews(packId: UUID, page: Int = 0, size: Int = 20): ContentPackReviewListResponse {
+        val pageSize = size.coerceIn(1, 100)
+        val offset = page.coerceAtLeast(0).toLong() * pageSize
+        val (pageItems, total) = store.listApproved(packId, offset, pageSize)
+
+        return ContentPackReviewListResponse(
+            reviews = pageItems,
+            total = total,
+            page = page,
+            size = pageSize,
+            hasNext = offset + pageItems.size < total
+        )
+    }
+
+    /**
+     * Record a helpful/not-helpful vote. Each user has one vote per review; voting again replaces it.
+     */
+    fun voteOnReview(reviewId: UUID, userId: UUID, helpful: Boolean): ContentPackReview {
+        return store.vote(reviewId, userId, helpful, clock())
+            ?: throw NoSuchElementException("Review not found")
+    }
+
+    private fun validateReview(request: ContentPackReviewRequest) {
+        require(request.rating in 1..5) { "Rating must be between 1 and 5" }
+        require((request.reviewText?.length ?: 0) <= MAX_REVIEW_LENGTH) {
+            "Review must be at most $MAX_REVIEW_LENGTH characters"
+        }
+        require((request.reviewTitle?.length ?: 0) <= MAX_TITLE_LENGTH) {
+            "Review title must be at most $MAX_TITLE_LENGTH characters"
+        }
+
+        val moderation = ContentModeration.check(request.reviewTitle, request.reviewText)
+        if (!moderation.approved) {
+            throw ReviewRejectedException(moderation.reasons)
+        }
+    }
+
+    companion object {
+        const val MAX_REVIEW_LENGTH = 1000
+        const val MAX_TITLE_LENGTH = 200
+    }
+}
+
+class ReviewRejectedException(val reasons: List<String>) :
+    IllegalArgumentException("Review rejected: ${reasons.joinToString("; ")}")
+
+class ReviewAlreadyExistsException(message: String) : IllegalStateException(message)
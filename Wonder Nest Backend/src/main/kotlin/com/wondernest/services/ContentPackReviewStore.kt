@@ -0,0 +1,169 @@
+package com.wondernest.services
+
+import com.wondernest.data.database.table.ContentPackReviewVotesTable
+import com.wondernest.data.database.table.ContentPackReviewsTable
+import com.wondernest.models.ContentPackReview
+import kotlinx.datetime.toJavaInstant
+import kotlinx.datetime.toKotlinInstant
+import org.jetbrains.exposed.sql.*
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.eq
+import org.jetbrains.exposed.sql.statements.UpdateBuilder
+import org.jetbrains.exposed.sql.transactions.transaction
+import java.time.Instant
+import java.util.UUID
+import java.util.concurrent.ConcurrentHashMap
+
+interface ContentPackReviewStore {
+    /** False when the family (or the parent) has already reviewed the pack */
+    fun insert(review: ContentPackReview): Boolean
+
+    fun find(reviewId: UUID): ContentPackReview?
+
+    /** Write the editable fields of [review] */
+    fun update(review: ContentPackReview)
+
+    /** One page of a pack's approved reviews, most helpful first, and the total number approved */
+    fun listApproved(packId: UUID, offset: Long, limit: Int): Pair<List<ContentPackReview>, Long>
+
+    /**
+     * Store or replace [userId]'s vote on [reviewId] and recount the review's votes.
+     * Returns null when the review doesn't exist.
+     */
+    fun vote(reviewId: UUID, userId: UUID, helpful: Boolean, now: Instant): ContentPackReview?
+}
+
+object DatabaseContentPackReviewStore : ContentPackReviewStore {
+    override fun insert(review: ContentPackReview): Boolean = transaction {
+        // The (pack_id, family_id) and (pack_id, user_id) unique indexes make a second review a no-op
+        ContentPackReviewsTable.insertIgnore {
+            it[id] = review.id
+            it[packId] = review.packId
+            it[userId] = review.userId
+            it[familyId] = review.familyId
+            it[createdAt] = review.createdAt.toKotlinInstant()
+            it.writeEditable(review)
+        }.insertedCount > 0
+    }
+
+    override fun find(reviewId: UUID): ContentPackReview? = transaction {
+        ContentPackReviewsTable.select { ContentPackReviewsTable.id eq reviewId }.singleOrNull()?.toReview()
+    }
+
+    override fun update(review: ContentPackReview) {
+        transaction {
+            ContentPackReviewsTable.update({ ContentPackReviewsTable.id eq review.id }) { it.writeEditable(review) }
+        }
+    }
+
+    override fun listApproved(packId: UUID, offset: Long, limit: Int): Pair<List<ContentPackReview>, Long> = transaction {
+        val approved = ContentPackReviewsTable.select {
+            (ContentPackReviewsTable.packId eq packId) and (ContentPackReviewsTable.isApproved eq true)
+        }
+        val page = approved.copy()
+            .orderBy(ContentPackReviewsTable.helpfulCount to SortOrder.DESC, ContentPackReviewsTable.createdAt to SortOrder.DESC)
+            .limit(limit, offset)
+            .map { it.toReview() }
+        page to approved.count()
+    }
+
+    override fun vote(reviewId: UUID, userId: UUID, helpful: Boolean, now: Instant): ContentPackReview? = transaction {
+        // Locking the review row makes concurrent votes recount one after another
+        ContentPackReviewsTable.slice(ContentPackReviewsTable.id)
+            .select { ContentPackReviewsTable.id eq reviewId }
+            .forUpdate()
+            .singleOrNull()
+            ?: return@transaction null
+
+        val updated = ContentPackReviewVotesTable.update({
+            (ContentPackReviewVotesTable.reviewId eq reviewId) and (ContentPackReviewVotesTable.userId eq userId)
+        }) {
+            it[isHelpful] = helpful
+        }
+        if (updated == 0) {
+            ContentPackReviewVotesTable.insert {
+                it[ContentPackReviewVotesTable.reviewId] = reviewId
+                it[ContentPackReviewVotesTable.userId] = userId
+                it[isHelpful] = helpful
+                it[createdAt] = now.toKotlinInstant()
+            }
+        }
+
+        val votes = ContentPackReviewVotesTable.slice(ContentPackReviewVotesTable.isHelpful)
+            .select { ContentPackReviewVotesTable.reviewId eq reviewId }
+            .map { it[ContentPackReviewVotesTable.isHelpful] }
+        ContentPackReviewsTable.update({ ContentPackReviewsTable.id eq reviewId }) {
+            it[helpfulCount] = votes.count { vote -> vote }
+            it[notHelpfulCount] = votes.count { vote -> !vote }
+        }
+        ContentPackReviewsTable.select { ContentPackReviewsTable.id eq reviewId }.single().toReview()
+    }
+
+    private fun UpdateBuilder<*>.writeEditable(review: ContentPackReview) {
+        this[ContentPackReviewsTable.rating] = review.rating
+        this[ContentPackReviewsTable.reviewText] = review.reviewText
+        this[ContentPackReviewsTable.reviewTitle] = review.reviewTitle
+        this[ContentPackReviewsTable.childAgeRange] = review.childAgeRange
+        this[ContentPackReviewsTable.isApproved] = review.isApproved
+        this[ContentPackReviewsTable.moderatedAt] = review.moderatedAt?.toKotlinInstant()
+        this[ContentPackReviewsTable.updatedAt] = review.updatedAt.toKotlinInstant()
+    }
+
+    private fun ResultRow.toReview() = ContentPackReview(
+        id = this[ContentPackReviewsTable.id].value,
+        packId = this[ContentPackReviewsTable.packId].value,
+        userId = this[ContentPackReviewsTable.userId],
+        familyId = this[ContentPackReviewsTable.familyId],
+        rating = this[ContentPackReviewsTable.rating],
+        reviewText = this[ContentPackReviewsTable.reviewText],
+        reviewTitle = this[ContentPackReviewsTable.reviewTitle],
+        helpfulCount = this[ContentPackReviewsTable.helpfulCount],
+        notHelpfulCount = this[ContentPackReviewsTable.notHelpfulCount],
+        childAgeRange = this[ContentPackReviewsTable.childAgeRange],
+        isApproved = this[ContentPackReviewsTable.isApproved],
+        isFeatured = this[ContentPackReviewsTable.isFeatured],
+        moderatedAt = this[ContentPackReviewsTable.moderatedAt]?.toJavaInstant(),
+        moderatedBy = this[ContentPackReviewsTable.moderatedBy],
+        createdAt = this[ContentPackReviewsTable.createdAt].toJavaInstant(),
+        updatedAt = this[ContentPackReviewsTable.updatedAt].toJavaInstant()
+    )
+}
+
+/**
+ * For tests and local runs without a database
+ */
+class InMemoryContentPackReviewStore : ContentPackReviewStore {
+    private val reviews = ConcurrentHashMap<UUID, ContentPackReview>()
+    private val votes = ConcurrentHashMap<Pair<UUID, UUID>, Boolean>()
+
+    override fun insert(review: ContentPackReview): Boolean = synchronized(reviews) {
+        val duplicate = reviews.values.any {
+            it.packId == review.packId && (it.familyId == review.familyId || it.userId == review.userId)
+        }
+        if (!duplicate) reviews[review.id] = review
+        !duplicate
+    }
+
+    override fun find(reviewId: UUID): ContentPackReview? = reviews[reviewId]
+
+    override fun update(review: ContentPackReview) {
+        reviews.computeIfPresent(review.id) { _, _ -> review }
+    }
+
+    override fun listApproved(packId: UUID, offset: Long, limit: Int): Pair<List<ContentPackReview>, Long> {
+        val approved = reviews.values
+            .filter { it.packId == packId && it.isApproved }
+            .sortedWith(compareByDescending<ContentPackReview> { it.helpfulCount }.thenByDescending { it.createdAt })
+        return approved.drop(offset.toInt()).take(limit) to approved.size.toLong()
+    }
+
+    override fun vote(reviewId: UUID, userId: UUID, helpful: Boolean, now: Instant): ContentPackReview? =
+        synchronized(reviews) {
+            val review = reviews[reviewId] ?: return@synchronized null
+            votes[reviewId to userId] = helpful
+            val reviewVotes = votes.filterKeys { it.first == reviewId }.values
+            review.copy(
+                helpfulCount = reviewVotes.count { it },
+                notHelpfulCount = reviewVotes.count { !it }
+            ).also { reviews[reviewId] = it }
+        }
+}
@@ -0,0 +1,56 @@
+package com.wondernest.services
+
+import com.wondernest.models.ContentPack
+import com.wondernest.models.ContentPackSearchFacets
+import com.wondernest.models.ContentPackSearchRequest
+
+/**
+ * Search filters and facet counts for content packs
+ */
+object ContentPackSearchFilters {
+    const val UNCATEGORIZED = "uncategorized"
+
+    enum class Facet { CATEGORY, PACK_TYPE, IS_FREE }
+
+    fun matches(pack: ContentPack, request: ContentPackSearchRequest, except: Facet? = null): Boolean {
+        if (except != Facet.CATEGORY && request.category != null && !matchesCategory(pack, request.category)) return false
+        if (except != Facet.PACK_TYPE && request.packType != null && !pack.packType.equals(request.packType, ignoreCase = true)) return false
+        if (except != Facet.IS_FREE && request.isFree != null && pack.isFree != request.isFree) return false
+
+        // Age filters keep packs whose age band overlaps the requested one
+        if (request.ageMin != null && pack.ageMax < request.ageMin) return false
+        if (request.ageMax != null && pack.ageMin > request.ageMax) return false
+        if (request.priceMin != null && pack.priceCents < request.priceMin) return false
+        if (request.priceMax != null && pack.priceCents > request.priceMax) return false
+        if (request.educationalGoals.isNotEmpty() &&
+            request.educationalGoals.none { goal -> pack.educationalGoals.any { it.equals(goal, ignoreCase = true) } }
+        ) return false
+        return true
+    }
+
+    /**
+     * Counts per facet value. Each dimension ignores its own filter so the counts show what
+     * switching to another value would return, while the query and every other filter still apply.
+     */
+    fun facets(packs: List<ContentPack>, request: ContentPackSearchRequest) = ContentPackSearchFacets(
+        category = count(packs, request, Facet.CATEGORY) { categoryKey(it) },
+        packType = count(packs, request, Facet.PACK_TYPE) { it.packType },
+        isFree = count(packs, request, Facet.IS_FREE) { it.isFree.toString() }
+    )
+
+    private fun count(
+        packs: List<ContentPack>,
+        request: ContentPackSearchRequest,
+        facet: Facet,
+        key: (ContentPack) -> String
+    ): Map<String, Int> = packs.filter { matches(it, request, except = facet) }
+        .groupingBy(key)
+        .eachCount()
+        .toSortedMap()
+
+    private fun categoryKey(pack: ContentPack): String =
+        pack.category?.name ?: pack.categoryId?.toString() ?: UNCATEGORIZED
+
+    private fun matchesCategory(pack: ContentPack, category: String): Boolean =
+        categoryKey(pack).equals(category, ignoreCase = true) || pack.categoryId?.toString() == category
+}
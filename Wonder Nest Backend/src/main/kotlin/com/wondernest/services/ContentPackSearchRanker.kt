@@ -0,0 +1,77 @@
+package com.wondernest.services
+
+import com.wondernest.models.ContentPack
+import com.wondernest.models.ContentPackSearchRequest
+
+/**
+ * Relevance ranking for content pack search. Mirrors the weighted search_vector from V32:
+ * every query term must match (like plainto_tsquery), and a term scores by the best field
+ * it appears in using ts_rank's default weights (name A, description B, tags and keywords C).
+ */
+object ContentPackSearchRanker {
+    private const val NAME_WEIGHT = 1.0
+    private const val DESCRIPTION_WEIGHT = 0.4
+    private const val TAG_WEIGHT = 0.2
+
+    private val STOP_WORDS = setOf("a", "an", "and", "are", "for", "in", "is", "of", "on", "or", "the", "to", "with")
+
+    /**
+     * Normalized query terms; empty when the query is blank or only stop words,
+     * in which case search falls back to the default listing.
+     */
+    fun terms(query: String?): List<String> = query?.let(::tokenize)?.distinct().orEmpty()
+
+    /**
+     * Packs matching every term, paired with their relevance score
+     */
+    fun rank(packs: List<ContentPack>, terms: List<String>): List<Pair<ContentPack, Double>> {
+        if (terms.isEmpty()) return packs.map { it to 0.0 }
+        return packs.mapNotNull { pack ->
+            val name = tokenize(pack.name).toSet()
+            val description = tokenize(pack.description.orEmpty()).toSet()
+            val tags = (pack.curriculumTags + pack.moodTags + listOfNotNull(pack.searchKeywords))
+                .flatMap(::tokenize).toSet()
+
+            val scores = terms.map { term ->
+                when (term) {
+                    in name -> NAME_WEIGHT
+                    in description -> DESCRIPTION_WEIGHT
+                    in tags -> TAG_WEIGHT
+                    else -> return@mapNotNull null
+                }
+            }
+            pack to scores.sum()
+        }
+    }
+
+    /**
+     * Order packs for [request]. Relevance only applies when there is a query;
+     * otherwise packs use the default popularity ordering.
+     */
+    fun sort(ranked: List<Pair<ContentPack, Double>>, request: ContentPackSearchRequest, hasQuery: Boolean): List<ContentPack> {
+        val byPopularity = compareBy<Pair<ContentPack, Double>> { it.first.popularityScore }
+        val comparator = when (request.sortBy.lowercase()) {
+            "relevance" -> if (hasQuery) compareBy<Pair<ContentPack, Double>> { it.second }.then(byPopularity) else byPopularity
+            "rating" -> compareBy { it.first.ratingAverage }
+            "downloads" -> compareBy { it.first.downloadCount }
+            "price" -> compareBy { it.first.priceCents }
+            "newest" -> compareBy { it.first.publishedAt }
+            "name" -> compareBy { it.first.name.lowercase() }
+            else -> byPopularity
+        }
+        val ordered = if (request.sortOrder.equals("asc", ignoreCase = true)) comparator else comparator.reversed()
+        return ranked.sortedWith(ordered).map { it.first }
+    }
+
+    private fun tokenize(text: String): List<String> = text.lowercase()
+        .split(Regex("[^\\p{L}\\p{N}]+"))
+        .filter { it.isNotEmpty() && it !in STOP_WORDS }
+        .map(::stem)
+
+    // Enough stemming that plurals match their singular, as the english text search config does
+    private fun stem(word: String): String = when {
+        word.length > 4 && word.endsWith("ies") -> word.dropLast(3) + "y"
+        word.length > 3 && word.endsWith("s") && !word.endsWith("ss") -> word.dropLast(1)
+        else -> word
+    }
+}
@@ -1,6 +1,7 @@
 package com.wondernest.services
 
 import com.wondernest.models.*
+import com.wondernest.services.moderation.ContentFlagService
 import java.time.Instant
 import java.util.UUID
 import java.math.BigDecimal
@@ -9,7 +10,54 @@ import java.math.BigDecimal
  * Simplified ContentPackService that returns mock data
  * This allows the API endpoints to work while the full implementation is being fixed
  */
-class ContentPackServiceSimple {
+class ContentPackServiceSimple(
+    private val contentFlagService: ContentFlagService = ContentFlagService(),
+    private val clock: () -> Instant = Instant::now,
+    private val statsStore: ContentPackStatsStore = DatabaseContentPackStatsStore
+) {
+    companion object {
+        // The packs the mock catalogue serves; V56 gives each a content_packs row
+        val CATALOGUE_PACK_IDS: List<UUID> = listOf(
+            UUID.fromString("11111111-1111-1111-1111-111111111111"),
+            UUID.fromString("22222222-2222-2222-2222-222222222222"),
+            UUID.fromString("33333333-3333-3333-3333-333333333333")
+        )
+    }
+
+    /**
+     * Set or clear when a seasonal pack leaves listings and search
+     */
+    fun setPackExpiry(packId: UUID, expiresAt: Instant?): ContentPack {
+        getMockPacks().find { it.id == packId } ?: throw NoSuchElementException("Pack not found")
+        statsStore.setExpiry(packId, expiresAt, clock())
+        return getMockPacks().first { it.id == packId }
+    }
+
+    /**
+     * Feature a pack until [until], or indefinitely with null. Setting a window reinstates a
+     * pack whose previous window was cleared.
+     */
+    fun setFeaturedUntil(packId: UUID, until: Instant?): ContentPack {
+        getMockPacks().find { it.id == packId } ?: throw NoSuchElementException("Pack not found")
+        statsStore.setFeaturedUntil(packId, until, clock())
+        return getMockPacks().first { it.id == packId }
+    }
+
+    /**
+     * Clear the featured flag on packs whose featured window has ended, so stale flags
+     * don't show up anywhere else. Returns how many packs were cleared.
+     */
+    fun clearExpiredFeatures(): Int = statsStore.clearExpiredFeatures(CATALOGUE_PACK_IDS, clock())
+
+    fun packExists(packId: UUID): Boolean = getMockPacks().any { it.id == packId }
+
+    /**
+     * Packs families can discover: not flagged and not past their expiry
+     */
+    private fun listablePacks(): List<ContentPack> {
+        val expired = statsStore.expired(CATALOGUE_PACK_IDS, clock())
+        return contentFlagService.filterVisible(getMockPacks()) { it.id }.filterNot { it.id in expired }
+    }
 
     fun getCategories(): List<ContentPackCategory> {
         return listOf(
@@ -55,26 +103,26 @@ class ContentPackServiceSimple {
         )
     }
 
+    /**
+     * Featured packs whose window is still open, highest rated first
+     */
     fun getFeaturedPacks(userId: UUID, limit: Int = 10): List<ContentPack> {
-        return getMockPacks().take(limit)
+        clearExpiredFeatures()
+        val now = clock()
+        return listablePacks()
+            .filter { it.isCurrentlyFeatured(now) }
+            .sortedByDescending { it.ratingAverage }
+            .take(limit)
     }
 
     fun searchPacks(request: ContentPackSearchRequest, userId: UUID): ContentPackSearchResponse {
-        val allPacks = getMockPacks()
-        
-        // Simple filtering
-        var filteredPacks = allPacks
+        val allPacks = listablePacks()
         
-        request.query?.let { query ->
-            filteredPacks = filteredPacks.filter { 
-                it.name.contains(query, ignoreCase = true) || 
-                it.description?.contains(query, ignoreCase = true) == true 
-            }
-        }
-        
-        request.category?.let { category ->
-            // Filter by category if needed
-        }
+        // Blank queries fall back to the default listing
+        val terms = ContentPackSearchRanker.terms(request.query)
+        val ranked = ContentPackSearchRanker.rank(allPacks, terms)
+        val matching = ranked.filter { ContentPackSearchFilters.matches(it.first, request) }
+        val filteredPacks = ContentPackSearchRanker.sort(matching, request, hasQuery = terms.isNotEmpty())
         
         // Pagination
         val start = request.page * request.size
@@ -90,12 +138,15 @@ class ContentPackServiceSimple {
             total = filteredPacks.size.toLong(),
             page = request.page,
             size = request.size,
-            hasNext = end < filteredPacks.size
+            hasNext = end < filteredPacks.size,
+            facets = ContentPackSearchFilters.facets(ranked.map { it.first }, request)
         )
     }
 
     fun getPackById(packId: UUID, userId: UUID): ContentPack? {
+        // Expired packs stay reachable for families who already own them
         return getMockPacks().find { it.id == packId }
+            ?.takeIf { !it.isExpired(clock()) || ownsPack(userId, packId) }
     }
 
     fun getUserOwnedPacks(userId: UUID, childId: UUID? = null): List<ContentPack> {
@@ -124,9 +175,16 @@ class ContentPackServiceSimple {
         }
     }
 
+    fun ownsPack(userId: UUID, packId: UUID): Boolean {
+        return getUserOwnedPacks(userId).any { it.id == packId }
+    }
+
     fun purchasePack(userId: UUID, request: PackPurchaseRequest): PackPurchaseResponse {
         val pack = getMockPacks().find { it.id == request.packId }
             ?: return PackPurchaseResponse(false, error = "Pack not found")
+        if (pack.isExpired(clock())) {
+            return PackPurchaseResponse(false, error = "Pack is no longer available")
+        }
         
         val ownership = UserPackOwnership(
             id = UUID.randomUUID(),
@@ -154,6 +212,15 @@ class ContentPackServiceSimple {
         )
     }
 
+    /**
+     * Record that [familyId] installed [packId]. Only a family's first install counts towards
+     * the pack's downloads (see [ContentPackStatsStore.recordInstall]).
+     */
+    fun recordInstall(familyId: UUID, packId: UUID): PackInstallResponse {
+        if (!packExists(packId)) throw NoSuchElementException("Pack not found")
+        return statsStore.recordInstall(packId, familyId, clock())
+    }
+
     fun updateDownloadStatus(
         userId: UUID, 
         packId: UUID, 
@@ -213,9 +280,9 @@ class ContentPackServiceSimple {
     }
 
     private fun getMockPacks(): List<ContentPack> {
-        val packId1 = UUID.fromString("11111111-1111-1111-1111-111111111111")
-        val packId2 = UUID.fromString("22222222-2222-2222-2222-222222222222")
-        val packId3 = UUID.fromString("33333333-3333-3333-3333-333333333333")
+        val (packId1, packId2, packId3) = CATALOGUE_PACK_IDS
+        val stats = statsStore.stats(CATALOGUE_PACK_IDS)
+        val listings = statsStore.listings(CATALOGUE_PACK_IDS)
         
         return listOf(
             ContentPack(
@@ -338,6 +405,17 @@ class ContentPackServiceSimple {
                 assets = emptyList(),
                 userOwnership = null
             )
-        )
+        ).map { pack ->
+            pack.copy(
+                // Expiry and featuring are set by admins and kept in the pack's row
+                expiresAt = listings[pack.id]?.expiresAt ?: pack.expiresAt,
+                featuredUntil = listings[pack.id]?.featuredUntil ?: pack.featuredUntil,
+                isFeatured = listings[pack.id]?.isFeatured ?: pack.isFeatured,
+                // Stored figures take over from the mock catalogue once a pack has them
+                ratingAverage = stats[pack.id]?.ratingAverage ?: pack.ratingAverage,
+                ratingCount = stats[pack.id]?.ratingCount ?: pack.ratingCount,
+                downloadCount = stats[pack.id]?.downloadCount ?: pack.downloadCount
+            )
+        }
     }
 }
\ No newline at end of file
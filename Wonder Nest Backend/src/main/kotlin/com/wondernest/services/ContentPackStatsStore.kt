@@ -0,0 +1,297 @@
+package com.wondernest.services
+
+import com.wondernest.data.database.table.ContentPackInstallsTable
+import com.wondernest.data.database.table.ContentPackRatingsTable
+import com.wondernest.data.database.table.ContentPacksTable
+import com.wondernest.models.PackInstallResponse
+import kotlinx.datetime.toJavaInstant
+import kotlinx.datetime.toKotlinInstant
+import org.jetbrains.exposed.sql.*
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.eq
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.inList
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.lessEq
+import org.jetbrains.exposed.sql.transactions.transaction
+import java.math.BigDecimal
+import java.math.RoundingMode
+import java.time.Instant
+import java.util.UUID
+import java.util.concurrent.ConcurrentHashMap
+
+/**
+ * A pack's figures as stored in content_packs
+ */
+data class ContentPackStats(
+    val ratingAverage: BigDecimal,
+    val ratingCount: Int,
+    val downloadCount: Long
+)
+
+/**
+ * When a pack leaves listings and whether and until when it is featured, as stored in content_packs
+ */
+data class ContentPackListing(
+    val isFeatured: Boolean,
+    val expiresAt: Instant? = null,
+    val featuredUntil: Instant? = null
+)
+
+/**
+ * Per-family activity on content packs and the admin-set listing windows, which have to survive
+ * restarts, kept apart from the catalogue itself so the catalogue can stay mock data for now
+ */
+interface ContentPackStatsStore {
+    /** Stats for the packs that have them; packs without a row are left out */
+    fun stats(packIds: Collection<UUID>): Map<UUID, ContentPackStats>
+
+    /**
+     * Store or replace [userId]'s rating for [packId] and recompute the pack's aggregate from
+     * every stored rating. Throws NoSuchElementException when the pack has no row.
+     */
+    fun upsertRating(packId: UUID, userId: UUID, rating: Int, now: Instant): ContentPackStats
+
+    fun findRating(packId: UUID, userId: UUID): Int?
+
+    /**
+     * Record that [familyId] installed [packId]. Only a family's first install adds to the pack's
+     * download count. Throws NoSuchElementException when the pack has no row.
+     */
+    fun recordInstall(packId: UUID, familyId: UUID, now: Instant): PackInstallResponse
+
+    /** Listing windows for the packs that have them; packs without a row are left out */
+    fun listings(packIds: Collection<UUID>): Map<UUID, ContentPackListing>
+
+    /** The packs among [packIds] whose expiry is at or before [now] */
+    fun expired(packIds: Collection<UUID>, now: Instant): Set<UUID>
+
+    /** Set or clear [packId]'s expiry. Throws NoSuchElementException when the pack has no row. */
+    fun setExpiry(packId: UUID, expiresAt: Instant?, now: Instant)
+
+    /**
+     * Feature [packId] until [until], or indefinitely with null. Throws NoSuchElementException
+     * when the pack has no row.
+     */
+    fun setFeaturedUntil(packId: UUID, until: Instant?, now: Instant)
+
+    /** Stop featuring those of [packIds] whose featured window ended at or before [now]; returns how many */
+    fun clearExpiredFeatures(packIds: Collection<UUID>, now: Instant): Int
+}
+
+object DatabaseContentPackStatsStore : ContentPackStatsStore {
+    override fun stats(packIds: Collection<UUID>): Map<UUID, ContentPackStats> = transaction {
+        if (packIds.isEmpty()) return@transaction emptyMap()
+        ContentPacksTable.slice(
+            ContentPacksTable.id, ContentPacksTable.ratingAverage, ContentPacksTable.ratingCount, ContentPacksTable.downloadCount
+        )
+            .select { ContentPacksTable.id inList packIds }
+            .associate { row ->
+                row[ContentPacksTable.id].value to ContentPackStats(
+                    ratingAverage = row[ContentPacksTable.ratingAverage],
+                    ratingCount = row[ContentPacksTable.ratingCount],
+                    downloadCount = row[ContentPacksTable.downloadCount]
+                )
+            }
+    }
+
+    override fun upsertRating(packId: UUID, userId: UUID, rating: Int, now: Instant): ContentPackStats = transaction {
+        // Locking the pack row makes concurrent ratings recompute one after another, so the
+        // aggregate left behind always includes every rating
+        val downloads = ContentPacksTable.slice(ContentPacksTable.downloadCount)
+            .select { ContentPacksTable.id eq packId }
+            .forUpdate()
+            .singleOrNull()
+            ?.get(ContentPacksTable.downloadCount)
+            ?: throw NoSuchElementException("Pack not found")
+
+        val stamp = now.toKotlinInstant()
+        val updated = ContentPackRatingsTable.update({
+            (ContentPackRatingsTable.packId eq packId) and (ContentPackRatingsTable.userId eq userId)
+        }) {
+            it[ContentPackRatingsTable.rating] = rating.toShort()
+            it[ContentPackRatingsTable.updatedAt] = stamp
+        }
+        if (updated == 0) {
+            ContentPackRatingsTable.insert {
+                it[ContentPackRatingsTable.packId] = packId
+                it[ContentPackRatingsTable.userId] = userId
+                it[ContentPackRatingsTable.rating] = rating.toShort()
+                it[ContentPackRatingsTable.createdAt] = stamp
+                it[ContentPackRatingsTable.updatedAt] = stamp
+            }
+        }
+
+        val average = ContentPackRatingsTable.rating.avg(2)
+        val count = ContentPackRatingsTable.rating.count()
+        val aggregate = ContentPackRatingsTable.slice(average, count)
+            .select { ContentPackRatingsTable.packId eq packId }
+            .single()
+        val stats = ContentPackStats(
+            ratingAverage = (aggregate[average] ?: BigDecimal.ZERO).setScale(2, RoundingMode.HALF_UP),
+            ratingCount = aggregate[count].toInt(),
+            downloadCount = downloads
+        )
+        ContentPacksTable.update({ ContentPacksTable.id eq packId }) {
+            it[ContentPacksTable.ratingAverage] = stats.ratingAverage
+            it[ContentPacksTable.ratingCount] = stats.ratingCount
+            it[ContentPacksTable.updatedAt] = stamp
+        }
+        stats
+    }
+
+    override fun findRating(packId: UUID, userId: UUID): Int? = transaction {
+        ContentPackRatingsTable.slice(ContentPackRatingsTable.rating)
+            .select { (ContentPackRatingsTable.packId eq packId) and (ContentPackRatingsTable.userId eq userId) }
+            .singleOrNull()
+            ?.get(ContentPackRatingsTable.rating)
+            ?.toInt()
+    }
+
+    override fun recordInstall(packId: UUID, familyId: UUID, now: Instant): PackInstallResponse = transaction {
+        if (ContentPacksTable.select { ContentPacksTable.id eq packId }.empty()) {
+            throw NoSuchElementException("Pack not found")
+        }
+        // The (pack_id, family_id) primary key makes a repeat install a no-op, and the count is
+        // incremented in SQL, so parallel installs can't double count or lose an increment
+        val firstInstall = ContentPackInstallsTable.insertIgnore {
+            it[ContentPackInstallsTable.packId] = packId
+            it[ContentPackInstallsTable.familyId] = familyId
+            it[ContentPackInstallsTable.installedAt] = now.toKotlinInstant()
+        }.insertedCount > 0
+        if (firstInstall) {
+            ContentPacksTable.update({ ContentPacksTable.id eq packId }) {
+                with(SqlExpressionBuilder) {
+                    it.update(ContentPacksTable.downloadCount, ContentPacksTable.downloadCount + 1)
+                }
+            }
+        }
+        PackInstallResponse(
+            packId = packId,
+            downloadCount = ContentPacksTable.slice(ContentPacksTable.downloadCount)
+                .select { ContentPacksTable.id eq packId }
+                .single()[ContentPacksTable.downloadCount],
+            firstInstall = firstInstall
+        )
+    }
+
+    override fun listings(packIds: Collection<UUID>): Map<UUID, ContentPackListing> = transaction {
+        if (packIds.isEmpty()) return@transaction emptyMap()
+        ContentPacksTable.slice(
+            ContentPacksTable.id, ContentPacksTable.isFeatured, ContentPacksTable.expiresAt, ContentPacksTable.featuredUntil
+        )
+            .select { ContentPacksTable.id inList packIds }
+            .associate { row ->
+                row[ContentPacksTable.id].value to ContentPackListing(
+                    isFeatured = row[ContentPacksTable.isFeatured],
+                    expiresAt = row[ContentPacksTable.expiresAt]?.toJavaInstant(),
+                    featuredUntil = row[ContentPacksTable.featuredUntil]?.toJavaInstant()
+                )
+            }
+    }
+
+    override fun expired(packIds: Collection<UUID>, now: Instant): Set<UUID> = transaction {
+        if (packIds.isEmpty()) return@transaction emptySet()
+        ContentPacksTable.slice(ContentPacksTable.id)
+            .select { (ContentPacksTable.id inList packIds) and (ContentPacksTable.expiresAt lessEq now.toKotlinInstant()) }
+            .mapTo(mutableSetOf()) { it[ContentPacksTable.id].value }
+    }
+
+    override fun setExpiry(packId: UUID, expiresAt: Instant?, now: Instant) {
+        val updated = transaction {
+            ContentPacksTable.update({ ContentPacksTable.id eq packId }) {
+                it[ContentPacksTable.expiresAt] = expiresAt?.toKotlinInstant()
+                it[ContentPacksTable.updatedAt] = now.toKotlinInstant()
+            }
+        }
+        if (updated == 0) throw NoSuchElementException("Pack not found")
+    }
+
+    override fun setFeaturedUntil(packId: UUID, until: Instant?, now: Instant) {
+        val updated = transaction {
+            ContentPacksTable.update({ ContentPacksTable.id eq packId }) {
+                it[ContentPacksTable.isFeatured] = true
+                it[ContentPacksTable.featuredUntil] = until?.toKotlinInstant()
+                it[ContentPacksTable.updatedAt] = now.toKotlinInstant()
+            }
+        }
+        if (updated == 0) throw NoSuchElementException("Pack not found")
+    }
+
+    override fun clearExpiredFeatures(packIds: Collection<UUID>, now: Instant): Int = transaction {
+        if (packIds.isEmpty()) return@transaction 0
+        // The window is checked in the update itself, so a window extended meanwhile isn't cleared
+        val cutoff = now.toKotlinInstant()
+        ContentPacksTable.update({
+            (ContentPacksTable.id inList packIds) and (ContentPacksTable.isFeatured eq true) and
+                (ContentPacksTable.featuredUntil lessEq cutoff)
+        }) {
+            it[ContentPacksTable.isFeatured] = false
+            it[ContentPacksTable.updatedAt] = cutoff
+        }
+    }
+}
+
+/**
+ * For tests and local runs without a database. [seed] and [listings] play the part of the
+ * content_packs rows; packs that aren't seeded have no stats and can't be rated or installed,
+ * and packs without a listing can't have their expiry or featured window set.
+ */
+class InMemoryContentPackStatsStore(
+    seed: Map<UUID, ContentPackStats> = emptyMap(),
+    listings: Map<UUID, ContentPackListing> = emptyMap()
+) : ContentPackStatsStore {
+    private val packs = ConcurrentHashMap(seed)
+    private val listings = ConcurrentHashMap(listings)
+    private val ratings = ConcurrentHashMap<Pair<UUID, UUID>, Int>()
+    private val installs = ConcurrentHashMap.newKeySet<Pair<UUID, UUID>>()
+
+    override fun stats(packIds: Collection<UUID>): Map<UUID, ContentPackStats> =
+        packIds.mapNotNull { packId -> packs[packId]?.let { packId to it } }.toMap()
+
+    override fun upsertRating(packId: UUID, userId: UUID, rating: Int, now: Instant): ContentPackStats =
+        synchronized(packs) {
+            val current = packs[packId] ?: throw NoSuchElementException("Pack not found")
+            ratings[packId to userId] = rating
+            val packRatings = ratings.filterKeys { it.first == packId }.values
+            current.copy(
+                ratingAverage = BigDecimal(packRatings.sum()).divide(BigDecimal(packRatings.size), 2, RoundingMode.HALF_UP),
+                ratingCount = packRatings.size
+            ).also { packs[packId] = it }
+        }
+
+    override fun findRating(packId: UUID, userId: UUID): Int? = ratings[packId to userId]
+
+    override fun recordInstall(packId: UUID, familyId: UUID, now: Instant): PackInstallResponse =
+        synchronized(packs) {
+            val current = packs[packId] ?: throw NoSuchElementException("Pack not found")
+            val firstInstall = installs.add(packId to familyId)
+            val updated = if (firstInstall) current.copy(downloadCount = current.downloadCount + 1) else current
+            packs[packId] = updated
+            PackInstallResponse(packId, updated.downloadCount, firstInstall)
+        }
+
+    override fun listings(packIds: Collection<UUID>): Map<UUID, ContentPackListing> =
+        packIds.mapNotNull { packId -> listings[packId]?.let { packId to it } }.toMap()
+
+    override fun expired(packIds: Collection<UUID>, now: Instant): Set<UUID> =
+        packIds.filterTo(mutableSetOf()) { packId -> listings[packId]?.expiresAt?.let { !it.isAfter(now) } ?: false }
+
+    override fun setExpiry(packId: UUID, expiresAt: Instant?, now: Instant) {
+        listings.computeIfPresent(packId) { _, listing -> listing.copy(expiresAt = expiresAt) }
+            ?: throw NoSuchElementException("Pack not found")
+    }
+
+    override fun setFeaturedUntil(packId: UUID, until: Instant?, now: Instant) {
+        listings.computeIfPresent(packId) { _, listing -> listing.copy(isFeatured = true, featuredUntil = until) }
+            ?: throw NoSuchElementException("Pack not found")
+    }
+
+    override fun clearExpiredFeatures(packIds: Collection<UUID>, now: Instant): Int =
+        packIds.count { packId ->
+            var cleared = false
+            listings.computeIfPresent(packId) { _, listing ->
+                val ended = listing.isFeatured && listing.featuredUntil?.let { !it.isAfter(now) } ?: false
+                cleared = ended
+                if (ended) listing.copy(isFeatured = false) else listing
+            }
+            cleared
+        }
+}
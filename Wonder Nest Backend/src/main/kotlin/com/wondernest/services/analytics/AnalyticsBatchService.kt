@@ -0,0 +1,127 @@
+package com.wondernest.services.analytics
+
+import com.wondernest.api.FamilyContext
+import com.wondernest.api.analytics.AnalyticsEvent
+import com.wondernest.services.coppa.DataCollectionConsentChecker
+import com.wondernest.services.family.ChildPseudonymService
+import kotlinx.serialization.Serializable
+import mu.KotlinLogging
+import java.util.UUID
+
+private val logger = KotlinLogging.logger {}
+
+data class AnalyticsBatchConfig(
+    val maxEvents: Int = 500
+) {
+    companion object {
+        /**
+         * Reads ANALYTICS_BATCH_MAX_EVENTS
+         */
+        fun fromEnvironment(env: Map<String, String> = System.getenv()) = AnalyticsBatchConfig(
+            maxEvents = env["ANALYTICS_BATCH_MAX_EVENTS"]?.toIntOrNull()?.takeIf { it > 0 } ?: 500
+        )
+    }
+}
+
+@Serializable
+data class BatchEventResult(
+    val index: Int,
+    val clientEventId: String? = null,
+    val accepted: Boolean,
+    val eventId: String? = null,
+    val duplicate: Boolean = false,
+    val error: String? = null
+)
+
+@Serializable
+data class AnalyticsBatchResponse(
+    val accepted: Int,
+    val rejected: Int,
+    val results: List<BatchEventResult>
+)
+
+class AnalyticsBatchTooLargeException(val maxEvents: Int) :
+    IllegalArgumentException("A batch can hold at most $maxEvents events")
+
+/**
+ * Ingests events buffered offline. Each event carries a client-generated UUID so a batch can be
+ * flushed again after a crash without counting anything twice. The whole batch is validated
+ * before anything is recorded; invalid events are rejected individually.
+ */
+class AnalyticsBatchService(
+    private val analyticsEventService: AnalyticsEventService,
+    private val childPseudonymService: ChildPseudonymService,
+    private val consentChecker: DataCollectionConsentChecker,
+    private val config: AnalyticsBatchConfig = AnalyticsBatchConfig.fromEnvironment(),
+    private val screenTimeService: ScreenTimeService? = null
+) {
+    private sealed interface Checked {
+        data class Valid(val childId: UUID, val clientEventId: String, val event: AnalyticsEvent) : Checked
+        data class Invalid(val reason: String) : Checked
+    }
+
+    /**
+     * Throws [AnalyticsBatchTooLargeException] when [events] is over the configured size
+     */
+    suspend fun ingest(family: FamilyContext, events: List<AnalyticsEvent>): AnalyticsBatchResponse {
+        if (events.size > config.maxEvents) throw AnalyticsBatchTooLargeException(config.maxEvents)
+
+        val consent = mutableMapOf<UUID, Boolean>()
+        val checked = events.map { event -> check(family, event, consent) }
+
+        val results = checked.mapIndexed { index, result ->
+            when (result) {
+                is Checked.Invalid -> BatchEventResult(
+                    index = index,
+                    clientEventId = events[index].clientEventId,
+                    accepted = false,
+                    error = result.reason
+                )
+                is Checked.Valid -> {
+                    val recorded = analyticsEventService.record(
+                        childId = result.childId.toString(),
+                        eventType = result.event.eventType,
+                        contentId = result.event.contentId,
+                        duration = result.event.duration,
+                        eventData = result.event.eventData,
+                        sessionId = result.event.sessionId,
+                        clientEventId = result.clientEventId
+                    )
+                    BatchEventResult(
+                        index = index,
+                        clientEventId = result.clientEventId,
+                        accepted = true,
+                        eventId = recorded.eventId,
+                        duplicate = recorded.duplicate
+                    )
+                }
+            }
+        }
+
+        // New activity may have pushed a child over their screen-time limit
+        checked.filterIndexed { index, result -> result is Checked.Valid && !results[index].duplicate }
+            .map { (it as Checked.Valid).childId }
+            .distinct()
+            .forEach { screenTimeService?.checkLimit(it) }
+
+        val accepted = results.count { it.accepted }
+        logger.info { "Ingested analytics batch for family ${family.familyId}: $accepted of ${events.size} accepted" }
+        return AnalyticsBatchResponse(accepted = accepted, rejected = events.size - accepted, results = results)
+    }
+
+    private suspend fun check(family: FamilyContext, event: AnalyticsEvent, consent: MutableMap<UUID, Boolean>): Checked {
+        val clientEventId = event.clientEventId
+            ?.takeIf { runCatching { UUID.fromString(it) }.isSuccess }
+            ?: return Checked.Invalid("clientEventId must be a UUID")
+        if (event.eventType.isBlank()) return Checked.Invalid("Event type is required")
+
+        val childId = childPseudonymService.resolve(event.childId)
+            ?: return Checked.Invalid("Unknown child ID")
+        if (!family.ownsChild(childId)) return Checked.Invalid("Child does not belong to your family")
+        if (!consent.getOrPut(childId) { consentChecker.isDataCollectionAllowed(childId) }) {
+            return Checked.Invalid("Parental consent required before collecting data for this child")
+        }
+
+        return Checked.Valid(childId, clientEventId, event)
+    }
+}
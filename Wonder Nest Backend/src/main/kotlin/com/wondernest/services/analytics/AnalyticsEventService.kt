@@ -0,0 +1,202 @@
+package com.wondernest.services.analytics
+
+import com.wondernest.api.analytics.WeeklyOverview
+import kotlinx.serialization.Serializable
+import kotlinx.serialization.json.JsonElement
+import kotlinx.serialization.json.JsonPrimitive
+import kotlinx.serialization.json.booleanOrNull
+import kotlinx.serialization.json.contentOrNull
+import mu.KotlinLogging
+import java.time.Instant
+import java.time.LocalDate
+import java.time.ZoneId
+import java.time.ZoneOffset
+import java.util.UUID
+import kotlin.random.Random
+
+private val logger = KotlinLogging.logger {}
+
+/**
+ * Per-event-type sampling rates for raw analytics rows (1.0 keeps every event).
+ * Event types without a configured rate are never sampled.
+ */
+data class AnalyticsSamplingConfig(
+    val rates: Map<String, Double> = DEFAULT_RATES
+) {
+    fun rateFor(eventType: String): Double = (rates[eventType.lowercase()] ?: 1.0).coerceIn(0.0, 1.0)
+
+    companion object {
+        val DEFAULT_RATES = mapOf(
+            "heartbeat" to 0.05,
+            "engagement_tick" to 0.1,
+            "content_progress" to 0.25
+        )
+
+        /**
+         * Reads ANALYTICS_SAMPLE_RATE_<EVENT_TYPE> (0.0-1.0) on top of the defaults
+         */
+        fun fromEnvironment(env: Map<String, String> = System.getenv()): AnalyticsSamplingConfig {
+            val prefix = "ANALYTICS_SAMPLE_RATE_"
+            val overrides = env.filterKeys { it.startsWith(prefix) }
+                .mapNotNull { (key, value) ->
+                    value.toDoubleOrNull()?.let { key.removePrefix(prefix).lowercase() to it.coerceIn(0.0, 1.0) }
+                }
+                .toMap()
+            return AnalyticsSamplingConfig(DEFAULT_RATES + overrides)
+        }
+    }
+}
+
+@Serializable
+data class StoredAnalyticsEvent(
+    val id: String,
+    val eventType: String,
+    val childId: String,
+    val contentId: String? = null,
+    val duration: Int? = null,
+    val eventData: Map<String, JsonElement> = emptyMap(),
+    val sessionId: String? = null,
+    val sampleRate: Double,
+    val recordedAt: String
+)
+
+/**
+ * Daily totals per child and event type, counted before sampling so they stay exact
+ */
+@Serializable
+data class AnalyticsEventAggregate(
+    val childId: String,
+    val eventType: String,
+    val date: String,
+    val eventCount: Long,
+    val totalDuration: Long
+)
+
+data class RecordedAnalyticsEvent(
+    val eventId: String,
+    val rawStored: Boolean,
+    // Already recorded under the same client event ID; nothing was counted again
+    val duplicate: Boolean = false
+)
+
+/**
+ * Records analytics events in the [store]. Daily totals are always updated; raw events for
+ * high-volume event types are kept at the configured sample rate.
+ */
+class AnalyticsEventService(
+    private val sampling: AnalyticsSamplingConfig = AnalyticsSamplingConfig.fromEnvironment(),
+    private val random: Random = Random.Default,
+    private val store: AnalyticsEventStore = DatabaseAnalyticsEventStore,
+    private val clock: () -> Instant = Instant::now
+) {
+    companion object {
+        const val TOP_CATEGORY_COUNT = 3
+        private const val EDUCATIONAL_CATEGORY = "educational"
+    }
+
+    fun record(
+        childId: String,
+        eventType: String,
+        contentId: String? = null,
+        duration: Int? = null,
+        eventData: Map<String, JsonElement> = emptyMap(),
+        sessionId: String? = null,
+        clientEventId: String? = null
+    ): RecordedAnalyticsEvent {
+        val now = clock()
+        val type = eventType.lowercase()
+        val rate = sampling.rateFor(type)
+        val keepRaw = rate >= 1.0 || random.nextDouble() < rate
+        val event = StoredAnalyticsEvent(
+            id = UUID.randomUUID().toString(),
+            eventType = type,
+            childId = childId,
+            contentId = contentId,
+            duration = duration,
+            eventData = eventData,
+            sessionId = sessionId,
+            sampleRate = rate,
+            recordedAt = now.toString()
+        )
+
+        store.record(event, LocalDate.ofInstant(now, ZoneOffset.UTC), keepRaw, clientEventId)?.let { existing ->
+            logger.debug { "Skipping replayed event $clientEventId for child $childId" }
+            return RecordedAnalyticsEvent(existing, rawStored = false, duplicate = true)
+        }
+        if (!keepRaw) logger.debug { "Sampled out raw $type event for child $childId" }
+
+        return RecordedAnalyticsEvent(event.id, keepRaw)
+    }
+
+    /**
+     * The week starting on [weekStart], midnight to midnight in [zone] so a family's Sunday
+     * evening isn't counted in the next week. Raw events are weighted by their sample rate
+     * so sampled event types still add up. Weeks without events come back zeroed.
+     */
+    fun weeklyOverview(childId: String, weekStart: LocalDate, zone: ZoneId): WeeklyOverview {
+        val events = eventsBetween(
+            childId,
+            weekStart.atStartOfDay(zone).toInstant(),
+            weekStart.plusDays(7).atStartOfDay(zone).toInstant()
+        )
+
+        fun weighted(matching: List<StoredAnalyticsEvent>) = matching.sumOf { 1.0 / it.sampleRate }
+
+        val totalMinutes = minutes(events)
+        val educationalMinutes = minutes(events.filter { it.isEducational() })
+        val minutesByCategory = events.groupBy { it.category() }
+            .filterKeys { it != null }
+            .mapValues { (_, categoryEvents) -> minutes(categoryEvents) }
+        // Every "x_started" event is an activity; a matching "x_completed" finishes it
+        val started = weighted(events.filter { it.eventType.endsWith("_started") })
+        val completed = weighted(events.filter { it.eventType.endsWith("_completed") })
+
+        return WeeklyOverview(
+            weekStart = weekStart.toString(),
+            totalScreenTime = totalMinutes.toInt(),
+            educationalPercentage = if (totalMinutes > 0) educationalMinutes * 100 / totalMinutes else 0.0,
+            averageDailyUsage = (totalMinutes / 7).toInt(),
+            topCategories = minutesByCategory.entries
+                .sortedWith(compareByDescending<Map.Entry<String?, Double>> { it.value }.thenBy { it.key })
+                .take(TOP_CATEGORY_COUNT)
+                .mapNotNull { it.key },
+            completionRate = if (started > 0) (completed / started).coerceAtMost(1.0) else 0.0,
+            // Parent interactions aren't recorded as child events yet
+            parentalInteraction = 0
+        )
+    }
+
+    /**
+     * Screen time on [date] in [zone], from event durations weighted by sample rate
+     */
+    fun minutesOn(childId: String, date: LocalDate, zone: ZoneId): Int =
+        minutes(eventsBetween(childId, date.atStartOfDay(zone).toInstant(), date.plusDays(1).atStartOfDay(zone).toInstant()))
+            .toInt()
+
+    private fun eventsBetween(childId: String, from: Instant, until: Instant): List<StoredAnalyticsEvent> =
+        store.eventsBetween(childId, from, until)
+
+    private fun minutes(events: List<StoredAnalyticsEvent>): Double = events.sumOf { (it.duration ?: 0) / it.sampleRate }
+
+    private fun StoredAnalyticsEvent.category(): String? =
+        (eventData["category"] as? JsonPrimitive)?.contentOrNull?.lowercase()?.takeIf { it.isNotBlank() }
+
+    private fun StoredAnalyticsEvent.isEducational(): Boolean =
+        (eventData["isEducational"] as? JsonPrimitive)?.booleanOrNull ?: (category() == EDUCATIONAL_CATEGORY)
+
+    fun getRawEvents(childId: String, eventType: String? = null): List<StoredAnalyticsEvent> =
+        store.rawEvents(childId, eventType?.lowercase())
+
+    /**
+     * Delete raw events recorded before [cutoff], for the retention sweep. Returns how many were removed.
+     */
+    fun pruneEventsBefore(cutoff: Instant): Int = store.pruneEventsBefore(cutoff)
+
+    /**
+     * Delete daily totals for days ending before [cutoff]. Returns how many were removed.
+     */
+    fun pruneAggregatesBefore(cutoff: Instant): Int =
+        store.pruneAggregatesThrough(LocalDate.ofInstant(cutoff, ZoneOffset.UTC).minusDays(1))
+
+    fun getAggregates(childId: String): List<AnalyticsEventAggregate> = store.aggregates(childId)
+}
\ No newline at end of file
@@ -0,0 +1,215 @@
+package com.wondernest.services.analytics
+
+import com.wondernest.data.database.table.AnalyticsClientEvents
+import com.wondernest.data.database.table.AnalyticsEventDailyTotals
+import com.wondernest.data.database.table.AnalyticsEvents
+import kotlinx.datetime.toJavaInstant
+import kotlinx.datetime.toJavaLocalDate
+import kotlinx.datetime.toKotlinInstant
+import kotlinx.datetime.toKotlinLocalDate
+import kotlinx.serialization.json.JsonObject
+import org.jetbrains.exposed.sql.*
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.eq
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.less
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.lessEq
+import org.jetbrains.exposed.sql.transactions.transaction
+import java.time.Instant
+import java.time.LocalDate
+import java.util.UUID
+import java.util.concurrent.ConcurrentHashMap
+import java.util.concurrent.CopyOnWriteArrayList
+
+interface AnalyticsEventStore {
+    /**
+     * Count [event] in the child's totals for [day] and keep the raw event when [keepRaw]. When
+     * [clientEventId] was already recorded for the child nothing is stored and the ID of the
+     * earlier event is returned; otherwise null.
+     */
+    fun record(event: StoredAnalyticsEvent, day: LocalDate, keepRaw: Boolean, clientEventId: String?): String?
+
+    /** Raw events recorded in [from, until), oldest first */
+    fun eventsBetween(childId: String, from: Instant, until: Instant): List<StoredAnalyticsEvent>
+
+    /** Raw events for the child, oldest first, optionally of one type */
+    fun rawEvents(childId: String, eventType: String? = null): List<StoredAnalyticsEvent>
+
+    fun aggregates(childId: String): List<AnalyticsEventAggregate>
+
+    /** Delete raw events and client event IDs recorded before [cutoff]; returns the raw events deleted */
+    fun pruneEventsBefore(cutoff: Instant): Int
+
+    /** Delete daily totals for [lastExpiredDay] and earlier; returns how many were deleted */
+    fun pruneAggregatesThrough(lastExpiredDay: LocalDate): Int
+}
+
+object DatabaseAnalyticsEventStore : AnalyticsEventStore {
+    override fun record(event: StoredAnalyticsEvent, day: LocalDate, keepRaw: Boolean, clientEventId: String?): String? =
+        transaction {
+            val childId = UUID.fromString(event.childId)
+            val recordedAt = Instant.parse(event.recordedAt).toKotlinInstant()
+
+            // Claimed before counting, in the same transaction, so a concurrent replay of the same
+            // event waits on the key and then finds it taken
+            if (clientEventId != null) {
+                val claimed = AnalyticsClientEvents.insertIgnore {
+                    it[AnalyticsClientEvents.childId] = childId
+                    it[AnalyticsClientEvents.clientEventId] = clientEventId
+                    it[eventId] = UUID.fromString(event.id)
+                    it[AnalyticsClientEvents.recordedAt] = recordedAt
+                }.insertedCount > 0
+                if (!claimed) {
+                    return@transaction AnalyticsClientEvents.slice(AnalyticsClientEvents.eventId)
+                        .select {
+                            (AnalyticsClientEvents.childId eq childId) and (AnalyticsClientEvents.clientEventId eq clientEventId)
+                        }
+                        .single()[AnalyticsClientEvents.eventId]
+                        .toString()
+                }
+            }
+
+            // Totals are incremented in SQL so parallel events can't lose a count
+            val date = day.toKotlinLocalDate()
+            AnalyticsEventDailyTotals.insertIgnore {
+                it[AnalyticsEventDailyTotals.childId] = childId
+                it[eventType] = event.eventType
+                it[eventDate] = date
+            }
+            AnalyticsEventDailyTotals.update({
+                (AnalyticsEventDailyTotals.childId eq childId) and
+                    (AnalyticsEventDailyTotals.eventType eq event.eventType) and
+                    (AnalyticsEventDailyTotals.eventDate eq date)
+            }) {
+                with(SqlExpressionBuilder) {
+                    it.update(AnalyticsEventDailyTotals.eventCount, AnalyticsEventDailyTotals.eventCount + 1L)
+                    it.update(
+                        AnalyticsEventDailyTotals.totalDuration,
+                        AnalyticsEventDailyTotals.totalDuration + (event.duration ?: 0).toLong()
+                    )
+                }
+            }
+
+            if (keepRaw) {
+                AnalyticsEvents.insert {
+                    it[id] = UUID.fromString(event.id)
+                    it[AnalyticsEvents.childId] = childId
+                    it[eventType] = event.eventType
+                    it[eventData] = JsonObject(event.eventData)
+                    it[sessionId] = event.sessionId
+                    it[contentId] = event.contentId
+                    it[duration] = event.duration
+                    it[sampleRate] = event.sampleRate
+                    it[createdAt] = recordedAt
+                }
+            }
+            null
+        }
+
+    override fun eventsBetween(childId: String, from: Instant, until: Instant): List<StoredAnalyticsEvent> = transaction {
+        AnalyticsEvents.select {
+            (AnalyticsEvents.childId eq UUID.fromString(childId)) and
+                (AnalyticsEvents.createdAt greaterEq from.toKotlinInstant()) and
+                (AnalyticsEvents.createdAt less until.toKotlinInstant())
+        }
+            .orderBy(AnalyticsEvents.createdAt to SortOrder.ASC)
+            .map { it.toStoredEvent() }
+    }
+
+    override fun rawEvents(childId: String, eventType: String?): List<StoredAnalyticsEvent> = transaction {
+        AnalyticsEvents.select {
+            val ofChild = AnalyticsEvents.childId eq UUID.fromString(childId)
+            if (eventType == null) ofChild else ofChild and (AnalyticsEvents.eventType eq eventType)
+        }
+            .orderBy(AnalyticsEvents.createdAt to SortOrder.ASC)
+            .map { it.toStoredEvent() }
+    }
+
+    override fun aggregates(childId: String): List<AnalyticsEventAggregate> = transaction {
+        AnalyticsEventDailyTotals.select { AnalyticsEventDailyTotals.childId eq UUID.fromString(childId) }
+            .orderBy(AnalyticsEventDailyTotals.eventDate to SortOrder.ASC, AnalyticsEventDailyTotals.eventType to SortOrder.ASC)
+            .map { row ->
+                AnalyticsEventAggregate(
+                    childId = childId,
+                    eventType = row[AnalyticsEventDailyTotals.eventType],
+                    date = row[AnalyticsEventDailyTotals.eventDate].toJavaLocalDate().toString(),
+                    eventCount = row[AnalyticsEventDailyTotals.eventCount],
+                    totalDuration = row[AnalyticsEventDailyTotals.totalDuration]
+                )
+            }
+    }
+
+    override fun pruneEventsBefore(cutoff: Instant): Int = transaction {
+        val before = cutoff.toKotlinInstant()
+        AnalyticsClientEvents.deleteWhere { recordedAt less before }
+        AnalyticsEvents.deleteWhere { createdAt less before }
+    }
+
+    override fun pruneAggregatesThrough(lastExpiredDay: LocalDate): Int = transaction {
+        AnalyticsEventDailyTotals.deleteWhere { eventDate lessEq lastExpiredDay.toKotlinLocalDate() }
+    }
+
+    private fun ResultRow.toStoredEvent() = StoredAnalyticsEvent(
+        id = this[AnalyticsEvents.id].value.toString(),
+        eventType = this[AnalyticsEvents.eventType],
+        childId = this[AnalyticsEvents.childId].toString(),
+        contentId = this[AnalyticsEvents.contentId],
+        duration = this[AnalyticsEvents.duration],
+        eventData = this[AnalyticsEvents.eventData] as? JsonObject ?: emptyMap(),
+        sessionId = this[AnalyticsEvents.sessionId],
+        sampleRate = this[AnalyticsEvents.sampleRate],
+        recordedAt = this[AnalyticsEvents.createdAt].toJavaInstant().toString()
+    )
+}
+
+/**
+ * For tests and local runs without a database
+ */
+class InMemoryAnalyticsEventStore : AnalyticsEventStore {
+    private data class AggregateKey(val childId: String, val eventType: String, val date: LocalDate)
+
+    private val rawEvents = ConcurrentHashMap<String, CopyOnWriteArrayList<StoredAnalyticsEvent>>()
+    private val aggregates = ConcurrentHashMap<AggregateKey, AnalyticsEventAggregate>()
+    // "childId/clientEventId" to the event ID and when it was recorded
+    private val clientEventIds = ConcurrentHashMap<String, Pair<String, Instant>>()
+
+    override fun record(event: StoredAnalyticsEvent, day: LocalDate, keepRaw: Boolean, clientEventId: String?): String? {
+        if (clientEventId != null) {
+            clientEventIds.putIfAbsent("${event.childId}/$clientEventId", event.id to Instant.parse(event.recordedAt))
+                ?.let { (existing, _) -> return existing }
+        }
+        aggregates.merge(
+            AggregateKey(event.childId, event.eventType, day),
+            AnalyticsEventAggregate(event.childId, event.eventType, day.toString(), 1, (event.duration ?: 0).toLong())
+        ) { current, added ->
+            current.copy(eventCount = current.eventCount + 1, totalDuration = current.totalDuration + added.totalDuration)
+        }
+        if (keepRaw) rawEvents.computeIfAbsent(event.childId) { CopyOnWriteArrayList() }.add(event)
+        return null
+    }
+
+    override fun eventsBetween(childId: String, from: Instant, until: Instant): List<StoredAnalyticsEvent> =
+        rawEvents[childId].orEmpty().filter {
+            val at = Instant.parse(it.recordedAt)
+            !at.isBefore(from) && at.isBefore(until)
+        }
+
+    override fun rawEvents(childId: String, eventType: String?): List<StoredAnalyticsEvent> =
+        rawEvents[childId].orEmpty().filter { eventType == null || it.eventType == eventType }
+
+    override fun aggregates(childId: String): List<AnalyticsEventAggregate> =
+        aggregates.filterKeys { it.childId == childId }.values.sortedWith(compareBy({ it.date }, { it.eventType }))
+
+    override fun pruneEventsBefore(cutoff: Instant): Int {
+        clientEventIds.values.removeIf { (_, recordedAt) -> recordedAt.isBefore(cutoff) }
+        return rawEvents.values.sumOf { events ->
+            val expired = events.filter { Instant.parse(it.recordedAt).isBefore(cutoff) }
+            events.removeAll(expired.toSet())
+            expired.size
+        }
+    }
+
+    override fun pruneAggregatesThrough(lastExpiredDay: LocalDate): Int {
+        val expired = aggregates.keys.filter { it.date <= lastExpiredDay }
+        expired.forEach { aggregates.remove(it) }
+        return expired.size
+    }
+}
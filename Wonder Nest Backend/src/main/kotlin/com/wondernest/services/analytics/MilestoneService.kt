@@ -0,0 +1,121 @@
+package com.wondernest.services.analytics
+
+import com.wondernest.domain.model.ChildMilestones
+import com.wondernest.domain.model.MilestoneDefinition
+import com.wondernest.domain.model.MilestoneItem
+import com.wondernest.domain.repository.FamilyRepository
+import com.wondernest.domain.repository.MilestoneRepository
+import kotlinx.datetime.Clock
+import kotlinx.datetime.Instant
+import kotlinx.datetime.TimeZone
+import kotlinx.datetime.monthsUntil
+import kotlinx.datetime.toLocalDateTime
+import java.util.UUID
+
+/**
+ * Default milestone sets by age, loosely following the CDC developmental checklists
+ */
+object MilestoneCatalog {
+    val DEFAULT = listOf(
+        MilestoneDefinition("first-words", "language", "First words", "Says a few words like \"mama\" or \"ball\"", 9, 18),
+        MilestoneDefinition("walks-alone", "motor", "Walks alone", "Takes several steps without holding on", 9, 18),
+        MilestoneDefinition("points-to-show", "social", "Points to show", "Points to show you something interesting", 12, 18),
+        MilestoneDefinition("two-word-phrases", "language", "Two-word phrases", "Puts two words together, like \"more milk\"", 18, 30),
+        MilestoneDefinition("kicks-ball", "motor", "Kicks a ball", "Kicks a ball forward", 18, 30),
+        MilestoneDefinition("pretend-play", "cognitive", "Pretend play", "Plays make-believe with toys", 18, 36),
+        MilestoneDefinition("names-colors", "cognitive", "Names colors", "Names a few colors correctly", 30, 48),
+        MilestoneDefinition("takes-turns", "social", "Takes turns", "Takes turns in simple games", 30, 48),
+        MilestoneDefinition("tells-stories", "language", "Tells stories", "Tells a simple story with a beginning and end", 42, 60),
+        MilestoneDefinition("counts-to-ten", "cognitive", "Counts to ten", "Counts ten objects correctly", 42, 66),
+        MilestoneDefinition("hops-one-foot", "motor", "Hops on one foot", "Hops on one foot a few times", 42, 66),
+        MilestoneDefinition("writes-name", "language", "Writes own name", "Writes their first name", 54, 78),
+        MilestoneDefinition("recognizes-sight-words", "language", "Recognizes sight words", "Reads common short words by sight", 60, 84),
+        MilestoneDefinition("counts-to-100", "cognitive", "Counts to 100", "Counts to 100 by ones and tens", 60, 84),
+        MilestoneDefinition("resolves-conflicts", "social", "Resolves conflicts", "Works out small disagreements with friends", 72, 108),
+        MilestoneDefinition("reads-sentences", "language", "Reads sentences", "Reads simple sentences aloud", 72, 96),
+        MilestoneDefinition("adds-and-subtracts", "cognitive", "Adds and subtracts", "Adds and subtracts within 20", 72, 108),
+        MilestoneDefinition("rides-bike", "motor", "Rides a bike", "Rides a bike without training wheels", 60, 108),
+        MilestoneDefinition("reads-chapter-books", "language", "Reads chapter books", "Reads short chapter books independently", 96, 132),
+        MilestoneDefinition("multiplication", "cognitive", "Multiplication facts", "Knows multiplication facts up to 10", 96, 132),
+        MilestoneDefinition("team-play", "social", "Plays on a team", "Follows rules and cooperates in team games", 96, 144)
+    )
+
+    fun forAgeMonths(ageMonths: Int, catalog: List<MilestoneDefinition> = DEFAULT): List<MilestoneDefinition> =
+        catalog.filter { ageMonths in it.ageMonthsMin..it.ageMonthsMax }
+}
+
+class UnknownMilestoneException(key: String) : IllegalArgumentException("No milestone '$key' for this child's age")
+
+/**
+ * A child's milestones for their age band with what the parent has marked achieved
+ */
+class MilestoneService(
+    private val familyRepository: FamilyRepository,
+    private val milestoneRepository: MilestoneRepository,
+    private val catalog: List<MilestoneDefinition> = MilestoneCatalog.DEFAULT,
+    private val clock: () -> Instant = { Clock.System.now() }
+) {
+    companion object {
+        const val MAX_NEXT_GOALS = 3
+    }
+
+    /**
+     * Null when the child doesn't exist
+     */
+    suspend fun milestonesFor(childId: UUID): ChildMilestones? {
+        val child = familyRepository.getChildProfile(childId) ?: return null
+        val ageMonths = ageInMonths(child.birthDate)
+        val achieved = milestoneRepository.getAchievedMilestones(childId).associateBy { it.key }
+
+        val items = MilestoneCatalog.forAgeMonths(ageMonths, catalog).map { milestone ->
+            val record = achieved[milestone.key]
+            MilestoneItem(
+                key = milestone.key,
+                category = milestone.category,
+                name = milestone.name,
+                description = milestone.description,
+                ageMonthsMin = milestone.ageMonthsMin,
+                ageMonthsMax = milestone.ageMonthsMax,
+                achieved = record != null,
+                achievedAt = record?.achievedAt
+            )
+        }
+
+        return ChildMilestones(
+            childId = childId.toString(),
+            ageMonths = ageMonths,
+            milestones = items,
+            // Milestones children usually reach soonest come first
+            nextGoals = items.filterNot { it.achieved }.sortedBy { it.ageMonthsMin }.take(MAX_NEXT_GOALS).map { it.name }
+        )
+    }
+
+    /**
+     * Marks [key] achieved; marking it again keeps the first timestamp. Null when the child
+     * doesn't exist, throws [UnknownMilestoneException] for milestones outside their age band.
+     */
+    suspend fun markAchieved(childId: UUID, key: String): MilestoneItem? {
+        val child = familyRepository.getChildProfile(childId) ?: return null
+        val ageMonths = ageInMonths(child.birthDate)
+        val milestone = MilestoneCatalog.forAgeMonths(ageMonths, catalog).find { it.key == key }
+            ?: throw UnknownMilestoneException(key)
+
+        val record = milestoneRepository.markAchieved(childId, milestone, clock(), ageMonths)
+        return MilestoneItem(
+            key = milestone.key,
+            category = milestone.category,
+            name = milestone.name,
+            description = milestone.description,
+            ageMonthsMin = milestone.ageMonthsMin,
+            ageMonthsMax = milestone.ageMonthsMax,
+            achieved = true,
+            achievedAt = record.achievedAt
+        )
+    }
+
+    private fun ageInMonths(birthDate: Instant): Int {
+        val born = birthDate.toLocalDateTime(TimeZone.UTC).date
+        val today = clock().toLocalDateTime(TimeZone.UTC).date
+        return born.monthsUntil(today).coerceAtLeast(0)
+    }
+}
@@ -0,0 +1,105 @@
+package com.wondernest.services.analytics
+
+import com.wondernest.domain.repository.FamilyRepository
+import kotlinx.serialization.Serializable
+import kotlinx.serialization.json.JsonPrimitive
+import mu.KotlinLogging
+import java.time.Instant
+import java.time.LocalDate
+import java.time.ZoneId
+import java.time.ZoneOffset
+import java.util.UUID
+
+private val logger = KotlinLogging.logger {}
+
+@Serializable
+data class ScreenTimeStatus(
+    val childId: String,
+    val date: String,
+    val minutesUsed: Int,
+    val limitMinutes: Int,
+    val remainingMinutes: Int,
+    val limitReached: Boolean,
+    val exceeded: Boolean
+)
+
+@Serializable
+data class ScreenTimeLimitRequest(
+    // Null clears the child's own limit so the family default applies again
+    val dailyLimitMinutes: Int? = null
+)
+
+/**
+ * Parent-set daily screen-time limits, measured against the day's recorded event durations in
+ * the family's timezone. Going over the limit records one alert event per child per day.
+ */
+class ScreenTimeService(
+    private val familyRepository: FamilyRepository,
+    private val analyticsEventService: AnalyticsEventService,
+    private val clock: () -> Instant = Instant::now
+) {
+    companion object {
+        const val ALERT_EVENT_TYPE = "screen_time_limit_exceeded"
+        val LIMIT_RANGE = 0..24 * 60
+    }
+
+    /**
+     * Null when the child doesn't exist
+     */
+    suspend fun status(childId: UUID): ScreenTimeStatus? {
+        val child = familyRepository.getChildProfile(childId) ?: return null
+        val family = familyRepository.getFamilyById(child.familyId)
+        val zone = runCatching { ZoneId.of(family?.timezone) }.getOrDefault(ZoneOffset.UTC)
+        val limit = familyRepository.getDailyScreenTimeLimit(childId)
+            ?: family?.familySettings?.maxScreenTimeMinutes
+            ?: child.timeRestrictions.dailyScreenTimeMinutes
+        val today = LocalDate.ofInstant(clock(), zone)
+        val used = analyticsEventService.minutesOn(childId.toString(), today, zone)
+
+        return ScreenTimeStatus(
+            childId = childId.toString(),
+            date = today.toString(),
+            minutesUsed = used,
+            limitMinutes = limit,
+            remainingMinutes = (limit - used).coerceAtLeast(0),
+            limitReached = used >= limit,
+            exceeded = used > limit
+        )
+    }
+
+    /**
+     * Re-checks the child's limit after new activity was recorded, alerting once per day when
+     * it's exceeded. Returns the current status, or null when the child doesn't exist.
+     */
+    suspend fun checkLimit(childId: UUID): ScreenTimeStatus? {
+        val status = status(childId) ?: return null
+        if (status.exceeded) {
+            val alert = analyticsEventService.record(
+                childId = status.childId,
+                eventType = ALERT_EVENT_TYPE,
+                eventData = mapOf(
+                    "date" to JsonPrimitive(status.date),
+                    "minutesUsed" to JsonPrimitive(status.minutesUsed),
+                    "limitMinutes" to JsonPrimitive(status.limitMinutes)
+                ),
+                // One alert a day, however many events arrive after the limit is passed
+                clientEventId = "$ALERT_EVENT_TYPE:${status.date}"
+            )
+            if (!alert.duplicate) {
+                logger.info { "Child $childId exceeded their screen-time limit (${status.minutesUsed}/${status.limitMinutes} min)" }
+            }
+        }
+        return status
+    }
+
+    fun alerts(childId: UUID): List<StoredAnalyticsEvent> =
+        analyticsEventService.getRawEvents(childId.toString(), ALERT_EVENT_TYPE)
+
+    /**
+     * Throws [IllegalArgumentException] for limits outside a day
+     */
+    suspend fun setLimit(childId: UUID, minutes: Int?): Boolean {
+        require(minutes == null || minutes in LIMIT_RANGE) { "dailyLimitMinutes must be between 0 and ${LIMIT_RANGE.last}" }
+        return familyRepository.setDailyScreenTimeLimit(childId, minutes)
+    }
+}
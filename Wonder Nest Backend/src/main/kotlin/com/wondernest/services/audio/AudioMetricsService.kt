@@ -0,0 +1,193 @@
+package com.wondernest.services.audio
+
+import kotlinx.serialization.Serializable
+import mu.KotlinLogging
+import java.time.Instant
+import java.time.LocalDate
+import java.time.ZoneId
+import java.util.UUID
+
+private val logger = KotlinLogging.logger {}
+
+/**
+ * Per-session speech metrics computed on the device; only the aggregate scores and the set of
+ * words used are uploaded, never audio
+ */
+@Serializable
+data class AudioMetricsRequest(
+    val childId: String,
+    val sessionId: String? = null,
+    val speechClarity: Double,
+    val engagementLevel: Double,
+    val sessionDuration: Int, // seconds
+    val vocabularyUsed: List<String> = emptyList()
+)
+
+data class StoredAudioMetrics(
+    val id: String,
+    val childId: String,
+    val sessionId: String?,
+    val speechClarity: Double,
+    val engagementLevel: Double,
+    val sessionDuration: Int,
+    val vocabulary: Set<String>,
+    val recordedAt: Instant
+)
+
+enum class SpeechTrendPeriod(val buckets: Int, val bucketDays: Long) {
+    WEEK(7, 1),
+    MONTH(30, 1),
+    QUARTER(13, 7);
+
+    companion object {
+        fun parse(value: String?): SpeechTrendPeriod? =
+            if (value == null) MONTH else entries.find { it.name.equals(value, ignoreCase = true) }
+    }
+}
+
+/**
+ * One bucket of the series. Averages are null for buckets without sessions so gaps stay visible.
+ */
+@Serializable
+data class SpeechTrendPoint(
+    val periodStart: String,
+    val sessions: Int,
+    val averageClarity: Double? = null,
+    val averageEngagement: Double? = null,
+    val newWords: Int,
+    val cumulativeVocabulary: Int
+)
+
+/**
+ * Least-squares slope per bucket; null with fewer than two buckets of data
+ */
+@Serializable
+data class TrendIndicator(
+    val slope: Double? = null,
+    val direction: String // improving, declining, stable, insufficient_data
+)
+
+@Serializable
+data class SpeechTrends(
+    val childId: String,
+    val period: String,
+    val from: String,
+    val to: String,
+    val totalSessions: Int,
+    val averageClarity: Double? = null,
+    val vocabularySize: Int,
+    val points: List<SpeechTrendPoint>,
+    val clarity: TrendIndicator,
+    val engagement: TrendIndicator,
+    val vocabulary: TrendIndicator
+)
+
+/**
+ * Stores speech metrics per session and turns them into a longitudinal view for parents
+ */
+class AudioMetricsService(
+    private val store: AudioMetricsStore = InMemoryAudioMetricsStore(),
+    private val clock: () -> Instant = Instant::now
+) {
+    companion object {
+        // Slopes smaller than this per bucket are reported as stable
+        const val STABLE_SLOPE = 0.005
+    }
+
+    fun record(childId: UUID, request: AudioMetricsRequest): StoredAudioMetrics {
+        val stored = StoredAudioMetrics(
+            id = UUID.randomUUID().toString(),
+            childId = childId.toString(),
+            sessionId = request.sessionId,
+            speechClarity = request.speechClarity,
+            engagementLevel = request.engagementLevel,
+            sessionDuration = request.sessionDuration,
+            // The same word said twice, or in a different case, is still one word
+            vocabulary = request.vocabularyUsed.map { it.trim().lowercase() }.filter { it.isNotEmpty() }.toSet(),
+            recordedAt = clock()
+        )
+        store.record(stored)
+        logger.debug { "Recorded speech metrics for child $childId" }
+        return stored
+    }
+
+    /**
+     * The [period] ending today in [zone]. Vocabulary is cumulative over everything recorded,
+     * so words first heard before the window don't count as new inside it.
+     */
+    fun trends(childId: UUID, period: SpeechTrendPeriod, zone: ZoneId): SpeechTrends {
+        val today = LocalDate.ofInstant(clock(), zone)
+        val from = today.minusDays(period.buckets * period.bucketDays - 1)
+        // Only the window's sessions are loaded; earlier ones just contribute the words already known
+        val windowStart = from.atStartOfDay(zone).toInstant()
+        val sessions = store.sessions(childId, since = windowStart)
+        val known = store.vocabularyBefore(childId, windowStart).toMutableSet()
+
+        val points = (0 until period.buckets).map { index ->
+            val start = from.plusDays(index * period.bucketDays)
+            val end = start.plusDays(period.bucketDays)
+            val inBucket = sessions.filter {
+                val date = LocalDate.ofInstant(it.recordedAt, zone)
+                date >= start && date < end
+            }
+            val before = known.size
+            inBucket.forEach { known += it.vocabulary }
+            SpeechTrendPoint(
+                periodStart = start.toString(),
+                sessions = inBucket.size,
+                averageClarity = inBucket.averageOrNull { it.speechClarity },
+                averageEngagement = inBucket.averageOrNull { it.engagementLevel },
+                newWords = known.size - before,
+                cumulativeVocabulary = known.size
+            )
+        }
+
+        return SpeechTrends(
+            childId = childId.toString(),
+            period = period.name.lowercase(),
+            from = from.toString(),
+            to = today.toString(),
+            totalSessions = sessions.size,
+            averageClarity = sessions.averageOrNull { it.speechClarity },
+            vocabularySize = known.size,
+            points = points,
+            clarity = indicator(points.map { it.averageClarity }),
+            engagement = indicator(points.map { it.averageEngagement }),
+            // Only buckets with sessions, so quiet days don't flatten the growth line
+            vocabulary = indicator(points.map { point -> point.cumulativeVocabulary.toDouble().takeIf { point.sessions > 0 } })
+        )
+    }
+
+    /**
+     * Every session recorded for a child, oldest first
+     */
+    fun sessions(childId: UUID): List<StoredAudioMetrics> = store.sessions(childId)
+
+    /**
+     * Forget a child's speech metrics. Returns the number of sessions removed.
+     */
+    fun deleteChild(childId: String): Int = store.deleteChild(UUID.fromString(childId))
+
+    /**
+     * Forget sessions recorded before [cutoff], for the retention sweep. Returns how many were removed.
+     */
+    fun pruneBefore(cutoff: Instant): Int = store.pruneBefore(cutoff)
+
+    private fun List<StoredAudioMetrics>.averageOrNull(selector: (StoredAudioMetrics) -> Double): Double? =
+        if (isEmpty()) null else sumOf(selector) / size
+
+    private fun indicator(values: List<Double?>): TrendIndicator {
+        val points = values.withIndex().mapNotNull { (index, value) -> value?.let { index.toDouble() to it } }
+        if (points.size < 2) return TrendIndicator(direction = "insufficient_data")
+
+        val meanX = points.sumOf { it.first } / points.size
+        val meanY = points.sumOf { it.second } / points.size
+        val slope = points.sumOf { (x, y) -> (x - meanX) * (y - meanY) } / points.sumOf { (x, _) -> (x - meanX) * (x - meanX) }
+        val direction = when {
+            slope > STABLE_SLOPE -> "improving"
+            slope < -STABLE_SLOPE -> "declining"
+            else -> "stable"
+        }
+        return TrendIndicator(slope, direction)
+    }
+}
@@ -0,0 +1,112 @@
+package com.wondernest.services.audio
+
+import com.wondernest.data.database.table.SpeechSessionMetrics
+import kotlinx.datetime.toJavaInstant
+import kotlinx.datetime.toKotlinInstant
+import org.jetbrains.exposed.sql.*
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.eq
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.less
+import org.jetbrains.exposed.sql.transactions.transaction
+import java.time.Instant
+import java.util.UUID
+import java.util.concurrent.ConcurrentHashMap
+import java.util.concurrent.CopyOnWriteArrayList
+
+interface AudioMetricsStore {
+    fun record(metrics: StoredAudioMetrics)
+
+    /** The child's sessions, oldest first, optionally only those recorded at or after [since] */
+    fun sessions(childId: UUID, since: Instant? = null): List<StoredAudioMetrics>
+
+    /** Every word the child used in sessions recorded before [before] */
+    fun vocabularyBefore(childId: UUID, before: Instant): Set<String>
+
+    /** Delete the child's sessions; returns how many were deleted */
+    fun deleteChild(childId: UUID): Int
+
+    /** Delete sessions recorded before [cutoff]; returns how many were deleted */
+    fun pruneBefore(cutoff: Instant): Int
+}
+
+object DatabaseAudioMetricsStore : AudioMetricsStore {
+    override fun record(metrics: StoredAudioMetrics) {
+        transaction {
+            SpeechSessionMetrics.insert {
+                it[id] = UUID.fromString(metrics.id)
+                it[childId] = UUID.fromString(metrics.childId)
+                it[sessionId] = metrics.sessionId
+                it[speechClarity] = metrics.speechClarity
+                it[engagementLevel] = metrics.engagementLevel
+                it[sessionDuration] = metrics.sessionDuration
+                it[vocabulary] = metrics.vocabulary.sorted()
+                it[recordedAt] = metrics.recordedAt.toKotlinInstant()
+            }
+        }
+    }
+
+    override fun sessions(childId: UUID, since: Instant?): List<StoredAudioMetrics> = transaction {
+        SpeechSessionMetrics.select {
+            val ofChild = SpeechSessionMetrics.childId eq childId
+            if (since == null) ofChild else ofChild and (SpeechSessionMetrics.recordedAt greaterEq since.toKotlinInstant())
+        }
+            .orderBy(SpeechSessionMetrics.recordedAt to SortOrder.ASC)
+            .map { row ->
+                StoredAudioMetrics(
+                    id = row[SpeechSessionMetrics.id].toString(),
+                    childId = row[SpeechSessionMetrics.childId].toString(),
+                    sessionId = row[SpeechSessionMetrics.sessionId],
+                    speechClarity = row[SpeechSessionMetrics.speechClarity],
+                    engagementLevel = row[SpeechSessionMetrics.engagementLevel],
+                    sessionDuration = row[SpeechSessionMetrics.sessionDuration],
+                    vocabulary = row[SpeechSessionMetrics.vocabulary].toSet(),
+                    recordedAt = row[SpeechSessionMetrics.recordedAt].toJavaInstant()
+                )
+            }
+    }
+
+    override fun vocabularyBefore(childId: UUID, before: Instant): Set<String> = transaction {
+        SpeechSessionMetrics.slice(SpeechSessionMetrics.vocabulary)
+            .select {
+                (SpeechSessionMetrics.childId eq childId) and
+                    (SpeechSessionMetrics.recordedAt less before.toKotlinInstant())
+            }
+            .flatMapTo(mutableSetOf()) { it[SpeechSessionMetrics.vocabulary] }
+    }
+
+    override fun deleteChild(childId: UUID): Int = transaction {
+        SpeechSessionMetrics.deleteWhere { SpeechSessionMetrics.childId eq childId }
+    }
+
+    override fun pruneBefore(cutoff: Instant): Int = transaction {
+        SpeechSessionMetrics.deleteWhere { recordedAt less cutoff.toKotlinInstant() }
+    }
+}
+
+/**
+ * For tests and local runs without a database
+ */
+class InMemoryAudioMetricsStore : AudioMetricsStore {
+    private val metrics = ConcurrentHashMap<String, CopyOnWriteArrayList<StoredAudioMetrics>>()
+
+    override fun record(metrics: StoredAudioMetrics) {
+        this.metrics.computeIfAbsent(metrics.childId) { CopyOnWriteArrayList() }.add(metrics)
+    }
+
+    override fun sessions(childId: UUID, since: Instant?): List<StoredAudioMetrics> =
+        metrics[childId.toString()].orEmpty()
+            .filter { since == null || !it.recordedAt.isBefore(since) }
+            .sortedBy { it.recordedAt }
+
+    override fun vocabularyBefore(childId: UUID, before: Instant): Set<String> =
+        metrics[childId.toString()].orEmpty()
+            .filter { it.recordedAt.isBefore(before) }
+            .flatMapTo(mutableSetOf()) { it.vocabulary }
+
+    override fun deleteChild(childId: UUID): Int = metrics.remove(childId.toString())?.size ?: 0
+
+    override fun pruneBefore(cutoff: Instant): Int = metrics.values.sumOf { sessions ->
+        val expired = sessions.filter { it.recordedAt.isBefore(cutoff) }
+        sessions.removeAll(expired.toSet())
+        expired.size
+    }
+}
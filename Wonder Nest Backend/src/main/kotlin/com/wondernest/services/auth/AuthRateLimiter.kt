@@ -0,0 +1,143 @@
+package com.wondernest.services.auth
+
+import com.wondernest.services.resilience.RedisConnections
+import com.wondernest.services.resilience.RedisFeature
+import com.wondernest.services.resilience.RedisGuard
+import com.wondernest.utils.EmailNormalizer
+import io.lettuce.core.ScriptOutputType
+import io.lettuce.core.api.StatefulRedisConnection
+import kotlinx.coroutines.future.await
+import mu.KotlinLogging
+
+private val logger = KotlinLogging.logger {}
+
+data class AuthRateLimitConfig(
+    val maxAttempts: Int = DEFAULT_MAX_ATTEMPTS,
+    val windowSeconds: Long = DEFAULT_WINDOW_SECONDS,
+    // Peers whose X-Forwarded-For is believed; anyone else could put any address there
+    val trustedProxies: Set<String> = emptySet()
+) {
+    companion object {
+        const val DEFAULT_MAX_ATTEMPTS = 5
+        const val DEFAULT_WINDOW_SECONDS = 300L
+
+        /**
+         * Reads AUTH_RATE_LIMIT_MAX_ATTEMPTS, AUTH_RATE_LIMIT_WINDOW_SECONDS and AUTH_TRUSTED_PROXIES
+         * (comma-separated addresses of the reverse proxies in front of the API)
+         */
+        fun fromEnvironment(env: Map<String, String> = System.getenv()) = AuthRateLimitConfig(
+            maxAttempts = env["AUTH_RATE_LIMIT_MAX_ATTEMPTS"]?.toIntOrNull()?.takeIf { it > 0 } ?: DEFAULT_MAX_ATTEMPTS,
+            windowSeconds = env["AUTH_RATE_LIMIT_WINDOW_SECONDS"]?.toLongOrNull()?.takeIf { it > 0 }
+                ?: DEFAULT_WINDOW_SECONDS,
+            trustedProxies = env["AUTH_TRUSTED_PROXIES"].orEmpty()
+                .split(",").map { it.trim() }.filter { it.isNotEmpty() }.toSet()
+        )
+    }
+}
+
+data class AttemptWindow(val attempts: Long, val resetsInSeconds: Long)
+
+/**
+ * Attempt counters per key, each living for one fixed window
+ */
+interface AuthAttemptStore {
+    /** Count an attempt against [key] and return the window it landed in; the first attempt opens it */
+    suspend fun increment(key: String, windowSeconds: Long): AttemptWindow
+    /** Take back one attempt counted against [key], if its window is still open */
+    suspend fun decrement(key: String)
+}
+
+/**
+ * One counter per key, counted and expired in a single script so concurrent attempts can't
+ * all read the same count
+ */
+class RedisAuthAttemptStore(
+    private val connection: () -> StatefulRedisConnection<String, String> = { RedisConnections.shared }
+) : AuthAttemptStore {
+    companion object {
+        // ARGV: window seconds. Returns {attempts, seconds until the window resets}
+        private const val INCREMENT = """
+            local attempts = redis.call('INCR', KEYS[1])
+            if attempts == 1 then
+                redis.call('EXPIRE', KEYS[1], ARGV[1])
+            end
+            return {attempts, redis.call('TTL', KEYS[1])}
+        """
+
+        // Never recreates an expired counter, which would then have no TTL
+        private const val DECREMENT = """
+            if redis.call('EXISTS', KEYS[1]) == 1 then
+                return redis.call('DECR', KEYS[1])
+            end
+            return 0
+        """
+    }
+
+    override suspend fun increment(key: String, windowSeconds: Long): AttemptWindow {
+        val result = connection().async().eval<List<Long>>(
+            INCREMENT, ScriptOutputType.MULTI, arrayOf(key), windowSeconds.toString()
+        ).await()
+        return AttemptWindow(attempts = result[0], resetsInSeconds = result[1])
+    }
+
+    override suspend fun decrement(key: String) {
+        connection().async().eval<Long>(DECREMENT, ScriptOutputType.INTEGER, arrayOf(key)).await()
+    }
+}
+
+/**
+ * Fixed-window limit on authentication attempts against credential stuffing. Every attempt is
+ * counted before it's tried; routes hand back the ones that shouldn't count, so login releases
+ * successful sign-ins and a parent who signs in keeps their budget, while every signup counts.
+ * Redis outages fail open per [RedisFeature.RATE_LIMIT].
+ */
+class AuthRateLimiter(
+    private val config: AuthRateLimitConfig = AuthRateLimitConfig.fromEnvironment(),
+    private val store: AuthAttemptStore = RedisAuthAttemptStore(),
+    private val redisGuard: RedisGuard = RedisGuard()
+) {
+    companion object {
+        /**
+         * Keys for an attempt at [action] (e.g. "login"): always the client IP, plus the email when known
+         */
+        fun keysFor(action: String, ipAddress: String, email: String? = null): List<String> =
+            listOfNotNull(
+                "auth-attempts:$action:ip:$ipAddress",
+                email?.let(EmailNormalizer::canonical)?.takeIf { it.isNotEmpty() }?.let { "auth-attempts:$action:email:$it" }
+            )
+    }
+
+    /**
+     * The address to limit: the connecting peer, unless it's a trusted proxy, in which case the
+     * right-most X-Forwarded-For entry that isn't one of our proxies
+     */
+    fun clientAddress(peer: String, forwardedFor: String?): String {
+        if (peer !in config.trustedProxies || forwardedFor.isNullOrBlank()) return peer
+        return forwardedFor.split(",").map { it.trim() }.filter { it.isNotEmpty() }
+            .lastOrNull { it !in config.trustedProxies }
+            ?: peer
+    }
+
+    /**
+     * Count an attempt against every key in [keys]. Returns seconds until another attempt is
+     * allowed when any of them has gone over the limit, otherwise null and the attempt may go ahead
+     */
+    suspend fun acquire(keys: List<String>): Long? =
+        keys.mapNotNull { key ->
+            val window = redisGuard.execute(RedisFeature.RATE_LIMIT, fallback = { null }) {
+                store.increment(key, config.windowSeconds)
+            } ?: return@mapNotNull null
+            if (window.attempts <= config.maxAttempts) null else maxOf(1L, window.resetsInSeconds)
+        }.maxOrNull()?.also { logger.warn { "Throttled authentication attempt for ${keys.first()}" } }
+
+    /**
+     * Hand back an attempt taken by [acquire] that shouldn't count, e.g. a successful login
+     */
+    suspend fun release(keys: List<String>) {
+        keys.forEach { key ->
+            redisGuard.execute(RedisFeature.RATE_LIMIT, fallback = { }) {
+                store.decrement(key)
+            }
+        }
+    }
+}
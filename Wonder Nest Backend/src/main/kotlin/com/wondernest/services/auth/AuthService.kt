@@ -1,5 +1,7 @@
 package com.wondernest.services.auth
 
+import com.auth0.jwt.JWT
+import com.auth0.jwt.exceptions.JWTDecodeException
 import com.wondernest.data.database.table.AuthProvider
 import com.wondernest.data.database.table.UserRole
 import com.wondernest.data.database.table.UserStatus
@@ -8,9 +10,13 @@ import com.wondernest.domain.model.UserSession
 import com.wondernest.domain.model.PasswordResetToken
 import com.wondernest.domain.repository.UserRepository
 import com.wondernest.domain.repository.FamilyRepository
+import com.wondernest.domain.repository.TransactionRunner
 import com.wondernest.services.email.EmailService
+import com.wondernest.services.security.CreatorTwoFactorService
+import com.wondernest.utils.EmailNormalizer
 import kotlinx.datetime.Clock
 import kotlinx.datetime.DateTimeUnit
+import kotlinx.datetime.Instant
 import kotlinx.datetime.plus
 import mu.KotlinLogging
 import org.springframework.security.crypto.bcrypt.BCryptPasswordEncoder
@@ -36,7 +42,9 @@ data class SignupRequest(
 @Serializable
 data class LoginRequest(
     val email: String,
-    val password: String
+    val password: String,
+    // TOTP or backup code, only needed for creator accounts with 2FA enabled
+    val otpCode: String? = null
 )
 
 @Serializable
@@ -54,6 +62,26 @@ data class AuthData(
     val expiresIn: Long,
     val hasPin: Boolean = false,
     val requiresPinSetup: Boolean = false,
+    val children: List<String> = emptyList(),
+    // When true no tokens were issued; repeat the login with otpCode
+    val requires2FA: Boolean = false
+)
+
+/**
+ * The signed-in user's current context, read fresh so role and family changes show up without
+ * a new token
+ */
+@Serializable
+data class CurrentUserInfo(
+    val userId: String,
+    val email: String,
+    val firstName: String? = null,
+    val lastName: String? = null,
+    val role: String,
+    val emailVerified: Boolean,
+    val familyId: String? = null,
+    val hasPin: Boolean = false,
+    val requiresPinSetup: Boolean = false,
     val children: List<String> = emptyList()
 )
 
@@ -66,23 +94,73 @@ data class OAuthLoginRequest(
     val lastName: String? = null
 )
 
+/**
+ * @param maxFailedAttempts consecutive failed logins before a family account locks
+ * @param lockoutMinutes how long a locked account stays locked
+ */
+data class UserLockoutConfig(
+    val maxFailedAttempts: Int = 5,
+    val lockoutMinutes: Long = 15
+) {
+    companion object {
+        fun fromEnvironment(env: Map<String, String> = System.getenv()): UserLockoutConfig {
+            val defaults = UserLockoutConfig()
+            return UserLockoutConfig(
+                maxFailedAttempts = env["USER_MAX_FAILED_ATTEMPTS"]?.toIntOrNull()?.takeIf { it > 0 }
+                    ?: defaults.maxFailedAttempts,
+                lockoutMinutes = env["USER_LOCKOUT_MINUTES"]?.toLongOrNull()?.takeIf { it > 0 }
+                    ?: defaults.lockoutMinutes
+            )
+        }
+    }
+}
+
+data class EmailVerificationConfig(
+    val tokenTtlHours: Long = 24
+) {
+    companion object {
+        fun fromEnvironment(env: Map<String, String> = System.getenv()) = EmailVerificationConfig(
+            tokenTtlHours = env["EMAIL_VERIFICATION_TOKEN_TTL_HOURS"]?.toLongOrNull()?.takeIf { it > 0 } ?: 24
+        )
+    }
+}
+
+enum class EmailVerificationResult(val message: String) {
+    VERIFIED("Email verified successfully"),
+    ALREADY_USED("This verification link has already been used"),
+    EXPIRED("This verification link has expired, request a new one"),
+    INVALID("This verification link is not valid")
+}
+
 class AuthService(
     private val userRepository: UserRepository,
     private val familyRepository: FamilyRepository,
     private val jwtService: JwtService,
-    private val emailService: EmailService? = null
+    private val emailService: EmailService? = null,
+    private val lockoutConfig: UserLockoutConfig = UserLockoutConfig(),
+    private val emailVerificationConfig: EmailVerificationConfig = EmailVerificationConfig(),
+    private val creatorTwoFactor: CreatorTwoFactorService? = null,
+    private val emailNormalizer: EmailNormalizer = EmailNormalizer(),
+    private val tokenBlocklist: TokenBlocklist? = null,
+    private val transactionRunner: TransactionRunner? = null,
+    private val clock: () -> Instant = { Clock.System.now() }
 ) {
     private val passwordEncoder = BCryptPasswordEncoder(12)
     private val secureRandom = SecureRandom()
 
+    // Compared against for unknown emails so they take as long as a wrong password
+    private val dummyPasswordHash by lazy { passwordEncoder.encode(UUID.randomUUID().toString()) }
+
     suspend fun signupParent(request: SignupRequest): AuthResponse {
+        val email = emailNormalizer.normalize(request.email)
+
         // Validate email format
-        if (!isValidEmail(request.email)) {
+        if (!isValidEmail(email)) {
             throw IllegalArgumentException("Invalid email format")
         }
 
         // Check if user already exists
-        val existingUser = userRepository.getUserByEmail(request.email)
+        val existingUser = userRepository.getUserByEmail(email)
         if (existingUser != null) {
             throw IllegalArgumentException("User with this email already exists")
         }
@@ -100,7 +178,7 @@ class AuthService(
         
         val newUser = User(
             id = UUID.randomUUID(),
-            email = request.email.lowercase(),
+            email = email,
             emailVerified = false,
             authProvider = AuthProvider.EMAIL,
             firstName = firstName?.trim(),
@@ -113,45 +191,44 @@ class AuthService(
             updatedAt = now
         )
 
-        val createdUser = userRepository.createUser(newUser)
-        
-        // Store password hash separately
-        userRepository.updateUserPassword(createdUser.id, hashedPassword)
+        // User, family and membership are created together or not at all
+        val (createdUser, createdFamily) = atomically {
+            val user = userRepository.createUser(newUser)
 
-        // Create family for the parent using the user's first name
-        val familyName = "${newUser.firstName ?: "Unknown"}'s Family"
-        val family = com.wondernest.domain.model.Family(
-            id = UUID.randomUUID(),
-            name = familyName,
-            createdBy = createdUser.id,
-            timezone = request.timezone,
-            language = request.language,
-            familySettings = com.wondernest.domain.model.FamilySettings(),
-            createdAt = now,
-            updatedAt = now
-        )
+            // Store password hash separately
+            userRepository.updateUserPassword(user.id, hashedPassword)
 
-        val createdFamily = familyRepository.createFamily(family)
+            // Create family for the parent using the user's first name
+            val familyName = "${newUser.firstName ?: "Unknown"}'s Family"
+            val family = com.wondernest.domain.model.Family(
+                id = UUID.randomUUID(),
+                name = familyName,
+                createdBy = user.id,
+                timezone = request.timezone,
+                language = request.language,
+                familySettings = com.wondernest.domain.model.FamilySettings(),
+                createdAt = now,
+                updatedAt = now
+            )
 
-        // Add user as family member
-        val familyMember = com.wondernest.domain.model.FamilyMember(
-            id = UUID.randomUUID(),
-            familyId = createdFamily.id,
-            userId = createdUser.id,
-            role = "parent",
-            permissions = emptyMap(), // Simplified - not stored in database
-            joinedAt = now
-        )
+            val savedFamily = familyRepository.createFamily(family)
 
-        familyRepository.addFamilyMember(familyMember)
+            // Add user as family member
+            val familyMember = com.wondernest.domain.model.FamilyMember(
+                id = UUID.randomUUID(),
+                familyId = savedFamily.id,
+                userId = user.id,
+                role = "parent",
+                permissions = emptyMap(), // Simplified - not stored in database
+                joinedAt = now
+            )
 
-        // Send verification email
-        try {
-            emailService?.sendVerificationEmail(createdUser)
-        } catch (e: Exception) {
-            logger.warn(e) { "Failed to send verification email to ${createdUser.email}" }
+            familyRepository.addFamilyMember(familyMember)
+            user to savedFamily
         }
 
+        issueVerificationEmail(createdUser)
+
         // Generate tokens with family context
         val tokenPair = jwtService.generateTokenWithFamilyContext(createdUser, createdFamily.id)
         
@@ -176,8 +253,7 @@ class AuthService(
     }
 
     suspend fun loginParent(request: LoginRequest): AuthResponse {
-        val user = userRepository.getUserByEmail(request.email.lowercase())
-            ?: throw SecurityException("Invalid credentials")
+        val user = findUserForLogin(request)
 
         if (user.status == UserStatus.SUSPENDED) {
             throw SecurityException("Account suspended")
@@ -191,9 +267,14 @@ class AuthService(
         val passwordHash = userRepository.getUserPasswordHash(user.id)
             ?: throw SecurityException("Invalid credentials")
 
-        if (!passwordEncoder.matches(request.password, passwordHash)) {
+        val passwordMatches = passwordEncoder.matches(request.password, passwordHash)
+        rejectIfLocked(user)
+        if (!passwordMatches) {
+            recordFailedLogin(user)
             throw SecurityException("Invalid credentials")
         }
+        checkSecondFactor(user, request)?.let { return it }
+        resetFailedLogins(user)
 
         // Get family context
         val family = familyRepository.getFamilyByUserId(user.id)
@@ -230,13 +311,15 @@ class AuthService(
     }
 
     suspend fun signup(request: SignupRequest): AuthResponse {
+        val email = emailNormalizer.normalize(request.email)
+
         // Validate email format
-        if (!isValidEmail(request.email)) {
+        if (!isValidEmail(email)) {
             throw IllegalArgumentException("Invalid email format")
         }
 
         // Check if user already exists
-        val existingUser = userRepository.getUserByEmail(request.email)
+        val existingUser = userRepository.getUserByEmail(email)
         if (existingUser != null) {
             throw IllegalArgumentException("User with this email already exists")
         }
@@ -250,7 +333,7 @@ class AuthService(
         
         val newUser = User(
             id = UUID.randomUUID(),
-            email = request.email.lowercase(),
+            email = email,
             emailVerified = false,
             authProvider = AuthProvider.EMAIL,
             firstName = request.firstName?.trim(),
@@ -263,18 +346,16 @@ class AuthService(
             updatedAt = now
         )
 
-        val createdUser = userRepository.createUser(newUser)
-        
-        // Store password hash separately
-        userRepository.updateUserPassword(createdUser.id, hashedPassword)
+        val createdUser = atomically {
+            val user = userRepository.createUser(newUser)
 
-        // Send verification email
-        try {
-            emailService?.sendVerificationEmail(createdUser)
-        } catch (e: Exception) {
-            logger.warn(e) { "Failed to send verification email to ${createdUser.email}" }
+            // Store password hash separately
+            userRepository.updateUserPassword(user.id, hashedPassword)
+            user
         }
 
+        issueVerificationEmail(createdUser)
+
         // Generate tokens
         val tokenPair = jwtService.generateToken(createdUser)
         
@@ -299,8 +380,7 @@ class AuthService(
     }
 
     suspend fun login(request: LoginRequest): AuthResponse {
-        val user = userRepository.getUserByEmail(request.email.lowercase())
-            ?: throw SecurityException("Invalid credentials")
+        val user = findUserForLogin(request)
 
         if (user.status == UserStatus.SUSPENDED) {
             throw SecurityException("Account suspended")
@@ -310,9 +390,14 @@ class AuthService(
         val passwordHash = userRepository.getUserPasswordHash(user.id)
             ?: throw SecurityException("Invalid credentials")
 
-        if (!passwordEncoder.matches(request.password, passwordHash)) {
+        val passwordMatches = passwordEncoder.matches(request.password, passwordHash)
+        rejectIfLocked(user)
+        if (!passwordMatches) {
+            recordFailedLogin(user)
             throw SecurityException("Invalid credentials")
         }
+        checkSecondFactor(user, request)?.let { return it }
+        resetFailedLogins(user)
 
         // Update last login
         userRepository.updateLastLogin(user.id)
@@ -346,14 +431,15 @@ class AuthService(
         // In a real implementation, you would verify the idToken with the provider
         // For now, we'll assume it's valid
         
-        var user = userRepository.getUserByEmail(request.email.lowercase())
+        val email = emailNormalizer.normalize(request.email)
+        var user = userRepository.getUserByEmail(email)
         
         if (user == null) {
             // Create new user from OAuth
             val now = Clock.System.now()
             user = User(
                 id = UUID.randomUUID(),
-                email = request.email.lowercase(),
+                email = email,
                 emailVerified = true, // OAuth providers verify emails
                 emailVerifiedAt = now,
                 authProvider = provider,
@@ -428,8 +514,42 @@ class AuthService(
         )
     }
 
-    suspend fun logout(sessionToken: String): Boolean {
-        val session = userRepository.getSessionByToken(sessionToken)
+    /**
+     * Looks up [userId] and their family rather than trusting token claims; null when the
+     * account no longer exists
+     */
+    suspend fun getCurrentUser(userId: UUID): CurrentUserInfo? {
+        val user = userRepository.getUserById(userId)?.takeUnless { it.isDeleted } ?: return null
+        val family = familyRepository.getFamilyByUserId(user.id)
+        val children = family?.let { familyRepository.getChildrenByFamily(it.id) }.orEmpty()
+        val hasPin = userRepository.getUserPinHash(user.id) != null
+
+        return CurrentUserInfo(
+            userId = user.id.toString(),
+            email = user.email,
+            firstName = user.firstName,
+            lastName = user.lastName,
+            role = user.role.name,
+            emailVerified = user.emailVerified,
+            familyId = family?.id?.toString(),
+            hasPin = hasPin,
+            requiresPinSetup = user.role == UserRole.PARENT && !hasPin,
+            children = children.map { it.id.toString() }
+        )
+    }
+
+    /**
+     * Ends the session behind [accessToken] and blocklists the token itself, so it stops
+     * working right away instead of when it expires.
+     */
+    suspend fun logout(accessToken: String): Boolean {
+        val session = userRepository.getSessionByToken(accessToken)
+        val decoded = try {
+            JWT.decode(accessToken)
+        } catch (e: JWTDecodeException) {
+            null
+        }
+        decoded?.let { tokenBlocklist?.revoke(it) }
         return if (session != null) {
             userRepository.invalidateSession(session.id)
         } else {
@@ -437,12 +557,50 @@ class AuthService(
         }
     }
 
-    suspend fun verifyEmail(userId: UUID): Boolean {
-        return userRepository.verifyUserEmail(userId)
+    /**
+     * Issues a new verification token for [userId] and emails it; false when it couldn't be sent.
+     * Throws [IllegalStateException] when the email is already verified.
+     */
+    suspend fun sendVerificationEmail(userId: UUID): Boolean {
+        val user = userRepository.getUserById(userId) ?: return false
+        check(!user.emailVerified) { "Email is already verified" }
+        return issueVerificationEmail(user)
+    }
+
+    /**
+     * Marks the token's user as verified. Each token works once; used and expired tokens are
+     * reported separately so the client can offer to send a new link.
+     */
+    suspend fun confirmEmailVerification(token: String): EmailVerificationResult {
+        val stored = userRepository.getEmailVerificationToken(token) ?: return EmailVerificationResult.INVALID
+        val now = clock()
+        if (stored.usedAt != null) return EmailVerificationResult.ALREADY_USED
+        if (stored.expiresAt <= now) return EmailVerificationResult.EXPIRED
+        if (!userRepository.markEmailVerificationTokenUsed(stored.id, now)) return EmailVerificationResult.ALREADY_USED
+
+        userRepository.verifyUserEmail(stored.userId)
+        logger.info { "Email verified for user ${stored.userId}" }
+        return EmailVerificationResult.VERIFIED
+    }
+
+    private suspend fun <T> atomically(block: suspend () -> T): T {
+        val runner = transactionRunner ?: return block()
+        return runner.inTransaction(block)
+    }
+
+    private suspend fun issueVerificationEmail(user: User): Boolean = try {
+        val token = generateSecureToken()
+        val expiresAt = clock().plus(emailVerificationConfig.tokenTtlHours, DateTimeUnit.HOUR)
+        userRepository.createEmailVerificationToken(user.id, token, expiresAt)
+        emailService?.sendVerificationEmail(user, token) ?: false
+    } catch (e: Exception) {
+        // Signup still succeeds; the user can ask for a new link
+        logger.warn(e) { "Failed to send verification email to ${user.email}" }
+        false
     }
 
     suspend fun requestPasswordReset(email: String): Boolean {
-        val user = userRepository.getUserByEmail(email.lowercase()) ?: return false
+        val user = userRepository.getUserByEmail(emailNormalizer.normalize(email)) ?: return false
         
         val token = generateSecureToken()
         val expiresAt = Clock.System.now().plus(24, DateTimeUnit.HOUR) // 24 hours
@@ -480,12 +638,75 @@ class AuthService(
             userRepository.markPasswordResetTokenUsed(resetToken.id)
             // Invalidate all existing sessions
             userRepository.invalidateAllUserSessions(resetToken.userId)
+            tokenBlocklist?.revokeAllForUser(resetToken.userId)
             logger.info { "Password reset completed for user: ${resetToken.userId}" }
         }
         
         return updated
     }
 
+    private suspend fun findUserForLogin(request: LoginRequest): User =
+        userRepository.getUserByEmail(emailNormalizer.normalize(request.email)) ?: run {
+            passwordEncoder.matches(request.password, dummyPasswordHash)
+            throw SecurityException("Invalid credentials")
+        }
+
+    /**
+     * A locked account fails exactly like a wrong password, and only after the password hash
+     * was checked, so neither the response nor its timing shows that the account exists or is
+     * locked. Wrong passwords during a lock don't extend it.
+     */
+    private fun rejectIfLocked(user: User) {
+        val lockedUntil = user.lockedUntil ?: return
+        if (lockedUntil > clock()) {
+            logger.warn { "Login attempt for locked account ${user.id}" }
+            throw SecurityException("Invalid credentials")
+        }
+    }
+
+    private suspend fun recordFailedLogin(user: User) {
+        val now = clock()
+        // Counted in the database: the user was read before the password check, so its count
+        // may already be stale. A lock that has run out starts a fresh count.
+        val attempts = userRepository.incrementFailedLoginAttempts(user.id, now) ?: return
+
+        if (attempts >= lockoutConfig.maxFailedAttempts) {
+            userRepository.lockUser(user.id, now.plus(lockoutConfig.lockoutMinutes, DateTimeUnit.MINUTE))
+            logger.warn { "Account ${user.id} locked after $attempts failed logins" }
+        }
+    }
+
+    /**
+     * Returns a 2FA challenge when the account needs a code that wasn't sent. A wrong code
+     * counts towards the lockout like a wrong password.
+     */
+    private suspend fun checkSecondFactor(user: User, request: LoginRequest): AuthResponse? {
+        val twoFactor = creatorTwoFactor?.takeIf { it.isEnabled(user.id) } ?: return null
+        val code = request.otpCode?.trim()?.takeIf { it.isNotEmpty() }
+            ?: return AuthResponse(
+                data = AuthData(
+                    userId = user.id.toString(),
+                    email = user.email,
+                    accessToken = "",
+                    refreshToken = "",
+                    expiresIn = 0,
+                    requires2FA = true
+                )
+            )
+
+        if (!twoFactor.verify(user.id, code)) {
+            recordFailedLogin(user)
+            throw SecurityException("Invalid two-factor code")
+        }
+        return null
+    }
+
+    private suspend fun resetFailedLogins(user: User) {
+        if (user.failedLoginAttempts > 0 || user.lockedUntil != null) {
+            userRepository.updateFailedLoginAttempts(user.id, 0)
+        }
+    }
+
     private fun createUserSession(user: User, tokenPair: TokenPair): UserSession {
         val now = Clock.System.now()
         return UserSession(
@@ -1,8 +1,12 @@
 package com.wondernest.services.auth
 
 import com.auth0.jwt.JWT
+import com.auth0.jwt.JWTCreator
 import com.auth0.jwt.algorithms.Algorithm
+import com.auth0.jwt.exceptions.JWTDecodeException
+import com.auth0.jwt.interfaces.JWTVerifier
 import com.wondernest.domain.model.User
+import com.wondernest.domain.web.AdminUser
 import kotlinx.datetime.*
 import kotlinx.serialization.Serializable
 import java.util.*
@@ -14,16 +18,87 @@ data class TokenPair(
     val expiresIn: Long
 )
 
-class JwtService {
+/**
+ * Signing keys for JWTs. New tokens are signed with [signingKeyId] and carry it as the `kid`
+ * header; tokens are verified with whichever of [keys] their `kid` names. Tokens without a `kid`,
+ * issued before rotation was configured, are verified with [legacySecret], which also signs new
+ * tokens while no [signingKeyId] is set.
+ */
+data class JwtKeyConfig(
+    val legacySecret: String = DEFAULT_SECRET,
+    val signingKeyId: String? = null,
+    val keys: Map<String, String> = emptyMap()
+) {
+    init {
+        require(signingKeyId == null || signingKeyId in keys) {
+            "JWT_SIGNING_KEY_ID '$signingKeyId' is not one of the keys in JWT_KEYS"
+        }
+        require(keys.keys.none { it.isBlank() } && keys.values.none { it.isBlank() }) {
+            "JWT_KEYS entries need a non-blank key id and secret"
+        }
+    }
+
+    companion object {
+        const val DEFAULT_SECRET = "your-super-secret-jwt-key-change-this-in-production"
+
+        /**
+         * Reads JWT_SECRET (the legacy secret), JWT_SIGNING_KEY_ID and JWT_KEYS, a comma separated
+         * list of `kid=secret` entries holding the signing key and every key still accepted.
+         * To rotate: add the new key to JWT_KEYS, point JWT_SIGNING_KEY_ID at it, and remove the
+         * old key once the tokens it signed have expired.
+         */
+        fun fromEnvironment(env: Map<String, String> = System.getenv()) = JwtKeyConfig(
+            legacySecret = env["JWT_SECRET"] ?: DEFAULT_SECRET,
+            signingKeyId = env["JWT_SIGNING_KEY_ID"]?.trim()?.takeIf { it.isNotEmpty() },
+            keys = env["JWT_KEYS"].orEmpty()
+                .split(',')
+                .filter { it.isNotBlank() }
+                .associate { entry ->
+                    val kid = entry.substringBefore('=', missingDelimiterValue = "").trim()
+                    require(kid.isNotEmpty()) { "JWT_KEYS entries must look like kid=secret" }
+                    kid to entry.substringAfter('=').trim()
+                }
+        )
+    }
+}
+
+class JwtService(private val keyConfig: JwtKeyConfig = JwtKeyConfig.fromEnvironment()) {
     val issuer = System.getenv("JWT_ISSUER") ?: "wondernest-api"
     val audience = System.getenv("JWT_AUDIENCE") ?: "wondernest-users"
     val realm = System.getenv("JWT_REALM") ?: "WonderNest API"
-    val secret = System.getenv("JWT_SECRET") ?: "your-super-secret-jwt-key-change-this-in-production"
+    val secret = keyConfig.legacySecret
     
-    private val expiresIn = System.getenv("JWT_EXPIRES_IN")?.toLong() ?: 3600000L // 1 hour
+    val expiresIn = System.getenv("JWT_EXPIRES_IN")?.toLong() ?: 3600000L // 1 hour
     private val refreshExpiresIn = System.getenv("JWT_REFRESH_EXPIRES_IN")?.toLong() ?: 2592000000L // 30 days
     
-    private val algorithm = Algorithm.HMAC256(secret)
+    private val legacyAlgorithm = Algorithm.HMAC256(secret)
+    private val algorithms = keyConfig.keys.mapValues { (_, keySecret) -> Algorithm.HMAC256(keySecret) }
+    private val signingAlgorithm = keyConfig.signingKeyId?.let { algorithms.getValue(it) } ?: legacyAlgorithm
+
+    private fun JWTCreator.Builder.signed(): String =
+        (keyConfig.signingKeyId?.let { withKeyId(it) } ?: this).sign(signingAlgorithm)
+
+    /**
+     * The key a token with [keyId] in its header is verified with, or null for an unknown `kid`
+     */
+    fun algorithmFor(keyId: String?): Algorithm? = if (keyId == null) legacyAlgorithm else algorithms[keyId]
+
+    /**
+     * Verifier for [token] using the key its `kid` selects; null when the token can't be
+     * decoded or names a key that is no longer accepted
+     */
+    fun verifierFor(token: String, audience: String? = null): JWTVerifier? {
+        val keyId = try {
+            JWT.decode(token).keyId
+        } catch (e: JWTDecodeException) {
+            return null
+        }
+        val algorithm = algorithmFor(keyId) ?: return null
+        return JWT.require(algorithm)
+            .withIssuer(issuer)
+            .apply { audience?.let { withAudience(it) } }
+            .build()
+    }
 
     fun generateToken(user: User): TokenPair {
         val now = Clock.System.now()
@@ -42,7 +117,7 @@ class JwtService {
             .withClaim("nonce", nonce) // Add unique nonce for token uniqueness
             .withIssuedAt(Date(now.toEpochMilliseconds()))
             .withExpiresAt(Date(expiresAt.toEpochMilliseconds()))
-            .sign(algorithm)
+            .signed()
         
         val refreshNonce = UUID.randomUUID().toString() // Separate nonce for refresh token
         val refreshToken = JWT.create()
@@ -54,7 +129,7 @@ class JwtService {
             .withClaim("nonce", refreshNonce) // Add unique nonce for refresh token uniqueness
             .withIssuedAt(Date(now.toEpochMilliseconds()))
             .withExpiresAt(Date(refreshExpiresAt.toEpochMilliseconds()))
-            .sign(algorithm)
+            .signed()
         
         return TokenPair(accessToken, refreshToken, expiresIn)
     }
@@ -77,7 +152,7 @@ class JwtService {
             .withClaim("nonce", nonce) // Add unique nonce for token uniqueness
             .withIssuedAt(Date(now.toEpochMilliseconds()))
             .withExpiresAt(Date(expiresAt.toEpochMilliseconds()))
-            .sign(algorithm)
+            .signed()
         
         val refreshNonce = UUID.randomUUID().toString() // Separate nonce for refresh token
         val refreshToken = JWT.create()
@@ -90,17 +165,56 @@ class JwtService {
             .withClaim("nonce", refreshNonce) // Add unique nonce for refresh token uniqueness
             .withIssuedAt(Date(now.toEpochMilliseconds()))
             .withExpiresAt(Date(refreshExpiresAt.toEpochMilliseconds()))
-            .sign(algorithm)
+            .signed()
+        
+        return TokenPair(accessToken, refreshToken, expiresIn)
+    }
+
+    /**
+     * Admin tokens carry role "admin" and the admin's permission codes so that
+     * routes behind the admin-jwt authenticator can check them. [sessionId] ties the
+     * token to its AdminSession so that session can be listed and revoked.
+     */
+    fun generateAdminToken(adminUser: AdminUser, sessionId: UUID? = null): TokenPair {
+        val now = Clock.System.now()
+        val nonce = UUID.randomUUID().toString()
+        val expiresAt = now.plus(expiresIn, DateTimeUnit.MILLISECOND)
+        val refreshExpiresAt = now.plus(refreshExpiresIn, DateTimeUnit.MILLISECOND)
+        
+        val accessToken = JWT.create()
+            .withIssuer(issuer)
+            .withAudience(audience)
+            .withSubject(adminUser.id.toString())
+            .withClaim("userId", adminUser.id.toString())
+            .withClaim("email", adminUser.email)
+            .withClaim("role", "admin")
+            .withClaim("adminRole", adminUser.role.name)
+            .withClaim("permissions", adminUser.effectivePermissions())
+            .withClaim("sessionId", sessionId?.toString())
+            .withClaim("nonce", nonce)
+            .withIssuedAt(Date(now.toEpochMilliseconds()))
+            .withExpiresAt(Date(expiresAt.toEpochMilliseconds()))
+            .signed()
+        
+        val refreshNonce = UUID.randomUUID().toString()
+        val refreshToken = JWT.create()
+            .withIssuer(issuer)
+            .withAudience("$audience-refresh")
+            .withSubject(adminUser.id.toString())
+            .withClaim("userId", adminUser.id.toString())
+            .withClaim("type", "refresh")
+            .withClaim("sessionId", sessionId?.toString())
+            .withClaim("nonce", refreshNonce)
+            .withIssuedAt(Date(now.toEpochMilliseconds()))
+            .withExpiresAt(Date(refreshExpiresAt.toEpochMilliseconds()))
+            .signed()
         
         return TokenPair(accessToken, refreshToken, expiresIn)
     }
 
     fun verifyToken(token: String): String? {
         return try {
-            val jwt = JWT.require(algorithm)
-                .withIssuer(issuer)
-                .build()
-                .verify(token)
+            val jwt = verifierFor(token)?.verify(token) ?: return null
             jwt.getClaim("userId").asString()
         } catch (e: Exception) {
             null
@@ -109,11 +223,7 @@ class JwtService {
 
     fun verifyRefreshToken(token: String): String? {
         return try {
-            val jwt = JWT.require(algorithm)
-                .withIssuer(issuer)
-                .withAudience("$audience-refresh")
-                .build()
-                .verify(token)
+            val jwt = verifierFor(token, "$audience-refresh")?.verify(token) ?: return null
             
             if (jwt.getClaim("type").asString() == "refresh") {
                 jwt.getClaim("userId").asString()
@@ -123,6 +233,20 @@ class JwtService {
         }
     }
 
+    /**
+     * The AdminSession a valid admin refresh token was issued for, or null if the token is
+     * invalid or carries no session
+     */
+    fun verifyAdminRefreshToken(token: String): UUID? {
+        return try {
+            val jwt = verifierFor(token, "$audience-refresh")?.verify(token) ?: return null
+            if (jwt.getClaim("type").asString() != "refresh") return null
+            jwt.getClaim("sessionId").asString()?.let { UUID.fromString(it) }
+        } catch (e: Exception) {
+            null
+        }
+    }
+
     fun extractUserIdFromToken(token: String): String? {
         return try {
             val jwt = JWT.decode(token)
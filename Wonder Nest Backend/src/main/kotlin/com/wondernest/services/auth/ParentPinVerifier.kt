@@ -0,0 +1,209 @@
+package com.wondernest.services.auth
+
+import com.wondernest.domain.repository.UserRepository
+import com.wondernest.services.resilience.RedisConnections
+import com.wondernest.services.resilience.RedisFeature
+import com.wondernest.services.resilience.RedisGuard
+import com.wondernest.services.resilience.RedisUnavailableException
+import io.lettuce.core.ScriptOutputType
+import io.lettuce.core.api.StatefulRedisConnection
+import kotlinx.coroutines.future.await
+import kotlinx.serialization.Serializable
+import mu.KotlinLogging
+import org.springframework.security.crypto.bcrypt.BCryptPasswordEncoder
+import java.util.UUID
+
+private val logger = KotlinLogging.logger {}
+
+/**
+ * @param maxAttempts consecutive wrong PINs before PIN entry locks
+ * @param baseLockoutSeconds first lockout; each further lockout doubles it up to [maxLockoutSeconds]
+ */
+data class PinLockoutConfig(
+    val maxAttempts: Int = 5,
+    val baseLockoutSeconds: Long = 30,
+    val maxLockoutSeconds: Long = 3600
+) {
+    fun lockoutSeconds(previousLockouts: Int): Long =
+        (baseLockoutSeconds shl previousLockouts.coerceAtMost(20)).coerceAtMost(maxLockoutSeconds)
+
+    companion object {
+        fun fromEnvironment(env: Map<String, String> = System.getenv()): PinLockoutConfig {
+            val defaults = PinLockoutConfig()
+            return PinLockoutConfig(
+                maxAttempts = env["PIN_MAX_ATTEMPTS"]?.toIntOrNull()?.takeIf { it > 0 } ?: defaults.maxAttempts,
+                baseLockoutSeconds = env["PIN_LOCKOUT_BASE_SECONDS"]?.toLongOrNull()?.takeIf { it > 0 }
+                    ?: defaults.baseLockoutSeconds,
+                maxLockoutSeconds = env["PIN_LOCKOUT_MAX_SECONDS"]?.toLongOrNull()?.takeIf { it > 0 }
+                    ?: defaults.maxLockoutSeconds
+            )
+        }
+    }
+}
+
+/**
+ * [failures] counts attempts since the last success or lockout, including the one in progress.
+ * [lockouts] survives a lockout ending so repeat offenders wait longer each time.
+ */
+@Serializable
+data class PinAttemptState(
+    val failures: Int = 0,
+    val lockouts: Int = 0,
+    val lockedUntilMillis: Long? = null
+)
+
+/**
+ * Each operation must be atomic per subject, so concurrent guesses can't share an attempt
+ */
+interface PinAttemptStore {
+    /**
+     * Count an attempt and return the new state, or return the state unchanged while the
+     * subject is locked
+     */
+    suspend fun beginAttempt(subject: String, nowMillis: Long, ttlSeconds: Long): PinAttemptState
+
+    /**
+     * Lock the subject for [PinLockoutConfig.lockoutSeconds] of its previous lockouts and
+     * reset its failures. Returns the lockout length in seconds.
+     */
+    suspend fun lock(subject: String, nowMillis: Long, config: PinLockoutConfig, ttlSeconds: Long): Long
+
+    suspend fun clear(subject: String)
+}
+
+class RedisPinAttemptStore(
+    private val connection: () -> StatefulRedisConnection<String, String> = { RedisConnections.shared }
+) : PinAttemptStore {
+    companion object {
+        // ARGV: now, ttl. Returns {failures, lockouts, lockedUntil}
+        private const val BEGIN_ATTEMPT = """
+            local state = redis.call('HMGET', KEYS[1], 'failures', 'lockouts', 'locked_until')
+            local failures = tonumber(state[1] or '0')
+            local lockouts = tonumber(state[2] or '0')
+            local lockedUntil = tonumber(state[3] or '0')
+            if lockedUntil > tonumber(ARGV[1]) then
+                return {failures, lockouts, lockedUntil}
+            end
+            failures = redis.call('HINCRBY', KEYS[1], 'failures', 1)
+            redis.call('EXPIRE', KEYS[1], ARGV[2])
+            return {failures, lockouts, 0}
+        """
+
+        // ARGV: now, base seconds, max seconds, ttl. Returns the lockout in seconds
+        private const val LOCK = """
+            local lockouts = redis.call('HINCRBY', KEYS[1], 'lockouts', 1)
+            local seconds = math.min(tonumber(ARGV[2]) * 2 ^ math.min(lockouts - 1, 20), tonumber(ARGV[3]))
+            redis.call('HSET', KEYS[1], 'failures', 0, 'locked_until', math.floor(tonumber(ARGV[1]) + seconds * 1000))
+            redis.call('EXPIRE', KEYS[1], ARGV[4])
+            return seconds
+        """
+    }
+
+    override suspend fun beginAttempt(subject: String, nowMillis: Long, ttlSeconds: Long): PinAttemptState {
+        val result = connection().async().eval<List<Long>>(
+            BEGIN_ATTEMPT, ScriptOutputType.MULTI, arrayOf(key(subject)), nowMillis.toString(), ttlSeconds.toString()
+        ).await()
+        return PinAttemptState(
+            failures = result[0].toInt(),
+            lockouts = result[1].toInt(),
+            lockedUntilMillis = result[2].takeIf { it > 0 }
+        )
+    }
+
+    override suspend fun lock(subject: String, nowMillis: Long, config: PinLockoutConfig, ttlSeconds: Long): Long =
+        connection().async().eval<Long>(
+            LOCK, ScriptOutputType.INTEGER, arrayOf(key(subject)),
+            nowMillis.toString(), config.baseLockoutSeconds.toString(), config.maxLockoutSeconds.toString(), ttlSeconds.toString()
+        ).await()
+
+    override suspend fun clear(subject: String) {
+        connection().async().del(key(subject)).await()
+    }
+
+    private fun key(subject: String) = "pin-attempts:$subject"
+}
+
+sealed class PinVerificationOutcome {
+    object Verified : PinVerificationOutcome()
+    object InvalidFormat : PinVerificationOutcome()
+    data class Incorrect(val attemptsRemaining: Int) : PinVerificationOutcome()
+    data class LockedOut(val retryAfterSeconds: Long) : PinVerificationOutcome()
+    // The parent hasn't set a PIN yet
+    object NotSet : PinVerificationOutcome()
+    // Attempts can't be counted, so no PIN is checked
+    object Unavailable : PinVerificationOutcome()
+}
+
+/**
+ * Checks parent-mode PINs against the BCrypt hash stored for the parent, with brute-force
+ * protection. Attempts are tracked per signed-in user and counted before the PIN is checked,
+ * so at most [PinLockoutConfig.maxAttempts] PINs are ever compared between lockouts, however
+ * many requests arrive at once. While locked, even the right PIN is refused, and when attempts
+ * can't be counted no PIN is accepted.
+ */
+class ParentPinVerifier(
+    private val userRepository: UserRepository,
+    private val config: PinLockoutConfig = PinLockoutConfig.fromEnvironment(),
+    private val store: PinAttemptStore = RedisPinAttemptStore(),
+    private val redisGuard: RedisGuard = RedisGuard(),
+    private val clock: () -> Long = System::currentTimeMillis
+) {
+    companion object {
+        val PIN_LENGTH = 4..8
+
+        // Escalation is forgotten after a quiet day
+        private const val STATE_TTL_SECONDS = 24 * 60 * 60L
+    }
+
+    private val encoder = BCryptPasswordEncoder()
+
+    private fun isWellFormed(pin: String) = pin.length in PIN_LENGTH && pin.all { it.isDigit() }
+
+    /**
+     * Set the parent's first PIN. Returns false when they already have one; changing a PIN
+     * isn't done through this.
+     */
+    suspend fun setupPin(userId: UUID, pin: String): Boolean {
+        require(isWellFormed(pin)) { "PIN must be ${PIN_LENGTH.first} to ${PIN_LENGTH.last} digits" }
+        return userRepository.setUserPinIfUnset(userId, encoder.encode(pin))
+    }
+
+    suspend fun verify(userId: UUID, pin: String): PinVerificationOutcome {
+        if (!isWellFormed(pin)) return PinVerificationOutcome.InvalidFormat
+        val pinHash = userRepository.getUserPinHash(userId) ?: return PinVerificationOutcome.NotSet
+
+        val subject = "user:$userId"
+        val now = clock()
+        val attempt = try {
+            redisGuard.execute(RedisFeature.RATE_LIMIT, fallback = { null }) {
+                store.beginAttempt(subject, now, STATE_TTL_SECONDS)
+            }
+        } catch (e: RedisUnavailableException) {
+            null
+        } ?: return PinVerificationOutcome.Unavailable
+
+        attempt.lockedUntilMillis?.takeIf { it > now }?.let {
+            return PinVerificationOutcome.LockedOut((it - now + 999) / 1000)
+        }
+        // Another request used the last attempt and is locking the subject
+        if (attempt.failures > config.maxAttempts) {
+            return PinVerificationOutcome.LockedOut(config.lockoutSeconds(attempt.lockouts))
+        }
+
+        if (encoder.matches(pin, pinHash)) {
+            redisGuard.execute(RedisFeature.RATE_LIMIT, fallback = { }) { store.clear(subject) }
+            return PinVerificationOutcome.Verified
+        }
+
+        if (attempt.failures < config.maxAttempts) {
+            return PinVerificationOutcome.Incorrect(config.maxAttempts - attempt.failures)
+        }
+
+        val lockoutSeconds = redisGuard.execute(
+            RedisFeature.RATE_LIMIT,
+            fallback = { config.lockoutSeconds(attempt.lockouts) }
+        ) { store.lock(subject, now, config, STATE_TTL_SECONDS) }
+        logger.warn { "PIN entry locked for $subject for ${lockoutSeconds}s after ${attempt.failures} wrong PINs" }
+        return PinVerificationOutcome.LockedOut(lockoutSeconds)
+    }
+}
@@ -0,0 +1,122 @@
+package com.wondernest.services.auth
+
+import com.auth0.jwt.interfaces.Payload
+import com.wondernest.services.resilience.RedisConnections
+import com.wondernest.services.resilience.RedisFeature
+import com.wondernest.services.resilience.RedisGuard
+import com.wondernest.services.resilience.RedisUnavailableException
+import io.lettuce.core.SetArgs
+import io.lettuce.core.api.StatefulRedisConnection
+import kotlinx.coroutines.future.await
+import mu.KotlinLogging
+import java.util.UUID
+import java.util.concurrent.ConcurrentHashMap
+
+private val logger = KotlinLogging.logger {}
+
+interface TokenBlocklistStore {
+    suspend fun put(key: String, value: String, ttlSeconds: Long)
+
+    /** Values for [keys] in the same order, null where absent, fetched in one round trip */
+    suspend fun getAll(keys: List<String>): List<String?>
+}
+
+class RedisTokenBlocklistStore(
+    private val connection: () -> StatefulRedisConnection<String, String> = { RedisConnections.shared }
+) : TokenBlocklistStore {
+    override suspend fun put(key: String, value: String, ttlSeconds: Long) {
+        connection().async().set(key, value, SetArgs().ex(ttlSeconds)).await()
+    }
+
+    override suspend fun getAll(keys: List<String>): List<String?> =
+        connection().async().mget(*keys.toTypedArray()).await().map { if (it.hasValue()) it.value else null }
+}
+
+class InMemoryTokenBlocklistStore(
+    private val clock: () -> Long = System::currentTimeMillis
+) : TokenBlocklistStore {
+    private val entries = ConcurrentHashMap<String, Pair<String, Long>>()
+
+    override suspend fun put(key: String, value: String, ttlSeconds: Long) {
+        entries[key] = value to clock() + ttlSeconds * 1000
+    }
+
+    override suspend fun getAll(keys: List<String>): List<String?> = keys.map { key ->
+        entries[key]?.takeIf { (_, expiresAt) -> clock() < expiresAt }?.first
+    }
+}
+
+/**
+ * Revoked access tokens, so logging out or resetting a password takes effect before the
+ * tokens expire. Single tokens are blocklisted by their `nonce` claim until they would have
+ * expired; a password reset records a cutoff that revokes every token the user was issued
+ * up to then. Checking a token is one Redis lookup.
+ *
+ * Redis outages follow [RedisFeature.TOKEN_BLOCKLIST]: failing open lets tokens through,
+ * failing closed treats every token as revoked until Redis is back.
+ */
+class TokenBlocklist(
+    private val store: TokenBlocklistStore = RedisTokenBlocklistStore(),
+    private val redisGuard: RedisGuard = RedisGuard(),
+    private val accessTokenLifetimeMillis: Long = DEFAULT_ACCESS_TOKEN_LIFETIME_MILLIS,
+    private val clock: () -> Long = System::currentTimeMillis
+) {
+    companion object {
+        const val DEFAULT_ACCESS_TOKEN_LIFETIME_MILLIS = 3_600_000L
+
+        private fun nonceKey(nonce: String) = "token-blocklist:nonce:$nonce"
+        private fun userKey(userId: String) = "token-blocklist:user:$userId"
+    }
+
+    /**
+     * Revoke the token with [nonce] until [expiresAtMillis]; already expired tokens need nothing
+     */
+    suspend fun revoke(nonce: String, expiresAtMillis: Long) {
+        val ttlSeconds = (expiresAtMillis - clock() + 999) / 1000
+        if (ttlSeconds <= 0) return
+        write { store.put(nonceKey(nonce), "1", ttlSeconds) }
+    }
+
+    suspend fun revoke(payload: Payload) {
+        val nonce = payload.getClaim("nonce").asString() ?: return
+        revoke(nonce, payload.expiresAt?.time ?: (clock() + accessTokenLifetimeMillis))
+    }
+
+    /**
+     * Revoke every token issued to [userId] so far. Kept for one access token lifetime, after
+     * which those tokens have expired anyway.
+     */
+    suspend fun revokeAllForUser(userId: UUID) {
+        val ttlSeconds = (accessTokenLifetimeMillis + 999) / 1000
+        write { store.put(userKey(userId.toString()), (clock() / 1000).toString(), ttlSeconds) }
+    }
+
+    suspend fun isRevoked(payload: Payload): Boolean {
+        val nonce = payload.getClaim("nonce").asString()
+        val userId = payload.getClaim("userId").asString()
+        if (nonce == null && userId == null) return false
+
+        return try {
+            redisGuard.execute(RedisFeature.TOKEN_BLOCKLIST, fallback = { false }) {
+                val (blocked, cutoff) = store.getAll(
+                    listOf(nonce?.let(::nonceKey) ?: "", userId?.let(::userKey) ?: "")
+                )
+                // JWT timestamps are whole seconds, so a token issued in the same second as a
+                // password reset is treated as issued before it
+                val issuedAt = payload.issuedAt?.time?.div(1000)
+                blocked != null || (cutoff != null && issuedAt != null && issuedAt <= cutoff.toLong())
+            }
+        } catch (e: RedisUnavailableException) {
+            logger.warn { "Token blocklist unavailable, rejecting token: ${e.message}" }
+            true
+        }
+    }
+
+    private suspend fun write(block: suspend () -> Unit) {
+        try {
+            redisGuard.execute(RedisFeature.TOKEN_BLOCKLIST, fallback = { }, block = block)
+        } catch (e: RedisUnavailableException) {
+            logger.error { "Token revocation could not be recorded: ${e.message}" }
+        }
+    }
+}
@@ -0,0 +1,165 @@
+package com.wondernest.services.coppa
+
+import com.wondernest.data.database.table.*
+import com.wondernest.services.audio.AudioMetricsService
+import com.wondernest.services.storage.FileAccessController
+import com.wondernest.services.storage.StorageProvider
+import kotlinx.datetime.Clock
+import kotlinx.datetime.Instant
+import kotlinx.serialization.Serializable
+import mu.KotlinLogging
+import org.jetbrains.exposed.dao.id.UUIDTable
+import org.jetbrains.exposed.sql.*
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.eq
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.inList
+import org.jetbrains.exposed.sql.transactions.transaction
+import java.util.UUID
+
+private val logger = KotlinLogging.logger {}
+
+/**
+ * What was removed for a child, per category
+ */
+@Serializable
+data class ChildDataDeletionSummary(
+    val childId: String,
+    val gameDataRecords: Int,
+    val analyticsEvents: Int,
+    val audioSessions: Int,
+    val filesDeleted: Int,
+    val filesDetached: Int,
+    val profileDeleted: Boolean,
+    val deletedAt: String
+)
+
+/**
+ * Database side of a deletion. [deletedFiles] are file ids and storage keys whose rows were
+ * soft-deleted and whose bytes still have to be removed from storage.
+ */
+data class StoredChildDataDeletion(
+    val gameDataRecords: Int,
+    val analyticsEvents: Int,
+    val deletedFiles: List<Pair<UUID, String>>,
+    val filesDetached: Int,
+    val profileDeleted: Boolean
+)
+
+interface ChildDataDeletionStore {
+    fun deleteChildData(childId: UUID, now: Instant): StoredChildDataDeletion
+}
+
+// V3 created games.game_sessions keyed by child; the GameSessions mapping is the per-instance
+// shape from the disabled games schema, so only the column needed here is mapped
+private object ChildGameSessionRows : UUIDTable("games.game_sessions") {
+    val childId = uuid("child_id")
+}
+
+object DatabaseChildDataDeletionStore : ChildDataDeletionStore {
+    override fun deleteChildData(childId: UUID, now: Instant): StoredChildDataDeletion = transaction {
+        // The per-instance game tables are only present on databases that still carry the old
+        // games schema; instances cascade to their data, history, sessions and achievements
+        val instanceGameData = if (!ChildGameInstances.exists()) 0 else {
+            val instanceIds = ChildGameInstances.slice(ChildGameInstances.id)
+                .select { ChildGameInstances.childId eq childId }
+                .map { it[ChildGameInstances.id].value }
+            val count = if (instanceIds.isEmpty()) 0 else {
+                ChildGameData.select { ChildGameData.childGameInstanceId inList instanceIds }.count().toInt()
+            }
+            ChildGameInstances.deleteWhere { ChildGameInstances.childId eq childId }
+            count
+        }
+        val simpleGameData = SimpleGameData.deleteWhere { SimpleGameData.childId eq childId }
+        SimpleGameDataHistory.deleteWhere { SimpleGameDataHistory.childId eq childId }
+        ChildGameSessionRows.deleteWhere { ChildGameSessionRows.childId eq childId }
+        CurrencyTransactions.deleteWhere { CurrencyTransactions.childId eq childId }
+        VirtualCurrency.deleteWhere { VirtualCurrency.childId eq childId }
+        PiiReviewQueue.deleteWhere { PiiReviewQueue.childId eq childId }
+
+        val events = AnalyticsEvents.deleteWhere { AnalyticsEvents.childId eq childId }
+        AnalyticsEventDailyTotals.deleteWhere { AnalyticsEventDailyTotals.childId eq childId }
+        AnalyticsClientEvents.deleteWhere { AnalyticsClientEvents.childId eq childId }
+        DailyChildMetrics.deleteWhere { DailyChildMetrics.childId eq childId }
+        LearningInsights.deleteWhere { LearningInsights.childId eq childId }
+        Milestones.deleteWhere { Milestones.childId eq childId }
+        ChildPseudonyms.deleteWhere { ChildPseudonyms.childId eq childId }
+
+        // Same rule as deleting a single file: anything still referenced by content is kept
+        // (only detached from the child) so the content using it doesn't break
+        val files = UploadedFiles.slice(UploadedFiles.id, UploadedFiles.fileKey)
+            .select { (UploadedFiles.childId eq childId) and UploadedFiles.deletedAt.isNull() }
+            .map { it[UploadedFiles.id].value to it[UploadedFiles.fileKey] }
+        val referenced = if (files.isEmpty()) emptySet() else {
+            FileReferences.slice(FileReferences.fileId)
+                .select { FileReferences.fileId inList files.map { it.first } }
+                .map { it[FileReferences.fileId] }
+                .toSet()
+        }
+        val (detached, deleted) = files.partition { it.first in referenced }
+        if (detached.isNotEmpty()) {
+            UploadedFiles.update({ UploadedFiles.id inList detached.map { it.first } }) {
+                it[UploadedFiles.childId] = null
+            }
+        }
+        if (deleted.isNotEmpty()) {
+            UploadedFiles.update({ UploadedFiles.id inList deleted.map { it.first } }) {
+                it[isDeleted] = true
+                it[deletedAt] = now
+            }
+        }
+
+        // Marked deleted rather than archived, so the profile can't come back through restore
+        val profileDeleted = ChildProfiles.update({ ChildProfiles.id eq childId }) {
+            it[isActive] = false
+            it[deletedAt] = now
+            it[updatedAt] = now
+        } > 0
+
+        StoredChildDataDeletion(
+            gameDataRecords = instanceGameData + simpleGameData,
+            analyticsEvents = events,
+            deletedFiles = deleted,
+            filesDetached = detached.size,
+            profileDeleted = profileDeleted
+        )
+    }
+}
+
+/**
+ * Deletes a child's personal information when a parent asks for it (COPPA right to deletion).
+ * Database rows go in one transaction; stored file bytes and speech metrics are removed
+ * after it commits, so a failure there never leaves the database half deleted. Consent
+ * records are kept as the audit trail of what the parent agreed to.
+ */
+class ChildDataDeletionService(
+    private val storageProvider: StorageProvider,
+    private val fileAccessController: FileAccessController,
+    private val store: ChildDataDeletionStore = DatabaseChildDataDeletionStore,
+    private val clock: () -> Instant = { Clock.System.now() },
+    private val audioMetricsService: AudioMetricsService? = null
+) {
+    suspend fun deleteChildData(childId: UUID): ChildDataDeletionSummary {
+        val now = clock()
+        val stored = store.deleteChildData(childId, now)
+        val audioSessions = audioMetricsService?.deleteChild(childId.toString()) ?: 0
+
+        stored.deletedFiles.forEach { (fileId, fileKey) ->
+            fileAccessController.invalidateFile(fileId)
+            val removed = runCatching { storageProvider.delete(fileKey) }
+                .onFailure { logger.error(it) { "Failed to remove stored file $fileId for deleted child $childId" } }
+                .getOrDefault(false)
+            if (!removed) logger.warn { "Stored file $fileId was not removed; it is soft-deleted and unreachable" }
+        }
+
+        logger.info { "Deleted personal data for child $childId" }
+        return ChildDataDeletionSummary(
+            childId = childId.toString(),
+            gameDataRecords = stored.gameDataRecords,
+            analyticsEvents = stored.analyticsEvents,
+            audioSessions = audioSessions,
+            filesDeleted = stored.deletedFiles.size,
+            filesDetached = stored.filesDetached,
+            profileDeleted = stored.profileDeleted,
+            deletedAt = now.toString()
+        )
+    }
+}
@@ -0,0 +1,43 @@
+package com.wondernest.services.coppa
+
+import kotlinx.coroutines.CancellationException
+import kotlinx.coroutines.CoroutineScope
+import kotlinx.coroutines.Job
+import kotlinx.coroutines.delay
+import kotlinx.coroutines.isActive
+import kotlinx.coroutines.launch
+import mu.KotlinLogging
+
+private val logger = KotlinLogging.logger {}
+
+/**
+ * Periodically removes stored export archives once they pass COPPA_EXPORT_RETENTION_SECONDS
+ */
+class ChildDataExportExpiryTask(
+    private val exportService: ChildDataExportService,
+    private val intervalMillis: Long = DEFAULT_INTERVAL_MILLIS
+) {
+
+    suspend fun runOnce(): Int {
+        val removed = exportService.purgeExpiredArchives()
+        if (removed > 0) logger.info { "Removed $removed expired child data export archives" }
+        return removed
+    }
+
+    fun start(scope: CoroutineScope): Job = scope.launch {
+        while (isActive) {
+            try {
+                runOnce()
+            } catch (e: CancellationException) {
+                throw e
+            } catch (e: Exception) {
+                logger.warn(e) { "Child data export expiry failed, retrying next interval" }
+            }
+            delay(intervalMillis)
+        }
+    }
+
+    companion object {
+        const val DEFAULT_INTERVAL_MILLIS = 15 * 60 * 1000L
+    }
+}
@@ -0,0 +1,484 @@
+package com.wondernest.services.coppa
+
+import com.wondernest.data.database.DatabasePoolConfig
+import com.wondernest.data.database.table.*
+import com.wondernest.data.database.transactionWithStatementTimeout
+import com.wondernest.services.analytics.AnalyticsEventAggregate
+import com.wondernest.services.analytics.AnalyticsEventService
+import com.wondernest.services.audio.AudioMetricsService
+import com.wondernest.services.games.fromStorageMap
+import com.wondernest.services.storage.SignedUrlService
+import com.wondernest.services.storage.StorageProvider
+import com.wondernest.services.storage.StorageResult
+import kotlinx.coroutines.CoroutineScope
+import kotlinx.coroutines.Dispatchers
+import kotlinx.coroutines.SupervisorJob
+import kotlinx.coroutines.launch
+import kotlinx.datetime.Clock
+import kotlinx.datetime.Instant
+import kotlinx.serialization.Serializable
+import kotlinx.serialization.encodeToString
+import kotlinx.serialization.json.Json
+import kotlinx.serialization.json.JsonArray
+import kotlinx.serialization.json.JsonObject
+import kotlinx.serialization.json.JsonPrimitive
+import kotlinx.serialization.json.buildJsonObject
+import kotlinx.serialization.json.put
+import mu.KotlinLogging
+import org.jetbrains.exposed.dao.id.EntityID
+import org.jetbrains.exposed.sql.*
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.eq
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.inList
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.lessEq
+import org.jetbrains.exposed.sql.transactions.experimental.newSuspendedTransaction
+import org.jetbrains.exposed.sql.transactions.transaction
+import java.util.UUID
+import kotlin.time.Duration.Companion.seconds
+
+private val logger = KotlinLogging.logger {}
+
+@Serializable
+data class ExportedAnalytics(
+    val events: List<JsonObject>,
+    val dailyTotals: List<AnalyticsEventAggregate>,
+    val dailyMetrics: List<JsonObject> = emptyList(),
+    val learningInsights: List<JsonObject> = emptyList()
+)
+
+/**
+ * Everything stored about a child, for a parent to review
+ */
+@Serializable
+data class ChildDataExport(
+    val childId: String,
+    val exportedAt: String,
+    val profile: JsonObject,
+    val gameData: List<JsonObject>,
+    val analytics: ExportedAnalytics,
+    val milestones: List<JsonObject>,
+    val audioSessions: List<JsonObject>,
+    val files: List<JsonObject>
+)
+
+/**
+ * Database rows for an export; null profile means the child doesn't exist
+ */
+data class ChildDataRows(
+    val profile: JsonObject?,
+    val gameData: List<JsonObject>,
+    val events: List<JsonObject>,
+    val dailyMetrics: List<JsonObject> = emptyList(),
+    val learningInsights: List<JsonObject> = emptyList(),
+    val milestones: List<JsonObject> = emptyList(),
+    val files: List<JsonObject>
+)
+
+interface ChildDataExportSource {
+    /** Rough number of rows an export would contain, to decide whether to build it in the background */
+    fun countRows(childId: UUID): Long
+
+    fun load(childId: UUID): ChildDataRows
+}
+
+object DatabaseChildDataExportSource : ChildDataExportSource {
+    // Exports read everything stored about a child, more than the per-request statement timeout allows for
+    private val statementTimeoutMillis by lazy { DatabasePoolConfig.fromEnvironment().heavyStatementTimeoutMillis }
+
+    override fun countRows(childId: UUID): Long = transactionWithStatementTimeout(statementTimeoutMillis) {
+        (if (ChildGameInstances.exists()) gameDataQuery(childId).count() else 0) +
+            SimpleGameData.select { SimpleGameData.childId eq childId }.count() +
+            AnalyticsEvents.select { AnalyticsEvents.childId eq childId }.count() +
+            DailyChildMetrics.select { DailyChildMetrics.childId eq childId }.count() +
+            LearningInsights.select { LearningInsights.childId eq childId }.count() +
+            Milestones.select { Milestones.childId eq childId }.count()
+    }
+
+    override fun load(childId: UUID): ChildDataRows = transactionWithStatementTimeout(statementTimeoutMillis) {
+        val profile = ChildProfiles.select { ChildProfiles.id eq childId }.singleOrNull()?.let { row ->
+            buildJsonObject {
+                put("name", row[ChildProfiles.name])
+                put("nickname", row[ChildProfiles.nickname])
+                put("birthDate", row[ChildProfiles.birthDate].toString())
+                put("gender", row[ChildProfiles.gender])
+                put("avatarUrl", row[ChildProfiles.avatarUrl])
+                put("interests", row[ChildProfiles.interests])
+                put("favoriteColors", row[ChildProfiles.favoriteColors])
+                put("createdAt", row[ChildProfiles.createdAt].toString())
+                put("updatedAt", row[ChildProfiles.updatedAt].toString())
+            }
+        }
+
+        // The per-instance game tables only exist on databases that still carry the old games schema
+        val instanceGameData = if (!ChildGameInstances.exists()) emptyList() else gameDataQuery(childId).map { row ->
+            buildJsonObject {
+                put("gameKey", row[GameRegistry.gameKey])
+                put("dataKey", row[ChildGameData.dataKey])
+                put("dataVersion", row[ChildGameData.dataVersion])
+                put("dataValue", fromStorageMap(row[ChildGameData.dataValue]))
+                put("updatedAt", row[ChildGameData.updatedAt].toString())
+            }
+        }
+        val gameData = instanceGameData + SimpleGameData.select { SimpleGameData.childId eq childId }.map { row ->
+            buildJsonObject {
+                put("gameKey", row[SimpleGameData.gameType])
+                put("dataKey", row[SimpleGameData.dataKey])
+                put("dataValue", JsonObject(row[SimpleGameData.dataValue]))
+                put("updatedAt", row[SimpleGameData.updatedAt].toString())
+            }
+        }
+
+        val events = AnalyticsEvents.select { AnalyticsEvents.childId eq childId }
+            .orderBy(AnalyticsEvents.createdAt to SortOrder.ASC)
+            .map { row ->
+                buildJsonObject {
+                    put("eventType", row[AnalyticsEvents.eventType])
+                    put("eventCategory", row[AnalyticsEvents.eventCategory])
+                    put("eventData", row[AnalyticsEvents.eventData])
+                    put("contentId", row[AnalyticsEvents.contentId])
+                    put("duration", row[AnalyticsEvents.duration])
+                    put("sessionId", row[AnalyticsEvents.sessionId])
+                    put("timestamp", row[AnalyticsEvents.createdAt].toString())
+                }
+            }
+
+        val dailyMetrics = DailyChildMetrics.select { DailyChildMetrics.childId eq childId }
+            .orderBy(DailyChildMetrics.date to SortOrder.ASC)
+            .map { it.toJson(DailyChildMetrics, DailyChildMetrics.childId) }
+        val learningInsights = LearningInsights.select { LearningInsights.childId eq childId }
+            .orderBy(LearningInsights.createdAt to SortOrder.ASC)
+            .map { it.toJson(LearningInsights, LearningInsights.childId) }
+        val milestones = Milestones.select { Milestones.childId eq childId }
+            .map { it.toJson(Milestones, Milestones.childId) }
+
+        // Metadata only; the files themselves can be downloaded through the file routes
+        val files = UploadedFiles.select { (UploadedFiles.childId eq childId) and UploadedFiles.deletedAt.isNull() }
+            .map { row ->
+                buildJsonObject {
+                    put("id", row[UploadedFiles.id].value.toString())
+                    put("originalName", row[UploadedFiles.originalName])
+                    put("mimeType", row[UploadedFiles.mimeType])
+                    put("fileSize", row[UploadedFiles.fileSize])
+                    put("category", row[UploadedFiles.category])
+                    put("uploadedAt", row[UploadedFiles.uploadedAt].toString())
+                }
+            }
+
+        ChildDataRows(profile, gameData, events, dailyMetrics, learningInsights, milestones, files)
+    }
+
+    // Every mapped column by its database name, so new columns show up in exports without a change here
+    private fun ResultRow.toJson(table: Table, vararg skip: Column<*>): JsonObject = buildJsonObject {
+        table.columns.filterNot { it in skip }.forEach { column ->
+            val value = this@toJson[column]
+            put(column.name, if (value is EntityID<*>) value.value.toString() else value?.toString())
+        }
+    }
+
+    private fun gameDataQuery(childId: UUID): Query =
+        ChildGameData.join(ChildGameInstances, JoinType.INNER) {
+            ChildGameData.childGameInstanceId eq ChildGameInstances.id
+        }.join(GameRegistry, JoinType.INNER) {
+            ChildGameInstances.gameId eq GameRegistry.id
+        }.select { ChildGameInstances.childId eq childId }
+}
+
+/**
+ * Records who exported a child's data
+ */
+fun interface ExportAuditLog {
+    fun recordExport(parentId: UUID, childId: UUID, delivery: String)
+}
+
+object DatabaseExportAuditLog : ExportAuditLog {
+    override fun recordExport(parentId: UUID, childId: UUID, delivery: String) {
+        transaction {
+            AdminAuditLog.insert {
+                it[userId] = parentId
+                it[userType] = "parent"
+                it[action] = "child_data_export"
+                it[resourceType] = "child_profile"
+                it[resourceId] = childId
+                it[actionData] = buildJsonObject { put("delivery", delivery) }
+                it[success] = true
+                it[createdAt] = Clock.System.now()
+            }
+        }
+    }
+}
+
+data class ChildDataExportConfig(
+    val inlineMaxRows: Long = DEFAULT_INLINE_MAX_ROWS,
+    val urlExpirySeconds: Int = DEFAULT_URL_EXPIRY_SECONDS,
+    val archiveRetentionSeconds: Long = DEFAULT_ARCHIVE_RETENTION_SECONDS
+) {
+    companion object {
+        const val DEFAULT_INLINE_MAX_ROWS = 5_000L
+        const val DEFAULT_URL_EXPIRY_SECONDS = 15 * 60
+        const val DEFAULT_ARCHIVE_RETENTION_SECONDS = 24 * 60 * 60L
+
+        /**
+         * Reads COPPA_EXPORT_INLINE_MAX_ROWS, COPPA_EXPORT_URL_EXPIRY_SECONDS and
+         * COPPA_EXPORT_RETENTION_SECONDS
+         */
+        fun fromEnvironment(env: Map<String, String> = System.getenv()) = ChildDataExportConfig(
+            inlineMaxRows = env["COPPA_EXPORT_INLINE_MAX_ROWS"]?.toLongOrNull()?.takeIf { it >= 0 }
+                ?: DEFAULT_INLINE_MAX_ROWS,
+            urlExpirySeconds = env["COPPA_EXPORT_URL_EXPIRY_SECONDS"]?.toIntOrNull()?.takeIf { it > 0 }
+                ?: DEFAULT_URL_EXPIRY_SECONDS,
+            archiveRetentionSeconds = env["COPPA_EXPORT_RETENTION_SECONDS"]?.toLongOrNull()?.takeIf { it > 0 }
+                ?: DEFAULT_ARCHIVE_RETENTION_SECONDS
+        )
+    }
+}
+
+enum class ExportJobStatus { PENDING, READY, FAILED, EXPIRED }
+
+@Serializable
+data class ChildDataExportJob(
+    val jobId: String,
+    val childId: String,
+    val status: ExportJobStatus,
+    val downloadUrl: String? = null,
+    val requestedAt: String,
+    val expiresAt: String? = null
+)
+
+sealed class ChildDataExportResult {
+    data class Inline(val export: ChildDataExport) : ChildDataExportResult()
+    data class Deferred(val job: ChildDataExportJob) : ChildDataExportResult()
+    object ChildNotFound : ChildDataExportResult()
+}
+
+/**
+ * A deferred export and, once built, where its archive is stored and until when
+ */
+data class StoredExportJob(
+    val job: ChildDataExportJob,
+    val fileKey: String? = null,
+    val fileExpiresAt: Instant? = null
+)
+
+interface ChildDataExportJobStore {
+    suspend fun create(job: ChildDataExportJob, parentId: UUID)
+
+    /**
+     * Records the built [archive] as the job's download. The archive is registered as an uploaded
+     * file with the job's ID, owned by the requesting parent, so it's served by the signed
+     * download route.
+     */
+    suspend fun markReady(jobId: String, archive: StorageResult, fileName: String, completedAt: Instant, fileExpiresAt: Instant)
+
+    suspend fun markFailed(jobId: String, completedAt: Instant)
+
+    suspend fun find(jobId: String): StoredExportJob?
+
+    /** Marks ready jobs whose archive is past [now] as expired, along with their files, and returns them */
+    suspend fun expireDue(now: Instant): List<StoredExportJob>
+}
+
+object DatabaseChildDataExportJobStore : ChildDataExportJobStore {
+    override suspend fun create(job: ChildDataExportJob, parentId: UUID) {
+        newSuspendedTransaction(Dispatchers.IO) {
+            ChildDataExportJobs.insert {
+                it[id] = UUID.fromString(job.jobId)
+                it[childId] = UUID.fromString(job.childId)
+                it[ChildDataExportJobs.parentId] = parentId
+                it[status] = job.status.name.lowercase()
+                it[requestedAt] = Instant.parse(job.requestedAt)
+            }
+        }
+    }
+
+    override suspend fun markReady(jobId: String, archive: StorageResult, fileName: String, completedAt: Instant, fileExpiresAt: Instant) {
+        val id = UUID.fromString(jobId)
+        newSuspendedTransaction(Dispatchers.IO) {
+            val job = ChildDataExportJobs.select { ChildDataExportJobs.id eq id }.single()
+            UploadedFiles.insert {
+                it[UploadedFiles.id] = id
+                it[userId] = job[ChildDataExportJobs.parentId]
+                it[UploadedFiles.childId] = job[ChildDataExportJobs.childId].value
+                it[UploadedFiles.fileKey] = archive.key
+                it[originalName] = fileName
+                it[mimeType] = archive.contentType
+                it[fileSize] = archive.size
+                it[storageProvider] = "local"
+                it[url] = archive.url
+                it[isPublic] = false
+                it[category] = "document"
+                it[metadata] = mapOf("purpose" to "coppa_export")
+                it[uploadedAt] = completedAt
+            }
+            ChildDataExportJobs.update({ ChildDataExportJobs.id eq id }) {
+                it[status] = ExportJobStatus.READY.name.lowercase()
+                it[ChildDataExportJobs.fileKey] = archive.key
+                it[ChildDataExportJobs.completedAt] = completedAt
+                it[ChildDataExportJobs.fileExpiresAt] = fileExpiresAt
+            }
+        }
+    }
+
+    override suspend fun markFailed(jobId: String, completedAt: Instant) {
+        newSuspendedTransaction(Dispatchers.IO) {
+            ChildDataExportJobs.update({ ChildDataExportJobs.id eq UUID.fromString(jobId) }) {
+                it[status] = ExportJobStatus.FAILED.name.lowercase()
+                it[ChildDataExportJobs.completedAt] = completedAt
+            }
+        }
+    }
+
+    override suspend fun find(jobId: String): StoredExportJob? {
+        val id = runCatching { UUID.fromString(jobId) }.getOrNull() ?: return null
+        return newSuspendedTransaction(Dispatchers.IO) {
+            ChildDataExportJobs.select { ChildDataExportJobs.id eq id }.singleOrNull()?.toStoredJob()
+        }
+    }
+
+    override suspend fun expireDue(now: Instant): List<StoredExportJob> = newSuspendedTransaction(Dispatchers.IO) {
+        val due = ChildDataExportJobs.select {
+            (ChildDataExportJobs.status eq ExportJobStatus.READY.name.lowercase()) and
+                (ChildDataExportJobs.fileExpiresAt lessEq now)
+        }.forUpdate().map { it.toStoredJob() }
+        if (due.isNotEmpty()) {
+            val ids = due.map { UUID.fromString(it.job.jobId) }
+            ChildDataExportJobs.update({ ChildDataExportJobs.id inList ids }) {
+                it[status] = ExportJobStatus.EXPIRED.name.lowercase()
+            }
+            UploadedFiles.update({ (UploadedFiles.id inList ids) and UploadedFiles.deletedAt.isNull() }) {
+                it[isDeleted] = true
+                it[deletedAt] = now
+            }
+        }
+        due
+    }
+
+    private fun ResultRow.toStoredJob() = StoredExportJob(
+        job = ChildDataExportJob(
+            jobId = this[ChildDataExportJobs.id].value.toString(),
+            childId = this[ChildDataExportJobs.childId].value.toString(),
+            status = ExportJobStatus.valueOf(this[ChildDataExportJobs.status].uppercase()),
+            requestedAt = this[ChildDataExportJobs.requestedAt].toString(),
+            expiresAt = this[ChildDataExportJobs.fileExpiresAt]?.toString()
+        ),
+        fileKey = this[ChildDataExportJobs.fileKey],
+        fileExpiresAt = this[ChildDataExportJobs.fileExpiresAt]
+    )
+}
+
+/**
+ * Builds a parent's review copy of everything stored about their child. Small exports are
+ * returned directly; large ones are built in the background, stored, and fetched through a
+ * short-lived, single-use signed link until the archive expires. Every export is written to
+ * the audit log.
+ */
+class ChildDataExportService(
+    private val analyticsEventService: AnalyticsEventService,
+    private val storageProvider: StorageProvider,
+    private val signedUrlService: SignedUrlService,
+    private val source: ChildDataExportSource = DatabaseChildDataExportSource,
+    private val auditLog: ExportAuditLog = DatabaseExportAuditLog,
+    private val config: ChildDataExportConfig = ChildDataExportConfig.fromEnvironment(),
+    private val scope: CoroutineScope = CoroutineScope(SupervisorJob() + Dispatchers.IO),
+    private val clock: () -> Instant = { Clock.System.now() },
+    private val jobStore: ChildDataExportJobStore = DatabaseChildDataExportJobStore,
+    private val audioMetricsService: AudioMetricsService? = null
+) {
+    suspend fun requestExport(childId: UUID, parentId: UUID): ChildDataExportResult {
+        if (source.countRows(childId) <= config.inlineMaxRows) {
+            val export = build(childId) ?: return ChildDataExportResult.ChildNotFound
+            auditLog.recordExport(parentId, childId, "inline")
+            return ChildDataExportResult.Inline(export)
+        }
+
+        val job = ChildDataExportJob(
+            jobId = UUID.randomUUID().toString(),
+            childId = childId.toString(),
+            status = ExportJobStatus.PENDING,
+            requestedAt = clock().toString()
+        )
+        jobStore.create(job, parentId)
+        auditLog.recordExport(parentId, childId, "deferred")
+
+        scope.launch {
+            try {
+                val export = build(childId) ?: error("Child $childId no longer exists")
+                val bytes = Json.encodeToString(export).toByteArray()
+                val fileName = "child-export-${job.jobId}.json"
+                val stored = storageProvider.upload(
+                    fileName = fileName,
+                    contentType = "application/json",
+                    inputStream = bytes.inputStream(),
+                    metadata = mapOf("childId" to childId.toString(), "purpose" to "coppa_export")
+                )
+                val completedAt = clock()
+                jobStore.markReady(job.jobId, stored, fileName, completedAt, completedAt + config.archiveRetentionSeconds.seconds)
+            } catch (e: Exception) {
+                logger.error(e) { "Export ${job.jobId} for child $childId failed" }
+                jobStore.markFailed(job.jobId, clock())
+            }
+        }
+        return ChildDataExportResult.Deferred(job)
+    }
+
+    /**
+     * A deferred export's state, with a freshly signed single-use download link while its archive
+     * is kept. [requestBaseUrl] is used for the link when no public base URL is configured.
+     */
+    suspend fun getJob(childId: UUID, jobId: String, requestBaseUrl: String? = null): ChildDataExportJob? {
+        val stored = jobStore.find(jobId)?.takeIf { it.job.childId == childId.toString() } ?: return null
+        if (stored.job.status != ExportJobStatus.READY || stored.fileKey == null) return stored.job
+        // Past its expiry the archive is about to be swept; don't hand out another link to it
+        if (stored.fileExpiresAt != null && stored.fileExpiresAt <= clock()) {
+            return stored.job.copy(status = ExportJobStatus.EXPIRED)
+        }
+        val downloadUrl = signedUrlService.generateSignedUrl(
+            UUID.fromString(stored.job.jobId),
+            config.urlExpirySeconds.toLong(),
+            singleUse = true,
+            requestBaseUrl = requestBaseUrl
+        )
+        return stored.job.copy(downloadUrl = downloadUrl)
+    }
+
+    /**
+     * Removes stored archives past their retention; returns how many were removed
+     */
+    suspend fun purgeExpiredArchives(): Int {
+        val expired = jobStore.expireDue(clock())
+        expired.forEach { stored ->
+            val key = stored.fileKey ?: return@forEach
+            runCatching { storageProvider.delete(key) }
+                .onFailure { logger.error(it) { "Failed to remove expired export archive for job ${stored.job.jobId}" } }
+        }
+        return expired.size
+    }
+
+    private fun build(childId: UUID): ChildDataExport? {
+        val rows = source.load(childId)
+        val profile = rows.profile ?: return null
+        val child = childId.toString()
+        return ChildDataExport(
+            childId = child,
+            exportedAt = clock().toString(),
+            profile = profile,
+            gameData = rows.gameData,
+            analytics = ExportedAnalytics(
+                events = rows.events,
+                dailyTotals = analyticsEventService.getAggregates(child),
+                dailyMetrics = rows.dailyMetrics,
+                learningInsights = rows.learningInsights
+            ),
+            milestones = rows.milestones,
+            audioSessions = audioMetricsService?.sessions(childId).orEmpty().map { session ->
+                buildJsonObject {
+                    put("sessionId", session.sessionId)
+                    put("speechClarity", session.speechClarity)
+                    put("engagementLevel", session.engagementLevel)
+                    put("sessionDuration", session.sessionDuration)
+                    put("vocabularyUsed", JsonArray(session.vocabulary.sorted().map(::JsonPrimitive)))
+                    put("recordedAt", session.recordedAt.toString())
+                }
+            },
+            files = rows.files
+        )
+    }
+}
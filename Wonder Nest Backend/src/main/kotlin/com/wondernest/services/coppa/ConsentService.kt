@@ -0,0 +1,258 @@
+package com.wondernest.services.coppa
+
+import com.wondernest.domain.model.ChildProfile
+import com.wondernest.domain.model.ConsentRecord
+import com.wondernest.domain.model.ConsentVerificationStatus
+import com.wondernest.domain.repository.ConsentRepository
+import com.wondernest.domain.repository.FamilyRepository
+import kotlinx.datetime.Clock
+import kotlinx.datetime.Instant
+import kotlinx.serialization.Serializable
+import mu.KotlinLogging
+import java.util.UUID
+import kotlin.time.Duration.Companion.days
+
+private val logger = KotlinLogging.logger {}
+
+enum class ConsentStatus {
+    NOT_PROVIDED, // no consent records for the child
+    GRANTED,      // data collection consent is active
+    LIMITED,      // some permissions granted, but not data collection
+    REVOKED       // every consent given has since been revoked or refused
+}
+
+@Serializable
+data class ChildConsentSummary(
+    val childId: String,
+    val childName: String,
+    val consentStatus: ConsentStatus,
+    val grantedPermissions: List<String>,
+    val dataCollectionAllowed: Boolean,
+    val lastUpdatedAt: String?,
+    val warnings: List<String>,
+    val nextSteps: List<String>,
+    val expiresAt: String? = null,
+    val pendingVerification: Boolean = false
+)
+
+@Serializable
+data class ConsentDashboard(
+    val familyId: String,
+    val children: List<ChildConsentSummary>,
+    val generatedAt: String
+)
+
+/**
+ * Outcome of one consent attempt, before it is shaped into an API response
+ */
+data class ConsentSubmission(
+    val records: List<ConsentRecord>,
+    val verificationStatus: ConsentVerificationStatus,
+    val expiresAt: Instant?,
+    val complianceWarnings: List<String>
+)
+
+/**
+ * Stores COPPA consent decisions and summarizes them per child
+ */
+class ConsentService(
+    private val familyRepository: FamilyRepository,
+    private val consentRepository: ConsentRepository,
+    private val verifiers: ConsentVerifiers = ConsentVerifiers.fromEnvironment(),
+    private val clock: () -> Instant = { Clock.System.now() }
+) {
+    companion object {
+        const val DATA_COLLECTION = "data_collection"
+        private const val COPPA_AGE_LIMIT = 13
+        const val CONSENT_VALIDITY_DAYS = 365
+
+        /**
+         * Permissions currently in effect: the most recent verified decision per consent type wins,
+         * and lapses at its expiry. Pending and failed verifications change nothing.
+         */
+        fun grantedPermissions(records: List<ConsentRecord>, now: Instant = Clock.System.now()): List<String> =
+            records.filter { it.verificationStatus == ConsentVerificationStatus.VERIFIED }
+                .groupBy { it.consentType }
+                .mapValues { (_, decisions) -> decisions.maxBy { it.createdAt } }
+                .values
+                .filter { it.granted && it.revokedAt == null && (it.expiresAt == null || it.expiresAt > now) }
+                .map { it.consentType }
+                .sorted()
+    }
+
+    /**
+     * Record one consent row per permission. The caller must already have checked
+     * that the child belongs to the family.
+     */
+    suspend fun recordConsent(
+        familyId: UUID,
+        parentId: UUID,
+        childId: UUID,
+        permissions: Map<String, Boolean>,
+        consentText: String? = null,
+        verificationMethod: String? = null,
+        verificationStatus: ConsentVerificationStatus = ConsentVerificationStatus.PENDING,
+        expiresAt: Instant? = null
+    ): List<ConsentRecord> {
+        require(permissions.isNotEmpty()) { "At least one permission is required" }
+        val now = clock()
+        logger.info { "Recording ${permissions.size} consent decisions for child $childId" }
+
+        return permissions.map { (type, granted) ->
+            consentRepository.recordConsent(
+                ConsentRecord(
+                    id = UUID.randomUUID(),
+                    familyId = familyId,
+                    childId = childId,
+                    parentId = parentId,
+                    consentType = type.trim().lowercase(),
+                    granted = granted,
+                    consentText = consentText,
+                    createdAt = now,
+                    verificationMethod = verificationMethod,
+                    verificationStatus = verificationStatus,
+                    expiresAt = expiresAt
+                )
+            )
+        }
+    }
+
+    /**
+     * Verify the parent with [verificationMethod] and record the attempt. Grants only take effect
+     * once verified; a pending attempt is stored so it can be completed later. Throws
+     * [UnsupportedVerificationMethodException] for methods that aren't verifiable consent.
+     */
+    suspend fun submitConsent(
+        familyId: UUID,
+        parentId: UUID,
+        childId: UUID,
+        permissions: Map<String, Boolean>,
+        verificationMethod: String,
+        verificationData: Map<String, String> = emptyMap(),
+        consentText: String? = null
+    ): ConsentSubmission {
+        require(permissions.isNotEmpty()) { "At least one permission is required" }
+        val verifier = verifiers.forMethod(verificationMethod)
+        val result = verifier.verify(parentId, childId, verificationData)
+        val expiresAt = (clock() + CONSENT_VALIDITY_DAYS.days)
+            .takeIf { result.status == ConsentVerificationStatus.VERIFIED }
+
+        val records = recordConsent(
+            familyId, parentId, childId, permissions, consentText,
+            verificationMethod = verifier.method,
+            verificationStatus = result.status,
+            expiresAt = expiresAt
+        )
+
+        val warnings = mutableListOf<String>()
+        if (verifier.isStub) {
+            warnings.add("${verifier.method} verification is a development stub and does not contact a provider")
+        }
+        when (result.status) {
+            ConsentVerificationStatus.PENDING ->
+                warnings.add("Consent is pending verification; data collection stays disabled until it completes")
+            ConsentVerificationStatus.FAILED ->
+                warnings.add("Verification failed: ${result.message ?: "parent could not be verified"}")
+            ConsentVerificationStatus.VERIFIED -> Unit
+        }
+
+        return ConsentSubmission(records, result.status, expiresAt, warnings)
+    }
+
+    /**
+     * Record the outcome of an out-of-band verification (e.g. the staff call for phone consent)
+     * on every pending decision for the child. Verified decisions take effect from now for
+     * [CONSENT_VALIDITY_DAYS]. Throws [NoSuchElementException] when nothing is pending.
+     */
+    suspend fun completeVerification(childId: UUID, outcome: ConsentVerificationStatus): List<ConsentRecord> {
+        require(outcome != ConsentVerificationStatus.PENDING) { "Outcome must be verified or failed" }
+        val pending = consentRepository.getConsentRecordsByChild(childId)
+            .filter { it.verificationStatus == ConsentVerificationStatus.PENDING }
+        if (pending.isEmpty()) throw NoSuchElementException("No consent is waiting for verification for child $childId")
+
+        val expiresAt = (clock() + CONSENT_VALIDITY_DAYS.days).takeIf { outcome == ConsentVerificationStatus.VERIFIED }
+        pending.forEach { consentRepository.updateVerification(it.id, outcome, expiresAt) }
+        logger.info { "Marked ${pending.size} pending consent decisions ${outcome.name.lowercase()} for child $childId" }
+        return pending.map { it.copy(verificationStatus = outcome, expiresAt = expiresAt) }
+    }
+
+    /**
+     * Consent state of one child, or null when the child doesn't exist
+     */
+    suspend fun getChildStatus(childId: UUID): ChildConsentSummary? {
+        val child = familyRepository.getChildProfile(childId) ?: return null
+        return summarize(child, consentRepository.getConsentRecordsByChild(childId))
+    }
+
+    /**
+     * Consent state of every child in the family
+     */
+    suspend fun getDashboard(familyId: UUID): ConsentDashboard {
+        val children = familyRepository.getChildrenByFamily(familyId)
+        val recordsByChild = consentRepository.getConsentRecordsByFamily(familyId).groupBy { it.childId }
+
+        return ConsentDashboard(
+            familyId = familyId.toString(),
+            children = children.map { summarize(it, recordsByChild[it.id].orEmpty()) },
+            generatedAt = clock().toString()
+        )
+    }
+
+    fun summarize(child: ChildProfile, records: List<ConsentRecord>): ChildConsentSummary {
+        val now = clock()
+        val granted = grantedPermissions(records, now)
+        val dataCollectionAllowed = DATA_COLLECTION in granted
+        val verified = records.filter { it.verificationStatus == ConsentVerificationStatus.VERIFIED }
+        // Waiting on verification unless a later attempt has already been verified
+        val pending = records.filter { it.verificationStatus != ConsentVerificationStatus.FAILED }
+            .maxByOrNull { it.createdAt }?.verificationStatus == ConsentVerificationStatus.PENDING
+
+        val status = when {
+            verified.isEmpty() -> ConsentStatus.NOT_PROVIDED
+            dataCollectionAllowed -> ConsentStatus.GRANTED
+            granted.isNotEmpty() -> ConsentStatus.LIMITED
+            else -> ConsentStatus.REVOKED
+        }
+
+        val warnings = mutableListOf<String>()
+        val nextSteps = mutableListOf<String>()
+        when (status) {
+            ConsentStatus.NOT_PROVIDED -> {
+                warnings.add("No parental consent on file")
+                nextSteps.add("Provide parental consent for ${child.name}")
+            }
+            ConsentStatus.LIMITED -> {
+                warnings.add("Data collection is not allowed, so progress and analytics are not recorded")
+                nextSteps.add("Grant data collection consent to enable progress tracking")
+            }
+            ConsentStatus.REVOKED -> {
+                warnings.add("Consent has been revoked")
+                nextSteps.add("Grant consent again to restore access for ${child.name}")
+            }
+            ConsentStatus.GRANTED -> Unit
+        }
+        if (pending) {
+            warnings.add("A consent submission is waiting for parent verification")
+        }
+        if (child.age >= COPPA_AGE_LIMIT) {
+            warnings.add("${child.name} is $COPPA_AGE_LIMIT or older; additional verification is required")
+        }
+
+        return ChildConsentSummary(
+            childId = child.id.toString(),
+            childName = child.name,
+            consentStatus = status,
+            grantedPermissions = granted,
+            dataCollectionAllowed = dataCollectionAllowed,
+            lastUpdatedAt = records.maxOfOrNull { maxOf(it.createdAt, it.revokedAt ?: it.createdAt) }?.toString(),
+            warnings = warnings,
+            nextSteps = nextSteps,
+            expiresAt = verified.filter { it.granted && it.revokedAt == null }
+                .mapNotNull { it.expiresAt }
+                .filter { it > now }
+                .minOrNull()
+                ?.toString(),
+            pendingVerification = pending
+        )
+    }
+}
@@ -0,0 +1,113 @@
+package com.wondernest.services.coppa
+
+import com.wondernest.domain.model.ConsentVerificationStatus
+import java.util.UUID
+
+data class VerificationResult(
+    val status: ConsentVerificationStatus,
+    val message: String? = null
+)
+
+/**
+ * One FTC-recognised way of verifying that the person giving consent is the child's parent
+ */
+interface ConsentVerifier {
+    val method: String
+
+    /**
+     * True while this verifier doesn't call a real provider; responses then carry a compliance warning
+     */
+    val isStub: Boolean get() = false
+
+    suspend fun verify(parentId: UUID, childId: UUID, verificationData: Map<String, String>): VerificationResult
+}
+
+class UnsupportedVerificationMethodException(method: String, reason: String) :
+    IllegalArgumentException("Verification method '$method' is not accepted: $reason")
+
+/**
+ * A small charge or authorization on the parent's card. The stub only checks that a payment
+ * token was collected; it does not contact a payment provider.
+ */
+class CreditCardConsentVerifier : ConsentVerifier {
+    override val method = "credit_card"
+    override val isStub = true
+
+    override suspend fun verify(parentId: UUID, childId: UUID, verificationData: Map<String, String>): VerificationResult {
+        val token = verificationData["paymentToken"]
+        if (token.isNullOrBlank()) {
+            return VerificationResult(ConsentVerificationStatus.FAILED, "paymentToken is required for credit card verification")
+        }
+        return VerificationResult(ConsentVerificationStatus.VERIFIED)
+    }
+}
+
+/**
+ * A call to the parent by trained staff. Consent stays pending until staff record the outcome
+ * of the call (POST /api/web/v1/admin/coppa/consent/{childId}/verification), so the stub
+ * never verifies on its own.
+ */
+class PhoneConsentVerifier : ConsentVerifier {
+    override val method = "phone"
+    override val isStub = true
+
+    override suspend fun verify(parentId: UUID, childId: UUID, verificationData: Map<String, String>): VerificationResult {
+        val phone = verificationData["phoneNumber"]?.filter { it.isDigit() }
+        if (phone == null || phone.length < 10) {
+            return VerificationResult(ConsentVerificationStatus.FAILED, "A valid phoneNumber is required for phone verification")
+        }
+        return VerificationResult(ConsentVerificationStatus.PENDING, "Verification call will be scheduled")
+    }
+}
+
+/**
+ * Dispatches consent attempts to the verifier for their method. Methods the FTC doesn't accept
+ * as verifiable on their own are rejected by name so the client can explain why. Stub verifiers
+ * are only accepted when [allowStubs] is set, which [fromEnvironment] limits to development.
+ */
+class ConsentVerifiers(
+    verifiers: List<ConsentVerifier> = listOf(CreditCardConsentVerifier(), PhoneConsentVerifier()),
+    private val allowStubs: Boolean = false
+) {
+    private val byMethod = verifiers.associateBy { it.method }
+
+    companion object {
+        private val NOT_VERIFIABLE = mapOf(
+            "email" to "email-only consent is not verifiable parental consent",
+            "email_only" to "email-only consent is not verifiable parental consent",
+            "checkbox" to "a checkbox does not verify that the person consenting is the parent",
+            "checkbox_only" to "a checkbox does not verify that the person consenting is the parent"
+        )
+
+        /**
+         * Reads COPPA_ALLOW_STUB_VERIFIERS. Setting it outside KTOR_ENV=development fails startup,
+         * since stub verification is not verifiable consent.
+         */
+        fun fromEnvironment(env: Map<String, String> = System.getenv()): ConsentVerifiers {
+            val allowStubs = env["COPPA_ALLOW_STUB_VERIFIERS"]?.toBooleanStrictOrNull() ?: false
+            check(!allowStubs || env["KTOR_ENV"] == "development") {
+                "COPPA_ALLOW_STUB_VERIFIERS is only allowed with KTOR_ENV=development"
+            }
+            return ConsentVerifiers(allowStubs = allowStubs)
+        }
+    }
+
+    val supportedMethods: Set<String> get() = byMethod.filterValues { allowStubs || !it.isStub }.keys
+
+    /**
+     * Throws [UnsupportedVerificationMethodException] for rejected, unknown or unavailable methods
+     */
+    fun forMethod(method: String): ConsentVerifier {
+        val normalized = method.trim().lowercase()
+        NOT_VERIFIABLE[normalized]?.let { throw UnsupportedVerificationMethodException(normalized, it) }
+        val verifier = byMethod[normalized]
+            ?: throw UnsupportedVerificationMethodException(
+                normalized,
+                "supported methods are ${supportedMethods.sorted().joinToString().ifEmpty { "none" }}"
+            )
+        if (verifier.isStub && !allowStubs) {
+            throw UnsupportedVerificationMethodException(normalized, "no verification provider is configured for it")
+        }
+        return verifier
+    }
+}
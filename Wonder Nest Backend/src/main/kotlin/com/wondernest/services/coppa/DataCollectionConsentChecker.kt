@@ -0,0 +1,65 @@
+package com.wondernest.services.coppa
+
+import com.wondernest.domain.repository.ConsentRepository
+import com.wondernest.services.resilience.RedisConnections
+import com.wondernest.services.resilience.RedisFeature
+import com.wondernest.services.resilience.RedisGuard
+import io.lettuce.core.SetArgs
+import io.lettuce.core.api.StatefulRedisConnection
+import kotlinx.coroutines.future.await
+import java.util.UUID
+
+interface ConsentCache {
+    suspend fun get(childId: UUID): Boolean?
+    suspend fun put(childId: UUID, allowed: Boolean, ttlSeconds: Long)
+    suspend fun evict(childId: UUID)
+}
+
+class RedisConsentCache(
+    private val connection: () -> StatefulRedisConnection<String, String> = { RedisConnections.shared }
+) : ConsentCache {
+    override suspend fun get(childId: UUID): Boolean? =
+        connection().async().get(key(childId)).await()?.toBooleanStrictOrNull()
+
+    override suspend fun put(childId: UUID, allowed: Boolean, ttlSeconds: Long) {
+        connection().async().set(key(childId), allowed.toString(), SetArgs().ex(ttlSeconds)).await()
+    }
+
+    override suspend fun evict(childId: UUID) {
+        connection().async().del(key(childId)).await()
+    }
+
+    private fun key(childId: UUID) = "coppa:data-collection:$childId"
+}
+
+/**
+ * Answers whether data may be collected for a child, for every route that writes child data.
+ * Answers are cached briefly; a Redis outage falls back to reading consent from the database,
+ * never to allowing the write.
+ */
+class DataCollectionConsentChecker(
+    private val consentRepository: ConsentRepository,
+    private val cache: ConsentCache = RedisConsentCache(),
+    private val redisGuard: RedisGuard = RedisGuard(),
+    private val ttlSeconds: Long = DEFAULT_TTL_SECONDS
+) {
+    companion object {
+        const val DEFAULT_TTL_SECONDS = 60L
+    }
+
+    suspend fun isDataCollectionAllowed(childId: UUID): Boolean {
+        redisGuard.execute(RedisFeature.CACHE, fallback = { null }) { cache.get(childId) }?.let { return it }
+
+        val allowed = ConsentService.DATA_COLLECTION in
+            ConsentService.grantedPermissions(consentRepository.getConsentRecordsByChild(childId))
+        redisGuard.execute(RedisFeature.CACHE, fallback = { }) { cache.put(childId, allowed, ttlSeconds) }
+        return allowed
+    }
+
+    /**
+     * Call after recording consent so a new grant or revocation applies immediately
+     */
+    suspend fun invalidate(childId: UUID) {
+        redisGuard.execute(RedisFeature.CACHE, fallback = { }) { cache.evict(childId) }
+    }
+}
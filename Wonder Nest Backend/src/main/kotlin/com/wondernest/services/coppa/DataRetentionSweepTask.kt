@@ -0,0 +1,156 @@
+package com.wondernest.services.coppa
+
+import com.wondernest.services.analytics.AnalyticsEventService
+import com.wondernest.services.audio.AudioMetricsService
+import kotlinx.coroutines.CancellationException
+import kotlinx.coroutines.CoroutineScope
+import kotlinx.coroutines.Job
+import kotlinx.coroutines.delay
+import kotlinx.coroutines.isActive
+import kotlinx.coroutines.launch
+import kotlinx.datetime.Clock
+import kotlinx.datetime.Instant
+import kotlinx.datetime.toJavaInstant
+import mu.KotlinLogging
+import org.jetbrains.exposed.sql.transactions.transaction
+import kotlin.time.Duration.Companion.days
+
+private val logger = KotlinLogging.logger {}
+
+/**
+ * Child data that is only kept for a limited time, with the table and timestamp column that
+ * decide its age
+ */
+enum class RetentionDataType(
+    val table: String,
+    val timestampColumn: String,
+    val defaultDays: Int
+) {
+    SESSION_LOGS("games.game_sessions", "started_at", 7),
+    LEARNING_ANALYTICS("analytics.analytics_events", "created_at", 30),
+    // Per-session speech metrics, which are also the only record of an audio session
+    SPEECH_METRICS("analytics.speech_session_metrics", "recorded_at", 30),
+    DAILY_METRICS("analytics.daily_child_metrics", "created_at", 365)
+}
+
+data class DataRetentionConfig(
+    val enabled: Boolean = true,
+    val retentionDays: Map<RetentionDataType, Int> = RetentionDataType.entries.associateWith { it.defaultDays },
+    val batchSize: Int = DEFAULT_BATCH_SIZE,
+    val intervalMillis: Long = DEFAULT_INTERVAL_MILLIS
+) {
+    fun daysFor(type: RetentionDataType): Int = retentionDays[type] ?: type.defaultDays
+
+    fun cutoff(type: RetentionDataType, now: Instant): Instant = now - daysFor(type).days
+
+    /**
+     * True when a row of [type] recorded at [recordedAt] is past its retention period
+     */
+    fun isExpired(type: RetentionDataType, recordedAt: Instant, now: Instant): Boolean =
+        recordedAt < cutoff(type, now)
+
+    companion object {
+        const val DEFAULT_BATCH_SIZE = 1_000
+        const val DEFAULT_INTERVAL_MILLIS = 60 * 60 * 1000L
+
+        /**
+         * Reads DATA_RETENTION_SWEEP_ENABLED, DATA_RETENTION_<TYPE>_DAYS (e.g. DATA_RETENTION_SESSION_LOGS_DAYS),
+         * DATA_RETENTION_BATCH_SIZE and DATA_RETENTION_INTERVAL_MINUTES
+         */
+        fun fromEnvironment(env: Map<String, String> = System.getenv()) = DataRetentionConfig(
+            enabled = env["DATA_RETENTION_SWEEP_ENABLED"]?.toBooleanStrictOrNull() ?: true,
+            retentionDays = RetentionDataType.entries.associateWith { type ->
+                env["DATA_RETENTION_${type.name}_DAYS"]?.toIntOrNull()?.takeIf { it > 0 } ?: type.defaultDays
+            },
+            batchSize = env["DATA_RETENTION_BATCH_SIZE"]?.toIntOrNull()?.takeIf { it > 0 } ?: DEFAULT_BATCH_SIZE,
+            intervalMillis = env["DATA_RETENTION_INTERVAL_MINUTES"]?.toLongOrNull()?.takeIf { it > 0 }
+                ?.let { it * 60 * 1000 } ?: DEFAULT_INTERVAL_MILLIS
+        )
+    }
+}
+
+interface DataRetentionStore {
+    /** Delete at most [limit] rows of [type] older than [cutoff]; returns how many were deleted */
+    fun deleteExpiredBatch(type: RetentionDataType, cutoff: Instant, limit: Int): Int
+}
+
+object DatabaseDataRetentionStore : DataRetentionStore {
+    override fun deleteExpiredBatch(type: RetentionDataType, cutoff: Instant, limit: Int): Int = transaction {
+        // Postgres has no DELETE ... LIMIT, so the batch is chosen by ctid. Everything inlined comes
+        // from the enum, an Instant or an Int, never from a request.
+        connection.prepareStatement(
+            """
+            DELETE FROM ${type.table}
+            WHERE ctid IN (SELECT ctid FROM ${type.table} WHERE ${type.timestampColumn} < '$cutoff'::timestamptz LIMIT $limit)
+            """.trimIndent(),
+            false
+        ).executeUpdate()
+    }
+}
+
+/**
+ * Periodically deletes child data past its retention period, from the tables listed on each
+ * [RetentionDataType] and through the analytics and audio services for the data they manage
+ * themselves. Each batch is its own short transaction so a large backlog never holds locks for long.
+ */
+class DataRetentionSweepTask(
+    private val config: DataRetentionConfig = DataRetentionConfig.fromEnvironment(),
+    private val store: DataRetentionStore = DatabaseDataRetentionStore,
+    private val clock: () -> Instant = { Clock.System.now() },
+    private val analyticsEventService: AnalyticsEventService? = null,
+    private val audioMetricsService: AudioMetricsService? = null
+) {
+
+    /**
+     * One sweep over every data type; returns the rows deleted per type. A type that fails is
+     * logged and left out of the result, and the remaining types are still swept.
+     */
+    fun runOnce(): Map<RetentionDataType, Int> {
+        val now = clock()
+        return RetentionDataType.entries.mapNotNull { type ->
+            val cutoff = config.cutoff(type, now)
+            try {
+                var total = 0
+                do {
+                    val deleted = store.deleteExpiredBatch(type, cutoff, config.batchSize)
+                    total += deleted
+                } while (deleted == config.batchSize)
+                total += pruneThroughServices(type, cutoff)
+                logger.info { "Retention sweep removed $total ${type.name.lowercase()} rows older than $cutoff" }
+                type to total
+            } catch (e: Exception) {
+                logger.error(e) { "Retention sweep failed for ${type.name.lowercase()}" }
+                null
+            }
+        }.toMap()
+    }
+
+    private fun pruneThroughServices(type: RetentionDataType, cutoff: Instant): Int = when (type) {
+        RetentionDataType.SESSION_LOGS -> 0
+        RetentionDataType.LEARNING_ANALYTICS -> analyticsEventService?.pruneEventsBefore(cutoff.toJavaInstant()) ?: 0
+        RetentionDataType.SPEECH_METRICS -> audioMetricsService?.pruneBefore(cutoff.toJavaInstant()) ?: 0
+        RetentionDataType.DAILY_METRICS -> analyticsEventService?.pruneAggregatesBefore(cutoff.toJavaInstant()) ?: 0
+    }
+
+    /**
+     * Null when the sweeper is disabled (DATA_RETENTION_SWEEP_ENABLED=false, e.g. in tests)
+     */
+    fun start(scope: CoroutineScope): Job? {
+        if (!config.enabled) {
+            logger.info { "Data retention sweep disabled" }
+            return null
+        }
+        return scope.launch {
+            while (isActive) {
+                try {
+                    runOnce()
+                } catch (e: CancellationException) {
+                    throw e
+                } catch (e: Exception) {
+                    logger.warn(e) { "Data retention sweep failed, retrying next interval" }
+                }
+                delay(config.intervalMillis)
+            }
+        }
+    }
+}
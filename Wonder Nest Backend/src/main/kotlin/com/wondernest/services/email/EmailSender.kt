@@ -0,0 +1,100 @@
+package com.wondernest.services.email
+
+import jakarta.mail.Authenticator
+import jakarta.mail.Message
+import jakarta.mail.PasswordAuthentication
+import jakarta.mail.Session
+import jakarta.mail.Transport
+import jakarta.mail.internet.InternetAddress
+import jakarta.mail.internet.MimeMessage
+import kotlinx.coroutines.Dispatchers
+import kotlinx.coroutines.withContext
+import mu.KotlinLogging
+import java.util.Properties
+
+private val logger = KotlinLogging.logger {}
+
+data class EmailMessage(
+    val to: String,
+    val subject: String,
+    val body: String
+)
+
+/**
+ * Delivers a single plain-text email. Implementations throw when delivery fails.
+ */
+interface EmailSender {
+    suspend fun send(message: EmailMessage)
+
+    companion object {
+        /**
+         * SMTP when SMTP_HOST is set, otherwise emails are only logged
+         */
+        fun fromEnvironment(env: Map<String, String> = System.getenv()): EmailSender =
+            SmtpConfig.fromEnvironment(env)?.let { SmtpEmailSender(it) } ?: LoggingEmailSender()
+    }
+}
+
+data class SmtpConfig(
+    val host: String,
+    val port: Int = 587,
+    val username: String? = null,
+    val password: String? = null,
+    val from: String = "WonderNest <no-reply@wondernest.app>",
+    val startTls: Boolean = true
+) {
+    companion object {
+        fun fromEnvironment(env: Map<String, String> = System.getenv()): SmtpConfig? {
+            val host = env["SMTP_HOST"]?.takeIf { it.isNotBlank() } ?: return null
+            val defaults = SmtpConfig(host)
+            return SmtpConfig(
+                host = host,
+                port = env["SMTP_PORT"]?.toIntOrNull()?.takeIf { it > 0 } ?: defaults.port,
+                username = env["SMTP_USERNAME"]?.takeIf { it.isNotBlank() },
+                password = env["SMTP_PASSWORD"]?.takeIf { it.isNotBlank() },
+                from = env["SMTP_FROM"]?.takeIf { it.isNotBlank() } ?: defaults.from,
+                startTls = env["SMTP_STARTTLS"]?.toBooleanStrictOrNull() ?: defaults.startTls
+            )
+        }
+    }
+}
+
+class SmtpEmailSender(private val config: SmtpConfig) : EmailSender {
+    private val session: Session by lazy {
+        val properties = Properties().apply {
+            put("mail.smtp.host", config.host)
+            put("mail.smtp.port", config.port.toString())
+            put("mail.smtp.auth", (config.username != null).toString())
+            put("mail.smtp.starttls.enable", config.startTls.toString())
+            put("mail.smtp.connectiontimeout", "10000")
+            put("mail.smtp.timeout", "10000")
+        }
+        val authenticator = config.username?.let { username ->
+            object : Authenticator() {
+                override fun getPasswordAuthentication() = PasswordAuthentication(username, config.password.orEmpty())
+            }
+        }
+        Session.getInstance(properties, authenticator)
+    }
+
+    override suspend fun send(message: EmailMessage) {
+        withContext(Dispatchers.IO) {
+            val mime = MimeMessage(session).apply {
+                setFrom(InternetAddress(config.from))
+                setRecipients(Message.RecipientType.TO, InternetAddress.parse(message.to))
+                subject = message.subject
+                setText(message.body, "UTF-8")
+            }
+            Transport.send(mime)
+        }
+    }
+}
+
+/**
+ * Development fallback: nothing is delivered
+ */
+class LoggingEmailSender : EmailSender {
+    override suspend fun send(message: EmailMessage) {
+        logger.info { "Email not sent (no SMTP configured): '${message.subject}' to ${message.to}" }
+    }
+}
@@ -1,42 +1,107 @@
 package com.wondernest.services.email
 
 import com.wondernest.domain.model.User
+import com.wondernest.domain.web.AdminInvitation
 import mu.KotlinLogging
+import java.time.ZoneOffset
+import java.time.format.DateTimeFormatter
 
 private val logger = KotlinLogging.logger {}
 
-class EmailService {
-    
-    suspend fun sendVerificationEmail(user: User): Boolean {
-        try {
-            // TODO: Implement with SendGrid or AWS SES
-            logger.info { "Would send verification email to ${user.email}" }
-            return true
-        } catch (e: Exception) {
-            logger.error(e) { "Failed to send verification email to ${user.email}" }
-            return false
-        }
-    }
-    
-    suspend fun sendPasswordResetEmail(user: User, token: String): Boolean {
-        try {
-            // TODO: Implement with SendGrid or AWS SES
-            logger.info { "Would send password reset email to ${user.email} with token: $token" }
-            return true
+/**
+ * Transactional emails. Every send returns false instead of throwing when delivery
+ * fails, so a mail outage never fails the request that triggered it.
+ *
+ * @param baseUrl public URL links in emails point at (APP_BASE_URL)
+ */
+class EmailService(
+    private val sender: EmailSender = EmailSender.fromEnvironment(),
+    private val baseUrl: String = System.getenv("APP_BASE_URL")?.takeIf { it.isNotBlank() } ?: "http://localhost:8080"
+) {
+
+    /**
+     * [token] goes in the link to GET /api/v1/auth/verify-email?token=...
+     */
+    suspend fun sendVerificationEmail(user: User, token: String): Boolean = deliver(
+        "verification",
+        EmailMessage(
+            to = user.email,
+            subject = "Verify your WonderNest email",
+            body = """
+                Hi${user.firstName?.let { " $it" }.orEmpty()},
+
+                Please confirm your email address by opening this link:
+
+                ${link("/api/v1/auth/verify-email", token)}
+
+                If you didn't create a WonderNest account you can ignore this email.
+            """.trimIndent()
+        )
+    )
+
+    suspend fun sendPasswordResetEmail(user: User, token: String): Boolean = deliver(
+        "password reset",
+        EmailMessage(
+            to = user.email,
+            subject = "Reset your WonderNest password",
+            body = """
+                We received a request to reset your WonderNest password. Open this link to choose a new one:
+
+                ${link("/reset-password", token)}
+
+                If you didn't ask for this, you can ignore this email and your password won't change.
+            """.trimIndent()
+        )
+    )
+
+    suspend fun sendWelcomeEmail(user: User): Boolean = deliver(
+        "welcome",
+        EmailMessage(
+            to = user.email,
+            subject = "Welcome to WonderNest",
+            body = """
+                Hi${user.firstName?.let { " $it" }.orEmpty()},
+
+                Welcome to WonderNest! Your account is ready.
+            """.trimIndent()
+        )
+    )
+
+    /**
+     * [token] is the raw invitation token; only its hash is stored
+     */
+    suspend fun sendAdminInvitationEmail(invitation: AdminInvitation, inviterName: String, token: String): Boolean = deliver(
+        "admin invitation",
+        EmailMessage(
+            to = invitation.email,
+            subject = "You've been invited to the WonderNest admin console",
+            body = """
+                $inviterName has invited you to join the WonderNest admin console as ${invitation.role.displayName}.
+
+                Accept the invitation and set up your account here:
+
+                ${link("/admin/invitations/accept", token)}
+
+                This invitation expires on ${EXPIRY_FORMAT.format(invitation.expiresAt)}.
+            """.trimIndent()
+        )
+    )
+
+    private fun link(path: String, token: String) = "${baseUrl.trimEnd('/')}$path?token=$token"
+
+    private suspend fun deliver(kind: String, message: EmailMessage): Boolean {
+        return try {
+            sender.send(message)
+            logger.info { "Sent $kind email to ${message.to}" }
+            true
         } catch (e: Exception) {
-            logger.error(e) { "Failed to send password reset email to ${user.email}" }
-            return false
+            logger.error(e) { "Failed to send $kind email to ${message.to}" }
+            false
         }
     }
-    
-    suspend fun sendWelcomeEmail(user: User): Boolean {
-        try {
-            // TODO: Implement with SendGrid or AWS SES
-            logger.info { "Would send welcome email to ${user.email}" }
-            return true
-        } catch (e: Exception) {
-            logger.error(e) { "Failed to send welcome email to ${user.email}" }
-            return false
-        }
+
+    companion object {
+        private val EXPIRY_FORMAT: DateTimeFormatter =
+            DateTimeFormatter.ofPattern("d MMMM yyyy, HH:mm 'UTC'").withZone(ZoneOffset.UTC)
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,60 @@
+package com.wondernest.services.family
+
+import kotlinx.serialization.Serializable
+import mu.KotlinLogging
+import java.security.SecureRandom
+import java.time.Instant
+import java.util.UUID
+
+private val logger = KotlinLogging.logger {}
+
+@Serializable
+data class ChildPseudonymResponse(
+    val externalId: String,
+    val previousExternalId: String? = null,
+    val rotatedAt: String? = null
+)
+
+/**
+ * Maps a child's internal UUID to the pseudonymous identifier exposed to clients and
+ * third parties, stored in family.child_pseudonyms. Parents can rotate the external ID; the
+ * internal UUID, and therefore all game data and analytics keyed by it, stays the same.
+ */
+class ChildPseudonymService(
+    private val store: ChildPseudonymStore = DatabaseChildPseudonymStore,
+    private val random: SecureRandom = SecureRandom(),
+    private val clock: () -> Instant = Instant::now
+) {
+
+    fun externalIdFor(childId: UUID): String =
+        store.externalIdFor(childId) ?: store.insertIfAbsent(childId, newExternalId(), clock())
+
+    /**
+     * Internal child ID for an external ID; null for anything else, including raw child UUIDs
+     * and IDs that have been rotated away
+     */
+    fun resolve(externalId: String): UUID? =
+        if (externalId.startsWith(PREFIX)) store.childFor(externalId) else null
+
+    /**
+     * Issue a new external ID for the child; the previous one stops resolving immediately
+     */
+    fun rotate(childId: UUID): ChildPseudonymResponse {
+        val now = clock()
+        val next = newExternalId()
+        val previous = store.replace(childId, next, now)
+
+        logger.info { "Rotated external ID for child $childId" }
+        return ChildPseudonymResponse(externalId = next, previousExternalId = previous, rotatedAt = now.toString())
+    }
+
+    private fun newExternalId(): String {
+        val bytes = ByteArray(ID_BYTES).also { random.nextBytes(it) }
+        return PREFIX + bytes.joinToString("") { "%02x".format(it) }
+    }
+
+    companion object {
+        const val PREFIX = "cx_"
+        private const val ID_BYTES = 12
+    }
+}
@@ -0,0 +1,102 @@
+package com.wondernest.services.family
+
+import com.wondernest.data.database.table.ChildPseudonyms
+import kotlinx.datetime.toKotlinInstant
+import org.jetbrains.exposed.sql.*
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.eq
+import org.jetbrains.exposed.sql.transactions.transaction
+import java.time.Instant
+import java.util.UUID
+import java.util.concurrent.ConcurrentHashMap
+
+interface ChildPseudonymStore {
+    fun externalIdFor(childId: UUID): String?
+
+    fun childFor(externalId: String): UUID?
+
+    /**
+     * Store [externalId] for the child unless it already has one; returns whichever ID the child
+     * ends up with, so concurrent first requests all see the same ID
+     */
+    fun insertIfAbsent(childId: UUID, externalId: String, now: Instant): String
+
+    /**
+     * Replace the child's external ID with [externalId] and return the one it replaced, if any
+     */
+    fun replace(childId: UUID, externalId: String, now: Instant): String?
+}
+
+object DatabaseChildPseudonymStore : ChildPseudonymStore {
+    override fun externalIdFor(childId: UUID): String? = transaction {
+        ChildPseudonyms.slice(ChildPseudonyms.externalId)
+            .select { ChildPseudonyms.childId eq childId }
+            .singleOrNull()
+            ?.get(ChildPseudonyms.externalId)
+    }
+
+    override fun childFor(externalId: String): UUID? = transaction {
+        ChildPseudonyms.slice(ChildPseudonyms.childId)
+            .select { ChildPseudonyms.externalId eq externalId }
+            .singleOrNull()
+            ?.get(ChildPseudonyms.childId)
+    }
+
+    override fun insertIfAbsent(childId: UUID, externalId: String, now: Instant): String = transaction {
+        // The child_id primary key turns a losing concurrent insert into a no-op
+        ChildPseudonyms.insertIgnore {
+            it[ChildPseudonyms.childId] = childId
+            it[ChildPseudonyms.externalId] = externalId
+            it[createdAt] = now.toKotlinInstant()
+        }
+        ChildPseudonyms.slice(ChildPseudonyms.externalId)
+            .select { ChildPseudonyms.childId eq childId }
+            .single()[ChildPseudonyms.externalId]
+    }
+
+    override fun replace(childId: UUID, externalId: String, now: Instant): String? = transaction {
+        // Locking the row keeps concurrent rotations from both reporting the same previous ID
+        val previous = ChildPseudonyms.slice(ChildPseudonyms.externalId)
+            .select { ChildPseudonyms.childId eq childId }
+            .forUpdate()
+            .singleOrNull()
+            ?.get(ChildPseudonyms.externalId)
+
+        if (previous == null) {
+            ChildPseudonyms.insert {
+                it[ChildPseudonyms.childId] = childId
+                it[ChildPseudonyms.externalId] = externalId
+                it[createdAt] = now.toKotlinInstant()
+                it[rotatedAt] = now.toKotlinInstant()
+            }
+        } else {
+            ChildPseudonyms.update({ ChildPseudonyms.childId eq childId }) {
+                it[ChildPseudonyms.externalId] = externalId
+                it[rotatedAt] = now.toKotlinInstant()
+            }
+        }
+        previous
+    }
+}
+
+/**
+ * For tests and local runs without a database
+ */
+class InMemoryChildPseudonymStore : ChildPseudonymStore {
+    private val externalByChild = ConcurrentHashMap<UUID, String>()
+    private val childByExternal = ConcurrentHashMap<String, UUID>()
+
+    override fun externalIdFor(childId: UUID): String? = externalByChild[childId]
+
+    override fun childFor(externalId: String): UUID? = childByExternal[externalId]
+
+    override fun insertIfAbsent(childId: UUID, externalId: String, now: Instant): String = synchronized(this) {
+        externalByChild.getOrPut(childId) { externalId.also { childByExternal[it] = childId } }
+    }
+
+    override fun replace(childId: UUID, externalId: String, now: Instant): String? = synchronized(this) {
+        val previous = externalByChild.put(childId, externalId)
+        childByExternal[externalId] = childId
+        previous?.let { childByExternal.remove(it) }
+        previous
+    }
+}
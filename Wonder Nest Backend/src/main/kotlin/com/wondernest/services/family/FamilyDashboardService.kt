@@ -0,0 +1,61 @@
+package com.wondernest.services.family
+
+import com.wondernest.api.analytics.DailyAnalytics
+import com.wondernest.api.content.ContentItem
+import com.wondernest.api.content.ContentRecommendationService
+import com.wondernest.domain.repository.AnalyticsRepository
+import com.wondernest.domain.repository.FamilyRepository
+import kotlinx.datetime.Clock
+import kotlinx.datetime.Instant
+import kotlinx.serialization.Serializable
+import java.util.UUID
+
+@Serializable
+data class ChildDashboardSummary(
+    val childId: String,
+    val name: String,
+    val age: Int,
+    val avatarUrl: String? = null,
+    val latestAnalytics: DailyAnalytics? = null,
+    val recommendations: List<ContentItem> = emptyList()
+)
+
+@Serializable
+data class FamilyDashboardResponse(
+    val familyId: String,
+    val children: List<ChildDashboardSummary>,
+    val generatedAt: String
+)
+
+/**
+ * Everything the family home screen needs in one response. Children and their latest
+ * analytics are two queries however many children there are; recommendations come from the
+ * in-memory catalog.
+ */
+class FamilyDashboardService(
+    private val familyRepository: FamilyRepository,
+    private val analyticsRepository: AnalyticsRepository,
+    private val recommendationService: ContentRecommendationService,
+    private val clock: () -> Instant = { Clock.System.now() }
+) {
+
+    suspend fun dashboardFor(familyId: UUID): FamilyDashboardResponse {
+        val children = familyRepository.getChildrenByFamily(familyId)
+        val latestAnalytics = analyticsRepository.getLatestDailyAnalyticsByFamily(familyId)
+
+        return FamilyDashboardResponse(
+            familyId = familyId.toString(),
+            children = children.map { child ->
+                ChildDashboardSummary(
+                    childId = child.id.toString(),
+                    name = child.name,
+                    age = child.age,
+                    avatarUrl = child.avatarUrl,
+                    latestAnalytics = latestAnalytics[child.id],
+                    recommendations = recommendationService.recommendationsForAge(child.age)
+                )
+            },
+            generatedAt = clock().toString()
+        )
+    }
+}
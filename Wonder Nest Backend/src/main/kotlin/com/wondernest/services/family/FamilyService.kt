@@ -54,8 +54,8 @@ class FamilyService(
         )
     }
 
-    suspend fun getChildren(familyId: UUID): List<ChildProfile> {
-        val children = familyRepository.getChildrenByFamily(familyId)
+    suspend fun getChildren(familyId: UUID, includeArchived: Boolean = false): List<ChildProfile> {
+        val children = familyRepository.getChildrenByFamily(familyId, includeArchived)
         logger.info { "Retrieved ${children.size} children for family: $familyId" }
         return children
     }
@@ -156,6 +156,28 @@ class FamilyService(
         return archived
     }
 
+    /**
+     * Hides [childId] from [familyId]'s default child listings. Only the profile is flagged;
+     * the child's analytics and game data are kept for a later restore. Null when the child
+     * isn't an active child of this family.
+     */
+    suspend fun archiveChild(familyId: UUID, childId: UUID): ChildProfile? {
+        val child = familyRepository.getChildrenByFamily(familyId).find { it.id == childId } ?: return null
+        if (!familyRepository.archiveChildProfile(childId)) return null
+        return child.copy(archivedAt = Clock.System.now())
+    }
+
+    /**
+     * Brings an archived child of [familyId] back; null when there's no such archived child
+     */
+    suspend fun restoreChild(familyId: UUID, childId: UUID): ChildProfile? {
+        val child = familyRepository.getChildrenByFamily(familyId, includeArchived = true)
+            .find { it.id == childId && it.archivedAt != null } ?: return null
+        if (!familyRepository.restoreChildProfile(childId)) return null
+        logger.info { "Restored child profile: $childId" }
+        return child.copy(archivedAt = null)
+    }
+
     suspend fun getChildProfile(childId: UUID): ChildProfile? {
         return familyRepository.getChildProfile(childId)
     }
@@ -35,6 +35,7 @@ class ChildGameInstanceService {
                 settings = Json.parseToJsonElement(Json.encodeToString(existingInstance[ChildGameInstances.settings])),
                 preferences = Json.parseToJsonElement(Json.encodeToString(existingInstance[ChildGameInstances.preferences])),
                 isUnlocked = existingInstance[ChildGameInstances.isUnlocked],
+                isEnabled = existingInstance[ChildGameInstances.isEnabled],
                 totalPlayTimeMinutes = existingInstance[ChildGameInstances.totalPlayTimeMinutes],
                 sessionCount = existingInstance[ChildGameInstances.sessionCount],
                 lastPlayedAt = existingInstance[ChildGameInstances.lastPlayedAt]?.toString(),
@@ -50,6 +51,7 @@ class ChildGameInstanceService {
                 it[ChildGameInstances.settings] = mapOf<String, String>()
                 it[ChildGameInstances.preferences] = mapOf<String, String>()
                 it[ChildGameInstances.isUnlocked] = true
+                it[ChildGameInstances.isEnabled] = true
                 it[ChildGameInstances.totalPlayTimeMinutes] = 0
                 it[ChildGameInstances.sessionCount] = 0
                 it[ChildGameInstances.lastPlayedAt] = null
@@ -64,6 +66,7 @@ class ChildGameInstanceService {
                 settings = Json.parseToJsonElement("{}"),
                 preferences = Json.parseToJsonElement("{}"),
                 isUnlocked = true,
+                isEnabled = true,
                 totalPlayTimeMinutes = 0,
                 sessionCount = 0,
                 lastPlayedAt = null,
@@ -86,6 +89,7 @@ class ChildGameInstanceService {
                     settings = Json.parseToJsonElement(Json.encodeToString(row[ChildGameInstances.settings])),
                     preferences = Json.parseToJsonElement(Json.encodeToString(row[ChildGameInstances.preferences])),
                     isUnlocked = row[ChildGameInstances.isUnlocked],
+                    isEnabled = row[ChildGameInstances.isEnabled],
                     totalPlayTimeMinutes = row[ChildGameInstances.totalPlayTimeMinutes],
                     sessionCount = row[ChildGameInstances.sessionCount],
                     lastPlayedAt = row[ChildGameInstances.lastPlayedAt]?.toString(),
@@ -109,6 +113,7 @@ class ChildGameInstanceService {
                     settings = Json.parseToJsonElement(Json.encodeToString(row[ChildGameInstances.settings])),
                     preferences = Json.parseToJsonElement(Json.encodeToString(row[ChildGameInstances.preferences])),
                     isUnlocked = row[ChildGameInstances.isUnlocked],
+                    isEnabled = row[ChildGameInstances.isEnabled],
                     totalPlayTimeMinutes = row[ChildGameInstances.totalPlayTimeMinutes],
                     sessionCount = row[ChildGameInstances.sessionCount],
                     lastPlayedAt = row[ChildGameInstances.lastPlayedAt]?.toString(),
@@ -178,6 +183,28 @@ class ChildGameInstanceService {
         }.count() > 0
     }
     
+    /**
+     * Enable or disable a game for a child (parental control)
+     */
+    fun setInstanceEnabled(instanceId: UUID, enabled: Boolean): Boolean = transaction {
+        val updateCount = ChildGameInstances.update({ ChildGameInstances.id eq instanceId }) {
+            it[ChildGameInstances.isEnabled] = enabled
+            it[ChildGameInstances.updatedAt] = Clock.System.now()
+        }
+        updateCount > 0
+    }
+    
+    /**
+     * Check whether a game is enabled for a child. Games without an instance yet are enabled.
+     */
+    fun isGameEnabled(childId: UUID, gameKey: String): Boolean = transaction {
+        ChildGameInstances.join(GameRegistry, JoinType.INNER) {
+            ChildGameInstances.gameId eq GameRegistry.id
+        }.slice(ChildGameInstances.isEnabled).select {
+            (ChildGameInstances.childId eq childId) and (GameRegistry.gameKey eq gameKey)
+        }.singleOrNull()?.get(ChildGameInstances.isEnabled) ?: true
+    }
+    
     /**
      * Get instance by child ID and game key (convenience method)
      */
@@ -194,6 +221,7 @@ class ChildGameInstanceService {
                 settings = Json.parseToJsonElement(Json.encodeToString(row[ChildGameInstances.settings])),
                 preferences = Json.parseToJsonElement(Json.encodeToString(row[ChildGameInstances.preferences])),
                 isUnlocked = row[ChildGameInstances.isUnlocked],
+                isEnabled = row[ChildGameInstances.isEnabled],
                 totalPlayTimeMinutes = row[ChildGameInstances.totalPlayTimeMinutes],
                 sessionCount = row[ChildGameInstances.sessionCount],
                 lastPlayedAt = row[ChildGameInstances.lastPlayedAt]?.toString(),
@@ -213,6 +241,7 @@ data class ChildGameInstanceInfo(
     val settings: JsonElement,
     val preferences: JsonElement,
     val isUnlocked: Boolean,
+    val isEnabled: Boolean = true,
     val totalPlayTimeMinutes: Int,
     val sessionCount: Int,
     val lastPlayedAt: String?,
@@ -231,6 +260,11 @@ data class UpdateInstancePreferencesRequest(
     val preferences: Map<String, JsonElement>
 )
 
+@Serializable
+data class UpdateInstanceEnabledRequest(
+    val isEnabled: Boolean
+)
+
 @Serializable
 data class UpdatePlayTimeRequest(
     val additionalMinutes: Int
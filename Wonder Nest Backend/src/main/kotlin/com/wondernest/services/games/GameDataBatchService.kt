@@ -0,0 +1,161 @@
+package com.wondernest.services.games
+
+import com.wondernest.data.database.table.SimpleGameData
+import com.wondernest.services.moderation.ChildDataPiiGuard
+import com.wondernest.services.moderation.HeldDataTarget
+import com.wondernest.services.moderation.PiiScreening
+import kotlinx.datetime.Clock
+import kotlinx.datetime.Instant
+import kotlinx.serialization.json.JsonElement
+import kotlinx.serialization.json.JsonObject
+import org.jetbrains.exposed.sql.*
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.eq
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.inList
+import org.jetbrains.exposed.sql.transactions.transaction
+import java.util.UUID
+
+data class GameDataBatchItem(
+    val gameType: String,
+    val dataKey: String,
+    val dataValue: Map<String, JsonElement>
+)
+
+data class SimpleGameDataRecord(
+    val id: UUID,
+    val childId: UUID,
+    val gameType: String,
+    val dataKey: String,
+    val dataValue: Map<String, JsonElement>,
+    val createdAt: Instant,
+    val updatedAt: Instant
+)
+
+/**
+ * Outcome of a batch save. On failure nothing was written and [failedKey] names the
+ * first item that was rejected, when the failure can be pinned on one item. [heldKeys] were
+ * held for PII review rather than written.
+ */
+data class GameDataBatchResult(
+    val success: Boolean,
+    val savedCount: Int,
+    val failedKey: String? = null,
+    val error: String? = null,
+    val heldKeys: List<String> = emptyList()
+)
+
+interface GameDataBatchStore {
+    /** Upsert every item in one transaction */
+    fun saveAll(childId: UUID, gameType: String, items: List<GameDataBatchItem>, now: Instant)
+
+    fun load(childId: UUID, gameType: String, dataKeys: List<String>): List<SimpleGameDataRecord>
+}
+
+object DatabaseGameDataBatchStore : GameDataBatchStore {
+    override fun saveAll(childId: UUID, gameType: String, items: List<GameDataBatchItem>, now: Instant) {
+        transaction {
+            SimpleGameData.batchUpsert(
+                items,
+                SimpleGameData.childId, SimpleGameData.gameType, SimpleGameData.dataKey
+            ) { item ->
+                this[SimpleGameData.childId] = childId
+                this[SimpleGameData.gameType] = gameType
+                this[SimpleGameData.dataKey] = item.dataKey
+                this[SimpleGameData.dataValue] = item.dataValue
+                this[SimpleGameData.updatedAt] = now
+                this[SimpleGameData.createdAt] = now // Will be ignored for updates due to ON CONFLICT
+            }
+        }
+    }
+
+    override fun load(childId: UUID, gameType: String, dataKeys: List<String>): List<SimpleGameDataRecord> = transaction {
+        SimpleGameData.select {
+            (SimpleGameData.childId eq childId) and
+            (SimpleGameData.gameType eq gameType) and
+            (SimpleGameData.dataKey inList dataKeys)
+        }.map { row ->
+            SimpleGameDataRecord(
+                id = row[SimpleGameData.id].value,
+                childId = row[SimpleGameData.childId],
+                gameType = row[SimpleGameData.gameType],
+                dataKey = row[SimpleGameData.dataKey],
+                dataValue = row[SimpleGameData.dataValue],
+                createdAt = row[SimpleGameData.createdAt],
+                updatedAt = row[SimpleGameData.updatedAt]
+            )
+        }
+    }
+}
+
+/**
+ * Batch save/load for SimpleGameData so games like the sticker book can flush many
+ * small keys in one request. Every item is validated before anything is written, and
+ * the writes share a transaction, so a batch is applied completely or not at all. An item that
+ * looks like it contains personal information fails the whole batch when the PII action is
+ * REJECT; with QUEUE every flagged item is held for review and the rest are written.
+ */
+class GameDataBatchService(
+    private val store: GameDataBatchStore = DatabaseGameDataBatchStore,
+    private val dataValidator: GameDataValidator = GameDataValidator(),
+    private val piiGuard: ChildDataPiiGuard = ChildDataPiiGuard(),
+    private val clock: () -> Instant = { Clock.System.now() }
+) {
+    companion object {
+        const val MAX_BATCH_ITEMS = 100
+        const val MAX_DATA_KEY_LENGTH = 200 // Matches simple_game_data.data_key
+    }
+
+    fun saveBatch(childId: UUID, gameType: String, items: List<GameDataBatchItem>): GameDataBatchResult {
+        if (items.isEmpty()) {
+            return GameDataBatchResult(false, 0, error = "Batch must contain at least one item")
+        }
+        if (items.size > MAX_BATCH_ITEMS) {
+            return GameDataBatchResult(false, 0, error = "Batch exceeds $MAX_BATCH_ITEMS items")
+        }
+
+        val seenKeys = mutableSetOf<String>()
+        for (item in items) {
+            val error = validate(item, gameType)
+                ?: if (!seenKeys.add(item.dataKey)) "Duplicate data key in batch" else null
+            if (error != null) {
+                return GameDataBatchResult(false, 0, failedKey = item.dataKey, error = error)
+            }
+        }
+        val heldKeys = mutableListOf<String>()
+        val toSave = items.filter { item ->
+            when (val screening = piiGuard.screen(childId, gameType, item.dataKey, JsonObject(item.dataValue), HeldDataTarget.SIMPLE)) {
+                PiiScreening.Clean -> true
+                is PiiScreening.Held -> {
+                    heldKeys += item.dataKey
+                    false
+                }
+                // Only happens under REJECT, which queues nothing, so failing here leaves no trace
+                is PiiScreening.Rejected ->
+                    return GameDataBatchResult(false, 0, failedKey = item.dataKey, error = screening.blockedMessage())
+            }
+        }
+
+        if (toSave.isNotEmpty()) {
+            store.saveAll(childId, gameType, toSave, clock())
+        }
+        return GameDataBatchResult(true, toSave.size, heldKeys = heldKeys)
+    }
+
+    fun loadBatch(childId: UUID, gameType: String, dataKeys: List<String>): List<SimpleGameDataRecord> {
+        val keys = dataKeys.filter { it.isNotBlank() }.distinct()
+        require(keys.size <= MAX_BATCH_ITEMS) { "Cannot load more than $MAX_BATCH_ITEMS keys at once" }
+        if (keys.isEmpty()) return emptyList()
+        return store.load(childId, gameType, keys)
+    }
+
+    private fun validate(item: GameDataBatchItem, gameType: String): String? {
+        if (item.gameType != gameType) {
+            return "Item game type '${item.gameType}' does not match '$gameType'"
+        }
+        if (item.dataKey.isBlank() || item.dataKey.length > MAX_DATA_KEY_LENGTH) {
+            return "Data key must be 1-$MAX_DATA_KEY_LENGTH characters"
+        }
+        return dataValidator.validate(gameType, JsonObject(item.dataValue))
+            .takeIf { it.isNotEmpty() }
+            ?.joinToString("; ")
+    }
+}
@@ -0,0 +1,49 @@
+package com.wondernest.services.games
+
+import kotlinx.coroutines.CancellationException
+import kotlinx.coroutines.CoroutineScope
+import kotlinx.coroutines.Job
+import kotlinx.coroutines.delay
+import kotlinx.coroutines.isActive
+import kotlinx.coroutines.launch
+import mu.KotlinLogging
+
+private val logger = KotlinLogging.logger {}
+
+/**
+ * Periodically drops game data history past its retention window
+ */
+class GameDataHistoryPruneTask(
+    private val historyService: GameDataHistoryService,
+    private val intervalMillis: Long = DEFAULT_INTERVAL_MILLIS
+) {
+
+    fun runOnce(): Int {
+        val pruned = historyService.prune()
+        if (pruned > 0) logger.info { "Pruned $pruned game data history entries" }
+        return pruned
+    }
+
+    fun start(scope: CoroutineScope): Job = scope.launch {
+        while (isActive) {
+            try {
+                runOnce()
+            } catch (e: CancellationException) {
+                throw e
+            } catch (e: Exception) {
+                logger.warn(e) { "Game data history prune failed, retrying next interval" }
+            }
+            delay(intervalMillis)
+        }
+    }
+
+    companion object {
+        const val DEFAULT_INTERVAL_MILLIS = 6 * 60 * 60 * 1000L
+
+        fun fromEnvironment(historyService: GameDataHistoryService, env: Map<String, String> = System.getenv()) = GameDataHistoryPruneTask(
+            historyService = historyService,
+            intervalMillis = env["GAME_DATA_HISTORY_PRUNE_INTERVAL_HOURS"]?.toLongOrNull()?.takeIf { it > 0 }
+                ?.let { it * 60 * 60 * 1000 } ?: DEFAULT_INTERVAL_MILLIS
+        )
+    }
+}
@@ -0,0 +1,223 @@
+package com.wondernest.services.games
+
+import com.wondernest.data.database.table.ChildGameDataHistory
+import com.wondernest.data.database.table.ChildGameInstances
+import com.wondernest.data.database.table.GameRegistry
+import com.wondernest.data.database.table.SimpleGameData
+import com.wondernest.data.database.table.SimpleGameDataHistory
+import kotlinx.datetime.Clock
+import kotlinx.datetime.Instant
+import kotlinx.serialization.Serializable
+import kotlinx.serialization.Transient
+import kotlinx.serialization.json.JsonElement
+import kotlinx.serialization.json.JsonObject
+import kotlinx.serialization.json.jsonObject
+import org.jetbrains.exposed.sql.*
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.eq
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.less
+import org.jetbrains.exposed.sql.transactions.transaction
+import org.jetbrains.exposed.sql.upsert
+import java.util.UUID
+import kotlin.time.Duration.Companion.days
+
+@Serializable
+data class GameDataHistoryEntry(
+    val dataKey: String,
+    val dataVersion: Int,
+    val dataValue: JsonElement,
+    val recordedAt: String,
+    @Transient val source: GameDataHistorySource = GameDataHistorySource.SIMPLE
+)
+
+/** Which table a history entry was saved from, and so which one a restore writes back to */
+enum class GameDataHistorySource { SIMPLE, INSTANCE }
+
+/**
+ * How long game data history is kept. Children's data shouldn't outlive what COPPA
+ * retention allows, so old versions are dropped by age as well as by count.
+ */
+data class GameDataHistoryConfig(
+    val retentionDays: Int = DEFAULT_RETENTION_DAYS,
+    val maxVersionsPerKey: Int = DEFAULT_MAX_VERSIONS
+) {
+    companion object {
+        const val DEFAULT_RETENTION_DAYS = 90
+        const val DEFAULT_MAX_VERSIONS = 50
+
+        /**
+         * Reads GAME_DATA_HISTORY_RETENTION_DAYS and GAME_DATA_HISTORY_MAX_VERSIONS
+         */
+        fun fromEnvironment(env: Map<String, String> = System.getenv()) = GameDataHistoryConfig(
+            retentionDays = env["GAME_DATA_HISTORY_RETENTION_DAYS"]?.toIntOrNull()?.takeIf { it > 0 }
+                ?: DEFAULT_RETENTION_DAYS,
+            maxVersionsPerKey = env["GAME_DATA_HISTORY_MAX_VERSIONS"]?.toIntOrNull()?.takeIf { it > 0 }
+                ?: DEFAULT_MAX_VERSIONS
+        )
+    }
+}
+
+interface GameDataHistoryStore {
+    /** Newest version first */
+    fun find(childId: UUID, gameKey: String, dataKey: String, limit: Int): List<GameDataHistoryEntry>
+
+    fun findVersion(childId: UUID, gameKey: String, dataKey: String, version: Int): GameDataHistoryEntry?
+
+    /** Write [dataValue] back to simple_game_data, which records it as a new version */
+    fun saveSimple(childId: UUID, gameKey: String, dataKey: String, dataValue: Map<String, JsonElement>, now: Instant)
+
+    /** Drop entries recorded before [cutoff] and all but the newest [keepVersions] per key */
+    fun prune(cutoff: Instant, keepVersions: Int): Int
+}
+
+/**
+ * History from games.simple_game_data_history, where most games save, falling back to
+ * games.child_game_data_history for keys saved through game instances
+ */
+object DatabaseGameDataHistoryStore : GameDataHistoryStore {
+    override fun find(childId: UUID, gameKey: String, dataKey: String, limit: Int): List<GameDataHistoryEntry> = transaction {
+        simpleHistoryFor(childId, gameKey, dataKey)
+            .orderBy(SimpleGameDataHistory.dataVersion to SortOrder.DESC)
+            .limit(limit)
+            .map { it.toSimpleEntry() }
+            .ifEmpty {
+                instanceHistoryFor(childId, gameKey, dataKey)
+                    ?.orderBy(ChildGameDataHistory.dataVersion to SortOrder.DESC)
+                    ?.limit(limit)
+                    ?.map { it.toInstanceEntry() }
+                    .orEmpty()
+            }
+    }
+
+    override fun findVersion(childId: UUID, gameKey: String, dataKey: String, version: Int): GameDataHistoryEntry? = transaction {
+        simpleHistoryFor(childId, gameKey, dataKey)
+            .andWhere { SimpleGameDataHistory.dataVersion eq version }
+            .singleOrNull()
+            ?.toSimpleEntry()
+            ?: instanceHistoryFor(childId, gameKey, dataKey)
+                ?.andWhere { ChildGameDataHistory.dataVersion eq version }
+                ?.singleOrNull()
+                ?.toInstanceEntry()
+    }
+
+    override fun saveSimple(childId: UUID, gameKey: String, dataKey: String, dataValue: Map<String, JsonElement>, now: Instant) {
+        transaction {
+            SimpleGameData.upsert(
+                keys = arrayOf(SimpleGameData.childId, SimpleGameData.gameType, SimpleGameData.dataKey)
+            ) {
+                it[SimpleGameData.childId] = childId
+                it[SimpleGameData.gameType] = gameKey
+                it[SimpleGameData.dataKey] = dataKey
+                it[SimpleGameData.dataValue] = dataValue
+                it[SimpleGameData.updatedAt] = now
+                it[SimpleGameData.createdAt] = now // Will be ignored for updates due to ON CONFLICT
+            }
+        }
+    }
+
+    override fun prune(cutoff: Instant, keepVersions: Int): Int = transaction {
+        val expired = SimpleGameDataHistory.deleteWhere { SimpleGameDataHistory.recordedAt less cutoff } +
+            ChildGameDataHistory.deleteWhere { ChildGameDataHistory.recordedAt less cutoff }
+        // keepVersions is an Int from config, so it is safe to inline
+        val overflow = connection.prepareStatement(
+            """
+            DELETE FROM games.simple_game_data_history h
+            USING (
+                SELECT child_id, game_type, data_key, MAX(data_version) AS latest
+                FROM games.simple_game_data_history
+                GROUP BY child_id, game_type, data_key
+            ) latest
+            WHERE h.child_id = latest.child_id
+              AND h.game_type = latest.game_type
+              AND h.data_key = latest.data_key
+              AND h.data_version <= latest.latest - $keepVersions
+            """.trimIndent(),
+            false
+        ).executeUpdate() + connection.prepareStatement(
+            """
+            DELETE FROM games.child_game_data_history h
+            USING (
+                SELECT child_game_instance_id, data_key, MAX(data_version) AS latest
+                FROM games.child_game_data_history
+                GROUP BY child_game_instance_id, data_key
+            ) latest
+            WHERE h.child_game_instance_id = latest.child_game_instance_id
+              AND h.data_key = latest.data_key
+              AND h.data_version <= latest.latest - $keepVersions
+            """.trimIndent(),
+            false
+        ).executeUpdate()
+        expired + overflow
+    }
+
+    private fun simpleHistoryFor(childId: UUID, gameKey: String, dataKey: String): Query =
+        SimpleGameDataHistory.select {
+            (SimpleGameDataHistory.childId eq childId) and
+            (SimpleGameDataHistory.gameType eq gameKey) and
+            (SimpleGameDataHistory.dataKey eq dataKey)
+        }
+
+    // The per-instance game tables are only present on databases that still carry the old games schema
+    private fun instanceHistoryFor(childId: UUID, gameKey: String, dataKey: String): Query? {
+        if (!ChildGameInstances.exists()) return null
+        return ChildGameDataHistory.join(ChildGameInstances, JoinType.INNER) {
+            ChildGameDataHistory.childGameInstanceId eq ChildGameInstances.id
+        }.join(GameRegistry, JoinType.INNER) {
+            ChildGameInstances.gameId eq GameRegistry.id
+        }.select {
+            (ChildGameInstances.childId eq childId) and
+            (GameRegistry.gameKey eq gameKey) and
+            (ChildGameDataHistory.dataKey eq dataKey)
+        }
+    }
+
+    private fun ResultRow.toSimpleEntry() = GameDataHistoryEntry(
+        dataKey = this[SimpleGameDataHistory.dataKey],
+        dataVersion = this[SimpleGameDataHistory.dataVersion],
+        dataValue = JsonObject(this[SimpleGameDataHistory.dataValue]),
+        recordedAt = this[SimpleGameDataHistory.recordedAt].toString(),
+        source = GameDataHistorySource.SIMPLE
+    )
+
+    private fun ResultRow.toInstanceEntry() = GameDataHistoryEntry(
+        dataKey = this[ChildGameDataHistory.dataKey],
+        dataVersion = this[ChildGameDataHistory.dataVersion],
+        dataValue = fromStorageMap(this[ChildGameDataHistory.dataValue]),
+        recordedAt = this[ChildGameDataHistory.recordedAt].toString(),
+        source = GameDataHistorySource.INSTANCE
+    )
+}
+
+/**
+ * Earlier versions of a child's game data, so parents can undo an accidental reset
+ */
+class GameDataHistoryService(
+    private val gameDataService: GameDataService = GameDataService(),
+    private val store: GameDataHistoryStore = DatabaseGameDataHistoryStore,
+    private val config: GameDataHistoryConfig = GameDataHistoryConfig.fromEnvironment(),
+    private val clock: () -> Instant = { Clock.System.now() }
+) {
+    companion object {
+        const val DEFAULT_LIMIT = 10
+    }
+
+    fun getHistory(childId: UUID, gameKey: String, dataKey: String, limit: Int = DEFAULT_LIMIT): List<GameDataHistoryEntry> =
+        store.find(childId, gameKey, dataKey, limit.coerceIn(1, config.maxVersionsPerKey))
+
+    /**
+     * Save the value from [version] as a new current version. The old versions stay in history,
+     * so a restore can itself be undone.
+     */
+    fun restore(childId: UUID, gameKey: String, dataKey: String, version: Int): GameDataOperationResult {
+        val entry = store.findVersion(childId, gameKey, dataKey, version)
+            ?: return GameDataOperationResult.failure("Version $version of '$dataKey' not found")
+        if (entry.source == GameDataHistorySource.INSTANCE) {
+            return gameDataService.saveGameData(childId, gameKey, dataKey, entry.dataValue)
+        }
+        // Same checks as a fresh save, since the game may have been disabled since this version was written
+        gameDataService.rejectSave(childId, gameKey, dataKey, entry.dataValue)?.let { return it }
+        store.saveSimple(childId, gameKey, dataKey, entry.dataValue.jsonObject, clock())
+        return GameDataOperationResult.success("Game data saved successfully", null)
+    }
+
+    fun prune(): Int = store.prune(clock() - config.retentionDays.days, config.maxVersionsPerKey)
+}
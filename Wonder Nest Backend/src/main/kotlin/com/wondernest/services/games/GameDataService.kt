@@ -1,6 +1,9 @@
 package com.wondernest.services.games
 
 import com.wondernest.data.database.table.*
+import com.wondernest.services.moderation.ChildDataPiiGuard
+import com.wondernest.services.moderation.HeldDataTarget
+import com.wondernest.services.moderation.PiiScreening
 import kotlinx.serialization.Serializable
 import kotlinx.serialization.json.Json
 import kotlinx.serialization.json.JsonElement
@@ -16,16 +19,123 @@ import java.util.UUID
  * Service for managing game data operations
  * Handles all game data CRUD operations following proper GameRegistry architecture
  */
-class GameDataService {
-    
-    private val childGameInstanceService = ChildGameInstanceService()
-    private val gameRegistryService = GameRegistryService()
+class GameDataService(
+    private val childGameInstanceService: ChildGameInstanceService = ChildGameInstanceService(),
+    private val gameRegistryService: GameRegistryService = GameRegistryService(),
+    private val versionStore: GameDataVersionStore = DatabaseGameDataVersionStore,
+    private val dataValidator: GameDataValidator = GameDataValidator(),
+    private val piiGuard: ChildDataPiiGuard = ChildDataPiiGuard()
+) {
     
     /**
      * Save or update game data for a child
      * Automatically creates game instance if it doesn't exist
+     * Rejected when a parent has disabled the game for this child, and rejected or held for
+     * review when it looks like it contains personal information
+     *
+     * Without [expectedVersion] the last write wins. With it the save only applies if the
+     * stored version still matches (0 meaning "not saved yet"); otherwise the result is a
+     * conflict carrying the current server state so the client can merge.
      */
     fun saveGameData(
+        childId: UUID,
+        gameKey: String,
+        dataKey: String,
+        dataValue: JsonElement,
+        expectedVersion: Int? = null
+    ): GameDataOperationResult {
+        rejectSave(childId, gameKey, dataKey, dataValue)?.let { return it }
+        if (expectedVersion != null) {
+            return saveVersionedGameData(childId, gameKey, dataKey, dataValue, expectedVersion)
+        }
+        return saveEnabledGameData(childId, gameKey, dataKey, dataValue)
+    }
+    
+    private fun saveVersionedGameData(
+        childId: UUID,
+        gameKey: String,
+        dataKey: String,
+        dataValue: JsonElement,
+        expectedVersion: Int
+    ): GameDataOperationResult {
+        if (expectedVersion < 0) {
+            return GameDataOperationResult.failure("expectedVersion must not be negative")
+        }
+        
+        val game = gameRegistryService.getGameByKey(gameKey)
+            ?: return GameDataOperationResult.failure("Game '$gameKey' not found in registry")
+        val instance = childGameInstanceService.getOrCreateInstance(childId, UUID.fromString(game.id))
+        val instanceId = UUID.fromString(instance.id)
+        
+        val now = Clock.System.now()
+        val dataValueMap = toStorageMap(dataValue)
+        val saved = if (expectedVersion == 0) {
+            versionStore.insertIfAbsent(instanceId, dataKey, dataValueMap, now)
+        } else {
+            versionStore.updateIfVersion(instanceId, dataKey, expectedVersion, dataValueMap, now)
+        }
+        
+        if (saved == null) {
+            val current = versionStore.find(instanceId, dataKey)
+            return GameDataOperationResult.conflict(
+                "Game data was changed on another device (expected version $expectedVersion, " +
+                    "current version ${current?.dataVersion ?: 0})",
+                current?.toInfo(childId, gameKey)
+            )
+        }
+        
+        childGameInstanceService.updatePlayTime(instanceId, 0) // Just update timestamp
+        return GameDataOperationResult.success("Game data saved successfully", saved.toInfo(childId, gameKey))
+    }
+    
+    /**
+     * Why [dataValue] can't be saved for this child (game disabled, invalid data or personal
+     * information), or null when it can
+     */
+    fun rejectSave(childId: UUID, gameKey: String, dataKey: String, dataValue: JsonElement): GameDataOperationResult? {
+        if (!childGameInstanceService.isGameEnabled(childId, gameKey)) {
+            return GameDataOperationResult.disabled(gameKey)
+        }
+        return invalidData(gameKey, dataValue) ?: containsPii(childId, gameKey, dataKey, dataValue)
+    }
+    
+    /**
+     * Write data a moderator released from the PII review queue. It was validated and screened
+     * when it was held, but the parent may have disabled the game since.
+     */
+    fun saveReviewedGameData(childId: UUID, gameKey: String, dataKey: String, dataValue: JsonElement): GameDataOperationResult {
+        if (!childGameInstanceService.isGameEnabled(childId, gameKey)) {
+            return GameDataOperationResult.disabled(gameKey)
+        }
+        return saveEnabledGameData(childId, gameKey, dataKey, dataValue)
+    }
+    
+    private fun invalidData(gameKey: String, dataValue: JsonElement): GameDataOperationResult? {
+        val errors = dataValidator.validate(gameKey, dataValue)
+        if (errors.isEmpty()) return null
+        return GameDataOperationResult.failure("Invalid game data: ${errors.joinToString("; ")}")
+    }
+    
+    private fun containsPii(childId: UUID, gameKey: String, dataKey: String, dataValue: JsonElement): GameDataOperationResult? =
+        when (val screening = piiGuard.screen(childId, gameKey, dataKey, dataValue, HeldDataTarget.GAME_INSTANCE)) {
+            PiiScreening.Clean -> null
+            is PiiScreening.Held -> GameDataOperationResult.heldForReview(screening.blockedMessage()!!)
+            is PiiScreening.Rejected -> GameDataOperationResult.failure(screening.blockedMessage()!!)
+        }
+    
+    private fun StoredGameData.toInfo(childId: UUID, gameKey: String) = GameDataInfo(
+        id = id.toString(),
+        instanceId = instanceId.toString(),
+        childId = childId.toString(),
+        gameKey = gameKey,
+        dataKey = dataKey,
+        dataValue = fromStorageMap(dataValue),
+        dataVersion = dataVersion,
+        createdAt = createdAt.toString(),
+        updatedAt = updatedAt.toString()
+    )
+    
+    private fun saveEnabledGameData(
         childId: UUID,
         gameKey: String,
         dataKey: String,
@@ -41,10 +151,7 @@ class GameDataService {
         val instanceId = UUID.fromString(instance.id)
         
         // Convert JsonElement to Map<String, String> for storage
-        val dataValueMap = when (dataValue) {
-            is JsonObject -> dataValue.mapValues { (_, value) -> value.toString() }
-            else -> mapOf("data" to dataValue.toString())  // Wrap non-object JSON in a map
-        }
+        val dataValueMap = toStorageMap(dataValue)
         
         // Check if data already exists for this instance and key
         val existingData = ChildGameData
@@ -128,6 +235,12 @@ class GameDataService {
         val instance = childGameInstanceService.getInstanceByChildAndGameKey(childId, gameKey)
             ?: return@transaction GameDataOperationResult.failure("Child does not have access to game '$gameKey'")
         
+        if (!instance.isEnabled) {
+            return@transaction GameDataOperationResult.disabled(gameKey)
+        }
+        invalidData(gameKey, dataValue)?.let { return@transaction it }
+        containsPii(childId, gameKey, dataKey, dataValue)?.let { return@transaction it }
+        
         // Find existing data entry
         val existingData = ChildGameData.join(ChildGameInstances, JoinType.INNER) {
             ChildGameData.childGameInstanceId eq ChildGameInstances.id
@@ -137,10 +250,7 @@ class GameDataService {
         }.singleOrNull()
         
         // Convert JsonElement to Map<String, String> for storage
-        val dataValueMap = when (dataValue) {
-            is JsonObject -> dataValue.mapValues { (_, value) -> value.toString() }
-            else -> mapOf("data" to dataValue.toString())  // Wrap non-object JSON in a map
-        }
+        val dataValueMap = toStorageMap(dataValue)
         
         if (existingData != null) {
             // Update existing data
@@ -172,7 +282,7 @@ class GameDataService {
             )
         } else {
             // Create new data entry
-            return@transaction saveGameData(childId, gameKey, dataKey, dataValue)
+            return@transaction saveEnabledGameData(childId, gameKey, dataKey, dataValue)
         }
     }
     
@@ -196,19 +306,8 @@ class GameDataService {
         
         filteredQuery.orderBy(ChildGameData.updatedAt to SortOrder.DESC)
             .map { row ->
-                val storedData = row[ChildGameData.dataValue]
-                
                 // Reconstruct JsonElement from stored data
-                val dataValueJson = if (storedData.containsKey("data")) {
-                    // Was wrapped non-object JSON
-                    Json.parseToJsonElement(storedData["data"].toString())
-                } else {
-                    // Was a JsonObject, reconstruct it
-                    val jsonMap = storedData.mapValues { (_, value) ->
-                        Json.parseToJsonElement(value.toString())
-                    }
-                    JsonObject(jsonMap)
-                }
+                val dataValueJson = fromStorageMap(row[ChildGameData.dataValue])
                 
                 GameDataInfo(
                     id = row[ChildGameData.id].toString(),
@@ -308,6 +407,19 @@ class GameDataService {
     }
 }
 
+// Non-object JSON is wrapped under "data"; object fields are stored as their JSON text
+internal fun toStorageMap(dataValue: JsonElement): Map<String, String> = when (dataValue) {
+    is JsonObject -> dataValue.mapValues { (_, value) -> value.toString() }
+    else -> mapOf("data" to dataValue.toString())
+}
+
+internal fun fromStorageMap(storedData: Map<String, String>): JsonElement =
+    if (storedData.containsKey("data")) {
+        Json.parseToJsonElement(storedData.getValue("data"))
+    } else {
+        JsonObject(storedData.mapValues { (_, value) -> Json.parseToJsonElement(value) })
+    }
+
 // Data models for GameDataService
 @Serializable
 data class GameDataInfo(
@@ -335,7 +447,10 @@ data class ChildActiveGameInfo(
 data class GameDataOperationResult(
     val success: Boolean,
     val message: String,
-    val data: GameDataInfo?
+    val data: GameDataInfo?,
+    val gameDisabled: Boolean = false,
+    val conflict: Boolean = false,
+    val heldForReview: Boolean = false
 ) {
     companion object {
         fun success(message: String, data: GameDataInfo?): GameDataOperationResult {
@@ -345,6 +460,18 @@ data class GameDataOperationResult(
         fun failure(message: String): GameDataOperationResult {
             return GameDataOperationResult(false, message, null)
         }
+        
+        fun disabled(gameKey: String): GameDataOperationResult {
+            return GameDataOperationResult(false, "Game '$gameKey' has been disabled for this child", null, gameDisabled = true)
+        }
+        
+        fun conflict(message: String, current: GameDataInfo?): GameDataOperationResult {
+            return GameDataOperationResult(false, message, current, conflict = true)
+        }
+        
+        fun heldForReview(message: String): GameDataOperationResult {
+            return GameDataOperationResult(false, message, null, heldForReview = true)
+        }
     }
 }
 
@@ -353,7 +480,8 @@ data class GameDataOperationResult(
 data class SaveGameDataRequest(
     val gameKey: String,
     val dataKey: String,
-    val dataValue: JsonElement  // Accept any JSON structure, not just Map
+    val dataValue: JsonElement,  // Accept any JSON structure, not just Map
+    val expectedVersion: Int? = null  // Omit for last-write-wins
 )
 
 @Serializable
@@ -0,0 +1,147 @@
+package com.wondernest.services.games
+
+import com.wondernest.data.database.table.GameRegistry
+import kotlinx.serialization.json.*
+import mu.KotlinLogging
+import org.jetbrains.exposed.sql.select
+import org.jetbrains.exposed.sql.transactions.transaction
+
+private val logger = KotlinLogging.logger {}
+
+fun interface GameDataSchemaSource {
+    fun schemaFor(gameKey: String): JsonObject?
+}
+
+/**
+ * Schemas are stored as JSON text under the "dataSchema" key of game_registry.configuration
+ */
+object DatabaseGameDataSchemaSource : GameDataSchemaSource {
+    const val CONFIG_KEY = "dataSchema"
+
+    override fun schemaFor(gameKey: String): JsonObject? = transaction {
+        val raw = GameRegistry.slice(GameRegistry.configuration)
+            .select { GameRegistry.gameKey eq gameKey }
+            .singleOrNull()
+            ?.get(GameRegistry.configuration)
+            ?.get(CONFIG_KEY)
+            ?: return@transaction null
+        try {
+            Json.parseToJsonElement(raw).jsonObject
+        } catch (e: Exception) {
+            // A broken schema shouldn't lock children out of saving
+            logger.warn { "Ignoring invalid data schema for game '$gameKey': ${e.message}" }
+            null
+        }
+    }
+}
+
+/**
+ * Checks game save data before it is persisted. Size and nesting limits always apply;
+ * games with a registered schema are also checked against it, everything else stays free-form.
+ *
+ * Supports the subset of JSON Schema game saves need: type, properties, required,
+ * additionalProperties (boolean), items, enum, minimum/maximum, minLength/maxLength and maxItems.
+ */
+class GameDataValidator(
+    private val schemaSource: GameDataSchemaSource = DatabaseGameDataSchemaSource,
+    private val maxDepth: Int = MAX_DEPTH,
+    private val maxBytes: Int = MAX_BYTES
+) {
+    companion object {
+        const val MAX_DEPTH = 20
+        const val MAX_BYTES = 64 * 1024
+    }
+
+    /**
+     * Returns a description of every problem found, or an empty list when [value] can be saved
+     */
+    fun validate(gameKey: String, value: JsonElement): List<String> {
+        depthError(value, 1, "$")?.let { return listOf(it) }
+
+        val size = value.toString().toByteArray().size
+        if (size > maxBytes) {
+            return listOf("$: data is $size bytes, limit is $maxBytes")
+        }
+
+        val schema = schemaSource.schemaFor(gameKey) ?: return emptyList()
+        return mutableListOf<String>().also { check(schema, value, "$", it) }
+    }
+
+    // Stops at the first branch past the limit rather than walking the whole payload
+    private fun depthError(value: JsonElement, depth: Int, path: String): String? {
+        val children = when (value) {
+            is JsonObject -> value.entries.map { (key, child) -> "$path.$key" to child }
+            is JsonArray -> value.mapIndexed { index, child -> "$path[$index]" to child }
+            else -> return null
+        }
+        if (depth > maxDepth) return "$path: nesting deeper than $maxDepth levels"
+        return children.firstNotNullOfOrNull { (childPath, child) -> depthError(child, depth + 1, childPath) }
+    }
+
+    private fun check(schema: JsonObject, value: JsonElement, path: String, errors: MutableList<String>) {
+        val type = schema["type"]?.jsonPrimitive?.contentOrNull
+        if (type != null && !matchesType(type, value)) {
+            errors += "$path: expected $type"
+            return
+        }
+
+        schema["enum"]?.jsonArray?.let { allowed ->
+            if (value !in allowed) errors += "$path: must be one of $allowed"
+        }
+
+        when (value) {
+            is JsonObject -> checkObject(schema, value, path, errors)
+            is JsonArray -> checkArray(schema, value, path, errors)
+            is JsonPrimitive -> checkPrimitive(schema, value, path, errors)
+        }
+    }
+
+    private fun checkObject(schema: JsonObject, value: JsonObject, path: String, errors: MutableList<String>) {
+        val properties = schema["properties"]?.jsonObject ?: JsonObject(emptyMap())
+
+        schema["required"]?.jsonArray?.map { it.jsonPrimitive.content }?.forEach { name ->
+            if (name !in value) errors += "$path.$name: is required"
+        }
+
+        val allowsExtra = schema["additionalProperties"]?.jsonPrimitive?.booleanOrNull ?: true
+        value.forEach { (name, child) ->
+            val childSchema = properties[name]?.jsonObject
+            when {
+                childSchema != null -> check(childSchema, child, "$path.$name", errors)
+                !allowsExtra -> errors += "$path.$name: is not allowed"
+            }
+        }
+    }
+
+    private fun checkArray(schema: JsonObject, value: JsonArray, path: String, errors: MutableList<String>) {
+        schema["maxItems"]?.jsonPrimitive?.intOrNull?.let { max ->
+            if (value.size > max) errors += "$path: more than $max items"
+        }
+        schema["items"]?.jsonObject?.let { itemSchema ->
+            value.forEachIndexed { index, item -> check(itemSchema, item, "$path[$index]", errors) }
+        }
+    }
+
+    private fun checkPrimitive(schema: JsonObject, value: JsonPrimitive, path: String, errors: MutableList<String>) {
+        if (value.isString) {
+            val length = value.content.length
+            schema["minLength"]?.jsonPrimitive?.intOrNull?.let { if (length < it) errors += "$path: shorter than $it characters" }
+            schema["maxLength"]?.jsonPrimitive?.intOrNull?.let { if (length > it) errors += "$path: longer than $it characters" }
+            return
+        }
+        val number = value.doubleOrNull ?: return
+        schema["minimum"]?.jsonPrimitive?.doubleOrNull?.let { if (number < it) errors += "$path: below minimum $it" }
+        schema["maximum"]?.jsonPrimitive?.doubleOrNull?.let { if (number > it) errors += "$path: above maximum $it" }
+    }
+
+    private fun matchesType(type: String, value: JsonElement): Boolean = when (type) {
+        "object" -> value is JsonObject
+        "array" -> value is JsonArray
+        "null" -> value is JsonNull
+        "string" -> value is JsonPrimitive && value !is JsonNull && value.isString
+        "boolean" -> value is JsonPrimitive && !value.isString && value.booleanOrNull != null
+        "integer" -> value is JsonPrimitive && !value.isString && value.longOrNull != null
+        "number" -> value is JsonPrimitive && !value.isString && value.doubleOrNull != null
+        else -> true
+    }
+}
@@ -0,0 +1,96 @@
+package com.wondernest.services.games
+
+import com.wondernest.data.database.table.ChildGameData
+import kotlinx.datetime.Instant
+import org.jetbrains.exposed.sql.*
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.eq
+import org.jetbrains.exposed.sql.transactions.transaction
+import java.util.UUID
+
+/**
+ * A child_game_data row as the server currently has it
+ */
+data class StoredGameData(
+    val id: UUID,
+    val instanceId: UUID,
+    val dataKey: String,
+    val dataValue: Map<String, String>,
+    val dataVersion: Int,
+    val createdAt: Instant,
+    val updatedAt: Instant
+)
+
+/**
+ * Version-checked writes to child game data. Both writes return null when
+ * another device got there first, leaving the row untouched.
+ */
+interface GameDataVersionStore {
+    fun find(instanceId: UUID, dataKey: String): StoredGameData?
+
+    /** Create the entry at version 1, only if no entry exists yet */
+    fun insertIfAbsent(instanceId: UUID, dataKey: String, dataValue: Map<String, String>, now: Instant): StoredGameData?
+
+    /** UPDATE ... WHERE data_version = [expectedVersion], bumping the version on success */
+    fun updateIfVersion(
+        instanceId: UUID,
+        dataKey: String,
+        expectedVersion: Int,
+        dataValue: Map<String, String>,
+        now: Instant
+    ): StoredGameData?
+}
+
+object DatabaseGameDataVersionStore : GameDataVersionStore {
+    override fun find(instanceId: UUID, dataKey: String): StoredGameData? = transaction {
+        ChildGameData.select {
+            (ChildGameData.childGameInstanceId eq instanceId) and (ChildGameData.dataKey eq dataKey)
+        }.singleOrNull()?.let { row ->
+            StoredGameData(
+                id = row[ChildGameData.id].value,
+                instanceId = row[ChildGameData.childGameInstanceId].value,
+                dataKey = row[ChildGameData.dataKey],
+                dataValue = row[ChildGameData.dataValue],
+                dataVersion = row[ChildGameData.dataVersion],
+                createdAt = row[ChildGameData.createdAt],
+                updatedAt = row[ChildGameData.updatedAt]
+            )
+        }
+    }
+
+    override fun insertIfAbsent(
+        instanceId: UUID,
+        dataKey: String,
+        dataValue: Map<String, String>,
+        now: Instant
+    ): StoredGameData? = transaction {
+        // The (instance, data_key) unique index makes the losing insert a no-op
+        val inserted = ChildGameData.insertIgnore {
+            it[ChildGameData.childGameInstanceId] = instanceId
+            it[ChildGameData.dataKey] = dataKey
+            it[ChildGameData.dataVersion] = 1
+            it[ChildGameData.dataValue] = dataValue
+            it[ChildGameData.createdAt] = now
+            it[ChildGameData.updatedAt] = now
+        }.insertedCount
+        if (inserted == 0) null else find(instanceId, dataKey)
+    }
+
+    override fun updateIfVersion(
+        instanceId: UUID,
+        dataKey: String,
+        expectedVersion: Int,
+        dataValue: Map<String, String>,
+        now: Instant
+    ): StoredGameData? = transaction {
+        val updated = ChildGameData.update({
+            (ChildGameData.childGameInstanceId eq instanceId) and
+            (ChildGameData.dataKey eq dataKey) and
+            (ChildGameData.dataVersion eq expectedVersion)
+        }) {
+            it[ChildGameData.dataValue] = dataValue
+            it[ChildGameData.dataVersion] = expectedVersion + 1
+            it[ChildGameData.updatedAt] = now
+        }
+        if (updated == 0) null else find(instanceId, dataKey)
+    }
+}
@@ -0,0 +1,62 @@
+package com.wondernest.services.marketplace
+
+import com.wondernest.services.moderation.ContentScanFinding
+import com.wondernest.services.moderation.ContentValidationResult
+import kotlinx.serialization.Serializable
+
+class InvalidAgeRangeException(val validation: ContentValidationResult) : IllegalArgumentException(
+    "Invalid age range: ${validation.findings.joinToString { it.message }}"
+)
+
+@Serializable
+data class InvalidAgeRangeResponse(
+    val error: String,
+    val validation: ContentValidationResult
+)
+
+fun InvalidAgeRangeException.toResponse() = InvalidAgeRangeResponse(
+    error = message ?: "Invalid age range",
+    validation = validation
+)
+
+/**
+ * Checks a submission's "min-max" age range (e.g. "3-8") so the marketplace's age filters can
+ * be trusted: both ends within [MIN_AGE]..[MAX_AGE], min not above max, and a span no wider
+ * than [MAX_SPAN] years, since no single pack suits toddlers and teenagers alike.
+ */
+object AgeRangeValidation {
+    const val MIN_AGE = 0
+    const val MAX_AGE = 18
+    const val MAX_SPAN = 10
+
+    private const val CHECK = "age_range"
+    private val RANGE = Regex("""^\s*(\d{1,3})\s*-\s*(\d{1,3})\s*$""")
+
+    fun validate(ageRange: String): ContentValidationResult {
+        val match = RANGE.matchEntire(ageRange)
+            ?: return failed(ContentScanFinding(CHECK, "ageRange", "Age range must look like \"3-8\""))
+        val (min, max) = match.destructured.toList().map { it.toInt() }
+
+        val findings = buildList {
+            if (min !in MIN_AGE..MAX_AGE) {
+                add(ContentScanFinding(CHECK, "ageRangeMin", "Minimum age must be between $MIN_AGE and $MAX_AGE"))
+            }
+            if (max !in MIN_AGE..MAX_AGE) {
+                add(ContentScanFinding(CHECK, "ageRangeMax", "Maximum age must be between $MIN_AGE and $MAX_AGE"))
+            }
+            if (min > max) {
+                add(ContentScanFinding(CHECK, "ageRangeMin", "Minimum age can't be above the maximum age"))
+            } else if (isEmpty() && max - min > MAX_SPAN) {
+                add(ContentScanFinding(CHECK, "ageRange", "Age range can span at most $MAX_SPAN years"))
+            }
+        }
+        return ContentValidationResult(passed = findings.isEmpty(), findings = findings)
+    }
+
+    fun requireValid(ageRange: String) {
+        val result = validate(ageRange)
+        if (!result.passed) throw InvalidAgeRangeException(result)
+    }
+
+    private fun failed(finding: ContentScanFinding) = ContentValidationResult(passed = false, findings = listOf(finding))
+}
@@ -0,0 +1,186 @@
+package com.wondernest.services.marketplace
+
+import kotlinx.serialization.Serializable
+import kotlinx.serialization.json.JsonArray
+import kotlinx.serialization.json.JsonElement
+import kotlinx.serialization.json.JsonObject
+import kotlinx.serialization.json.JsonPrimitive
+
+/**
+ * A single RFC 6902 operation. Paths are JSON Pointers relative to the draft's content data.
+ */
+@Serializable
+data class JsonPatchOperation(
+    val op: String,
+    val path: String,
+    val value: JsonElement? = null,
+    val from: String? = null
+)
+
+/**
+ * @param expectedRevision revision of the draft the client edited; stale patches are rejected
+ */
+@Serializable
+data class ContentPatchRequest(
+    val expectedRevision: Int,
+    val operations: List<JsonPatchOperation>
+)
+
+@Serializable
+data class DraftContentResponse(
+    val itemId: String,
+    val revision: Int,
+    val contentData: Map<String, String>
+)
+
+class JsonPatchException(message: String) : IllegalArgumentException(message)
+
+class ContentVersionConflictException(val currentRevision: Int) :
+    IllegalStateException("Content was modified concurrently; current revision is $currentRevision")
+
+/**
+ * Applies RFC 6902 operations (add, remove, replace, move, copy, test) to a JSON document.
+ * Operations are applied in order and the whole patch fails if any one of them does.
+ */
+object JsonPatch {
+
+    fun apply(document: JsonElement, operations: List<JsonPatchOperation>): JsonElement =
+        operations.fold(document) { doc, operation -> applyOperation(doc, operation) }
+
+    private fun applyOperation(doc: JsonElement, operation: JsonPatchOperation): JsonElement {
+        val path = parsePointer(operation.path)
+        return when (operation.op) {
+            "add" -> add(doc, path, operation.requireValue())
+            "remove" -> remove(doc, path)
+            "replace" -> add(remove(doc, path), path, operation.requireValue())
+            "move" -> {
+                val from = parsePointer(operation.requireFrom())
+                if (path.size > from.size && path.subList(0, from.size) == from) {
+                    throw JsonPatchException("Cannot move '${operation.from}' into one of its children")
+                }
+                add(remove(doc, from), path, get(doc, from))
+            }
+            "copy" -> add(doc, path, get(doc, parsePointer(operation.requireFrom())))
+            "test" -> {
+                if (get(doc, path) != operation.requireValue()) {
+                    throw JsonPatchException("Test failed at '${operation.path}'")
+                }
+                doc
+            }
+            else -> throw JsonPatchException("Unsupported patch operation '${operation.op}'")
+        }
+    }
+
+    private fun JsonPatchOperation.requireValue(): JsonElement =
+        value ?: throw JsonPatchException("Operation '$op' at '$path' requires a value")
+
+    private fun JsonPatchOperation.requireFrom(): String =
+        from ?: throw JsonPatchException("Operation '$op' at '$path' requires 'from'")
+
+    private fun parsePointer(pointer: String): List<String> {
+        if (pointer.isEmpty()) return emptyList()
+        if (!pointer.startsWith("/")) throw JsonPatchException("Invalid JSON pointer '$pointer'")
+        return pointer.substring(1).split("/").map { it.replace("~1", "/").replace("~0", "~") }
+    }
+
+    private fun get(doc: JsonElement, path: List<String>): JsonElement =
+        path.fold(doc) { node, token ->
+            when (node) {
+                is JsonObject -> node[token]
+                is JsonArray -> token.toIntOrNull()?.let { node.getOrNull(it) }
+                else -> null
+            } ?: throw JsonPatchException("Path '/${path.joinToString("/")}' does not exist")
+        }
+
+    private fun add(doc: JsonElement, path: List<String>, value: JsonElement): JsonElement {
+        if (path.isEmpty()) return value
+        val token = path.first()
+        val rest = path.drop(1)
+        return when (doc) {
+            is JsonObject -> {
+                if (rest.isEmpty()) JsonObject(doc + (token to value))
+                else {
+                    val child = doc[token] ?: throw JsonPatchException("Path segment '$token' does not exist")
+                    JsonObject(doc + (token to add(child, rest, value)))
+                }
+            }
+            is JsonArray -> {
+                val items = doc.toMutableList()
+                if (rest.isEmpty()) {
+                    val index = if (token == "-") items.size else arrayIndex(token, items.size)
+                    items.add(index, value)
+                } else {
+                    val index = arrayIndex(token, items.size - 1)
+                    items[index] = add(items[index], rest, value)
+                }
+                JsonArray(items)
+            }
+            else -> throw JsonPatchException("Cannot add below a primitive value at '$token'")
+        }
+    }
+
+    private fun remove(doc: JsonElement, path: List<String>): JsonElement {
+        if (path.isEmpty()) throw JsonPatchException("Cannot remove the whole document")
+        val token = path.first()
+        val rest = path.drop(1)
+        return when (doc) {
+            is JsonObject -> {
+                val child = doc[token] ?: throw JsonPatchException("Path segment '$token' does not exist")
+                if (rest.isEmpty()) JsonObject(doc - token)
+                else JsonObject(doc + (token to remove(child, rest)))
+            }
+            is JsonArray -> {
+                val items = doc.toMutableList()
+                val index = arrayIndex(token, items.size - 1)
+                if (rest.isEmpty()) items.removeAt(index) else items[index] = remove(items[index], rest)
+                JsonArray(items)
+            }
+            else -> throw JsonPatchException("Cannot remove below a primitive value at '$token'")
+        }
+    }
+
+    private fun arrayIndex(token: String, max: Int): Int =
+        token.toIntOrNull()?.takeIf { it in 0..max }
+            ?: throw JsonPatchException("Invalid array index '$token'")
+}
+
+/**
+ * Shape content data must have for each content type. Content data is a flat map of
+ * string fields, so patched documents must stay a flat object of non-blank strings.
+ */
+object ContentDataSchema {
+    private const val MAX_FIELD_LENGTH = 50_000
+
+    private val requiredFields = mapOf(
+        ContentType.STORY to setOf("text"),
+        ContentType.INTERACTIVE_BOOK to setOf("text"),
+        ContentType.GAME to setOf("instructions"),
+        ContentType.ACTIVITY to setOf("instructions"),
+        ContentType.EDUCATIONAL_VIDEO to setOf("videoUrl")
+    )
+
+    fun requiredFields(contentType: ContentType): Set<String> = requiredFields[contentType].orEmpty()
+
+    /**
+     * Validate a patched document and convert it back to content data
+     */
+    fun validate(contentType: ContentType, document: JsonElement): Map<String, String> {
+        if (document !is JsonObject) throw JsonPatchException("Content data must be a JSON object")
+
+        val fields = document.mapValues { (key, value) ->
+            if (value !is JsonPrimitive || !value.isString) {
+                throw JsonPatchException("Content field '$key' must be a string")
+            }
+            if (value.content.length > MAX_FIELD_LENGTH) {
+                throw JsonPatchException("Content field '$key' exceeds $MAX_FIELD_LENGTH characters")
+            }
+            value.content
+        }
+
+        val missing = requiredFields(contentType).filter { fields[it].isNullOrBlank() }
+        if (missing.isNotEmpty()) {
+            throw JsonPatchException("${contentType.name} content requires: ${missing.joinToString()}")
+        }
+        return fields
+    }
+}
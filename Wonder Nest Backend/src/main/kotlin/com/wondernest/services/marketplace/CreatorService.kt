@@ -1,50 +1,142 @@
 package com.wondernest.services.marketplace
 
+import com.wondernest.services.moderation.AutomatedContentScanner
+import com.wondernest.services.moderation.ContentFingerprint
+import com.wondernest.services.moderation.ContentScanFinding
+import com.wondernest.services.moderation.ContentValidationResult
+import com.wondernest.services.moderation.ContentModerationDecision
+import com.wondernest.services.moderation.DuplicateDetector
+import com.wondernest.services.moderation.DuplicateMatch
+import com.wondernest.services.moderation.InMemoryModerationDecisionLog
+import com.wondernest.services.moderation.ModerationDecisionLog
+import com.wondernest.services.moderation.ModerationDecisionRequest
+import com.wondernest.services.moderation.ModerationEventType
+import com.wondernest.services.moderation.ModerationOutcome
+import com.wondernest.services.moderation.ModerationWebhookDispatcher
+import com.wondernest.services.moderation.ModerationWebhookEvent
+import com.wondernest.services.moderation.validate
+import com.wondernest.utils.ValidationUtils
 import kotlinx.serialization.Contextual
 import kotlinx.serialization.Serializable
+import kotlinx.serialization.json.JsonObject
+import kotlinx.serialization.json.JsonPrimitive
 import mu.KotlinLogging
 import org.jetbrains.exposed.sql.*
 import org.jetbrains.exposed.sql.transactions.transaction
 import java.math.BigDecimal
+import java.net.URI
+import java.time.Duration
 import java.time.Instant
 import java.time.LocalDate
+import java.time.ZoneOffset
 import java.util.UUID
 
 private val logger = KotlinLogging.logger {}
 
+// Creators whose profiles are hidden from families
+private val HIDDEN_ACCOUNT_STATUSES = setOf(CreatorAccountStatus.SUSPENDED, CreatorAccountStatus.BANNED)
+
+// Drafts the creator can still edit; automated scan failures come back as PENDING_CHANGES
+private val EDITABLE_STATUSES = setOf(PublishStatus.DRAFT, PublishStatus.PENDING_CHANGES, PublishStatus.WITHDRAWN)
+
+// Submissions a creator can pull back out of review
+private val WITHDRAWABLE_STATUSES = setOf(PublishStatus.PENDING_REVIEW, PublishStatus.PENDING_CHANGES)
+
+// Submissions that can go back into the review queue
+private val RESUBMITTABLE_STATUSES = setOf(PublishStatus.WITHDRAWN, PublishStatus.PENDING_CHANGES)
+
+// Content can be put under embargo until it has been published
+private val EMBARGOABLE_STATUSES = setOf(PublishStatus.DRAFT, PublishStatus.PENDING_REVIEW, PublishStatus.APPROVED)
+
 /**
  * Service for managing creator profiles, analytics, and payouts
  */
-class CreatorService {
-    
+class CreatorService(
+    private val publishingLimits: PublishingLimitsConfig = PublishingLimitsConfig.fromEnvironment(),
+    private val duplicateDetector: DuplicateDetector = DuplicateDetector(),
+    private val decisionLog: ModerationDecisionLog = InMemoryModerationDecisionLog(),
+    private val webhooks: ModerationWebhookDispatcher = ModerationWebhookDispatcher(),
+    private val contentScanner: AutomatedContentScanner = AutomatedContentScanner(),
+    private val queueConfig: ModerationQueueConfig = ModerationQueueConfig(),
+    private val clock: () -> Instant = Instant::now,
+    private val store: CreatorStore = InMemoryCreatorStore()
+) {
+
     /**
-     * Register as a content creator
+     * Register as a content creator. A user who is already registered gets their existing profile.
      */
     suspend fun registerCreator(userId: UUID, request: CreatorRegistrationRequest): CreatorProfile {
         logger.info { "Registering user $userId as content creator" }
+        store.findProfileByUser(userId)?.let { return it }
         
-        return transaction {
-            // Create creator profile
-            // Set up payment information
-            // Initialize metrics
-            
-            CreatorProfile(
-                id = UUID.randomUUID(),
-                userId = userId,
-                displayName = request.displayName,
-                bio = request.bio,
-                avatarUrl = request.avatarUrl,
-                tier = CreatorTier.HOBBYIST,
-                verified = false,
-                totalSales = 0,
-                totalRevenue = BigDecimal.ZERO,
-                averageRating = 0.0,
-                contentCount = 0,
-                followerCount = 0,
-                accountStatus = CreatorAccountStatus.PENDING_VERIFICATION,
-                createdAt = Instant.now()
-            )
-        }
+        val profile = CreatorProfile(
+            id = UUID.randomUUID(),
+            userId = userId,
+            displayName = request.displayName,
+            bio = request.bio,
+            avatarUrl = request.avatarUrl,
+            tier = CreatorTier.HOBBYIST,
+            verified = false,
+            totalSales = 0,
+            totalRevenue = BigDecimal.ZERO,
+            averageRating = 0.0,
+            contentCount = 0,
+            followerCount = 0,
+            accountStatus = CreatorAccountStatus.PENDING_VERIFICATION,
+            createdAt = Instant.now(),
+            contentSpecialties = request.contentSpecialties,
+            // Shown to parents as links, so only plain web URLs are kept
+            socialLinks = request.socialLinks.filterValues(::isWebUrl)
+        )
+        store.saveProfile(profile)
+        return profile
+    }
+
+    /**
+     * Look up the creator profile id registered for a user
+     */
+    fun findCreatorIdForUser(userId: UUID): UUID? = store.findProfileByUser(userId)?.id
+
+    /**
+     * Change a creator's tier (e.g. after verification or partnership)
+     */
+    fun updateCreatorTier(creatorId: UUID, tier: CreatorTier) {
+        logger.info { "Updating tier for creator $creatorId to $tier" }
+        store.updateTier(creatorId, tier)
+    }
+
+    fun getCreatorTier(creatorId: UUID): CreatorTier = store.tierOf(creatorId) ?: CreatorTier.HOBBYIST
+
+    /**
+     * Change a creator's account status, e.g. to suspend or ban them
+     */
+    fun updateAccountStatus(creatorId: UUID, status: CreatorAccountStatus) {
+        logger.info { "Updating account status for creator $creatorId to $status" }
+        if (!store.updateAccountStatus(creatorId, status)) throw NoSuchElementException("Creator not found")
+    }
+
+    /**
+     * What parents see about a creator. Suspended and banned creators aren't shown at all.
+     */
+    fun getPublicProfile(creatorId: UUID): PublicCreatorProfile? {
+        val profile = store.findProfile(creatorId) ?: return null
+        if (profile.accountStatus in HIDDEN_ACCOUNT_STATUSES) return null
+
+        val published = store.submissions(creatorId).count { it.isVisibleToFamilies() }
+        return PublicCreatorProfile(
+            id = profile.id.toString(),
+            displayName = profile.displayName,
+            bio = profile.bio,
+            avatarUrl = profile.avatarUrl,
+            tier = getCreatorTier(creatorId),
+            verified = profile.verified,
+            contentSpecialties = profile.contentSpecialties,
+            socialLinks = profile.socialLinks,
+            publishedContentCount = published,
+            averageRating = profile.averageRating.takeIf { published > 0 },
+            followerCount = profile.followerCount,
+            memberSince = profile.createdAt.toString()
+        )
     }
     
     /**
@@ -133,29 +225,566 @@ class CreatorService {
     }
     
     /**
-     * Publish content to marketplace
+     * Save content as a draft without submitting it for review
      */
-    suspend fun publishContent(
+    fun saveDraft(
         creatorId: UUID,
         request: PublishContentRequest
     ): PublishResult {
-        logger.info { "Publishing content for creator $creatorId: ${request.title}" }
-        
-        return transaction {
-            // Validate creator can publish
-            // Create marketplace listing
-            // Set up pricing and licensing
-            // Submit for review if needed
-            
+        logger.info { "Saving draft for creator $creatorId: ${request.title}" }
+        AgeRangeValidation.requireValid(request.ageRange)
+
+        val tier = PublishingTier.forCreatorTier(getCreatorTier(creatorId))
+
+        return store.locked(creatorId) {
+            publishingLimits.limitFor(tier).maxConcurrentDrafts?.let { limit ->
+                val drafts = store.submissions(creatorId).count { it.status == PublishStatus.DRAFT }
+                if (drafts >= limit) {
+                    throw PublishingLimitExceededException(PublishingLimitType.CONCURRENT_DRAFTS, tier, limit)
+                }
+            }
+
+            val submission = CreatorSubmission(
+                itemId = UUID.randomUUID(),
+                title = request.title,
+                status = PublishStatus.DRAFT,
+                submittedAt = null,
+                contentType = request.contentType
+            )
+            store.saveSubmission(creatorId, submission)
+            store.saveDraftContent(submission.itemId, DraftContent(request.withSanitizedDescriptions(), revision = 1))
+
             PublishResult(
                 success = true,
-                itemId = UUID.randomUUID(),
+                itemId = submission.itemId,
+                status = PublishStatus.DRAFT,
+                message = "Draft saved"
+            )
+        }
+    }
+
+    /**
+     * Apply a JSON Patch to a draft's content data. The patch must target the draft's
+     * current revision and the patched content must still match the content schema;
+     * otherwise nothing changes.
+     */
+    fun patchDraftContent(creatorId: UUID, itemId: UUID, request: ContentPatchRequest): DraftContentResponse =
+        store.locked(creatorId) {
+            val draft = store.findSubmission(itemId)?.takeIf { it.creatorId == creatorId }?.submission
+            val current = store.draftContent(itemId)
+            if (draft == null || current == null) throw NoSuchElementException("Draft not found")
+            require(draft.status in EDITABLE_STATUSES) { "Only drafts can be edited" }
+            if (request.expectedRevision != current.revision) throw ContentVersionConflictException(current.revision)
+
+            val document = JsonObject(current.request.contentData.mapValues { JsonPrimitive(it.value) })
+            val patched = ContentDataSchema.validate(
+                current.request.contentType,
+                JsonPatch.apply(document, request.operations)
+            )
+
+            val updated = DraftContent(current.request.copy(contentData = patched), current.revision + 1)
+            store.saveDraftContent(itemId, updated)
+            logger.info { "Patched draft $itemId to revision ${updated.revision} (${request.operations.size} operations)" }
+
+            DraftContentResponse(itemId.toString(), updated.revision, patched)
+        }
+
+    fun getDraftContent(itemId: UUID): DraftContentResponse? =
+        store.draftContent(itemId)?.let { DraftContentResponse(itemId.toString(), it.revision, it.request.contentData) }
+
+    /**
+     * Submit an existing draft for review. Drafts that fail the automated content scan are
+     * sent back to the creator as [PublishStatus.PENDING_CHANGES] instead of joining the queue.
+     */
+    suspend fun submitForReview(creatorId: UUID, itemId: UUID): PublishResult {
+        logger.info { "Submitting item $itemId for review for creator $creatorId" }
+
+        if (store.findSubmission(itemId)?.creatorId != creatorId) throw NoSuchElementException("Draft not found")
+
+        // The scan can make network requests, so it runs before taking the lock
+        val content = store.draftContent(itemId)?.let { current ->
+            current.copy(request = LocalizationCompleteness.check(current.request))
+        }
+        val validation = contentScanner.scan(content?.request?.contentData.orEmpty())
+
+        return store.locked(creatorId) {
+            val draft = store.findSubmission(itemId)?.submission ?: throw NoSuchElementException("Draft not found")
+            require(draft.status in EDITABLE_STATUSES) { "Only drafts can be submitted for review" }
+            check(store.draftContent(itemId)?.revision == content?.revision) {
+                "The draft changed while it was being checked; submit it again"
+            }
+
+            checkMonthlyPublishLimit(creatorId, store.submissions(creatorId))
+            if (!validation.passed) {
+                store.saveSubmission(
+                    creatorId,
+                    draft.copy(status = PublishStatus.PENDING_CHANGES, automatedFindings = validation.findings)
+                )
+                return@locked changesRequired(itemId, validation)
+            }
+            content?.let { store.saveDraftContent(itemId, it) }
+            val fingerprint = content?.request?.fingerprint(itemId) ?: ContentFingerprint.of(itemId, draft.title, "")
+            val duplicates = duplicateDetector.checkAndRegister(fingerprint)
+            val submitted = draft.copy(
+                status = PublishStatus.PENDING_REVIEW,
+                submittedAt = clock(),
+                possibleDuplicates = duplicates,
+                automatedFindings = emptyList()
+            )
+            store.saveSubmission(creatorId, submitted)
+            webhooks.publish(
+                ModerationWebhookEvent.of(ModerationEventType.SUBMITTED, itemId, submitted.title, submitted.submittedAt!!)
+            )
+
+            submittedForReview(itemId, duplicates)
+                .copy(languagesSupported = content?.request?.languagesSupported.orEmpty(), validation = validation)
+        }
+    }
+
+    /**
+     * Pull a submission out of review. It leaves the moderation queue, dropping any claim,
+     * and can be edited and resubmitted later.
+     */
+    fun withdrawSubmission(creatorId: UUID, itemId: UUID): PublishResult = store.locked(creatorId) {
+        val submission = ownSubmission(creatorId, itemId)
+        require(submission.status in WITHDRAWABLE_STATUSES) {
+            "Only submissions pending review or changes can be withdrawn"
+        }
+
+        store.saveSubmission(creatorId, submission.copy(status = PublishStatus.WITHDRAWN, claimedBy = null, claimedAt = null))
+        logger.info { "Creator $creatorId withdrew submission $itemId (was ${submission.status})" }
+        PublishResult(
+            success = true,
+            itemId = itemId,
+            status = PublishStatus.WITHDRAWN,
+            message = "Submission withdrawn from review"
+        )
+    }
+
+    /**
+     * Send a withdrawn submission, or one that needed changes, back into the review queue.
+     * It goes through the automated scan again and queues as newly submitted.
+     */
+    suspend fun resubmitSubmission(creatorId: UUID, itemId: UUID): PublishResult {
+        if (store.findSubmission(itemId)?.creatorId != creatorId) throw NoSuchElementException("Submission not found")
+
+        // The scan can make network requests, so it runs before taking the lock
+        val content = store.draftContent(itemId)
+        val validation = contentScanner.scan(content?.request?.contentData.orEmpty())
+
+        return store.locked(creatorId) {
+            val submission = ownSubmission(creatorId, itemId)
+            require(submission.status in RESUBMITTABLE_STATUSES) {
+                "Only withdrawn submissions or those needing changes can be resubmitted"
+            }
+            check(store.draftContent(itemId)?.revision == content?.revision) {
+                "The submission changed while it was being checked; resubmit it again"
+            }
+
+            if (!validation.passed) {
+                store.saveSubmission(
+                    creatorId,
+                    submission.copy(status = PublishStatus.PENDING_CHANGES, automatedFindings = validation.findings)
+                )
+                logger.info { "Resubmission of $itemId failed the automated scan" }
+                return@locked changesRequired(itemId, validation)
+            }
+
+            val resubmitted = submission.copy(
                 status = PublishStatus.PENDING_REVIEW,
-                message = "Content submitted for review"
+                submittedAt = clock(),
+                automatedFindings = emptyList()
             )
+            store.saveSubmission(creatorId, resubmitted)
+            logger.info { "Creator $creatorId resubmitted $itemId for review (was ${submission.status})" }
+            webhooks.publish(
+                ModerationWebhookEvent.of(ModerationEventType.SUBMITTED, itemId, resubmitted.title, resubmitted.submittedAt!!)
+            )
+            submittedForReview(itemId, resubmitted.possibleDuplicates).copy(validation = validation)
+        }
+    }
+
+    /**
+     * Apply a moderator's decision to a submission awaiting review. Rejections are validated
+     * against the rejection reason taxonomy before anything changes.
+     */
+    fun moderateSubmission(moderatorId: UUID, itemId: UUID, request: ModerationDecisionRequest): ModerationDecisionResult {
+        val decision = request.validate()
+        val creatorId = creatorOf(itemId)
+
+        return store.locked(creatorId) {
+            val submission = ownSubmission(creatorId, itemId)
+            require(submission.status == PublishStatus.PENDING_REVIEW) { "Only submissions pending review can be moderated" }
+            submission.activeClaim(clock())?.let { (claimedBy, expiresAt) ->
+                if (claimedBy != moderatorId) throw SubmissionClaimedException(claimedBy.toString(), expiresAt)
+            }
+
+            val status = if (decision.outcome == ModerationOutcome.APPROVE) PublishStatus.APPROVED else PublishStatus.REJECTED
+            val decidedAt = Instant.now()
+            val feedback = decision.reasonCategory?.let { category ->
+                RejectionFeedback(
+                    category = category.code,
+                    guidance = category.creatorGuidance,
+                    notes = decision.notes,
+                    moderatorId = moderatorId,
+                    decidedAt = decidedAt
+                )
+            }
+            store.saveSubmission(
+                creatorId,
+                submission.copy(status = status, rejection = feedback, claimedBy = null, claimedAt = null)
+            )
+            decisionLog.record(
+                ContentModerationDecision(
+                    itemId = itemId,
+                    moderatorId = moderatorId,
+                    outcome = decision.outcome,
+                    submittedAt = submission.submittedAt ?: decidedAt,
+                    decidedAt = decidedAt
+                )
+            )
+            webhooks.publish(
+                ModerationWebhookEvent.of(
+                    type = if (decision.outcome == ModerationOutcome.APPROVE) ModerationEventType.APPROVED
+                        else ModerationEventType.REJECTED,
+                    itemId = itemId,
+                    title = submission.title,
+                    occurredAt = decidedAt,
+                    moderatorId = moderatorId,
+                    reasonCategory = decision.reasonCategory
+                )
+            )
+            logger.info { "Moderator $moderatorId set submission $itemId to $status" }
+
+            ModerationDecisionResult(itemId = itemId, status = status, rejection = feedback)
+        }
+    }
+
+    /**
+     * Submissions awaiting review, highest priority first. Items claimed by another moderator
+     * are left out until their claim expires.
+     */
+    fun getModerationQueue(moderatorId: UUID, limit: Int = 50): ModerationQueueResponse {
+        val now = clock()
+        val pending = store.submissionsWithStatus(PublishStatus.PENDING_REVIEW)
+        val tiers = pending.map { it.creatorId }.distinct().associateWith { getCreatorTier(it) }
+        val items = pending
+            .filter { (_, submission) -> submission.activeClaim(now)?.first.let { it == null || it == moderatorId } }
+            .map { (creatorId, submission) -> submission.toQueueItem(tiers.getValue(creatorId), now) }
+            .sortedWith(compareByDescending<ModerationQueueItem> { it.priority }.thenBy { it.submittedAt })
+
+        return ModerationQueueResponse(items = items.take(limit), total = items.size)
+    }
+
+    /**
+     * Claim a submission for review so no other moderator picks it up. Claims lapse after
+     * [ModerationQueueConfig.claimTtl], putting abandoned items back in the queue; claiming
+     * again before then extends the claim.
+     */
+    fun claimSubmission(moderatorId: UUID, itemId: UUID): ModerationQueueItem {
+        val creatorId = creatorOf(itemId)
+
+        return store.locked(creatorId) {
+            val submission = ownSubmission(creatorId, itemId)
+            require(submission.status == PublishStatus.PENDING_REVIEW) { "Only submissions pending review can be claimed" }
+
+            val now = clock()
+            submission.activeClaim(now)?.let { (claimedBy, expiresAt) ->
+                if (claimedBy != moderatorId) throw SubmissionClaimedException(claimedBy.toString(), expiresAt)
+            }
+            val claimed = submission.copy(claimedBy = moderatorId, claimedAt = now)
+            store.saveSubmission(creatorId, claimed)
+            logger.info { "Moderator $moderatorId claimed submission $itemId" }
+            claimed.toQueueItem(getCreatorTier(creatorId), now)
+        }
+    }
+
+    /**
+     * Hand a claimed submission back to the queue
+     */
+    fun releaseClaim(moderatorId: UUID, itemId: UUID) {
+        val creatorId = creatorOf(itemId)
+        store.locked(creatorId) {
+            val submission = ownSubmission(creatorId, itemId)
+            val claim = submission.activeClaim(clock()) ?: return@locked
+            if (claim.first != moderatorId) throw SubmissionClaimedException(claim.first.toString(), claim.second)
+            store.saveSubmission(creatorId, submission.copy(claimedBy = null, claimedAt = null))
+        }
+    }
+
+    /**
+     * The moderator holding this submission and when their claim lapses, or null when unclaimed
+     */
+    private fun CreatorSubmission.activeClaim(now: Instant): Pair<UUID, Instant>? {
+        val moderator = claimedBy ?: return null
+        val expiresAt = claimedAt?.plus(queueConfig.claimTtl) ?: return null
+        return if (expiresAt.isAfter(now)) moderator to expiresAt else null
+    }
+
+    private fun CreatorSubmission.toQueueItem(tier: CreatorTier, now: Instant): ModerationQueueItem {
+        val waitingSince = submittedAt ?: now
+        val waiting = Duration.between(waitingSince, now).coerceAtLeast(Duration.ZERO)
+        val claim = activeClaim(now)
+        return ModerationQueueItem(
+            itemId = itemId.toString(),
+            title = title,
+            creatorTier = tier,
+            contentType = contentType,
+            submittedAt = waitingSince.toString(),
+            waitingMinutes = waiting.toMinutes(),
+            priority = ModerationPriority.score(tier, contentType, waiting),
+            claimedBy = claim?.first?.toString(),
+            claimExpiresAt = claim?.second?.toString()
+        )
+    }
+
+    /**
+     * Hold a submission until [embargoUntil]. The content can still be reviewed and approved,
+     * but only the creator and admins see it until the embargo lifts and it auto-publishes.
+     */
+    fun setEmbargo(creatorId: UUID, itemId: UUID, embargoUntil: Instant): EmbargoStatus {
+        require(embargoUntil.isAfter(clock())) { "Embargo must end in the future" }
+
+        return store.locked(creatorId) {
+            val submission = ownSubmission(creatorId, itemId)
+            require(submission.status in EMBARGOABLE_STATUSES) {
+                "Only drafts and submissions awaiting publication can be embargoed"
+            }
+
+            val updated = submission.copy(embargoUntil = embargoUntil)
+            store.saveSubmission(creatorId, updated)
+            logger.info { "Creator $creatorId embargoed $itemId until $embargoUntil" }
+            updated.toEmbargoStatus()
+        }
+    }
+
+    /**
+     * Lift an embargo early; approved content publishes immediately.
+     * [creatorId] restricts this to the creator's own submissions; admins pass null.
+     */
+    fun liftEmbargo(itemId: UUID, creatorId: UUID? = null): EmbargoStatus {
+        val owner = creatorId ?: creatorOf(itemId)
+        return store.locked(owner) {
+            val submission = ownSubmission(owner, itemId)
+            requireNotNull(submission.embargoUntil) { "Submission is not embargoed" }
+
+            val lifted = submission.copy(embargoUntil = null).let {
+                if (it.status == PublishStatus.APPROVED) it.copy(status = PublishStatus.PUBLISHED, publishedAt = clock()) else it
+            }
+            store.saveSubmission(owner, lifted)
+            logger.info { "Embargo on $itemId lifted early" }
+            lifted.toEmbargoStatus()
+        }
+    }
+
+    /**
+     * Publish approved submissions whose embargo has passed. Run periodically by [EmbargoReleaseTask].
+     */
+    fun releaseExpiredEmbargoes(): List<UUID> {
+        val now = clock()
+        fun CreatorSubmission.due() = status == PublishStatus.APPROVED && embargoUntil?.isAfter(now) == false
+
+        val released = store.submissionsWithStatus(PublishStatus.APPROVED)
+            .filter { it.submission.due() }
+            .mapNotNull { (creatorId, candidate) ->
+                store.locked(creatorId) {
+                    // Re-read under the lock in case the embargo was lifted or moved meanwhile
+                    val submission = store.findSubmission(candidate.itemId)?.submission?.takeIf { it.due() }
+                        ?: return@locked null
+                    store.saveSubmission(
+                        creatorId,
+                        submission.copy(status = PublishStatus.PUBLISHED, embargoUntil = null, publishedAt = now)
+                    )
+                    submission.itemId
+                }
+            }
+        if (released.isNotEmpty()) logger.info { "Released ${released.size} embargoed submissions" }
+        return released
+    }
+
+    /**
+     * Submissions families can see: published and not under embargo
+     */
+    fun getPublishedSubmissions(): List<CreatorSubmission> =
+        store.submissionsWithStatus(PublishStatus.PUBLISHED).map { it.submission }
+            .filter { it.isVisibleToFamilies() }
+
+    /**
+     * A submission as seen by [viewerCreatorId]; embargoed or unpublished content is only
+     * returned to its creator and to admins
+     */
+    fun getSubmissionForViewer(itemId: UUID, viewerCreatorId: UUID?, isAdmin: Boolean = false): CreatorSubmission? {
+        val (creatorId, submission) = store.findSubmission(itemId) ?: return null
+        return submission.takeIf { isAdmin || creatorId == viewerCreatorId || it.isVisibleToFamilies() }
+    }
+
+    /**
+     * Whether families may see marketplace item [itemId]. Items backed by a creator submission stay
+     * hidden until it is published and out of embargo; listings without one are unaffected.
+     */
+    fun isVisibleToFamilies(itemId: UUID): Boolean =
+        store.findSubmission(itemId)?.submission?.isVisibleToFamilies() ?: true
+
+    fun <T> filterVisibleToFamilies(items: List<T>, idOf: (T) -> UUID): List<T> {
+        if (items.isEmpty()) return items
+        val backed = store.findSubmissions(items.map(idOf).distinct())
+        return items.filter { backed[idOf(it)]?.isVisibleToFamilies() ?: true }
+    }
+
+    private fun CreatorSubmission.isVisibleToFamilies() =
+        status == PublishStatus.PUBLISHED && embargoUntil == null
+
+    private fun isWebUrl(link: String): Boolean {
+        val uri = runCatching { URI(link.trim()) }.getOrNull() ?: return false
+        return (uri.scheme == "http" || uri.scheme == "https") && !uri.host.isNullOrEmpty()
+    }
+
+    private fun creatorOf(itemId: UUID): UUID =
+        store.findSubmission(itemId)?.creatorId ?: throw NoSuchElementException("Submission not found")
+
+    /**
+     * [itemId] as currently stored, when it belongs to [creatorId]; call while holding the creator's lock
+     */
+    private fun ownSubmission(creatorId: UUID, itemId: UUID): CreatorSubmission =
+        store.findSubmission(itemId)?.takeIf { it.creatorId == creatorId }?.submission
+            ?: throw NoSuchElementException("Submission not found")
+
+    private fun checkMonthlyPublishLimit(creatorId: UUID, creatorSubmissions: List<CreatorSubmission>) {
+        val tier = PublishingTier.forCreatorTier(getCreatorTier(creatorId))
+        val limit = publishingLimits.limitFor(tier).maxMonthlyPublishes ?: return
+        val monthStart = LocalDate.now(ZoneOffset.UTC).withDayOfMonth(1).atStartOfDay().toInstant(ZoneOffset.UTC)
+        val submittedThisMonth = creatorSubmissions.count { it.submittedAt?.isBefore(monthStart) == false }
+        if (submittedThisMonth >= limit) {
+            throw PublishingLimitExceededException(PublishingLimitType.MONTHLY_PUBLISHES, tier, limit)
         }
     }
     
+    /**
+     * Publish content to marketplace. When [PublishContentRequest.itemId] refers to an existing
+     * listing this publishes a new version of it, which must be higher than the current one
+     * and come with a changelog. Every declared language must be fully translated.
+     */
+    suspend fun publishContent(
+        creatorId: UUID,
+        request: PublishContentRequest
+    ): PublishResult {
+        logger.info { "Publishing content for creator $creatorId: ${request.title}" }
+        AgeRangeValidation.requireValid(request.ageRange)
+
+        val localized = LocalizationCompleteness.check(request.withSanitizedDescriptions())
+        // Nothing is recorded for content that fails the automated scan; the creator fixes it and publishes again
+        val validation = contentScanner.scan(localized.contentData)
+        if (!validation.passed) return changesRequired(request.itemId, validation)
+        val itemId = request.itemId ?: UUID.randomUUID()
+        return store.locked(creatorId) {
+            val isUpdate = request.itemId != null
+            if (isUpdate && store.findSubmission(itemId)?.creatorId != creatorId) {
+                throw NoSuchElementException("Content not found")
+            }
+            val entry = nextVersionEntry(itemId, request, isUpdate)
+
+            checkMonthlyPublishLimit(creatorId, store.submissions(creatorId))
+            val duplicates = duplicateDetector.checkAndRegister(request.fingerprint(itemId))
+            val submittedAt = clock()
+            // A new version replaces the item's earlier submission
+            store.saveSubmission(
+                creatorId,
+                CreatorSubmission(
+                    itemId = itemId,
+                    title = request.title,
+                    status = PublishStatus.PENDING_REVIEW,
+                    submittedAt = submittedAt,
+                    possibleDuplicates = duplicates,
+                    contentType = request.contentType
+                )
+            )
+            store.addVersion(itemId, entry)
+            webhooks.publish(ModerationWebhookEvent.of(ModerationEventType.SUBMITTED, itemId, request.title, submittedAt))
+
+            // TODO: Create marketplace listing, set up pricing and licensing once listings are persisted
+            submittedForReview(itemId, duplicates)
+                .copy(languagesSupported = localized.languagesSupported, validation = validation)
+        }
+    }
+
+    private fun changesRequired(itemId: UUID?, validation: ContentValidationResult) = PublishResult(
+        success = false,
+        itemId = itemId,
+        status = PublishStatus.PENDING_CHANGES,
+        message = "Content needs changes before it can be reviewed",
+        validation = validation
+    )
+
+    private fun submittedForReview(itemId: UUID, duplicates: List<DuplicateMatch>) = PublishResult(
+        success = true,
+        itemId = itemId,
+        status = PublishStatus.PENDING_REVIEW,
+        message = if (duplicates.isEmpty()) "Content submitted for review"
+            else "Content submitted for review and flagged as a possible duplicate",
+        possibleDuplicates = duplicates
+    )
+
+    /**
+     * Descriptions are shown as formatted marketing text, so keep safe tags only
+     */
+    private fun PublishContentRequest.withSanitizedDescriptions() = copy(
+        description = ValidationUtils.sanitizeRichText(description) ?: description,
+        localizations = localizations.mapValues { (_, localized) ->
+            localized.copy(description = ValidationUtils.sanitizeRichText(localized.description))
+        }
+    )
+
+    private fun PublishContentRequest.fingerprint(itemId: UUID) = ContentFingerprint.of(
+        itemId = itemId,
+        title = title,
+        body = listOf(description, contentData.values.joinToString(" ")).joinToString(" ")
+    )
+
+    /**
+     * Check content against submitted items without registering it (used by admin imports)
+     */
+    fun checkForDuplicates(title: String, body: String): List<DuplicateMatch> =
+        duplicateDetector.findDuplicates(ContentFingerprint.of(UUID.randomUUID(), title, body))
+
+    /**
+     * Possible duplicates recorded when a submission entered review
+     */
+    fun getPossibleDuplicates(itemId: UUID): List<DuplicateMatch> {
+        val submission = store.findSubmission(itemId)?.submission ?: throw NoSuchElementException("Submission not found")
+        return submission.possibleDuplicates
+    }
+
+    private fun nextVersionEntry(itemId: UUID, request: PublishContentRequest, isUpdate: Boolean): PackVersionEntry {
+        val changelog = request.changelog?.trim()?.takeIf { it.isNotEmpty() }
+        val current = store.versions(itemId).maxOfOrNull { PackVersion.parse(it.version) }
+
+        if (!isUpdate || current == null) {
+            val version = request.version?.let { PackVersion.parse(it) } ?: PackVersion.INITIAL
+            return PackVersionEntry(version.toString(), changelog ?: "Initial release", Instant.now().toString())
+        }
+
+        val version = request.version?.let { PackVersion.parse(it) }
+            ?: throw IllegalArgumentException("A version is required when updating published content")
+        require(version > current) { "Version $version must be greater than current version $current" }
+        if (changelog == null) throw ChangelogRequiredException(version.toString())
+
+        return PackVersionEntry(version.toString(), changelog, Instant.now().toString())
+    }
+
+    /**
+     * Version history of a published pack, newest first
+     */
+    fun getVersionHistory(itemId: UUID): PackVersionHistory? {
+        val versions = store.versions(itemId).takeIf { it.isNotEmpty() }
+            ?.sortedByDescending { PackVersion.parse(it.version) }
+            ?: return null
+        return PackVersionHistory(
+            packId = itemId.toString(),
+            currentVersion = versions.first().version,
+            versions = versions
+        )
+    }
+    
     /**
      * Get creator's published content
      */
@@ -260,7 +889,8 @@ data class CreatorRegistrationRequest(
     val avatarUrl: String?,
     val contentSpecialties: List<String>,
     val languagesSupported: List<String>,
-    val educatorCredentials: Map<String, String>? = null
+    val educatorCredentials: Map<String, String>? = null,
+    val socialLinks: Map<String, String> = emptyMap() // e.g. "website", "instagram"
 )
 
 @Serializable
@@ -278,7 +908,29 @@ data class CreatorProfile(
     val contentCount: Int,
     val followerCount: Int,
     val accountStatus: CreatorAccountStatus,
-    @Contextual val createdAt: Instant
+    @Contextual val createdAt: Instant,
+    val contentSpecialties: List<String> = emptyList(),
+    val socialLinks: Map<String, String> = emptyMap()
+)
+
+/**
+ * Public subset of [CreatorProfile] for parents browsing who made a pack; nothing that
+ * identifies the account holder or reveals earnings or moderation state
+ */
+@Serializable
+data class PublicCreatorProfile(
+    val id: String,
+    val displayName: String,
+    val bio: String?,
+    val avatarUrl: String?,
+    val tier: CreatorTier,
+    val verified: Boolean,
+    val contentSpecialties: List<String>,
+    val socialLinks: Map<String, String>,
+    val publishedContentCount: Int,
+    val averageRating: Double?, // null until the creator has published content
+    val followerCount: Int,
+    val memberSince: String
 )
 
 @Serializable
@@ -340,7 +992,14 @@ data class PublishContentRequest(
     val licensingModel: LicensingModel,
     val tags: List<String>,
     val educationalGoals: List<String>,
-    val contentData: Map<String, String> // Specific to content type
+    val contentData: Map<String, String>, // Specific to content type
+    @Contextual val itemId: UUID? = null, // Set when publishing a new version of existing content
+    val version: String? = null,
+    val changelog: String? = null,
+    val primaryLanguage: String = "en",
+    val languagesSupported: List<String> = emptyList(),
+    val localizations: Map<String, LocalizedContent> = emptyMap(), // Keyed by language code
+    val dropIncompleteLanguages: Boolean = false
 )
 
 @Serializable
@@ -348,7 +1007,59 @@ data class PublishResult(
     val success: Boolean,
     @Contextual val itemId: UUID?,
     val status: PublishStatus,
-    val message: String
+    val message: String,
+    val possibleDuplicates: List<DuplicateMatch> = emptyList(),
+    val languagesSupported: List<String> = emptyList(),
+    val validation: ContentValidationResult? = null
+)
+
+data class CreatorSubmission(
+    val itemId: UUID,
+    val title: String,
+    val status: PublishStatus,
+    val submittedAt: Instant?,
+    val rejection: RejectionFeedback? = null,
+    val possibleDuplicates: List<DuplicateMatch> = emptyList(),
+    val embargoUntil: Instant? = null,
+    val publishedAt: Instant? = null,
+    val automatedFindings: List<ContentScanFinding> = emptyList(),
+    val contentType: ContentType? = null,
+    val claimedBy: UUID? = null, // moderator reviewing it; the claim lapses after ModerationQueueConfig.claimTtl
+    val claimedAt: Instant? = null
+) {
+    fun toEmbargoStatus() = EmbargoStatus(
+        itemId = itemId.toString(),
+        status = status,
+        embargoUntil = embargoUntil?.toString(),
+        publishedAt = publishedAt?.toString()
+    )
+}
+
+@Serializable
+data class EmbargoStatus(
+    val itemId: String,
+    val status: PublishStatus,
+    val embargoUntil: String?,
+    val publishedAt: String?
+)
+
+/**
+ * Feedback shown to the creator when a submission is rejected
+ */
+@Serializable
+data class RejectionFeedback(
+    val category: String,
+    val guidance: String,
+    val notes: String?,
+    @Contextual val moderatorId: UUID,
+    @Contextual val decidedAt: Instant
+)
+
+@Serializable
+data class ModerationDecisionResult(
+    @Contextual val itemId: UUID,
+    val status: PublishStatus,
+    val rejection: RejectionFeedback? = null
 )
 
 @Serializable
@@ -427,6 +1138,8 @@ enum class CreatorAccountStatus {
 enum class PublishStatus {
     DRAFT,
     PENDING_REVIEW,
+    PENDING_CHANGES,
+    WITHDRAWN,
     APPROVED,
     PUBLISHED,
     REJECTED,
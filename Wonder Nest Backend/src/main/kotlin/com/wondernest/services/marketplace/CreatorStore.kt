@@ -0,0 +1,374 @@
+package com.wondernest.services.marketplace
+
+import com.wondernest.data.database.table.CreatorContentVersions
+import com.wondernest.data.database.table.CreatorDrafts
+import com.wondernest.data.database.table.CreatorProfiles
+import com.wondernest.data.database.table.CreatorSubmissions
+import kotlinx.datetime.toJavaInstant
+import kotlinx.datetime.toKotlinInstant
+import org.jetbrains.exposed.sql.*
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.eq
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.inList
+import org.jetbrains.exposed.sql.transactions.transaction
+import java.math.BigDecimal
+import java.math.RoundingMode
+import java.time.Instant
+import java.util.UUID
+import java.util.concurrent.ConcurrentHashMap
+
+/**
+ * A submission together with the creator it belongs to
+ */
+data class StoredSubmission(
+    val creatorId: UUID,
+    val submission: CreatorSubmission
+)
+
+/**
+ * The editable content of a draft; [revision] goes up with every patch
+ */
+data class DraftContent(
+    val request: PublishContentRequest,
+    val revision: Int
+)
+
+/**
+ * Creator profiles and everything creators draft and submit, for [CreatorService]
+ */
+interface CreatorStore {
+    /**
+     * Run [block] holding [creatorId]'s lock, so checks and writes on that creator's
+     * submissions don't interleave with another request's
+     */
+    fun <T> locked(creatorId: UUID, block: () -> T): T
+
+    fun saveProfile(profile: CreatorProfile)
+
+    fun findProfile(creatorId: UUID): CreatorProfile?
+
+    fun findProfileByUser(userId: UUID): CreatorProfile?
+
+    fun updateTier(creatorId: UUID, tier: CreatorTier)
+
+    /** The creator's tier, or null when none has been set */
+    fun tierOf(creatorId: UUID): CreatorTier?
+
+    /** False when there is no such creator */
+    fun updateAccountStatus(creatorId: UUID, status: CreatorAccountStatus): Boolean
+
+    fun submissions(creatorId: UUID): List<CreatorSubmission>
+
+    fun findSubmission(itemId: UUID): StoredSubmission?
+
+    /** The submissions among [itemIds]; ids without one are left out */
+    fun findSubmissions(itemIds: Collection<UUID>): Map<UUID, CreatorSubmission>
+
+    fun submissionsWithStatus(status: PublishStatus): List<StoredSubmission>
+
+    /** Insert the submission or replace the one with the same item id */
+    fun saveSubmission(creatorId: UUID, submission: CreatorSubmission)
+
+    fun draftContent(itemId: UUID): DraftContent?
+
+    fun saveDraftContent(itemId: UUID, content: DraftContent)
+
+    /** Version entries of an item, oldest first */
+    fun versions(itemId: UUID): List<PackVersionEntry>
+
+    fun addVersion(itemId: UUID, entry: PackVersionEntry)
+}
+
+object DatabaseCreatorStore : CreatorStore {
+    override fun <T> locked(creatorId: UUID, block: () -> T): T = transaction {
+        // Creators without a profile row can still have submissions, so lock on the id
+        // rather than on a row; the lock is held until the transaction ends
+        exec("SELECT pg_advisory_xact_lock(hashtext('creator:$creatorId'))")
+        block()
+    }
+
+    override fun saveProfile(profile: CreatorProfile) {
+        transaction {
+            val now = Instant.now().toKotlinInstant()
+            CreatorProfiles.insert {
+                it[id] = profile.id
+                it[userId] = profile.userId
+                it[displayName] = profile.displayName
+                it[bio] = profile.bio
+                it[avatarUrl] = profile.avatarUrl
+                it[socialLinks] = profile.socialLinks
+                it[contentSpecialties] = profile.contentSpecialties
+                it[tier] = profile.tier.name
+                it[verified] = profile.verified
+                it[totalSales] = profile.totalSales
+                it[totalRevenue] = profile.totalRevenue
+                it[averageRating] = BigDecimal.valueOf(profile.averageRating).setScale(2, RoundingMode.HALF_UP)
+                it[contentCount] = profile.contentCount
+                it[followerCount] = profile.followerCount
+                it[accountStatus] = profile.accountStatus.name
+                it[createdAt] = profile.createdAt.toKotlinInstant()
+                it[updatedAt] = now
+            }
+        }
+    }
+
+    override fun findProfile(creatorId: UUID): CreatorProfile? = transaction {
+        CreatorProfiles.select { CreatorProfiles.id eq creatorId }.singleOrNull()?.toCreatorProfile()
+    }
+
+    override fun findProfileByUser(userId: UUID): CreatorProfile? = transaction {
+        CreatorProfiles.select { CreatorProfiles.userId eq userId }.singleOrNull()?.toCreatorProfile()
+    }
+
+    override fun updateTier(creatorId: UUID, tier: CreatorTier) {
+        transaction {
+            CreatorProfiles.update({ CreatorProfiles.id eq creatorId }) {
+                it[CreatorProfiles.tier] = tier.name
+                it[updatedAt] = Instant.now().toKotlinInstant()
+            }
+        }
+    }
+
+    override fun tierOf(creatorId: UUID): CreatorTier? = transaction {
+        CreatorProfiles.slice(CreatorProfiles.tier)
+            .select { CreatorProfiles.id eq creatorId }
+            .singleOrNull()
+            ?.let { CreatorTier.valueOf(it[CreatorProfiles.tier]) }
+    }
+
+    override fun updateAccountStatus(creatorId: UUID, status: CreatorAccountStatus): Boolean = transaction {
+        CreatorProfiles.update({ CreatorProfiles.id eq creatorId }) {
+            it[accountStatus] = status.name
+            it[updatedAt] = Instant.now().toKotlinInstant()
+        } > 0
+    }
+
+    override fun submissions(creatorId: UUID): List<CreatorSubmission> = transaction {
+        CreatorSubmissions.select { CreatorSubmissions.creatorId eq creatorId }.map { it.toCreatorSubmission() }
+    }
+
+    override fun findSubmission(itemId: UUID): StoredSubmission? = transaction {
+        CreatorSubmissions.select { CreatorSubmissions.itemId eq itemId }
+            .singleOrNull()
+            ?.let { StoredSubmission(it[CreatorSubmissions.creatorId], it.toCreatorSubmission()) }
+    }
+
+    override fun findSubmissions(itemIds: Collection<UUID>): Map<UUID, CreatorSubmission> = transaction {
+        if (itemIds.isEmpty()) return@transaction emptyMap()
+        CreatorSubmissions.select { CreatorSubmissions.itemId inList itemIds }
+            .associate { it[CreatorSubmissions.itemId] to it.toCreatorSubmission() }
+    }
+
+    override fun submissionsWithStatus(status: PublishStatus): List<StoredSubmission> = transaction {
+        CreatorSubmissions.select { CreatorSubmissions.status eq status.name }
+            .map { StoredSubmission(it[CreatorSubmissions.creatorId], it.toCreatorSubmission()) }
+    }
+
+    override fun saveSubmission(creatorId: UUID, submission: CreatorSubmission) {
+        transaction {
+            CreatorSubmissions.upsert(keys = arrayOf(CreatorSubmissions.itemId)) {
+                it[itemId] = submission.itemId
+                it[CreatorSubmissions.creatorId] = creatorId
+                it[title] = submission.title
+                it[status] = submission.status.name
+                it[contentType] = submission.contentType?.name
+                it[submittedAt] = submission.submittedAt?.toKotlinInstant()
+                it[publishedAt] = submission.publishedAt?.toKotlinInstant()
+                it[embargoUntil] = submission.embargoUntil?.toKotlinInstant()
+                it[claimedBy] = submission.claimedBy
+                it[claimedAt] = submission.claimedAt?.toKotlinInstant()
+                it[rejectionCategory] = submission.rejection?.category
+                it[rejectionGuidance] = submission.rejection?.guidance
+                it[rejectionNotes] = submission.rejection?.notes
+                it[rejectedBy] = submission.rejection?.moderatorId
+                it[rejectedAt] = submission.rejection?.decidedAt?.toKotlinInstant()
+                it[possibleDuplicates] = submission.possibleDuplicates
+                it[automatedFindings] = submission.automatedFindings
+                it[updatedAt] = Instant.now().toKotlinInstant()
+            }
+        }
+    }
+
+    override fun draftContent(itemId: UUID): DraftContent? = transaction {
+        CreatorDrafts.select { CreatorDrafts.itemId eq itemId }.singleOrNull()?.let { row ->
+            DraftContent(
+                request = PublishContentRequest(
+                    title = row[CreatorDrafts.title],
+                    description = row[CreatorDrafts.description],
+                    contentType = ContentType.valueOf(row[CreatorDrafts.contentType]),
+                    ageRange = row[CreatorDrafts.ageRange],
+                    price = row[CreatorDrafts.price],
+                    licensingModel = LicensingModel.valueOf(row[CreatorDrafts.licensingModel]),
+                    tags = row[CreatorDrafts.tags],
+                    educationalGoals = row[CreatorDrafts.educationalGoals],
+                    contentData = row[CreatorDrafts.contentData],
+                    version = row[CreatorDrafts.version],
+                    changelog = row[CreatorDrafts.changelog],
+                    primaryLanguage = row[CreatorDrafts.primaryLanguage],
+                    languagesSupported = row[CreatorDrafts.languagesSupported],
+                    localizations = row[CreatorDrafts.localizations],
+                    dropIncompleteLanguages = row[CreatorDrafts.dropIncompleteLanguages]
+                ),
+                revision = row[CreatorDrafts.revision]
+            )
+        }
+    }
+
+    override fun saveDraftContent(itemId: UUID, content: DraftContent) {
+        val request = content.request
+        transaction {
+            CreatorDrafts.upsert(keys = arrayOf(CreatorDrafts.itemId)) {
+                it[CreatorDrafts.itemId] = itemId
+                it[revision] = content.revision
+                it[title] = request.title
+                it[description] = request.description
+                it[contentType] = request.contentType.name
+                it[ageRange] = request.ageRange
+                it[price] = request.price
+                it[licensingModel] = request.licensingModel.name
+                it[tags] = request.tags
+                it[educationalGoals] = request.educationalGoals
+                it[contentData] = request.contentData
+                it[version] = request.version
+                it[changelog] = request.changelog
+                it[primaryLanguage] = request.primaryLanguage
+                it[languagesSupported] = request.languagesSupported
+                it[localizations] = request.localizations
+                it[dropIncompleteLanguages] = request.dropIncompleteLanguages
+                it[updatedAt] = Instant.now().toKotlinInstant()
+            }
+        }
+    }
+
+    override fun versions(itemId: UUID): List<PackVersionEntry> = transaction {
+        CreatorContentVersions.select { CreatorContentVersions.itemId eq itemId }
+            .orderBy(CreatorContentVersions.releasedAt)
+            .map {
+                PackVersionEntry(
+                    version = it[CreatorContentVersions.version],
+                    changelog = it[CreatorContentVersions.changelog],
+                    releasedAt = it[CreatorContentVersions.releasedAt].toJavaInstant().toString()
+                )
+            }
+    }
+
+    override fun addVersion(itemId: UUID, entry: PackVersionEntry) {
+        transaction {
+            CreatorContentVersions.insert {
+                it[CreatorContentVersions.itemId] = itemId
+                it[version] = entry.version
+                it[changelog] = entry.changelog
+                it[releasedAt] = Instant.parse(entry.releasedAt).toKotlinInstant()
+            }
+        }
+    }
+
+    private fun ResultRow.toCreatorProfile() = CreatorProfile(
+        id = this[CreatorProfiles.id],
+        userId = this[CreatorProfiles.userId],
+        displayName = this[CreatorProfiles.displayName],
+        bio = this[CreatorProfiles.bio],
+        avatarUrl = this[CreatorProfiles.avatarUrl],
+        tier = CreatorTier.valueOf(this[CreatorProfiles.tier]),
+        verified = this[CreatorProfiles.verified],
+        totalSales = this[CreatorProfiles.totalSales],
+        totalRevenue = this[CreatorProfiles.totalRevenue],
+        averageRating = this[CreatorProfiles.averageRating].toDouble(),
+        contentCount = this[CreatorProfiles.contentCount],
+        followerCount = this[CreatorProfiles.followerCount],
+        accountStatus = CreatorAccountStatus.valueOf(this[CreatorProfiles.accountStatus]),
+        createdAt = this[CreatorProfiles.createdAt].toJavaInstant(),
+        contentSpecialties = this[CreatorProfiles.contentSpecialties],
+        socialLinks = this[CreatorProfiles.socialLinks]
+    )
+
+    private fun ResultRow.toCreatorSubmission(): CreatorSubmission {
+        val rejection = this[CreatorSubmissions.rejectionCategory]?.let { category ->
+            RejectionFeedback(
+                category = category,
+                guidance = this[CreatorSubmissions.rejectionGuidance].orEmpty(),
+                notes = this[CreatorSubmissions.rejectionNotes],
+                moderatorId = this[CreatorSubmissions.rejectedBy]!!,
+                decidedAt = this[CreatorSubmissions.rejectedAt]!!.toJavaInstant()
+            )
+        }
+        return CreatorSubmission(
+            itemId = this[CreatorSubmissions.itemId],
+            title = this[CreatorSubmissions.title],
+            status = PublishStatus.valueOf(this[CreatorSubmissions.status]),
+            submittedAt = this[CreatorSubmissions.submittedAt]?.toJavaInstant(),
+            rejection = rejection,
+            possibleDuplicates = this[CreatorSubmissions.possibleDuplicates],
+            embargoUntil = this[CreatorSubmissions.embargoUntil]?.toJavaInstant(),
+            publishedAt = this[CreatorSubmissions.publishedAt]?.toJavaInstant(),
+            automatedFindings = this[CreatorSubmissions.automatedFindings],
+            contentType = this[CreatorSubmissions.contentType]?.let { ContentType.valueOf(it) },
+            claimedBy = this[CreatorSubmissions.claimedBy],
+            claimedAt = this[CreatorSubmissions.claimedAt]?.toJavaInstant()
+        )
+    }
+}
+
+/**
+ * For tests and local runs without a database
+ */
+class InMemoryCreatorStore : CreatorStore {
+    private val profiles = ConcurrentHashMap<UUID, CreatorProfile>()
+    private val tiers = ConcurrentHashMap<UUID, CreatorTier>()
+    private val submissions = ConcurrentHashMap<UUID, StoredSubmission>()
+    private val drafts = ConcurrentHashMap<UUID, DraftContent>()
+    private val versions = ConcurrentHashMap<UUID, MutableList<PackVersionEntry>>()
+    private val locks = ConcurrentHashMap<UUID, Any>()
+
+    override fun <T> locked(creatorId: UUID, block: () -> T): T =
+        synchronized(locks.computeIfAbsent(creatorId) { Any() }) { block() }
+
+    override fun saveProfile(profile: CreatorProfile) {
+        profiles[profile.id] = profile
+        tiers[profile.id] = profile.tier
+    }
+
+    override fun findProfile(creatorId: UUID): CreatorProfile? =
+        profiles[creatorId]?.let { profile -> tiers[creatorId]?.let { profile.copy(tier = it) } ?: profile }
+
+    override fun findProfileByUser(userId: UUID): CreatorProfile? =
+        profiles.values.firstOrNull { it.userId == userId }?.let { findProfile(it.id) }
+
+    // Unlike the database, tiers can be set for creators without a profile
+    override fun updateTier(creatorId: UUID, tier: CreatorTier) {
+        tiers[creatorId] = tier
+    }
+
+    override fun tierOf(creatorId: UUID): CreatorTier? = tiers[creatorId]
+
+    override fun updateAccountStatus(creatorId: UUID, status: CreatorAccountStatus): Boolean =
+        profiles.computeIfPresent(creatorId) { _, profile -> profile.copy(accountStatus = status) } != null
+
+    override fun submissions(creatorId: UUID): List<CreatorSubmission> =
+        submissions.values.filter { it.creatorId == creatorId }.map { it.submission }
+
+    override fun findSubmission(itemId: UUID): StoredSubmission? = submissions[itemId]
+
+    override fun findSubmissions(itemIds: Collection<UUID>): Map<UUID, CreatorSubmission> =
+        itemIds.mapNotNull { id -> submissions[id]?.let { id to it.submission } }.toMap()
+
+    override fun submissionsWithStatus(status: PublishStatus): List<StoredSubmission> =
+        submissions.values.filter { it.submission.status == status }
+
+    override fun saveSubmission(creatorId: UUID, submission: CreatorSubmission) {
+        submissions[submission.itemId] = StoredSubmission(creatorId, submission)
+    }
+
+    override fun draftContent(itemId: UUID): DraftContent? = drafts[itemId]
+
+    override fun saveDraftContent(itemId: UUID, content: DraftContent) {
+        drafts[itemId] = content
+    }
+
+    override fun versions(itemId: UUID): List<PackVersionEntry> =
+        versions[itemId]?.let { synchronized(it) { it.toList() } }.orEmpty()
+
+    override fun addVersion(itemId: UUID, entry: PackVersionEntry) {
+        val entries = versions.computeIfAbsent(itemId) { mutableListOf() }
+        synchronized(entries) { entries.add(entry) }
+    }
+}
@@ -0,0 +1,45 @@
+package com.wondernest.services.marketplace
+
+import kotlinx.coroutines.CancellationException
+import kotlinx.coroutines.CoroutineScope
+import kotlinx.coroutines.Job
+import kotlinx.coroutines.delay
+import kotlinx.coroutines.isActive
+import kotlinx.coroutines.launch
+import mu.KotlinLogging
+
+private val logger = KotlinLogging.logger {}
+
+/**
+ * Periodically publishes approved submissions whose embargo has passed
+ */
+class EmbargoReleaseTask(
+    private val creatorService: CreatorService,
+    private val intervalMillis: Long = DEFAULT_INTERVAL_MILLIS
+) {
+
+    fun runOnce(): Int = creatorService.releaseExpiredEmbargoes().size
+
+    fun start(scope: CoroutineScope): Job = scope.launch {
+        while (isActive) {
+            try {
+                runOnce()
+            } catch (e: CancellationException) {
+                throw e
+            } catch (e: Exception) {
+                logger.warn(e) { "Embargo release run failed, retrying next interval" }
+            }
+            delay(intervalMillis)
+        }
+    }
+
+    companion object {
+        const val DEFAULT_INTERVAL_MILLIS = 60 * 1000L
+
+        fun fromEnvironment(creatorService: CreatorService, env: Map<String, String> = System.getenv()) = EmbargoReleaseTask(
+            creatorService = creatorService,
+            intervalMillis = env["EMBARGO_RELEASE_INTERVAL_SECONDS"]?.toLongOrNull()?.takeIf { it > 0 }
+                ?.let { it * 1000 } ?: DEFAULT_INTERVAL_MILLIS
+        )
+    }
+}
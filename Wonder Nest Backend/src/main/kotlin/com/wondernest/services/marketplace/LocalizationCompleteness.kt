@@ -0,0 +1,90 @@
+package com.wondernest.services.marketplace
+
+import kotlinx.serialization.Serializable
+
+/**
+ * Translated fields for one declared language. Content data keys mirror the primary-language keys.
+ */
+@Serializable
+data class LocalizedContent(
+    val title: String? = null,
+    val description: String? = null,
+    val contentData: Map<String, String> = emptyMap()
+)
+
+@Serializable
+data class MissingTranslations(
+    val language: String,
+    val fields: List<String>
+)
+
+class LocalizationIncompleteException(val missing: List<MissingTranslations>) : IllegalArgumentException(
+    "Missing translations for ${missing.joinToString { "${it.language} (${it.fields.size} fields)" }}"
+)
+
+@Serializable
+data class LocalizationIncompleteResponse(
+    val error: String,
+    val missing: List<MissingTranslations>,
+    val hint: String
+)
+
+fun LocalizationIncompleteException.toResponse() = LocalizationIncompleteResponse(
+    error = message ?: "Missing translations",
+    missing = missing,
+    hint = "Add the missing translations, remove the languages from languagesSupported, " +
+        "or set dropIncompleteLanguages to publish without them"
+)
+
+/**
+ * Checks that every language a pack declares has its name, description and text
+ * content data translated before it can be published.
+ */
+object LocalizationCompleteness {
+
+    fun missingTranslations(request: PublishContentRequest): List<MissingTranslations> {
+        val textKeys = request.contentData.filterValues { isTranslatable(it) }.keys.sorted()
+
+        return declaredTranslations(request).mapNotNull { language ->
+            val localized = request.localizations.entries
+                .firstOrNull { normalize(it.key) == language }?.value ?: LocalizedContent()
+            val missing = buildList {
+                if (localized.title.isNullOrBlank()) add("title")
+                if (localized.description.isNullOrBlank()) add("description")
+                textKeys.filter { localized.contentData[it].isNullOrBlank() }.forEach { add("contentData.$it") }
+            }
+            missing.takeIf { it.isNotEmpty() }?.let { MissingTranslations(language, it) }
+        }
+    }
+
+    /**
+     * Returns the request ready to publish. Incomplete languages either block publishing with
+     * [LocalizationIncompleteException] or, when the creator opted in, are dropped from the
+     * declared languages.
+     */
+    fun check(request: PublishContentRequest): PublishContentRequest {
+        val missing = missingTranslations(request)
+        if (missing.isEmpty()) return request
+        if (!request.dropIncompleteLanguages) throw LocalizationIncompleteException(missing)
+
+        val dropped = missing.map { it.language }.toSet()
+        return request.copy(
+            languagesSupported = request.languagesSupported.filterNot { normalize(it) in dropped },
+            localizations = request.localizations.filterKeys { normalize(it) !in dropped }
+        )
+    }
+
+    private fun declaredTranslations(request: PublishContentRequest): List<String> {
+        val primary = normalize(request.primaryLanguage)
+        return request.languagesSupported.map { normalize(it) }.filter { it != primary }.distinct()
+    }
+
+    private fun normalize(language: String) = language.trim().lowercase()
+
+    // URLs and asset paths aren't translated
+    private fun isTranslatable(value: String): Boolean {
+        val trimmed = value.trim()
+        return trimmed.isNotEmpty() &&
+            !trimmed.startsWith("http://") && !trimmed.startsWith("https://") && !trimmed.startsWith("/")
+    }
+}
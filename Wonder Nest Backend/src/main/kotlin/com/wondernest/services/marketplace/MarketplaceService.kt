@@ -1,6 +1,7 @@
 package com.wondernest.services.marketplace
 
 import com.wondernest.data.database.repository.marketplace.MarketplaceRepository
+import com.wondernest.services.moderation.ContentFlagService
 import kotlinx.serialization.Contextual
 import kotlinx.serialization.Serializable
 import mu.KotlinLogging
@@ -14,15 +15,23 @@ private val logger = KotlinLogging.logger {}
  * Service for managing marketplace content discovery, purchasing, and creator management
  */
 class MarketplaceService(
-    private val marketplaceRepository: MarketplaceRepository
+    private val marketplaceRepository: MarketplaceRepository,
+    private val contentFlagService: ContentFlagService = ContentFlagService(),
+    private val creatorService: CreatorService
 ) {
     
     /**
      * Search and discover marketplace content
+     * Reported, under-review, embargoed and unpublished items are left out of results
      */
     suspend fun searchContent(request: SearchRequest): SearchResult {
         logger.info { "Searching marketplace with query: ${request.query}" }
-        return marketplaceRepository.searchListings(request)
+        val result = marketplaceRepository.searchListings(request)
+        val visible = visibleToFamilies(result.items)
+        return result.copy(
+            items = visible,
+            totalCount = result.totalCount - (result.items.size - visible.size)
+        )
     }
     
     /**
@@ -30,7 +39,14 @@ class MarketplaceService(
      */
     suspend fun getFeaturedContent(): FeaturedContent {
         logger.info { "Getting featured marketplace content" }
-        return marketplaceRepository.getFeaturedContent()
+        val featured = marketplaceRepository.getFeaturedContent()
+        return featured.copy(
+            spotlightItems = visibleToFamilies(featured.spotlightItems),
+            newReleases = visibleToFamilies(featured.newReleases),
+            topRated = visibleToFamilies(featured.topRated),
+            editorsPicks = visibleToFamilies(featured.editorsPicks),
+            trendingNow = visibleToFamilies(featured.trendingNow)
+        )
     }
     
     /**
@@ -38,7 +54,9 @@ class MarketplaceService(
      */
     suspend fun getItemDetails(itemId: UUID): MarketplaceItemDetails? {
         logger.info { "Getting details for marketplace item: $itemId" }
+        if (!creatorService.isVisibleToFamilies(itemId)) return null
         return marketplaceRepository.getListingById(itemId)
+            ?.let { it.copy(similarItems = visibleToFamilies(it.similarItems)) }
     }
     
     /**
@@ -83,7 +101,15 @@ class MarketplaceService(
      */
     suspend fun getRecommendations(childId: UUID?, familyId: UUID): List<MarketplaceItem> {
         logger.info { "Getting recommendations for child $childId, family $familyId" }
-        return marketplaceRepository.getRecommendations(childId, familyId)
+        return visibleToFamilies(marketplaceRepository.getRecommendations(childId, familyId))
+    }
+    
+    /**
+     * Report an item as inappropriate. Returns the number of active reports.
+     */
+    fun reportItem(familyId: UUID, itemId: UUID, reason: String): Int {
+        logger.info { "Family $familyId reported marketplace item $itemId" }
+        return contentFlagService.reportContent(itemId, familyId, reason)
     }
     
     /**
@@ -93,6 +119,9 @@ class MarketplaceService(
         logger.info { "Tracking interaction: $interactionType for item $itemId" }
         marketplaceRepository.trackInteraction(childId, itemId, interactionType)
     }
+
+    private fun visibleToFamilies(items: List<MarketplaceItem>): List<MarketplaceItem> =
+        creatorService.filterVisibleToFamilies(contentFlagService.filterVisible(items) { it.id }) { it.id }
 }
 
 // Data classes for marketplace operations
@@ -0,0 +1,70 @@
+package com.wondernest.services.marketplace
+
+import kotlinx.serialization.Serializable
+import java.time.Duration
+import java.time.Instant
+
+data class ModerationQueueConfig(
+    val claimTtl: Duration = DEFAULT_CLAIM_TTL
+) {
+    companion object {
+        val DEFAULT_CLAIM_TTL: Duration = Duration.ofMinutes(30)
+
+        /**
+         * Reads MODERATION_CLAIM_TTL_MINUTES
+         */
+        fun fromEnvironment(env: Map<String, String> = System.getenv()) = ModerationQueueConfig(
+            claimTtl = env["MODERATION_CLAIM_TTL_MINUTES"]?.toLongOrNull()?.takeIf { it > 0 }
+                ?.let { Duration.ofMinutes(it) }
+                ?: DEFAULT_CLAIM_TTL
+        )
+    }
+}
+
+/**
+ * Orders the review queue: higher scores are reviewed first. Tier gives established creators
+ * a head start, and every hour in the queue adds to the score so nothing waits indefinitely.
+ */
+object ModerationPriority {
+    const val POINTS_PER_HOUR_WAITING = 2
+
+    private fun tierPoints(tier: CreatorTier) = when (tier) {
+        CreatorTier.HOBBYIST -> 0
+        CreatorTier.EMERGING -> 10
+        CreatorTier.PROFESSIONAL -> 20
+        CreatorTier.VERIFIED_EDUCATOR -> 30
+        CreatorTier.PARTNER_STUDIO -> 40
+    }
+
+    // Quick reviews go slightly earlier so they don't sit behind long videos and games
+    private fun typePoints(type: ContentType?) = when (type) {
+        ContentType.STORY, ContentType.ACTIVITY -> 5
+        ContentType.INTERACTIVE_BOOK -> 3
+        ContentType.GAME, ContentType.EDUCATIONAL_VIDEO, null -> 0
+    }
+
+    fun score(tier: CreatorTier, contentType: ContentType?, waiting: Duration): Int =
+        tierPoints(tier) + typePoints(contentType) + waiting.toHours().toInt() * POINTS_PER_HOUR_WAITING
+}
+
+class SubmissionClaimedException(val claimedBy: String, val claimExpiresAt: Instant) :
+    IllegalStateException("Submission is claimed by another moderator until $claimExpiresAt")
+
+@Serializable
+data class ModerationQueueItem(
+    val itemId: String,
+    val title: String,
+    val creatorTier: CreatorTier,
+    val contentType: ContentType?,
+    val submittedAt: String,
+    val waitingMinutes: Long,
+    val priority: Int,
+    val claimedBy: String? = null,
+    val claimExpiresAt: String? = null
+)
+
+@Serializable
+data class ModerationQueueResponse(
+    val items: List<ModerationQueueItem>,
+    val total: Int
+)
@@ -0,0 +1,44 @@
+package com.wondernest.services.marketplace
+
+import kotlinx.serialization.Serializable
+
+/**
+ * Semantic version of a published pack (MAJOR.MINOR.PATCH)
+ */
+data class PackVersion(val major: Int, val minor: Int, val patch: Int) : Comparable<PackVersion> {
+
+    override fun compareTo(other: PackVersion): Int =
+        compareValuesBy(this, other, { it.major }, { it.minor }, { it.patch })
+
+    override fun toString(): String = "$major.$minor.$patch"
+
+    companion object {
+        val INITIAL = PackVersion(1, 0, 0)
+
+        fun parse(value: String): PackVersion {
+            val parts = value.trim().removePrefix("v").split(".")
+            val numbers = parts.map { it.toIntOrNull()?.takeIf { n -> n >= 0 } }
+            require(parts.size == 3 && numbers.none { it == null }) {
+                "Invalid version '$value', expected MAJOR.MINOR.PATCH"
+            }
+            return PackVersion(numbers[0]!!, numbers[1]!!, numbers[2]!!)
+        }
+    }
+}
+
+@Serializable
+data class PackVersionEntry(
+    val version: String,
+    val changelog: String,
+    val releasedAt: String
+)
+
+@Serializable
+data class PackVersionHistory(
+    val packId: String,
+    val currentVersion: String,
+    val versions: List<PackVersionEntry> // newest first
+)
+
+class ChangelogRequiredException(version: String) :
+    IllegalArgumentException("A changelog is required when publishing version $version")
@@ -0,0 +1,102 @@
+package com.wondernest.services.marketplace
+
+import kotlinx.serialization.Serializable
+
+/**
+ * Publishing tiers used for rate limiting creator submissions.
+ * TIER_1 is the most restricted, TIER_4 is effectively unlimited.
+ */
+enum class PublishingTier {
+    TIER_1,
+    TIER_2,
+    TIER_3,
+    TIER_4;
+
+    companion object {
+        fun forCreatorTier(tier: CreatorTier): PublishingTier = when (tier) {
+            CreatorTier.HOBBYIST -> TIER_1
+            CreatorTier.EMERGING -> TIER_2
+            CreatorTier.PROFESSIONAL, CreatorTier.VERIFIED_EDUCATOR -> TIER_3
+            CreatorTier.PARTNER_STUDIO -> TIER_4
+        }
+    }
+}
+
+/**
+ * Limits for a single tier. A null value means the limit is not enforced.
+ */
+data class TierPublishingLimit(
+    val maxConcurrentDrafts: Int?,
+    val maxMonthlyPublishes: Int?
+)
+
+enum class PublishingLimitType {
+    CONCURRENT_DRAFTS,
+    MONTHLY_PUBLISHES
+}
+
+/**
+ * Per-tier publishing limits. Defaults can be overridden with
+ * PUBLISH_LIMIT_TIER{n}_DRAFTS / PUBLISH_LIMIT_TIER{n}_MONTHLY, where a
+ * value of "unlimited" (or a negative number) disables the limit.
+ */
+class PublishingLimitsConfig(
+    private val limits: Map<PublishingTier, TierPublishingLimit> = DEFAULT_LIMITS
+) {
+    fun limitFor(tier: PublishingTier): TierPublishingLimit =
+        limits[tier] ?: DEFAULT_LIMITS.getValue(tier)
+
+    companion object {
+        val DEFAULT_LIMITS = mapOf(
+            PublishingTier.TIER_1 to TierPublishingLimit(maxConcurrentDrafts = 3, maxMonthlyPublishes = 5),
+            PublishingTier.TIER_2 to TierPublishingLimit(maxConcurrentDrafts = 10, maxMonthlyPublishes = 20),
+            PublishingTier.TIER_3 to TierPublishingLimit(maxConcurrentDrafts = 25, maxMonthlyPublishes = 100),
+            PublishingTier.TIER_4 to TierPublishingLimit(maxConcurrentDrafts = null, maxMonthlyPublishes = null)
+        )
+
+        fun fromEnvironment(env: (String) -> String? = System::getenv): PublishingLimitsConfig {
+            val limits = PublishingTier.entries.associateWith { tier ->
+                val defaults = DEFAULT_LIMITS.getValue(tier)
+                val n = tier.ordinal + 1
+                TierPublishingLimit(
+                    maxConcurrentDrafts = parseLimit(env("PUBLISH_LIMIT_TIER${n}_DRAFTS"), defaults.maxConcurrentDrafts),
+                    maxMonthlyPublishes = parseLimit(env("PUBLISH_LIMIT_TIER${n}_MONTHLY"), defaults.maxMonthlyPublishes)
+                )
+            }
+            return PublishingLimitsConfig(limits)
+        }
+
+        private fun parseLimit(raw: String?, default: Int?): Int? {
+            if (raw.isNullOrBlank()) return default
+            if (raw.equals("unlimited", ignoreCase = true)) return null
+            val value = raw.toIntOrNull() ?: return default
+            return if (value < 0) null else value
+        }
+    }
+}
+
+class PublishingLimitExceededException(
+    val limitType: PublishingLimitType,
+    val tier: PublishingTier,
+    val limit: Int
+) : RuntimeException(
+    when (limitType) {
+        PublishingLimitType.CONCURRENT_DRAFTS -> "Draft limit reached: $tier creators may have at most $limit drafts in progress"
+        PublishingLimitType.MONTHLY_PUBLISHES -> "Monthly publish limit reached: $tier creators may submit at most $limit items per month"
+    }
+)
+
+@Serializable
+data class PublishingLimitErrorResponse(
+    val error: String,
+    val limitType: String,
+    val tier: String,
+    val limit: Int
+)
+
+fun PublishingLimitExceededException.toResponse() = PublishingLimitErrorResponse(
+    error = message ?: "Publishing limit exceeded",
+    limitType = limitType.name,
+    tier = tier.name,
+    limit = limit
+)
@@ -0,0 +1,214 @@
+package com.wondernest.services.moderation
+
+import com.wondernest.utils.ContentModeration
+import kotlinx.coroutines.Dispatchers
+import kotlinx.coroutines.withContext
+import kotlinx.serialization.Serializable
+import kotlinx.serialization.json.JsonObject
+import kotlinx.serialization.json.JsonPrimitive
+import mu.KotlinLogging
+import java.net.Inet6Address
+import java.net.InetAddress
+import java.net.URI
+import java.net.http.HttpClient
+import java.net.http.HttpRequest
+import java.net.http.HttpResponse
+import java.time.Duration
+
+private val logger = KotlinLogging.logger {}
+
+enum class ContentScanCheck(val code: String) {
+    PROFANITY("profanity"),
+    PII("pii"),
+    IMAGE_LINKS("image_links");
+
+    companion object {
+        fun fromCode(code: String): ContentScanCheck =
+            entries.firstOrNull { it.code == code.trim().lowercase() }
+                ?: throw IllegalArgumentException(
+                    "Unknown content scan check '$code'. Expected one of: ${entries.joinToString { it.code }}"
+                )
+    }
+}
+
+/**
+ * Something an automated check objected to, in [field] of the submission's content data
+ */
+@Serializable
+data class ContentScanFinding(
+    val check: String,
+    val field: String,
+    val message: String
+)
+
+@Serializable
+data class ContentValidationResult(
+    val passed: Boolean,
+    val findings: List<ContentScanFinding> = emptyList()
+)
+
+/**
+ * One automated first-pass check over a submission's content data
+ */
+interface ContentSafetyCheck {
+    val check: ContentScanCheck
+    suspend fun scan(contentData: Map<String, String>): List<ContentScanFinding>
+}
+
+/**
+ * Whole-word match against the banned word list, case-insensitive
+ */
+class BannedWordsCheck(
+    private val bannedWords: Set<String> = ContentModeration.PROFANITY
+) : ContentSafetyCheck {
+    override val check = ContentScanCheck.PROFANITY
+
+    override suspend fun scan(contentData: Map<String, String>) = contentData.mapNotNull { (field, value) ->
+        val words = value.lowercase().split(Regex("[^a-z]+"))
+        // The matched words aren't echoed back; the field is enough to find them
+        if (words.any { it in bannedWords }) {
+            ContentScanFinding(check.code, field, "Contains language that isn't allowed for children")
+        } else {
+            null
+        }
+    }
+}
+
+class PiiCheck(
+    private val scanner: PiiScanner = PiiScanner(PiiScannerConfig())
+) : ContentSafetyCheck {
+    override val check = ContentScanCheck.PII
+
+    override suspend fun scan(contentData: Map<String, String>): List<ContentScanFinding> {
+        val document = JsonObject(contentData.mapValues { JsonPrimitive(it.value) })
+        return scanner.scan(document).map { finding ->
+            ContentScanFinding(
+                check.code,
+                finding.path.removePrefix("$."),
+                "Appears to contain personal information (${finding.category.name.lowercase().replace('_', ' ')})"
+            )
+        }
+    }
+}
+
+/**
+ * Image URLs in the content must resolve. [probe] returns whether a URL answered successfully.
+ * The default probe only contacts hosts that resolve to public addresses and doesn't follow
+ * redirects, so a submission can't point the server at its own network.
+ */
+class ImageLinkCheck(
+    private val probe: suspend (URI) -> Boolean = ::headRequest
+) : ContentSafetyCheck {
+    override val check = ContentScanCheck.IMAGE_LINKS
+
+    override suspend fun scan(contentData: Map<String, String>) = contentData.mapNotNull { (field, value) ->
+        val uri = imageUri(value) ?: return@mapNotNull null
+        val reachable = try {
+            probe(uri)
+        } catch (e: Exception) {
+            logger.debug(e) { "Image link $uri in field $field could not be fetched" }
+            false
+        }
+        if (reachable) null else ContentScanFinding(check.code, field, "Image link could not be reached")
+    }
+
+    companion object {
+        private val IMAGE_EXTENSIONS = setOf("png", "jpg", "jpeg", "gif", "webp", "svg")
+        private val client by lazy {
+            HttpClient.newBuilder()
+                .connectTimeout(Duration.ofSeconds(5))
+                .followRedirects(HttpClient.Redirect.NEVER)
+                .build()
+        }
+
+        fun imageUri(value: String): URI? {
+            val uri = runCatching { URI(value.trim()) }.getOrNull() ?: return null
+            if (uri.scheme != "http" && uri.scheme != "https" || uri.host.isNullOrEmpty()) return null
+            val extension = uri.path.orEmpty().substringAfterLast('.', "").lowercase()
+            return uri.takeIf { extension in IMAGE_EXTENSIONS }
+        }
+
+        /**
+         * False for loopback, private, link-local, carrier-grade NAT, unique-local, wildcard and
+         * multicast addresses
+         */
+        fun isPublicAddress(address: InetAddress): Boolean {
+            if (address.isLoopbackAddress || address.isSiteLocalAddress || address.isLinkLocalAddress ||
+                address.isAnyLocalAddress || address.isMulticastAddress
+            ) {
+                return false
+            }
+            val bytes = address.address
+            return when (address) {
+                // fc00::/7
+                is Inet6Address -> (bytes[0].toInt() and 0xFE) != 0xFC
+                // 100.64.0.0/10
+                else -> !((bytes[0].toInt() and 0xFF) == 100 && (bytes[1].toInt() and 0xC0) == 64)
+            }
+        }
+
+        // Blocking DNS and HTTP, so both run on the IO dispatcher rather than the request's thread
+        private suspend fun headRequest(uri: URI): Boolean = withContext(Dispatchers.IO) {
+            val addresses = InetAddress.getAllByName(uri.host)
+            if (!addresses.all(::isPublicAddress)) {
+                logger.warn { "Image link host ${uri.host} resolves to a non-public address; not fetching it" }
+                return@withContext false
+            }
+            val request = HttpRequest.newBuilder(uri)
+                .method("HEAD", HttpRequest.BodyPublishers.noBody())
+                .timeout(Duration.ofSeconds(5))
+                .build()
+            client.send(request, HttpResponse.BodyHandlers.discarding()).statusCode() in 200..399
+        }
+    }
+}
+
+data class ContentScanConfig(
+    val checks: Set<ContentScanCheck> = DEFAULT_CHECKS,
+    val extraBannedWords: Set<String> = emptySet()
+) {
+    companion object {
+        // Image links are opt-in since checking them makes outbound requests during submission
+        val DEFAULT_CHECKS = setOf(ContentScanCheck.PROFANITY, ContentScanCheck.PII)
+
+        /**
+         * Reads CONTENT_SCAN_CHECKS (comma separated: profanity, pii, image_links) and
+         * CONTENT_SCAN_EXTRA_BANNED_WORDS (comma separated)
+         */
+        fun fromEnvironment(env: Map<String, String> = System.getenv()) = ContentScanConfig(
+            checks = env["CONTENT_SCAN_CHECKS"]?.takeIf { it.isNotBlank() }
+                ?.split(',')
+                ?.filter { it.isNotBlank() }
+                ?.map { ContentScanCheck.fromCode(it) }
+                ?.toSet()
+                ?: DEFAULT_CHECKS,
+            extraBannedWords = env["CONTENT_SCAN_EXTRA_BANNED_WORDS"].orEmpty()
+                .split(',')
+                .map { it.trim().lowercase() }
+                .filter { it.isNotEmpty() }
+                .toSet()
+        )
+    }
+}
+
+/**
+ * Automated first pass over creator submissions before they reach a human moderator.
+ * Submissions with any finding go back to the creator instead of into the review queue.
+ */
+class AutomatedContentScanner(private val checks: List<ContentSafetyCheck>) {
+
+    constructor(config: ContentScanConfig = ContentScanConfig()) : this(
+        config.checks.map { check ->
+            when (check) {
+                ContentScanCheck.PROFANITY -> BannedWordsCheck(ContentModeration.PROFANITY + config.extraBannedWords)
+                ContentScanCheck.PII -> PiiCheck()
+                ContentScanCheck.IMAGE_LINKS -> ImageLinkCheck()
+            }
+        }
+    )
+
+    suspend fun scan(contentData: Map<String, String>): ContentValidationResult {
+        val findings = checks.flatMap { it.scan(contentData) }
+        return ContentValidationResult(passed = findings.isEmpty(), findings = findings)
+    }
+}
@@ -0,0 +1,145 @@
+package com.wondernest.services.moderation
+
+import com.wondernest.data.database.table.ContentReports
+import kotlinx.datetime.toKotlinInstant
+import mu.KotlinLogging
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.eq
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.inList
+import org.jetbrains.exposed.sql.count
+import org.jetbrains.exposed.sql.deleteWhere
+import org.jetbrains.exposed.sql.select
+import org.jetbrains.exposed.sql.transactions.transaction
+import org.jetbrains.exposed.sql.upsert
+import java.time.Instant
+import java.util.UUID
+import java.util.concurrent.ConcurrentHashMap
+
+private val logger = KotlinLogging.logger {}
+
+data class ContentReport(
+    val reporterFamilyId: UUID,
+    val reason: String,
+    val reportedAt: Instant
+)
+
+/**
+ * @param reportThreshold active reports at which content stops being recommended or listed
+ */
+data class ContentFlagConfig(
+    val reportThreshold: Int = DEFAULT_REPORT_THRESHOLD
+) {
+    companion object {
+        const val DEFAULT_REPORT_THRESHOLD = 3
+
+        fun fromEnvironment(env: Map<String, String> = System.getenv()) = ContentFlagConfig(
+            reportThreshold = env["CONTENT_REPORT_EXCLUSION_THRESHOLD"]?.toIntOrNull()?.takeIf { it > 0 }
+                ?: DEFAULT_REPORT_THRESHOLD
+        )
+    }
+}
+
+/**
+ * Where family reports are kept
+ */
+interface ContentReportStore {
+    /**
+     * Store [report] against [contentId], replacing that family's earlier report.
+     * Returns the number of active reports for the item.
+     */
+    fun upsert(contentId: UUID, report: ContentReport): Int
+
+    fun clear(contentId: UUID)
+
+    /** Active report counts for the items that have any; unreported items are left out */
+    fun counts(contentIds: Collection<UUID>): Map<UUID, Int>
+}
+
+object DatabaseContentReportStore : ContentReportStore {
+    override fun upsert(contentId: UUID, report: ContentReport): Int = transaction {
+        // The (content_id, reporter_family_id) key keeps a family to one report per item
+        ContentReports.upsert(keys = arrayOf(ContentReports.contentId, ContentReports.reporterFamilyId)) {
+            it[ContentReports.contentId] = contentId
+            it[ContentReports.reporterFamilyId] = report.reporterFamilyId
+            it[ContentReports.reason] = report.reason
+            it[ContentReports.reportedAt] = report.reportedAt.toKotlinInstant()
+        }
+        ContentReports.select { ContentReports.contentId eq contentId }.count().toInt()
+    }
+
+    override fun clear(contentId: UUID) {
+        transaction { ContentReports.deleteWhere { ContentReports.contentId eq contentId } }
+    }
+
+    override fun counts(contentIds: Collection<UUID>): Map<UUID, Int> = transaction {
+        if (contentIds.isEmpty()) return@transaction emptyMap()
+        val reports = ContentReports.reporterFamilyId.count()
+        ContentReports.slice(ContentReports.contentId, reports)
+            .select { ContentReports.contentId inList contentIds }
+            .groupBy(ContentReports.contentId)
+            .associate { it[ContentReports.contentId] to it[reports].toInt() }
+    }
+}
+
+/**
+ * For tests and local runs without a database
+ */
+class InMemoryContentReportStore : ContentReportStore {
+    // Reports per content item, then per reporting family
+    private val reports = ConcurrentHashMap<UUID, MutableMap<UUID, ContentReport>>()
+
+    override fun upsert(contentId: UUID, report: ContentReport): Int {
+        val itemReports = reports.computeIfAbsent(contentId) { ConcurrentHashMap() }
+        itemReports[report.reporterFamilyId] = report
+        return itemReports.size
+    }
+
+    override fun clear(contentId: UUID) {
+        reports.remove(contentId)
+    }
+
+    override fun counts(contentIds: Collection<UUID>): Map<UUID, Int> =
+        contentIds.mapNotNull { id -> reports[id]?.size?.takeIf { it > 0 }?.let { id to it } }.toMap()
+}
+
+/**
+ * Tracks family reports for marketplace items and content packs.
+ * Discovery surfaces (search, featured, recommendations) use [filterVisible] so reported
+ * content is hidden consistently even while it is still published. Submissions awaiting
+ * review are hidden by [com.wondernest.services.marketplace.CreatorService] instead.
+ */
+class ContentFlagService(
+    private val config: ContentFlagConfig = ContentFlagConfig.fromEnvironment(),
+    private val store: ContentReportStore = InMemoryContentReportStore()
+) {
+    /**
+     * Record a report. Each family counts once per item; reporting again updates the reason.
+     * Returns the number of active reports for the item.
+     */
+    fun reportContent(contentId: UUID, reporterFamilyId: UUID, reason: String): Int {
+        require(reason.isNotBlank()) { "A reason is required" }
+        val count = store.upsert(contentId, ContentReport(reporterFamilyId, reason.trim(), Instant.now()))
+
+        if (count >= config.reportThreshold) {
+            logger.warn { "Content $contentId reached $count reports, excluding from discovery" }
+        }
+        return count
+    }
+
+    /**
+     * Moderator cleared the content: drops active reports so it can be recommended again
+     */
+    fun clear(contentId: UUID) {
+        store.clear(contentId)
+    }
+
+    fun activeReportCount(contentId: UUID): Int = store.counts(listOf(contentId))[contentId] ?: 0
+
+    fun isExcluded(contentId: UUID): Boolean = activeReportCount(contentId) >= config.reportThreshold
+
+    // One lookup for the whole page rather than one per item
+    fun <T> filterVisible(items: List<T>, idOf: (T) -> UUID): List<T> {
+        if (items.isEmpty()) return items
+        val counts = store.counts(items.map(idOf).distinct())
+        return items.filterNot { (counts[idOf(it)] ?: 0) >= config.reportThreshold }
+    }
+}
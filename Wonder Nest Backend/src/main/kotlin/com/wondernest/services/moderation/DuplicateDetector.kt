@@ -0,0 +1,211 @@
+package com.wondernest.services.moderation
+
+import com.wondernest.data.database.table.ContentFingerprints
+import kotlinx.datetime.Clock
+import kotlinx.serialization.Serializable
+import mu.KotlinLogging
+import org.jetbrains.exposed.sql.selectAll
+import org.jetbrains.exposed.sql.transactions.transaction
+import org.jetbrains.exposed.sql.upsert
+import java.security.MessageDigest
+import java.util.UUID
+import java.util.concurrent.ConcurrentHashMap
+
+private val logger = KotlinLogging.logger {}
+
+/**
+ * @param titleThreshold title similarity (0-1) at which titles are considered near-identical
+ * @param vocabularyThreshold vocabulary overlap (0-1) at which bodies are considered near-identical
+ * @param scoreThreshold combined score at which a match is flagged
+ */
+data class DuplicateDetectionConfig(
+    val titleThreshold: Double = 0.85,
+    val vocabularyThreshold: Double = 0.8,
+    val scoreThreshold: Double = 0.8
+) {
+    companion object {
+        fun fromEnvironment(env: Map<String, String> = System.getenv()): DuplicateDetectionConfig {
+            val defaults = DuplicateDetectionConfig()
+            fun threshold(name: String, default: Double) =
+                env[name]?.toDoubleOrNull()?.takeIf { it in 0.0..1.0 } ?: default
+            return DuplicateDetectionConfig(
+                titleThreshold = threshold("DUPLICATE_TITLE_THRESHOLD", defaults.titleThreshold),
+                vocabularyThreshold = threshold("DUPLICATE_VOCABULARY_THRESHOLD", defaults.vocabularyThreshold),
+                scoreThreshold = threshold("DUPLICATE_SCORE_THRESHOLD", defaults.scoreThreshold)
+            )
+        }
+    }
+}
+
+/**
+ * Normalized view of a piece of content used for duplicate comparison
+ */
+data class ContentFingerprint(
+    val itemId: UUID,
+    val normalizedTitle: String,
+    val contentHash: String,
+    val vocabulary: Set<String>
+) {
+    companion object {
+        private val WORD = Regex("[\\p{L}\\p{N}]+")
+        private const val MIN_WORD_LENGTH = 3
+
+        fun of(itemId: UUID, title: String, body: String): ContentFingerprint {
+            val normalizedBody = normalize(body)
+            return ContentFingerprint(
+                itemId = itemId,
+                normalizedTitle = normalize(title),
+                contentHash = sha256(normalizedBody),
+                vocabulary = WORD.findAll(normalizedBody).map { it.value }.filter { it.length >= MIN_WORD_LENGTH }.toSet()
+            )
+        }
+
+        private fun normalize(text: String): String =
+            WORD.findAll(text.lowercase()).joinToString(" ") { it.value }
+
+        private fun sha256(text: String): String =
+            MessageDigest.getInstance("SHA-256").digest(text.toByteArray())
+                .joinToString("") { "%02x".format(it) }
+    }
+}
+
+@Serializable
+data class DuplicateMatch(
+    val itemId: String,
+    val titleSimilarity: Double,
+    val vocabularyOverlap: Double,
+    val identicalContent: Boolean,
+    val score: Double
+)
+
+/**
+ * Fingerprints of submitted content that new submissions are compared against
+ */
+interface ContentFingerprintIndex {
+    fun all(): List<ContentFingerprint>
+
+    /** Add [fingerprint], replacing any earlier one for the same item */
+    fun register(fingerprint: ContentFingerprint)
+}
+
+object DatabaseContentFingerprintIndex : ContentFingerprintIndex {
+    override fun all(): List<ContentFingerprint> = transaction {
+        ContentFingerprints.selectAll().map {
+            ContentFingerprint(
+                itemId = it[ContentFingerprints.itemId],
+                normalizedTitle = it[ContentFingerprints.normalizedTitle],
+                contentHash = it[ContentFingerprints.contentHash],
+                vocabulary = it[ContentFingerprints.vocabulary].toSet()
+            )
+        }
+    }
+
+    override fun register(fingerprint: ContentFingerprint) {
+        transaction {
+            ContentFingerprints.upsert(keys = arrayOf(ContentFingerprints.itemId)) {
+                it[itemId] = fingerprint.itemId
+                it[normalizedTitle] = fingerprint.normalizedTitle
+                it[contentHash] = fingerprint.contentHash
+                it[vocabulary] = fingerprint.vocabulary.sorted()
+                it[registeredAt] = Clock.System.now()
+            }
+        }
+    }
+}
+
+/**
+ * For tests and local runs without a database
+ */
+class InMemoryContentFingerprintIndex : ContentFingerprintIndex {
+    private val fingerprints = ConcurrentHashMap<UUID, ContentFingerprint>()
+
+    override fun all(): List<ContentFingerprint> = fingerprints.values.toList()
+
+    override fun register(fingerprint: ContentFingerprint) {
+        fingerprints[fingerprint.itemId] = fingerprint
+    }
+}
+
+/**
+ * Flags likely duplicates among registered content using title similarity,
+ * an exact content hash and vocabulary overlap.
+ */
+class DuplicateDetector(
+    private val config: DuplicateDetectionConfig = DuplicateDetectionConfig.fromEnvironment(),
+    private val index: ContentFingerprintIndex = InMemoryContentFingerprintIndex()
+) {
+    /**
+     * Likely duplicates of [candidate] among registered content, best match first
+     */
+    fun findDuplicates(candidate: ContentFingerprint): List<DuplicateMatch> =
+        index.all()
+            .filter { it.itemId != candidate.itemId }
+            .mapNotNull { compare(candidate, it) }
+            .sortedByDescending { it.score }
+
+    /**
+     * Find duplicates and then add the candidate to the index
+     */
+    fun checkAndRegister(candidate: ContentFingerprint): List<DuplicateMatch> {
+        val matches = findDuplicates(candidate)
+        if (matches.isNotEmpty()) {
+            logger.info { "Content ${candidate.itemId} flagged as possible duplicate of ${matches.map { it.itemId }}" }
+        }
+        index.register(candidate)
+        return matches
+    }
+
+    private fun compare(candidate: ContentFingerprint, existing: ContentFingerprint): DuplicateMatch? {
+        val identical = candidate.vocabulary.isNotEmpty() && candidate.contentHash == existing.contentHash
+        val title = titleSimilarity(candidate.normalizedTitle, existing.normalizedTitle)
+        val vocabulary = jaccard(candidate.vocabulary, existing.vocabulary)
+        val score = if (identical) 1.0 else TITLE_WEIGHT * title + (1 - TITLE_WEIGHT) * vocabulary
+
+        val flagged = identical ||
+            score >= config.scoreThreshold ||
+            (title >= config.titleThreshold && vocabulary >= config.vocabularyThreshold)
+        if (!flagged) return null
+
+        return DuplicateMatch(
+            itemId = existing.itemId.toString(),
+            titleSimilarity = title.round(),
+            vocabularyOverlap = vocabulary.round(),
+            identicalContent = identical,
+            score = score.round()
+        )
+    }
+
+    companion object {
+        private const val TITLE_WEIGHT = 0.4
+
+        /**
+         * 1 - normalized Levenshtein distance
+         */
+        fun titleSimilarity(a: String, b: String): Double {
+            if (a.isEmpty() && b.isEmpty()) return 1.0
+            val longest = maxOf(a.length, b.length)
+            return 1.0 - levenshtein(a, b).toDouble() / longest
+        }
+
+        fun jaccard(a: Set<String>, b: Set<String>): Double {
+            if (a.isEmpty() || b.isEmpty()) return 0.0
+            return (a intersect b).size.toDouble() / (a union b).size
+        }
+
+        private fun levenshtein(a: String, b: String): Int {
+            var previous = IntArray(b.length + 1) { it }
+            for (i in 1..a.length) {
+                val current = IntArray(b.length + 1)
+                current[0] = i
+                for (j in 1..b.length) {
+                    val cost = if (a[i - 1] == b[j - 1]) 0 else 1
+                    current[j] = minOf(current[j - 1] + 1, previous[j] + 1, previous[j - 1] + cost)
+                }
+                previous = current
+            }
+            return previous[b.length]
+        }
+
+        private fun Double.round(): Double = Math.round(this * 1000) / 1000.0
+    }
+}
@@ -0,0 +1,177 @@
+package com.wondernest.services.moderation
+
+import com.wondernest.data.database.table.ModerationDecisions
+import kotlinx.datetime.toJavaInstant
+import kotlinx.datetime.toKotlinInstant
+import kotlinx.serialization.Serializable
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.greaterEq
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.less
+import org.jetbrains.exposed.sql.and
+import org.jetbrains.exposed.sql.insert
+import org.jetbrains.exposed.sql.select
+import org.jetbrains.exposed.sql.transactions.transaction
+import java.time.Duration
+import java.time.Instant
+import java.time.LocalDate
+import java.time.ZoneOffset
+import java.time.temporal.ChronoUnit
+import java.util.UUID
+import java.util.concurrent.CopyOnWriteArrayList
+
+/**
+ * A moderator's decision on a submission, kept for workload and quality reporting
+ */
+data class ContentModerationDecision(
+    val id: UUID = UUID.randomUUID(),
+    val itemId: UUID,
+    val moderatorId: UUID,
+    val outcome: ModerationOutcome,
+    val submittedAt: Instant,
+    val decidedAt: Instant,
+    val appealed: Boolean = false,
+    val overturnedOnAppeal: Boolean = false
+)
+
+/**
+ * Record of moderation decisions
+ */
+interface ModerationDecisionLog {
+    fun record(decision: ContentModerationDecision)
+
+    /** Decisions made in [from, until) */
+    fun decidedBetween(from: Instant, until: Instant): List<ContentModerationDecision>
+}
+
+object DatabaseModerationDecisionLog : ModerationDecisionLog {
+    override fun record(decision: ContentModerationDecision) {
+        transaction {
+            ModerationDecisions.insert {
+                it[id] = decision.id
+                it[itemId] = decision.itemId
+                it[moderatorId] = decision.moderatorId
+                it[outcome] = decision.outcome.name.lowercase()
+                it[submittedAt] = decision.submittedAt.toKotlinInstant()
+                it[decidedAt] = decision.decidedAt.toKotlinInstant()
+                it[appealed] = decision.appealed
+                it[overturnedOnAppeal] = decision.overturnedOnAppeal
+            }
+        }
+    }
+
+    override fun decidedBetween(from: Instant, until: Instant): List<ContentModerationDecision> = transaction {
+        ModerationDecisions.select {
+            (ModerationDecisions.decidedAt greaterEq from.toKotlinInstant()) and
+                (ModerationDecisions.decidedAt less until.toKotlinInstant())
+        }.map { row ->
+            ContentModerationDecision(
+                id = row[ModerationDecisions.id].value,
+                itemId = row[ModerationDecisions.itemId],
+                moderatorId = row[ModerationDecisions.moderatorId],
+                outcome = ModerationOutcome.valueOf(row[ModerationDecisions.outcome].uppercase()),
+                submittedAt = row[ModerationDecisions.submittedAt].toJavaInstant(),
+                decidedAt = row[ModerationDecisions.decidedAt].toJavaInstant(),
+                appealed = row[ModerationDecisions.appealed],
+                overturnedOnAppeal = row[ModerationDecisions.overturnedOnAppeal]
+            )
+        }
+    }
+}
+
+/**
+ * For tests and local runs without a database
+ */
+class InMemoryModerationDecisionLog : ModerationDecisionLog {
+    private val decisions = CopyOnWriteArrayList<ContentModerationDecision>()
+
+    override fun record(decision: ContentModerationDecision) {
+        decisions.add(decision)
+    }
+
+    override fun decidedBetween(from: Instant, until: Instant): List<ContentModerationDecision> =
+        decisions.filter { !it.decidedAt.isBefore(from) && it.decidedAt.isBefore(until) }
+}
+
+@Serializable
+data class ModeratorWorkload(
+    val moderatorId: String,
+    val decisions: Int,
+    val decisionsPerDay: Double,
+    val averageTimeToDecisionMinutes: Double,
+    val approvals: Int,
+    val rejections: Int,
+    val approvalRatio: Double,
+    val rejectionRatio: Double,
+    val appealed: Int,
+    val overturnedOnAppeal: Int,
+    val overturnRate: Double? // null when none of the moderator's decisions were appealed
+)
+
+@Serializable
+data class ModerationWorkloadReport(
+    val from: String,
+    val to: String,
+    val days: Int,
+    val totalDecisions: Int,
+    val moderators: List<ModeratorWorkload>
+)
+
+/**
+ * Per-moderator throughput and decision quality over a date range (UTC, both ends inclusive)
+ */
+class ModerationAnalyticsService(
+    private val decisionLog: ModerationDecisionLog
+) {
+
+    fun workload(from: LocalDate, to: LocalDate): ModerationWorkloadReport {
+        require(!to.isBefore(from)) { "'to' must not be before 'from'" }
+        val days = ChronoUnit.DAYS.between(from, to).toInt() + 1
+        require(days <= MAX_RANGE_DAYS) { "Date range cannot exceed $MAX_RANGE_DAYS days" }
+
+        val decisions = decisionLog.decidedBetween(
+            from.atStartOfDay().toInstant(ZoneOffset.UTC),
+            to.plusDays(1).atStartOfDay().toInstant(ZoneOffset.UTC)
+        )
+
+        val moderators = decisions.groupBy { it.moderatorId }
+            .map { (moderatorId, own) -> summarize(moderatorId, own, days) }
+            .sortedByDescending { it.decisions }
+
+        return ModerationWorkloadReport(
+            from = from.toString(),
+            to = to.toString(),
+            days = days,
+            totalDecisions = decisions.size,
+            moderators = moderators
+        )
+    }
+
+    private fun summarize(moderatorId: UUID, decisions: List<ContentModerationDecision>, days: Int): ModeratorWorkload {
+        val total = decisions.size
+        val approvals = decisions.count { it.outcome == ModerationOutcome.APPROVE }
+        val appealed = decisions.filter { it.appealed }
+        val overturned = appealed.count { it.overturnedOnAppeal }
+        val averageMinutes = decisions
+            .map { Duration.between(it.submittedAt, it.decidedAt).toMillis().coerceAtLeast(0) / 60_000.0 }
+            .average()
+
+        return ModeratorWorkload(
+            moderatorId = moderatorId.toString(),
+            decisions = total,
+            decisionsPerDay = (total.toDouble() / days).round(),
+            averageTimeToDecisionMinutes = averageMinutes.round(),
+            approvals = approvals,
+            rejections = total - approvals,
+            approvalRatio = (approvals.toDouble() / total).round(),
+            rejectionRatio = ((total - approvals).toDouble() / total).round(),
+            appealed = appealed.size,
+            overturnedOnAppeal = overturned,
+            overturnRate = if (appealed.isEmpty()) null else (overturned.toDouble() / appealed.size).round()
+        )
+    }
+
+    private fun Double.round(): Double = Math.round(this * 100) / 100.0
+
+    companion object {
+        const val MAX_RANGE_DAYS = 366
+    }
+}
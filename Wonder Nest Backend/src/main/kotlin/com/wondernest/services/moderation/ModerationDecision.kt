@@ -0,0 +1,97 @@
+package com.wondernest.services.moderation
+
+import kotlinx.serialization.Serializable
+
+/**
+ * Why a submission was rejected. Categories with [requiresNote] must come with notes
+ * to the creator so the rejection is actionable.
+ */
+enum class RejectionReasonCategory(
+    val code: String,
+    val label: String,
+    val requiresNote: Boolean,
+    val creatorGuidance: String
+) {
+    SAFETY(
+        "safety", "Child safety", true,
+        "Content includes material that is not safe for children"
+    ),
+    QUALITY(
+        "quality", "Quality", true,
+        "Content does not meet our quality standards"
+    ),
+    METADATA(
+        "metadata", "Metadata", false,
+        "Title, description, tags or thumbnail need to be corrected"
+    ),
+    AGE_MISMATCH(
+        "age_mismatch", "Age mismatch", false,
+        "The selected age range does not match the content"
+    ),
+    COPYRIGHT(
+        "copyright", "Copyright", true,
+        "Content appears to use material you may not have rights to"
+    );
+
+    fun toInfo() = RejectionReasonInfo(code, label, requiresNote, creatorGuidance)
+
+    companion object {
+        fun fromCode(code: String): RejectionReasonCategory =
+            entries.firstOrNull { it.code == code.lowercase() }
+                ?: throw IllegalArgumentException(
+                    "Unknown rejection reason '$code'. Expected one of: ${entries.joinToString { it.code }}"
+                )
+
+        fun taxonomy(): List<RejectionReasonInfo> = entries.map { it.toInfo() }
+    }
+}
+
+enum class ModerationOutcome {
+    APPROVE,
+    REJECT
+}
+
+@Serializable
+data class RejectionReasonInfo(
+    val code: String,
+    val label: String,
+    val requiresNote: Boolean,
+    val creatorGuidance: String
+)
+
+@Serializable
+data class ModerationDecisionRequest(
+    val decision: String, // approve, reject
+    val reasonCategory: String? = null,
+    val notes: String? = null
+)
+
+/**
+ * A decision that has passed [ModerationDecisionRequest.validate]
+ */
+data class ModerationDecision(
+    val outcome: ModerationOutcome,
+    val reasonCategory: RejectionReasonCategory?,
+    val notes: String?
+)
+
+/**
+ * Rejections must pick a category from the taxonomy and include notes when the category requires them
+ */
+fun ModerationDecisionRequest.validate(): ModerationDecision {
+    val outcome = ModerationOutcome.entries.firstOrNull { it.name.equals(decision, ignoreCase = true) }
+        ?: throw IllegalArgumentException("Decision must be 'approve' or 'reject'")
+    val trimmedNotes = notes?.trim()?.takeIf { it.isNotEmpty() }
+
+    if (outcome == ModerationOutcome.APPROVE) {
+        require(reasonCategory == null) { "Approvals do not take a rejection reason" }
+        return ModerationDecision(outcome, null, trimmedNotes)
+    }
+
+    val category = reasonCategory?.let { RejectionReasonCategory.fromCode(it) }
+        ?: throw IllegalArgumentException("A rejection reason category is required")
+    require(!category.requiresNote || trimmedNotes != null) {
+        "Notes to the creator are required for '${category.code}' rejections"
+    }
+    return ModerationDecision(outcome, category, trimmedNotes)
+}
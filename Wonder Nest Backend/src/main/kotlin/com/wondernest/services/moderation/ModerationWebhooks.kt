@@ -0,0 +1,313 @@
+package com.wondernest.services.moderation
+
+import com.wondernest.data.database.table.ModerationWebhookDeadLetters
+import io.ktor.client.HttpClient
+import io.ktor.client.engine.cio.CIO
+import io.ktor.client.plugins.HttpTimeout
+import io.ktor.client.request.header
+import io.ktor.client.request.post
+import io.ktor.client.request.setBody
+import io.ktor.http.ContentType
+import io.ktor.http.contentType
+import kotlinx.coroutines.CoroutineScope
+import kotlinx.coroutines.Dispatchers
+import kotlinx.coroutines.SupervisorJob
+import kotlinx.coroutines.delay
+import kotlinx.coroutines.launch
+import kotlinx.datetime.toJavaInstant
+import kotlinx.datetime.toKotlinInstant
+import kotlinx.serialization.Serializable
+import kotlinx.serialization.encodeToString
+import kotlinx.serialization.json.Json
+import mu.KotlinLogging
+import org.jetbrains.exposed.sql.SortOrder
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.eq
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.isNull
+import org.jetbrains.exposed.sql.and
+import org.jetbrains.exposed.sql.insert
+import org.jetbrains.exposed.sql.select
+import org.jetbrains.exposed.sql.transactions.transaction
+import org.jetbrains.exposed.sql.update
+import java.net.URI
+import java.net.URISyntaxException
+import java.time.Instant
+import java.util.UUID
+import java.util.concurrent.ConcurrentHashMap
+import javax.crypto.Mac
+import javax.crypto.spec.SecretKeySpec
+
+private val logger = KotlinLogging.logger {}
+
+enum class ModerationEventType(val value: String) {
+    SUBMITTED("submission.submitted"),
+    APPROVED("submission.approved"),
+    REJECTED("submission.rejected")
+}
+
+/**
+ * Body POSTed to moderation webhook endpoints
+ */
+@Serializable
+data class ModerationWebhookEvent(
+    val id: String,
+    val type: String,
+    val itemId: String,
+    val title: String,
+    val occurredAt: String,
+    val moderatorId: String? = null,
+    val reasonCategory: String? = null
+) {
+    companion object {
+        fun of(
+            type: ModerationEventType,
+            itemId: UUID,
+            title: String,
+            occurredAt: Instant,
+            moderatorId: UUID? = null,
+            reasonCategory: RejectionReasonCategory? = null
+        ) = ModerationWebhookEvent(
+            id = UUID.randomUUID().toString(),
+            type = type.value,
+            itemId = itemId.toString(),
+            title = title,
+            occurredAt = occurredAt.toString(),
+            moderatorId = moderatorId?.toString(),
+            reasonCategory = reasonCategory?.code
+        )
+    }
+}
+
+data class ModerationWebhookConfig(
+    val endpoints: List<URI> = emptyList(),
+    val secret: String? = null,
+    val maxRetries: Int = DEFAULT_MAX_RETRIES,
+    val baseDelayMillis: Long = DEFAULT_BASE_DELAY_MILLIS,
+    val maxDelayMillis: Long = DEFAULT_MAX_DELAY_MILLIS
+) {
+    init {
+        require(endpoints.isEmpty() || !secret.isNullOrBlank()) {
+            "MODERATION_WEBHOOK_SECRET is required when moderation webhook URLs are configured"
+        }
+    }
+
+    companion object {
+        const val DEFAULT_MAX_RETRIES = 3
+        const val DEFAULT_BASE_DELAY_MILLIS = 500L
+        const val DEFAULT_MAX_DELAY_MILLIS = 30_000L
+
+        /**
+         * Reads MODERATION_WEBHOOK_URLS (comma separated), MODERATION_WEBHOOK_SECRET and
+         * MODERATION_WEBHOOK_MAX_RETRIES. No URLs means webhooks are off.
+         */
+        fun fromEnvironment(env: Map<String, String> = System.getenv()) = ModerationWebhookConfig(
+            endpoints = env["MODERATION_WEBHOOK_URLS"].orEmpty()
+                .split(',')
+                .map { it.trim() }
+                .filter { it.isNotEmpty() }
+                .map(::parseEndpoint),
+            secret = env["MODERATION_WEBHOOK_SECRET"]?.takeIf { it.isNotBlank() },
+            maxRetries = env["MODERATION_WEBHOOK_MAX_RETRIES"]?.toIntOrNull()?.takeIf { it >= 0 }
+                ?: DEFAULT_MAX_RETRIES
+        )
+
+        fun parseEndpoint(value: String): URI {
+            val uri = try {
+                URI(value)
+            } catch (e: URISyntaxException) {
+                throw IllegalArgumentException("Invalid moderation webhook URL '$value': ${e.message}")
+            }
+            require((uri.scheme == "http" || uri.scheme == "https") && !uri.host.isNullOrEmpty()) {
+                "Invalid moderation webhook URL '$value': expected an absolute http(s) URL"
+            }
+            return uri
+        }
+    }
+}
+
+/**
+ * A delivery that ran out of retries, kept so it can be inspected and replayed
+ */
+data class FailedWebhookDelivery(
+    val endpoint: URI,
+    val event: ModerationWebhookEvent,
+    val attempts: Int,
+    val lastError: String,
+    val failedAt: Instant,
+    val id: UUID = UUID.randomUUID()
+)
+
+/**
+ * Dead-letter log for webhook deliveries
+ */
+interface ModerationWebhookDeadLetterLog {
+    fun record(delivery: FailedWebhookDelivery)
+
+    /** Deliveries not yet replayed, oldest first */
+    fun pending(limit: Int): List<FailedWebhookDelivery>
+
+    /** False when [id] is unknown or was already replayed */
+    fun markReplayed(id: UUID, now: Instant): Boolean
+}
+
+object DatabaseModerationWebhookDeadLetterLog : ModerationWebhookDeadLetterLog {
+    override fun record(delivery: FailedWebhookDelivery) {
+        transaction {
+            ModerationWebhookDeadLetters.insert {
+                it[id] = delivery.id
+                it[endpoint] = delivery.endpoint.toString()
+                it[event] = delivery.event
+                it[attempts] = delivery.attempts
+                it[lastError] = delivery.lastError
+                it[failedAt] = delivery.failedAt.toKotlinInstant()
+            }
+        }
+    }
+
+    override fun pending(limit: Int): List<FailedWebhookDelivery> = transaction {
+        ModerationWebhookDeadLetters.select { ModerationWebhookDeadLetters.replayedAt.isNull() }
+            .orderBy(ModerationWebhookDeadLetters.failedAt to SortOrder.ASC)
+            .limit(limit)
+            .map { row ->
+                FailedWebhookDelivery(
+                    endpoint = URI(row[ModerationWebhookDeadLetters.endpoint]),
+                    event = row[ModerationWebhookDeadLetters.event],
+                    attempts = row[ModerationWebhookDeadLetters.attempts],
+                    lastError = row[ModerationWebhookDeadLetters.lastError],
+                    failedAt = row[ModerationWebhookDeadLetters.failedAt].toJavaInstant(),
+                    id = row[ModerationWebhookDeadLetters.id].value
+                )
+            }
+    }
+
+    override fun markReplayed(id: UUID, now: Instant): Boolean = transaction {
+        ModerationWebhookDeadLetters.update({
+            (ModerationWebhookDeadLetters.id eq id) and ModerationWebhookDeadLetters.replayedAt.isNull()
+        }) {
+            it[replayedAt] = now.toKotlinInstant()
+        } > 0
+    }
+}
+
+/**
+ * For tests and local runs without a database
+ */
+class InMemoryModerationWebhookDeadLetterLog : ModerationWebhookDeadLetterLog {
+    private val deliveries = ConcurrentHashMap<UUID, FailedWebhookDelivery>()
+    private val replayed = ConcurrentHashMap.newKeySet<UUID>()
+
+    override fun record(delivery: FailedWebhookDelivery) {
+        deliveries[delivery.id] = delivery
+    }
+
+    override fun pending(limit: Int): List<FailedWebhookDelivery> =
+        deliveries.values.filter { it.id !in replayed }.sortedBy { it.failedAt }.take(limit)
+
+    override fun markReplayed(id: UUID, now: Instant): Boolean = id in deliveries && replayed.add(id)
+}
+
+/**
+ * POSTs moderation events to every configured endpoint. Each request carries
+ * `X-WonderNest-Signature: sha256=<hex>`, an HMAC-SHA256 over `<timestamp>.<body>` keyed with the
+ * shared secret, where the timestamp is the `X-WonderNest-Timestamp` header, so receivers can
+ * reject forged or replayed calls.
+ *
+ * Network errors, 408, 429 and 5xx responses are retried with exponential backoff; other
+ * failures, and deliveries that exhaust their retries, go to the [deadLetters] log.
+ */
+class ModerationWebhookDispatcher(
+    private val config: ModerationWebhookConfig = ModerationWebhookConfig(),
+    private val deadLetters: ModerationWebhookDeadLetterLog = InMemoryModerationWebhookDeadLetterLog(),
+    private val sleep: suspend (Long) -> Unit = { delay(it) },
+    private val clock: () -> Instant = Instant::now
+) {
+    private val json = Json { encodeDefaults = true }
+    private val scope = CoroutineScope(SupervisorJob() + Dispatchers.IO)
+    private val client by lazy {
+        HttpClient(CIO) {
+            install(HttpTimeout) { requestTimeoutMillis = REQUEST_TIMEOUT_MILLIS }
+            expectSuccess = false
+        }
+    }
+
+    /**
+     * Deliver [event] in the background; moderation never waits on webhook receivers
+     */
+    fun publish(event: ModerationWebhookEvent) {
+        if (config.endpoints.isEmpty()) return
+        config.endpoints.forEach { endpoint -> scope.launch { deliver(endpoint, event) } }
+    }
+
+    /**
+     * Deliver [event] to [endpoint], retrying as configured. Returns whether it was accepted.
+     */
+    suspend fun deliver(endpoint: URI, event: ModerationWebhookEvent): Boolean {
+        val (attempts, error) = attemptDelivery(endpoint, event)
+        if (error == null) return true
+
+        logger.error {
+            "Moderation webhook ${event.type} for item ${event.itemId} to $endpoint " +
+                "failed after $attempts attempts: $error"
+        }
+        deadLetters.record(FailedWebhookDelivery(endpoint, event, attempts, error, clock()))
+        return false
+    }
+
+    /**
+     * Deliver up to [limit] dead letters again, oldest first. Ones that fail again stay in the
+     * log for a later replay. Returns how many went through.
+     */
+    suspend fun replayDeadLetters(limit: Int = DEFAULT_REPLAY_LIMIT): Int =
+        deadLetters.pending(limit).count { failed ->
+            val (_, error) = attemptDelivery(failed.endpoint, failed.event)
+            if (error != null) logger.warn { "Replaying moderation webhook ${failed.id} to ${failed.endpoint} failed: $error" }
+            error == null && deadLetters.markReplayed(failed.id, clock())
+        }
+
+    /**
+     * Returns the number of attempts made and the last error, which is null once one succeeded
+     */
+    private suspend fun attemptDelivery(endpoint: URI, event: ModerationWebhookEvent): Pair<Int, String?> {
+        val body = json.encodeToString(event)
+        var attempt = 0
+        while (true) {
+            attempt++
+            val timestamp = clock().epochSecond.toString()
+            val error = try {
+                val response = client.post(endpoint.toString()) {
+                    contentType(ContentType.Application.Json)
+                    header(EVENT_HEADER, event.type)
+                    header(TIMESTAMP_HEADER, timestamp)
+                    header(SIGNATURE_HEADER, "sha256=${sign(timestamp, body)}")
+                    setBody(body)
+                }
+                val status = response.status.value
+                if (status in 200..299) return attempt to null
+                if (status != 408 && status != 429 && status < 500) return attempt to "HTTP $status"
+                "HTTP $status"
+            } catch (e: Exception) {
+                e.message ?: e::class.simpleName ?: "request failed"
+            }
+
+            if (attempt > config.maxRetries) return attempt to error
+            val backoff = (config.baseDelayMillis shl (attempt - 1).coerceAtMost(20))
+                .coerceAtMost(config.maxDelayMillis)
+            logger.warn { "Moderation webhook to $endpoint failed ($error), retrying in ${backoff}ms" }
+            sleep(backoff)
+        }
+    }
+
+    fun sign(timestamp: String, body: String): String {
+        val mac = Mac.getInstance(ALGORITHM)
+        mac.init(SecretKeySpec(config.secret.orEmpty().toByteArray(), ALGORITHM))
+        return mac.doFinal("$timestamp.$body".toByteArray()).joinToString("") { "%02x".format(it) }
+    }
+
+    companion object {
+        const val EVENT_HEADER = "X-WonderNest-Event"
+        const val TIMESTAMP_HEADER = "X-WonderNest-Timestamp"
+        const val SIGNATURE_HEADER = "X-WonderNest-Signature"
+        private const val ALGORITHM = "HmacSHA256"
+        private const val REQUEST_TIMEOUT_MILLIS = 10_000L
+        const val DEFAULT_REPLAY_LIMIT = 100
+    }
+}
@@ -0,0 +1,62 @@
+package com.wondernest.services.moderation
+
+import com.wondernest.services.games.DatabaseGameDataBatchStore
+import com.wondernest.services.games.GameDataBatchItem
+import com.wondernest.services.games.GameDataBatchStore
+import com.wondernest.services.games.GameDataService
+import kotlinx.datetime.Clock
+import kotlinx.datetime.Instant
+import kotlinx.serialization.json.jsonObject
+import mu.KotlinLogging
+import java.util.UUID
+
+private val logger = KotlinLogging.logger {}
+
+/**
+ * The moderator side of the PII review queue: child data saves that [ChildDataPiiGuard] held
+ * back are either released, which writes them where the original save was going, or discarded.
+ */
+class PiiReviewService(
+    private val queue: PiiReviewQueueStore = DatabasePiiReviewQueueStore,
+    private val simpleStore: GameDataBatchStore = DatabaseGameDataBatchStore,
+    private val gameDataService: GameDataService = GameDataService(),
+    private val clock: () -> Instant = { Clock.System.now() }
+) {
+    companion object {
+        const val DEFAULT_PAGE_SIZE = 50
+        const val MAX_PAGE_SIZE = 200
+    }
+
+    fun pending(limit: Int = DEFAULT_PAGE_SIZE): List<PendingPiiReview> =
+        queue.pending(limit.coerceIn(1, MAX_PAGE_SIZE))
+
+    /**
+     * Write the held data and mark the entry released. Returns null when the entry doesn't exist
+     * or was already reviewed; a failed write throws and leaves it pending.
+     */
+    fun release(id: UUID, reviewerId: UUID): HeldChildData? =
+        queue.resolve(id, PiiReviewStatus.RELEASED, reviewerId, clock()) { held -> write(held) }
+            ?.also { logger.info { "PII review $id released by $reviewerId" } }
+
+    /**
+     * Drop the held data without writing it. Returns null when the entry doesn't exist or was already reviewed.
+     */
+    fun discard(id: UUID, reviewerId: UUID): HeldChildData? =
+        queue.resolve(id, PiiReviewStatus.DISCARDED, reviewerId, clock()) { }
+            ?.also { logger.info { "PII review $id discarded by $reviewerId" } }
+
+    private fun write(held: HeldChildData) {
+        when (held.target) {
+            HeldDataTarget.SIMPLE -> simpleStore.saveAll(
+                held.childId,
+                held.gameKey,
+                listOf(GameDataBatchItem(held.gameKey, held.dataKey, held.dataValue.jsonObject)),
+                clock()
+            )
+            HeldDataTarget.GAME_INSTANCE -> {
+                val result = gameDataService.saveReviewedGameData(held.childId, held.gameKey, held.dataKey, held.dataValue)
+                check(result.success) { result.message }
+            }
+        }
+    }
+}
@@ -0,0 +1,303 @@
+package com.wondernest.services.moderation
+
+import com.wondernest.data.database.table.PiiReviewQueue
+import kotlinx.datetime.Clock
+import kotlinx.datetime.Instant
+import kotlinx.serialization.json.JsonArray
+import kotlinx.serialization.json.JsonElement
+import kotlinx.serialization.json.JsonObject
+import kotlinx.serialization.json.JsonPrimitive
+import mu.KotlinLogging
+import org.jetbrains.exposed.sql.ResultRow
+import org.jetbrains.exposed.sql.SortOrder
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.eq
+import org.jetbrains.exposed.sql.and
+import org.jetbrains.exposed.sql.insertAndGetId
+import org.jetbrains.exposed.sql.select
+import org.jetbrains.exposed.sql.update
+import org.jetbrains.exposed.sql.transactions.transaction
+import java.util.UUID
+
+private val logger = KotlinLogging.logger {}
+
+enum class PiiCategory {
+    SUSPICIOUS_KEY,
+    EMAIL,
+    PHONE_NUMBER,
+    STREET_ADDRESS,
+    FULL_NAME
+}
+
+/**
+ * A place in a JSON document that looks like personal information, e.g. `$.profile.home_address`
+ */
+data class PiiFinding(
+    val path: String,
+    val category: PiiCategory
+)
+
+/**
+ * What happens to a save that contains suspected PII
+ */
+enum class PiiAction {
+    REJECT, // the save fails and the client is told which paths were flagged
+    QUEUE   // the save is held in the review queue instead of being written
+}
+
+data class PiiScannerConfig(
+    val action: PiiAction = PiiAction.REJECT,
+    val suspiciousKeys: Set<String> = DEFAULT_SUSPICIOUS_KEYS
+) {
+    companion object {
+        // Compared after lowercasing and dropping separators, so "homeAddress" matches "home_address"
+        val DEFAULT_SUSPICIOUS_KEYS = setOf(
+            "address", "homeaddress", "streetaddress", "postcode", "postalcode", "zipcode",
+            "phone", "phonenumber", "mobilenumber",
+            "email", "emailaddress",
+            "fullname", "lastname", "surname", "realname",
+            "chatmessages", "chatlog",
+            "ssn", "socialsecuritynumber", "dateofbirth", "birthdate",
+            "schoolname", "geolocation", "latitude", "longitude"
+        )
+
+        /**
+         * Reads GAME_DATA_PII_ACTION (reject/queue) and GAME_DATA_PII_EXTRA_KEYS (comma separated)
+         */
+        fun fromEnvironment(env: Map<String, String> = System.getenv()) = PiiScannerConfig(
+            action = env["GAME_DATA_PII_ACTION"]?.trim()?.uppercase()
+                ?.let { runCatching { PiiAction.valueOf(it) }.getOrNull() }
+                ?: PiiAction.REJECT,
+            suspiciousKeys = DEFAULT_SUSPICIOUS_KEYS + env["GAME_DATA_PII_EXTRA_KEYS"].orEmpty()
+                .split(',')
+                .map { normalizeKey(it) }
+                .filter { it.isNotEmpty() }
+        )
+
+        fun normalizeKey(key: String): String = key.lowercase().filter { it.isLetterOrDigit() }
+    }
+}
+
+/**
+ * Walks child game data looking for personal information children shouldn't be storing:
+ * suspicious keys anywhere in the document, and string values that look like emails,
+ * phone numbers, street addresses or a person's full name.
+ */
+class PiiScanner(
+    private val config: PiiScannerConfig = PiiScannerConfig.fromEnvironment()
+) {
+    companion object {
+        private val EMAIL = Regex("""[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}""")
+        // Not inside a longer token, so UUID segments and clock times aren't mistaken for numbers
+        private val PHONE = Regex("""(?<![\w-])\+?\d[\d\s()-]{8,}\d(?![\w:-])""")
+        private val STREET_ADDRESS = Regex(
+            """\b\d{1,5}\s+(?:[A-Za-z]+\s+){1,4}(?:street|st|avenue|ave|road|rd|lane|ln|drive|dr|boulevard|blvd|court|ct|way|place|pl)\b\.?""",
+            RegexOption.IGNORE_CASE
+        )
+        private val FULL_NAME = Regex("""^\p{Lu}\p{Ll}+(?:[\s-]+\p{Lu}\p{Ll}+)+$""")
+
+        // Only person names are checked for a full name, so a project "name" like "My Zoo Trip" is fine:
+        // keys that name a person, or a plain "name" inside an object describing one
+        private val NAME_KEYS = setOf("childname", "firstname", "parentname", "friendname", "playername", "author")
+        private val PERSON_KEYS = setOf("friend", "friends", "parent", "child", "contact", "contacts", "person", "teacher")
+
+        // Phone numbers have 10-15 digits; shorter runs are scores, timestamps and the like
+        private val PHONE_DIGITS = 10..15
+    }
+
+    fun scan(value: JsonElement): List<PiiFinding> =
+        mutableListOf<PiiFinding>().also { scan(value, "$", null, null, it) }
+
+    /**
+     * [key] is the object key [value] sits under (array items inherit it), [parentKey] the one above that
+     */
+    private fun scan(value: JsonElement, path: String, key: String?, parentKey: String?, findings: MutableList<PiiFinding>) {
+        when (value) {
+            is JsonObject -> value.forEach { (childKey, child) ->
+                val childPath = "$path.$childKey"
+                if (PiiScannerConfig.normalizeKey(childKey) in config.suspiciousKeys) {
+                    findings.add(PiiFinding(childPath, PiiCategory.SUSPICIOUS_KEY))
+                } else {
+                    scan(child, childPath, childKey, key, findings)
+                }
+            }
+            is JsonArray -> value.forEachIndexed { index, child -> scan(child, "$path[$index]", key, parentKey, findings) }
+            is JsonPrimitive -> if (value.isString) scanString(value.content, path, key, parentKey)?.let { findings.add(it) }
+        }
+    }
+
+    private fun scanString(text: String, path: String, key: String?, parentKey: String?): PiiFinding? {
+        val category = when {
+            EMAIL.containsMatchIn(text) -> PiiCategory.EMAIL
+            PHONE.findAll(text).any { match -> match.value.count { it.isDigit() } in PHONE_DIGITS } ->
+                PiiCategory.PHONE_NUMBER
+            STREET_ADDRESS.containsMatchIn(text) -> PiiCategory.STREET_ADDRESS
+            isPersonName(key, parentKey) && FULL_NAME.matches(text.trim()) -> PiiCategory.FULL_NAME
+            else -> null
+        }
+        return category?.let { PiiFinding(path, it) }
+    }
+
+    private fun isPersonName(key: String?, parentKey: String?): Boolean {
+        val normalized = key?.let { PiiScannerConfig.normalizeKey(it) } ?: return false
+        return normalized in NAME_KEYS ||
+            (normalized == "name" && parentKey != null && PiiScannerConfig.normalizeKey(parentKey) in PERSON_KEYS)
+    }
+}
+
+/**
+ * Where held child data is written if a moderator releases it
+ */
+enum class HeldDataTarget(val dbValue: String) {
+    SIMPLE("simple"),               // simple_game_data, from the plain and batch saves
+    GAME_INSTANCE("game_instance"); // per-instance game data saved through GameDataService
+
+    companion object {
+        fun fromDbValue(value: String) = entries.first { it.dbValue == value }
+    }
+}
+
+enum class PiiReviewStatus(val dbValue: String) {
+    PENDING("pending"),
+    RELEASED("released"),
+    DISCARDED("discarded")
+}
+
+/**
+ * Child data held back for a moderator because it was flagged by [PiiScanner]
+ */
+data class HeldChildData(
+    val childId: UUID,
+    val gameKey: String,
+    val dataKey: String,
+    val dataValue: JsonElement,
+    val flaggedPaths: List<String>,
+    val heldAt: Instant,
+    val target: HeldDataTarget
+)
+
+data class PendingPiiReview(
+    val id: UUID,
+    val data: HeldChildData
+)
+
+interface PiiReviewQueueStore {
+    /** Returns the queue entry's id */
+    fun enqueue(item: HeldChildData): UUID
+
+    /** Entries still waiting for a moderator, oldest first */
+    fun pending(limit: Int): List<PendingPiiReview>
+
+    /**
+     * Move a pending entry to [status] and run [apply] on it in the same transaction, so an entry
+     * whose release fails stays pending. Returns null when it doesn't exist or was already reviewed.
+     */
+    fun resolve(
+        id: UUID,
+        status: PiiReviewStatus,
+        reviewerId: UUID,
+        now: Instant,
+        apply: (HeldChildData) -> Unit
+    ): HeldChildData?
+}
+
+object DatabasePiiReviewQueueStore : PiiReviewQueueStore {
+    override fun enqueue(item: HeldChildData): UUID = transaction {
+        PiiReviewQueue.insertAndGetId {
+            it[childId] = item.childId
+            it[gameKey] = item.gameKey
+            it[dataKey] = item.dataKey
+            it[dataValue] = item.dataValue
+            it[flaggedPaths] = item.flaggedPaths
+            it[target] = item.target.dbValue
+            it[createdAt] = item.heldAt
+        }.value
+    }
+
+    override fun pending(limit: Int): List<PendingPiiReview> = transaction {
+        PiiReviewQueue.select { PiiReviewQueue.status eq PiiReviewStatus.PENDING.dbValue }
+            .orderBy(PiiReviewQueue.createdAt to SortOrder.ASC)
+            .limit(limit)
+            .map { PendingPiiReview(it[PiiReviewQueue.id].value, it.toHeldChildData()) }
+    }
+
+    override fun resolve(
+        id: UUID,
+        status: PiiReviewStatus,
+        reviewerId: UUID,
+        now: Instant,
+        apply: (HeldChildData) -> Unit
+    ): HeldChildData? = transaction {
+        // Locked so two moderators can't both act on the same entry
+        val held = PiiReviewQueue.select {
+            (PiiReviewQueue.id eq id) and (PiiReviewQueue.status eq PiiReviewStatus.PENDING.dbValue)
+        }.forUpdate().singleOrNull()?.toHeldChildData() ?: return@transaction null
+
+        PiiReviewQueue.update({ PiiReviewQueue.id eq id }) {
+            it[PiiReviewQueue.status] = status.dbValue
+            it[reviewedAt] = now
+            it[reviewedBy] = reviewerId
+        }
+        apply(held)
+        held
+    }
+
+    private fun ResultRow.toHeldChildData() = HeldChildData(
+        childId = this[PiiReviewQueue.childId].value,
+        gameKey = this[PiiReviewQueue.gameKey],
+        dataKey = this[PiiReviewQueue.dataKey],
+        dataValue = this[PiiReviewQueue.dataValue],
+        flaggedPaths = this[PiiReviewQueue.flaggedPaths],
+        heldAt = this[PiiReviewQueue.createdAt],
+        target = HeldDataTarget.fromDbValue(this[PiiReviewQueue.target])
+    )
+}
+
+sealed class PiiScreening {
+    object Clean : PiiScreening()
+    data class Rejected(val findings: List<PiiFinding>) : PiiScreening()
+    data class Held(val reviewId: UUID, val findings: List<PiiFinding>) : PiiScreening()
+
+    /**
+     * Message for the client when the save didn't go through, null when it may be written
+     */
+    fun blockedMessage(): String? = when (this) {
+        Clean -> null
+        is Rejected -> "Game data appears to contain personal information at ${findings.joinToString { it.path }}"
+        is Held -> "Game data appears to contain personal information and is held for review"
+    }
+}
+
+/**
+ * Screens a child data save before it is written, rejecting or queueing it per [PiiScannerConfig.action]
+ */
+class ChildDataPiiGuard(
+    private val config: PiiScannerConfig = PiiScannerConfig.fromEnvironment(),
+    private val queue: PiiReviewQueueStore = DatabasePiiReviewQueueStore,
+    private val clock: () -> Instant = { Clock.System.now() }
+) {
+    private val scanner = PiiScanner(config)
+
+    /**
+     * [target] is where the data would be written, so a held save can be released there later
+     */
+    fun screen(
+        childId: UUID,
+        gameKey: String,
+        dataKey: String,
+        dataValue: JsonElement,
+        target: HeldDataTarget
+    ): PiiScreening {
+        val findings = scanner.scan(dataValue)
+        if (findings.isEmpty()) return PiiScreening.Clean
+
+        // Paths only; the flagged values themselves must not end up in logs
+        logger.warn { "Suspected PII in $gameKey/$dataKey for child $childId at ${findings.map { it.path }}" }
+        return when (config.action) {
+            PiiAction.REJECT -> PiiScreening.Rejected(findings)
+            PiiAction.QUEUE -> {
+                val held = HeldChildData(childId, gameKey, dataKey, dataValue, findings.map { it.path }, clock(), target)
+                PiiScreening.Held(queue.enqueue(held), findings)
+            }
+        }
+    }
+}
@@ -0,0 +1,113 @@
+package com.wondernest.services.resilience
+
+import io.lettuce.core.SetArgs
+import io.lettuce.core.api.StatefulRedisConnection
+import kotlinx.coroutines.future.await
+import kotlinx.serialization.Serializable
+import kotlinx.serialization.encodeToString
+import kotlinx.serialization.json.Json
+import java.security.MessageDigest
+import java.util.concurrent.ConcurrentHashMap
+
+/**
+ * A response recorded for an Idempotency-Key, replayed verbatim when the key is reused
+ */
+@Serializable
+data class IdempotentResponse(
+    val status: Int,
+    val body: String,
+    val requestHash: String
+)
+
+interface IdempotencyStore {
+    suspend fun get(key: String): IdempotentResponse?
+    suspend fun put(key: String, response: IdempotentResponse, ttlSeconds: Long)
+}
+
+class RedisIdempotencyStore(
+    private val connection: () -> StatefulRedisConnection<String, String> = { RedisConnections.shared }
+) : IdempotencyStore {
+    override suspend fun get(key: String): IdempotentResponse? =
+        connection().async().get(key).await()?.let { Json.decodeFromString<IdempotentResponse>(it) }
+
+    override suspend fun put(key: String, response: IdempotentResponse, ttlSeconds: Long) {
+        connection().async().set(key, Json.encodeToString(response), SetArgs().ex(ttlSeconds)).await()
+    }
+}
+
+class InMemoryIdempotencyStore(
+    private val clock: () -> Long = System::currentTimeMillis
+) : IdempotencyStore {
+    private val entries = ConcurrentHashMap<String, Pair<IdempotentResponse, Long>>()
+
+    override suspend fun get(key: String): IdempotentResponse? {
+        val (response, expiresAt) = entries[key] ?: return null
+        if (clock() >= expiresAt) {
+            entries.remove(key)
+            return null
+        }
+        return response
+    }
+
+    override suspend fun put(key: String, response: IdempotentResponse, ttlSeconds: Long) {
+        entries[key] = response to clock() + ttlSeconds * 1000
+    }
+}
+
+/**
+ * The key was already used for a request with a different body
+ */
+class IdempotencyKeyReusedException(val key: String) :
+    IllegalStateException("Idempotency key '$key' was already used for a different request")
+
+/**
+ * Replays recorded responses for retried writes. Keys are scoped per caller and operation, and
+ * a reused key with a different request body is rejected instead of replaying the wrong result.
+ * Redis outages degrade through [RedisGuard]: the write simply runs again, as it would without a key.
+ */
+class IdempotencyService(
+    private val store: IdempotencyStore,
+    private val redisGuard: RedisGuard = RedisGuard(),
+    private val ttlSeconds: Long = DEFAULT_TTL_SECONDS
+) {
+
+    /**
+     * Run [write] once per [key]. Later calls with the same key and [requestBody] get the first
+     * call's status and body back without running [write] again.
+     */
+    suspend fun execute(
+        scope: String,
+        key: String,
+        requestBody: String,
+        write: suspend () -> Pair<Int, String>
+    ): IdempotentResponse {
+        require(key.isNotBlank() && key.length <= MAX_KEY_LENGTH) {
+            "Idempotency-Key must be 1-$MAX_KEY_LENGTH characters"
+        }
+        val storeKey = "idempotency:$scope:$key"
+        val requestHash = sha256(requestBody)
+
+        val recorded = redisGuard.execute(RedisFeature.IDEMPOTENCY, fallback = { null }) { store.get(storeKey) }
+        if (recorded != null) {
+            if (recorded.requestHash != requestHash) throw IdempotencyKeyReusedException(key)
+            return recorded
+        }
+
+        val (status, body) = write()
+        val response = IdempotentResponse(status, body, requestHash)
+        // Only successful writes are replayed; failures can be retried with the same key
+        if (status in 200..299) {
+            redisGuard.execute(RedisFeature.IDEMPOTENCY, fallback = { }) { store.put(storeKey, response, ttlSeconds) }
+        }
+        return response
+    }
+
+    private fun sha256(value: String): String =
+        MessageDigest.getInstance("SHA-256").digest(value.toByteArray()).joinToString("") { "%02x".format(it) }
+
+    companion object {
+        const val HEADER = "Idempotency-Key"
+        const val DEFAULT_TTL_SECONDS = 15 * 60L
+        const val MAX_KEY_LENGTH = 255
+    }
+}
@@ -0,0 +1,73 @@
+package com.wondernest.services.resilience
+
+import io.lettuce.core.ClientOptions
+import io.lettuce.core.RedisClient
+import io.lettuce.core.SocketOptions
+import io.lettuce.core.TimeoutOptions
+import io.lettuce.core.api.StatefulRedisConnection
+import java.time.Duration
+
+/**
+ * Timeouts and reconnect behaviour for the shared connection. While disconnected, commands are
+ * rejected immediately rather than queued, so a Redis blip fails over to [RedisGuard]'s fallbacks
+ * instead of stalling requests until it reconnects.
+ */
+data class RedisConnectionConfig(
+    val commandTimeoutMillis: Long = 2_000,
+    val connectTimeoutMillis: Long = 2_000,
+    val autoReconnect: Boolean = true
+) {
+    fun clientOptions(): ClientOptions = ClientOptions.builder()
+        .autoReconnect(autoReconnect)
+        .disconnectedBehavior(ClientOptions.DisconnectedBehavior.REJECT_COMMANDS)
+        .timeoutOptions(TimeoutOptions.enabled(Duration.ofMillis(commandTimeoutMillis)))
+        .socketOptions(SocketOptions.builder().connectTimeout(Duration.ofMillis(connectTimeoutMillis)).build())
+        .build()
+
+    companion object {
+        /**
+         * Reads REDIS_COMMAND_TIMEOUT_MS, REDIS_CONNECT_TIMEOUT_MS and REDIS_AUTO_RECONNECT
+         */
+        fun fromEnvironment(env: Map<String, String> = System.getenv()) = RedisConnectionConfig(
+            commandTimeoutMillis = env["REDIS_COMMAND_TIMEOUT_MS"]?.toLongOrNull()?.takeIf { it > 0 } ?: 2_000,
+            connectTimeoutMillis = env["REDIS_CONNECT_TIMEOUT_MS"]?.toLongOrNull()?.takeIf { it > 0 } ?: 2_000,
+            autoReconnect = env["REDIS_AUTO_RECONNECT"]?.toBooleanStrictOrNull() ?: true
+        )
+    }
+}
+
+/**
+ * One Lettuce connection shared by the Redis-backed stores; Lettuce connections are thread-safe.
+ * Always call it through [RedisGuard] so an outage degrades instead of failing requests.
+ */
+object RedisConnections {
+    /**
+     * Builds the URI from REDIS_HOST, REDIS_PORT, REDIS_PASSWORD and REDIS_DATABASE
+     */
+    fun uriFromEnvironment(env: Map<String, String> = System.getenv()): String {
+        val host = env["REDIS_HOST"] ?: "localhost"
+        val port = env["REDIS_PORT"] ?: "6379"
+        val database = env["REDIS_DATABASE"] ?: "0"
+        val auth = env["REDIS_PASSWORD"]?.takeIf { it.isNotBlank() }?.let { ":$it@" } ?: ""
+        return "redis://$auth$host:$port/$database"
+    }
+
+    private val client = lazy {
+        val config = RedisConnectionConfig.fromEnvironment()
+        RedisClient.create(uriFromEnvironment()).apply {
+            options = config.clientOptions()
+            setDefaultTimeout(Duration.ofMillis(config.commandTimeoutMillis))
+        }
+    }
+    private val connection = lazy { client.value.connect() }
+
+    val shared: StatefulRedisConnection<String, String> by connection
+
+    /**
+     * Closes the connection and client if they were ever opened; called once on shutdown
+     */
+    fun close() {
+        if (connection.isInitialized()) connection.value.close()
+        if (client.isInitialized()) client.value.shutdown()
+    }
+}
@@ -0,0 +1,161 @@
+package com.wondernest.services.resilience
+
+import io.micrometer.core.instrument.MeterRegistry
+import io.micrometer.core.instrument.Metrics
+import kotlinx.coroutines.withTimeout
+import mu.KotlinLogging
+import java.util.concurrent.atomic.AtomicInteger
+import java.util.concurrent.atomic.AtomicLong
+
+private val logger = KotlinLogging.logger {}
+
+/**
+ * How a feature behaves when Redis is unavailable.
+ * FAIL_OPEN falls back (cache miss, request allowed); FAIL_CLOSED rejects with [RedisUnavailableException].
+ */
+enum class RedisFailureMode {
+    FAIL_OPEN,
+    FAIL_CLOSED;
+
+    companion object {
+        fun fromString(value: String): RedisFailureMode? = when (value.trim().lowercase()) {
+            "open", "fail_open", "fail-open" -> FAIL_OPEN
+            "closed", "fail_closed", "fail-closed" -> FAIL_CLOSED
+            else -> null
+        }
+    }
+}
+
+/**
+ * Features backed by Redis and their default degradation.
+ * Only security-sensitive features are expected to be switched to FAIL_CLOSED.
+ */
+enum class RedisFeature(val key: String, val defaultMode: RedisFailureMode) {
+    CACHE("cache", RedisFailureMode.FAIL_OPEN),
+    SESSIONS("sessions", RedisFailureMode.FAIL_OPEN),
+    RATE_LIMIT("rate_limit", RedisFailureMode.FAIL_OPEN),
+    TOKEN_BLOCKLIST("token_blocklist", RedisFailureMode.FAIL_OPEN),
+    IDEMPOTENCY("idempotency", RedisFailureMode.FAIL_OPEN),
+    // Single-use download links can't be honoured safely without Redis
+    SIGNED_URL_NONCES("signed_url_nonces", RedisFailureMode.FAIL_CLOSED)
+}
+
+data class RedisGuardConfig(
+    val failureModes: Map<RedisFeature, RedisFailureMode> = RedisFeature.entries.associateWith { it.defaultMode },
+    val failureThreshold: Int = 5,
+    val openDurationMillis: Long = 30_000L,
+    // Backstop for calls Lettuce's own command timeout doesn't cover, such as a slow connect
+    val callTimeoutMillis: Long = 2_000L
+) {
+    fun modeFor(feature: RedisFeature): RedisFailureMode = failureModes[feature] ?: feature.defaultMode
+
+    companion object {
+        /**
+         * Reads REDIS_FAILURE_MODE_<FEATURE> (open/closed), REDIS_CIRCUIT_FAILURE_THRESHOLD,
+         * REDIS_CIRCUIT_OPEN_MS and REDIS_COMMAND_TIMEOUT_MS, falling back to defaults for
+         * missing or invalid values.
+         */
+        fun fromEnvironment(env: Map<String, String> = System.getenv()): RedisGuardConfig {
+            val modes = RedisFeature.entries.associateWith { feature ->
+                env["REDIS_FAILURE_MODE_${feature.name}"]?.let { RedisFailureMode.fromString(it) }
+                    ?: feature.defaultMode
+            }
+            return RedisGuardConfig(
+                failureModes = modes,
+                failureThreshold = env["REDIS_CIRCUIT_FAILURE_THRESHOLD"]?.toIntOrNull()?.takeIf { it > 0 } ?: 5,
+                openDurationMillis = env["REDIS_CIRCUIT_OPEN_MS"]?.toLongOrNull()?.takeIf { it > 0 } ?: 30_000L,
+                callTimeoutMillis = env["REDIS_COMMAND_TIMEOUT_MS"]?.toLongOrNull()?.takeIf { it > 0 } ?: 2_000L
+            )
+        }
+    }
+}
+
+class RedisUnavailableException(val feature: RedisFeature, cause: Throwable? = null) :
+    IllegalStateException("Redis unavailable for ${feature.key}", cause)
+
+/**
+ * Central policy for Redis-backed calls.
+ *
+ * Every call goes through [execute]. Failures are counted and once [RedisGuardConfig.failureThreshold]
+ * consecutive failures occur the circuit opens: calls skip Redis entirely for
+ * [RedisGuardConfig.openDurationMillis] and degrade per feature. After that a single trial call
+ * is let through; success closes the circuit again.
+ */
+class RedisGuard(
+    private val config: RedisGuardConfig = RedisGuardConfig.fromEnvironment(),
+    private val meterRegistry: MeterRegistry = Metrics.globalRegistry,
+    private val clock: () -> Long = System::currentTimeMillis
+) {
+    private val consecutiveFailures = AtomicInteger(0)
+    private val openedAt = AtomicLong(NOT_OPEN)
+    private val degradedGauge = AtomicInteger(0)
+
+    init {
+        meterRegistry.gauge("redis.degraded", degradedGauge)
+    }
+
+    fun isDegraded(): Boolean = openedAt.get() != NOT_OPEN
+
+    fun modeFor(feature: RedisFeature): RedisFailureMode = config.modeFor(feature)
+
+    /**
+     * Run [block] against Redis. When Redis fails, takes longer than
+     * [RedisGuardConfig.callTimeoutMillis] (or the circuit is open) a FAIL_OPEN feature
+     * returns [fallback]; a FAIL_CLOSED feature throws [RedisUnavailableException].
+     */
+    suspend fun <T> execute(feature: RedisFeature, fallback: () -> T, block: suspend () -> T): T {
+        if (!allowRequest()) {
+            return degrade(feature, fallback, null)
+        }
+        return try {
+            val result = withTimeout(config.callTimeoutMillis) { block() }
+            onSuccess()
+            result
+        } catch (e: RedisUnavailableException) {
+            throw e
+        } catch (e: Exception) {
+            // Includes Lettuce's RedisCommandTimeoutException and our own withTimeout
+            onFailure(feature, e)
+            degrade(feature, fallback, e)
+        }
+    }
+
+    private fun allowRequest(): Boolean {
+        val opened = openedAt.get()
+        if (opened == NOT_OPEN) return true
+        // Half-open: let one trial call through once the open window has passed
+        return clock() - opened >= config.openDurationMillis && openedAt.compareAndSet(opened, clock())
+    }
+
+    private fun onSuccess() {
+        consecutiveFailures.set(0)
+        if (openedAt.getAndSet(NOT_OPEN) != NOT_OPEN) {
+            degradedGauge.set(0)
+            logger.info { "Redis recovered, leaving degraded mode" }
+        }
+    }
+
+    private fun onFailure(feature: RedisFeature, e: Exception) {
+        val failures = consecutiveFailures.incrementAndGet()
+        logger.warn { "Redis call for ${feature.key} failed ($failures consecutive): ${e.message}" }
+        if (failures >= config.failureThreshold && openedAt.get() == NOT_OPEN) {
+            openedAt.set(clock())
+            degradedGauge.set(1)
+            logger.error { "Redis circuit opened after $failures failures, entering degraded mode" }
+        }
+    }
+
+    private fun <T> degrade(feature: RedisFeature, fallback: () -> T, cause: Throwable?): T {
+        val mode = config.modeFor(feature)
+        meterRegistry.counter("redis.degraded.calls", "feature", feature.key, "mode", mode.name.lowercase())
+            .increment()
+        return when (mode) {
+            RedisFailureMode.FAIL_OPEN -> fallback()
+            RedisFailureMode.FAIL_CLOSED -> throw RedisUnavailableException(feature, cause)
+        }
+    }
+
+    companion object {
+        private const val NOT_OPEN = -1L
+    }
+}
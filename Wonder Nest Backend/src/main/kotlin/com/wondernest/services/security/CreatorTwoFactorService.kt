@@ -0,0 +1,145 @@
+package com.wondernest.services.security
+
+import com.wondernest.data.database.table.CreatorTwoFactor
+import kotlinx.coroutines.Dispatchers
+import kotlinx.datetime.Clock
+import kotlinx.serialization.Serializable
+import mu.KotlinLogging
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.eq
+import org.jetbrains.exposed.sql.and
+import org.jetbrains.exposed.sql.select
+import org.jetbrains.exposed.sql.transactions.experimental.newSuspendedTransaction
+import org.jetbrains.exposed.sql.update
+import org.jetbrains.exposed.sql.upsert
+import java.util.UUID
+
+private val logger = KotlinLogging.logger {}
+
+/**
+ * Shown to the creator once. [qrCode] is the otpauth:// URI to render as a QR code.
+ */
+@Serializable
+data class CreatorTwoFactorEnrollment(
+    val secret: String,
+    val qrCode: String,
+    val backupCodes: List<String>
+)
+
+data class CreatorTwoFactorRecord(
+    val userId: UUID,
+    val encryptedSecret: String,
+    val backupCodeHashes: List<String>,
+    val enabled: Boolean
+)
+
+interface CreatorTwoFactorStore {
+    suspend fun find(userId: UUID): CreatorTwoFactorRecord?
+    /** Starts or restarts an enrollment; [record] is not yet enabled */
+    suspend fun saveEnrollment(record: CreatorTwoFactorRecord)
+    suspend fun enable(userId: UUID): Boolean
+    /** Swaps the backup codes only if they are still [expected], so a code can't be spent twice concurrently */
+    suspend fun replaceBackupCodes(userId: UUID, expected: List<String>, remaining: List<String>): Boolean
+}
+
+object DatabaseCreatorTwoFactorStore : CreatorTwoFactorStore {
+    override suspend fun find(userId: UUID): CreatorTwoFactorRecord? = newSuspendedTransaction(Dispatchers.IO) {
+        CreatorTwoFactor.select { CreatorTwoFactor.userId eq userId }
+            .map {
+                CreatorTwoFactorRecord(
+                    userId = it[CreatorTwoFactor.userId].value,
+                    encryptedSecret = it[CreatorTwoFactor.encryptedSecret],
+                    backupCodeHashes = it[CreatorTwoFactor.backupCodeHashes],
+                    enabled = it[CreatorTwoFactor.enabled]
+                )
+            }
+            .singleOrNull()
+    }
+
+    override suspend fun saveEnrollment(record: CreatorTwoFactorRecord) {
+        newSuspendedTransaction(Dispatchers.IO) {
+            CreatorTwoFactor.upsert {
+                it[userId] = record.userId
+                it[encryptedSecret] = record.encryptedSecret
+                it[backupCodeHashes] = record.backupCodeHashes
+                it[enabled] = false
+                it[enabledAt] = null
+            }
+        }
+    }
+
+    override suspend fun enable(userId: UUID): Boolean = newSuspendedTransaction(Dispatchers.IO) {
+        CreatorTwoFactor.update({ CreatorTwoFactor.userId eq userId }) {
+            it[enabled] = true
+            it[enabledAt] = Clock.System.now()
+        } > 0
+    }
+
+    override suspend fun replaceBackupCodes(userId: UUID, expected: List<String>, remaining: List<String>): Boolean =
+        newSuspendedTransaction(Dispatchers.IO) {
+            CreatorTwoFactor.update({
+                (CreatorTwoFactor.userId eq userId) and (CreatorTwoFactor.backupCodeHashes eq expected)
+            }) {
+                it[backupCodeHashes] = remaining
+            } > 0
+        }
+}
+
+/**
+ * TOTP enrollment and verification for creator accounts, on top of the admin [TwoFactorService].
+ * Enrollment is two steps: [beginEnrollment] hands out the secret and backup codes, and
+ * [confirmEnrollment] turns 2FA on once the creator proves their app produces valid codes.
+ */
+class CreatorTwoFactorService(
+    private val twoFactorService: TwoFactorService = TwoFactorService(),
+    private val cipher: SecretCipher = SecretCipher.fromEnvironment(),
+    private val store: CreatorTwoFactorStore = DatabaseCreatorTwoFactorStore
+) {
+    /**
+     * Throws [IllegalStateException] when 2FA is already enabled for [userId]
+     */
+    suspend fun beginEnrollment(userId: UUID, accountName: String): CreatorTwoFactorEnrollment {
+        check(store.find(userId)?.enabled != true) { "Two-factor authentication is already enabled" }
+
+        val secret = twoFactorService.generateSecret()
+        val backupCodes = twoFactorService.generateBackupCodes()
+        store.saveEnrollment(
+            CreatorTwoFactorRecord(
+                userId = userId,
+                encryptedSecret = cipher.encrypt(secret),
+                backupCodeHashes = backupCodes.map { twoFactorService.hashBackupCode(it) },
+                enabled = false
+            )
+        )
+        return CreatorTwoFactorEnrollment(
+            secret = secret,
+            qrCode = twoFactorService.enrollment(accountName, secret).otpauthUri,
+            backupCodes = backupCodes
+        )
+    }
+
+    /**
+     * Enables 2FA when [code] is valid for the pending secret; false otherwise
+     */
+    suspend fun confirmEnrollment(userId: UUID, code: String): Boolean {
+        val record = store.find(userId) ?: return false
+        if (record.enabled) return true
+        if (!twoFactorService.validateCode(cipher.decrypt(record.encryptedSecret), code)) return false
+        logger.info { "Two-factor authentication enabled for creator account $userId" }
+        return store.enable(userId)
+    }
+
+    suspend fun isEnabled(userId: UUID): Boolean = store.find(userId)?.enabled == true
+
+    /**
+     * Accepts a current TOTP code or an unused backup code, which is spent in the process
+     */
+    suspend fun verify(userId: UUID, code: String): Boolean {
+        val record = store.find(userId)?.takeIf { it.enabled } ?: return false
+        if (twoFactorService.validateCode(cipher.decrypt(record.encryptedSecret), code)) return true
+
+        val remaining = twoFactorService.consumeBackupCode(record.backupCodeHashes, code) ?: return false
+        if (!store.replaceBackupCodes(userId, record.backupCodeHashes, remaining)) return false
+        logger.warn { "Creator account $userId signed in with a backup code (${remaining.size} left)" }
+        return true
+    }
+}
@@ -0,0 +1,59 @@
+package com.wondernest.services.security
+
+import mu.KotlinLogging
+import java.security.SecureRandom
+import java.util.Base64
+import javax.crypto.Cipher
+import javax.crypto.spec.GCMParameterSpec
+import javax.crypto.spec.SecretKeySpec
+
+private val logger = KotlinLogging.logger {}
+
+/**
+ * AES-256-GCM for secrets that must be stored but read back, such as TOTP secrets.
+ * Output is Base64 of the random IV followed by the ciphertext and tag.
+ */
+class SecretCipher(key: ByteArray) {
+    init {
+        require(key.size == KEY_BYTES) { "Encryption key must be $KEY_BYTES bytes" }
+    }
+
+    private val keySpec = SecretKeySpec(key, "AES")
+    private val random = SecureRandom()
+
+    fun encrypt(plaintext: String): String {
+        val iv = ByteArray(IV_BYTES).also { random.nextBytes(it) }
+        val cipher = Cipher.getInstance(TRANSFORMATION)
+        cipher.init(Cipher.ENCRYPT_MODE, keySpec, GCMParameterSpec(TAG_BITS, iv))
+        return Base64.getEncoder().encodeToString(iv + cipher.doFinal(plaintext.toByteArray()))
+    }
+
+    fun decrypt(encoded: String): String {
+        val bytes = Base64.getDecoder().decode(encoded)
+        val cipher = Cipher.getInstance(TRANSFORMATION)
+        cipher.init(Cipher.DECRYPT_MODE, keySpec, GCMParameterSpec(TAG_BITS, bytes, 0, IV_BYTES))
+        return String(cipher.doFinal(bytes, IV_BYTES, bytes.size - IV_BYTES))
+    }
+
+    companion object {
+        private const val TRANSFORMATION = "AES/GCM/NoPadding"
+        private const val KEY_BYTES = 32
+        private const val IV_BYTES = 12
+        private const val TAG_BITS = 128
+
+        /**
+         * Reads a Base64 key from TWO_FACTOR_ENCRYPTION_KEY. The key is required unless
+         * KTOR_ENV=development, where a random one is used instead and anything encrypted
+         * becomes unreadable after a restart.
+         */
+        fun fromEnvironment(env: Map<String, String> = System.getenv()): SecretCipher {
+            val configured = env["TWO_FACTOR_ENCRYPTION_KEY"]?.takeIf { it.isNotBlank() }
+            if (configured != null) return SecretCipher(Base64.getDecoder().decode(configured.trim()))
+            check(env["KTOR_ENV"] == "development") {
+                "TWO_FACTOR_ENCRYPTION_KEY must be set outside development"
+            }
+            logger.warn { "TWO_FACTOR_ENCRYPTION_KEY is not set; using a temporary key" }
+            return SecretCipher(ByteArray(KEY_BYTES).also { SecureRandom().nextBytes(it) })
+        }
+    }
+}
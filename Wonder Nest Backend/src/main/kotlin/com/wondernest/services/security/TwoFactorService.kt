@@ -0,0 +1,95 @@
+package com.wondernest.services.security
+
+import dev.samstevens.totp.code.CodeGenerator
+import dev.samstevens.totp.code.DefaultCodeGenerator
+import dev.samstevens.totp.code.HashingAlgorithm
+import dev.samstevens.totp.qr.QrData
+import dev.samstevens.totp.recovery.RecoveryCodeGenerator
+import dev.samstevens.totp.secret.DefaultSecretGenerator
+import kotlinx.serialization.Serializable
+import java.security.MessageDigest
+import java.time.Instant
+
+/**
+ * Secret and provisioning URI for an authenticator app. [otpauthUri] is the QR code payload.
+ */
+@Serializable
+data class TwoFactorEnrollment(
+    val secret: String,
+    val otpauthUri: String
+)
+
+/**
+ * TOTP (RFC 6238) codes and single-use backup codes for admin two-factor authentication
+ */
+class TwoFactorService(
+    private val issuer: String = "WonderNest",
+    private val clock: () -> Instant = Instant::now
+) {
+    companion object {
+        const val CODE_DIGITS = 6
+        const val PERIOD_SECONDS = 30
+        const val BACKUP_CODE_COUNT = 10
+        // Accept the previous and next time step to tolerate clock skew
+        private const val ALLOWED_STEP_DRIFT = 1
+    }
+
+    private val secretGenerator = DefaultSecretGenerator()
+    private val recoveryCodeGenerator = RecoveryCodeGenerator()
+    private val codeGenerator: CodeGenerator = DefaultCodeGenerator(HashingAlgorithm.SHA1, CODE_DIGITS)
+
+    /**
+     * New Base32 secret (32 characters, 160 bits)
+     */
+    fun generateSecret(): String = secretGenerator.generate()
+
+    fun enrollment(accountName: String, secret: String): TwoFactorEnrollment {
+        val qrData = QrData.Builder()
+            .label(accountName)
+            .secret(secret)
+            .issuer(issuer)
+            .algorithm(HashingAlgorithm.SHA1)
+            .digits(CODE_DIGITS)
+            .period(PERIOD_SECONDS)
+            .build()
+        return TwoFactorEnrollment(secret = secret, otpauthUri = qrData.uri)
+    }
+
+    fun validateCode(secret: String, code: String): Boolean = matchingStep(secret, code) != null
+
+    /**
+     * The time step [code] was generated for, or null when it isn't valid now. Callers that
+     * must not accept the same code twice remember the step and reject steps not after it.
+     */
+    fun matchingStep(secret: String, code: String): Long? {
+        val trimmed = code.trim()
+        if (trimmed.length != CODE_DIGITS || !trimmed.all { it.isDigit() }) return null
+        val current = clock().epochSecond / PERIOD_SECONDS
+        return (current - ALLOWED_STEP_DRIFT..current + ALLOWED_STEP_DRIFT).firstOrNull { step ->
+            MessageDigest.isEqual(codeGenerator.generate(secret, step).toByteArray(), trimmed.toByteArray())
+        }
+    }
+
+    /**
+     * Plain backup codes to show the admin once; store only [hashBackupCode] of each
+     */
+    fun generateBackupCodes(): List<String> = recoveryCodeGenerator.generateCodes(BACKUP_CODE_COUNT).toList()
+
+    fun hashBackupCode(code: String): String {
+        val normalized = code.trim().lowercase()
+        return MessageDigest.getInstance("SHA-256")
+            .digest(normalized.toByteArray())
+            .joinToString("") { "%02x".format(it) }
+    }
+
+    /**
+     * Returns the remaining hashes with the matching code removed, or null if the code
+     * doesn't match any unused backup code.
+     */
+    fun consumeBackupCode(storedHashes: List<String>, code: String): List<String>? {
+        val hash = hashBackupCode(code)
+        val candidate = storedHashes.firstOrNull { MessageDigest.isEqual(it.toByteArray(), hash.toByteArray()) }
+            ?: return null
+        return storedHashes - candidate
+    }
+}
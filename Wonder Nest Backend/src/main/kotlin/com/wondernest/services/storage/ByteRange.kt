@@ -0,0 +1,45 @@
+package com.wondernest.services.storage
+
+/**
+ * Outcome of parsing a `Range` request header against a file size
+ */
+sealed class ByteRangeResult {
+    /** Inclusive byte range to serve with 206 Partial Content */
+    data class Satisfiable(val start: Long, val end: Long) : ByteRangeResult() {
+        val length: Long get() = end - start + 1
+        fun contentRange(totalSize: Long) = "bytes $start-$end/$totalSize"
+    }
+
+    /** The range starts past the end of the file; respond 416 */
+    object Unsatisfiable : ByteRangeResult()
+
+    /** Malformed or multi-range header; serve the whole file */
+    object Ignored : ByteRangeResult()
+}
+
+/**
+ * Parses single `bytes=` ranges (RFC 9110): `start-end`, open-ended `start-` and suffix `-length`.
+ * Multiple ranges aren't supported and fall back to the full file.
+ */
+object ByteRange {
+    private val SINGLE_RANGE = Regex("""^bytes=(\d*)-(\d*)$""")
+
+    fun parse(header: String, fileSize: Long): ByteRangeResult {
+        val match = SINGLE_RANGE.matchEntire(header.trim()) ?: return ByteRangeResult.Ignored
+        val (startText, endText) = match.destructured
+        val start = startText.toLongOrNull()
+        val end = endText.toLongOrNull()
+
+        return when {
+            // Suffix range: the last N bytes
+            start == null && end != null -> when {
+                end == 0L || fileSize == 0L -> ByteRangeResult.Unsatisfiable
+                else -> ByteRangeResult.Satisfiable((fileSize - end).coerceAtLeast(0), fileSize - 1)
+            }
+            start == null -> ByteRangeResult.Ignored
+            end != null && end < start -> ByteRangeResult.Ignored
+            start >= fileSize -> ByteRangeResult.Unsatisfiable
+            else -> ByteRangeResult.Satisfiable(start, (end ?: fileSize - 1).coerceAtMost(fileSize - 1))
+        }
+    }
+}
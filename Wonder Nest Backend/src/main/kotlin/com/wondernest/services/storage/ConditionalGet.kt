@@ -0,0 +1,39 @@
+package com.wondernest.services.storage
+
+import kotlinx.datetime.Instant
+import kotlinx.datetime.toJavaInstant
+import java.time.ZoneOffset
+import java.time.ZonedDateTime
+import java.time.format.DateTimeFormatter
+
+/**
+ * Evaluates `If-None-Match` / `If-Modified-Since` for GET downloads (RFC 9110 section 13).
+ * If-None-Match wins when both are sent; validators compare weakly, as GET allows.
+ */
+object ConditionalGet {
+
+    fun isNotModified(
+        ifNoneMatch: String?,
+        ifModifiedSince: String?,
+        etag: String,
+        lastModified: Instant
+    ): Boolean {
+        if (ifNoneMatch != null) {
+            val current = opaque(etag)
+            return ifNoneMatch.split(',').map { it.trim() }.any { it == "*" || opaque(it) == current }
+        }
+        val since = ifModifiedSince?.let { parseHttpDate(it) } ?: return false
+        // HTTP dates have second precision
+        return lastModified.epochSeconds <= since.epochSeconds
+    }
+
+    fun formatHttpDate(instant: Instant): String =
+        DateTimeFormatter.RFC_1123_DATE_TIME.format(instant.toJavaInstant().atOffset(ZoneOffset.UTC))
+
+    private fun parseHttpDate(value: String): Instant? = runCatching {
+        val parsed = ZonedDateTime.parse(value.trim(), DateTimeFormatter.RFC_1123_DATE_TIME)
+        Instant.fromEpochSeconds(parsed.toEpochSecond())
+    }.getOrNull()
+
+    private fun opaque(tag: String) = tag.removePrefix("W/")
+}
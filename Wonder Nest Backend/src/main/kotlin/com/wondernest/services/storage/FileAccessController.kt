@@ -0,0 +1,120 @@
+package com.wondernest.services.storage
+
+import com.wondernest.data.database.table.FamilyMembers
+import com.wondernest.data.database.table.UploadedFiles
+import io.micrometer.core.instrument.MeterRegistry
+import io.micrometer.core.instrument.Metrics
+import kotlinx.coroutines.Dispatchers
+import mu.KotlinLogging
+import org.jetbrains.exposed.sql.select
+import org.jetbrains.exposed.sql.transactions.experimental.newSuspendedTransaction
+import java.util.UUID
+import java.util.concurrent.ConcurrentHashMap
+
+private val logger = KotlinLogging.logger {}
+
+/**
+ * What access decisions about a file depend on
+ */
+data class FileAccessInfo(
+    val fileId: UUID,
+    val ownerId: UUID,
+    val isPublic: Boolean,
+    val deleted: Boolean
+)
+
+interface FileAccessSource {
+    suspend fun load(fileId: UUID): FileAccessInfo?
+
+    /** The families [userId] belongs to */
+    suspend fun familiesOf(userId: UUID): Set<UUID>
+}
+
+object DatabaseFileAccessSource : FileAccessSource {
+    override suspend fun load(fileId: UUID): FileAccessInfo? = newSuspendedTransaction(Dispatchers.IO) {
+        UploadedFiles.select { UploadedFiles.id eq fileId }
+            .singleOrNull()
+            ?.let { row ->
+                FileAccessInfo(
+                    fileId = fileId,
+                    ownerId = row[UploadedFiles.userId],
+                    isPublic = row[UploadedFiles.isPublic],
+                    deleted = row[UploadedFiles.deletedAt] != null
+                )
+            }
+    }
+
+    override suspend fun familiesOf(userId: UUID): Set<UUID> = newSuspendedTransaction(Dispatchers.IO) {
+        FamilyMembers.slice(FamilyMembers.familyId)
+            .select { FamilyMembers.userId eq userId }
+            .map { it[FamilyMembers.familyId].value }
+            .toSet()
+    }
+}
+
+class FileAccessDeniedException(fileId: UUID) : SecurityException("Access to file $fileId denied")
+
+/**
+ * Decides who may view a file: its owner, or, once it's public, members of the owner's family.
+ *
+ * Decisions are cached per (user, file) for [ttlMillis] because gallery browsing and
+ * previews check the same files repeatedly. Anything that changes ownership or visibility
+ * must call [invalidateFile]; family membership changes call [invalidateUser] for the member.
+ * Cache lookups are counted in the "file.access.cache" meter (result=hit|miss).
+ */
+class FileAccessController(
+    private val source: FileAccessSource = DatabaseFileAccessSource,
+    private val ttlMillis: Long = DEFAULT_TTL_MILLIS,
+    private val meterRegistry: MeterRegistry = Metrics.globalRegistry,
+    private val clock: () -> Long = System::currentTimeMillis
+) {
+    private data class CacheKey(val userId: UUID, val fileId: UUID)
+    private data class CachedDecision(val allowed: Boolean, val expiresAt: Long)
+
+    private val decisions = ConcurrentHashMap<CacheKey, CachedDecision>()
+
+    suspend fun canViewFile(userId: UUID, fileId: UUID): Boolean {
+        val key = CacheKey(userId, fileId)
+        val cached = decisions[key]
+        if (cached != null && cached.expiresAt > clock()) {
+            record("hit")
+            return cached.allowed
+        }
+        record("miss")
+
+        val info = source.load(fileId)
+        val allowed = when {
+            info == null || info.deleted -> false
+            info.ownerId == userId -> true
+            !info.isPublic -> false
+            else -> source.familiesOf(userId).any { it in source.familiesOf(info.ownerId) }
+        }
+        decisions[key] = CachedDecision(allowed, clock() + ttlMillis)
+        return allowed
+    }
+
+    /**
+     * Throws [FileAccessDeniedException] unless [userId] may view the file
+     */
+    suspend fun validateFileAccess(userId: UUID, fileId: UUID) {
+        if (!canViewFile(userId, fileId)) throw FileAccessDeniedException(fileId)
+    }
+
+    fun invalidateFile(fileId: UUID) {
+        decisions.keys.removeIf { it.fileId == fileId }
+        logger.debug { "Invalidated cached access decisions for file $fileId" }
+    }
+
+    fun invalidateUser(userId: UUID) {
+        decisions.keys.removeIf { it.userId == userId }
+        logger.debug { "Invalidated cached access decisions for user $userId" }
+    }
+
+    private fun record(result: String) {
+        meterRegistry.counter("file.access.cache", "result", result).increment()
+    }
+
+    companion object {
+        const val DEFAULT_TTL_MILLIS = 30_000L
+    }
+}
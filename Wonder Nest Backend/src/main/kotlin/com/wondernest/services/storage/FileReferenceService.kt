@@ -0,0 +1,76 @@
+package com.wondernest.services.storage
+
+import com.wondernest.data.database.table.FileReferences
+import kotlinx.coroutines.Dispatchers
+import org.jetbrains.exposed.dao.id.UUIDTable
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.eq
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.inList
+import org.jetbrains.exposed.sql.select
+import org.jetbrains.exposed.sql.transactions.experimental.newSuspendedTransaction
+import java.util.UUID
+
+/**
+ * One place a file is used. Title and page count are only known for stories.
+ */
+data class FileReference(
+    val referenceType: String,
+    val referenceId: UUID,
+    val title: String? = null,
+    val pageCount: Int? = null
+)
+
+data class FileReferenceSummary(
+    val fileId: UUID,
+    val stories: List<FileReference>,
+    val other: List<FileReference>
+) {
+    val referenceCount: Int get() = stories.size + other.size
+    val isUsed: Boolean get() = referenceCount > 0
+}
+
+fun interface FileReferenceSource {
+    suspend fun load(fileId: UUID): List<FileReference>
+}
+
+// Just the story template columns needed for usage summaries
+private object StoryTemplateSummaries : UUIDTable("games.story_templates") {
+    val title = varchar("title", 255)
+    val pageCount = integer("page_count")
+}
+
+object DatabaseFileReferenceSource : FileReferenceSource {
+    override suspend fun load(fileId: UUID): List<FileReference> = newSuspendedTransaction(Dispatchers.IO) {
+        val references = FileReferences.select { FileReferences.fileId eq fileId }
+            .map { it[FileReferences.referenceType] to it[FileReferences.referenceId] }
+
+        val storyIds = references.filter { it.first == FileReferenceService.STORY }.map { it.second }
+        val stories = if (storyIds.isEmpty()) {
+            emptyMap()
+        } else {
+            StoryTemplateSummaries.select { StoryTemplateSummaries.id inList storyIds }
+                .associate { it[StoryTemplateSummaries.id].value to (it[StoryTemplateSummaries.title] to it[StoryTemplateSummaries.pageCount]) }
+        }
+
+        references.map { (type, id) ->
+            FileReference(type, id, title = stories[id]?.first, pageCount = stories[id]?.second)
+        }
+    }
+}
+
+/**
+ * Looks up everything that references an uploaded file (stories, profile pictures, ...)
+ */
+class FileReferenceService(
+    private val source: FileReferenceSource = DatabaseFileReferenceSource
+) {
+    companion object {
+        const val STORY = "story"
+    }
+
+    suspend fun getComprehensiveReferences(fileId: UUID): FileReferenceSummary {
+        val (stories, other) = source.load(fileId)
+            .distinctBy { it.referenceType to it.referenceId }
+            .partition { it.referenceType == STORY }
+        return FileReferenceSummary(fileId, stories, other)
+    }
+}
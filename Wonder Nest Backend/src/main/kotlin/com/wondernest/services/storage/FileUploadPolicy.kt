@@ -0,0 +1,65 @@
+package com.wondernest.services.storage
+
+import com.wondernest.domain.model.FileCategory
+import io.ktor.server.config.*
+
+/**
+ * Size and MIME type limits for one upload category
+ */
+data class CategoryLimits(
+    val maxFileSize: Long,
+    val allowedMimeTypes: Set<String>
+)
+
+/**
+ * Maps each upload category to its limits. Images, audio and documents get separate
+ * size tiers; any category can be overridden under `storage.categories.<category>`.
+ */
+class FileUploadPolicy(private val limits: Map<FileCategory, CategoryLimits>) {
+
+    fun limitsFor(category: FileCategory): CategoryLimits = limits.getValue(category)
+
+    companion object {
+        const val IMAGE_MAX_FILE_SIZE = 5L * 1024 * 1024
+        const val AUDIO_MAX_FILE_SIZE = 10L * 1024 * 1024
+        const val DOCUMENT_MAX_FILE_SIZE = 50L * 1024 * 1024
+
+        val IMAGE_TYPES = setOf("image/jpeg", "image/png", "image/gif", "image/webp")
+        val AUDIO_TYPES = setOf("audio/mpeg", "audio/wav")
+        val DOCUMENT_TYPES = setOf("application/pdf")
+
+        /**
+         * [contentMaxFileSize] and [contentMimeTypes] apply to general content, which has no tier of its own
+         */
+        fun fromConfig(
+            config: ApplicationConfig,
+            contentMaxFileSize: Long,
+            contentMimeTypes: Set<String>
+        ): FileUploadPolicy = FileUploadPolicy(FileCategory.entries.associateWith { category ->
+            val defaults = defaultLimits(category, contentMaxFileSize, contentMimeTypes)
+            val prefix = "storage.categories.${category.toDbValue()}"
+            CategoryLimits(
+                maxFileSize = config.propertyOrNull("$prefix.max-file-size")?.getString()?.toLongOrNull()
+                    ?: defaults.maxFileSize,
+                allowedMimeTypes = config.propertyOrNull("$prefix.allowed-types")?.getString()
+                    ?.split(",")
+                    ?.map { it.trim() }
+                    ?.filter { it.isNotEmpty() }
+                    ?.toSet()
+                    ?: defaults.allowedMimeTypes
+            )
+        })
+
+        private fun defaultLimits(
+            category: FileCategory,
+            contentMaxFileSize: Long,
+            contentMimeTypes: Set<String>
+        ): CategoryLimits = when (category) {
+            FileCategory.PROFILE_PICTURE, FileCategory.ARTWORK -> CategoryLimits(IMAGE_MAX_FILE_SIZE, IMAGE_TYPES)
+            FileCategory.AUDIO -> CategoryLimits(AUDIO_MAX_FILE_SIZE, AUDIO_TYPES)
+            FileCategory.DOCUMENT -> CategoryLimits(DOCUMENT_MAX_FILE_SIZE, DOCUMENT_TYPES)
+            FileCategory.GAME_ASSET -> CategoryLimits(contentMaxFileSize, IMAGE_TYPES + AUDIO_TYPES)
+            FileCategory.CONTENT -> CategoryLimits(contentMaxFileSize, contentMimeTypes)
+        }
+    }
+}
@@ -4,7 +4,9 @@ import com.wondernest.data.database.table.UploadedFiles
 import com.wondernest.domain.model.FileCategory
 import com.wondernest.domain.model.UploadedFile
 import com.wondernest.domain.model.User
+import com.wondernest.utils.ValidationUtils
 import kotlinx.coroutines.Dispatchers
+import kotlinx.coroutines.withContext
 import kotlinx.datetime.Clock
 import kotlinx.datetime.Instant
 import mu.KotlinLogging
@@ -12,6 +14,10 @@ import org.jetbrains.exposed.sql.*
 import org.jetbrains.exposed.sql.SqlExpressionBuilder.eq
 import org.jetbrains.exposed.sql.transactions.experimental.newSuspendedTransaction
 import java.io.InputStream
+import java.nio.file.Files
+import java.nio.file.Path
+import java.security.DigestInputStream
+import java.security.MessageDigest
 import java.util.UUID
 
 private val logger = KotlinLogging.logger {}
@@ -21,7 +27,8 @@ private val logger = KotlinLogging.logger {}
  */
 class FileUploadService(
     private val storageProvider: StorageProvider,
-    private val validationService: FileValidationService
+    private val validationService: FileValidationService,
+    private val fileAccessController: FileAccessController = FileAccessController()
 ) {
     
     /**
@@ -35,46 +42,58 @@ class FileUploadService(
         category: FileCategory = FileCategory.CONTENT,
         childId: UUID? = null,
         isPublic: Boolean = false,
-        metadata: Map<String, String> = emptyMap()
+        metadata: Map<String, String> = emptyMap(),
+        familyId: UUID? = null
     ): UploadedFile {
-        // Get file size
-        val fileSize = inputStream.available().toLong()
-        
-        // Validate file
-        val validationResult = validationService.validateFile(fileName, contentType, fileSize)
+        // Name and type first; the size is only known once the body has been read
+        val validationResult = validationService.validateFile(fileName, contentType, 0, category)
         if (!validationResult.isValid) {
-            throw IllegalArgumentException(validationResult.error ?: "File validation failed")
-        }
-        
-        // Validate file content (magic bytes)
-        if (inputStream.markSupported()) {
-            inputStream.mark(16)
-            if (!validationService.validateFileContent(inputStream, contentType)) {
-                throw IllegalArgumentException("File content does not match declared content type")
-            }
-            inputStream.reset()
+            val message = validationResult.error ?: "File validation failed"
+            throw validationResult.errorCode?.let { FileValidationException(it, message) }
+                ?: IllegalArgumentException(message)
         }
         
-        // Upload to storage provider
-        val storageResult = storageProvider.upload(
-            fileName = fileName,
-            contentType = contentType,
-            inputStream = inputStream,
-            metadata = metadata + mapOf(
-                "userId" to user.id.toString(),
-                "category" to category.toDbValue()
+        // Spool the body to a temp file, counting bytes as they arrive, so an oversized upload is
+        // rejected before anything reaches storage whatever the client claimed its size was. The
+        // SHA-256 for the download ETag is taken on the same pass.
+        val (spooled, contentHash) = spoolWithinLimit(inputStream, validationService.sizeLimitFor(category))
+        val (verifiedType, storageResult) = try {
+            // Validate file content (magic bytes) rather than trusting the declared type
+            val header = Files.newInputStream(spooled).use { it.readNBytes(MagicBytes.SNIFF_LENGTH) }
+            val verifiedType = validationService.verifyContentType(header, contentType, category)
+                ?: throw FileValidationException(
+                    FileValidationService.INVALID_MIME_TYPE,
+                    "File content does not match declared content type"
+                )
+            
+            // Stored as temp until the row below is saved, so the bucket's lifecycle rule expires
+            // objects whose upload failed before anything referenced them
+            val storageResult = storageProvider.upload(
+                fileName = fileName,
+                contentType = verifiedType,
+                inputStream = Files.newInputStream(spooled).buffered(),
+                metadata = metadata + mapOf(
+                    "userId" to user.id.toString(),
+                    "category" to category.toDbValue()
+                ),
+                tags = StorageTags.forUpload(category, familyId, lifecycle = StorageLifecycle.TEMP)
             )
-        )
+            verifiedType to storageResult
+        } finally {
+            withContext(Dispatchers.IO) { Files.deleteIfExists(spooled) }
+        }
         
         // Save to database
+        val originalFileName = ValidationUtils.normalizeFileName(fileName)
         return newSuspendedTransaction(Dispatchers.IO) {
             val uploadedFileId = UploadedFiles.insertAndGetId {
                 it[userId] = user.id
                 it[this.childId] = childId
                 it[fileKey] = storageResult.key
-                it[originalName] = fileName
-                it[mimeType] = contentType
+                it[originalName] = originalFileName
+                it[mimeType] = verifiedType
                 it[this.fileSize] = storageResult.size
+                it[this.contentHash] = contentHash
                 it[storageProvider] = "local"
                 it[url] = storageResult.url
                 it[this.isPublic] = isPublic
@@ -82,15 +101,20 @@ class FileUploadService(
                 it[this.metadata] = metadata
                 it[uploadedAt] = Clock.System.now()
             }
+            // Inside the transaction, so a failed promotion rolls the row back and the object expires
+            check(storageProvider.updateTags(storageResult.key, StorageTags.forUpload(category, familyId))) {
+                "Stored object ${storageResult.key} disappeared before it was saved"
+            }
             
             UploadedFile(
                 id = uploadedFileId.value,
                 userId = user.id,
                 childId = childId,
                 fileKey = storageResult.key,
-                originalName = fileName,
-                mimeType = contentType,
+                originalName = originalFileName,
+                mimeType = verifiedType,
                 fileSize = storageResult.size,
+                contentHash = contentHash,
                 storageProvider = "local",
                 url = storageResult.url,
                 isPublic = isPublic,
@@ -122,6 +146,7 @@ class FileUploadService(
                         originalName = row[UploadedFiles.originalName],
                         mimeType = row[UploadedFiles.mimeType],
                         fileSize = row[UploadedFiles.fileSize],
+                        contentHash = row[UploadedFiles.contentHash],
                         storageProvider = row[UploadedFiles.storageProvider],
                         url = row[UploadedFiles.url],
                         isPublic = row[UploadedFiles.isPublic],
@@ -136,19 +161,91 @@ class FileUploadService(
     }
     
     /**
-     * Download a file
+     * Get a file the user may view: their own, or a public file uploaded by someone in their family
      */
-    suspend fun downloadFile(fileId: UUID, userId: UUID): ByteArray? {
-        val file = getFile(fileId, userId) ?: return null
-        
+    suspend fun getViewableFile(fileId: UUID, userId: UUID): UploadedFile? {
+        if (!fileAccessController.canViewFile(userId, fileId)) return null
+        return newSuspendedTransaction(Dispatchers.IO) {
+            UploadedFiles
+                .select { (UploadedFiles.id eq fileId) and (UploadedFiles.deletedAt.isNull()) }
+                .singleOrNull()
+                ?.toUploadedFile()
+        }
+    }
+    
+    /**
+     * Make a file public or private. Only the owner can change visibility.
+     */
+    suspend fun setVisibility(fileId: UUID, userId: UUID, isPublic: Boolean): Boolean {
+        val updated = newSuspendedTransaction(Dispatchers.IO) {
+            UploadedFiles.update({
+                (UploadedFiles.id eq fileId) and
+                (UploadedFiles.userId eq userId) and
+                (UploadedFiles.deletedAt.isNull())
+            }) {
+                it[this.isPublic] = isPublic
+            }
+        }
+        if (updated > 0) {
+            fileAccessController.invalidateFile(fileId)
+        }
+        return updated > 0
+    }
+    
+    /**
+     * Open a file, or an inclusive byte [range] of it, for streaming to the client;
+     * the caller closes the stream
+     */
+    suspend fun downloadFile(fileId: UUID, userId: UUID, range: LongRange? = null): StorageDownload? {
+        val file = getViewableFile(fileId, userId) ?: return null
+        return openFile(file, range)
+    }
+    
+    /**
+     * Look up a file with no access check, for requests already authorised another way
+     * (a valid signed URL)
+     */
+    suspend fun getStoredFile(fileId: UUID): UploadedFile? {
+        return newSuspendedTransaction(Dispatchers.IO) {
+            UploadedFiles
+                .select { (UploadedFiles.id eq fileId) and (UploadedFiles.deletedAt.isNull()) }
+                .singleOrNull()
+                ?.toUploadedFile()
+        }
+    }
+    
+    /**
+     * Look up a public file by its storage key, for the unauthenticated /files links
+     */
+    suspend fun getPublicFileByKey(fileKey: String): UploadedFile? {
+        return newSuspendedTransaction(Dispatchers.IO) {
+            UploadedFiles
+                .select {
+                    (UploadedFiles.fileKey eq fileKey) and
+                        (UploadedFiles.isPublic eq true) and
+                        (UploadedFiles.deletedAt.isNull())
+                }
+                .singleOrNull()
+                ?.toUploadedFile()
+        }
+    }
+    
+    /**
+     * Open [file], or an inclusive byte [range] of it, and record the access
+     */
+    suspend fun openFile(file: UploadedFile, range: LongRange? = null): StorageDownload? {
         // Update accessed timestamp
         newSuspendedTransaction(Dispatchers.IO) {
-            UploadedFiles.update({ UploadedFiles.id eq fileId }) {
+            UploadedFiles.update({ UploadedFiles.id eq file.id }) {
                 it[accessedAt] = Clock.System.now()
             }
         }
         
-        return storageProvider.download(file.fileKey)
+        return if (range != null) {
+            storageProvider.downloadRange(file.fileKey, range.first, range.last)
+        } else {
+            storageProvider.downloadStream(file.fileKey)
+        }
     }
     
     /**
@@ -165,6 +262,7 @@ class FileUploadService(
             }
             
             if (updated > 0) {
+                fileAccessController.invalidateFile(fileId)
                 // Optionally delete from storage provider
                 val file = getFile(fileId, userId)
                 file?.let {
@@ -213,6 +311,7 @@ class FileUploadService(
                         originalName = row[UploadedFiles.originalName],
                         mimeType = row[UploadedFiles.mimeType],
                         fileSize = row[UploadedFiles.fileSize],
+                        contentHash = row[UploadedFiles.contentHash],
                         storageProvider = row[UploadedFiles.storageProvider],
                         url = row[UploadedFiles.url],
                         isPublic = row[UploadedFiles.isPublic],
@@ -233,4 +332,56 @@ class FileUploadService(
         val file = getFile(fileId, userId) ?: return null
         return storageProvider.getPresignedUrl(file.fileKey, expirationSeconds)
     }
-}
\ No newline at end of file
+    
+    /**
+     * Copies [input] to a temp file, failing once it passes [sizeLimit]. Returns the file and
+     * the hex SHA-256 of its content.
+     */
+    private suspend fun spoolWithinLimit(input: InputStream, sizeLimit: Long): Pair<Path, String> = withContext(Dispatchers.IO) {
+        val path = Files.createTempFile("wondernest-upload-", ".part")
+        val digest = MessageDigest.getInstance("SHA-256")
+        try {
+            DigestInputStream(input, digest).use { source ->
+                Files.newOutputStream(path).use { target ->
+                    val buffer = ByteArray(DEFAULT_BUFFER_SIZE)
+                    var total = 0L
+                    while (true) {
+                        val read = source.read(buffer)
+                        if (read < 0) break
+                        total += read
+                        if (total > sizeLimit) {
+                            throw FileValidationException(
+                                FileValidationService.FILE_TOO_LARGE,
+                                validationService.tooLargeMessage(sizeLimit)
+                            )
+                        }
+                        target.write(buffer, 0, read)
+                    }
+                }
+            }
+            path to digest.digest().joinToString("") { "%02x".format(it) }
+        } catch (e: Exception) {
+            Files.deleteIfExists(path)
+            throw e
+        }
+    }
+    
+    private fun ResultRow.toUploadedFile() = UploadedFile(
+        id = this[UploadedFiles.id].value,
+        userId = this[UploadedFiles.userId],
+        childId = this[UploadedFiles.childId],
+        fileKey = this[UploadedFiles.fileKey],
+        originalName = this[UploadedFiles.originalName],
+        mimeType = this[UploadedFiles.mimeType],
+        fileSize = this[UploadedFiles.fileSize],
+        contentHash = this[UploadedFiles.contentHash],
+        storageProvider = this[UploadedFiles.storageProvider],
+        url = this[UploadedFiles.url],
+        isPublic = this[UploadedFiles.isPublic],
+        category = FileCategory.fromString(this[UploadedFiles.category]),
+        metadata = this[UploadedFiles.metadata],
+        uploadedAt = this[UploadedFiles.uploadedAt],
+        accessedAt = this[UploadedFiles.accessedAt],
+        deletedAt = this[UploadedFiles.deletedAt]
+    )
+}
@@ -1,6 +1,8 @@
 package com.wondernest.services.storage
 
+import com.wondernest.domain.model.FileCategory
 import io.ktor.server.application.*
+import io.ktor.server.config.*
 import mu.KotlinLogging
 import java.io.InputStream
 
@@ -10,9 +12,9 @@ private val logger = KotlinLogging.logger {}
  * Service for validating uploaded files
  */
 class FileValidationService(
-    private val application: Application
+    private val config: ApplicationConfig = MapApplicationConfig()
 ) {
-    private val config = application.environment.config
+    constructor(application: Application) : this(application.environment.config)
     
     // Default max file size: 10MB
     private val maxFileSize: Long = try {
@@ -42,27 +44,64 @@ class FileValidationService(
         )
     }
     
+    // Upload categories clients may use, e.g. "profile_picture,artwork,audio"
+    val allowedCategories: Set<FileCategory> = config.propertyOrNull("storage.categories.allowed")
+        ?.getString()
+        ?.split(",")
+        ?.mapNotNull { FileCategory.fromStringOrNull(it) }
+        ?.toSet()
+        ?.takeIf { it.isNotEmpty() }
+        ?: FileCategory.entries.toSet()
+    
+    val uploadPolicy: FileUploadPolicy = FileUploadPolicy.fromConfig(config, maxFileSize, allowedMimeTypes)
+    
+    /**
+     * Resolve a client-supplied category against the allow-list.
+     * Missing values fall back to the default; unknown or disallowed values are rejected.
+     */
+    fun resolveCategory(value: String?, default: FileCategory = FileCategory.CONTENT): FileCategory {
+        if (value.isNullOrBlank()) return default
+        val category = FileCategory.fromStringOrNull(value)
+        if (category == null || category !in allowedCategories) {
+            throw UnknownFileCategoryException(value, allowedCategories)
+        }
+        return category
+    }
+    
+    fun policyFor(category: FileCategory): CategoryLimits = uploadPolicy.limitsFor(category)
+    
+    fun sizeLimitFor(category: FileCategory?): Long = category?.let { policyFor(it).maxFileSize } ?: maxFileSize
+    
+    fun tooLargeMessage(sizeLimit: Long) = "File size exceeds maximum allowed size of ${sizeLimit / (1024 * 1024)}MB"
+    
     /**
      * Validate file before upload
      */
     fun validateFile(
         fileName: String,
         contentType: String,
-        fileSize: Long
+        fileSize: Long,
+        category: FileCategory? = null
     ): ValidationResult {
+        val policy = category?.let { policyFor(it) }
+        val sizeLimit = sizeLimitFor(category)
+        val mimeTypes = policy?.allowedMimeTypes ?: allowedMimeTypes
+        
         // Check file size
-        if (fileSize > maxFileSize) {
+        if (fileSize > sizeLimit) {
             return ValidationResult(
                 isValid = false,
-                error = "File size exceeds maximum allowed size of ${maxFileSize / (1024 * 1024)}MB"
+                error = tooLargeMessage(sizeLimit),
+                errorCode = FILE_TOO_LARGE
             )
         }
         
         // Check MIME type
-        if (!allowedMimeTypes.contains(contentType)) {
+        if (!mimeTypes.contains(contentType)) {
             return ValidationResult(
                 isValid = false,
-                error = "File type '$contentType' is not allowed"
+                error = "File type '$contentType' is not allowed",
+                errorCode = INVALID_MIME_TYPE
             )
         }
         
@@ -71,7 +110,8 @@ class FileValidationService(
         if (!isExtensionValidForContentType(extension, contentType)) {
             return ValidationResult(
                 isValid = false,
-                error = "File extension does not match content type"
+                error = "File extension does not match content type",
+                errorCode = INVALID_MIME_TYPE
             )
         }
         
@@ -83,53 +123,38 @@ class FileValidationService(
      */
     fun validateFileContent(inputStream: InputStream, contentType: String): Boolean {
         return try {
-            val magicBytes = ByteArray(16)
-            val bytesRead = inputStream.read(magicBytes)
+            val header = inputStream.readNBytes(MagicBytes.SNIFF_LENGTH)
             inputStream.reset() // Reset stream for actual upload
-            
-            if (bytesRead < 4) {
-                return false
-            }
-            
-            when (contentType) {
-                "image/jpeg" -> {
-                    // JPEG magic bytes: FF D8 FF
-                    magicBytes[0] == 0xFF.toByte() && 
-                    magicBytes[1] == 0xD8.toByte() && 
-                    magicBytes[2] == 0xFF.toByte()
-                }
-                "image/png" -> {
-                    // PNG magic bytes: 89 50 4E 47
-                    magicBytes[0] == 0x89.toByte() && 
-                    magicBytes[1] == 0x50.toByte() && 
-                    magicBytes[2] == 0x4E.toByte() && 
-                    magicBytes[3] == 0x47.toByte()
-                }
-                "image/gif" -> {
-                    // GIF magic bytes: 47 49 46 38 (GIF8)
-                    magicBytes[0] == 0x47.toByte() && 
-                    magicBytes[1] == 0x49.toByte() && 
-                    magicBytes[2] == 0x46.toByte() && 
-                    magicBytes[3] == 0x38.toByte()
-                }
-                "application/pdf" -> {
-                    // PDF magic bytes: 25 50 44 46 (%PDF)
-                    magicBytes[0] == 0x25.toByte() && 
-                    magicBytes[1] == 0x50.toByte() && 
-                    magicBytes[2] == 0x44.toByte() && 
-                    magicBytes[3] == 0x46.toByte()
-                }
-                else -> {
-                    // For other types, skip magic byte validation
-                    true
-                }
-            }
+            verifyContentType(header, contentType) != null
         } catch (e: Exception) {
             logger.error(e) { "Error validating file content" }
             false
         }
     }
     
+    /**
+     * Compare the type sniffed from [header] with the client's [declaredType].
+     * Returns the content type to store, or null when the content is spoofed: executables are
+     * always rejected, and a recognised type must either match the declared one or be an allowed
+     * type of the same kind (a PNG sent as image/jpeg is stored as image/png).
+     */
+    fun verifyContentType(header: ByteArray, declaredType: String, category: FileCategory? = null): String? {
+        val detected = MagicBytes.detect(header)
+        if (detected in MagicBytes.EXECUTABLE_TYPES) {
+            logger.warn { "Rejected executable content declared as $declaredType" }
+            return null
+        }
+        if (detected == null) {
+            // Formats we can't sniff are trusted; formats we can must prove themselves
+            return declaredType.takeUnless { it in MagicBytes.knownTypes }
+        }
+        if (detected == declaredType) return detected
+        
+        val mimeTypes = category?.let { policyFor(it).allowedMimeTypes } ?: allowedMimeTypes
+        val sameKind = detected.substringBefore("/") == declaredType.substringBefore("/")
+        return detected.takeIf { sameKind && it in mimeTypes }
+    }
+    
     private fun isExtensionValidForContentType(extension: String, contentType: String): Boolean {
         return when (contentType) {
             "image/jpeg" -> extension in listOf("jpg", "jpeg")
@@ -147,6 +172,27 @@ class FileValidationService(
     
     data class ValidationResult(
         val isValid: Boolean,
-        val error: String? = null
+        val error: String? = null,
+        val errorCode: String? = null
     )
-}
\ No newline at end of file
+    
+    companion object {
+        const val FILE_TOO_LARGE = "FILE_TOO_LARGE"
+        const val INVALID_MIME_TYPE = "INVALID_MIME_TYPE"
+    }
+}
+
+/**
+ * Upload rejected by the category policy; [code] is returned to clients in the error response
+ */
+class FileValidationException(
+    val code: String,
+    message: String
+) : IllegalArgumentException(message)
+
+class UnknownFileCategoryException(
+    val category: String,
+    val allowedCategories: Set<FileCategory>
+) : IllegalArgumentException(
+    "Unknown upload category '$category'. Allowed categories: ${allowedCategories.joinToString(", ") { it.toDbValue() }}"
+)
\ No newline at end of file
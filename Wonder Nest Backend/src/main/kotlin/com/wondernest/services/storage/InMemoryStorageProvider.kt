@@ -0,0 +1,102 @@
+package com.wondernest.services.storage
+
+import kotlinx.coroutines.sync.Mutex
+import kotlinx.coroutines.sync.withLock
+import kotlinx.datetime.Clock
+import kotlinx.datetime.Instant
+import java.io.ByteArrayInputStream
+import java.io.InputStream
+import java.util.UUID
+
+/**
+ * Keeps objects in memory so upload and download paths can be tested without
+ * real storage. Presigned URLs are deterministic fakes and contents are lost on restart.
+ */
+class InMemoryStorageProvider(
+    private val baseUrl: String = "memory://storage",
+    private val clock: () -> Instant = { Clock.System.now() }
+) : StorageProvider {
+
+    private class StoredObject(val bytes: ByteArray, val metadata: FileMetadata)
+
+    private val mutex = Mutex()
+    private val objects = mutableMapOf<String, StoredObject>()
+
+    override suspend fun upload(
+        fileName: String,
+        contentType: String,
+        inputStream: InputStream,
+        metadata: Map<String, String>,
+        tags: Map<String, String>
+    ): StorageResult {
+        StorageTags.validate(tags)
+        val bytes = inputStream.use { it.readBytes() }
+        val key = generateKey(fileName)
+        val fileMetadata = FileMetadata(
+            key = key,
+            size = bytes.size.toLong(),
+            contentType = contentType,
+            lastModified = clock(),
+            metadata = metadata,
+            tags = tags
+        )
+        mutex.withLock { objects[key] = StoredObject(bytes, fileMetadata) }
+
+        return StorageResult(
+            key = key,
+            url = "$baseUrl/$key",
+            size = fileMetadata.size,
+            contentType = contentType,
+            metadata = metadata,
+            tags = tags
+        )
+    }
+
+    override suspend fun download(key: String): ByteArray? = find(key)?.bytes?.copyOf()
+
+    override suspend fun downloadStream(key: String): StorageDownload? =
+        find(key)?.let { StorageDownload(ByteArrayInputStream(it.bytes), it.bytes.size.toLong()) }
+
+    override suspend fun downloadRange(key: String, start: Long, endInclusive: Long): StorageDownload? {
+        val stored = find(key) ?: return null
+        val from = start.coerceIn(0, stored.bytes.size.toLong()).toInt()
+        val to = (endInclusive + 1).coerceIn(from.toLong(), stored.bytes.size.toLong()).toInt()
+        return StorageDownload(ByteArrayInputStream(stored.bytes, from, to - from), (to - from).toLong())
+    }
+
+    override suspend fun getPresignedUrl(key: String, expirationSeconds: Int): String? =
+        find(key)?.let { "$baseUrl/$key?expires=$expirationSeconds" }
+
+    override suspend fun delete(key: String): Boolean = mutex.withLock { objects.remove(key) != null }
+
+    override suspend fun exists(key: String): Boolean = find(key) != null
+
+    override suspend fun getMetadata(key: String): FileMetadata? = find(key)?.metadata
+
+    override suspend fun updateTags(key: String, tags: Map<String, String>): Boolean {
+        StorageTags.validate(tags)
+        return mutex.withLock {
+            val stored = objects[key] ?: return@withLock false
+            objects[key] = StoredObject(stored.bytes, stored.metadata.copy(tags = tags))
+            true
+        }
+    }
+
+    override suspend fun listFiles(prefix: String?, maxResults: Int): List<FileMetadata> = mutex.withLock {
+        objects.values
+            .map { it.metadata }
+            .filter { prefix == null || it.key.startsWith(prefix) }
+            .sortedBy { it.key }
+            .take(maxResults)
+    }
+
+    override suspend fun healthCheck(): Boolean = true
+
+    private suspend fun find(key: String): StoredObject? = mutex.withLock { objects[key] }
+
+    private fun generateKey(fileName: String): String {
+        val extension = fileName.substringAfterLast(".", "")
+        val name = if (extension.isNotEmpty()) "${UUID.randomUUID()}.$extension" else UUID.randomUUID().toString()
+        return "${clock().epochSeconds}/$name"
+    }
+}
@@ -6,7 +6,9 @@ import kotlinx.datetime.Clock
 import kotlinx.datetime.Instant
 import mu.KotlinLogging
 import java.io.File
+import java.io.FilterInputStream
 import java.io.InputStream
+import java.nio.channels.Channels
 import java.nio.file.Files
 import java.nio.file.Path
 import java.nio.file.Paths
@@ -16,14 +18,16 @@ import java.util.UUID
 private val logger = KotlinLogging.logger {}
 
 /**
- * Local file system storage provider for development
+ * Stores files under [basePath] with the storage key as the relative path and metadata/tags
+ * in sidecar files. Used in development and by self-hosters without S3-compatible storage
+ * (STORAGE_PROVIDER=filesystem).
  */
 class LocalStorageProvider(
     private val basePath: String = "./uploads",
     private val baseUrl: String = "http://localhost:8080"
 ) : StorageProvider {
     
-    private val rootPath: Path = Paths.get(basePath).toAbsolutePath()
+    private val rootPath: Path = Paths.get(basePath).toAbsolutePath().normalize()
     
     init {
         // Ensure base directory exists
@@ -37,11 +41,13 @@ class LocalStorageProvider(
         fileName: String,
         contentType: String,
         inputStream: InputStream,
-        metadata: Map<String, String>
+        metadata: Map<String, String>,
+        tags: Map<String, String>
     ): StorageResult = withContext(Dispatchers.IO) {
+        StorageTags.validate(tags)
         try {
             val key = generateKey(fileName)
-            val filePath = rootPath.resolve(key)
+            val filePath = resolve(key)
             
             // Ensure parent directories exist
             Files.createDirectories(filePath.parent)
@@ -53,6 +59,7 @@ class LocalStorageProvider(
             
             // Save metadata as JSON file
             saveMetadata(key, contentType, metadata)
+            saveTags(key, tags)
             
             logger.info { "File uploaded locally: $key ($size bytes)" }
             
@@ -61,7 +68,8 @@ class LocalStorageProvider(
                 url = "$baseUrl/files/$key",
                 size = size,
                 contentType = contentType,
-                metadata = metadata
+                metadata = metadata,
+                tags = tags
             )
         } catch (e: Exception) {
             logger.error(e) { "Failed to upload file: $fileName" }
@@ -70,8 +78,8 @@ class LocalStorageProvider(
     }
     
     override suspend fun download(key: String): ByteArray? = withContext(Dispatchers.IO) {
+        val filePath = resolve(key)
         try {
-            val filePath = rootPath.resolve(key)
             if (Files.exists(filePath)) {
                 Files.readAllBytes(filePath)
             } else {
@@ -84,6 +92,39 @@ class LocalStorageProvider(
         }
     }
     
+    override suspend fun downloadStream(key: String): StorageDownload? = withContext(Dispatchers.IO) {
+        val filePath = resolve(key)
+        try {
+            if (Files.exists(filePath)) {
+                StorageDownload(Files.newInputStream(filePath), Files.size(filePath))
+            } else {
+                logger.warn { "File not found: $key" }
+                null
+            }
+        } catch (e: Exception) {
+            logger.error(e) { "Failed to open file: $key" }
+            null
+        }
+    }
+    
+    override suspend fun downloadRange(key: String, start: Long, endInclusive: Long): StorageDownload? =
+        withContext(Dispatchers.IO) {
+            val filePath = resolve(key)
+            try {
+                if (Files.exists(filePath)) {
+                    val channel = Files.newByteChannel(filePath).position(start)
+                    val length = endInclusive - start + 1
+                    StorageDownload(BoundedInputStream(Channels.newInputStream(channel), length), length)
+                } else {
+                    logger.warn { "File not found: $key" }
+                    null
+                }
+            } catch (e: Exception) {
+                logger.error(e) { "Failed to open range $start-$endInclusive of file: $key" }
+                null
+            }
+        }
+    
     override suspend fun getPresignedUrl(key: String, expirationSeconds: Int): String? {
         // For local storage, just return the direct URL
         return if (exists(key)) {
@@ -94,10 +135,10 @@ class LocalStorageProvider(
     }
     
     override suspend fun delete(key: String): Boolean = withContext(Dispatchers.IO) {
+        val filePath = resolve(key)
+        val metadataPath = resolve("$key.metadata")
+        val tagsPath = resolve("$key.tags")
         try {
-            val filePath = rootPath.resolve(key)
-            val metadataPath = rootPath.resolve("$key.metadata")
-            
             var deleted = false
             if (Files.exists(filePath)) {
                 Files.delete(filePath)
@@ -106,6 +147,7 @@ class LocalStorageProvider(
             if (Files.exists(metadataPath)) {
                 Files.delete(metadataPath)
             }
+            Files.deleteIfExists(tagsPath)
             
             if (deleted) {
                 logger.info { "File deleted: $key" }
@@ -118,17 +160,17 @@ class LocalStorageProvider(
     }
     
     override suspend fun exists(key: String): Boolean = withContext(Dispatchers.IO) {
-        Files.exists(rootPath.resolve(key))
+        Files.exists(resolve(key))
     }
     
     override suspend fun getMetadata(key: String): FileMetadata? = withContext(Dispatchers.IO) {
+        val filePath = resolve(key)
         try {
-            val filePath = rootPath.resolve(key)
             if (!Files.exists(filePath)) {
                 return@withContext null
             }
             
-            val metadataPath = rootPath.resolve("$key.metadata")
+            val metadataPath = resolve("$key.metadata")
             val metadata = if (Files.exists(metadataPath)) {
                 val content = Files.readString(metadataPath)
                 parseMetadata(content)
@@ -143,7 +185,8 @@ class LocalStorageProvider(
                 size = size,
                 contentType = metadata["contentType"] ?: "application/octet-stream",
                 lastModified = Instant.fromEpochMilliseconds(lastModified.toMillis()),
-                metadata = metadata
+                metadata = metadata,
+                tags = readTags(key)
             )
         } catch (e: Exception) {
             logger.error(e) { "Failed to get metadata for file: $key" }
@@ -151,12 +194,21 @@ class LocalStorageProvider(
         }
     }
     
+    override suspend fun updateTags(key: String, tags: Map<String, String>): Boolean = withContext(Dispatchers.IO) {
+        StorageTags.validate(tags)
+        if (!Files.exists(resolve(key))) {
+            return@withContext false
+        }
+        saveTags(key, tags)
+        true
+    }
+    
     override suspend fun listFiles(prefix: String?, maxResults: Int): List<FileMetadata> = withContext(Dispatchers.IO) {
         try {
             val files = mutableListOf<FileMetadata>()
             val paths = Files.walk(rootPath)
                 .filter { Files.isRegularFile(it) }
-                .filter { !it.fileName.toString().endsWith(".metadata") }
+                .filter { !it.fileName.toString().endsWith(".metadata") && !it.fileName.toString().endsWith(".tags") }
                 .filter { path ->
                     prefix?.let { p ->
                         rootPath.relativize(path).toString().startsWith(p)
@@ -176,6 +228,24 @@ class LocalStorageProvider(
         }
     }
     
+    override suspend fun healthCheck(): Boolean = withContext(Dispatchers.IO) {
+        Files.isDirectory(rootPath) && Files.isWritable(rootPath)
+    }
+    
+    /**
+     * Maps a storage key to its file under [rootPath]. Keys come from URLs, so anything that
+     * could escape the root (absolute paths, '..' segments, backslashes) is rejected.
+     */
+    private fun resolve(key: String): Path {
+        require(key.isNotBlank() && !key.startsWith("/") && '\\' !in key && '\u0000' !in key) {
+            "Invalid storage key: $key"
+        }
+        require(key.split("/").none { it == ".." || it == "." }) { "Invalid storage key: $key" }
+        val path = rootPath.resolve(key).normalize()
+        require(path.startsWith(rootPath) && path != rootPath) { "Invalid storage key: $key" }
+        return path
+    }
+    
     private fun generateKey(fileName: String): String {
         val uuid = UUID.randomUUID().toString()
         val extension = fileName.substringAfterLast(".", "")
@@ -190,7 +260,7 @@ class LocalStorageProvider(
     
     private suspend fun saveMetadata(key: String, contentType: String, metadata: Map<String, String>) = withContext(Dispatchers.IO) {
         try {
-            val metadataPath = rootPath.resolve("$key.metadata")
+            val metadataPath = resolve("$key.metadata")
             Files.createDirectories(metadataPath.parent)
             
             val allMetadata = metadata + mapOf("contentType" to contentType)
@@ -201,6 +271,22 @@ class LocalStorageProvider(
         }
     }
     
+    // Tags live in their own sidecar so they can be replaced without touching metadata
+    private fun saveTags(key: String, tags: Map<String, String>) {
+        val tagsPath = resolve("$key.tags")
+        if (tags.isEmpty()) {
+            Files.deleteIfExists(tagsPath)
+            return
+        }
+        Files.createDirectories(tagsPath.parent)
+        Files.writeString(tagsPath, tags.entries.joinToString("\n") { "${it.key}=${it.value}" })
+    }
+    
+    private fun readTags(key: String): Map<String, String> {
+        val tagsPath = resolve("$key.tags")
+        return if (Files.exists(tagsPath)) parseMetadata(Files.readString(tagsPath)) else emptyMap()
+    }
+    
     private fun parseMetadata(content: String): Map<String, String> {
         return content.lines()
             .filter { it.contains("=") }
@@ -209,4 +295,23 @@ class LocalStorageProvider(
                 parts[0] to (parts.getOrNull(1) ?: "")
             }
     }
-}
\ No newline at end of file
+}
+
+/**
+ * Reads at most [limit] bytes from the wrapped stream
+ */
+private class BoundedInputStream(input: InputStream, private var limit: Long) : FilterInputStream(input) {
+    override fun read(): Int {
+        if (limit <= 0) return -1
+        val byte = super.read()
+        if (byte >= 0) limit--
+        return byte
+    }
+
+    override fun read(b: ByteArray, off: Int, len: Int): Int {
+        if (limit <= 0) return -1
+        val count = super.read(b, off, minOf(len.toLong(), limit).toInt())
+        if (count > 0) limit -= count
+        return count
+    }
+}
@@ -0,0 +1,56 @@
+package com.wondernest.services.storage
+
+/**
+ * Identifies a file's real type from its leading bytes, regardless of the name or the
+ * content type the client sent.
+ */
+object MagicBytes {
+    /** Bytes needed to recognise every signature below */
+    const val SNIFF_LENGTH = 16
+
+    val EXECUTABLE_TYPES = setOf(
+        "application/x-msdownload",
+        "application/x-executable",
+        "application/x-mach-binary",
+        "application/x-sh"
+    )
+
+    private class Signature(val mimeType: String, val offset: Int, val bytes: ByteArray)
+
+    private fun signature(mimeType: String, vararg bytes: Int, offset: Int = 0) =
+        Signature(mimeType, offset, ByteArray(bytes.size) { bytes[it].toByte() })
+
+    private fun signature(mimeType: String, text: String, offset: Int = 0) =
+        Signature(mimeType, offset, text.toByteArray(Charsets.US_ASCII))
+
+    private val signatures = listOf(
+        signature("image/png", 0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A),
+        signature("image/jpeg", 0xFF, 0xD8, 0xFF),
+        signature("image/gif", "GIF8"),
+        signature("image/webp", "WEBP", offset = 8),
+        signature("audio/wav", "WAVE", offset = 8),
+        signature("application/pdf", "%PDF"),
+        signature("audio/mpeg", "ID3"),
+        signature("audio/mpeg", 0xFF, 0xFB),
+        signature("audio/mpeg", 0xFF, 0xF3),
+        signature("audio/mpeg", 0xFF, 0xF2),
+        signature("video/mp4", "ftyp", offset = 4),
+        signature("video/webm", 0x1A, 0x45, 0xDF, 0xA3),
+        signature("application/x-msdownload", "MZ"),
+        signature("application/x-executable", 0x7F, 0x45, 0x4C, 0x46),
+        signature("application/x-mach-binary", 0xFE, 0xED, 0xFA, 0xCE),
+        signature("application/x-mach-binary", 0xFE, 0xED, 0xFA, 0xCF),
+        signature("application/x-mach-binary", 0xCF, 0xFA, 0xED, 0xFE),
+        signature("application/x-sh", "#!")
+    )
+
+    // Types we can recognise; a declared type in this set must be confirmed by the content
+    val knownTypes: Set<String> = signatures.map { it.mimeType }.toSet()
+
+    fun detect(header: ByteArray): String? = signatures.firstOrNull { it.matches(header) }?.mimeType
+
+    private fun Signature.matches(header: ByteArray): Boolean {
+        if (header.size < offset + bytes.size) return false
+        return bytes.indices.all { header[offset + it] == bytes[it] }
+    }
+}
@@ -0,0 +1,123 @@
+package com.wondernest.services.storage
+
+import kotlinx.coroutines.delay
+import mu.KotlinLogging
+import java.io.FileNotFoundException
+import java.io.IOException
+import java.io.InputStream
+import java.nio.file.AccessDeniedException
+import java.nio.file.NoSuchFileException
+import kotlin.random.Random
+
+private val logger = KotlinLogging.logger {}
+
+data class StorageRetryConfig(
+    val maxRetries: Int = DEFAULT_MAX_RETRIES,
+    val baseDelayMillis: Long = DEFAULT_BASE_DELAY_MILLIS,
+    val maxDelayMillis: Long = DEFAULT_MAX_DELAY_MILLIS
+) {
+    companion object {
+        const val DEFAULT_MAX_RETRIES = 3
+        const val DEFAULT_BASE_DELAY_MILLIS = 100L
+        const val DEFAULT_MAX_DELAY_MILLIS = 2_000L
+
+        /**
+         * Reads STORAGE_MAX_RETRIES and STORAGE_RETRY_BASE_DELAY_MS
+         */
+        fun fromEnvironment(env: Map<String, String> = System.getenv()) = StorageRetryConfig(
+            maxRetries = env["STORAGE_MAX_RETRIES"]?.toIntOrNull()?.takeIf { it >= 0 } ?: DEFAULT_MAX_RETRIES,
+            baseDelayMillis = env["STORAGE_RETRY_BASE_DELAY_MS"]?.toLongOrNull()?.takeIf { it > 0 }
+                ?: DEFAULT_BASE_DELAY_MILLIS
+        )
+    }
+}
+
+/**
+ * Retries transient failures (timeouts, connection errors, [StorageException]s marked retryable
+ * such as a 5xx from the backend) with exponential backoff and full jitter. Missing objects
+ * come back as null and permission or validation errors are rethrown at once, so neither is retried.
+ *
+ * Uploads are only retried when the stream can be rewound ([InputStream.markSupported]).
+ */
+class RetryingStorageProvider(
+    private val delegate: StorageProvider,
+    private val config: StorageRetryConfig = StorageRetryConfig(),
+    private val random: Random = Random.Default,
+    private val sleep: suspend (Long) -> Unit = { delay(it) }
+) : StorageProvider {
+
+    override suspend fun upload(
+        fileName: String,
+        contentType: String,
+        inputStream: InputStream,
+        metadata: Map<String, String>,
+        tags: Map<String, String>
+    ): StorageResult {
+        if (!inputStream.markSupported()) {
+            return delegate.upload(fileName, contentType, inputStream, metadata, tags)
+        }
+        inputStream.mark(Int.MAX_VALUE)
+        var first = true
+        return withRetry("upload $fileName") {
+            if (!first) inputStream.reset()
+            first = false
+            delegate.upload(fileName, contentType, NonClosingInputStream(inputStream), metadata, tags)
+        }.also { inputStream.close() }
+    }
+
+    override suspend fun download(key: String) = withRetry("download $key") { delegate.download(key) }
+
+    override suspend fun downloadStream(key: String) = withRetry("download $key") { delegate.downloadStream(key) }
+
+    override suspend fun downloadRange(key: String, start: Long, endInclusive: Long) =
+        withRetry("download $key") { delegate.downloadRange(key, start, endInclusive) }
+
+    override suspend fun getPresignedUrl(key: String, expirationSeconds: Int) =
+        withRetry("presign $key") { delegate.getPresignedUrl(key, expirationSeconds) }
+
+    override suspend fun delete(key: String) = withRetry("delete $key") { delegate.delete(key) }
+
+    override suspend fun exists(key: String) = withRetry("exists $key") { delegate.exists(key) }
+
+    override suspend fun getMetadata(key: String) = withRetry("metadata $key") { delegate.getMetadata(key) }
+
+    override suspend fun updateTags(key: String, tags: Map<String, String>) =
+        withRetry("tag $key") { delegate.updateTags(key, tags) }
+
+    override suspend fun listFiles(prefix: String?, maxResults: Int) =
+        withRetry("list $prefix") { delegate.listFiles(prefix, maxResults) }
+
+    // Not retried: the probe has its own timeout and should report what it sees
+    override suspend fun healthCheck() = delegate.healthCheck()
+
+    private suspend fun <T> withRetry(operation: String, block: suspend () -> T): T {
+        var attempt = 0
+        while (true) {
+            try {
+                return block()
+            } catch (e: Exception) {
+                if (attempt >= config.maxRetries || !isRetryable(e)) throw e
+                // Full jitter: anywhere between 0 and the exponential ceiling
+                val ceiling = minOf(config.maxDelayMillis, config.baseDelayMillis shl minOf(attempt, 20))
+                val wait = random.nextLong(ceiling + 1)
+                attempt++
+                logger.warn { "Storage $operation failed (${e.message}), retry $attempt/${config.maxRetries} in ${wait}ms" }
+                sleep(wait)
+            }
+        }
+    }
+
+    companion object {
+        fun isRetryable(e: Throwable): Boolean = when (e) {
+            is StorageException -> e.retryable || (e.cause?.let(::isRetryable) ?: false)
+            is FileNotFoundException, is NoSuchFileException, is AccessDeniedException -> false
+            is IOException -> true
+            else -> false
+        }
+    }
+}
+
+// Lets the delegate close what it was given without closing the stream we may rewind
+private class NonClosingInputStream(input: InputStream) : java.io.FilterInputStream(input) {
+    override fun close() = Unit
+}
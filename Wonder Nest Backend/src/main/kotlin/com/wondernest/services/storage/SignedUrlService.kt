@@ -0,0 +1,212 @@
+package com.wondernest.services.storage
+
+import com.wondernest.services.resilience.RedisConnections
+import com.wondernest.services.resilience.RedisFeature
+import com.wondernest.services.resilience.RedisGuard
+import io.lettuce.core.SetArgs
+import io.lettuce.core.api.StatefulRedisConnection
+import kotlinx.coroutines.future.await
+import kotlinx.datetime.Clock
+import kotlinx.datetime.Instant
+import mu.KotlinLogging
+import java.net.URI
+import java.net.URISyntaxException
+import java.security.MessageDigest
+import java.security.SecureRandom
+import java.util.Base64
+import java.util.UUID
+import java.util.concurrent.ConcurrentHashMap
+import javax.crypto.Mac
+import javax.crypto.spec.SecretKeySpec
+
+private val logger = KotlinLogging.logger {}
+
+/**
+ * @param publicBaseUrl scheme://host[:port] links are built on; when null they use the
+ * requesting host instead
+ */
+data class SignedUrlConfig(
+    val defaultExpirySeconds: Long = DEFAULT_EXPIRY_SECONDS,
+    val maxExpirySeconds: Long = DEFAULT_MAX_EXPIRY_SECONDS,
+    val publicBaseUrl: String? = null
+) {
+    companion object {
+        const val DEFAULT_EXPIRY_SECONDS = 86_400L
+        const val DEFAULT_MAX_EXPIRY_SECONDS = 7 * 86_400L
+
+        /**
+         * Reads SIGNED_URL_DEFAULT_EXPIRY_SECONDS, SIGNED_URL_MAX_EXPIRY_SECONDS and PUBLIC_BASE_URL.
+         * Throws [IllegalArgumentException] when PUBLIC_BASE_URL isn't an absolute http(s) URL.
+         */
+        fun fromEnvironment(env: Map<String, String> = System.getenv()): SignedUrlConfig {
+            val max = env["SIGNED_URL_MAX_EXPIRY_SECONDS"]?.toLongOrNull()?.takeIf { it > 0 }
+                ?: DEFAULT_MAX_EXPIRY_SECONDS
+            val default = env["SIGNED_URL_DEFAULT_EXPIRY_SECONDS"]?.toLongOrNull()?.takeIf { it > 0 }
+                ?: DEFAULT_EXPIRY_SECONDS
+            return SignedUrlConfig(
+                defaultExpirySeconds = minOf(default, max),
+                maxExpirySeconds = max,
+                publicBaseUrl = env["PUBLIC_BASE_URL"]?.takeIf { it.isNotBlank() }?.let(::parseBaseUrl)
+            )
+        }
+
+        fun parseBaseUrl(value: String): String {
+            val uri = try {
+                URI(value.trim())
+            } catch (e: URISyntaxException) {
+                throw IllegalArgumentException("Invalid PUBLIC_BASE_URL '$value': ${e.message}")
+            }
+            require((uri.scheme == "http" || uri.scheme == "https") && !uri.host.isNullOrEmpty()) {
+                "Invalid PUBLIC_BASE_URL '$value': expected an absolute http(s) URL"
+            }
+            require(uri.rawQuery == null && uri.rawFragment == null) {
+                "Invalid PUBLIC_BASE_URL '$value': must not contain a query or fragment"
+            }
+            return uri.toString().trimEnd('/')
+        }
+    }
+}
+
+enum class SignedUrlValidation { VALID, INVALID, EXPIRED, ALREADY_USED }
+
+/**
+ * Nonces of single-use links that haven't been used yet
+ */
+interface SignedUrlNonceStore {
+    suspend fun register(nonce: String, ttlSeconds: Long)
+
+    /** True exactly once for a registered nonce; false when unknown, used or expired */
+    suspend fun consume(nonce: String): Boolean
+}
+
+class RedisSignedUrlNonceStore(
+    private val connection: () -> StatefulRedisConnection<String, String> = { RedisConnections.shared }
+) : SignedUrlNonceStore {
+    override suspend fun register(nonce: String, ttlSeconds: Long) {
+        connection().async().set(key(nonce), "1", SetArgs().ex(ttlSeconds)).await()
+    }
+
+    // GETDEL is atomic, so two concurrent requests can't both consume the nonce
+    override suspend fun consume(nonce: String): Boolean =
+        connection().async().getdel(key(nonce)).await() != null
+
+    private fun key(nonce: String) = "signed-url:nonce:$nonce"
+}
+
+class InMemorySignedUrlNonceStore(
+    private val clock: () -> Long = System::currentTimeMillis
+) : SignedUrlNonceStore {
+    private val expiries = ConcurrentHashMap<String, Long>()
+
+    override suspend fun register(nonce: String, ttlSeconds: Long) {
+        expiries[nonce] = clock() + ttlSeconds * 1000
+    }
+
+    override suspend fun consume(nonce: String): Boolean {
+        val expiresAt = expiries.remove(nonce) ?: return false
+        return clock() < expiresAt
+    }
+}
+
+/**
+ * Time-limited download links for files that are otherwise only reachable with a session,
+ * signed with HMAC-SHA256 over the file id, expiry and, for single-use links, a nonce.
+ * Single-use nonces live in [nonceStore] behind [RedisGuard]; with Redis down they are
+ * neither issued nor accepted unless REDIS_FAILURE_MODE_SIGNED_URL_NONCES=open.
+ */
+class SignedUrlService(
+    secret: ByteArray,
+    private val config: SignedUrlConfig = SignedUrlConfig(),
+    private val nonceStore: SignedUrlNonceStore = InMemorySignedUrlNonceStore(),
+    private val redisGuard: RedisGuard = RedisGuard(),
+    private val clock: () -> Instant = { Clock.System.now() }
+) {
+    private val key = SecretKeySpec(secret, ALGORITHM)
+    private val random = SecureRandom()
+
+    /**
+     * Link to GET /api/v1/files/{fileId}/signed-download, valid for [expiresInSeconds].
+     * A [singleUse] link stops working after its first successful download. The link is built
+     * on the configured public base URL, else on [requestBaseUrl], else left relative.
+     */
+    suspend fun generateSignedUrl(
+        fileId: UUID,
+        expiresInSeconds: Long = config.defaultExpirySeconds,
+        singleUse: Boolean = false,
+        requestBaseUrl: String? = null
+    ): String {
+        require(expiresInSeconds in 1..config.maxExpirySeconds) {
+            "Signed URL expiry must be between 1 and ${config.maxExpirySeconds} seconds"
+        }
+        val expires = clock().epochSeconds + expiresInSeconds
+        val nonce = if (singleUse) newNonce() else null
+        if (nonce != null) {
+            redisGuard.execute(RedisFeature.SIGNED_URL_NONCES, fallback = {}) {
+                nonceStore.register(nonce, expiresInSeconds)
+            }
+        }
+        val nonceParam = nonce?.let { "&nonce=$it" }.orEmpty()
+        val base = (config.publicBaseUrl ?: requestBaseUrl)?.trimEnd('/').orEmpty()
+        return "$base/api/v1/files/$fileId/signed-download?expires=$expires$nonceParam&signature=${sign(fileId, expires, nonce)}"
+    }
+
+    /**
+     * Checks the signature and expiry; a valid single-use link is consumed by this call unless
+     * [consume] is false, in which case the caller consumes it with [consumeNonce]
+     */
+    suspend fun validateSignedUrl(
+        fileId: UUID,
+        expires: Long?,
+        signature: String?,
+        nonce: String? = null,
+        consume: Boolean = true
+    ): SignedUrlValidation {
+        if (expires == null || signature == null) return SignedUrlValidation.INVALID
+        val expected = sign(fileId, expires, nonce).toByteArray()
+        if (!MessageDigest.isEqual(expected, signature.toByteArray())) return SignedUrlValidation.INVALID
+        if (clock().epochSeconds >= expires) return SignedUrlValidation.EXPIRED
+        if (nonce != null && consume && !consumeNonce(nonce)) return SignedUrlValidation.ALREADY_USED
+        return SignedUrlValidation.VALID
+    }
+
+    /**
+     * Uses up the nonce of a single-use link; false when it was already used or has expired
+     */
+    suspend fun consumeNonce(nonce: String): Boolean =
+        redisGuard.execute(RedisFeature.SIGNED_URL_NONCES, fallback = { true }) {
+            nonceStore.consume(nonce)
+        }
+
+    private fun sign(fileId: UUID, expires: Long, nonce: String?): String {
+        val mac = Mac.getInstance(ALGORITHM).apply { init(key) }
+        val payload = if (nonce != null) "$fileId:$expires:$nonce" else "$fileId:$expires"
+        return Base64.getUrlEncoder().withoutPadding().encodeToString(mac.doFinal(payload.toByteArray()))
+    }
+
+    private fun newNonce(): String =
+        Base64.getUrlEncoder().withoutPadding().encodeToString(ByteArray(16).also { random.nextBytes(it) })
+
+    companion object {
+        private const val ALGORITHM = "HmacSHA256"
+
+        /**
+         * Signs with the Base64 key in SIGNED_URL_SECRET. Startup fails without it unless
+         * KTOR_ENV=development, where each run signs with its own random key.
+         */
+        fun fromEnvironment(
+            nonceStore: SignedUrlNonceStore,
+            redisGuard: RedisGuard,
+            env: Map<String, String> = System.getenv()
+        ): SignedUrlService {
+            val config = SignedUrlConfig.fromEnvironment(env)
+            val secret = env["SIGNED_URL_SECRET"]?.takeIf { it.isNotBlank() }
+                ?.let { Base64.getDecoder().decode(it.trim()) }
+                ?: ByteArray(32).also {
+                    check(env["KTOR_ENV"] == "development") { "SIGNED_URL_SECRET must be set outside development" }
+                    logger.warn { "SIGNED_URL_SECRET is not set; using a temporary key" }
+                    SecureRandom().nextBytes(it)
+                }
+            return SignedUrlService(secret, config, nonceStore, redisGuard)
+        }
+    }
+}
@@ -11,11 +11,24 @@ interface StorageProvider {
         fileName: String,
         contentType: String,
         inputStream: InputStream,
-        metadata: Map<String, String> = emptyMap()
+        metadata: Map<String, String> = emptyMap(),
+        tags: Map<String, String> = emptyMap()
     ): StorageResult
     
     suspend fun download(key: String): ByteArray?
     
+    /**
+     * Open the object for streaming without loading it into memory. The caller must
+     * close [StorageDownload.stream]. Returns null when the object doesn't exist.
+     */
+    suspend fun downloadStream(key: String): StorageDownload?
+    
+    /**
+     * Stream bytes [start]..[endInclusive] of the object, for HTTP Range requests.
+     * Returns null when the object doesn't exist.
+     */
+    suspend fun downloadRange(key: String, start: Long, endInclusive: Long): StorageDownload?
+    
     suspend fun getPresignedUrl(key: String, expirationSeconds: Int = 3600): String?
     
     suspend fun delete(key: String): Boolean
@@ -24,7 +37,18 @@ interface StorageProvider {
     
     suspend fun getMetadata(key: String): FileMetadata?
     
+    /**
+     * Replace the object's tags (see [StorageTags]). Returns false when the object doesn't exist.
+     */
+    suspend fun updateTags(key: String, tags: Map<String, String>): Boolean
+    
     suspend fun listFiles(prefix: String? = null, maxResults: Int = 100): List<FileMetadata>
+    
+    /**
+     * Cheap check that the backend is reachable and writable, for the readiness probe.
+     * Returns false or throws when it isn't.
+     */
+    suspend fun healthCheck(): Boolean
 }
 
 /**
@@ -35,7 +59,16 @@ data class StorageResult(
     val url: String? = null,
     val size: Long,
     val contentType: String,
-    val metadata: Map<String, String> = emptyMap()
+    val metadata: Map<String, String> = emptyMap(),
+    val tags: Map<String, String> = emptyMap()
+)
+
+/**
+ * An open object stream; [size] is null when the provider can't report it up front
+ */
+class StorageDownload(
+    val stream: InputStream,
+    val size: Long? = null
 )
 
 /**
@@ -46,10 +79,15 @@ data class FileMetadata(
     val size: Long,
     val contentType: String,
     val lastModified: Instant,
-    val metadata: Map<String, String> = emptyMap()
+    val metadata: Map<String, String> = emptyMap(),
+    val tags: Map<String, String> = emptyMap()
 )
 
 /**
- * Storage exception
+ * Storage exception; [retryable] marks transient backend failures (timeouts, 5xx responses)
  */
-class StorageException(message: String, cause: Throwable? = null) : Exception(message, cause)
\ No newline at end of file
+class StorageException(
+    message: String,
+    cause: Throwable? = null,
+    val retryable: Boolean = false
+) : Exception(message, cause)
\ No newline at end of file
@@ -11,9 +11,12 @@ private val logger = KotlinLogging.logger {}
 class StorageProviderFactory {
     
     companion object {
-        fun create(application: Application): StorageProvider {
+        /**
+         * STORAGE_PROVIDER overrides storage.provider; "filesystem" is an alias for "local"
+         */
+        fun create(application: Application, env: Map<String, String> = System.getenv()): StorageProvider {
             val config = application.environment.config
-            val provider = try {
+            val provider = env["STORAGE_PROVIDER"]?.takeIf { it.isNotBlank() } ?: try {
                 config.property("storage.provider").getString()
             } catch (e: Exception) {
                 "local"
@@ -21,27 +24,36 @@ class StorageProviderFactory {
             
             logger.info { "Creating storage provider: $provider" }
             
-            return when (provider.lowercase()) {
-                "local" -> createLocalProvider(application)
+            val retryConfig = StorageRetryConfig.fromEnvironment(env)
+            val selected = when (provider.trim().lowercase()) {
+                "local", "filesystem" -> createLocalProvider(application, env)
                 "s3" -> {
                     logger.warn { "S3 provider not yet implemented, falling back to local" }
-                    createLocalProvider(application)
+                    createLocalProvider(application, env)
                 }
                 else -> {
                     logger.warn { "Unknown storage provider: $provider, falling back to local" }
-                    createLocalProvider(application)
+                    createLocalProvider(application, env)
                 }
             }
+            return RetryingStorageProvider(selected, retryConfig)
         }
         
-        private fun createLocalProvider(application: Application): LocalStorageProvider {
-            val config = application.environment.config
-            
-            val basePath = try {
-                config.property("storage.local.base-path").getString()
+        /**
+         * Root directory for the local provider: STORAGE_ROOT, then storage.local.base-path.
+         * /files serves from the same directory.
+         */
+        fun localBasePath(application: Application, env: Map<String, String> = System.getenv()): String =
+            env["STORAGE_ROOT"]?.takeIf { it.isNotBlank() } ?: try {
+                application.environment.config.property("storage.local.base-path").getString()
             } catch (e: Exception) {
                 "./uploads"
             }
+        
+        private fun createLocalProvider(application: Application, env: Map<String, String>): LocalStorageProvider {
+            val config = application.environment.config
+            
+            val basePath = localBasePath(application, env)
             
             val baseUrl = try {
                 config.property("storage.local.serve-url").getString()
@@ -0,0 +1,42 @@
+package com.wondernest.services.storage
+
+import com.wondernest.domain.model.FileCategory
+import java.util.UUID
+
+/**
+ * Object tags used by bucket lifecycle rules (e.g. expire `lifecycle=temp` after a day).
+ * Uploads are stored as temp and promoted to permanent once their database row is saved.
+ * Limits follow S3 object tagging so tags behave the same across providers.
+ */
+object StorageTags {
+    const val CATEGORY = "category"
+    const val OWNER_FAMILY = "owner-family"
+    const val LIFECYCLE = "lifecycle"
+
+    const val MAX_TAGS = 10
+    const val MAX_KEY_LENGTH = 128
+    const val MAX_VALUE_LENGTH = 256
+
+    fun forUpload(
+        category: FileCategory,
+        ownerFamilyId: UUID? = null,
+        lifecycle: StorageLifecycle = StorageLifecycle.PERMANENT
+    ): Map<String, String> = buildMap {
+        put(CATEGORY, category.toDbValue())
+        ownerFamilyId?.let { put(OWNER_FAMILY, it.toString()) }
+        put(LIFECYCLE, lifecycle.value)
+    }
+
+    fun validate(tags: Map<String, String>) {
+        require(tags.size <= MAX_TAGS) { "At most $MAX_TAGS tags are allowed per object" }
+        tags.forEach { (key, value) ->
+            require(key.isNotBlank() && key.length <= MAX_KEY_LENGTH) { "Invalid tag key '$key'" }
+            require(value.length <= MAX_VALUE_LENGTH) { "Value for tag '$key' is too long" }
+        }
+    }
+}
+
+enum class StorageLifecycle(val value: String) {
+    PERMANENT("permanent"),
+    TEMP("temp")
+}
@@ -0,0 +1,179 @@
+package com.wondernest.services.web.admin
+
+import com.wondernest.data.database.repository.web.AdminAuditEntry
+import com.wondernest.data.database.repository.web.AdminAuditRepository
+import com.wondernest.data.database.repository.web.AdminInvitationRepository
+import com.wondernest.data.database.repository.web.AdminSessionRepository
+import com.wondernest.data.database.repository.web.AdminUserRepository
+import com.wondernest.domain.web.*
+import com.wondernest.services.email.EmailService
+import com.wondernest.utils.EmailNormalizer
+import com.wondernest.utils.ValidationUtils
+import mu.KotlinLogging
+import java.security.MessageDigest
+import java.security.SecureRandom
+import java.time.Instant
+import java.time.temporal.ChronoUnit
+import java.util.*
+
+private val logger = KotlinLogging.logger {}
+
+/**
+ * Management of other admin accounts
+ */
+class AdminAccountService(
+    private val adminUserRepository: AdminUserRepository,
+    private val adminAuditRepository: AdminAuditRepository,
+    private val adminInvitationRepository: AdminInvitationRepository,
+    private val emailService: EmailService,
+    private val adminSessionRepository: AdminSessionRepository,
+    private val emailNormalizer: EmailNormalizer = EmailNormalizer(),
+    private val clock: () -> Instant = Instant::now
+) {
+    companion object {
+        const val INVITATION_TTL_DAYS = 7L
+
+        fun hashInvitationToken(token: String): String =
+            MessageDigest.getInstance("SHA-256").digest(token.toByteArray())
+                .joinToString("") { "%02x".format(it) }
+    }
+
+    private val secureRandom = SecureRandom()
+
+    /**
+     * Invite a new admin by email. Admins can't invite a role above their own. The
+     * invitation is kept even if the email can't be delivered; the response says so.
+     */
+    suspend fun inviteAdmin(
+        permissions: Collection<String>,
+        actorId: UUID,
+        request: AdminInvitationRequest
+    ): AdminInvitationResponse {
+        if (AdminPermission.MANAGE_ADMIN_USERS.code !in permissions) {
+            throw SecurityException("Missing permissions: ${AdminPermission.MANAGE_ADMIN_USERS.code}")
+        }
+        val email = emailNormalizer.normalize(request.email)
+        require(ValidationUtils.isValidEmail(email)) { "Invalid email address" }
+        val role = AdminRole.values().firstOrNull { it.name.equals(request.role, ignoreCase = true) }
+            ?: throw IllegalArgumentException("Unknown role: ${request.role}")
+
+        val inviter = adminUserRepository.findById(actorId)
+            ?: throw SecurityException("Inviting admin not found")
+        if (role.hasHigherLevelThan(inviter.role)) {
+            throw SecurityException("Admins can't invite a role above their own")
+        }
+
+        val token = ByteArray(32).also { secureRandom.nextBytes(it) }
+            .let { Base64.getUrlEncoder().withoutPadding().encodeToString(it) }
+        val now = clock()
+        val invitation = adminInvitationRepository.create(
+            AdminInvitation(
+                id = UUID.randomUUID(),
+                email = email,
+                role = role,
+                tokenHash = hashInvitationToken(token),
+                invitedBy = actorId,
+                expiresAt = now.plus(INVITATION_TTL_DAYS, ChronoUnit.DAYS),
+                createdAt = now
+            )
+        )
+        adminAuditRepository.record(
+            AdminAuditEntry(
+                adminId = actorId,
+                action = "admin_user.invited",
+                resourceType = "admin_invitation",
+                resourceId = invitation.id,
+                details = mapOf("email" to email, "role" to role.name.lowercase())
+            )
+        )
+
+        val emailSent = emailService.sendAdminInvitationEmail(
+            invitation, "${inviter.firstName} ${inviter.lastName}".trim(), token
+        )
+        if (!emailSent) {
+            logger.error { "Invitation ${invitation.id} for $email was created but the email was not delivered" }
+        }
+        return invitation.toResponse(emailSent)
+    }
+
+    /**
+     * Replace the custom grants and denials of [adminId]. Every code must exist in the
+     * [AdminPermission] catalog, and admins can't change their own overrides.
+     */
+    suspend fun updateCustomPermissions(
+        permissions: Collection<String>,
+        actorId: UUID,
+        adminId: UUID,
+        customPermissions: CustomPermissions
+    ): AdminUserProfile {
+        requireCanManage(permissions, actorId, adminId)
+        val unknown = customPermissions.unknownCodes()
+        require(unknown.isEmpty()) { "Unknown permissions: ${unknown.joinToString()}" }
+        val overlap = customPermissions.granted.intersect(customPermissions.denied.toSet())
+        require(overlap.isEmpty()) { "Permissions both granted and denied: ${overlap.joinToString()}" }
+
+        val adminUser = adminUserRepository.findById(adminId)
+            ?: throw NoSuchElementException("Admin user $adminId not found")
+        val normalized = CustomPermissions(
+            granted = customPermissions.granted.distinct().sorted(),
+            denied = customPermissions.denied.distinct().sorted()
+        )
+        val updated = adminUserRepository.update(adminUser.copy(customPermissions = normalized, updatedAt = clock()))
+        // Permissions are baked into access tokens, so end the admin's sessions and make them sign in again
+        val revokedSessions = adminSessionRepository.deactivateAllUserSessions(adminId)
+
+        logger.info { "Admin $actorId set custom permissions for $adminId: $normalized ($revokedSessions sessions revoked)" }
+        adminAuditRepository.record(
+            AdminAuditEntry(
+                adminId = actorId,
+                action = "admin_user.permissions_changed",
+                resourceType = "admin_user",
+                resourceId = adminId,
+                details = mapOf(
+                    "granted" to normalized.granted.joinToString(","),
+                    "denied" to normalized.denied.joinToString(",")
+                )
+            )
+        )
+        return updated.toProfile()
+    }
+
+    /**
+     * Restrict [adminId] to the given weekly windows, or lift the restriction with null.
+     * Admins can't change their own hours.
+     */
+    suspend fun updateAllowedHours(
+        permissions: Collection<String>,
+        actorId: UUID,
+        adminId: UUID,
+        allowedHours: AllowedHours?
+    ): AdminUserProfile {
+        requireCanManage(permissions, actorId, adminId)
+        allowedHours?.validate()
+
+        val adminUser = adminUserRepository.findById(adminId)
+            ?: throw NoSuchElementException("Admin user $adminId not found")
+        val updated = adminUserRepository.update(adminUser.copy(allowedHours = allowedHours, updatedAt = clock()))
+
+        logger.info { "Admin $actorId set allowed hours for $adminId: $allowedHours" }
+        adminAuditRepository.record(
+            AdminAuditEntry(
+                adminId = actorId,
+                action = "admin_user.allowed_hours_changed",
+                resourceType = "admin_user",
+                resourceId = adminId,
+                details = mapOf("restricted" to (allowedHours != null).toString())
+            )
+        )
+        return updated.toProfile()
+    }
+
+    private fun requireCanManage(permissions: Collection<String>, actorId: UUID, adminId: UUID) {
+        if (AdminPermission.MANAGE_ADMIN_USERS.code !in permissions) {
+            throw SecurityException("Missing permissions: ${AdminPermission.MANAGE_ADMIN_USERS.code}")
+        }
+        if (actorId == adminId) {
+            throw SecurityException("Admins can't change their own access settings")
+        }
+    }
+}
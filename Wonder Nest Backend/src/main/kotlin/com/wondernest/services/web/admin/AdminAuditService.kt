@@ -0,0 +1,48 @@
+package com.wondernest.services.web.admin
+
+import com.wondernest.data.database.repository.web.AdminAuditRepository
+import com.wondernest.domain.web.*
+
+/**
+ * Read access to the admin audit log for senior admins
+ */
+class AdminAuditService(
+    private val adminAuditRepository: AdminAuditRepository
+) {
+    companion object {
+        const val DEFAULT_PAGE_SIZE = 50
+        const val MAX_PAGE_SIZE = 200
+
+        // Content moderators and above can review the audit trail
+        val MINIMUM_ROLE = AdminRole.CONTENT_MODERATOR
+    }
+
+    /**
+     * Page [page] (1-based) of entries matching [filter], newest first. Page size is capped at [MAX_PAGE_SIZE].
+     */
+    suspend fun queryLogs(
+        callerRole: AdminRole?,
+        filter: AuditLogFilter,
+        page: Int = 1,
+        pageSize: Int = DEFAULT_PAGE_SIZE
+    ): AuditLogPage {
+        if (callerRole == null || callerRole.level < MINIMUM_ROLE.level) {
+            throw SecurityException("Audit logs require the ${MINIMUM_ROLE.displayName} role or higher")
+        }
+        require(page >= 1) { "page must be 1 or greater" }
+        require(pageSize >= 1) { "pageSize must be 1 or greater" }
+        if (filter.from != null && filter.to != null) {
+            require(filter.from < filter.to) { "from must be before to" }
+        }
+
+        val size = pageSize.coerceAtMost(MAX_PAGE_SIZE)
+        // One extra row tells us whether another page exists without a count query
+        val rows = adminAuditRepository.query(filter, size + 1, (page - 1).toLong() * size)
+        return AuditLogPage(
+            entries = rows.take(size).map { it.toResponse() },
+            page = page,
+            pageSize = size,
+            hasMore = rows.size > size
+        )
+    }
+}
@@ -0,0 +1,155 @@
+package com.wondernest.services.web.admin
+
+import com.wondernest.data.database.repository.web.AdminAuditEntry
+import com.wondernest.data.database.repository.web.AdminAuditRepository
+import com.wondernest.data.database.repository.web.AdminUserRepository
+import com.wondernest.data.database.repository.web.ContentItemRepository
+import com.wondernest.domain.web.*
+import mu.KotlinLogging
+import java.time.Instant
+import java.util.*
+
+private val logger = KotlinLogging.logger {}
+
+/**
+ * Admin operations on workflow content (bulk status changes, etc.)
+ */
+class AdminContentService(
+    private val contentItemRepository: ContentItemRepository,
+    private val adminAuditRepository: AdminAuditRepository,
+    private val adminUserRepository: AdminUserRepository
+) {
+    companion object {
+        const val MAX_BATCH_SIZE = 500
+        private const val MAX_REPORTED_FAILURES = 100
+    }
+
+    /**
+     * Apply a status transition to every content item matching the filter.
+     * Items are processed in bounded batches, each updated in its own transaction;
+     * items whose current status can't move to the target are reported as failures.
+     */
+    suspend fun bulkTransition(
+        adminId: UUID,
+        permissions: Collection<String>,
+        request: BulkStatusTransitionRequest,
+        ipAddress: String? = null
+    ): BulkStatusTransitionResult {
+        val target = ContentStatus.fromDbValue(request.targetStatus)
+        requirePermissions(permissions, target)
+        val filter = request.toFilter()
+        require(!filter.isEmpty()) { "At least one filter is required for bulk transitions" }
+        val batchSize = request.batchSize.coerceIn(1, MAX_BATCH_SIZE)
+
+        logger.info { "Admin $adminId bulk transition to ${target.dbValue} with filter $filter" }
+
+        var matched = 0
+        var updated = 0
+        var batches = 0
+        val failures = mutableListOf<BulkTransitionFailure>()
+        var failedCount = 0
+        var afterId: UUID? = null
+
+        while (true) {
+            val page = contentItemRepository.findByFilter(filter, afterId, batchSize)
+            if (page.isEmpty()) break
+            batches++
+            matched += page.size
+            afterId = page.last().id
+
+            val (eligible, ineligible) = page.partition { it.status.canTransitionTo(target) }
+            ineligible.forEach { item ->
+                failedCount++
+                if (failures.size < MAX_REPORTED_FAILURES) {
+                    failures.add(
+                        BulkTransitionFailure(
+                            contentId = item.id.toString(),
+                            reason = "Cannot transition from ${item.status.dbValue} to ${target.dbValue}"
+                        )
+                    )
+                }
+            }
+
+            if (eligible.isNotEmpty()) {
+                requireStoredPermissions(adminId, target)
+                try {
+                    updated += contentItemRepository.updateStatusBatch(eligible.map { it.id }, target, adminId)
+                } catch (e: Exception) {
+                    logger.error(e) { "Bulk transition batch $batches failed" }
+                    failedCount += eligible.size
+                    eligible.forEach { item ->
+                        if (failures.size < MAX_REPORTED_FAILURES) {
+                            failures.add(BulkTransitionFailure(item.id.toString(), "Update failed: ${e.message}"))
+                        }
+                    }
+                }
+            }
+
+            if (page.size < batchSize) break
+        }
+
+        val result = BulkStatusTransitionResult(
+            targetStatus = target.dbValue,
+            matched = matched,
+            updated = updated,
+            failed = failedCount,
+            batches = batches,
+            failures = failures
+        )
+
+        adminAuditRepository.record(
+            AdminAuditEntry(
+                adminId = adminId,
+                action = "content.bulk_status_transition",
+                resourceType = "content_item",
+                details = buildMap {
+                    put("targetStatus", target.dbValue)
+                    filter.creatorId?.let { put("filter.creatorId", it.toString()) }
+                    filter.contentType?.let { put("filter.contentType", it) }
+                    filter.status?.let { put("filter.status", it.dbValue) }
+                    filter.createdAfter?.let { put("filter.createdAfter", it.toString()) }
+                    filter.createdBefore?.let { put("filter.createdBefore", it.toString()) }
+                    request.reason?.let { put("reason", it) }
+                    put("matched", matched.toString())
+                    put("updated", updated.toString())
+                    put("failed", failedCount.toString())
+                },
+                success = failedCount == 0,
+                ipAddress = ipAddress
+            )
+        )
+
+        return result
+    }
+
+    private fun requirePermissions(permissions: Collection<String>, target: ContentStatus) {
+        val required = buildList {
+            add(AdminPermission.MODERATE_CONTENT)
+            if (target == ContentStatus.PUBLISHED) add(AdminPermission.PUBLISH_CONTENT)
+            if (target == ContentStatus.DELETED) add(AdminPermission.DELETE_CONTENT)
+        }
+        val missing = required.filter { it.code !in permissions }
+        if (missing.isNotEmpty()) {
+            throw SecurityException("Missing permissions: ${missing.joinToString { it.code }}")
+        }
+    }
+
+    /**
+     * Checked against the stored account before every batch write, so an admin who is
+     * deactivated or loses the permission mid-run stops changing content
+     */
+    private suspend fun requireStoredPermissions(adminId: UUID, target: ContentStatus) {
+        val admin = adminUserRepository.findById(adminId)
+            ?.takeIf { it.isActive && !it.isLocked() }
+            ?: throw SecurityException("Admin account is not active")
+        requirePermissions(admin.effectivePermissions(), target)
+    }
+
+    private fun BulkStatusTransitionRequest.toFilter() = ContentItemFilter(
+        creatorId = creatorId?.let { UUID.fromString(it) },
+        contentType = contentType?.takeIf { it.isNotBlank() },
+        status = currentStatus?.let { ContentStatus.fromDbValue(it) },
+        createdAfter = createdAfter?.let { Instant.parse(it) },
+        createdBefore = createdBefore?.let { Instant.parse(it) }
+    )
+}
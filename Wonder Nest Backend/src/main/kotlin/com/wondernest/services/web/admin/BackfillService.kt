@@ -0,0 +1,212 @@
+package com.wondernest.services.web.admin
+
+import com.wondernest.data.database.repository.web.AdminAuditEntry
+import com.wondernest.data.database.repository.web.AdminAuditRepository
+import com.wondernest.data.database.repository.web.BackfillProgressRepository
+import com.wondernest.domain.web.*
+import kotlinx.coroutines.CoroutineScope
+import kotlinx.coroutines.Dispatchers
+import kotlinx.coroutines.SupervisorJob
+import kotlinx.coroutines.launch
+import mu.KotlinLogging
+import java.time.Duration
+import java.time.Instant
+import java.util.*
+
+private val logger = KotlinLogging.logger {}
+
+/**
+ * A data fix that walks a table in id order. Both steps must be safe to repeat:
+ * a batch that failed part-way is retried from the previous cursor.
+ */
+interface BackfillJob {
+    val name: String
+    val description: String
+
+    /**
+     * Ids of the next rows to process, in ascending order, strictly after [afterId]
+     */
+    suspend fun nextBatch(afterId: String?, limit: Int): List<String>
+
+    /**
+     * Process the given rows and return how many were actually changed
+     */
+    suspend fun process(ids: List<String>): Int
+}
+
+class BackfillAlreadyRunningException(jobName: String) :
+    IllegalStateException("Backfill $jobName is already running")
+
+/**
+ * Runs named backfill jobs in bounded batches on a background scope, saving the cursor after
+ * every batch so an interrupted, paused or failed run picks up where it stopped. A run claims
+ * the job's progress row, so only one run per job is active across instances; a run that stops
+ * saving progress for [staleAfter] is treated as crashed and marked failed.
+ */
+class BackfillService(
+    jobs: List<BackfillJob>,
+    private val progressRepository: BackfillProgressRepository,
+    private val adminAuditRepository: AdminAuditRepository,
+    private val scope: CoroutineScope = CoroutineScope(SupervisorJob() + Dispatchers.IO),
+    private val staleAfter: Duration = Duration.ofMinutes(15),
+    private val clock: () -> Instant = Instant::now
+) {
+    companion object {
+        const val MAX_BATCH_SIZE = 500
+    }
+
+    private val jobs = jobs.associateBy { it.name }
+
+    suspend fun listJobs(permissions: Collection<String>): List<BackfillJobInfo> {
+        requirePermission(permissions)
+        recoverStaleRuns()
+        return jobs.values.sortedBy { it.name }.map { job ->
+            BackfillJobInfo(job.name, job.description, progressRepository.find(job.name)?.toResponse())
+        }
+    }
+
+    suspend fun status(permissions: Collection<String>, jobName: String): BackfillProgressResponse {
+        requirePermission(permissions)
+        val job = findJob(jobName)
+        recoverStaleRuns()
+        return (progressRepository.find(job.name) ?: BackfillProgress(job.name, updatedAt = clock())).toResponse()
+    }
+
+    /**
+     * Start a job in the background and return its progress as it starts. The run continues
+     * until the job completes, fails, or has processed [BackfillRunRequest.maxBatches] batches.
+     * Completed jobs are not re-run unless [BackfillRunRequest.restart] is set.
+     */
+    suspend fun start(
+        adminId: UUID,
+        permissions: Collection<String>,
+        jobName: String,
+        request: BackfillRunRequest,
+        ipAddress: String? = null
+    ): BackfillProgressResponse {
+        requirePermission(permissions)
+        val job = findJob(jobName)
+        request.maxBatches?.let { require(it > 0) { "maxBatches must be positive" } }
+        val batchSize = request.batchSize.coerceIn(1, MAX_BATCH_SIZE)
+
+        recoverStaleRuns()
+        if (!request.restart) {
+            progressRepository.find(job.name)
+                ?.takeIf { it.status == BackfillStatus.COMPLETED }
+                ?.let { return it.toResponse() }
+        }
+
+        val now = clock()
+        val runId = UUID.randomUUID()
+        val claimed = progressRepository.claim(job.name, runId, now)
+            ?: throw BackfillAlreadyRunningException(job.name)
+        val base = if (request.restart || claimed.startedAt == null) {
+            BackfillProgress(job.name, startedBy = adminId, startedAt = now)
+        } else {
+            claimed
+        }
+        val progress = base.copy(
+            status = BackfillStatus.RUNNING,
+            lastError = null,
+            updatedAt = now,
+            completedAt = null,
+            runId = runId
+        )
+        progressRepository.save(progress)
+        logger.info { "Admin $adminId started backfill ${job.name} from cursor ${progress.cursor}" }
+
+        scope.launch { runBatches(job, progress, request, batchSize, adminId, ipAddress) }
+        return progress.toResponse()
+    }
+
+    private suspend fun runBatches(
+        job: BackfillJob,
+        started: BackfillProgress,
+        request: BackfillRunRequest,
+        batchSize: Int,
+        adminId: UUID,
+        ipAddress: String?
+    ) {
+        var progress = started
+        var processedThisRun = 0L
+        var batchesThisRun = 0
+
+        try {
+            while (progress.status == BackfillStatus.RUNNING) {
+                progress = if (request.maxBatches != null && batchesThisRun >= request.maxBatches) {
+                    progress.copy(status = BackfillStatus.PAUSED, updatedAt = clock())
+                } else {
+                    try {
+                        val ids = job.nextBatch(progress.cursor, batchSize)
+                        if (ids.isEmpty()) {
+                            progress.copy(status = BackfillStatus.COMPLETED, updatedAt = clock(), completedAt = clock())
+                        } else {
+                            val updated = job.process(ids)
+                            batchesThisRun++
+                            processedThisRun += ids.size
+                            val next = progress.copy(
+                                cursor = ids.last(),
+                                processed = progress.processed + ids.size,
+                                updated = progress.updated + updated,
+                                batches = progress.batches + 1,
+                                updatedAt = clock()
+                            )
+                            // A short page means there is nothing after it
+                            if (ids.size < batchSize) {
+                                next.copy(status = BackfillStatus.COMPLETED, completedAt = clock())
+                            } else {
+                                next
+                            }
+                        }
+                    } catch (e: Exception) {
+                        logger.error(e) { "Backfill ${job.name} failed after cursor ${progress.cursor}" }
+                        progress.copy(status = BackfillStatus.FAILED, lastError = e.message, updatedAt = clock())
+                    }
+                }
+                if (!progressRepository.save(progress)) {
+                    // Marked stale and claimed by another run; that run owns the cursor now
+                    logger.warn { "Backfill ${job.name} run ${started.runId} lost its claim, stopping" }
+                    return
+                }
+            }
+        } catch (e: Exception) {
+            // Saving progress failed; the row stays RUNNING until it is recovered as stale
+            logger.error(e) { "Backfill ${job.name} could not save progress after cursor ${progress.cursor}" }
+            progress = progress.copy(status = BackfillStatus.FAILED, lastError = e.message)
+        }
+
+        adminAuditRepository.record(
+            AdminAuditEntry(
+                adminId = adminId,
+                action = "system.backfill_run",
+                resourceType = "backfill_job",
+                details = buildMap {
+                    put("job", job.name)
+                    put("status", progress.status.dbValue)
+                    started.cursor?.let { put("fromCursor", it) }
+                    progress.cursor?.let { put("toCursor", it) }
+                    put("processed", processedThisRun.toString())
+                    put("restart", request.restart.toString())
+                },
+                success = progress.status != BackfillStatus.FAILED,
+                errorMessage = progress.lastError,
+                ipAddress = ipAddress
+            )
+        )
+    }
+
+    private suspend fun recoverStaleRuns() {
+        val now = clock()
+        val recovered = progressRepository.failStale(now.minus(staleAfter), now)
+        if (recovered > 0) logger.warn { "Marked $recovered stale backfill run(s) as failed" }
+    }
+
+    private fun findJob(jobName: String): BackfillJob =
+        jobs[jobName] ?: throw NoSuchElementException("Unknown backfill job: $jobName")
+
+    private fun requirePermission(permissions: Collection<String>) {
+        if (AdminPermission.MANAGE_SYSTEM_SETTINGS.code !in permissions) {
+            throw SecurityException("Missing permissions: ${AdminPermission.MANAGE_SYSTEM_SETTINGS.code}")
+        }
+    }
+}
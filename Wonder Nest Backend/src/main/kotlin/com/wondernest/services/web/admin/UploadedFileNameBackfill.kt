@@ -0,0 +1,39 @@
+package com.wondernest.services.web.admin
+
+import com.wondernest.data.database.table.UploadedFiles
+import com.wondernest.utils.ValidationUtils
+import kotlinx.coroutines.Dispatchers
+import org.jetbrains.exposed.sql.*
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.eq
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.greater
+import org.jetbrains.exposed.sql.SqlExpressionBuilder.inList
+import org.jetbrains.exposed.sql.transactions.experimental.newSuspendedTransaction
+import java.util.*
+
+/**
+ * Applies [ValidationUtils.normalizeFileName] to files uploaded before names were
+ * normalized on upload. Already-normal names are left untouched.
+ */
+class UploadedFileNameBackfill : BackfillJob {
+    override val name = "uploaded-file-names"
+    override val description = "Strip directory parts and control characters from stored upload file names"
+
+    override suspend fun nextBatch(afterId: String?, limit: Int): List<String> = newSuspendedTransaction(Dispatchers.IO) {
+        val query = afterId?.let { UploadedFiles.select { UploadedFiles.id greater UUID.fromString(it) } }
+            ?: UploadedFiles.selectAll()
+        query.orderBy(UploadedFiles.id to SortOrder.ASC)
+            .limit(limit)
+            .map { it[UploadedFiles.id].value.toString() }
+    }
+
+    override suspend fun process(ids: List<String>): Int = newSuspendedTransaction(Dispatchers.IO) {
+        UploadedFiles.select { UploadedFiles.id inList ids.map { UUID.fromString(it) } }
+            .map { it[UploadedFiles.id].value to it[UploadedFiles.originalName] }
+            .filter { (_, originalName) -> ValidationUtils.normalizeFileName(originalName) != originalName }
+            .sumOf { (id, originalName) ->
+                UploadedFiles.update({ UploadedFiles.id eq id }) {
+                    it[UploadedFiles.originalName] = ValidationUtils.normalizeFileName(originalName)
+                }
+            }
+    }
+}
@@ -0,0 +1,50 @@
+package com.wondernest.utils
+
+/**
+ * Lightweight text moderation for user-submitted content (reviews, comments).
+ * Flags profanity and personally identifiable information before publishing.
+ */
+object ContentModeration {
+
+    val PROFANITY = setOf(
+        "damn", "hell", "crap", "shit", "fuck", "bitch", "bastard", "ass", "asshole",
+        "piss", "dick", "cock", "pussy", "slut", "whore", "fag", "retard"
+    )
+
+    private val EMAIL_REGEX = Regex("[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\\.[A-Za-z]{2,}")
+    private val PHONE_REGEX = Regex("(\\+?\\d{1,2}[\\s.-]?)?\\(?\\d{3}\\)?[\\s.-]?\\d{3}[\\s.-]?\\d{4}")
+    private val STREET_ADDRESS_REGEX = Regex(
+        "\\b\\d{1,5}\\s+\\w+(\\s\\w+)*\\s+(street|st|avenue|ave|road|rd|lane|ln|drive|dr|court|ct|boulevard|blvd)\\b",
+        RegexOption.IGNORE_CASE
+    )
+    private val SSN_REGEX = Regex("\\b\\d{3}-\\d{2}-\\d{4}\\b")
+
+    fun containsProfanity(text: String?): Boolean {
+        if (text.isNullOrBlank()) return false
+        val words = text.lowercase().split(Regex("[^a-z]+")).filter { it.isNotEmpty() }
+        return words.any { it in PROFANITY }
+    }
+
+    fun containsPii(text: String?): Boolean {
+        if (text.isNullOrBlank()) return false
+        return EMAIL_REGEX.containsMatchIn(text) ||
+            PHONE_REGEX.containsMatchIn(text) ||
+            STREET_ADDRESS_REGEX.containsMatchIn(text) ||
+            SSN_REGEX.containsMatchIn(text)
+    }
+
+    /**
+     * Checks all provided fields and returns the reasons the content was rejected, if any
+     */
+    fun check(vararg texts: String?): ModerationResult {
+        val reasons = mutableListOf<String>()
+        if (texts.any { containsProfanity(it) }) reasons.add("Content contains inappropriate language")
+        if (texts.any { containsPii(it) }) reasons.add("Content contains personal information")
+        return ModerationResult(approved = reasons.isEmpty(), reasons = reasons)
+    }
+}
+
+data class ModerationResult(
+    val approved: Boolean,
+    val reasons: List<String> = emptyList()
+)
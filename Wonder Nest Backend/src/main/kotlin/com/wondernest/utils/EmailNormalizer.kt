@@ -0,0 +1,43 @@
+package com.wondernest.utils
+
+data class EmailNormalizationConfig(
+    val stripGmailAliases: Boolean = false
+) {
+    companion object {
+        /**
+         * Reads EMAIL_NORMALIZE_GMAIL_ALIASES. Stored emails are compared in normalized form, so
+         * turning this on for an existing deployment needs existing Gmail addresses rewritten too.
+         */
+        fun fromEnvironment(env: Map<String, String> = System.getenv()) = EmailNormalizationConfig(
+            stripGmailAliases = env["EMAIL_NORMALIZE_GMAIL_ALIASES"]?.toBooleanStrictOrNull() ?: false
+        )
+    }
+}
+
+/**
+ * The one place emails are normalized before they're stored or looked up, so `Test@x.com` and
+ * `test@x.com` are always the same account.
+ */
+class EmailNormalizer(private val config: EmailNormalizationConfig = EmailNormalizationConfig()) {
+
+    fun normalize(email: String): String {
+        val canonical = canonical(email)
+        if (!config.stripGmailAliases) return canonical
+
+        val local = canonical.substringBeforeLast('@', missingDelimiterValue = "")
+        val domain = canonical.substringAfterLast('@', missingDelimiterValue = "")
+        if (local.isEmpty() || domain !in GMAIL_DOMAINS) return canonical
+        // Gmail ignores dots and anything after a plus in the local part
+        val mailbox = local.substringBefore('+').replace(".", "")
+        return if (mailbox.isEmpty()) canonical else "$mailbox@gmail.com"
+    }
+
+    companion object {
+        private val GMAIL_DOMAINS = setOf("gmail.com", "googlemail.com")
+
+        /**
+         * Trimmed and lowercased; what every email is normalized to regardless of configuration
+         */
+        fun canonical(email: String): String = email.trim().lowercase()
+    }
+}
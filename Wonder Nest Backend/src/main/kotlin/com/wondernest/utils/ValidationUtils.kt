@@ -1,6 +1,8 @@
 package com.wondernest.utils
 
 import kotlinx.serialization.Serializable
+import org.owasp.html.HtmlPolicyBuilder
+import org.owasp.html.PolicyFactory
 import java.util.*
 import java.util.regex.Pattern
 
@@ -20,6 +22,34 @@ object ValidationUtils {
     private val PASSWORD_DIGIT = Pattern.compile(".*\\d.*")
     private val PASSWORD_SPECIAL = Pattern.compile(".*[!@#$%^&*()_+\\-=\\[\\]{};':\"\\\\|,.<>/?].*")
     
+    // Tags allowed in formatted text; links are forced to nofollow/noopener
+    private val RICH_TEXT_POLICY: PolicyFactory = HtmlPolicyBuilder()
+        .allowElements("b", "strong", "i", "em", "u", "p", "br", "ul", "ol", "li", "blockquote", "h3", "h4")
+        .allowElements("a")
+        .allowAttributes("href").onElements("a")
+        .allowStandardUrlProtocols()
+        .requireRelsOnLinks("nofollow", "noopener")
+        .toFactory()
+    
+    private val COMMON_PASSWORDS = setOf(
+        "password", "123456", "123456789", "12345678", "12345", "1234567",
+        "password123", "admin", "qwerty", "abc123", "password1", "iloveyou", "letmein"
+    )
+    
+    // Checked as substrings after undoing common letter substitutions
+    private val COMMON_WORDS = listOf(
+        "password", "qwerty", "admin", "welcome", "letmein", "login", "monkey", "dragon",
+        "master", "shadow", "sunshine", "princess", "football", "baseball", "soccer", "iloveyou",
+        "love", "hello", "secret", "summer", "winter", "spring", "autumn", "family", "wondernest"
+    )
+    
+    private val LEET_SUBSTITUTIONS = mapOf('0' to 'o', '1' to 'l', '3' to 'e', '4' to 'a', '5' to 's', '7' to 't', '@' to 'a', '$' to 's')
+    
+    private val KEYBOARD_ROWS = listOf("qwertyuiop", "asdfghjkl", "zxcvbnm")
+    
+    // Matches the original_name column width
+    private const val MAX_FILE_NAME_LENGTH = 255
+    
     /**
      * Validates email format
      */
@@ -80,6 +110,79 @@ object ValidationUtils {
         }
     }
     
+    /**
+     * Scores password strength for the signup strength meter. Besides length and
+     * character variety, common words, sequences (abc, 123, qwe) and repeated
+     * characters count against the score.
+     */
+    fun scorePassword(password: String): PasswordStrengthResult {
+        val suggestions = mutableListOf<String>()
+        val lowered = password.lowercase()
+
+        if (lowered in COMMON_PASSWORDS) {
+            return PasswordStrengthResult(PasswordStrength.VERY_WEAK, listOf("This password is too common"))
+        }
+
+        val lengthPoints = when {
+            password.length < 8 -> 0
+            password.length < 12 -> 1
+            password.length < 16 -> 2
+            else -> 3
+        }
+        when {
+            password.length < 8 -> suggestions.add("Use at least 8 characters")
+            password.length < 12 -> suggestions.add("Use 12 or more characters")
+        }
+
+        val hasLower = password.any { it.isLowerCase() }
+        val hasUpper = password.any { it.isUpperCase() }
+        val hasDigit = password.any { it.isDigit() }
+        val hasSpecial = password.any { !it.isLetterOrDigit() }
+        val classes = listOf(hasLower, hasUpper, hasDigit, hasSpecial).count { it }
+        val varietyPoints = when (classes) {
+            4 -> 2
+            2, 3 -> 1
+            else -> 0
+        }
+        // Long passphrases don't need every character class
+        if (password.length < 16) {
+            if (!hasLower) suggestions.add("Add a lowercase letter")
+            if (!hasUpper) suggestions.add("Add an uppercase letter")
+            if (!hasDigit) suggestions.add("Add a number")
+            if (!hasSpecial) suggestions.add("Add a special character")
+        }
+
+        var penalty = 0
+        val deLeeted = lowered.map { LEET_SUBSTITUTIONS[it] ?: it }.joinToString("")
+        if (COMMON_WORDS.any { it in deLeeted }) {
+            penalty += 2
+            suggestions.add("Avoid common words and names")
+        }
+        if (hasSequence(lowered)) {
+            penalty++
+            suggestions.add("Avoid sequences like abc, 123 or qwerty")
+        }
+        if (Regex("(.)\\1\\1").containsMatchIn(lowered)) {
+            penalty++
+            suggestions.add("Avoid repeating the same character")
+        }
+
+        var points = (lengthPoints + varietyPoints - penalty).coerceIn(0, PasswordStrength.entries.size - 1)
+        if (password.length < 8) points = points.coerceAtMost(PasswordStrength.WEAK.ordinal)
+
+        return PasswordStrengthResult(PasswordStrength.entries[points], suggestions)
+    }
+
+    private fun hasSequence(password: String): Boolean {
+        val ascending = (0 until password.length - 2).any { i ->
+            val (a, b, c) = Triple(password[i], password[i + 1], password[i + 2])
+            a.isLetterOrDigit() && b - a == c - b && (b - a == 1 || b - a == -1)
+        }
+        return ascending || KEYBOARD_ROWS.any { row ->
+            (0 until row.length - 2).any { row.substring(it, it + 3) in password }
+        }
+    }
+    
     /**
      * Validates UUID format
      */
@@ -298,7 +401,8 @@ object ValidationUtils {
     }
     
     /**
-     * Sanitizes string input to prevent XSS and injection attacks
+     * Sanitizes string input to prevent XSS and injection attacks.
+     * Escapes all markup, so use [sanitizeRichText] for fields that allow formatting.
      */
     fun sanitizeString(input: String?): String? {
         if (input.isNullOrBlank()) return input
@@ -312,6 +416,34 @@ object ValidationUtils {
             .replace("/", "&#x2F;")
     }
     
+    /**
+     * Sanitizes fields that allow limited formatting (descriptions, marketing text).
+     * Keeps allowlisted tags and drops everything else, including scripts, event
+     * handler attributes and non-http(s)/mailto links.
+     */
+    fun sanitizeRichText(input: String?): String? {
+        if (input.isNullOrBlank()) return input
+        return RICH_TEXT_POLICY.sanitize(input.trim())
+    }
+    
+    /**
+     * Normalizes a client-supplied file name for storage: drops any directory part,
+     * strips control characters and caps the length (keeping the extension).
+     */
+    fun normalizeFileName(input: String): String {
+        val baseName = input.substringAfterLast('/').substringAfterLast('\\')
+        val cleaned = baseName.filterNot { it.isISOControl() }.trim()
+        if (cleaned.isEmpty() || cleaned == "." || cleaned == "..") return "file"
+        if (cleaned.length <= MAX_FILE_NAME_LENGTH) return cleaned
+
+        val extension = cleaned.substringAfterLast('.', "").takeIf { it.length in 1..10 }
+        return if (extension != null) {
+            cleaned.take(MAX_FILE_NAME_LENGTH - extension.length - 1) + "." + extension
+        } else {
+            cleaned.take(MAX_FILE_NAME_LENGTH)
+        }
+    }
+    
     /**
      * Checks for potential SQL injection patterns
      */
@@ -370,37 +502,74 @@ object ValidationUtils {
 }
 
 /**
- * Validation result wrapper
+ * Validation result wrapper. [field] names the request field a failure belongs to, when known.
  */
 @Serializable
 data class ValidationResult(
     val isValid: Boolean,
-    val errorMessage: String? = null
+    val errorMessage: String? = null,
+    val field: String? = null
 ) {
+    fun forField(field: String) = if (isValid) this else copy(field = field)
+
     companion object {
         fun success() = ValidationResult(isValid = true)
-        fun failure(message: String) = ValidationResult(isValid = false, errorMessage = message)
+        fun failure(message: String, field: String? = null) =
+            ValidationResult(isValid = false, errorMessage = message, field = field)
     }
 }
 
+/**
+ * A validation failure attached to a single request field, so forms can show it next to the input
+ */
+@Serializable
+data class FieldError(
+    val field: String,
+    val message: String
+)
+
 /**
  * Multiple validation results wrapper
  */
 @Serializable
 data class ValidationResults(
     val isValid: Boolean,
-    val errors: List<String> = emptyList()
+    val errors: List<String> = emptyList(),
+    val fieldErrors: List<FieldError> = emptyList()
 ) {
     companion object {
         fun success() = ValidationResults(isValid = true)
-        fun failure(errors: List<String>) = ValidationResults(isValid = false, errors = errors)
+        fun failure(errors: List<String>, fieldErrors: List<FieldError> = emptyList()) =
+            ValidationResults(isValid = false, errors = errors, fieldErrors = fieldErrors)
         fun combine(vararg results: ValidationResult): ValidationResults {
-            val errors = results.filter { !it.isValid }.mapNotNull { it.errorMessage }
-            return if (errors.isEmpty()) {
+            val failures = results.filter { !it.isValid && it.errorMessage != null }
+            return if (failures.isEmpty()) {
                 success()
             } else {
-                failure(errors)
+                failure(
+                    errors = failures.map { it.errorMessage!! },
+                    fieldErrors = failures.mapNotNull { result ->
+                        result.field?.let { FieldError(it, result.errorMessage!!) }
+                    }
+                )
             }
         }
     }
-}
\ No newline at end of file
+}
+
+enum class PasswordStrength {
+    VERY_WEAK,
+    WEAK,
+    FAIR,
+    STRONG,
+    VERY_STRONG
+}
+
+/**
+ * Password strength with concrete suggestions for improving it
+ */
+@Serializable
+data class PasswordStrengthResult(
+    val strength: PasswordStrength,
+    val suggestions: List<String> = emptyList()
+)
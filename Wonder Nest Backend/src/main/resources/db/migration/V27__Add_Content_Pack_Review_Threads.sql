@@ -0,0 +1,20 @@
+-- V27: Content pack review threads
+-- One review per family per pack, plus helpful votes
+
+ALTER TABLE content_pack_reviews ADD COLUMN IF NOT EXISTS family_id UUID;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_content_pack_reviews_pack_family
+    ON content_pack_reviews(pack_id, family_id)
+    WHERE family_id IS NOT NULL;
+
+CREATE TABLE IF NOT EXISTS content_pack_review_votes (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    review_id UUID NOT NULL REFERENCES content_pack_reviews(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL,
+    is_helpful BOOLEAN NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+
+    UNIQUE(review_id, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_content_pack_review_votes_review ON content_pack_review_votes(review_id);
@@ -0,0 +1,5 @@
+-- V28: Parent control over which games are available to each child
+-- Disabled instances stay visible to parents but reject game data writes
+
+ALTER TABLE IF EXISTS games.child_game_instances
+    ADD COLUMN IF NOT EXISTS is_enabled BOOLEAN NOT NULL DEFAULT TRUE;
@@ -0,0 +1,9 @@
+-- V29: Optional expiry for seasonal/event content packs
+-- Expired packs leave listings and search; families who own them keep access
+
+ALTER TABLE IF EXISTS content_packs
+    ADD COLUMN IF NOT EXISTS expires_at TIMESTAMP;
+
+CREATE INDEX IF NOT EXISTS idx_content_packs_expires_at
+    ON content_packs(expires_at)
+    WHERE expires_at IS NOT NULL;
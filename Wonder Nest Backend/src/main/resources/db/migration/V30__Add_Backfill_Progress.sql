@@ -0,0 +1,16 @@
+-- V30: Progress tracking for admin-run data backfills
+-- One row per named job; the cursor is the last processed row id so runs can resume
+
+CREATE TABLE IF NOT EXISTS web_audit.backfill_progress (
+    job_name VARCHAR(100) PRIMARY KEY,
+    cursor TEXT,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending', -- pending, running, paused, completed, failed
+    processed BIGINT NOT NULL DEFAULT 0,
+    updated BIGINT NOT NULL DEFAULT 0,
+    batches INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    started_by UUID,
+    started_at TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    completed_at TIMESTAMP
+);
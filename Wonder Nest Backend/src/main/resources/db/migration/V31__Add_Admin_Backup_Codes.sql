@@ -0,0 +1,5 @@
+-- V31: Single-use backup codes for admin two-factor authentication
+-- Stores SHA-256 hashes only; a code is removed from the array when used
+
+ALTER TABLE IF EXISTS web_admin.admin_users
+    ADD COLUMN IF NOT EXISTS two_fa_backup_codes JSONB NOT NULL DEFAULT '[]';
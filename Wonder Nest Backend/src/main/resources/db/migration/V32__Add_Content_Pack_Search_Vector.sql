@@ -0,0 +1,34 @@
+-- V32: Weighted full-text search for content packs
+-- Name matches outrank description matches, which outrank tag/keyword matches,
+-- so ts_rank(search_vector, plainto_tsquery('english', :query)) orders sort_by=relevance
+
+ALTER TABLE IF EXISTS content_packs
+    ADD COLUMN IF NOT EXISTS search_vector tsvector;
+
+CREATE OR REPLACE FUNCTION update_content_pack_search_vector()
+RETURNS trigger AS $$
+BEGIN
+    NEW.search_vector :=
+        setweight(to_tsvector('english', coalesce(NEW.name, '')), 'A') ||
+        setweight(to_tsvector('english', coalesce(NEW.description, '')), 'B') ||
+        setweight(to_tsvector('english',
+            coalesce(NEW.search_keywords, '') || ' ' ||
+            coalesce(array_to_string(NEW.curriculum_tags, ' '), '') || ' ' ||
+            coalesce(array_to_string(NEW.mood_tags, ' '), '')
+        ), 'C');
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS trigger_update_content_pack_search_vector ON content_packs;
+CREATE TRIGGER trigger_update_content_pack_search_vector
+    BEFORE INSERT OR UPDATE ON content_packs
+    FOR EACH ROW
+    EXECUTE FUNCTION update_content_pack_search_vector();
+
+-- Fill the vector for existing packs (the trigger recomputes it)
+UPDATE content_packs SET updated_at = updated_at;
+
+-- The unweighted expression index from V26 is replaced by one on the stored vector
+DROP INDEX IF EXISTS idx_content_packs_search;
+CREATE INDEX IF NOT EXISTS idx_content_packs_search_vector ON content_packs USING gin(search_vector);
@@ -0,0 +1,9 @@
+-- V33: Time-boxed featuring for content packs
+-- Packs stop being featured once featured_until passes; NULL keeps them featured
+
+ALTER TABLE IF EXISTS content_packs
+    ADD COLUMN IF NOT EXISTS featured_until TIMESTAMP;
+
+CREATE INDEX IF NOT EXISTS idx_content_packs_featured_until
+    ON content_packs(featured_until)
+    WHERE is_featured = true AND featured_until IS NOT NULL;
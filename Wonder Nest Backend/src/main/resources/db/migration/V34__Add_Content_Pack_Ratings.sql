@@ -0,0 +1,11 @@
+-- V34: Individual star ratings for content packs
+-- One rating per parent per pack; content_packs.rating_average/rating_count are recomputed from these
+
+CREATE TABLE IF NOT EXISTS content_pack_ratings (
+    pack_id UUID NOT NULL REFERENCES content_packs(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL,
+    rating SMALLINT NOT NULL CHECK (rating >= 1 AND rating <= 5),
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (pack_id, user_id)
+);
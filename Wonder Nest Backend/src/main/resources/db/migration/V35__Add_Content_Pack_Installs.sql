@@ -0,0 +1,13 @@
+-- V35: Per-family content pack installs
+-- content_packs.download_count is only incremented when a family installs a pack for the first time:
+--   INSERT INTO content_pack_installs (pack_id, family_id) VALUES (...) ON CONFLICT DO NOTHING;
+--   UPDATE content_packs SET download_count = download_count + 1 WHERE id = ... (only when a row was inserted)
+
+CREATE TABLE IF NOT EXISTS content_pack_installs (
+    pack_id UUID NOT NULL REFERENCES content_packs(id) ON DELETE CASCADE,
+    family_id UUID NOT NULL,
+    installed_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (pack_id, family_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_content_pack_installs_family ON content_pack_installs(family_id);
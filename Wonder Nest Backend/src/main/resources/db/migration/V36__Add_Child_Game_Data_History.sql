@@ -0,0 +1,46 @@
+-- V36: Append-only history of child game data saves
+-- Every insert/update of games.child_game_data copies the new version here so parents can
+-- restore earlier progress. Rows older than the configured retention, and versions beyond the
+-- per-key cap, are pruned by GameDataHistoryPruneTask.
+
+CREATE TABLE IF NOT EXISTS games.child_game_data_history (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    child_game_instance_id UUID NOT NULL,
+    data_key VARCHAR(200) NOT NULL,
+    data_version INTEGER NOT NULL,
+    data_value JSONB NOT NULL,
+    recorded_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (child_game_instance_id, data_key, data_version)
+);
+
+CREATE INDEX IF NOT EXISTS idx_child_game_data_history_recorded_at
+    ON games.child_game_data_history(recorded_at);
+
+CREATE OR REPLACE FUNCTION games.record_child_game_data_history()
+RETURNS TRIGGER AS $$
+BEGIN
+    INSERT INTO games.child_game_data_history (child_game_instance_id, data_key, data_version, data_value, recorded_at)
+    VALUES (NEW.child_game_instance_id, NEW.data_key, NEW.data_version, NEW.data_value, NEW.updated_at)
+    ON CONFLICT (child_game_instance_id, data_key, data_version) DO NOTHING;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DO $$
+BEGIN
+    IF to_regclass('games.child_game_instances') IS NOT NULL THEN
+        -- Deleting a child's game instance (or the child) removes their history too
+        ALTER TABLE games.child_game_data_history
+            DROP CONSTRAINT IF EXISTS fk_child_game_data_history_instance;
+        ALTER TABLE games.child_game_data_history
+            ADD CONSTRAINT fk_child_game_data_history_instance
+            FOREIGN KEY (child_game_instance_id) REFERENCES games.child_game_instances(id) ON DELETE CASCADE;
+    END IF;
+
+    IF to_regclass('games.child_game_data') IS NOT NULL THEN
+        DROP TRIGGER IF EXISTS record_child_game_data_history ON games.child_game_data;
+        CREATE TRIGGER record_child_game_data_history
+            AFTER INSERT OR UPDATE OF data_value, data_version ON games.child_game_data
+            FOR EACH ROW EXECUTE FUNCTION games.record_child_game_data_history();
+    END IF;
+END $$;
@@ -0,0 +1,15 @@
+-- V37: Verifiable parental consent
+-- Each consent decision records how the parent was verified, whether that verification has
+-- completed, and when the consent lapses. Decisions recorded before this migration were taken
+-- at face value, so they are marked verified with no expiry.
+
+ALTER TABLE compliance.coppa_consent
+    ADD COLUMN IF NOT EXISTS verification_method VARCHAR(50),
+    ADD COLUMN IF NOT EXISTS verification_status VARCHAR(20) NOT NULL DEFAULT 'verified',
+    ADD COLUMN IF NOT EXISTS expires_at TIMESTAMP WITH TIME ZONE;
+
+ALTER TABLE compliance.coppa_consent
+    DROP CONSTRAINT IF EXISTS coppa_consent_verification_status_check;
+ALTER TABLE compliance.coppa_consent
+    ADD CONSTRAINT coppa_consent_verification_status_check
+    CHECK (verification_status IN ('pending', 'verified', 'failed'));
@@ -0,0 +1,19 @@
+-- V38: Review queue for child data flagged as containing personal information
+-- When GAME_DATA_PII_ACTION=queue, game data saves that look like they contain PII are held
+-- here instead of being written, until a moderator releases or discards them.
+
+CREATE TABLE IF NOT EXISTS compliance.pii_review_queue (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    child_id UUID NOT NULL REFERENCES family.child_profiles(id) ON DELETE CASCADE,
+    game_key VARCHAR(100) NOT NULL,
+    data_key VARCHAR(200) NOT NULL,
+    data_value JSONB NOT NULL,
+    flagged_paths JSONB NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'released', 'discarded')),
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    reviewed_at TIMESTAMP WITH TIME ZONE
+);
+
+CREATE INDEX IF NOT EXISTS idx_pii_review_queue_pending
+    ON compliance.pii_review_queue(created_at) WHERE status = 'pending';
+CREATE INDEX IF NOT EXISTS idx_pii_review_queue_child ON compliance.pii_review_queue(child_id);
@@ -0,0 +1,7 @@
+-- V39: Lock family accounts after repeated failed logins
+-- Mirrors the admin lockout: failed_login_attempts counts consecutive failures and resets on a
+-- successful login; locked_until is set once the configured threshold is reached.
+
+ALTER TABLE core.users
+    ADD COLUMN IF NOT EXISTS failed_login_attempts INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS locked_until TIMESTAMP WITH TIME ZONE;
@@ -0,0 +1,14 @@
+-- V40: Single-use email verification tokens
+-- Only a SHA-256 hash of each token is stored. used_at is set when the link is followed,
+-- so a replayed link can be told apart from an expired or unknown one.
+
+CREATE TABLE IF NOT EXISTS core.email_verification_tokens (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES core.users(id) ON DELETE CASCADE,
+    token_hash VARCHAR(64) NOT NULL UNIQUE,
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    used_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_email_verification_tokens_user ON core.email_verification_tokens(user_id);
@@ -0,0 +1,12 @@
+-- V41: TOTP two-factor authentication for creator accounts
+-- encrypted_secret is AES-GCM encrypted with TWO_FACTOR_ENCRYPTION_KEY; backup codes are stored
+-- as SHA-256 hashes and removed as they are used.
+
+CREATE TABLE IF NOT EXISTS core.creator_two_factor (
+    user_id UUID PRIMARY KEY REFERENCES core.users(id) ON DELETE CASCADE,
+    encrypted_secret TEXT NOT NULL,
+    backup_code_hashes JSONB NOT NULL DEFAULT '[]'::jsonb,
+    enabled BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    enabled_at TIMESTAMP WITH TIME ZONE
+);
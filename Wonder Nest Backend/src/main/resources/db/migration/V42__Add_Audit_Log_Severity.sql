@@ -0,0 +1,19 @@
+-- V42: Queryable admin audit log
+-- Severity was only recorded inside action_data; promote it to a column so it can be
+-- filtered on, and index the columns the audit-log endpoint filters and sorts by.
+
+ALTER TABLE web_audit.audit_log
+    ADD COLUMN IF NOT EXISTS severity VARCHAR(20) NOT NULL DEFAULT 'info';
+
+UPDATE web_audit.audit_log
+SET severity = action_data->>'severity'
+WHERE action_data->>'severity' IN ('info', 'warning', 'error');
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_created_at
+    ON web_audit.audit_log (created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_audit_log_user_created_at
+    ON web_audit.audit_log (user_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_audit_log_action_created_at
+    ON web_audit.audit_log (action, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_audit_log_severity_created_at
+    ON web_audit.audit_log (severity, created_at DESC);
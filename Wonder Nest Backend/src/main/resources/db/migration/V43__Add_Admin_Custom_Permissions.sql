@@ -0,0 +1,5 @@
+-- V43: Per-account permission overrides for admins
+-- Effective permissions are the role's permissions plus "granted", minus "denied".
+
+ALTER TABLE web_admin.admin_users
+    ADD COLUMN IF NOT EXISTS custom_permissions JSONB NOT NULL DEFAULT '{"granted": [], "denied": []}';
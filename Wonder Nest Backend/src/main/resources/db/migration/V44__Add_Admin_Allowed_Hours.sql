@@ -0,0 +1,6 @@
+-- V44: Optional time-of-day access restriction for admins
+-- NULL means unrestricted. Shape:
+-- {"timezone": "Europe/London", "windows": [{"days": ["MONDAY"], "start": "09:00", "end": "17:00"}]}
+
+ALTER TABLE web_admin.admin_users
+    ADD COLUMN IF NOT EXISTS allowed_hours JSONB;
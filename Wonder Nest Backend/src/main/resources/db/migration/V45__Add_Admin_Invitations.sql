@@ -0,0 +1,21 @@
+-- V45: Invitations for new admin accounts
+-- The emailed token is never stored; token_hash is its SHA-256 hex digest.
+
+CREATE TABLE IF NOT EXISTS web_admin.admin_invitations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    email VARCHAR(255) NOT NULL,
+    role VARCHAR(50) NOT NULL CHECK (role IN (
+        'super_admin',
+        'content_moderator',
+        'content_creator',
+        'analytics_viewer',
+        'support_agent'
+    )),
+    token_hash VARCHAR(64) NOT NULL UNIQUE,
+    invited_by UUID NOT NULL REFERENCES web_admin.admin_users(id) ON DELETE CASCADE,
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    accepted_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_admin_invitations_email ON web_admin.admin_invitations (email);
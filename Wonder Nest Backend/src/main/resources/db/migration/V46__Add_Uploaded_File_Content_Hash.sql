@@ -0,0 +1,6 @@
+-- V46: Content hash for uploaded files, backing the download ETag
+-- SHA-256 hex digest of the stored bytes; NULL for files uploaded before this migration,
+-- which fall back to a weak ETag built from key, size and upload time.
+
+ALTER TABLE core.uploaded_files
+    ADD COLUMN IF NOT EXISTS content_hash VARCHAR(64);
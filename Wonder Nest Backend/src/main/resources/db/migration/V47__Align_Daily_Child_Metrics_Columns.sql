@@ -0,0 +1,26 @@
+-- V47: Columns the application reads from analytics.daily_child_metrics
+-- V2 created the table with an earlier set of counters; the family dashboard reads the ones
+-- below. The original columns are left in place.
+
+ALTER TABLE analytics.daily_child_metrics
+    ADD COLUMN IF NOT EXISTS total_words INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS unique_words INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS conversation_turns INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS audio_session_count INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS total_audio_duration_minutes INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS content_sessions INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS total_screen_time_minutes INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS educational_content_minutes INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS completed_content_count INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS vocabulary_diversity_score DECIMAL(4, 2),
+    ADD COLUMN IF NOT EXISTS engagement_score DECIMAL(3, 2),
+    ADD COLUMN IF NOT EXISTS milestone_achievements INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS most_active_hour INTEGER,
+    ADD COLUMN IF NOT EXISTS preferred_content_types TEXT NOT NULL DEFAULT '[]';
+
+-- Carry over what the earlier columns already recorded
+UPDATE analytics.daily_child_metrics
+SET total_screen_time_minutes = COALESCE(screen_time_minutes, 0),
+    educational_content_minutes = COALESCE(learning_time_minutes, 0),
+    content_sessions = COALESCE(content_items_viewed, 0)
+WHERE total_screen_time_minutes = 0;
@@ -0,0 +1,6 @@
+-- V48: Per-child daily screen-time limit set by parents
+-- NULL means the child follows the family's max_screen_time_minutes setting.
+
+ALTER TABLE family.child_profiles
+    ADD COLUMN IF NOT EXISTS daily_screen_time_limit_minutes INTEGER
+        CHECK (daily_screen_time_limit_minutes BETWEEN 0 AND 1440);
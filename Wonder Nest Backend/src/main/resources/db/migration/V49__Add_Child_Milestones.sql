@@ -0,0 +1,27 @@
+-- V49: Developmental milestones a parent has marked achieved
+-- One row per child and catalog milestone; the catalog itself lives in the application and is
+-- filtered to the child's age. achieved_at is set on the first mark and never moved.
+
+SET search_path TO core, public;
+
+CREATE TABLE IF NOT EXISTS milestones (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    child_id UUID NOT NULL REFERENCES family.child_profiles(id) ON DELETE CASCADE,
+    milestone_key VARCHAR(100) NOT NULL,
+    milestone_type VARCHAR(100) NOT NULL,
+    milestone_name VARCHAR(200) NOT NULL,
+    description TEXT,
+    typical_age_months_min INTEGER NOT NULL,
+    typical_age_months_max INTEGER NOT NULL,
+    achieved BOOLEAN NOT NULL DEFAULT FALSE,
+    achieved_at TIMESTAMP WITH TIME ZONE,
+    child_age_months_at_achievement INTEGER,
+    evidence_source VARCHAR(100),
+    confidence_level DECIMAL(3, 2) NOT NULL DEFAULT 0,
+    parent_notes TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (child_id, milestone_key)
+);
+
+CREATE INDEX IF NOT EXISTS idx_milestones_child ON milestones(child_id);
@@ -0,0 +1,18 @@
+-- V50: Deferred COPPA child data exports
+-- Large exports are built in the background and stored until file_expires_at, after which the
+-- archive is removed from storage and the job marked expired. Jobs survive restarts.
+
+CREATE TABLE IF NOT EXISTS compliance.child_data_export_jobs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    child_id UUID NOT NULL REFERENCES family.child_profiles(id) ON DELETE CASCADE,
+    parent_id UUID NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'ready', 'failed', 'expired')),
+    file_key VARCHAR(500),
+    requested_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    completed_at TIMESTAMP WITH TIME ZONE,
+    file_expires_at TIMESTAMP WITH TIME ZONE
+);
+
+CREATE INDEX IF NOT EXISTS idx_child_data_export_jobs_child ON compliance.child_data_export_jobs (child_id);
+CREATE INDEX IF NOT EXISTS idx_child_data_export_jobs_expiry
+    ON compliance.child_data_export_jobs (file_expires_at) WHERE status = 'ready';
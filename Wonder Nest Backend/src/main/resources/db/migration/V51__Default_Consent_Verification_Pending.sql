@@ -0,0 +1,6 @@
+-- V51: New consent decisions start unverified
+-- V37 defaulted verification_status to 'verified' so existing rows kept working. New rows must
+-- go through a verifier, so anything inserted without an explicit status is pending.
+
+ALTER TABLE compliance.coppa_consent
+    ALTER COLUMN verification_status SET DEFAULT 'pending';
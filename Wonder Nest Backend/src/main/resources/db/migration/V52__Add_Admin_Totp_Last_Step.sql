@@ -0,0 +1,6 @@
+-- V52: TOTP replay protection for admin two-factor authentication
+-- The time step of the last accepted code; a code for the same or an earlier step is refused,
+-- so a code seen by someone else can't be used again within its validity window.
+
+ALTER TABLE web_admin.admin_users
+    ADD COLUMN IF NOT EXISTS two_fa_last_used_step BIGINT;
@@ -0,0 +1,7 @@
+-- V53: Separate marker for children whose data was deleted
+-- A COPPA deletion used to flag the profile the same way archiving does, so deleted children
+-- showed up in archived listings and could be restored. Deleted profiles now carry deleted_at
+-- instead and are left out of every listing and of restore.
+
+ALTER TABLE family.child_profiles
+    ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMP WITH TIME ZONE;
@@ -0,0 +1,9 @@
+-- V54: Allow the audio upload category
+-- FileCategory.AUDIO is stored as 'audio', which the V17 category check rejected, so audio
+-- uploads failed on insert.
+
+ALTER TABLE core.uploaded_files DROP CONSTRAINT IF EXISTS uploaded_files_category_check;
+
+ALTER TABLE core.uploaded_files
+    ADD CONSTRAINT uploaded_files_category_check
+    CHECK (category IN ('profile_picture', 'content', 'document', 'game_asset', 'artwork', 'audio'));
@@ -0,0 +1,38 @@
+-- V55: Append-only history of simple game data saves
+-- Most games save through games.simple_game_data (the game data routes, batch saves, story
+-- adventure and vocabulary), which V36 doesn't cover. Every insert/update there copies the new
+-- value here with a per-key version number so parents can restore earlier progress. Pruned by
+-- GameDataHistoryPruneTask with the same retention as games.child_game_data_history.
+
+CREATE TABLE IF NOT EXISTS games.simple_game_data_history (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    child_id UUID NOT NULL,
+    game_type VARCHAR(100) NOT NULL,
+    data_key VARCHAR(200) NOT NULL,
+    data_version INTEGER NOT NULL,
+    data_value JSONB NOT NULL,
+    recorded_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (child_id, game_type, data_key, data_version)
+);
+
+CREATE INDEX IF NOT EXISTS idx_simple_game_data_history_recorded_at
+    ON games.simple_game_data_history(recorded_at);
+
+-- Writers to one key are serialized by the row lock on games.simple_game_data (or its unique
+-- index on insert), so MAX + 1 can't hand out the same version twice
+CREATE OR REPLACE FUNCTION games.record_simple_game_data_history()
+RETURNS TRIGGER AS $$
+BEGIN
+    INSERT INTO games.simple_game_data_history (child_id, game_type, data_key, data_version, data_value, recorded_at)
+    SELECT NEW.child_id, NEW.game_type, NEW.data_key, COALESCE(MAX(h.data_version), 0) + 1, NEW.data_value,
+           COALESCE(NEW.updated_at, CURRENT_TIMESTAMP)
+    FROM games.simple_game_data_history h
+    WHERE h.child_id = NEW.child_id AND h.game_type = NEW.game_type AND h.data_key = NEW.data_key;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS record_simple_game_data_history ON games.simple_game_data;
+CREATE TRIGGER record_simple_game_data_history
+    AFTER INSERT OR UPDATE OF data_value ON games.simple_game_data
+    FOR EACH ROW EXECUTE FUNCTION games.record_simple_game_data_history();
@@ -0,0 +1,16 @@
+-- V56: Rows for the packs the content pack catalogue serves
+-- The catalogue is still mock data in ContentPackServiceSimple, but ratings (V34), installs (V35)
+-- and reviews reference content_packs, so the packs it serves need rows here. Ratings and download
+-- counts start from the catalogue's figures and are maintained in these rows from then on.
+
+INSERT INTO content_packs (
+    id, name, short_description, pack_type, price_cents, is_free, is_featured, is_premium,
+    age_min, age_max, status, published_at, download_count, rating_average, rating_count
+) VALUES
+    ('11111111-1111-1111-1111-111111111111', 'Safari Animals', 'African safari animals', 'character_bundle',
+     299, false, true, false, 3, 8, 'published', CURRENT_TIMESTAMP, 1250, 4.70, 234),
+    ('22222222-2222-2222-2222-222222222222', 'Magical Castle', 'Fantasy castle backgrounds', 'backdrop_collection',
+     399, false, true, true, 5, 10, 'published', CURRENT_TIMESTAMP, 2100, 4.90, 456),
+    ('33333333-3333-3333-3333-333333333333', 'Happy Vehicles', 'Colorful vehicle stickers', 'sticker_pack',
+     0, true, false, false, 2, 6, 'published', CURRENT_TIMESTAMP, 5000, 4.50, 789)
+ON CONFLICT (id) DO NOTHING;
@@ -0,0 +1,11 @@
+-- V57: Pseudonymous external IDs for children
+-- Analytics, audio metrics and other child-facing APIs identify children by an opaque external
+-- ID instead of the child_profiles UUID. Parents can rotate it; the old ID stops resolving as
+-- soon as the row is updated. One current ID per child, issued on first use.
+
+CREATE TABLE IF NOT EXISTS family.child_pseudonyms (
+    child_id UUID PRIMARY KEY REFERENCES family.child_profiles(id) ON DELETE CASCADE,
+    external_id VARCHAR(40) NOT NULL UNIQUE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    rotated_at TIMESTAMP WITH TIME ZONE
+);
@@ -0,0 +1,42 @@
+-- V58: Recorded analytics events, daily totals and replay markers
+-- AnalyticsEventService used to hold raw events, daily totals and the client event IDs it had
+-- seen in memory, so everything was lost on restart and the maps grew until then. Sampled raw
+-- events now go to analytics.analytics_events (with the fields the service records), and the
+-- totals and client event IDs get their own tables. All three are pruned by the retention sweep
+-- and removed with the rest of a child's data.
+
+ALTER TABLE analytics.analytics_events
+    ADD COLUMN IF NOT EXISTS content_id VARCHAR(255),
+    ADD COLUMN IF NOT EXISTS duration INTEGER,
+    ADD COLUMN IF NOT EXISTS sample_rate DOUBLE PRECISION NOT NULL DEFAULT 1.0,
+    -- Clients send their own session identifiers, which aren't always UUIDs
+    ALTER COLUMN session_id TYPE VARCHAR(100);
+
+CREATE INDEX IF NOT EXISTS idx_analytics_events_child_created
+    ON analytics.analytics_events(child_id, created_at);
+
+-- Counted before sampling, so they stay exact for sampled event types
+CREATE TABLE IF NOT EXISTS analytics.analytics_event_daily_totals (
+    child_id UUID NOT NULL REFERENCES family.child_profiles(id) ON DELETE CASCADE,
+    event_type VARCHAR(50) NOT NULL,
+    event_date DATE NOT NULL,
+    event_count BIGINT NOT NULL DEFAULT 0,
+    total_duration BIGINT NOT NULL DEFAULT 0,
+    PRIMARY KEY (child_id, event_type, event_date)
+);
+
+CREATE INDEX IF NOT EXISTS idx_analytics_event_daily_totals_date
+    ON analytics.analytics_event_daily_totals(event_date);
+
+-- One row per client event ID already counted, so a replayed event isn't counted again.
+-- Kept as long as raw events are.
+CREATE TABLE IF NOT EXISTS analytics.analytics_client_events (
+    child_id UUID NOT NULL REFERENCES family.child_profiles(id) ON DELETE CASCADE,
+    client_event_id VARCHAR(100) NOT NULL,
+    event_id UUID NOT NULL,
+    recorded_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (child_id, client_event_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_analytics_client_events_recorded_at
+    ON analytics.analytics_client_events(recorded_at);
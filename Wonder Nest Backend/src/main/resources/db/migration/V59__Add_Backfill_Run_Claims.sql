@@ -0,0 +1,8 @@
+-- V59: Claim backfill runs in the database
+-- Backfills used to run inside the admin's request behind a per-instance lock, so two instances
+-- could run the same job at once and a crash left the row stuck at 'running'. A run now claims
+-- the row by writing its run_id and only saves progress while it still holds it; a 'running' row
+-- whose updated_at stops moving is marked failed so the job can be resumed from its cursor.
+
+ALTER TABLE web_audit.backfill_progress
+    ADD COLUMN IF NOT EXISTS run_id UUID;
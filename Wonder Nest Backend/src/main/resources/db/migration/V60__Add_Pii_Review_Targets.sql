@@ -0,0 +1,10 @@
+-- V60: Let moderators release held child data
+-- V38 held flagged saves in compliance.pii_review_queue but nothing read them back. A moderator
+-- can now release an entry, which writes it where the original save was going, or discard it.
+-- target records that destination: 'simple' for simple_game_data (the plain and batch saves),
+-- 'game_instance' for per-instance game data saved through the game data service.
+
+ALTER TABLE compliance.pii_review_queue
+    ADD COLUMN IF NOT EXISTS target VARCHAR(20) NOT NULL DEFAULT 'simple'
+        CHECK (target IN ('simple', 'game_instance')),
+    ADD COLUMN IF NOT EXISTS reviewed_by UUID;
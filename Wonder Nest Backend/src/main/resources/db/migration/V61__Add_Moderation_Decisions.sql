@@ -0,0 +1,18 @@
+-- V61: Keep moderation decisions in the database
+-- Moderator workload analytics read every approve/reject decision over a date range, so the
+-- decisions have to outlive the process that made them. appealed/overturned_on_appeal are
+-- filled by an appeal flow once there is one; until then overturn rates report as null.
+
+CREATE TABLE IF NOT EXISTS games.moderation_decisions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    item_id UUID NOT NULL,
+    moderator_id UUID NOT NULL,
+    outcome VARCHAR(20) NOT NULL CHECK (outcome IN ('approve', 'reject')),
+    submitted_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    decided_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    appealed BOOLEAN NOT NULL DEFAULT FALSE,
+    overturned_on_appeal BOOLEAN NOT NULL DEFAULT FALSE
+);
+
+CREATE INDEX IF NOT EXISTS idx_moderation_decisions_decided_at ON games.moderation_decisions(decided_at);
+CREATE INDEX IF NOT EXISTS idx_moderation_decisions_item ON games.moderation_decisions(item_id);
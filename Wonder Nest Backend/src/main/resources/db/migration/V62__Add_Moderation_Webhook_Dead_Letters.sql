@@ -0,0 +1,17 @@
+-- V62: Keep moderation webhook dead letters in the database
+-- Deliveries that ran out of retries were only logged and held by the process, so a restart
+-- lost them. They're stored here until an admin replays them; replayed_at marks the ones that
+-- have since gone through.
+
+CREATE TABLE IF NOT EXISTS games.moderation_webhook_dead_letters (
+    id UUID PRIMARY KEY,
+    endpoint TEXT NOT NULL,
+    event JSONB NOT NULL,
+    attempts INTEGER NOT NULL,
+    last_error TEXT NOT NULL,
+    failed_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    replayed_at TIMESTAMP WITH TIME ZONE
+);
+
+CREATE INDEX IF NOT EXISTS idx_moderation_webhook_dead_letters_pending
+    ON games.moderation_webhook_dead_letters(failed_at) WHERE replayed_at IS NULL;
@@ -0,0 +1,12 @@
+-- V63: Keep family content reports in the database
+-- Reports on marketplace items and content packs were held by the process, so a restart
+-- cleared them and unhid whatever they had hidden from discovery. Each family counts once
+-- per item; reporting again replaces the reason.
+
+CREATE TABLE IF NOT EXISTS games.content_reports (
+    content_id UUID NOT NULL,
+    reporter_family_id UUID NOT NULL,
+    reason TEXT NOT NULL,
+    reported_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    CONSTRAINT uq_content_reports_content_reporter UNIQUE (content_id, reporter_family_id)
+);
@@ -0,0 +1,67 @@
+-- V64: Keep creator submissions, drafts and version history in the database
+-- Creator profiles, tiers, submissions, draft content and pack versions were held by the
+-- process, so a restart lost every draft and emptied the review queue. Profiles use the
+-- existing marketplace.creator_profiles table; the rest get tables of their own.
+
+ALTER TABLE marketplace.creator_profiles
+    ADD COLUMN IF NOT EXISTS content_specialties JSONB NOT NULL DEFAULT '[]';
+
+-- One row per item a creator has drafted or submitted, whatever its review state.
+-- creator_id has no foreign key because admins can file submissions for creators
+-- that haven't registered a profile yet
+CREATE TABLE IF NOT EXISTS marketplace.creator_submissions (
+    item_id UUID PRIMARY KEY,
+    creator_id UUID NOT NULL,
+    title VARCHAR(200) NOT NULL,
+    status VARCHAR(30) NOT NULL CHECK (status IN (
+        'DRAFT', 'PENDING_REVIEW', 'PENDING_CHANGES', 'WITHDRAWN', 'APPROVED', 'PUBLISHED', 'REJECTED', 'ARCHIVED'
+    )),
+    content_type VARCHAR(50),
+    submitted_at TIMESTAMP WITH TIME ZONE,
+    published_at TIMESTAMP WITH TIME ZONE,
+    embargo_until TIMESTAMP WITH TIME ZONE,
+    claimed_by UUID,
+    claimed_at TIMESTAMP WITH TIME ZONE,
+    rejection_category VARCHAR(50),
+    rejection_guidance TEXT,
+    rejection_notes TEXT,
+    rejected_by UUID,
+    rejected_at TIMESTAMP WITH TIME ZONE,
+    possible_duplicates JSONB NOT NULL DEFAULT '[]',
+    automated_findings JSONB NOT NULL DEFAULT '[]',
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_creator_submissions_creator ON marketplace.creator_submissions(creator_id);
+CREATE INDEX IF NOT EXISTS idx_creator_submissions_status ON marketplace.creator_submissions(status, submitted_at);
+
+-- The editable content behind a draft; revision guards JSON Patch edits
+CREATE TABLE IF NOT EXISTS marketplace.creator_drafts (
+    item_id UUID PRIMARY KEY REFERENCES marketplace.creator_submissions(item_id) ON DELETE CASCADE,
+    revision INTEGER NOT NULL,
+    title VARCHAR(200) NOT NULL,
+    description TEXT NOT NULL,
+    content_type VARCHAR(50) NOT NULL,
+    age_range VARCHAR(20) NOT NULL,
+    price DECIMAL(8,2) NOT NULL,
+    licensing_model VARCHAR(30) NOT NULL,
+    tags JSONB NOT NULL DEFAULT '[]',
+    educational_goals JSONB NOT NULL DEFAULT '[]',
+    content_data JSONB NOT NULL DEFAULT '{}',
+    version VARCHAR(50),
+    changelog TEXT,
+    primary_language VARCHAR(10) NOT NULL DEFAULT 'en',
+    languages_supported JSONB NOT NULL DEFAULT '[]',
+    localizations JSONB NOT NULL DEFAULT '{}',
+    drop_incomplete_languages BOOLEAN NOT NULL DEFAULT FALSE,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Released versions of a pack with their changelogs
+CREATE TABLE IF NOT EXISTS marketplace.creator_content_versions (
+    item_id UUID NOT NULL,
+    version VARCHAR(50) NOT NULL,
+    changelog TEXT NOT NULL,
+    released_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    PRIMARY KEY (item_id, version)
+);
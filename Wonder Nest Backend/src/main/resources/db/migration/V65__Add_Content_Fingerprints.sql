@@ -0,0 +1,11 @@
+-- V65: Keep duplicate-detection fingerprints in the database
+-- The fingerprint index was held by the process, so after a restart new submissions were only
+-- compared with content submitted since. One row per submitted item; a new version replaces it.
+
+CREATE TABLE IF NOT EXISTS marketplace.content_fingerprints (
+    item_id UUID PRIMARY KEY,
+    normalized_title TEXT NOT NULL,
+    content_hash VARCHAR(64) NOT NULL,
+    vocabulary JSONB NOT NULL DEFAULT '[]',
+    registered_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
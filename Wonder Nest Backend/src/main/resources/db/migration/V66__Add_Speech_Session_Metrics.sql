@@ -0,0 +1,19 @@
+-- V66: Keep per-session speech metrics in the database
+-- The metrics were held by the process, so trends only covered its uptime and a restart lost every
+-- record of a child's audio sessions. The retention sweep deletes rows by recorded_at.
+
+CREATE TABLE IF NOT EXISTS analytics.speech_session_metrics (
+    id UUID PRIMARY KEY,
+    child_id UUID NOT NULL REFERENCES family.child_profiles(id) ON DELETE CASCADE,
+    session_id VARCHAR(100),
+    speech_clarity DOUBLE PRECISION NOT NULL,
+    engagement_level DOUBLE PRECISION NOT NULL,
+    session_duration INTEGER NOT NULL,
+    vocabulary JSONB NOT NULL DEFAULT '[]',
+    recorded_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_speech_session_metrics_child_recorded
+    ON analytics.speech_session_metrics(child_id, recorded_at);
+CREATE INDEX IF NOT EXISTS idx_speech_session_metrics_recorded
+    ON analytics.speech_session_metrics(recorded_at);
@@ -0,0 +1,124 @@
+package com.wondernest.api
+
+import com.auth0.jwt.JWT
+import com.auth0.jwt.algorithms.Algorithm
+import com.wondernest.data.database.table.UserRole
+import com.wondernest.domain.model.ChildProfile
+import com.wondernest.domain.model.Family
+import com.wondernest.domain.model.FamilyMember
+import com.wondernest.domain.model.User
+import com.wondernest.domain.repository.FamilyRepository
+import com.wondernest.services.auth.JwtService
+import io.ktor.client.request.*
+import io.ktor.http.*
+import io.ktor.serialization.kotlinx.json.*
+import io.ktor.server.application.*
+import io.ktor.server.auth.*
+import io.ktor.server.auth.jwt.*
+import io.ktor.server.plugins.contentnegotiation.*
+import io.ktor.server.response.*
+import io.ktor.server.routing.*
+import io.ktor.server.testing.*
+import io.mockk.coEvery
+import io.mockk.every
+import io.mockk.mockk
+import kotlinx.coroutines.runBlocking
+import kotlinx.datetime.Clock
+import org.junit.jupiter.api.BeforeEach
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import org.junit.jupiter.api.assertThrows
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertFalse
+import kotlin.test.assertNotNull
+import kotlin.test.assertNull
+import kotlin.test.assertTrue
+
+@DisplayName("Family Context Tests")
+class FamilyContextTest {
+
+    private val familyRepository = mockk<FamilyRepository>()
+    private val resolver = FamilyContextResolver(familyRepository)
+    private val jwtService = JwtService()
+
+    private val parentId = UUID.randomUUID()
+    private val familyId = UUID.randomUUID()
+    private val ownChildId = UUID.randomUUID()
+    private val otherFamilyChildId = UUID.randomUUID()
+    private val familylessUserId = UUID.randomUUID()
+
+    @BeforeEach
+    fun setup() {
+        val family = mockk<Family> { every { id } returns familyId }
+        val child = mockk<ChildProfile> { every { id } returns ownChildId }
+
+        coEvery { familyRepository.getFamilyByUserId(parentId) } returns family
+        coEvery { familyRepository.getFamilyByUserId(familylessUserId) } returns null
+        coEvery { familyRepository.getFamilyMembers(familyId) } returns listOf(
+            FamilyMember(id = UUID.randomUUID(), familyId = familyId, userId = parentId, role = "guardian", joinedAt = Clock.System.now())
+        )
+        coEvery { familyRepository.getChildrenByFamily(familyId) } returns listOf(child)
+    }
+
+    @Test
+    @DisplayName("Context only owns the family's own children")
+    fun rejectsAnotherFamilysChild() = runBlocking {
+        val context = assertNotNull(resolver.resolve(parentId))
+
+        assertEquals(familyId, context.familyId)
+        assertTrue(context.isParent())
+        assertTrue(context.ownsChild(ownChildId))
+        assertFalse(context.ownsChild(otherFamilyChildId))
+        assertThrows<FamilyAccessDeniedException> { context.requireChild(otherFamilyChildId) }
+    }
+
+    @Test
+    @DisplayName("Users without a family resolve to no context")
+    fun noFamilyResolvesToNull() = runBlocking {
+        assertNull(resolver.resolve(familylessUserId))
+    }
+
+    @Test
+    @DisplayName("Route responds 403 for another family's child and for users without a family")
+    fun routeRejectsOtherFamilyAccess() = testApplication {
+        application {
+            install(ContentNegotiation) { json() }
+            install(Authentication) {
+                jwt("auth-jwt") {
+                    verifier(JWT.require(Algorithm.HMAC256(jwtService.secret)).withIssuer(jwtService.issuer).build())
+                    validate { JWTPrincipal(it.payload) }
+                }
+            }
+            routing {
+                authenticate("auth-jwt") {
+                    get("/children/{childId}") {
+                        val childId = UUID.fromString(call.parameters["childId"])
+                        call.requireChildAccess(resolver, childId) ?: return@get
+                        call.respond(HttpStatusCode.OK, "ok")
+                    }
+                }
+            }
+        }
+
+        val parentToken = tokenFor(parentId)
+        assertEquals(HttpStatusCode.OK, client.get("/children/$ownChildId") { bearerAuth(parentToken) }.status)
+        assertEquals(HttpStatusCode.Forbidden, client.get("/children/$otherFamilyChildId") { bearerAuth(parentToken) }.status)
+        assertEquals(
+            HttpStatusCode.Forbidden,
+            client.get("/children/$ownChildId") { bearerAuth(tokenFor(familylessUserId)) }.status
+        )
+    }
+
+    private fun tokenFor(userId: UUID): String = jwtService.generateToken(
+        User(
+            id = userId,
+            email = "parent@example.com",
+            firstName = "Test",
+            lastName = "Parent",
+            role = UserRole.PARENT,
+            createdAt = Clock.System.now(),
+            updatedAt = Clock.System.now()
+        )
+    ).accessToken
+}
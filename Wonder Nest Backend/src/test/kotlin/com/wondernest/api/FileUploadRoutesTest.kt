@@ -19,6 +19,7 @@ import kotlinx.serialization.json.Json
 import org.junit.jupiter.api.Test
 import java.util.UUID
 import kotlin.test.assertEquals
+import kotlin.test.assertFalse
 import kotlin.test.assertTrue
 
 class FileUploadRoutesTest {
@@ -183,7 +184,7 @@ class FileUploadRoutesTest {
         assertEquals(HttpStatusCode.BadRequest, response.status)
         
         val responseBody = response.bodyAsText()
-        assertTrue(responseBody.contains("VALIDATION_ERROR"))
+        assertTrue(responseBody.contains("INVALID_MIME_TYPE"))
     }
     
     @Test
@@ -222,4 +223,118 @@ class FileUploadRoutesTest {
             assertEquals(HttpStatusCode.Created, response.status, "Failed for category: $category")
         }
     }
-}
\ No newline at end of file
+    
+    @Test
+    fun `test file metadata rejects invalid ids and unknown files without mock data`() = testApplication {
+        application {
+            configureDependencyInjection()
+            configureSecurity()
+            routing {
+                authenticate("auth-jwt") {
+                    route("/api/v1") {
+                        fileUploadRoutes()
+                    }
+                }
+            }
+        }
+        
+        val client = createClient()
+        val token = generateTestToken()
+        
+        val invalid = client.get("/api/v1/files/not-a-uuid") {
+            header(HttpHeaders.Authorization, "Bearer $token")
+        }
+        assertEquals(HttpStatusCode.BadRequest, invalid.status)
+        assertTrue(invalid.bodyAsText().contains("INVALID_FILE_ID"))
+        
+        val missing = client.get("/api/v1/files/${UUID.randomUUID()}") {
+            header(HttpHeaders.Authorization, "Bearer $token")
+        }
+        val body = missing.bodyAsText()
+        assertEquals(HttpStatusCode.NotFound, missing.status)
+        assertTrue(body.contains("FILE_NOT_FOUND"))
+        assertFalse(body.contains("example.jpg"))
+    }
+    
+    @Test
+    fun `test batch upload reports failures per file`() = testApplication {
+        application {
+            configureDependencyInjection()
+            configureSecurity()
+            routing {
+                authenticate("auth-jwt") {
+                    route("/api/v1") {
+                        fileUploadRoutes()
+                    }
+                }
+            }
+        }
+        
+        val client = createClient()
+        val token = generateTestToken()
+        
+        val response = client.submitFormWithBinaryData(
+            url = "/api/v1/files/upload-batch",
+            formData = formData {
+                append("file", "MZ not a sticker".toByteArray(), Headers.build {
+                    append(HttpHeaders.ContentType, "image/png")
+                    append(HttpHeaders.ContentDisposition, "filename=\"sticker_0.png\"")
+                })
+                append("file", "notes".toByteArray(), Headers.build {
+                    append(HttpHeaders.ContentType, "text/plain")
+                    append(HttpHeaders.ContentDisposition, "filename=\"notes.txt\"")
+                })
+            }
+        ) {
+            header(HttpHeaders.Authorization, "Bearer $token")
+            parameter("category", "document")
+        }
+        
+        // One bad file doesn't fail the request; each file carries its own status
+        assertEquals(HttpStatusCode.OK, response.status)
+        
+        val body = response.bodyAsText()
+        assertTrue(body.contains("sticker_0.png"))
+        assertTrue(body.contains("INVALID_MIME_TYPE"))
+        assertTrue(body.contains("notes.txt"))
+    }
+    
+    @Test
+    fun `test batch upload over the limit is rejected as a whole`() = testApplication {
+        application {
+            configureDependencyInjection()
+            configureSecurity()
+            routing {
+                authenticate("auth-jwt") {
+                    route("/api/v1") {
+                        fileUploadRoutes()
+                    }
+                }
+            }
+        }
+        
+        val client = createClient()
+        val token = generateTestToken()
+        
+        val response = client.submitFormWithBinaryData(
+            url = "/api/v1/files/upload-batch",
+            formData = formData {
+                repeat(MAX_BATCH_FILES + 5) { index ->
+                    append("file", "MZ not a sticker".toByteArray(), Headers.build {
+                        append(HttpHeaders.ContentType, "image/png")
+                        append(HttpHeaders.ContentDisposition, "filename=\"sticker_$index.png\"")
+                    })
+                }
+            }
+        ) {
+            header(HttpHeaders.Authorization, "Bearer $token")
+            parameter("category", "artwork")
+        }
+        
+        assertEquals(HttpStatusCode.PayloadTooLarge, response.status)
+        
+        val body = response.bodyAsText()
+        assertTrue(body.contains("BATCH_LIMIT_EXCEEDED"))
+        assertFalse(body.contains("sticker_0.png"))
+    }
+}
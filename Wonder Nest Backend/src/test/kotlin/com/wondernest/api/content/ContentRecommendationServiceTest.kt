@@ -0,0 +1,65 @@
+package com.wondernest.api.content
+
+import com.wondernest.domain.model.ChildProfile
+import com.wondernest.domain.repository.FamilyRepository
+import io.mockk.coEvery
+import io.mockk.every
+import io.mockk.mockk
+import kotlinx.coroutines.runBlocking
+import kotlinx.datetime.Instant
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertNotNull
+import kotlin.test.assertNull
+import kotlin.test.assertTrue
+
+@DisplayName("Content Recommendation Tests")
+class ContentRecommendationServiceTest {
+
+    private val childId = UUID.randomUUID()
+    private val now = Instant.parse("2025-09-01T12:00:00Z")
+    private val familyRepository = mockk<FamilyRepository>()
+
+    private val catalog = listOf(3, 5, 6, 10, 1).map { rating ->
+        generateMockContent(null, null).first().copy(id = "rated_$rating", ageRating = rating)
+    }
+    private val service = ContentRecommendationService(familyRepository, { catalog }) { now }
+
+    private fun childBornOn(date: String) {
+        val child = mockk<ChildProfile> { every { birthDate } returns Instant.parse(date) }
+        coEvery { familyRepository.getChildProfile(childId) } returns child
+    }
+
+    @Test
+    @DisplayName("A 5-year-old never receives age-10 content")
+    fun fiveYearOldGetsNothingOlder() = runBlocking {
+        childBornOn("2020-03-01T00:00:00Z")
+
+        val response = assertNotNull(service.recommendationsFor(childId))
+
+        assertEquals(5, response.childAge)
+        assertEquals(listOf("rated_5", "rated_3"), response.recommendations.map { it.id })
+        assertTrue(response.recommendations.none { it.ageRating > 5 })
+    }
+
+    @Test
+    @DisplayName("Age is counted from the birthday, not the birth year")
+    fun ageBeforeBirthday() = runBlocking {
+        childBornOn("2020-09-02T00:00:00Z")
+
+        val response = assertNotNull(service.recommendationsFor(childId))
+
+        assertEquals(4, response.childAge)
+        assertEquals(listOf("rated_3", "rated_1"), response.recommendations.map { it.id })
+    }
+
+    @Test
+    @DisplayName("Unknown children get no recommendations")
+    fun unknownChild() = runBlocking {
+        coEvery { familyRepository.getChildProfile(childId) } returns null
+
+        assertNull(service.recommendationsFor(childId))
+    }
+}
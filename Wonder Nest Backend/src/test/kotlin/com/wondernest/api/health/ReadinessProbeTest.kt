@@ -0,0 +1,83 @@
+package com.wondernest.api.health
+
+import com.wondernest.services.storage.StorageProvider
+import io.ktor.client.request.*
+import io.ktor.client.statement.*
+import io.ktor.http.*
+import io.ktor.serialization.kotlinx.json.*
+import io.ktor.server.application.*
+import io.ktor.server.plugins.contentnegotiation.*
+import io.ktor.server.routing.*
+import io.ktor.server.testing.*
+import io.mockk.coEvery
+import io.mockk.mockk
+import kotlinx.coroutines.awaitCancellation
+import kotlinx.coroutines.runBlocking
+import kotlinx.serialization.json.Json
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.net.ConnectException
+import kotlin.test.assertEquals
+import kotlin.test.assertTrue
+
+@DisplayName("Readiness Probe Tests")
+class ReadinessProbeTest {
+
+    private val up: suspend () -> Boolean = { true }
+    private val redisDown: suspend () -> Boolean = { throw ConnectException("Connection refused") }
+    private val hung: suspend () -> Boolean = { awaitCancellation() }
+
+    private fun ApplicationTestBuilder.serve(probe: ReadinessProbe) {
+        application {
+            install(ContentNegotiation) { json() }
+            routing { readinessRoute(probe) }
+        }
+    }
+
+    @Test
+    @DisplayName("Ready when the database and Redis both answer")
+    fun ready() = testApplication {
+        serve(ReadinessProbe(mapOf("database" to up, "redis" to up)))
+
+        assertEquals(HttpStatusCode.OK, client.get("/health/ready").status)
+    }
+
+    @Test
+    @DisplayName("A broken Redis returns 503 with per-dependency status")
+    fun brokenRedis() = testApplication {
+        serve(ReadinessProbe(mapOf("database" to up, "redis" to redisDown)))
+
+        val response = client.get("/health/ready")
+        assertEquals(HttpStatusCode.ServiceUnavailable, response.status)
+
+        val body = Json.decodeFromString<ReadinessStatus>(response.bodyAsText())
+        assertEquals("NOT_READY", body.status)
+        assertEquals("UP", body.services["database"]?.status)
+        assertEquals("DOWN", body.services["redis"]?.status)
+    }
+
+    @Test
+    @DisplayName("A failing storage health check flips readiness to 503")
+    fun brokenStorage() = testApplication {
+        val storageProvider = mockk<StorageProvider>()
+        coEvery { storageProvider.healthCheck() } returns false
+        val storage: suspend () -> Boolean = { storageProvider.healthCheck() }
+        serve(ReadinessProbe(mapOf("database" to up, "redis" to up, "storage" to storage)))
+
+        val response = client.get("/health/ready")
+        assertEquals(HttpStatusCode.ServiceUnavailable, response.status)
+        val body = Json.decodeFromString<ReadinessStatus>(response.bodyAsText())
+        assertEquals("DOWN", body.services["storage"]?.status)
+        assertEquals("UP", body.services["redis"]?.status)
+    }
+
+    @Test
+    @DisplayName("A hung dependency times out instead of hanging the probe")
+    fun hungDependencyTimesOut() = runBlocking {
+        val readiness = ReadinessProbe(mapOf("database" to hung, "redis" to up), timeoutMillis = 50).check()
+
+        assertEquals("NOT_READY", readiness.status)
+        assertEquals("DOWN", readiness.services["database"]?.status)
+        assertTrue(readiness.services["database"]?.message.orEmpty().startsWith("Timed out"))
+    }
+}
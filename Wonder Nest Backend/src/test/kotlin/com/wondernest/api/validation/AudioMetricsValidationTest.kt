@@ -0,0 +1,57 @@
+package com.wondernest.api.validation
+
+import com.wondernest.services.audio.AudioMetricsRequest
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import kotlin.test.assertEquals
+import kotlin.test.assertFalse
+import kotlin.test.assertTrue
+
+@DisplayName("Audio Metrics Validation Tests")
+class AudioMetricsValidationTest {
+
+    private val valid = AudioMetricsRequest(
+        childId = "8c6f1f7e-6a51-4c4b-9a57-2f0f1f0b6f10",
+        speechClarity = 0.8,
+        engagementLevel = 0.6,
+        sessionDuration = 600,
+        vocabularyUsed = listOf("ball", "dog")
+    )
+
+    @Test
+    @DisplayName("A clarity of 1.5 is rejected against its field")
+    fun clarityOutOfRange() {
+        val result = AudioMetricsValidation.validate(valid.copy(speechClarity = 1.5))
+
+        assertFalse(result.isValid)
+        assertEquals(listOf("speechClarity"), result.fieldErrors.map { it.field })
+    }
+
+    @Test
+    @DisplayName("A negative session duration is rejected")
+    fun negativeDuration() {
+        val result = AudioMetricsValidation.validate(valid.copy(sessionDuration = -5))
+
+        assertEquals(listOf("sessionDuration"), result.fieldErrors.map { it.field })
+    }
+
+    @Test
+    @DisplayName("A session ID longer than the stored column is rejected")
+    fun sessionIdTooLong() {
+        val result = AudioMetricsValidation.validate(valid.copy(sessionId = "s".repeat(101)))
+
+        assertEquals(listOf("sessionId"), result.fieldErrors.map { it.field })
+    }
+
+    @Test
+    @DisplayName("Every bad field is reported at once; in-range metrics pass")
+    fun combinedAndValid() {
+        val result = AudioMetricsValidation.validate(
+            valid.copy(engagementLevel = Double.NaN, vocabularyUsed = listOf("ok", "x".repeat(51)))
+        )
+
+        assertEquals(listOf("engagementLevel", "vocabularyUsed"), result.fieldErrors.map { it.field })
+        assertTrue(AudioMetricsValidation.validate(valid).isValid)
+        assertTrue(AudioMetricsValidation.validate(valid.copy(speechClarity = 1.0, engagementLevel = 0.0)).isValid)
+    }
+}
@@ -0,0 +1,46 @@
+package com.wondernest.api.validation
+
+import com.wondernest.services.auth.SignupRequest
+import com.wondernest.utils.FieldError
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+import kotlin.test.assertTrue
+
+@DisplayName("Auth Validation Tests")
+class AuthValidationTest {
+
+    @Test
+    @DisplayName("An invalid email and a weak password are reported against their fields")
+    fun signupFieldErrors() {
+        val result = AuthValidation.validateSignupRequest(SignupRequest(email = "not-an-email", password = "short"))
+
+        assertEquals(listOf("email", "password"), result.fieldErrors.map { it.field })
+        assertEquals(FieldError("email", "Invalid email format"), result.fieldErrors.first())
+        assertTrue(result.fieldErrors[1].message.contains("at least 8 characters"))
+        // The flat list is still there for older clients
+        assertEquals(result.fieldErrors.map { it.message }, result.errors)
+    }
+
+    @Test
+    @DisplayName("Field errors travel with the validation exception")
+    fun exceptionCarriesFieldErrors() {
+        val error = assertFailsWith<AuthValidationException> {
+            AuthValidation.validateSignupRequest(
+                SignupRequest(email = "parent@example.com", password = "Sunflower42", firstName = "A".repeat(51), timezone = "Mars/Olympus")
+            ).throwIfInvalid()
+        }
+
+        assertEquals(listOf("firstName", "timezone"), error.fieldErrors.map { it.field })
+    }
+
+    @Test
+    @DisplayName("Valid signups have no field errors")
+    fun validSignup() {
+        val result = AuthValidation.validateSignupRequest(SignupRequest(email = "parent@example.com", password = "Sunflower42"))
+
+        assertTrue(result.isValid)
+        assertTrue(result.fieldErrors.isEmpty())
+    }
+}
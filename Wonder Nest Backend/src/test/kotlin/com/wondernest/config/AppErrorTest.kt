@@ -0,0 +1,114 @@
+package com.wondernest.config
+
+import com.wondernest.services.resilience.RedisFeature
+import com.wondernest.services.resilience.RedisUnavailableException
+import io.ktor.client.request.*
+import io.ktor.client.statement.*
+import io.ktor.http.*
+import io.ktor.server.application.*
+import io.ktor.server.plugins.*
+import io.ktor.server.request.*
+import io.ktor.server.response.*
+import io.ktor.server.routing.*
+import io.ktor.server.testing.*
+import kotlinx.serialization.Serializable
+import kotlinx.serialization.json.Json
+import kotlinx.serialization.json.jsonObject
+import kotlinx.serialization.json.jsonPrimitive
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.sql.SQLException
+import java.sql.SQLTransientConnectionException
+import kotlin.test.assertEquals
+
+@DisplayName("App Error Tests")
+class AppErrorTest {
+
+    @Serializable
+    private data class Greeting(val name: String)
+
+    @Test
+    @DisplayName("Each error code has a fixed HTTP status")
+    fun codeStatuses() {
+        val expected = mapOf(
+            AppErrorCode.VALIDATION_ERROR to HttpStatusCode.BadRequest,
+            AppErrorCode.MALFORMED_REQUEST to HttpStatusCode.BadRequest,
+            AppErrorCode.UNAUTHORIZED to HttpStatusCode.Unauthorized,
+            AppErrorCode.TOKEN_REVOKED to HttpStatusCode.Unauthorized,
+            AppErrorCode.SECURITY_ERROR to HttpStatusCode.Forbidden,
+            AppErrorCode.NOT_FOUND to HttpStatusCode.NotFound,
+            AppErrorCode.PAYLOAD_TOO_LARGE to HttpStatusCode.PayloadTooLarge,
+            AppErrorCode.UNSUPPORTED_MEDIA_TYPE to HttpStatusCode.UnsupportedMediaType,
+            AppErrorCode.SERVICE_UNAVAILABLE to HttpStatusCode.ServiceUnavailable,
+            AppErrorCode.QUERY_TIMEOUT to HttpStatusCode.ServiceUnavailable,
+            AppErrorCode.INTERNAL_ERROR to HttpStatusCode.InternalServerError
+        )
+
+        assertEquals(AppErrorCode.entries.toSet(), expected.keys)
+        expected.forEach { (code, status) -> assertEquals(status, AppError(code).status, code.name) }
+    }
+
+    @Test
+    @DisplayName("Exceptions are classified into error codes")
+    fun classification() {
+        val expected = listOf(
+            IllegalArgumentException("bad age") to AppErrorCode.VALIDATION_ERROR,
+            SecurityException("token for another family") to AppErrorCode.SECURITY_ERROR,
+            NoSuchElementException("no such child") to AppErrorCode.NOT_FOUND,
+            NotFoundException() to AppErrorCode.NOT_FOUND,
+            BadRequestException("Failed to convert request body") to AppErrorCode.MALFORMED_REQUEST,
+            MissingRequestParameterException("childId") to AppErrorCode.MALFORMED_REQUEST,
+            UnsupportedMediaTypeException(ContentType.Text.Plain) to AppErrorCode.UNSUPPORTED_MEDIA_TYPE,
+            PayloadTooLargeException(1024) to AppErrorCode.PAYLOAD_TOO_LARGE,
+            IllegalStateException("boom") to AppErrorCode.INTERNAL_ERROR,
+            SQLTransientConnectionException("Connection is not available") to AppErrorCode.SERVICE_UNAVAILABLE,
+            IllegalStateException("wrapped", SQLTransientConnectionException("timed out")) to AppErrorCode.SERVICE_UNAVAILABLE,
+            RedisUnavailableException(RedisFeature.SIGNED_URL_NONCES) to AppErrorCode.SERVICE_UNAVAILABLE,
+            SQLException("canceling statement due to statement timeout", "57014") to AppErrorCode.QUERY_TIMEOUT,
+            AppError(AppErrorCode.UNAUTHORIZED) to AppErrorCode.UNAUTHORIZED
+        )
+
+        expected.forEach { (cause, code) -> assertEquals(code, AppError.from(cause).code, cause::class.simpleName) }
+        // Security failures and internal errors never echo the exception message
+        assertEquals("Access denied", AppError.from(SecurityException("token for another family")).message)
+        assertEquals("An unexpected error occurred", AppError.from(IllegalStateException("boom")).message)
+    }
+
+    private fun ApplicationTestBuilder.serve() {
+        application {
+            configureSerialization()
+            configureSecurity()
+            configureMonitoring()
+            routing {
+                post("/greet") { call.respond(call.receive<Greeting>()) }
+                get("/denied") { throw SecurityException("nope") }
+            }
+        }
+    }
+
+    private fun errorOf(text: String) = Json.parseToJsonElement(text).jsonObject.getValue("error").jsonObject
+
+    @Test
+    @DisplayName("Thrown errors and request body rejections share one body shape")
+    fun responseShape() = testApplication {
+        serve()
+
+        val denied = client.get("/denied") { header(HttpHeaders.XRequestId, "req-7") }
+        assertEquals(HttpStatusCode.Forbidden, denied.status)
+        val error = errorOf(denied.bodyAsText())
+        assertEquals(setOf("code", "message", "request_id"), error.keys)
+        assertEquals("SECURITY_ERROR", error.getValue("code").jsonPrimitive.content)
+        assertEquals("req-7", error.getValue("request_id").jsonPrimitive.content)
+
+        val malformed = client.post("/greet") {
+            contentType(ContentType.Application.Json)
+            setBody("{\"name\": ")
+        }
+        assertEquals(HttpStatusCode.BadRequest, malformed.status)
+        assertEquals("MALFORMED_REQUEST", errorOf(malformed.bodyAsText()).getValue("code").jsonPrimitive.content)
+
+        val missing = client.get("/nowhere")
+        assertEquals(HttpStatusCode.NotFound, missing.status)
+        assertEquals("NOT_FOUND", errorOf(missing.bodyAsText()).getValue("code").jsonPrimitive.content)
+    }
+}
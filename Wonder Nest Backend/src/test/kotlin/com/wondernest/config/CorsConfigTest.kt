@@ -0,0 +1,68 @@
+package com.wondernest.config
+
+import io.ktor.client.request.*
+import io.ktor.http.*
+import io.ktor.server.application.*
+import io.ktor.server.config.*
+import io.ktor.server.response.*
+import io.ktor.server.routing.*
+import io.ktor.server.testing.*
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+import kotlin.test.assertNull
+
+@DisplayName("CORS Configuration Tests")
+class CorsConfigTest {
+
+    @Test
+    @DisplayName("A configured origin is allowed and an unconfigured one is not reflected")
+    fun configuredOriginOnly() = testApplication {
+        application {
+            configureHTTP(CorsConfig(listOf(CorsConfig.parseOrigin("https://staging.wondernest.app"))))
+            routing {
+                get("/ping") { call.respondText("pong") }
+            }
+        }
+
+        val allowed = client.get("/ping") { header(HttpHeaders.Origin, "https://staging.wondernest.app") }
+        assertEquals("https://staging.wondernest.app", allowed.headers[HttpHeaders.AccessControlAllowOrigin])
+        assertEquals("true", allowed.headers[HttpHeaders.AccessControlAllowCredentials])
+
+        val other = client.get("/ping") { header(HttpHeaders.Origin, "https://evil.example.com") }
+        assertNull(other.headers[HttpHeaders.AccessControlAllowOrigin])
+    }
+
+    @Test
+    @DisplayName("The environment overrides the config file, and empty values fall back to the defaults")
+    fun precedence() {
+        val fileConfig = MapApplicationConfig("cors.allowed_origins" to "https://admin.wondernest.app")
+
+        assertEquals(
+            listOf("https://staging.wondernest.app:8443"),
+            CorsConfig.fromConfig(fileConfig, mapOf("CORS_ALLOWED_ORIGINS" to " https://staging.wondernest.app:8443/ , "))
+                .allowedOrigins.map { it.toString() }
+        )
+        assertEquals(
+            listOf("https://admin.wondernest.app"),
+            CorsConfig.fromConfig(fileConfig, mapOf("CORS_ALLOWED_ORIGINS" to "")).allowedOrigins.map { it.toString() }
+        )
+        assertEquals(
+            CorsConfig.DEFAULT_ORIGINS,
+            CorsConfig.fromConfig(MapApplicationConfig("cors.allowed_origins" to " , "), emptyMap())
+                .allowedOrigins.map { it.toString() }
+        )
+    }
+
+    @Test
+    @DisplayName("Malformed origins fail fast")
+    fun malformedOrigins() {
+        listOf("staging.wondernest.app", "ftp://files.wondernest.app", "https://wondernest.app/admin", "https://")
+            .forEach { origin ->
+                assertFailsWith<IllegalArgumentException>(origin) {
+                    CorsConfig.fromConfig(MapApplicationConfig(), mapOf("CORS_ALLOWED_ORIGINS" to origin))
+                }
+            }
+    }
+}
@@ -0,0 +1,57 @@
+package com.wondernest.config
+
+import io.ktor.server.application.*
+import io.ktor.server.engine.*
+import io.ktor.server.netty.*
+import io.ktor.server.response.*
+import io.ktor.server.routing.*
+import kotlinx.coroutines.delay
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.net.ServerSocket
+import java.net.URI
+import java.net.http.HttpClient
+import java.net.http.HttpRequest
+import java.net.http.HttpResponse
+import java.util.concurrent.CopyOnWriteArrayList
+import java.util.concurrent.CountDownLatch
+import java.util.concurrent.TimeUnit
+import kotlin.test.assertEquals
+import kotlin.test.assertTrue
+
+@DisplayName("Graceful Shutdown Tests")
+class GracefulShutdownTest {
+
+    @Test
+    @DisplayName("A request started before shutdown completes, and resources close after it")
+    fun inFlightRequestCompletes() {
+        val port = ServerSocket(0).use { it.localPort }
+        val started = CountDownLatch(1)
+        val events = CopyOnWriteArrayList<String>()
+
+        val server = embeddedServer(Netty, port = port) {
+            registerShutdownSequence(listOf("database pool" to { events.add("database pool closed") }))
+            routing {
+                get("/slow") {
+                    started.countDown()
+                    delay(500)
+                    events.add("request finished")
+                    call.respondText("done")
+                }
+            }
+        }.start(wait = false)
+
+        val response = HttpClient.newHttpClient().sendAsync(
+            HttpRequest.newBuilder(URI("http://127.0.0.1:$port/slow")).build(),
+            HttpResponse.BodyHandlers.ofString()
+        )
+        assertTrue(started.await(5, TimeUnit.SECONDS))
+
+        server.stop(gracePeriodMillis = 2_000, timeoutMillis = 5_000)
+
+        val completed = response.get(5, TimeUnit.SECONDS)
+        assertEquals(200, completed.statusCode())
+        assertEquals("done", completed.body())
+        assertEquals(listOf("request finished", "database pool closed"), events)
+    }
+}
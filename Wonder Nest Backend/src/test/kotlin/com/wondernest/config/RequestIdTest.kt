@@ -0,0 +1,59 @@
+package com.wondernest.config
+
+import io.ktor.client.request.*
+import io.ktor.client.statement.*
+import io.ktor.http.*
+import io.ktor.server.application.*
+import io.ktor.server.routing.*
+import io.ktor.server.testing.*
+import kotlinx.serialization.json.Json
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.util.*
+import kotlin.test.assertEquals
+import kotlin.test.assertNotEquals
+import kotlin.test.assertNotNull
+
+@DisplayName("Request ID Tests")
+class RequestIdTest {
+
+    private fun ApplicationTestBuilder.serve() {
+        application {
+            configureSerialization()
+            configureSecurity()
+            configureMonitoring()
+            routing {
+                get("/fail") { throw IllegalArgumentException("bad input") }
+            }
+        }
+    }
+
+    private fun errorBody(text: String) = Json { ignoreUnknownKeys = true }.decodeFromString<ErrorResponse>(text).error
+
+    @Test
+    @DisplayName("A provided request id is echoed in the header and the error body")
+    fun providedIdEchoed() = testApplication {
+        serve()
+
+        val response = client.get("/fail") { header(HttpHeaders.XRequestId, "client-req-42") }
+
+        assertEquals(HttpStatusCode.BadRequest, response.status)
+        assertEquals("client-req-42", response.headers[HttpHeaders.XRequestId])
+        assertEquals("client-req-42", errorBody(response.bodyAsText()).requestId)
+    }
+
+    @Test
+    @DisplayName("Without a usable request id a UUID is generated")
+    fun generatedIdIsUuid() = testApplication {
+        serve()
+
+        for (incoming in listOf(null, "bad id\nforged log line")) {
+            val response = client.get("/fail") { incoming?.let { header(HttpHeaders.XRequestId, it) } }
+
+            val requestId = assertNotNull(response.headers[HttpHeaders.XRequestId])
+            assertEquals(requestId, UUID.fromString(requestId).toString())
+            assertNotEquals(incoming, requestId)
+            assertEquals(requestId, errorBody(response.bodyAsText()).requestId)
+        }
+    }
+}
@@ -0,0 +1,35 @@
+package com.wondernest.config
+
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.net.InetSocketAddress
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+
+@DisplayName("Server Binding Tests")
+class ServerBindingTest {
+
+    @Test
+    @DisplayName("A configured host and port produce the expected socket address")
+    fun configuredHost() {
+        val host = ServerBinding.hostFromEnvironment(mapOf("SERVER_HOST" to "127.0.0.1"))
+
+        assertEquals(InetSocketAddress("127.0.0.1", 8080), ServerBinding(host, 8080).socketAddress)
+        assertEquals("0:0:0:0:0:0:0:1", ServerBinding.parseHost("[::1]").hostAddress)
+    }
+
+    @Test
+    @DisplayName("Unset or empty SERVER_HOST binds all interfaces")
+    fun defaultHost() {
+        assertEquals("0.0.0.0", ServerBinding.hostFromEnvironment(emptyMap()).hostAddress)
+        assertEquals("0.0.0.0", ServerBinding.hostFromEnvironment(mapOf("SERVER_HOST" to " ")).hostAddress)
+    }
+
+    @Test
+    @DisplayName("Anything other than an IP address is rejected")
+    fun invalidHost() {
+        listOf("localhost", "256.0.0.1", "127.0.0", "not:an:address").forEach { host ->
+            assertFailsWith<IllegalArgumentException>(host) { ServerBinding.parseHost(host) }
+        }
+    }
+}
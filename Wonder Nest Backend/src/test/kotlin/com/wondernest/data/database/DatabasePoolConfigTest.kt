@@ -0,0 +1,71 @@
+package com.wondernest.data.database
+
+import com.wondernest.config.AppError
+import com.wondernest.config.AppErrorCode
+import com.zaxxer.hikari.HikariConfig
+import com.zaxxer.hikari.HikariDataSource
+import io.mockk.every
+import io.mockk.mockk
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.sql.Connection
+import java.sql.SQLTransientConnectionException
+import javax.sql.DataSource
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+import kotlin.test.assertNull
+import kotlin.test.assertTrue
+
+@DisplayName("Database Pool Config Tests")
+class DatabasePoolConfigTest {
+
+    @Test
+    @DisplayName("Pool settings are read from the environment with defaults for the rest")
+    fun fromEnvironment() {
+        val config = DatabasePoolConfig.fromEnvironment(
+            mapOf("DB_MAX_POOL_SIZE" to "8", "DB_ACQUIRE_TIMEOUT_MS" to "750", "DB_MAX_LIFETIME_MS" to "nonsense")
+        )
+
+        assertEquals(8, config.maxConnections)
+        assertEquals(5, config.minConnections)
+        assertEquals(750, config.acquireTimeoutMillis)
+        assertEquals(1_800_000, config.maxLifetimeMillis)
+        assertFailsWith<IllegalArgumentException> { DatabasePoolConfig.fromEnvironment(mapOf("DB_MIN_IDLE" to "50")) }
+    }
+
+    @Test
+    @DisplayName("The statement timeout is passed to the driver as a startup option")
+    fun statementTimeoutOption() {
+        val config = HikariConfig().also { DatabasePoolConfig(statementTimeoutMillis = 3_000).applyTo(it) }
+        assertEquals("-c statement_timeout=3000", config.dataSourceProperties.getProperty("options"))
+        assertNull(config.connectionInitSql)
+
+        val disabled = HikariConfig().also { DatabasePoolConfig(statementTimeoutMillis = 0).applyTo(it) }
+        assertNull(disabled.dataSourceProperties.getProperty("options"))
+    }
+
+    @Test
+    @DisplayName("An exhausted pool fails fast after the acquire timeout and maps to 503")
+    fun exhaustedPoolTimesOut() {
+        val driver = mockk<DataSource>(relaxed = true).also {
+            every { it.connection } answers {
+                mockk<Connection>(relaxed = true).also { connection -> every { connection.isValid(any()) } returns true }
+            }
+        }
+        val hikari = HikariConfig().apply {
+            dataSource = driver
+            DatabasePoolConfig(maxConnections = 1, minConnections = 0, acquireTimeoutMillis = 250).applyTo(this)
+        }
+
+        HikariDataSource(hikari).use { pool ->
+            pool.connection.use {
+                val started = System.nanoTime()
+                val error = assertFailsWith<SQLTransientConnectionException> { pool.connection }
+                val waitedMillis = (System.nanoTime() - started) / 1_000_000
+
+                assertTrue(waitedMillis < 2_000, "waited ${waitedMillis}ms for a connection")
+                assertEquals(AppErrorCode.SERVICE_UNAVAILABLE, AppError.from(error).code)
+            }
+        }
+    }
+}
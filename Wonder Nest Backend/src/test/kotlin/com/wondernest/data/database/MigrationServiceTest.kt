@@ -0,0 +1,34 @@
+package com.wondernest.data.database
+
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import kotlin.test.assertEquals
+import kotlin.test.assertFalse
+import kotlin.test.assertTrue
+
+@DisplayName("Migration Status Tests")
+class MigrationServiceTest {
+
+    @Test
+    @DisplayName("Schema behind the expected minimum is unhealthy")
+    fun tooOldVersionIsUnhealthy() {
+        val check = MigrationService.checkSchemaVersion(appliedVersion = "8", expectedMinimum = "26")
+
+        assertFalse(check.healthy)
+        assertEquals("8", check.appliedVersion)
+        assertEquals("26", check.expectedMinimumVersion)
+    }
+
+    @Test
+    @DisplayName("Schema at or beyond the expected minimum is healthy")
+    fun currentVersionIsHealthy() {
+        assertTrue(MigrationService.checkSchemaVersion("26", "26").healthy)
+        assertTrue(MigrationService.checkSchemaVersion("27", "26").healthy)
+    }
+
+    @Test
+    @DisplayName("Missing migration history is unhealthy")
+    fun noAppliedMigrationsIsUnhealthy() {
+        assertFalse(MigrationService.checkSchemaVersion(null).healthy)
+    }
+}
@@ -0,0 +1,71 @@
+package com.wondernest.data.database
+
+import com.wondernest.config.AppError
+import com.wondernest.config.AppErrorCode
+import com.zaxxer.hikari.HikariConfig
+import com.zaxxer.hikari.HikariDataSource
+import org.jetbrains.exposed.sql.Database
+import org.jetbrains.exposed.sql.transactions.transaction
+import org.junit.jupiter.api.AfterAll
+import org.junit.jupiter.api.BeforeAll
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import org.junit.jupiter.api.TestInstance
+import org.testcontainers.containers.PostgreSQLContainer
+import org.testcontainers.junit.jupiter.Testcontainers
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+import kotlin.test.assertTrue
+
+@Testcontainers(disabledWithoutDocker = true)
+@TestInstance(TestInstance.Lifecycle.PER_CLASS)
+@DisplayName("Statement Timeout Tests")
+class StatementTimeoutTest {
+
+    private val postgres = PostgreSQLContainer("postgres:15-alpine")
+
+    private lateinit var pool: HikariDataSource
+    private lateinit var database: Database
+
+    @BeforeAll
+    fun connect() {
+        postgres.start()
+        pool = HikariDataSource(HikariConfig().apply {
+            jdbcUrl = postgres.jdbcUrl
+            username = postgres.username
+            password = postgres.password
+            DatabasePoolConfig(maxConnections = 2, minConnections = 0, statementTimeoutMillis = 200).applyTo(this)
+        })
+        database = Database.connect(pool)
+    }
+
+    @AfterAll
+    fun close() {
+        pool.close()
+        postgres.stop()
+    }
+
+    @Test
+    @DisplayName("A query running past statement_timeout is cancelled and reported as a query timeout")
+    fun slowQueryIsCancelled() {
+        val started = System.nanoTime()
+        val error = assertFailsWith<Exception> {
+            transaction(database) { exec("SELECT pg_sleep(5)") }
+        }
+        val tookMillis = (System.nanoTime() - started) / 1_000_000
+
+        assertTrue(tookMillis < 3_000, "query ran for ${tookMillis}ms")
+        assertEquals(AppErrorCode.QUERY_TIMEOUT, AppError.from(error).code)
+    }
+
+    @Test
+    @DisplayName("Heavy work can raise the timeout for its own transaction only")
+    fun overrideIsScopedToTransaction() {
+        transactionWithStatementTimeout(5_000, database) { exec("SELECT pg_sleep(0.5)") }
+
+        val timeout = transaction(database) {
+            exec("SHOW statement_timeout") { rs -> rs.next(); rs.getString(1) }
+        }
+        assertEquals("200ms", timeout)
+    }
+}
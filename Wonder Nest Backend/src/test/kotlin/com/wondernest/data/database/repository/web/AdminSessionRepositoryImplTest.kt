@@ -0,0 +1,82 @@
+package com.wondernest.data.database.repository.web
+
+import com.wondernest.domain.web.AdminSession
+import kotlinx.coroutines.runBlocking
+import org.jetbrains.exposed.sql.Database
+import org.jetbrains.exposed.sql.transactions.transaction
+import org.junit.jupiter.api.AfterAll
+import org.junit.jupiter.api.BeforeAll
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import org.junit.jupiter.api.TestInstance
+import org.testcontainers.containers.PostgreSQLContainer
+import org.testcontainers.junit.jupiter.Testcontainers
+import java.time.Instant
+import java.time.temporal.ChronoUnit
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertFalse
+import kotlin.test.assertTrue
+
+@Testcontainers(disabledWithoutDocker = true)
+@TestInstance(TestInstance.Lifecycle.PER_CLASS)
+@DisplayName("Admin Session Repository Tests")
+class AdminSessionRepositoryImplTest {
+
+    private val postgres = PostgreSQLContainer("postgres:15-alpine")
+    private val repository = AdminSessionRepositoryImpl()
+
+    // web_admin.admin_sessions as V7 creates it, without the foreign key to admin_users
+    private val schema = """
+        CREATE SCHEMA web_admin;
+        CREATE TABLE web_admin.admin_sessions (
+            id UUID PRIMARY KEY, admin_user_id UUID NOT NULL,
+            session_token VARCHAR(255) UNIQUE NOT NULL, refresh_token VARCHAR(255) UNIQUE,
+            ip_address INET NOT NULL, user_agent TEXT, device_fingerprint VARCHAR(255),
+            expires_at TIMESTAMPTZ NOT NULL, last_activity TIMESTAMPTZ DEFAULT now(),
+            is_active BOOLEAN DEFAULT true, created_at TIMESTAMPTZ DEFAULT now())
+    """.trimIndent()
+
+    @BeforeAll
+    fun connect() {
+        postgres.start()
+        Database.connect(postgres.jdbcUrl, "org.postgresql.Driver", postgres.username, postgres.password)
+        transaction { schema.split(";").filter { it.isNotBlank() }.forEach { exec(it) } }
+    }
+
+    @AfterAll
+    fun close() {
+        postgres.stop()
+    }
+
+    private fun session(adminId: UUID, expiresIn: Long = 8) = Instant.now().truncatedTo(ChronoUnit.MILLIS).let { now ->
+        AdminSession(
+            id = UUID.randomUUID(),
+            adminUserId = adminId,
+            sessionToken = UUID.randomUUID().toString(),
+            refreshToken = UUID.randomUUID().toString(),
+            ipAddress = "203.0.113.7, 10.0.0.1",
+            expiresAt = now.plus(expiresIn, ChronoUnit.HOURS),
+            lastActivity = now,
+            createdAt = now
+        )
+    }
+
+    @Test
+    @DisplayName("Sessions round-trip and only active, unexpired ones are listed")
+    fun listsActiveSessions() = runBlocking {
+        val adminId = UUID.randomUUID()
+        val active = repository.create(session(adminId))
+        val revoked = repository.create(session(adminId))
+        repository.create(session(adminId, expiresIn = -1))
+
+        assertTrue(repository.deactivateSession(revoked.id))
+        assertFalse(repository.deactivateSession(revoked.id))
+
+        assertEquals(listOf(active.id), repository.findActiveSessionsForUser(adminId).map { it.id })
+        val stored = repository.findById(active.id)!!
+        assertEquals("203.0.113.7", stored.ipAddress)
+        assertEquals(active.id, repository.findByToken(active.refreshToken!!)?.id)
+        assertFalse(repository.findById(revoked.id)!!.isActive)
+    }
+}
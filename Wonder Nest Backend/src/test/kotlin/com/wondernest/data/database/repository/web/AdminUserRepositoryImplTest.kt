@@ -0,0 +1,148 @@
+package com.wondernest.data.database.repository.web
+
+import com.wondernest.domain.web.AdminRole
+import com.wondernest.domain.web.AdminUser
+import com.wondernest.domain.web.AllowedHours
+import com.wondernest.domain.web.AllowedHoursWindow
+import com.wondernest.domain.web.CustomPermissions
+import kotlinx.coroutines.runBlocking
+import org.jetbrains.exposed.sql.Database
+import org.jetbrains.exposed.sql.transactions.transaction
+import org.junit.jupiter.api.AfterAll
+import org.junit.jupiter.api.BeforeAll
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import org.junit.jupiter.api.TestInstance
+import org.testcontainers.containers.PostgreSQLContainer
+import org.testcontainers.junit.jupiter.Testcontainers
+import java.time.Instant
+import java.time.temporal.ChronoUnit
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertFalse
+import kotlin.test.assertNull
+import kotlin.test.assertTrue
+
+@Testcontainers(disabledWithoutDocker = true)
+@TestInstance(TestInstance.Lifecycle.PER_CLASS)
+@DisplayName("Admin User Repository Tests")
+class AdminUserRepositoryImplTest {
+
+    private val postgres = PostgreSQLContainer("postgres:15-alpine")
+    private val repository = AdminUserRepositoryImpl()
+
+    // web_admin.admin_users with the columns added by V31, V43, V44 and V52
+    private val schema = """
+        CREATE SCHEMA web_admin;
+        CREATE TABLE web_admin.admin_users (
+            id UUID PRIMARY KEY, email VARCHAR(255) UNIQUE NOT NULL,
+            password_hash VARCHAR(255) NOT NULL, salt VARCHAR(255) NOT NULL,
+            first_name VARCHAR(100) NOT NULL, last_name VARCHAR(100) NOT NULL, phone_number VARCHAR(20),
+            role VARCHAR(50) NOT NULL, permissions JSONB NOT NULL DEFAULT '[]',
+            two_fa_enabled BOOLEAN DEFAULT false, two_fa_secret VARCHAR(32),
+            is_active BOOLEAN DEFAULT true, email_verified BOOLEAN DEFAULT false,
+            last_login_at TIMESTAMPTZ, failed_login_attempts INTEGER DEFAULT 0, locked_until TIMESTAMPTZ,
+            created_by UUID, created_at TIMESTAMPTZ DEFAULT now(), updated_at TIMESTAMPTZ DEFAULT now(),
+            two_fa_backup_codes JSONB NOT NULL DEFAULT '[]',
+            custom_permissions JSONB NOT NULL DEFAULT '{"granted": [], "denied": []}',
+            allowed_hours JSONB, two_fa_last_used_step BIGINT)
+    """.trimIndent()
+
+    @BeforeAll
+    fun connect() {
+        postgres.start()
+        Database.connect(postgres.jdbcUrl, "org.postgresql.Driver", postgres.username, postgres.password)
+        transaction { schema.split(";").filter { it.isNotBlank() }.forEach { exec(it) } }
+    }
+
+    @AfterAll
+    fun close() {
+        postgres.stop()
+    }
+
+    private fun admin(): AdminUser {
+        val now = Instant.now().truncatedTo(ChronoUnit.MILLIS)
+        return AdminUser(
+            id = UUID.randomUUID(),
+            email = "admin-${UUID.randomUUID()}@wondernest.app",
+            passwordHash = "hash",
+            salt = "",
+            firstName = "Ada",
+            lastName = "Admin",
+            role = AdminRole.CONTENT_MODERATOR,
+            permissions = emptyList(),
+            createdAt = now,
+            updatedAt = now
+        )
+    }
+
+    @Test
+    @DisplayName("Profile edits, custom permissions, allowed hours and 2FA state are stored")
+    fun updatesRoundTrip() = runBlocking {
+        val created = repository.create(admin())
+        val hours = AllowedHours("Europe/London", listOf(AllowedHoursWindow(listOf("MONDAY"), "09:00", "17:00")))
+
+        val updated = repository.update(
+            created.copy(
+                firstName = "Grace",
+                phoneNumber = null,
+                customPermissions = CustomPermissions(granted = listOf("manage_system_settings")),
+                allowedHours = hours,
+                twoFactorEnabled = true,
+                twoFactorSecret = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ",
+                twoFactorBackupCodes = listOf("abc123")
+            )
+        )
+
+        assertEquals(updated, repository.findByEmail(created.email))
+        assertEquals("Grace", updated.firstName)
+        assertNull(updated.phoneNumber)
+        assertEquals(listOf("manage_system_settings"), updated.customPermissions.granted)
+        assertEquals(hours, updated.allowedHours)
+        assertEquals(listOf("abc123"), updated.twoFactorBackupCodes)
+        assertEquals(AdminRole.CONTENT_MODERATOR, updated.role)
+    }
+
+    @Test
+    @DisplayName("A TOTP step is accepted once and earlier steps are refused afterwards")
+    fun totpStepRecordedOnce() = runBlocking {
+        val created = repository.create(admin())
+
+        assertTrue(repository.recordTotpStep(created.id, 100))
+        assertFalse(repository.recordTotpStep(created.id, 100))
+        assertFalse(repository.recordTotpStep(created.id, 99))
+        assertTrue(repository.recordTotpStep(created.id, 101))
+        assertEquals(101L, repository.findById(created.id)?.twoFactorLastUsedStep)
+    }
+
+    @Test
+    @DisplayName("A backup code is removed once and other codes are kept")
+    fun backupCodeConsumedOnce() = runBlocking {
+        val created = repository.create(admin())
+        repository.update(created.copy(twoFactorBackupCodes = listOf("hash-a", "hash-b")))
+        val now = Instant.now().truncatedTo(ChronoUnit.MILLIS)
+
+        assertEquals(1, repository.consumeBackupCode(created.id, "hash-a", now))
+        assertNull(repository.consumeBackupCode(created.id, "hash-a", now))
+        assertNull(repository.consumeBackupCode(created.id, "hash-c", now))
+
+        val stored = repository.findById(created.id)
+        assertEquals(listOf("hash-b"), stored?.twoFactorBackupCodes)
+        assertEquals(now, stored?.updatedAt)
+    }
+
+    @Test
+    @DisplayName("Failed attempts add up in the database and restart after an expired lock")
+    fun failedAttemptsIncrement() = runBlocking {
+        val created = repository.create(admin())
+        val now = Instant.now()
+
+        assertEquals(1, repository.incrementFailedLoginAttempts(created.id, now))
+        assertEquals(2, repository.incrementFailedLoginAttempts(created.id, now))
+
+        repository.lockUser(created.id, now.minusSeconds(60))
+        assertEquals(1, repository.incrementFailedLoginAttempts(created.id, now))
+        assertNull(repository.findById(created.id)?.lockedUntil)
+        assertNull(repository.incrementFailedLoginAttempts(UUID.randomUUID(), now))
+    }
+}
@@ -0,0 +1,81 @@
+package com.wondernest.data.database.repository.web
+
+import com.wondernest.domain.web.BackfillStatus
+import kotlinx.coroutines.runBlocking
+import org.jetbrains.exposed.sql.Database
+import org.jetbrains.exposed.sql.transactions.transaction
+import org.junit.jupiter.api.AfterAll
+import org.junit.jupiter.api.BeforeAll
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import org.junit.jupiter.api.TestInstance
+import org.testcontainers.containers.PostgreSQLContainer
+import org.testcontainers.junit.jupiter.Testcontainers
+import java.time.Instant
+import java.time.temporal.ChronoUnit
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertFalse
+import kotlin.test.assertNotNull
+import kotlin.test.assertNull
+import kotlin.test.assertTrue
+
+@Testcontainers(disabledWithoutDocker = true)
+@TestInstance(TestInstance.Lifecycle.PER_CLASS)
+@DisplayName("Backfill Progress Repository Tests")
+class BackfillProgressRepositoryImplTest {
+
+    private val postgres = PostgreSQLContainer("postgres:15-alpine")
+    private val repository = BackfillProgressRepositoryImpl()
+    private val now = Instant.parse("2025-09-01T12:00:00Z")
+
+    // web_audit.backfill_progress as V30 and V59 leave it
+    private val schema = """
+        CREATE SCHEMA web_audit;
+        CREATE TABLE web_audit.backfill_progress (
+            job_name VARCHAR(100) PRIMARY KEY, cursor TEXT, status VARCHAR(20) NOT NULL DEFAULT 'pending',
+            processed BIGINT NOT NULL DEFAULT 0, updated BIGINT NOT NULL DEFAULT 0, batches INTEGER NOT NULL DEFAULT 0,
+            last_error TEXT, started_by UUID, started_at TIMESTAMP,
+            updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP, completed_at TIMESTAMP, run_id UUID)
+    """.trimIndent()
+
+    @BeforeAll
+    fun connect() {
+        postgres.start()
+        Database.connect(postgres.jdbcUrl, "org.postgresql.Driver", postgres.username, postgres.password)
+        transaction { schema.split(";").filter { it.isNotBlank() }.forEach { exec(it) } }
+    }
+
+    @AfterAll
+    fun close() {
+        postgres.stop()
+    }
+
+    @Test
+    @DisplayName("Only one run can claim a job, and only the holder can save progress")
+    fun claimIsExclusive() = runBlocking {
+        val first = UUID.randomUUID()
+        val claimed = assertNotNull(repository.claim("claim-job", first, now))
+        assertEquals(BackfillStatus.PENDING, claimed.status)
+
+        assertNull(repository.claim("claim-job", UUID.randomUUID(), now))
+        assertTrue(repository.save(claimed.copy(runId = first, status = BackfillStatus.RUNNING, cursor = "a", updatedAt = now)))
+        assertFalse(repository.save(claimed.copy(runId = UUID.randomUUID(), cursor = "b", updatedAt = now)))
+        assertEquals("a", repository.find("claim-job")?.cursor)
+    }
+
+    @Test
+    @DisplayName("A run that stopped saving is marked failed and the job can be claimed again")
+    fun staleRunsAreRecovered() = runBlocking {
+        val crashed = UUID.randomUUID()
+        repository.claim("stale-job", crashed, now)
+
+        assertEquals(0, repository.failStale(now.minus(15, ChronoUnit.MINUTES), now))
+        val later = now.plus(20, ChronoUnit.MINUTES)
+        assertEquals(1, repository.failStale(later.minus(15, ChronoUnit.MINUTES), later))
+
+        assertEquals(BackfillStatus.FAILED, repository.find("stale-job")?.status)
+        assertNotNull(repository.claim("stale-job", UUID.randomUUID(), later))
+        assertFalse(repository.save(assertNotNull(repository.find("stale-job")).copy(runId = crashed)))
+    }
+}
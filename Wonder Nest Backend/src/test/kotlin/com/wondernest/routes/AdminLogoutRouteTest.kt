@@ -0,0 +1,112 @@
+package com.wondernest.routes
+
+import com.wondernest.api.web.admin.adminAuthRoutes
+import com.wondernest.config.configureAuthentication
+import com.wondernest.data.database.repository.web.AdminSessionRepository
+import com.wondernest.data.database.repository.web.AdminUserRepository
+import com.wondernest.domain.web.AdminLoginRequest
+import com.wondernest.domain.web.AdminRole
+import com.wondernest.domain.web.AdminSession
+import com.wondernest.domain.web.AdminUser
+import com.wondernest.services.auth.JwtService
+import com.wondernest.services.auth.TokenBlocklist
+import com.wondernest.services.web.admin.AdminAuthService
+import io.ktor.client.request.*
+import io.ktor.http.*
+import io.ktor.serialization.kotlinx.json.*
+import io.ktor.server.application.*
+import io.ktor.server.plugins.contentnegotiation.*
+import io.ktor.server.routing.*
+import io.ktor.server.testing.*
+import io.mockk.coEvery
+import io.mockk.mockk
+import kotlinx.coroutines.runBlocking
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import org.koin.dsl.module
+import org.koin.ktor.plugin.Koin
+import org.springframework.security.crypto.bcrypt.BCryptPasswordEncoder
+import java.time.Instant
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertFalse
+
+@DisplayName("Admin Logout Route Tests")
+class AdminLogoutRouteTest {
+
+    private val jwtService = JwtService()
+    private val tokenBlocklist = mockk<TokenBlocklist>()
+    private val adminUserRepository = mockk<AdminUserRepository>(relaxed = true)
+    private val sessionRepository = mockk<AdminSessionRepository>(relaxed = true)
+    private val sessions = mutableMapOf<UUID, AdminSession>()
+    private val adminAuthService = AdminAuthService(adminUserRepository, sessionRepository, jwtService, mockk(), mockk(relaxed = true))
+
+    private val password = "correct horse battery"
+    private val adminUser = AdminUser(
+        id = UUID.randomUUID(),
+        email = "admin@wondernest.app",
+        passwordHash = BCryptPasswordEncoder(4).encode(password),
+        salt = "",
+        firstName = "Ada",
+        lastName = "Admin",
+        role = AdminRole.CONTENT_MODERATOR,
+        permissions = emptyList(),
+        createdAt = Instant.parse("2025-01-01T00:00:00Z"),
+        updatedAt = Instant.parse("2025-01-01T00:00:00Z")
+    )
+
+    init {
+        coEvery { tokenBlocklist.isRevoked(any()) } returns false
+        coEvery { adminUserRepository.findByEmail(adminUser.email) } returns adminUser
+        coEvery { adminUserRepository.findById(adminUser.id) } returns adminUser
+        coEvery { sessionRepository.create(any()) } answers { firstArg<AdminSession>().also { sessions[it.id] = it } }
+        coEvery { sessionRepository.findById(any()) } answers { sessions[firstArg()] }
+        coEvery { sessionRepository.deactivateSession(any()) } answers {
+            val session = sessions[firstArg()] ?: return@answers false
+            sessions[session.id] = session.copy(isActive = false)
+            true
+        }
+    }
+
+    private fun ApplicationTestBuilder.setup() = application {
+        install(Koin) {
+            modules(module {
+                single { jwtService }
+                single { tokenBlocklist }
+                single { sessionRepository }
+                single { adminAuthService }
+            })
+        }
+        install(ContentNegotiation) { json() }
+        configureAuthentication()
+        routing { route("/api/web/v1") { adminAuthRoutes() } }
+    }
+
+    @Test
+    @DisplayName("An access token stops working once its session is logged out")
+    fun loggedOutTokenRejected() = testApplication {
+        setup()
+        val token = runBlocking {
+            adminAuthService.authenticateAdmin(AdminLoginRequest(adminUser.email, password), "10.0.0.1")
+        }.accessToken
+
+        assertEquals(
+            HttpStatusCode.OK,
+            client.get("/api/web/v1/admin/auth/profile") { bearerAuth(token) }.status
+        )
+        assertEquals(
+            HttpStatusCode.OK,
+            client.post("/api/web/v1/admin/auth/logout") { bearerAuth(token) }.status
+        )
+
+        assertFalse(sessions.values.single().isActive)
+        assertEquals(
+            HttpStatusCode.Unauthorized,
+            client.get("/api/web/v1/admin/auth/profile") { bearerAuth(token) }.status
+        )
+        assertEquals(
+            HttpStatusCode.Unauthorized,
+            client.post("/api/web/v1/admin/auth/logout") { bearerAuth(token) }.status
+        )
+    }
+}
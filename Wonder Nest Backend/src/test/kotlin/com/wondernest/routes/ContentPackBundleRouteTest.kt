@@ -0,0 +1,103 @@
+package com.wondernest.routes
+
+import com.wondernest.config.configureAuthentication
+import com.wondernest.data.database.table.UserRole
+import com.wondernest.domain.model.User
+import com.wondernest.models.ContentPack
+import com.wondernest.services.ContentPackBundleService
+import com.wondernest.services.ContentPackRatingService
+import com.wondernest.services.ContentPackReviewService
+import com.wondernest.services.ContentPackServiceSimple
+import com.wondernest.services.auth.JwtService
+import com.wondernest.services.auth.TokenBlocklist
+import io.ktor.client.request.*
+import io.ktor.client.statement.*
+import io.ktor.http.*
+import io.ktor.serialization.kotlinx.json.*
+import io.ktor.server.application.*
+import io.ktor.server.plugins.contentnegotiation.*
+import io.ktor.server.routing.*
+import io.ktor.server.testing.*
+import io.mockk.coEvery
+import io.mockk.every
+import io.mockk.mockk
+import kotlinx.datetime.Clock
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import org.koin.dsl.module
+import org.koin.ktor.plugin.Koin
+import java.io.OutputStream
+import java.util.UUID
+import kotlin.test.assertEquals
+
+@DisplayName("Content Pack Bundle Route Tests")
+class ContentPackBundleRouteTest {
+
+    private val jwtService = JwtService()
+    private val userId = UUID.randomUUID()
+    private val packId = UUID.randomUUID()
+    private val pack = mockk<ContentPack>(relaxed = true)
+    private val contentPackService = mockk<ContentPackServiceSimple>()
+    private val bundleService = mockk<ContentPackBundleService>()
+    private val tokenBlocklist = mockk<TokenBlocklist>()
+
+    init {
+        every { contentPackService.getPackById(packId, userId) } returns pack
+        every { contentPackService.ownsPack(userId, packId) } returns true
+        every { contentPackService.getPackAssets(packId, userId) } returns emptyList()
+        coEvery { bundleService.writeBundle(pack, emptyList(), any()) } answers {
+            thirdArg<OutputStream>().write("PK".toByteArray())
+        }
+        coEvery { tokenBlocklist.isRevoked(any()) } returns false
+    }
+
+    private fun token(): String = jwtService.generateToken(
+        User(
+            id = userId,
+            email = "parent@example.com",
+            firstName = "Pat",
+            lastName = "Parent",
+            role = UserRole.PARENT,
+            createdAt = Clock.System.now(),
+            updatedAt = Clock.System.now()
+        )
+    ).accessToken
+
+    private fun ApplicationTestBuilder.setup() = application {
+        install(Koin) {
+            modules(module {
+                single { jwtService }
+                single { tokenBlocklist }
+                single { contentPackService }
+                single { bundleService }
+                single { mockk<ContentPackReviewService>() }
+                single { mockk<ContentPackRatingService>() }
+            })
+        }
+        install(ContentNegotiation) { json() }
+        configureAuthentication()
+        routing { contentPackRoutes() }
+    }
+
+    @Test
+    @DisplayName("The owner of a pack can download its bundle with a normal access token")
+    fun ownerDownloadsBundle() = testApplication {
+        setup()
+
+        val response = client.get("/content-packs/$packId/bundle") {
+            header(HttpHeaders.Authorization, "Bearer ${token()}")
+        }
+
+        assertEquals(HttpStatusCode.OK, response.status)
+        assertEquals(ContentType.Application.Zip, response.contentType())
+        assertEquals("PK", response.bodyAsText())
+    }
+
+    @Test
+    @DisplayName("Requests without a token are rejected")
+    fun unauthenticatedRejected() = testApplication {
+        setup()
+
+        assertEquals(HttpStatusCode.Unauthorized, client.get("/content-packs/$packId/bundle").status)
+    }
+}
@@ -0,0 +1,98 @@
+package com.wondernest.services
+
+import com.wondernest.models.ContentPack
+import com.wondernest.models.ContentPackAsset
+import com.wondernest.services.storage.InMemoryStorageProvider
+import kotlinx.coroutines.runBlocking
+import kotlinx.serialization.json.Json
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.io.ByteArrayInputStream
+import java.io.ByteArrayOutputStream
+import java.time.Instant
+import java.util.UUID
+import java.util.zip.ZipInputStream
+import kotlin.test.assertContentEquals
+import kotlin.test.assertEquals
+import kotlin.test.assertNull
+
+@DisplayName("Content Pack Bundle Tests")
+class ContentPackBundleServiceTest {
+
+    private val storage = InMemoryStorageProvider()
+    private val now = Instant.parse("2026-01-01T00:00:00Z")
+    private val service = ContentPackBundleService(storage) { now }
+    private val pack = ContentPack(
+        id = UUID.randomUUID(),
+        name = "Safari Animals",
+        packType = "sticker",
+        createdAt = now,
+        updatedAt = now
+    )
+
+    private fun asset(fileUrl: String, format: String?) = ContentPackAsset(
+        id = UUID.randomUUID(),
+        packId = pack.id,
+        name = "Asset",
+        assetType = "imageStatic",
+        fileUrl = fileUrl,
+        fileFormat = format,
+        createdAt = now,
+        updatedAt = now
+    )
+
+    private fun unzip(bytes: ByteArray): Map<String, ByteArray> {
+        val entries = linkedMapOf<String, ByteArray>()
+        ZipInputStream(ByteArrayInputStream(bytes)).use { zip ->
+            generateSequence { zip.nextEntry }.forEach { entries[it.name] = zip.readBytes() }
+        }
+        return entries
+    }
+
+    private suspend fun store(bytes: ByteArray): String =
+        storage.upload("lion.png", "image/png", ByteArrayInputStream(bytes)).key
+
+    @Test
+    @DisplayName("Bundle contains the manifest followed by each stored asset")
+    fun bundleEntries() = runBlocking {
+        val lionBytes = ByteArray(4096) { it.toByte() }
+        val lion = asset(store(lionBytes), "png")
+        val hippo = asset(store("hippo".toByteArray()), null)
+
+        val out = ByteArrayOutputStream()
+        service.writeBundle(pack, listOf(lion, hippo), out)
+        val entries = unzip(out.toByteArray())
+
+        assertEquals(
+            listOf(ContentPackBundleService.MANIFEST_ENTRY, "assets/${lion.id}.png", "assets/${hippo.id}"),
+            entries.keys.toList()
+        )
+        assertContentEquals(lionBytes, entries.getValue("assets/${lion.id}.png"))
+
+        val manifest = Json.decodeFromString<BundleManifest>(
+            entries.getValue(ContentPackBundleService.MANIFEST_ENTRY).decodeToString()
+        )
+        assertEquals(pack.id.toString(), manifest.packId)
+        assertEquals("2026-01-01T00:00:00Z", manifest.generatedAt)
+        assertEquals(listOf("assets/${lion.id}.png", "assets/${hippo.id}"), manifest.assets.map { it.path })
+    }
+
+    @Test
+    @DisplayName("External and missing assets are listed in the manifest but not archived")
+    fun externalAssetsAreReferenced() = runBlocking {
+        val external = asset("https://cdn.example.com/lion.png", "png")
+        val missing = asset("uploads/gone.png", "png")
+
+        val out = ByteArrayOutputStream()
+        service.writeBundle(pack, listOf(external, missing), out)
+        val entries = unzip(out.toByteArray())
+
+        assertEquals(listOf(ContentPackBundleService.MANIFEST_ENTRY), entries.keys.toList())
+        val manifest = Json.decodeFromString<BundleManifest>(
+            entries.getValue(ContentPackBundleService.MANIFEST_ENTRY).decodeToString()
+        )
+        assertNull(manifest.assets[0].path)
+        assertEquals("https://cdn.example.com/lion.png", manifest.assets[0].sourceUrl)
+        assertEquals("uploads/gone.png", manifest.assets[1].sourceUrl)
+    }
+}
@@ -0,0 +1,68 @@
+package com.wondernest.services
+
+import com.wondernest.models.ContentPackSearchRequest
+import com.wondernest.models.PackPurchaseRequest
+import kotlinx.coroutines.runBlocking
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.time.Instant
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertFalse
+import kotlin.test.assertNotNull
+import kotlin.test.assertTrue
+
+@DisplayName("Content Pack Expiry Tests")
+class ContentPackExpiryTest {
+
+    // The mock packs are owned by every user in ContentPackServiceSimple
+    private val holidayPackId = UUID.fromString("11111111-1111-1111-1111-111111111111")
+    private val userId = UUID.randomUUID()
+    private var now = Instant.parse("2025-12-20T00:00:00Z")
+
+    private val service = ContentPackServiceSimple(
+        clock = { now },
+        statsStore = InMemoryContentPackStatsStore(listings = mapOf(holidayPackId to ContentPackListing(isFeatured = true)))
+    )
+
+    @Test
+    @DisplayName("Expired packs leave featured and search but stay with their owners")
+    fun expiredPackHiddenButOwned() {
+        service.setPackExpiry(holidayPackId, Instant.parse("2026-01-06T00:00:00Z"))
+        assertTrue(service.getFeaturedPacks(userId).any { it.id == holidayPackId })
+
+        now = Instant.parse("2026-01-07T00:00:00Z")
+
+        assertFalse(service.getFeaturedPacks(userId).any { it.id == holidayPackId })
+        val search = service.searchPacks(ContentPackSearchRequest(), userId)
+        assertFalse(search.packs.any { it.id == holidayPackId })
+        assertEquals(2L, search.total)
+
+        val owned = service.getUserOwnedPacks(userId).single { it.id == holidayPackId }
+        assertTrue(owned.isExpired(now))
+        assertNotNull(service.getPackById(holidayPackId, userId))
+        assertNotNull(service.getPackAssets(holidayPackId, userId))
+    }
+
+    @Test
+    @DisplayName("Expired packs can no longer be purchased and clearing the expiry relists them")
+    fun expiredPackNotPurchasable() {
+        service.setPackExpiry(holidayPackId, now.minusSeconds(1))
+
+        val purchase = service.purchasePack(userId, PackPurchaseRequest(packId = holidayPackId))
+        assertFalse(purchase.success)
+
+        service.setPackExpiry(holidayPackId, null)
+        assertTrue(service.getFeaturedPacks(userId).any { it.id == holidayPackId })
+    }
+
+    @Test
+    @DisplayName("The scheduled task demotes packs using the current time")
+    fun taskDemotesExpiredPacks() = runBlocking {
+        val cutoffs = mutableListOf<Instant>()
+        val task = ContentPackExpiryTask(demoteExpired = { cutoff -> cutoffs.add(cutoff); 2 }, clock = { now })
+
+        assertEquals(2, task.runOnce())
+        assertEquals(listOf(now), cutoffs)
+    }
+}
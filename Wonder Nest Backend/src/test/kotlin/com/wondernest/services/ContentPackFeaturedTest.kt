@@ -0,0 +1,79 @@
+package com.wondernest.services
+
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.time.Instant
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertFalse
+import kotlin.test.assertNotNull
+
+@DisplayName("Content Pack Featured Window Tests")
+class ContentPackFeaturedTest {
+
+    private val safariPackId = UUID.fromString("11111111-1111-1111-1111-111111111111")
+    private val castlePackId = UUID.fromString("22222222-2222-2222-2222-222222222222")
+    private val vehiclesPackId = UUID.fromString("33333333-3333-3333-3333-333333333333")
+    private val userId = UUID.randomUUID()
+    private var now = Instant.parse("2025-09-01T12:00:00Z")
+
+    // As V56 seeds the rows
+    private val store = InMemoryContentPackStatsStore(
+        listings = mapOf(
+            safariPackId to ContentPackListing(isFeatured = true),
+            castlePackId to ContentPackListing(isFeatured = true),
+            vehiclesPackId to ContentPackListing(isFeatured = false)
+        )
+    )
+    private val service = ContentPackServiceSimple(clock = { now }, statsStore = store)
+
+    @Test
+    @DisplayName("Only featured packs are returned, highest rated first")
+    fun featuredOrderedByRating() {
+        val featured = service.getFeaturedPacks(userId)
+
+        // Happy Vehicles isn't featured
+        assertEquals(listOf(castlePackId, safariPackId), featured.map { it.id })
+    }
+
+    @Test
+    @DisplayName("A window ending exactly now is over and the stale flag is cleared on read")
+    fun windowEndingNowIsExpired() {
+        service.setFeaturedUntil(safariPackId, now.plusSeconds(60))
+        assertEquals(listOf(castlePackId, safariPackId), service.getFeaturedPacks(userId).map { it.id })
+
+        now = now.plusSeconds(60)
+
+        assertEquals(listOf(castlePackId), service.getFeaturedPacks(userId).map { it.id })
+        val safari = assertNotNull(service.getPackById(safariPackId, userId))
+        assertFalse(safari.isFeatured)
+        assertEquals(0, service.clearExpiredFeatures())
+    }
+
+    @Test
+    @DisplayName("A new window features a cleared pack again")
+    fun newWindowRefeatures() {
+        service.setFeaturedUntil(safariPackId, now.minusSeconds(1))
+        assertEquals(1, service.clearExpiredFeatures())
+
+        service.setFeaturedUntil(safariPackId, null)
+
+        assertEquals(listOf(castlePackId, safariPackId), service.getFeaturedPacks(userId).map { it.id })
+    }
+
+    @Test
+    @DisplayName("Windows and cleared flags are kept in the store, so a new service sees them")
+    fun windowsPersist() {
+        service.setFeaturedUntil(vehiclesPackId, now.plusSeconds(3_600))
+        service.setFeaturedUntil(safariPackId, now.minusSeconds(1))
+        service.clearExpiredFeatures()
+
+        val restarted = ContentPackServiceSimple(clock = { now }, statsStore = store)
+
+        assertEquals(listOf(castlePackId, vehiclesPackId), restarted.getFeaturedPacks(userId).map { it.id })
+        assertEquals(
+            ContentPackListing(isFeatured = false, featuredUntil = now.minusSeconds(1)),
+            store.listings(listOf(safariPackId))[safariPackId]
+        )
+    }
+}
@@ -0,0 +1,63 @@
+package com.wondernest.services
+
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.math.BigDecimal
+import java.util.UUID
+import java.util.concurrent.CountDownLatch
+import java.util.concurrent.Executors
+import java.util.concurrent.TimeUnit
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+import kotlin.test.assertFalse
+import kotlin.test.assertTrue
+
+@DisplayName("Content Pack Install Tests")
+class ContentPackInstallTest {
+
+    // Happy Vehicles starts with 5000 downloads, as V56 seeds it
+    private val packId = UUID.fromString("33333333-3333-3333-3333-333333333333")
+    private val userId = UUID.randomUUID()
+    private val service = ContentPackServiceSimple(
+        statsStore = InMemoryContentPackStatsStore(mapOf(packId to ContentPackStats(BigDecimal("4.50"), 789, 5000)))
+    )
+
+    private fun downloads() = service.getPackById(packId, userId)!!.downloadCount
+
+    @Test
+    @DisplayName("Concurrent installs count each family once")
+    fun concurrentInstalls() {
+        val families = List(40) { UUID.randomUUID() }
+        val executor = Executors.newFixedThreadPool(16)
+        val start = CountDownLatch(1)
+
+        // Every family installs three times, all racing each other
+        val tasks = families.flatMap { family -> List(3) { family } }.map { family ->
+            executor.submit {
+                start.await()
+                service.recordInstall(family, packId)
+            }
+        }
+        start.countDown()
+        tasks.forEach { it.get(10, TimeUnit.SECONDS) }
+        executor.shutdown()
+
+        assertEquals(5000L + families.size, downloads())
+    }
+
+    @Test
+    @DisplayName("Re-installing doesn't change the count and unknown packs are rejected")
+    fun reinstallIsNotCounted() {
+        val familyId = UUID.randomUUID()
+
+        val first = service.recordInstall(familyId, packId)
+        assertTrue(first.firstInstall)
+        assertEquals(5001L, first.downloadCount)
+
+        val again = service.recordInstall(familyId, packId)
+        assertFalse(again.firstInstall)
+        assertEquals(5001L, again.downloadCount)
+
+        assertFailsWith<NoSuchElementException> { service.recordInstall(familyId, UUID.randomUUID()) }
+    }
+}
@@ -0,0 +1,51 @@
+package com.wondernest.services
+
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.math.BigDecimal
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+
+@DisplayName("Content Pack Rating Tests")
+class ContentPackRatingServiceTest {
+
+    private val packId = UUID.fromString("22222222-2222-2222-2222-222222222222")
+    private val store = InMemoryContentPackStatsStore(mapOf(packId to ContentPackStats(BigDecimal("4.90"), 456, 2100)))
+    private val contentPackService = ContentPackServiceSimple(statsStore = store)
+    private val ratingService = ContentPackRatingService(contentPackService, store)
+
+    @Test
+    @DisplayName("The aggregate is the exact average and one parent's re-rating replaces their earlier one")
+    fun aggregateIsExactAverage() {
+        val firstParent = UUID.randomUUID()
+        ratingService.submitRating(firstParent, packId, 5)
+        ratingService.submitRating(UUID.randomUUID(), packId, 4)
+        ratingService.submitRating(UUID.randomUUID(), packId, 4)
+
+        // (5 + 4 + 4) / 3
+        assertEquals(BigDecimal("4.33"), ratingService.summarize(packId).ratingAverage)
+
+        val summary = ratingService.submitRating(firstParent, packId, 1)
+
+        // (1 + 4 + 4) / 3
+        assertEquals(BigDecimal("3.00"), summary.ratingAverage)
+        assertEquals(3, summary.ratingCount)
+        assertEquals(1, summary.userRating)
+
+        val pack = contentPackService.getPackById(packId, firstParent)!!
+        assertEquals(BigDecimal("3.00"), pack.ratingAverage)
+        assertEquals(3, pack.ratingCount)
+    }
+
+    @Test
+    @DisplayName("Ratings outside 1-5 and unknown packs are rejected")
+    fun rejectsInvalidRatings() {
+        val parentId = UUID.randomUUID()
+
+        assertFailsWith<IllegalArgumentException> { ratingService.submitRating(parentId, packId, 0) }
+        assertFailsWith<IllegalArgumentException> { ratingService.submitRating(parentId, packId, 6) }
+        assertFailsWith<NoSuchElementException> { ratingService.submitRating(parentId, UUID.randomUUID(), 3) }
+        assertEquals(456, ratingService.summarize(packId).ratingCount)
+    }
+}
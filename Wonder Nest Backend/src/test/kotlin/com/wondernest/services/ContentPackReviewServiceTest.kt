@@ -0,0 +1,103 @@
+package com.wondernest.services
+
+import com.wondernest.models.ContentPackReviewRequest
+import org.junit.jupiter.api.BeforeEach
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+import kotlin.test.assertTrue
+
+@DisplayName("Content Pack Review Tests")
+class ContentPackReviewServiceTest {
+
+    private lateinit var reviewService: ContentPackReviewService
+
+    // The first mock pack is always owned in ContentPackServiceSimple
+    private val ownedPackId = UUID.fromString("11111111-1111-1111-1111-111111111111")
+    private val familyId = UUID.randomUUID()
+    private val parentId = UUID.randomUUID()
+
+    @BeforeEach
+    fun setup() {
+        reviewService = ContentPackReviewService(
+            ContentPackServiceSimple(statsStore = InMemoryContentPackStatsStore()),
+            InMemoryContentPackReviewStore()
+        )
+    }
+
+    @Test
+    @DisplayName("Reviews containing profanity are rejected")
+    fun profanityIsRejected() {
+        val error = assertFailsWith<ReviewRejectedException> {
+            reviewService.submitReview(
+                parentId, familyId, ownedPackId,
+                ContentPackReviewRequest(rating = 1, reviewText = "This pack is crap")
+            )
+        }
+        assertTrue(error.reasons.isNotEmpty())
+        assertEquals(0, reviewService.listReviews(ownedPackId).total)
+    }
+
+    @Test
+    @DisplayName("Reviews containing contact details are rejected")
+    fun piiIsRejected() {
+        assertFailsWith<ReviewRejectedException> {
+            reviewService.submitReview(
+                parentId, familyId, ownedPackId,
+                ContentPackReviewRequest(rating = 5, reviewText = "Email me at parent@example.com")
+            )
+        }
+    }
+
+    @Test
+    @DisplayName("Only one review per family per pack")
+    fun onePerFamily() {
+        reviewService.submitReview(
+            parentId, familyId, ownedPackId,
+            ContentPackReviewRequest(rating = 5, reviewText = "My kids love the animals")
+        )
+
+        val otherParent = UUID.randomUUID()
+        assertFailsWith<ReviewAlreadyExistsException> {
+            reviewService.submitReview(
+                otherParent, familyId, ownedPackId,
+                ContentPackReviewRequest(rating = 4, reviewText = "Great pack")
+            )
+        }
+        assertEquals(1, reviewService.listReviews(ownedPackId).total)
+    }
+
+    @Test
+    @DisplayName("Family can edit its review and others can vote on it")
+    fun editAndVote() {
+        val review = reviewService.submitReview(
+            parentId, familyId, ownedPackId,
+            ContentPackReviewRequest(rating = 3, reviewText = "Pretty good")
+        )
+
+        val edited = reviewService.updateReview(familyId, review.id, ContentPackReviewRequest(rating = 5, reviewText = "Even better now"))
+        assertEquals(5, edited.rating)
+
+        assertFailsWith<SecurityException> {
+            reviewService.updateReview(UUID.randomUUID(), review.id, ContentPackReviewRequest(rating = 1))
+        }
+
+        val voter = UUID.randomUUID()
+        reviewService.voteOnReview(review.id, voter, helpful = true)
+        val voted = reviewService.voteOnReview(review.id, voter, helpful = true)
+        assertEquals(1, voted.helpfulCount)
+    }
+
+    @Test
+    @DisplayName("Families that do not own the pack cannot review it")
+    fun nonOwnerCannotReview() {
+        assertFailsWith<SecurityException> {
+            reviewService.submitReview(
+                parentId, familyId, UUID.randomUUID(),
+                ContentPackReviewRequest(rating = 5, reviewText = "Looks nice")
+            )
+        }
+    }
+}
@@ -0,0 +1,87 @@
+package com.wondernest.services
+
+import com.wondernest.models.ContentPackSearchRequest
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertTrue
+
+@DisplayName("Content Pack Search Tests")
+class ContentPackSearchTest {
+
+    private val userId = UUID.randomUUID()
+    private val service = ContentPackServiceSimple(statsStore = InMemoryContentPackStatsStore())
+    private val template = service.getPackById(UUID.fromString("11111111-1111-1111-1111-111111111111"), userId)!!
+
+    private val mathAdventure = template.copy(
+        id = UUID.randomUUID(),
+        name = "Math Adventure",
+        description = "Count your way through the jungle",
+        searchKeywords = null,
+        curriculumTags = emptyList()
+    )
+    private val puzzlePack = template.copy(
+        id = UUID.randomUUID(),
+        name = "Puzzle Friends",
+        description = "Shapes and patterns to sort",
+        searchKeywords = null,
+        curriculumTags = listOf("Math")
+    )
+
+    @Test
+    @DisplayName("A name match ranks above a pack that only mentions the term in a tag")
+    fun nameOutranksTag() {
+        val terms = ContentPackSearchRanker.terms("math")
+        val ranked = ContentPackSearchRanker.rank(listOf(puzzlePack, mathAdventure, template), terms)
+
+        val ordered = ContentPackSearchRanker.sort(ranked, ContentPackSearchRequest(sortBy = "relevance"), hasQuery = true)
+
+        assertEquals(listOf(mathAdventure.id, puzzlePack.id), ordered.map { it.id })
+    }
+
+    @Test
+    @DisplayName("Every query term has to match and plurals match their singular")
+    fun allTermsMustMatch() {
+        val packs = listOf(mathAdventure, puzzlePack)
+
+        val adventures = ContentPackSearchRanker.rank(packs, ContentPackSearchRanker.terms("Math adventures"))
+        assertEquals(listOf(mathAdventure.id), adventures.map { it.first.id })
+
+        assertTrue(ContentPackSearchRanker.rank(packs, ContentPackSearchRanker.terms("math dragons")).isEmpty())
+    }
+
+    @Test
+    @DisplayName("Blank queries list every pack and rating/downloads sorting keeps working")
+    fun blankQueryFallsBack() {
+        val everything = service.searchPacks(ContentPackSearchRequest(query = "   ", sortBy = "relevance"), userId)
+        assertEquals(3L, everything.total)
+        // Default popularity ordering
+        assertEquals("Magical Castle", everything.packs.first().name)
+
+        val byDownloads = service.searchPacks(ContentPackSearchRequest(sortBy = "downloads"), userId)
+        assertEquals(listOf("Happy Vehicles", "Magical Castle", "Safari Animals"), byDownloads.packs.map { it.name })
+
+        val byRating = service.searchPacks(ContentPackSearchRequest(sortBy = "rating", sortOrder = "asc"), userId)
+        assertEquals(listOf("Happy Vehicles", "Safari Animals", "Magical Castle"), byRating.packs.map { it.name })
+    }
+
+    @Test
+    @DisplayName("Facets apply the age filter but not their own dimension's filter")
+    fun facetsExcludeOwnDimension() {
+        val response = service.searchPacks(
+            ContentPackSearchRequest(packType = "characterBundle", ageMin = 7),
+            userId
+        )
+
+        assertEquals(listOf("Safari Animals"), response.packs.map { it.name })
+        // Happy Vehicles (ages 2-6) is outside the age band, so it isn't counted anywhere
+        assertEquals(mapOf("backdropCollection" to 1, "characterBundle" to 1), response.facets.packType)
+        assertEquals(mapOf("false" to 1), response.facets.isFree)
+        assertEquals(mapOf(ContentPackSearchFilters.UNCATEGORIZED to 1), response.facets.category)
+
+        val free = service.searchPacks(ContentPackSearchRequest(isFree = true), userId)
+        assertEquals(listOf("Happy Vehicles"), free.packs.map { it.name })
+        assertEquals(mapOf("false" to 2, "true" to 1), free.facets.isFree)
+    }
+}
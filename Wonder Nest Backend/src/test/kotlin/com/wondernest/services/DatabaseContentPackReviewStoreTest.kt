@@ -0,0 +1,114 @@
+package com.wondernest.services
+
+import com.wondernest.models.ContentPackReview
+import org.jetbrains.exposed.sql.Database
+import org.jetbrains.exposed.sql.transactions.transaction
+import org.junit.jupiter.api.AfterAll
+import org.junit.jupiter.api.BeforeAll
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import org.junit.jupiter.api.TestInstance
+import org.testcontainers.containers.PostgreSQLContainer
+import org.testcontainers.junit.jupiter.Testcontainers
+import java.time.Instant
+import java.time.temporal.ChronoUnit
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertFalse
+import kotlin.test.assertNull
+import kotlin.test.assertTrue
+
+@Testcontainers(disabledWithoutDocker = true)
+@TestInstance(TestInstance.Lifecycle.PER_CLASS)
+@DisplayName("Content Pack Review Store Tests")
+class DatabaseContentPackReviewStoreTest {
+
+    private val postgres = PostgreSQLContainer("postgres:15-alpine")
+    private val now = Instant.parse("2025-09-01T12:00:00Z")
+
+    // The columns the store reads or writes, as V26 and V27 create them
+    private val schema = """
+        CREATE TABLE content_packs (id UUID PRIMARY KEY);
+        CREATE TABLE content_pack_reviews (
+            id UUID PRIMARY KEY DEFAULT gen_random_uuid(), pack_id UUID NOT NULL REFERENCES content_packs(id),
+            user_id UUID NOT NULL, family_id UUID, rating INTEGER CHECK (rating >= 1 AND rating <= 5),
+            review_text TEXT, review_title VARCHAR(200), helpful_count INTEGER DEFAULT 0, not_helpful_count INTEGER DEFAULT 0,
+            child_age_range VARCHAR(10), used_features TEXT[], is_approved BOOLEAN DEFAULT false,
+            is_featured BOOLEAN DEFAULT false, moderated_at TIMESTAMP, moderated_by UUID,
+            created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP, updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+            UNIQUE(pack_id, user_id));
+        CREATE UNIQUE INDEX idx_content_pack_reviews_pack_family
+            ON content_pack_reviews(pack_id, family_id) WHERE family_id IS NOT NULL;
+        CREATE TABLE content_pack_review_votes (
+            id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+            review_id UUID NOT NULL REFERENCES content_pack_reviews(id) ON DELETE CASCADE,
+            user_id UUID NOT NULL, is_helpful BOOLEAN NOT NULL, created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+            UNIQUE(review_id, user_id))
+    """.trimIndent()
+
+    @BeforeAll
+    fun connect() {
+        postgres.start()
+        Database.connect(postgres.jdbcUrl, "org.postgresql.Driver", postgres.username, postgres.password)
+        transaction { schema.split(";").filter { it.isNotBlank() }.forEach { exec(it) } }
+    }
+
+    @AfterAll
+    fun close() {
+        postgres.stop()
+    }
+
+    private fun seedPack(): UUID {
+        val packId = UUID.randomUUID()
+        transaction { exec("INSERT INTO content_packs (id) VALUES ('$packId')") }
+        return packId
+    }
+
+    private fun review(packId: UUID, familyId: UUID, createdAt: Instant = now) = ContentPackReview(
+        id = UUID.randomUUID(),
+        packId = packId,
+        userId = UUID.randomUUID(),
+        familyId = familyId,
+        rating = 4,
+        reviewText = "My kids love it",
+        isApproved = true,
+        moderatedAt = createdAt,
+        createdAt = createdAt,
+        updatedAt = createdAt
+    )
+
+    @Test
+    @DisplayName("A second review from the same family is refused and reviews page most helpful first")
+    fun onePerFamilyAndOrdering() {
+        val packId = seedPack()
+        val family = UUID.randomUUID()
+        val first = review(packId, family)
+        val second = review(packId, UUID.randomUUID(), now.plus(1, ChronoUnit.HOURS))
+
+        assertTrue(DatabaseContentPackReviewStore.insert(first))
+        assertFalse(DatabaseContentPackReviewStore.insert(review(packId, family)))
+        assertTrue(DatabaseContentPackReviewStore.insert(second))
+        DatabaseContentPackReviewStore.vote(first.id, UUID.randomUUID(), helpful = true, now)
+
+        val (page, total) = DatabaseContentPackReviewStore.listApproved(packId, offset = 0, limit = 1)
+
+        assertEquals(2, total)
+        assertEquals(listOf(first.id), page.map { it.id })
+    }
+
+    @Test
+    @DisplayName("Voting again replaces the voter's earlier vote in the counts")
+    fun votesAreRecounted() {
+        val review = review(seedPack(), UUID.randomUUID())
+        DatabaseContentPackReviewStore.insert(review)
+        val voter = UUID.randomUUID()
+
+        DatabaseContentPackReviewStore.vote(review.id, voter, helpful = true, now)
+        DatabaseContentPackReviewStore.vote(review.id, UUID.randomUUID(), helpful = true, now)
+        val updated = DatabaseContentPackReviewStore.vote(review.id, voter, helpful = false, now)!!
+
+        assertEquals(1, updated.helpfulCount)
+        assertEquals(1, updated.notHelpfulCount)
+        assertNull(DatabaseContentPackReviewStore.vote(UUID.randomUUID(), voter, helpful = true, now))
+    }
+}
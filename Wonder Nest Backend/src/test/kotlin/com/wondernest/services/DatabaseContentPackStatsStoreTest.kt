@@ -0,0 +1,140 @@
+package com.wondernest.services
+
+import com.wondernest.models.PackInstallResponse
+import org.jetbrains.exposed.sql.Database
+import org.jetbrains.exposed.sql.transactions.transaction
+import org.junit.jupiter.api.AfterAll
+import org.junit.jupiter.api.BeforeAll
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import org.junit.jupiter.api.TestInstance
+import org.testcontainers.containers.PostgreSQLContainer
+import org.testcontainers.junit.jupiter.Testcontainers
+import java.math.BigDecimal
+import java.time.Instant
+import java.util.UUID
+import java.util.concurrent.CountDownLatch
+import java.util.concurrent.Executors
+import java.util.concurrent.TimeUnit
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+
+@Testcontainers(disabledWithoutDocker = true)
+@TestInstance(TestInstance.Lifecycle.PER_CLASS)
+@DisplayName("Content Pack Stats Store Tests")
+class DatabaseContentPackStatsStoreTest {
+
+    private val postgres = PostgreSQLContainer("postgres:15-alpine")
+    private val now = Instant.parse("2025-09-01T12:00:00Z")
+
+    // The columns the store reads or writes, as V26, V29, V33, V34 and V35 create them
+    private val schema = """
+        CREATE TABLE content_packs (
+            id UUID PRIMARY KEY, updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP, is_featured BOOLEAN DEFAULT false,
+            expires_at TIMESTAMP, featured_until TIMESTAMP,
+            download_count BIGINT DEFAULT 0, rating_average DECIMAL(3,2) DEFAULT 0.0, rating_count INTEGER DEFAULT 0);
+        CREATE TABLE content_pack_ratings (
+            pack_id UUID NOT NULL REFERENCES content_packs(id) ON DELETE CASCADE, user_id UUID NOT NULL,
+            rating SMALLINT NOT NULL CHECK (rating >= 1 AND rating <= 5),
+            created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP, updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            PRIMARY KEY (pack_id, user_id));
+        CREATE TABLE content_pack_installs (
+            pack_id UUID NOT NULL REFERENCES content_packs(id) ON DELETE CASCADE, family_id UUID NOT NULL,
+            installed_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP, PRIMARY KEY (pack_id, family_id))
+    """.trimIndent()
+
+    @BeforeAll
+    fun connect() {
+        postgres.start()
+        Database.connect(postgres.jdbcUrl, "org.postgresql.Driver", postgres.username, postgres.password)
+        transaction { schema.split(";").filter { it.isNotBlank() }.forEach { exec(it) } }
+    }
+
+    @AfterAll
+    fun close() {
+        postgres.stop()
+    }
+
+    private fun seedPack(): UUID {
+        val packId = UUID.randomUUID()
+        transaction {
+            exec("INSERT INTO content_packs (id, rating_average, rating_count, download_count) VALUES ('$packId', 4.70, 234, 5000)")
+        }
+        return packId
+    }
+
+    @Test
+    @DisplayName("The pack's aggregate is recomputed from the stored ratings and a re-rating replaces the earlier one")
+    fun recomputesAggregate() {
+        val packId = seedPack()
+        val parent = UUID.randomUUID()
+
+        DatabaseContentPackStatsStore.upsertRating(packId, parent, 5, now)
+        DatabaseContentPackStatsStore.upsertRating(packId, UUID.randomUUID(), 4, now)
+        val stats = DatabaseContentPackStatsStore.upsertRating(packId, UUID.randomUUID(), 4, now)
+        assertEquals(BigDecimal("4.33"), stats.ratingAverage)
+
+        val rerated = DatabaseContentPackStatsStore.upsertRating(packId, parent, 1, now)
+
+        assertEquals(ContentPackStats(BigDecimal("3.00"), 3, 5000), rerated)
+        assertEquals(rerated, DatabaseContentPackStatsStore.stats(listOf(packId))[packId])
+        assertEquals(1, DatabaseContentPackStatsStore.findRating(packId, parent))
+    }
+
+    @Test
+    @DisplayName("Concurrent installs count each family once")
+    fun concurrentInstalls() {
+        val packId = seedPack()
+        val families = List(40) { UUID.randomUUID() }
+        val executor = Executors.newFixedThreadPool(16)
+        val start = CountDownLatch(1)
+
+        // Every family installs three times, all racing each other
+        val tasks = families.flatMap { family -> List(3) { family } }.map { family ->
+            executor.submit<PackInstallResponse> {
+                start.await()
+                DatabaseContentPackStatsStore.recordInstall(packId, family, now)
+            }
+        }
+        start.countDown()
+        val firstInstalls = tasks.count { it.get(30, TimeUnit.SECONDS).firstInstall }
+        executor.shutdown()
+
+        assertEquals(families.size, firstInstalls)
+        assertEquals(5000L + families.size, DatabaseContentPackStatsStore.stats(listOf(packId))[packId]!!.downloadCount)
+    }
+
+    @Test
+    @DisplayName("Rating or installing a pack without a row is rejected")
+    fun unknownPack() {
+        assertFailsWith<NoSuchElementException> {
+            DatabaseContentPackStatsStore.upsertRating(UUID.randomUUID(), UUID.randomUUID(), 3, now)
+        }
+        assertFailsWith<NoSuchElementException> {
+            DatabaseContentPackStatsStore.recordInstall(UUID.randomUUID(), UUID.randomUUID(), now)
+        }
+    }
+
+    @Test
+    @DisplayName("Expiry and featured windows are stored on the pack and expired packs are found by the query")
+    fun listingWindows() {
+        val seasonal = seedPack()
+        val featured = seedPack()
+        val store = DatabaseContentPackStatsStore
+
+        store.setExpiry(seasonal, now, now)
+        store.setFeaturedUntil(featured, now.minusSeconds(60), now)
+        assertEquals(setOf(seasonal), store.expired(listOf(seasonal, featured), now))
+        assertEquals(emptySet<UUID>(), store.expired(listOf(seasonal), now.minusSeconds(1)))
+
+        assertEquals(1, store.clearExpiredFeatures(listOf(seasonal, featured), now))
+        assertEquals(
+            ContentPackListing(isFeatured = false, featuredUntil = now.minusSeconds(60)),
+            store.listings(listOf(featured))[featured]
+        )
+
+        store.setExpiry(seasonal, null, now)
+        assertEquals(emptySet<UUID>(), store.expired(listOf(seasonal), now))
+        assertFailsWith<NoSuchElementException> { store.setFeaturedUntil(UUID.randomUUID(), null, now) }
+    }
+}
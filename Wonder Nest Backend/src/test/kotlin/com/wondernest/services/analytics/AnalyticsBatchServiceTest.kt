@@ -0,0 +1,81 @@
+package com.wondernest.services.analytics
+
+import com.wondernest.api.FamilyContext
+import com.wondernest.api.analytics.AnalyticsEvent
+import com.wondernest.services.coppa.DataCollectionConsentChecker
+import com.wondernest.services.family.ChildPseudonymService
+import com.wondernest.services.family.InMemoryChildPseudonymStore
+import io.mockk.coEvery
+import io.mockk.mockk
+import kotlinx.coroutines.runBlocking
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.util.UUID
+import kotlin.random.Random
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+import kotlin.test.assertTrue
+
+@DisplayName("Analytics Batch Ingestion Tests")
+class AnalyticsBatchServiceTest {
+
+    private val childId = UUID.randomUUID()
+    private val otherFamilyChild = UUID.randomUUID()
+    private val family = FamilyContext(UUID.randomUUID(), UUID.randomUUID(), "parent", setOf(childId))
+
+    private val analytics = AnalyticsEventService(AnalyticsSamplingConfig(emptyMap()), Random(42), InMemoryAnalyticsEventStore())
+    private val consentChecker = mockk<DataCollectionConsentChecker>().also {
+        coEvery { it.isDataCollectionAllowed(any()) } returns true
+    }
+    private val pseudonyms = ChildPseudonymService(InMemoryChildPseudonymStore())
+    private val service = AnalyticsBatchService(
+        analytics, pseudonyms, consentChecker, AnalyticsBatchConfig(maxEvents = 3)
+    )
+
+    private fun event(child: UUID = childId, clientEventId: String? = UUID.randomUUID().toString()) = AnalyticsEvent(
+        eventType = "story_completed",
+        childId = pseudonyms.externalIdFor(child),
+        duration = 10,
+        clientEventId = clientEventId
+    )
+
+    @Test
+    @DisplayName("Replaying the same batch doesn't record anything twice")
+    fun replayIsIdempotent() = runBlocking {
+        val batch = listOf(event(), event(), event())
+
+        val first = service.ingest(family, batch)
+        val replay = service.ingest(family, batch)
+
+        assertEquals(3, first.accepted)
+        assertTrue(replay.results.all { it.accepted && it.duplicate })
+        assertEquals(first.results.map { it.eventId }, replay.results.map { it.eventId })
+        assertEquals(3, analytics.getRawEvents(childId.toString()).size)
+        assertEquals(3L, analytics.getAggregates(childId.toString()).single().eventCount)
+    }
+
+    @Test
+    @DisplayName("Events for another family's child or without a client ID are rejected individually")
+    fun perEventRejections() = runBlocking {
+        val response = service.ingest(
+            family,
+            listOf(event(), event(child = otherFamilyChild), event(clientEventId = null))
+        )
+
+        assertEquals(1, response.accepted)
+        assertEquals(2, response.rejected)
+        assertEquals(listOf(true, false, false), response.results.map { it.accepted })
+        assertEquals("Child does not belong to your family", response.results[1].error)
+        assertEquals(1, analytics.getRawEvents(childId.toString()).size)
+        assertEquals(0, analytics.getRawEvents(otherFamilyChild.toString()).size)
+    }
+
+    @Test
+    @DisplayName("Batches over the size limit are refused outright")
+    fun maxBatchSize() {
+        assertFailsWith<AnalyticsBatchTooLargeException> {
+            runBlocking { service.ingest(family, List(4) { event() }) }
+        }
+        assertEquals(0, analytics.getRawEvents(childId.toString()).size)
+    }
+}
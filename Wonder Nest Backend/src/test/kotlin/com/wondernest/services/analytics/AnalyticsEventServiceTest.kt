@@ -0,0 +1,54 @@
+package com.wondernest.services.analytics
+
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import kotlin.random.Random
+import kotlin.test.assertEquals
+import kotlin.test.assertTrue
+
+@DisplayName("Analytics Sampling Tests")
+class AnalyticsEventServiceTest {
+
+    private val childId = "8c6f1f7e-6a51-4c4b-9a57-2f0f1f0b6f10"
+
+    @Test
+    @DisplayName("Sampled events store fewer raw rows while aggregates stay exact")
+    fun sampledEventsKeepExactAggregates() {
+        val service = AnalyticsEventService(AnalyticsSamplingConfig(mapOf("heartbeat" to 0.1)), Random(42), InMemoryAnalyticsEventStore())
+
+        repeat(1000) { service.record(childId, "heartbeat", duration = 5) }
+
+        val raw = service.getRawEvents(childId, "heartbeat")
+        assertTrue(raw.size in 1 until 1000, "expected a sample of raw rows, got ${raw.size}")
+        assertTrue(raw.all { it.sampleRate == 0.1 })
+
+        val aggregate = service.getAggregates(childId).single { it.eventType == "heartbeat" }
+        assertEquals(1000L, aggregate.eventCount)
+        assertEquals(5000L, aggregate.totalDuration)
+    }
+
+    @Test
+    @DisplayName("Unconfigured event types are never sampled")
+    fun importantEventsAreKept() {
+        val service = AnalyticsEventService(AnalyticsSamplingConfig(mapOf("heartbeat" to 0.0)), Random(42), InMemoryAnalyticsEventStore())
+
+        repeat(20) { service.record(childId, "milestone_achieved") }
+        repeat(20) { service.record(childId, "heartbeat") }
+
+        assertEquals(20, service.getRawEvents(childId, "milestone_achieved").size)
+        assertEquals(0, service.getRawEvents(childId, "heartbeat").size)
+        assertEquals(20L, service.getAggregates(childId).single { it.eventType == "heartbeat" }.eventCount)
+    }
+
+    @Test
+    @DisplayName("Sample rates can be overridden from the environment")
+    fun ratesFromEnvironment() {
+        val config = AnalyticsSamplingConfig.fromEnvironment(
+            mapOf("ANALYTICS_SAMPLE_RATE_HEARTBEAT" to "0.5", "ANALYTICS_SAMPLE_RATE_SCROLL" to "2")
+        )
+
+        assertEquals(0.5, config.rateFor("heartbeat"))
+        assertEquals(1.0, config.rateFor("scroll"))
+        assertEquals(1.0, config.rateFor("content_completed"))
+    }
+}
@@ -0,0 +1,101 @@
+package com.wondernest.services.analytics
+
+import org.jetbrains.exposed.sql.Database
+import org.jetbrains.exposed.sql.transactions.transaction
+import org.junit.jupiter.api.AfterAll
+import org.junit.jupiter.api.BeforeAll
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import org.junit.jupiter.api.TestInstance
+import org.testcontainers.containers.PostgreSQLContainer
+import org.testcontainers.junit.jupiter.Testcontainers
+import java.time.Instant
+import java.time.LocalDate
+import java.time.temporal.ChronoUnit
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertNull
+
+@Testcontainers(disabledWithoutDocker = true)
+@TestInstance(TestInstance.Lifecycle.PER_CLASS)
+@DisplayName("Analytics Event Store Tests")
+class DatabaseAnalyticsEventStoreTest {
+
+    private val postgres = PostgreSQLContainer("postgres:15-alpine")
+    private val now = Instant.parse("2025-09-01T12:00:00Z")
+    private val today = LocalDate.parse("2025-09-01")
+
+    // The columns the store reads or writes, as V2 and V58 leave them
+    private val schema = """
+        CREATE SCHEMA analytics;
+        CREATE TABLE analytics.analytics_events (
+            id UUID PRIMARY KEY DEFAULT gen_random_uuid(), user_id UUID, child_id UUID,
+            event_type VARCHAR(50) NOT NULL, event_category VARCHAR(50), event_data JSONB DEFAULT '{}',
+            session_id VARCHAR(100), content_id VARCHAR(255), duration INTEGER,
+            sample_rate DOUBLE PRECISION NOT NULL DEFAULT 1.0, created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP);
+        CREATE TABLE analytics.analytics_event_daily_totals (
+            child_id UUID NOT NULL, event_type VARCHAR(50) NOT NULL, event_date DATE NOT NULL,
+            event_count BIGINT NOT NULL DEFAULT 0, total_duration BIGINT NOT NULL DEFAULT 0,
+            PRIMARY KEY (child_id, event_type, event_date));
+        CREATE TABLE analytics.analytics_client_events (
+            child_id UUID NOT NULL, client_event_id VARCHAR(100) NOT NULL, event_id UUID NOT NULL,
+            recorded_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP, PRIMARY KEY (child_id, client_event_id))
+    """.trimIndent()
+
+    @BeforeAll
+    fun connect() {
+        postgres.start()
+        Database.connect(postgres.jdbcUrl, "org.postgresql.Driver", postgres.username, postgres.password)
+        transaction { schema.split(";").filter { it.isNotBlank() }.forEach { exec(it) } }
+    }
+
+    @AfterAll
+    fun close() {
+        postgres.stop()
+    }
+
+    private fun event(childId: UUID, recordedAt: Instant = now, duration: Int = 30) = StoredAnalyticsEvent(
+        id = UUID.randomUUID().toString(),
+        eventType = "content_view",
+        childId = childId.toString(),
+        duration = duration,
+        sessionId = "session-1",
+        sampleRate = 1.0,
+        recordedAt = recordedAt.toString()
+    )
+
+    @Test
+    @DisplayName("A replayed client event is neither stored nor counted again, and sampled-out events still count")
+    fun replaysAndSampling() {
+        val childId = UUID.randomUUID()
+        val first = event(childId)
+
+        assertNull(DatabaseAnalyticsEventStore.record(first, today, keepRaw = true, clientEventId = "c-1"))
+        assertEquals(first.id, DatabaseAnalyticsEventStore.record(event(childId), today, keepRaw = true, clientEventId = "c-1"))
+        assertNull(DatabaseAnalyticsEventStore.record(event(childId, duration = 10), today, keepRaw = false, clientEventId = null))
+
+        assertEquals(listOf(first), DatabaseAnalyticsEventStore.rawEvents(childId.toString()))
+        val totals = DatabaseAnalyticsEventStore.aggregates(childId.toString()).single()
+        assertEquals(2L, totals.eventCount)
+        assertEquals(40L, totals.totalDuration)
+    }
+
+    @Test
+    @DisplayName("Pruning drops old events, their replay markers and old daily totals")
+    fun prunes() {
+        val childId = UUID.randomUUID()
+        val old = event(childId, now.minus(40, ChronoUnit.DAYS))
+        DatabaseAnalyticsEventStore.record(old, today.minusDays(40), keepRaw = true, clientEventId = "c-old")
+        DatabaseAnalyticsEventStore.record(event(childId), today, keepRaw = true, clientEventId = "c-new")
+
+        val cutoff = now.minus(30, ChronoUnit.DAYS)
+        DatabaseAnalyticsEventStore.pruneEventsBefore(cutoff)
+        DatabaseAnalyticsEventStore.pruneAggregatesThrough(today.minusDays(31))
+
+        assertEquals(1, DatabaseAnalyticsEventStore.eventsBetween(childId.toString(), cutoff, now.plusSeconds(1)).size)
+        assertEquals(1, DatabaseAnalyticsEventStore.rawEvents(childId.toString()).size)
+        assertEquals(listOf(today.toString()), DatabaseAnalyticsEventStore.aggregates(childId.toString()).map { it.date })
+        // With its marker gone the old client event ID counts as new again
+        assertNull(DatabaseAnalyticsEventStore.record(event(childId), today, keepRaw = false, clientEventId = "c-old"))
+    }
+}
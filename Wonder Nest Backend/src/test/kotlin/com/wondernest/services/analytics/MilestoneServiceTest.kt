@@ -0,0 +1,87 @@
+package com.wondernest.services.analytics
+
+import com.wondernest.domain.model.AchievedMilestone
+import com.wondernest.domain.model.MilestoneDefinition
+import com.wondernest.domain.repository.FamilyRepository
+import com.wondernest.domain.repository.MilestoneRepository
+import com.wondernest.utils.TestUtils
+import io.mockk.coEvery
+import io.mockk.mockk
+import kotlinx.coroutines.runBlocking
+import kotlinx.datetime.Clock
+import kotlinx.datetime.Instant
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+import kotlin.test.assertFalse
+import kotlin.test.assertNull
+import kotlin.test.assertTrue
+import kotlin.time.Duration.Companion.days
+
+@DisplayName("Milestone Tests")
+class MilestoneServiceTest {
+
+    private class InMemoryMilestoneRepository : MilestoneRepository {
+        val rows = mutableMapOf<Pair<UUID, String>, AchievedMilestone>()
+
+        override suspend fun getAchievedMilestones(childId: UUID) = rows.values.filter { it.childId == childId }
+
+        override suspend fun markAchieved(
+            childId: UUID,
+            milestone: MilestoneDefinition,
+            achievedAt: Instant,
+            childAgeMonths: Int
+        ) = rows.getOrPut(childId to milestone.key) { AchievedMilestone(childId, milestone.key, achievedAt, childAgeMonths) }
+    }
+
+    private val child = TestUtils.createTestChild(age = 5)
+    private var now = Clock.System.now()
+    private val repository = InMemoryMilestoneRepository()
+    private val familyRepository = mockk<FamilyRepository>(relaxed = true).also {
+        coEvery { it.getChildProfile(child.id) } returns child
+    }
+    private val service = MilestoneService(familyRepository, repository) { now }
+
+    @Test
+    @DisplayName("Only milestones in the child's age band are listed")
+    fun filteredToAgeBand() = runBlocking {
+        val milestones = service.milestonesFor(child.id)!!
+
+        assertEquals(60, milestones.ageMonths)
+        val keys = milestones.milestones.map { it.key }
+        assertTrue("counts-to-100" in keys)
+        assertFalse("first-words" in keys)
+        assertFalse("team-play" in keys)
+        assertTrue(milestones.milestones.all { 60 in it.ageMonthsMin..it.ageMonthsMax })
+        assertTrue(milestones.milestones.none { it.achieved })
+    }
+
+    @Test
+    @DisplayName("Marking a milestone persists it and marking again keeps the first timestamp")
+    fun markIsIdempotent() = runBlocking {
+        val first = service.markAchieved(child.id, "counts-to-100")!!
+        val firstAt = now
+        now += 3.days
+        val again = service.markAchieved(child.id, "counts-to-100")!!
+
+        assertEquals(firstAt, first.achievedAt)
+        assertEquals(first.achievedAt, again.achievedAt)
+        assertEquals(1, repository.rows.size)
+
+        val listed = service.milestonesFor(child.id)!!.milestones.single { it.key == "counts-to-100" }
+        assertTrue(listed.achieved)
+        assertEquals(firstAt, listed.achievedAt)
+        assertFalse("Counts to 100" in service.milestonesFor(child.id)!!.nextGoals)
+    }
+
+    @Test
+    @DisplayName("Milestones outside the child's age band can't be marked")
+    fun rejectsOtherBands() = runBlocking {
+        assertFailsWith<IllegalArgumentException> { service.markAchieved(child.id, "first-words") }
+        assertFailsWith<IllegalArgumentException> { service.markAchieved(child.id, "not-a-milestone") }
+        assertNull(service.markAchieved(UUID.randomUUID(), "counts-to-100"))
+        assertTrue(repository.rows.isEmpty())
+    }
+}
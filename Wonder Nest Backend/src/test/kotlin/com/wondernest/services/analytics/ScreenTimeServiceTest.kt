@@ -0,0 +1,98 @@
+package com.wondernest.services.analytics
+
+import com.wondernest.domain.repository.FamilyRepository
+import com.wondernest.utils.TestUtils
+import io.mockk.coEvery
+import io.mockk.mockk
+import kotlinx.coroutines.runBlocking
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.time.Instant
+import kotlin.random.Random
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+import kotlin.test.assertFalse
+import kotlin.test.assertTrue
+
+@DisplayName("Screen Time Limit Tests")
+class ScreenTimeServiceTest {
+
+    private val family = TestUtils.createTestFamily()
+    private val child = TestUtils.createTestChild(familyId = family.id)
+    private val childId = child.id.toString()
+
+    private var now = Instant.parse("2025-06-04T15:00:00Z")
+    private val analytics = AnalyticsEventService(AnalyticsSamplingConfig(emptyMap()), Random(42), InMemoryAnalyticsEventStore()) { now }
+    private val familyRepository = mockk<FamilyRepository>(relaxed = true).also {
+        coEvery { it.getChildProfile(child.id) } returns child
+        coEvery { it.getFamilyById(family.id) } returns family
+        coEvery { it.getDailyScreenTimeLimit(child.id) } returns 60
+    }
+    private val service = ScreenTimeService(familyRepository, analytics) { now }
+
+    private fun seedSessions(vararg minutes: Int) = minutes.forEach {
+        analytics.record(childId, "end_session", duration = it)
+    }
+
+    @Test
+    @DisplayName("Under the limit reports the remaining minutes and raises no alert")
+    fun underLimit() = runBlocking {
+        seedSessions(20, 25)
+
+        val status = service.checkLimit(child.id)!!
+
+        assertEquals(45, status.minutesUsed)
+        assertEquals(60, status.limitMinutes)
+        assertEquals(15, status.remainingMinutes)
+        assertFalse(status.limitReached)
+        assertFalse(status.exceeded)
+        assertTrue(service.alerts(child.id).isEmpty())
+    }
+
+    @Test
+    @DisplayName("Reaching the limit exactly is not exceeding it")
+    fun atLimit() = runBlocking {
+        seedSessions(30, 30)
+
+        val status = service.checkLimit(child.id)!!
+
+        assertEquals(0, status.remainingMinutes)
+        assertTrue(status.limitReached)
+        assertFalse(status.exceeded)
+        assertTrue(service.alerts(child.id).isEmpty())
+    }
+
+    @Test
+    @DisplayName("Going over the limit records one alert for the day")
+    fun overLimit() = runBlocking {
+        seedSessions(40, 35)
+
+        val status = service.checkLimit(child.id)!!
+        seedSessions(5)
+        service.checkLimit(child.id)
+
+        assertEquals(75, status.minutesUsed)
+        assertTrue(status.exceeded)
+        val alert = service.alerts(child.id).single()
+        assertEquals(ScreenTimeService.ALERT_EVENT_TYPE, alert.eventType)
+        assertEquals("2025-06-04", alert.eventData["date"]?.toString()?.trim('"'))
+        // The alert itself doesn't count as screen time
+        assertEquals(80, service.status(child.id)!!.minutesUsed)
+    }
+
+    @Test
+    @DisplayName("Yesterday's sessions don't count against today's limit")
+    fun newDayResets() = runBlocking {
+        seedSessions(90)
+        now = now.plusSeconds(24 * 60 * 60)
+
+        assertEquals(0, service.status(child.id)!!.minutesUsed)
+    }
+
+    @Test
+    @DisplayName("Limits must fit in a day")
+    fun limitValidation() {
+        assertFailsWith<IllegalArgumentException> { runBlocking { service.setLimit(child.id, 1441) } }
+        assertFailsWith<IllegalArgumentException> { runBlocking { service.setLimit(child.id, -1) } }
+    }
+}
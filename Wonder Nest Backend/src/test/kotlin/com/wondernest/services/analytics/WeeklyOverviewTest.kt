@@ -0,0 +1,63 @@
+package com.wondernest.services.analytics
+
+import kotlinx.serialization.json.JsonPrimitive
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.time.Instant
+import java.time.LocalDate
+import java.time.LocalDateTime
+import java.time.ZoneId
+import kotlin.random.Random
+import kotlin.test.assertEquals
+
+@DisplayName("Weekly Overview Tests")
+class WeeklyOverviewTest {
+
+    private val childId = "8c6f1f7e-6a51-4c4b-9a57-2f0f1f0b6f10"
+    private val zone = ZoneId.of("America/Los_Angeles")
+    private val weekStart = LocalDate.parse("2025-06-02") // a Monday
+
+    private var now = Instant.EPOCH
+    private val service = AnalyticsEventService(AnalyticsSamplingConfig(emptyMap()), Random(42), InMemoryAnalyticsEventStore()) { now }
+
+    private fun recordAt(localTime: String, eventType: String, category: String, duration: Int = 0) {
+        now = LocalDateTime.parse(localTime).atZone(zone).toInstant()
+        service.record(childId, eventType, duration = duration, eventData = mapOf("category" to JsonPrimitive(category)))
+    }
+
+    @Test
+    @DisplayName("Events across the week add up to screen time, categories and completion")
+    fun aggregatesSeededWeek() {
+        recordAt("2025-06-02T10:00", "story_started", "stories")
+        recordAt("2025-06-02T10:20", "story_completed", "stories", duration = 20)
+        recordAt("2025-06-04T16:00", "game_progress", "educational", duration = 30)
+        recordAt("2025-06-05T09:00", "story_started", "stories")
+        // Late Sunday evening locally is already Monday in UTC, but still this week
+        recordAt("2025-06-08T22:00", "song_played", "music", duration = 10)
+        // The Sunday before is Monday morning in UTC, but belongs to the previous week
+        recordAt("2025-06-01T23:00", "game_progress", "educational", duration = 100)
+
+        val weekly = service.weeklyOverview(childId, weekStart, zone)
+
+        assertEquals("2025-06-02", weekly.weekStart)
+        assertEquals(60, weekly.totalScreenTime)
+        assertEquals(50.0, weekly.educationalPercentage)
+        assertEquals(8, weekly.averageDailyUsage)
+        assertEquals(listOf("educational", "stories", "music"), weekly.topCategories)
+        assertEquals(0.5, weekly.completionRate)
+    }
+
+    @Test
+    @DisplayName("A week without events is zeroed rather than missing")
+    fun emptyWeek() {
+        recordAt("2025-06-04T16:00", "game_progress", "educational", duration = 30)
+
+        val weekly = service.weeklyOverview(childId, weekStart.plusWeeks(1), zone)
+
+        assertEquals("2025-06-09", weekly.weekStart)
+        assertEquals(0, weekly.totalScreenTime)
+        assertEquals(0.0, weekly.educationalPercentage)
+        assertEquals(emptyList(), weekly.topCategories)
+        assertEquals(0.0, weekly.completionRate)
+    }
+}
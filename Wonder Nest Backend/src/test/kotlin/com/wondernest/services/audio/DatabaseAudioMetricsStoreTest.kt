@@ -0,0 +1,104 @@
+package com.wondernest.services.audio
+
+import com.wondernest.services.coppa.DataRetentionConfig
+import com.wondernest.services.coppa.DataRetentionSweepTask
+import com.wondernest.services.coppa.DatabaseDataRetentionStore
+import com.wondernest.services.coppa.RetentionDataType
+import kotlinx.datetime.toKotlinInstant
+import org.jetbrains.exposed.sql.Database
+import org.jetbrains.exposed.sql.transactions.transaction
+import org.junit.jupiter.api.AfterAll
+import org.junit.jupiter.api.BeforeAll
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import org.junit.jupiter.api.TestInstance
+import org.testcontainers.containers.PostgreSQLContainer
+import org.testcontainers.junit.jupiter.Testcontainers
+import java.time.Instant
+import java.time.ZoneOffset
+import java.util.UUID
+import kotlin.test.assertEquals
+
+@Testcontainers(disabledWithoutDocker = true)
+@TestInstance(TestInstance.Lifecycle.PER_CLASS)
+@DisplayName("Database Audio Metrics Store Tests")
+class DatabaseAudioMetricsStoreTest {
+
+    private val postgres = PostgreSQLContainer("postgres:15-alpine")
+    private var now = Instant.parse("2025-06-08T09:00:00Z")
+
+    // speech_session_metrics as V66 creates it, with a bare child_profiles for its foreign key
+    private val schema = """
+        CREATE SCHEMA family;
+        CREATE TABLE family.child_profiles (id UUID PRIMARY KEY);
+        CREATE SCHEMA analytics;
+        CREATE TABLE analytics.speech_session_metrics (
+            id UUID PRIMARY KEY,
+            child_id UUID NOT NULL REFERENCES family.child_profiles(id) ON DELETE CASCADE,
+            session_id VARCHAR(100), speech_clarity DOUBLE PRECISION NOT NULL,
+            engagement_level DOUBLE PRECISION NOT NULL, session_duration INTEGER NOT NULL,
+            vocabulary JSONB NOT NULL DEFAULT '[]',
+            recorded_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP)
+    """.trimIndent()
+
+    @BeforeAll
+    fun connect() {
+        postgres.start()
+        Database.connect(postgres.jdbcUrl, "org.postgresql.Driver", postgres.username, postgres.password)
+        transaction { schema.split(";").filter { it.isNotBlank() }.forEach { exec(it) } }
+    }
+
+    @AfterAll
+    fun close() {
+        postgres.stop()
+    }
+
+    private fun child(): UUID = UUID.randomUUID().also { id ->
+        transaction { exec("INSERT INTO family.child_profiles (id) VALUES ('$id')") }
+    }
+
+    // A new service over the same store stands in for a restart
+    private fun service() = AudioMetricsService(DatabaseAudioMetricsStore) { now }
+
+    private fun record(childId: UUID, day: String, clarity: Double, vararg words: String) {
+        now = Instant.parse("${day}T12:00:00Z")
+        service().record(childId, AudioMetricsRequest(childId.toString(), "s-$day", clarity, 0.5, 300, words.toList()))
+    }
+
+    @Test
+    @DisplayName("Trends come from stored sessions after a restart")
+    fun trendsSurviveRestart() {
+        val childId = child()
+        record(childId, "2025-05-20", 0.5, "ball", "dog")
+        record(childId, "2025-06-03", 0.6, "Dog", "cat")
+        record(childId, "2025-06-05", 0.8, "sun")
+        now = Instant.parse("2025-06-08T09:00:00Z")
+
+        val trends = service().trends(childId, SpeechTrendPeriod.WEEK, ZoneOffset.UTC)
+
+        assertEquals(2, trends.totalSessions)
+        assertEquals(0.7, trends.averageClarity!!, 1e-9)
+        assertEquals(4, trends.vocabularySize)
+        // "dog" was already known before the window
+        assertEquals(listOf(0, 1, 0, 1, 0, 0, 0), trends.points.map { it.newWords })
+        assertEquals(3, service().sessions(childId).size)
+    }
+
+    @Test
+    @DisplayName("The retention sweep and child deletion remove stored sessions")
+    fun retentionAndDeletion() {
+        val expiring = child()
+        val deleted = child()
+        record(expiring, "2025-04-01", 0.5, "old")
+        record(expiring, "2025-06-01", 0.6, "new")
+        record(deleted, "2025-06-01", 0.7, "gone")
+        now = Instant.parse("2025-06-08T09:00:00Z")
+
+        val swept = DataRetentionSweepTask(DataRetentionConfig(), DatabaseDataRetentionStore, { now.toKotlinInstant() }).runOnce()
+
+        assertEquals(1, swept[RetentionDataType.SPEECH_METRICS])
+        assertEquals(listOf("new"), service().sessions(expiring).flatMap { it.vocabulary })
+        assertEquals(1, service().deleteChild(deleted.toString()))
+        assertEquals(0, service().sessions(deleted).size)
+    }
+}
@@ -0,0 +1,72 @@
+package com.wondernest.services.audio
+
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.time.Instant
+import java.time.ZoneOffset
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertNull
+
+@DisplayName("Speech Trend Tests")
+class SpeechTrendsTest {
+
+    private val childId = UUID.randomUUID()
+    private var now = Instant.parse("2025-06-01T12:00:00Z")
+    private val service = AudioMetricsService { now }
+
+    private fun session(day: String, clarity: Double, engagement: Double, vararg words: String) {
+        now = Instant.parse("${day}T12:00:00Z")
+        service.record(
+            childId,
+            AudioMetricsRequest(
+                childId = childId.toString(),
+                speechClarity = clarity,
+                engagementLevel = engagement,
+                sessionDuration = 600,
+                vocabularyUsed = words.toList()
+            )
+        )
+    }
+
+    @Test
+    @DisplayName("Vocabulary growth counts each word once and clarity averages over sessions")
+    fun seededWeek() {
+        session("2025-06-02", 0.5, 0.4, "ball", "dog", "ball")
+        session("2025-06-02", 0.7, 0.6, "Dog", "cat")
+        session("2025-06-05", 0.9, 0.8, "cat", "tree", "dog ")
+        now = Instant.parse("2025-06-08T09:00:00Z")
+
+        val trends = service.trends(childId, SpeechTrendPeriod.WEEK, ZoneOffset.UTC)
+
+        assertEquals("2025-06-02", trends.from)
+        assertEquals(3, trends.totalSessions)
+        assertEquals(0.7, trends.averageClarity!!, 1e-9)
+        assertEquals(4, trends.vocabularySize) // ball, dog, cat, tree
+
+        val monday = trends.points.first()
+        assertEquals(2, monday.sessions)
+        assertEquals(0.6, monday.averageClarity!!, 1e-9)
+        assertEquals(3, monday.newWords)
+        val thursday = trends.points[3]
+        assertEquals(1, thursday.newWords)
+        assertEquals(4, thursday.cumulativeVocabulary)
+        assertEquals("improving", trends.clarity.direction)
+        assertEquals("improving", trends.vocabulary.direction)
+    }
+
+    @Test
+    @DisplayName("Sparse data leaves gaps instead of zeros and can't produce a slope")
+    fun sparseData() {
+        session("2025-06-04", 0.8, 0.5, "ball")
+        now = Instant.parse("2025-06-08T09:00:00Z")
+
+        val trends = service.trends(childId, SpeechTrendPeriod.WEEK, ZoneOffset.UTC)
+
+        assertEquals(7, trends.points.size)
+        assertNull(trends.points.first().averageClarity)
+        assertEquals(1, trends.points.last().cumulativeVocabulary)
+        assertNull(trends.clarity.slope)
+        assertEquals("insufficient_data", trends.clarity.direction)
+    }
+}
@@ -0,0 +1,164 @@
+package com.wondernest.services.auth
+
+import com.wondernest.api.auth.authAttemptKeys
+import com.wondernest.api.auth.rejectIfThrottled
+import com.wondernest.services.resilience.RedisGuard
+import com.wondernest.services.resilience.RedisGuardConfig
+import io.ktor.client.request.*
+import io.ktor.http.*
+import io.ktor.serialization.kotlinx.json.*
+import io.ktor.server.application.*
+import io.ktor.server.plugins.contentnegotiation.*
+import io.ktor.server.plugins.forwardedheaders.*
+import io.ktor.server.response.*
+import io.ktor.server.routing.*
+import io.ktor.server.testing.*
+import io.micrometer.core.instrument.simple.SimpleMeterRegistry
+import kotlinx.coroutines.runBlocking
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.net.ConnectException
+import kotlin.test.assertEquals
+import kotlin.test.assertNotNull
+import kotlin.test.assertNull
+
+@DisplayName("Authentication Rate Limit Tests")
+class AuthRateLimiterTest {
+
+    private var now = 1_000_000L
+
+    // Fixed windows keyed like the Redis counters, expiring on the test clock
+    private inner class InMemoryAttemptStore : AuthAttemptStore {
+        val windows = mutableMapOf<String, Pair<Long, Long>>()
+
+        override suspend fun increment(key: String, windowSeconds: Long): AttemptWindow {
+            val (attempts, expiresAt) = windows[key]?.takeIf { it.second > now } ?: (0L to now + windowSeconds * 1000)
+            windows[key] = attempts + 1 to expiresAt
+            return AttemptWindow(attempts + 1, (expiresAt - now + 999) / 1000)
+        }
+
+        override suspend fun decrement(key: String) {
+            windows[key]?.takeIf { it.second > now }?.let { (attempts, expiresAt) -> windows[key] = attempts - 1 to expiresAt }
+        }
+    }
+
+    private val redisGuard = RedisGuard(RedisGuardConfig(), SimpleMeterRegistry())
+
+    private fun limiter(
+        store: AuthAttemptStore = InMemoryAttemptStore(),
+        config: AuthRateLimitConfig = AuthRateLimitConfig(maxAttempts = 5, windowSeconds = 60)
+    ) = AuthRateLimiter(config, store, redisGuard)
+
+    @Test
+    @DisplayName("The 6th rapid attempt is throttled")
+    fun sixthAttemptThrottled() = runBlocking {
+        val limiter = limiter()
+        val keys = AuthRateLimiter.keysFor("login", "203.0.113.7", "parent@example.com")
+
+        repeat(5) {
+            assertNull(limiter.acquire(keys), "attempt ${it + 1} should be allowed")
+            now += 1_000
+        }
+
+        assertEquals(55L, limiter.acquire(keys))
+    }
+
+    @Test
+    @DisplayName("Attempts are allowed again once the window resets")
+    fun windowResets() = runBlocking {
+        val limiter = limiter()
+        val keys = AuthRateLimiter.keysFor("login", "203.0.113.7")
+
+        repeat(5) { limiter.acquire(keys) }
+        now += 59_000
+        assertNotNull(limiter.acquire(keys))
+        now += 1_001
+        assertNull(limiter.acquire(keys))
+    }
+
+    @Test
+    @DisplayName("Failures against one email throttle it from any address")
+    fun emailKeyAppliesAcrossIps() = runBlocking {
+        val limiter = limiter()
+
+        repeat(5) { i -> limiter.acquire(AuthRateLimiter.keysFor("login", "198.51.100.$i", "Parent@Example.com")) }
+
+        assertNotNull(limiter.acquire(AuthRateLimiter.keysFor("login", "192.0.2.1", "parent@example.com")))
+        assertNull(limiter.acquire(AuthRateLimiter.keysFor("login", "192.0.2.2", "other@example.com")))
+    }
+
+    @Test
+    @DisplayName("X-Forwarded-For is only believed from a trusted proxy")
+    fun forwardedForNeedsTrustedProxy() {
+        val limiter = limiter(config = AuthRateLimitConfig(trustedProxies = setOf("10.0.0.2", "10.0.0.3")))
+
+        assertEquals("203.0.113.7", limiter.clientAddress("203.0.113.7", "198.51.100.1"))
+        assertEquals("203.0.113.7", limiter.clientAddress("10.0.0.2", "198.51.100.1, 203.0.113.7, 10.0.0.3"))
+        assertEquals("10.0.0.2", limiter.clientAddress("10.0.0.2", null))
+    }
+
+    @Test
+    @DisplayName("Attempts are allowed when Redis is down")
+    fun failsOpenWithoutRedis() = runBlocking {
+        val brokenStore = object : AuthAttemptStore {
+            override suspend fun increment(key: String, windowSeconds: Long): AttemptWindow = throw ConnectException("down")
+            override suspend fun decrement(key: String) = throw ConnectException("down")
+        }
+        val limiter = limiter(brokenStore)
+        val keys = AuthRateLimiter.keysFor("login", "203.0.113.7")
+
+        repeat(10) { assertNull(limiter.acquire(keys)) }
+        limiter.release(keys)
+    }
+
+    @Test
+    @DisplayName("Limits come from the environment")
+    fun configFromEnvironment() {
+        val config = AuthRateLimitConfig.fromEnvironment(
+            mapOf(
+                "AUTH_RATE_LIMIT_MAX_ATTEMPTS" to "10",
+                "AUTH_RATE_LIMIT_WINDOW_SECONDS" to "-5",
+                "AUTH_TRUSTED_PROXIES" to "10.0.0.2, 10.0.0.3,"
+            )
+        )
+        assertEquals(10, config.maxAttempts)
+        assertEquals(AuthRateLimitConfig.DEFAULT_WINDOW_SECONDS, config.windowSeconds)
+        assertEquals(setOf("10.0.0.2", "10.0.0.3"), config.trustedProxies)
+    }
+
+    @Test
+    @DisplayName("Login route returns 429 with Retry-After, and successful logins don't use the budget")
+    fun loginRouteThrottlesFailures() = testApplication {
+        val limiter = limiter()
+        application {
+            install(ContentNegotiation) { json() }
+            install(XForwardedHeaders)
+            routing {
+                post("/login") {
+                    val keys = call.authAttemptKeys(limiter, "login", call.request.queryParameters["email"])
+                    if (call.rejectIfThrottled(limiter, keys)) return@post
+                    if (call.request.queryParameters["password"] == "correct") {
+                        limiter.release(keys)
+                        call.respond(HttpStatusCode.OK, "ok")
+                    } else {
+                        call.respond(HttpStatusCode.Unauthorized, "no")
+                    }
+                }
+            }
+        }
+
+        repeat(10) {
+            assertEquals(HttpStatusCode.OK, client.post("/login?email=a@example.com&password=correct").status)
+        }
+        repeat(5) {
+            assertEquals(HttpStatusCode.Unauthorized, client.post("/login?email=a@example.com&password=wrong").status)
+        }
+        // A spoofed X-Forwarded-For doesn't get a fresh budget
+        val spoofed = client.post("/login?password=wrong") { header(HttpHeaders.XForwardedFor, "198.51.100.9") }
+        assertEquals(HttpStatusCode.TooManyRequests, spoofed.status)
+
+        val throttled = client.post("/login?email=a@example.com&password=wrong")
+        assertEquals(HttpStatusCode.TooManyRequests, throttled.status)
+        assertEquals("60", throttled.headers[HttpHeaders.RetryAfter])
+    }
+}
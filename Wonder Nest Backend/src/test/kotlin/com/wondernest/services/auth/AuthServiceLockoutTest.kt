@@ -0,0 +1,185 @@
+package com.wondernest.services.auth
+
+import com.wondernest.data.database.table.UserStatus
+import com.wondernest.domain.model.User
+import com.wondernest.domain.repository.FamilyRepository
+import com.wondernest.domain.repository.UserRepository
+import io.mockk.coEvery
+import io.mockk.coVerify
+import io.mockk.every
+import io.mockk.mockk
+import io.mockk.slot
+import kotlinx.coroutines.runBlocking
+import kotlinx.datetime.Instant
+import org.junit.jupiter.api.BeforeEach
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import org.springframework.security.crypto.bcrypt.BCryptPasswordEncoder
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+import kotlin.test.assertFalse
+import kotlin.test.assertTrue
+import kotlin.time.Duration.Companion.minutes
+
+@DisplayName("Family Account Lockout Tests")
+class AuthServiceLockoutTest {
+
+    private val password = "Correct-Horse-9"
+    private val passwordHash = BCryptPasswordEncoder(4).encode(password)
+    private var now = Instant.parse("2025-09-01T12:00:00Z")
+
+    private val user = User(
+        id = UUID.randomUUID(),
+        email = "parent@example.com",
+        status = UserStatus.ACTIVE,
+        createdAt = now,
+        updatedAt = now
+    )
+
+    private lateinit var userRepository: UserRepository
+    private lateinit var jwtService: JwtService
+    private lateinit var service: AuthService
+
+    private var attempts = 0
+    private var lockedUntil: Instant? = null
+
+    @BeforeEach
+    fun setup() {
+        userRepository = mockk(relaxed = true)
+        jwtService = mockk()
+        every { jwtService.generateToken(any()) } returns TokenPair("access", "refresh", 3600)
+        coEvery { userRepository.getUserByEmail(user.email) } answers {
+            user.copy(failedLoginAttempts = attempts, lockedUntil = lockedUntil)
+        }
+        coEvery { userRepository.getUserPasswordHash(user.id) } returns passwordHash
+        coEvery { userRepository.updateFailedLoginAttempts(user.id, any()) } answers {
+            attempts = secondArg()
+            if (attempts == 0) lockedUntil = null
+            true
+        }
+        // Mirrors the single UPDATE … RETURNING in UserRepositoryImpl
+        coEvery { userRepository.incrementFailedLoginAttempts(user.id, any()) } answers {
+            val at = secondArg<Instant>()
+            if (lockedUntil?.let { it <= at } == true) {
+                lockedUntil = null
+                attempts = 1
+            } else {
+                attempts += 1
+            }
+            attempts
+        }
+        coEvery { userRepository.lockUser(user.id, any()) } answers {
+            lockedUntil = secondArg()
+            true
+        }
+        service = service(UserLockoutConfig())
+    }
+
+    private fun service(config: UserLockoutConfig) =
+        AuthService(userRepository, mockk<FamilyRepository>(relaxed = true), jwtService, null, config) { now }
+
+    private suspend fun login(password: String) = service.login(LoginRequest(user.email, password))
+
+    @Test
+    @DisplayName("A configured threshold of 3 locks on the third failure and resets on success")
+    fun configurableLockoutThreshold() = runBlocking {
+        service = service(UserLockoutConfig(maxFailedAttempts = 3, lockoutMinutes = 15))
+        val lockedAt = slot<Instant>()
+        coEvery { userRepository.lockUser(user.id, capture(lockedAt)) } answers {
+            lockedUntil = secondArg()
+            true
+        }
+
+        repeat(2) { assertFailsWith<SecurityException> { login("wrong password") } }
+        assertFalse(lockedAt.isCaptured)
+
+        // A successful login before the threshold resets the counter
+        assertEquals("access", login(password).data.accessToken)
+        assertEquals(0, attempts)
+
+        repeat(2) { assertFailsWith<SecurityException> { login("wrong password") } }
+        assertFalse(lockedAt.isCaptured)
+
+        assertFailsWith<SecurityException> { login("wrong password") }
+        assertEquals(3, attempts)
+        assertEquals(now + 15.minutes, lockedAt.captured)
+    }
+
+    @Test
+    @DisplayName("A locked account fails like a wrong password, even with the right password")
+    fun lockedAccountLooksLikeWrongPassword() = runBlocking {
+        lockedUntil = now + 10.minutes
+
+        val locked = assertFailsWith<SecurityException> { login(password) }
+        val wrongPassword = assertFailsWith<SecurityException> { login("wrong password") }
+
+        assertEquals("Invalid credentials", locked.message)
+        assertEquals(wrongPassword.message, locked.message)
+        // Both paths verify the password hash, so they take the same time
+        coVerify(exactly = 2) { userRepository.getUserPasswordHash(user.id) }
+        coVerify(exactly = 0) { userRepository.createSession(any()) }
+        coVerify(exactly = 0) { userRepository.incrementFailedLoginAttempts(any(), any()) }
+        assertEquals(now + 10.minutes, lockedUntil)
+    }
+
+    @Test
+    @DisplayName("Once the lock expires the right password logs in and clears the lock")
+    fun expiredLockAllowsLogin() = runBlocking {
+        attempts = 5
+        lockedUntil = now - 1.minutes
+
+        assertEquals("access", login(password).data.accessToken)
+        assertEquals(0, attempts)
+        assertEquals(null, lockedUntil)
+    }
+
+    @Test
+    @DisplayName("The first failure after an expired lock starts a fresh count")
+    fun expiredLockStartsFreshCount() = runBlocking {
+        attempts = 5
+        lockedUntil = now - 1.minutes
+
+        assertFailsWith<SecurityException> { login("wrong password") }
+        assertEquals(1, attempts)
+        assertEquals(null, lockedUntil)
+
+        assertFailsWith<SecurityException> { login("wrong password") }
+        assertEquals(2, attempts)
+    }
+
+    @Test
+    @DisplayName("Failures add to the stored count, not the one read at login, so parallel attempts still lock")
+    fun failuresCountedFromStoredValue() = runBlocking {
+        // Every request reads the user before any of them has recorded its failure
+        coEvery { userRepository.getUserByEmail(user.email) } returns user
+        val lockedAt = slot<Instant>()
+        coEvery { userRepository.lockUser(user.id, capture(lockedAt)) } returns true
+
+        repeat(5) { assertFailsWith<SecurityException> { login("wrong password") } }
+
+        assertEquals(5, attempts)
+        assertTrue(lockedAt.isCaptured)
+    }
+
+    @Test
+    @DisplayName("Unknown emails fail like a wrong password and lock nothing")
+    fun unknownEmailRejected() = runBlocking {
+        coEvery { userRepository.getUserByEmail("nobody@example.com") } returns null
+
+        val error = assertFailsWith<SecurityException> { service.login(LoginRequest("nobody@example.com", password)) }
+
+        assertEquals("Invalid credentials", error.message)
+        coVerify(exactly = 0) { userRepository.incrementFailedLoginAttempts(any(), any()) }
+    }
+
+    @Test
+    @DisplayName("Lockout settings fall back to defaults for missing or invalid values")
+    fun lockoutConfigFromEnvironment() {
+        assertEquals(UserLockoutConfig(5, 15), UserLockoutConfig.fromEnvironment(emptyMap()))
+        assertEquals(
+            UserLockoutConfig(3, 15),
+            UserLockoutConfig.fromEnvironment(mapOf("USER_MAX_FAILED_ATTEMPTS" to "3", "USER_LOCKOUT_MINUTES" to "0"))
+        )
+    }
+}
@@ -0,0 +1,80 @@
+package com.wondernest.services.auth
+
+import com.wondernest.data.database.table.UserRole
+import com.wondernest.domain.repository.FamilyRepository
+import com.wondernest.domain.repository.UserRepository
+import com.wondernest.utils.TestUtils
+import io.mockk.coEvery
+import io.mockk.mockk
+import kotlinx.coroutines.runBlocking
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import kotlin.test.assertEquals
+import kotlin.test.assertFalse
+import kotlin.test.assertNull
+import kotlin.test.assertTrue
+
+@DisplayName("Current User Info Tests")
+class CurrentUserInfoTest {
+
+    private val userRepository = mockk<UserRepository>(relaxed = true)
+    private val familyRepository = mockk<FamilyRepository>(relaxed = true)
+    private val authService = AuthService(userRepository, familyRepository, mockk())
+
+    @Test
+    @DisplayName("A role change shows up on the next call without a new token")
+    fun roleChangeVisible() = runBlocking {
+        var user = TestUtils.createTestUser(role = UserRole.PARENT)
+        coEvery { userRepository.getUserById(user.id) } answers { user }
+        coEvery { familyRepository.getFamilyByUserId(user.id) } returns null
+
+        assertEquals("PARENT", authService.getCurrentUser(user.id)?.role)
+
+        user = user.copy(role = UserRole.ADMIN)
+
+        assertEquals("ADMIN", authService.getCurrentUser(user.id)?.role)
+    }
+
+    @Test
+    @DisplayName("Family and children come from the family record")
+    fun familyContext() = runBlocking {
+        val user = TestUtils.createTestUser()
+        val family = TestUtils.createTestFamily(createdBy = user.id)
+        val children = listOf(TestUtils.createTestChild(familyId = family.id), TestUtils.createTestChild(familyId = family.id))
+        coEvery { userRepository.getUserById(user.id) } returns user
+        coEvery { familyRepository.getFamilyByUserId(user.id) } returns family
+        coEvery { familyRepository.getChildrenByFamily(family.id) } returns children
+
+        val info = authService.getCurrentUser(user.id)!!
+
+        assertEquals(family.id.toString(), info.familyId)
+        assertEquals(children.map { it.id.toString() }, info.children)
+    }
+
+    @Test
+    @DisplayName("PIN status follows the PIN stored for the parent")
+    fun pinStatus() = runBlocking {
+        val user = TestUtils.createTestUser(role = UserRole.PARENT)
+        var pinHash: String? = null
+        coEvery { userRepository.getUserById(user.id) } returns user
+        coEvery { userRepository.getUserPinHash(user.id) } answers { pinHash }
+
+        val withoutPin = authService.getCurrentUser(user.id)!!
+        assertFalse(withoutPin.hasPin)
+        assertTrue(withoutPin.requiresPinSetup)
+
+        pinHash = "stored-hash"
+        val withPin = authService.getCurrentUser(user.id)!!
+        assertTrue(withPin.hasPin)
+        assertFalse(withPin.requiresPinSetup)
+    }
+
+    @Test
+    @DisplayName("Deleted accounts have no current user")
+    fun deletedUser() = runBlocking {
+        val user = TestUtils.createTestUser().copy(deletedAt = kotlinx.datetime.Clock.System.now())
+        coEvery { userRepository.getUserById(user.id) } returns user
+
+        assertNull(authService.getCurrentUser(user.id))
+    }
+}
@@ -0,0 +1,95 @@
+package com.wondernest.services.auth
+
+import com.wondernest.domain.model.User
+import com.wondernest.domain.repository.FamilyRepository
+import com.wondernest.domain.repository.UserRepository
+import com.wondernest.utils.EmailNormalizationConfig
+import com.wondernest.utils.EmailNormalizer
+import io.mockk.coEvery
+import io.mockk.coVerify
+import io.mockk.every
+import io.mockk.mockk
+import kotlinx.coroutines.runBlocking
+import org.junit.jupiter.api.BeforeEach
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+
+@DisplayName("Email Normalization Tests")
+class EmailNormalizationTest {
+
+    // Stands in for the unique index on core.users.email
+    private val usersByEmail = mutableMapOf<String, User>()
+
+    private lateinit var userRepository: UserRepository
+    private lateinit var jwtService: JwtService
+
+    @BeforeEach
+    fun setup() {
+        userRepository = mockk(relaxed = true)
+        jwtService = mockk()
+        every { jwtService.generateToken(any()) } returns TokenPair("access", "refresh", 3600)
+        coEvery { userRepository.getUserByEmail(any()) } answers { usersByEmail[firstArg()] }
+        coEvery { userRepository.createUser(any()) } answers {
+            val user = firstArg<User>()
+            check(usersByEmail.putIfAbsent(user.email, user) == null) {
+                "duplicate key value violates unique constraint \"users_email_key\""
+            }
+            user
+        }
+    }
+
+    private fun service(config: EmailNormalizationConfig = EmailNormalizationConfig()) = AuthService(
+        userRepository, mockk<FamilyRepository>(relaxed = true), jwtService,
+        emailNormalizer = EmailNormalizer(config)
+    )
+
+    private fun signup(service: AuthService, email: String) = runBlocking {
+        service.signup(SignupRequest(email = email, password = "Sunflower42"))
+    }
+
+    @Test
+    @DisplayName("Emails are trimmed and lowercased, and Gmail aliases only fold when enabled")
+    fun normalize() {
+        val plain = EmailNormalizer()
+        assertEquals("test@x.com", plain.normalize("  Test@X.com "))
+        assertEquals("first.last+kids@gmail.com", plain.normalize("First.Last+Kids@Gmail.com"))
+
+        val folding = EmailNormalizer(EmailNormalizationConfig(stripGmailAliases = true))
+        assertEquals("firstlast@gmail.com", folding.normalize("First.Last+Kids@Gmail.com"))
+        assertEquals("firstlast@gmail.com", folding.normalize("firstlast@googlemail.com"))
+        assertEquals("first.last+kids@example.com", folding.normalize("First.Last+Kids@example.com"))
+
+        assertEquals(
+            EmailNormalizationConfig(stripGmailAliases = true),
+            EmailNormalizationConfig.fromEnvironment(mapOf("EMAIL_NORMALIZE_GMAIL_ALIASES" to "true"))
+        )
+        assertEquals(EmailNormalizationConfig(), EmailNormalizationConfig.fromEnvironment(emptyMap()))
+    }
+
+    @Test
+    @DisplayName("A differently cased email can't register a second account")
+    fun caseInsensitiveDuplicates() {
+        val service = service()
+
+        assertEquals("test@x.com", signup(service, "Test@x.com").data.email)
+        val error = assertFailsWith<IllegalArgumentException> { signup(service, "  test@X.COM") }
+
+        assertEquals("User with this email already exists", error.message)
+        // Rejected before the insert, not by the unique constraint
+        coVerify(exactly = 1) { userRepository.createUser(any()) }
+        assertEquals(setOf("test@x.com"), usersByEmail.keys)
+    }
+
+    @Test
+    @DisplayName("With Gmail folding on, dotted and plus-tagged variants are the same account")
+    fun gmailVariants() {
+        val service = service(EmailNormalizationConfig(stripGmailAliases = true))
+
+        signup(service, "First.Last@gmail.com")
+
+        assertFailsWith<IllegalArgumentException> { signup(service, "firstlast+kids@googlemail.com") }
+        assertEquals(setOf("firstlast@gmail.com"), usersByEmail.keys)
+    }
+}
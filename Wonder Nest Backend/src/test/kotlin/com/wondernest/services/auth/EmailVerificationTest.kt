@@ -0,0 +1,120 @@
+package com.wondernest.services.auth
+
+import com.wondernest.domain.model.EmailVerificationToken
+import com.wondernest.domain.model.User
+import com.wondernest.domain.repository.FamilyRepository
+import com.wondernest.domain.repository.UserRepository
+import com.wondernest.services.email.EmailService
+import io.mockk.coEvery
+import io.mockk.coVerify
+import io.mockk.mockk
+import io.mockk.slot
+import kotlinx.coroutines.runBlocking
+import kotlinx.datetime.Instant
+import org.junit.jupiter.api.BeforeEach
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+import kotlin.test.assertTrue
+import kotlin.time.Duration.Companion.hours
+
+@DisplayName("Email Verification Tests")
+class EmailVerificationTest {
+
+    private var now = Instant.parse("2025-09-01T12:00:00Z")
+
+    private val user = User(
+        id = UUID.randomUUID(),
+        email = "parent@example.com",
+        createdAt = now,
+        updatedAt = now
+    )
+
+    // Keyed by raw token, as the repository looks tokens up by hashing the raw value
+    private val tokens = mutableMapOf<String, EmailVerificationToken>()
+
+    private lateinit var userRepository: UserRepository
+    private lateinit var emailService: EmailService
+    private lateinit var service: AuthService
+
+    @BeforeEach
+    fun setup() {
+        userRepository = mockk(relaxed = true)
+        emailService = mockk()
+        coEvery { userRepository.getUserById(user.id) } returns user
+        coEvery { userRepository.createEmailVerificationToken(user.id, any(), any()) } answers {
+            EmailVerificationToken(UUID.randomUUID(), user.id, thirdArg(), createdAt = now)
+                .also { tokens[secondArg()] = it }
+        }
+        coEvery { userRepository.getEmailVerificationToken(any()) } answers { tokens[firstArg()] }
+        coEvery { userRepository.markEmailVerificationTokenUsed(any(), any()) } answers {
+            val entry = tokens.entries.single { it.value.id == firstArg<UUID>() }
+            if (entry.value.usedAt != null) return@answers false
+            entry.setValue(entry.value.copy(usedAt = secondArg()))
+            true
+        }
+        service = AuthService(
+            userRepository, mockk<FamilyRepository>(), mockk<JwtService>(), emailService,
+            emailVerificationConfig = EmailVerificationConfig(tokenTtlHours = 24), clock = { now }
+        )
+    }
+
+    private suspend fun sendAndCaptureToken(): String {
+        val sent = slot<String>()
+        coEvery { emailService.sendVerificationEmail(user, capture(sent)) } returns true
+        assertTrue(service.sendVerificationEmail(user.id))
+        return sent.captured
+    }
+
+    @Test
+    @DisplayName("Following the emailed link verifies the email")
+    fun happyPath() = runBlocking {
+        val token = sendAndCaptureToken()
+
+        assertEquals(now + 24.hours, tokens.getValue(token).expiresAt)
+        assertEquals(EmailVerificationResult.VERIFIED, service.confirmEmailVerification(token))
+        coVerify(exactly = 1) { userRepository.verifyUserEmail(user.id) }
+    }
+
+    @Test
+    @DisplayName("Replaying a consumed token is rejected as already used")
+    fun replayRejected() = runBlocking {
+        val token = sendAndCaptureToken()
+        service.confirmEmailVerification(token)
+
+        assertEquals(EmailVerificationResult.ALREADY_USED, service.confirmEmailVerification(token))
+        coVerify(exactly = 1) { userRepository.verifyUserEmail(user.id) }
+    }
+
+    @Test
+    @DisplayName("Expired and unknown tokens get their own errors")
+    fun expiredAndUnknownTokens() = runBlocking {
+        val token = sendAndCaptureToken()
+        now += 25.hours
+
+        assertEquals(EmailVerificationResult.EXPIRED, service.confirmEmailVerification(token))
+        assertEquals(EmailVerificationResult.INVALID, service.confirmEmailVerification("not-a-token"))
+        coVerify(exactly = 0) { userRepository.verifyUserEmail(any()) }
+    }
+
+    @Test
+    @DisplayName("Verified users are not sent another link")
+    fun alreadyVerified() {
+        coEvery { userRepository.getUserById(user.id) } returns user.copy(emailVerified = true)
+
+        assertFailsWith<IllegalStateException> { runBlocking { service.sendVerificationEmail(user.id) } }
+        assertTrue(tokens.isEmpty())
+    }
+
+    @Test
+    @DisplayName("Token lifetime comes from the environment")
+    fun configFromEnvironment() {
+        assertEquals(24, EmailVerificationConfig.fromEnvironment(emptyMap()).tokenTtlHours)
+        assertEquals(
+            48,
+            EmailVerificationConfig.fromEnvironment(mapOf("EMAIL_VERIFICATION_TOKEN_TTL_HOURS" to "48")).tokenTtlHours
+        )
+    }
+}
@@ -0,0 +1,93 @@
+package com.wondernest.services.auth
+
+import com.auth0.jwt.JWT
+import com.auth0.jwt.algorithms.Algorithm
+import com.wondernest.utils.TestUtils
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+import kotlin.test.assertNull
+
+@DisplayName("JWT Key Rotation Tests")
+class JwtKeyRotationTest {
+
+    private val legacySecret = "legacy-secret-for-rotation-tests"
+    private val user = TestUtils.createTestUser()
+
+    private fun service(signingKeyId: String?, vararg keys: Pair<String, String>) =
+        JwtService(JwtKeyConfig(legacySecret, signingKeyId, mapOf(*keys)))
+
+    @Test
+    @DisplayName("Tokens signed with an old but still accepted key keep verifying after rotation")
+    fun oldKeyStillAccepted() {
+        val before = service("2025-01", "2025-01" to "first-secret")
+        val issued = before.generateToken(user)
+        assertEquals("2025-01", JWT.decode(issued.accessToken).keyId)
+
+        val rotated = service("2025-06", "2025-01" to "first-secret", "2025-06" to "second-secret")
+        val reissued = rotated.generateToken(user)
+
+        assertEquals("2025-06", JWT.decode(reissued.accessToken).keyId)
+        assertEquals(user.id.toString(), rotated.verifyToken(issued.accessToken))
+        assertEquals(user.id.toString(), rotated.verifyRefreshToken(issued.refreshToken))
+        assertEquals(user.id.toString(), rotated.verifyToken(reissued.accessToken))
+    }
+
+    @Test
+    @DisplayName("Removing a key from the accepted set invalidates the tokens it signed")
+    fun retiredKeyRejected() {
+        val issued = service("2025-01", "2025-01" to "first-secret").generateToken(user)
+        val retired = service("2025-06", "2025-06" to "second-secret")
+
+        assertNull(retired.verifyToken(issued.accessToken))
+    }
+
+    @Test
+    @DisplayName("Tokens without a kid verify against the legacy secret")
+    fun legacyTokens() {
+        val legacyToken = service(null).generateToken(user).accessToken
+        assertNull(JWT.decode(legacyToken).keyId)
+
+        val rotated = service("2025-06", "2025-06" to "second-secret")
+        assertEquals(user.id.toString(), rotated.verifyToken(legacyToken))
+    }
+
+    @Test
+    @DisplayName("A kid naming a key that doesn't match the signature is rejected")
+    fun forgedKeyId() {
+        val rotated = service("2025-06", "2025-06" to "second-secret")
+        val forged = JWT.create()
+            .withKeyId("2025-06")
+            .withIssuer(rotated.issuer)
+            .withClaim("userId", user.id.toString())
+            .sign(Algorithm.HMAC256("attacker-secret"))
+        val unknown = JWT.create()
+            .withKeyId("never-configured")
+            .withIssuer(rotated.issuer)
+            .withClaim("userId", user.id.toString())
+            .sign(Algorithm.HMAC256("second-secret"))
+
+        assertNull(rotated.verifyToken(forged))
+        assertNull(rotated.verifyToken(unknown))
+    }
+
+    @Test
+    @DisplayName("Keys are read from the environment and the signing key must be among them")
+    fun configFromEnvironment() {
+        val config = JwtKeyConfig.fromEnvironment(
+            mapOf(
+                "JWT_SECRET" to legacySecret,
+                "JWT_SIGNING_KEY_ID" to "2025-06",
+                "JWT_KEYS" to "2025-01=first-secret, 2025-06=second=secret"
+            )
+        )
+        assertEquals(mapOf("2025-01" to "first-secret", "2025-06" to "second=secret"), config.keys)
+        assertEquals("2025-06", config.signingKeyId)
+
+        assertEquals(JwtKeyConfig(), JwtKeyConfig.fromEnvironment(emptyMap()))
+        assertFailsWith<IllegalArgumentException> {
+            JwtKeyConfig.fromEnvironment(mapOf("JWT_SIGNING_KEY_ID" to "2025-06", "JWT_KEYS" to "2025-01=first-secret"))
+        }
+    }
+}
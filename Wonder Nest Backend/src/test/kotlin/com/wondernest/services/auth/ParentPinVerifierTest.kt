@@ -0,0 +1,170 @@
+package com.wondernest.services.auth
+
+import com.wondernest.domain.repository.UserRepository
+import com.wondernest.services.resilience.RedisGuard
+import com.wondernest.services.resilience.RedisGuardConfig
+import io.micrometer.core.instrument.simple.SimpleMeterRegistry
+import io.mockk.coEvery
+import io.mockk.mockk
+import kotlinx.coroutines.runBlocking
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import org.springframework.security.crypto.bcrypt.BCryptPasswordEncoder
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+import kotlin.test.assertFalse
+import kotlin.test.assertNull
+import kotlin.test.assertTrue
+
+@DisplayName("Parent PIN Lockout Tests")
+class ParentPinVerifierTest {
+
+    private var now = 1_000_000L
+    private val parentId = UUID.randomUUID()
+    private val subject = "user:$parentId"
+    private val correctPin = "4821"
+    private var storedPinHash: String? = BCryptPasswordEncoder(4).encode(correctPin)
+    private val userRepository = mockk<UserRepository> {
+        coEvery { getUserPinHash(parentId) } answers { storedPinHash }
+        coEvery { setUserPinIfUnset(parentId, any()) } answers {
+            (storedPinHash == null).also { unset -> if (unset) storedPinHash = secondArg() }
+        }
+    }
+
+    private class InMemoryPinAttemptStore : PinAttemptStore {
+        val states = mutableMapOf<String, PinAttemptState>()
+        override suspend fun beginAttempt(subject: String, nowMillis: Long, ttlSeconds: Long): PinAttemptState {
+            val state = states[subject] ?: PinAttemptState()
+            if ((state.lockedUntilMillis ?: 0) > nowMillis) return state
+            return state.copy(failures = state.failures + 1).also { states[subject] = it }
+        }
+        override suspend fun lock(subject: String, nowMillis: Long, config: PinLockoutConfig, ttlSeconds: Long): Long {
+            val state = states[subject] ?: PinAttemptState()
+            val seconds = config.lockoutSeconds(state.lockouts)
+            states[subject] = PinAttemptState(lockouts = state.lockouts + 1, lockedUntilMillis = nowMillis + seconds * 1000)
+            return seconds
+        }
+        override suspend fun clear(subject: String) {
+            states.remove(subject)
+        }
+    }
+
+    private val store = InMemoryPinAttemptStore()
+    private val verifier = ParentPinVerifier(
+        userRepository,
+        PinLockoutConfig(maxAttempts = 5, baseLockoutSeconds = 30, maxLockoutSeconds = 100),
+        store,
+        RedisGuard(RedisGuardConfig(), SimpleMeterRegistry()),
+        clock = { now }
+    )
+
+    private suspend fun failFiveTimes(): PinVerificationOutcome {
+        repeat(4) { i -> assertEquals(PinVerificationOutcome.Incorrect(4 - i), verifier.verify(parentId, "0000")) }
+        return verifier.verify(parentId, "0000")
+    }
+
+    @Test
+    @DisplayName("5 wrong PINs lock PIN entry")
+    fun fiveWrongPinsLock() = runBlocking {
+        assertEquals(PinVerificationOutcome.LockedOut(30), failFiveTimes())
+    }
+
+    @Test
+    @DisplayName("The correct PIN is rejected during a lockout and accepted after it")
+    fun correctPinRejectedWhileLocked() = runBlocking {
+        failFiveTimes()
+        now += 10_000
+
+        assertEquals(PinVerificationOutcome.LockedOut(20), verifier.verify(parentId, correctPin))
+
+        now += 20_000
+        assertEquals(PinVerificationOutcome.Verified, verifier.verify(parentId, correctPin))
+        assertNull(store.states[subject])
+    }
+
+    @Test
+    @DisplayName("Each further lockout doubles, up to the maximum")
+    fun backoffIncreases() = runBlocking {
+        assertEquals(PinVerificationOutcome.LockedOut(30), failFiveTimes())
+        now += 30_000
+        assertEquals(PinVerificationOutcome.LockedOut(60), failFiveTimes())
+        now += 60_000
+        assertEquals(PinVerificationOutcome.LockedOut(100), failFiveTimes())
+    }
+
+    @Test
+    @DisplayName("A correct PIN resets the failure count")
+    fun successResetsCount() = runBlocking {
+        repeat(4) { verifier.verify(parentId, "0000") }
+        assertEquals(PinVerificationOutcome.Verified, verifier.verify(parentId, correctPin))
+
+        assertEquals(PinVerificationOutcome.Incorrect(4), verifier.verify(parentId, "0000"))
+    }
+
+    @Test
+    @DisplayName("PINs of 4 to 8 digits are accepted; anything else is a format error that isn't counted")
+    fun pinLengthValidated() = runBlocking {
+        assertEquals(PinVerificationOutcome.InvalidFormat, verifier.verify(parentId, "123"))
+        assertEquals(PinVerificationOutcome.InvalidFormat, verifier.verify(parentId, "123456789"))
+        assertEquals(PinVerificationOutcome.InvalidFormat, verifier.verify(parentId, "12a4"))
+        assertNull(store.states[subject])
+
+        assertEquals(PinVerificationOutcome.Incorrect(4), verifier.verify(parentId, "12345678"))
+    }
+
+    @Test
+    @DisplayName("Attempts beyond the limit that raced the lockout are refused without checking the PIN")
+    fun attemptsPastLimitRefused() = runBlocking {
+        store.states[subject] = PinAttemptState(failures = 5)
+
+        assertEquals(PinVerificationOutcome.LockedOut(30), verifier.verify(parentId, correctPin))
+    }
+
+    @Test
+    @DisplayName("No PIN is accepted when attempts can't be counted")
+    fun failsClosedWithoutStore() = runBlocking {
+        val unavailable = object : PinAttemptStore {
+            override suspend fun beginAttempt(subject: String, nowMillis: Long, ttlSeconds: Long): PinAttemptState =
+                throw IllegalStateException("connection refused")
+            override suspend fun lock(subject: String, nowMillis: Long, config: PinLockoutConfig, ttlSeconds: Long): Long =
+                throw IllegalStateException("connection refused")
+            override suspend fun clear(subject: String) = throw IllegalStateException("connection refused")
+        }
+        val verifier = ParentPinVerifier(
+            userRepository,
+            PinLockoutConfig(),
+            unavailable,
+            RedisGuard(RedisGuardConfig(), SimpleMeterRegistry())
+        )
+
+        assertEquals(PinVerificationOutcome.Unavailable, verifier.verify(parentId, correctPin))
+    }
+
+    @Test
+    @DisplayName("A parent sets their own PIN once, and it is the only PIN that verifies")
+    fun setupPinStoresHash() = runBlocking {
+        storedPinHash = null
+        assertEquals(PinVerificationOutcome.NotSet, verifier.verify(parentId, "1234"))
+        assertNull(store.states[subject])
+
+        assertFailsWith<IllegalArgumentException> { verifier.setupPin(parentId, "12") }
+        assertTrue(verifier.setupPin(parentId, "90817263"))
+        assertFalse(verifier.setupPin(parentId, "1234"))
+
+        assertEquals(PinVerificationOutcome.Incorrect(4), verifier.verify(parentId, "1234"))
+        assertEquals(PinVerificationOutcome.Verified, verifier.verify(parentId, "90817263"))
+    }
+
+    @Test
+    @DisplayName("Lockout settings fall back to defaults for missing or invalid values")
+    fun configFromEnvironment() {
+        assertEquals(PinLockoutConfig(), PinLockoutConfig.fromEnvironment(emptyMap()))
+        assertEquals(
+            PinLockoutConfig(maxAttempts = 3, baseLockoutSeconds = 30, maxLockoutSeconds = 600),
+            PinLockoutConfig.fromEnvironment(
+                mapOf("PIN_MAX_ATTEMPTS" to "3", "PIN_LOCKOUT_BASE_SECONDS" to "0", "PIN_LOCKOUT_MAX_SECONDS" to "600")
+            )
+        )
+    }
+}
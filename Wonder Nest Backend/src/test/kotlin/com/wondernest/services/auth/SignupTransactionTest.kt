@@ -0,0 +1,82 @@
+package com.wondernest.services.auth
+
+import com.wondernest.domain.model.Family
+import com.wondernest.domain.model.User
+import com.wondernest.domain.repository.FamilyRepository
+import com.wondernest.domain.repository.TransactionRunner
+import com.wondernest.domain.repository.UserRepository
+import io.mockk.coEvery
+import io.mockk.coVerify
+import io.mockk.every
+import io.mockk.mockk
+import kotlinx.coroutines.runBlocking
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+import kotlin.test.assertTrue
+
+@DisplayName("Signup Transaction Tests")
+class SignupTransactionTest {
+
+    // Stand-ins for the users and families tables
+    private val users = mutableMapOf<UUID, User>()
+    private val families = mutableMapOf<UUID, Family>()
+
+    // Rolls the tables back to where they were when the block started, as Postgres would
+    private val transactionRunner = object : TransactionRunner {
+        override suspend fun <T> inTransaction(block: suspend () -> T): T {
+            val usersBefore = users.toMap()
+            val familiesBefore = families.toMap()
+            return try {
+                block()
+            } catch (e: Exception) {
+                users.clear()
+                users.putAll(usersBefore)
+                families.clear()
+                families.putAll(familiesBefore)
+                throw e
+            }
+        }
+    }
+
+    private val userRepository = mockk<UserRepository>(relaxed = true).also {
+        coEvery { it.getUserByEmail(any()) } returns null
+        coEvery { it.createUser(any()) } answers { firstArg<User>().also { user -> users[user.id] = user } }
+    }
+    private val familyRepository = mockk<FamilyRepository>(relaxed = true).also {
+        coEvery { it.createFamily(any()) } answers { firstArg<Family>().also { family -> families[family.id] = family } }
+    }
+    private val jwtService = mockk<JwtService>().also {
+        every { it.generateTokenWithFamilyContext(any(), any()) } returns TokenPair("access", "refresh", 3600)
+    }
+    private val service = AuthService(userRepository, familyRepository, jwtService, transactionRunner = transactionRunner)
+
+    private val request = SignupRequest(email = "parent@example.com", password = "Sunflower42", firstName = "Sam")
+
+    @Test
+    @DisplayName("A failure after the user is created rolls back the user and family")
+    fun failureRollsBack() {
+        coEvery { familyRepository.addFamilyMember(any()) } throws IllegalStateException("insert into family_members failed")
+
+        assertFailsWith<IllegalStateException> { runBlocking { service.signupParent(request) } }
+
+        assertTrue(users.isEmpty())
+        assertTrue(families.isEmpty())
+        // Nothing past the transaction ran
+        coVerify(exactly = 0) { userRepository.createSession(any()) }
+        coVerify(exactly = 0) { userRepository.createEmailVerificationToken(any(), any(), any()) }
+    }
+
+    @Test
+    @DisplayName("A successful signup keeps the user, family and membership")
+    fun successCommits() = runBlocking {
+        val response = service.signupParent(request)
+
+        val user = users.values.single()
+        assertEquals(user.id.toString(), response.data.userId)
+        assertEquals(user.id, families.values.single().createdBy)
+        coVerify(exactly = 1) { familyRepository.addFamilyMember(match { it.userId == user.id && it.role == "parent" }) }
+    }
+}
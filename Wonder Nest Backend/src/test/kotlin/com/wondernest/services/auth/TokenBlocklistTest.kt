@@ -0,0 +1,109 @@
+package com.wondernest.services.auth
+
+import com.auth0.jwt.JWT
+import com.auth0.jwt.algorithms.Algorithm
+import com.auth0.jwt.interfaces.Payload
+import com.wondernest.domain.repository.FamilyRepository
+import com.wondernest.domain.repository.UserRepository
+import com.wondernest.services.resilience.RedisFailureMode
+import com.wondernest.services.resilience.RedisFeature
+import com.wondernest.services.resilience.RedisGuard
+import com.wondernest.services.resilience.RedisGuardConfig
+import io.micrometer.core.instrument.simple.SimpleMeterRegistry
+import io.mockk.coEvery
+import io.mockk.mockk
+import kotlinx.coroutines.runBlocking
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.util.Date
+import java.util.UUID
+import kotlin.test.assertFalse
+import kotlin.test.assertTrue
+
+@DisplayName("Token Blocklist Tests")
+class TokenBlocklistTest {
+
+    private var now = 1_750_000_000_000L
+    private val userId = UUID.randomUUID()
+    private val store = InMemoryTokenBlocklistStore { now }
+
+    private fun blocklist(
+        store: TokenBlocklistStore = this.store,
+        mode: RedisFailureMode = RedisFailureMode.FAIL_OPEN
+    ) = TokenBlocklist(
+        store,
+        RedisGuard(RedisGuardConfig(failureModes = mapOf(RedisFeature.TOKEN_BLOCKLIST to mode)), SimpleMeterRegistry()),
+        accessTokenLifetimeMillis = 3_600_000L,
+        clock = { now }
+    )
+
+    private fun token(issuedAt: Long = now, nonce: String = UUID.randomUUID().toString()): String = JWT.create()
+        .withClaim("userId", userId.toString())
+        .withClaim("nonce", nonce)
+        .withIssuedAt(Date(issuedAt))
+        .withExpiresAt(Date(issuedAt + 3_600_000L))
+        .sign(Algorithm.HMAC256("blocklist-test-secret"))
+
+    private fun payload(token: String): Payload = JWT.decode(token)
+
+    @Test
+    @DisplayName("A logged-out token is rejected before it expires, other tokens keep working")
+    fun logoutRevokesToken() = runBlocking {
+        val blocklist = blocklist()
+        val userRepository = mockk<UserRepository>(relaxed = true)
+        coEvery { userRepository.getSessionByToken(any()) } returns null
+        val authService = AuthService(
+            userRepository, mockk<FamilyRepository>(relaxed = true), mockk(),
+            tokenBlocklist = blocklist
+        )
+        val loggedOut = token()
+        val otherDevice = token()
+
+        authService.logout(loggedOut)
+        now += 60_000L
+
+        assertTrue(blocklist.isRevoked(payload(loggedOut)))
+        assertFalse(blocklist.isRevoked(payload(otherDevice)))
+    }
+
+    @Test
+    @DisplayName("Blocklist entries only live as long as the token would have")
+    fun entriesExpireWithToken() = runBlocking {
+        val blocklist = blocklist()
+        val token = token()
+
+        blocklist.revoke(payload(token))
+        now += 3_600_000L
+
+        assertFalse(blocklist.isRevoked(payload(token)))
+    }
+
+    @Test
+    @DisplayName("A password reset revokes every token issued before it, but not later ones")
+    fun passwordResetRevokesEarlierTokens() = runBlocking {
+        val blocklist = blocklist()
+        val beforeReset = token(issuedAt = now - 600_000L)
+
+        blocklist.revokeAllForUser(userId)
+        now += 5_000L
+        val afterReset = token()
+
+        assertTrue(blocklist.isRevoked(payload(beforeReset)))
+        assertFalse(blocklist.isRevoked(payload(afterReset)))
+    }
+
+    @Test
+    @DisplayName("When Redis is down tokens pass failing open and are rejected failing closed")
+    fun redisOutage() = runBlocking {
+        val broken = object : TokenBlocklistStore {
+            override suspend fun put(key: String, value: String, ttlSeconds: Long) = error("connection refused")
+            override suspend fun getAll(keys: List<String>): List<String?> = error("connection refused")
+        }
+        val token = payload(token())
+
+        assertFalse(blocklist(broken, RedisFailureMode.FAIL_OPEN).isRevoked(token))
+        assertTrue(blocklist(broken, RedisFailureMode.FAIL_CLOSED).isRevoked(token))
+        // Revoking during an outage is logged, not thrown at the caller
+        blocklist(broken, RedisFailureMode.FAIL_CLOSED).revokeAllForUser(userId)
+    }
+}
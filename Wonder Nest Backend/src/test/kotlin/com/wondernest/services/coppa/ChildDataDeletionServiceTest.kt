@@ -0,0 +1,77 @@
+package com.wondernest.services.coppa
+
+import com.wondernest.services.audio.AudioMetricsRequest
+import com.wondernest.services.audio.AudioMetricsService
+import com.wondernest.services.storage.FileAccessController
+import com.wondernest.services.storage.StorageProvider
+import io.mockk.coEvery
+import io.mockk.coVerify
+import io.mockk.every
+import io.mockk.mockk
+import kotlinx.coroutines.runBlocking
+import kotlinx.datetime.Instant
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertTrue
+
+@DisplayName("Child Data Deletion Tests")
+class ChildDataDeletionServiceTest {
+
+    private val childId = UUID.randomUUID()
+    private val siblingId = UUID.randomUUID()
+    private val now = Instant.parse("2025-09-01T12:00:00Z")
+
+    private val storage = mockk<StorageProvider>()
+    private val store = mockk<ChildDataDeletionStore>()
+    private val audio = AudioMetricsService()
+    private val service = ChildDataDeletionService(
+        storage, FileAccessController(), store, { now }, audioMetricsService = audio
+    )
+
+    @Test
+    @DisplayName("The child's speech metrics are gone afterwards and the summary counts each category")
+    fun deletesChildData() = runBlocking {
+        audio.record(childId, AudioMetricsRequest(childId.toString(), speechClarity = 0.8, engagementLevel = 0.6, sessionDuration = 120))
+        audio.record(siblingId, AudioMetricsRequest(siblingId.toString(), speechClarity = 0.7, engagementLevel = 0.5, sessionDuration = 60))
+
+        val fileId = UUID.randomUUID()
+        every { store.deleteChildData(childId, now) } returns StoredChildDataDeletion(
+            gameDataRecords = 5,
+            analyticsEvents = 2,
+            deletedFiles = listOf(fileId to "children/$childId/drawing.png"),
+            filesDetached = 1,
+            profileDeleted = true
+        )
+        coEvery { storage.delete("children/$childId/drawing.png") } returns true
+
+        val summary = service.deleteChildData(childId)
+
+        assertTrue(audio.sessions(childId).isEmpty())
+        assertEquals(1, audio.sessions(siblingId).size)
+
+        assertEquals(2, summary.analyticsEvents)
+        assertEquals(5, summary.gameDataRecords)
+        assertEquals(1, summary.audioSessions)
+        assertEquals(1, summary.filesDeleted)
+        assertEquals(1, summary.filesDetached)
+        assertTrue(summary.profileDeleted)
+        coVerify(exactly = 1) { storage.delete("children/$childId/drawing.png") }
+    }
+
+    @Test
+    @DisplayName("A storage failure doesn't undo the deletion")
+    fun storageFailureTolerated() = runBlocking {
+        every { store.deleteChildData(childId, now) } returns StoredChildDataDeletion(
+            gameDataRecords = 0,
+            analyticsEvents = 0,
+            deletedFiles = listOf(UUID.randomUUID() to "missing.png"),
+            filesDetached = 0,
+            profileDeleted = true
+        )
+        coEvery { storage.delete(any()) } throws IllegalStateException("disk unavailable")
+
+        assertEquals(1, service.deleteChildData(childId).filesDeleted)
+    }
+}
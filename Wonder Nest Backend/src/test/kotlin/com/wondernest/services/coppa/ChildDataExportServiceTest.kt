@@ -0,0 +1,169 @@
+package com.wondernest.services.coppa
+
+import com.wondernest.services.analytics.AnalyticsEventService
+import com.wondernest.services.analytics.AnalyticsSamplingConfig
+import com.wondernest.services.analytics.InMemoryAnalyticsEventStore
+import com.wondernest.services.storage.SignedUrlConfig
+import com.wondernest.services.storage.SignedUrlService
+import com.wondernest.services.storage.SignedUrlValidation
+import com.wondernest.services.storage.StorageProvider
+import com.wondernest.services.storage.StorageResult
+import io.ktor.http.Url
+import io.mockk.coEvery
+import io.mockk.coVerify
+import io.mockk.every
+import io.mockk.mockk
+import io.mockk.verify
+import kotlinx.coroutines.CoroutineScope
+import kotlinx.coroutines.Dispatchers
+import kotlinx.coroutines.runBlocking
+import kotlinx.datetime.Instant
+import kotlinx.serialization.json.JsonPrimitive
+import kotlinx.serialization.json.buildJsonObject
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertIs
+import kotlin.test.assertNull
+
+@DisplayName("Child Data Export Tests")
+class ChildDataExportServiceTest {
+
+    private val childId = UUID.randomUUID()
+    private val parentId = UUID.randomUUID()
+
+    private val analytics = AnalyticsEventService(AnalyticsSamplingConfig(emptyMap()), store = InMemoryAnalyticsEventStore())
+    private val storage = mockk<StorageProvider>()
+    private val source = mockk<ChildDataExportSource>()
+    private val auditLog = mockk<ExportAuditLog>(relaxed = true)
+    private val jobs = InMemoryExportJobStore()
+    private var now = Instant.parse("2025-09-01T12:00:00Z")
+    private val signedUrls = SignedUrlService("test-secret-test-secret-test-sec".toByteArray(), SignedUrlConfig()) { now }
+
+    private fun service(inlineMaxRows: Long) = ChildDataExportService(
+        analytics,
+        storage,
+        signedUrls,
+        source,
+        auditLog,
+        ChildDataExportConfig(inlineMaxRows = inlineMaxRows, urlExpirySeconds = 600, archiveRetentionSeconds = 3_600),
+        CoroutineScope(Dispatchers.Unconfined),
+        { now },
+        jobs
+    )
+
+    private class InMemoryExportJobStore : ChildDataExportJobStore {
+        val jobs = mutableMapOf<String, StoredExportJob>()
+
+        override suspend fun create(job: ChildDataExportJob, parentId: UUID) {
+            jobs[job.jobId] = StoredExportJob(job)
+        }
+
+        override suspend fun markReady(
+            jobId: String,
+            archive: StorageResult,
+            fileName: String,
+            completedAt: Instant,
+            fileExpiresAt: Instant
+        ) {
+            val stored = jobs.getValue(jobId)
+            jobs[jobId] = StoredExportJob(
+                stored.job.copy(status = ExportJobStatus.READY, expiresAt = fileExpiresAt.toString()), archive.key, fileExpiresAt
+            )
+        }
+
+        override suspend fun markFailed(jobId: String, completedAt: Instant) {
+            jobs[jobId] = StoredExportJob(jobs.getValue(jobId).job.copy(status = ExportJobStatus.FAILED))
+        }
+
+        override suspend fun find(jobId: String): StoredExportJob? = jobs[jobId]
+
+        override suspend fun expireDue(now: Instant): List<StoredExportJob> {
+            val due = jobs.values.filter { it.job.status == ExportJobStatus.READY && it.fileExpiresAt!! <= now }
+            due.forEach { jobs[it.job.jobId] = it.copy(job = it.job.copy(status = ExportJobStatus.EXPIRED)) }
+            return due
+        }
+    }
+
+    private val rows = ChildDataRows(
+        profile = buildJsonObject { put("name", JsonPrimitive("Emma")) },
+        gameData = listOf(buildJsonObject { put("dataKey", JsonPrimitive("project_1")) }),
+        events = emptyList(),
+        files = emptyList()
+    )
+
+    @Test
+    @DisplayName("Small exports are returned directly and audited")
+    fun inlineExport() = runBlocking {
+        every { source.countRows(childId) } returns 3
+        every { source.load(childId) } returns rows
+        analytics.record(childId.toString(), "content_view")
+
+        val result = assertIs<ChildDataExportResult.Inline>(service(inlineMaxRows = 100).requestExport(childId, parentId))
+
+        assertEquals(JsonPrimitive("Emma"), result.export.profile["name"])
+        assertEquals(1, result.export.gameData.size)
+        assertEquals(1L, result.export.analytics.dailyTotals.single().eventCount)
+        verify(exactly = 1) { auditLog.recordExport(parentId, childId, "inline") }
+    }
+
+    @Test
+    @DisplayName("Large exports are stored and fetched through a single-use signed link")
+    fun deferredExport() = runBlocking {
+        every { source.countRows(childId) } returns 10_000
+        every { source.load(childId) } returns rows
+        coEvery { storage.upload(any(), "application/json", any(), any(), any()) } returns
+            StorageResult(key = "exports/abc.json", size = 100, contentType = "application/json")
+
+        val service = service(inlineMaxRows = 100)
+        val job = assertIs<ChildDataExportResult.Deferred>(service.requestExport(childId, parentId)).job
+
+        val ready = service.getJob(childId, job.jobId, "https://api.wondernest.app")
+        assertEquals(ExportJobStatus.READY, ready?.status)
+        val link = Url(ready?.downloadUrl!!)
+        assertEquals("/api/v1/files/${job.jobId}/signed-download", link.encodedPath)
+        val fileId = UUID.fromString(job.jobId)
+        val validate = suspend {
+            signedUrls.validateSignedUrl(
+                fileId, link.parameters["expires"]?.toLongOrNull(), link.parameters["signature"], link.parameters["nonce"]
+            )
+        }
+        assertEquals(SignedUrlValidation.VALID, validate())
+        assertEquals(SignedUrlValidation.ALREADY_USED, validate())
+        // Another child's ID can't be used to fetch the job
+        assertNull(service.getJob(UUID.randomUUID(), job.jobId))
+        verify(exactly = 1) { auditLog.recordExport(parentId, childId, "deferred") }
+    }
+
+    @Test
+    @DisplayName("Archives are removed from storage once their retention passes")
+    fun expiredArchiveRemoved() = runBlocking {
+        every { source.countRows(childId) } returns 10_000
+        every { source.load(childId) } returns rows
+        coEvery { storage.upload(any(), "application/json", any(), any(), any()) } returns
+            StorageResult(key = "exports/abc.json", size = 100, contentType = "application/json")
+        coEvery { storage.delete("exports/abc.json") } returns true
+
+        val service = service(inlineMaxRows = 100)
+        val job = assertIs<ChildDataExportResult.Deferred>(service.requestExport(childId, parentId)).job
+        assertEquals(0, service.purgeExpiredArchives())
+
+        now = Instant.parse("2025-09-01T13:00:00Z")
+        assertEquals(ExportJobStatus.EXPIRED, service.getJob(childId, job.jobId)?.status)
+        assertEquals(1, service.purgeExpiredArchives())
+
+        coVerify(exactly = 1) { storage.delete("exports/abc.json") }
+        assertNull(service.getJob(childId, job.jobId)?.downloadUrl)
+    }
+
+    @Test
+    @DisplayName("Unknown children get nothing and nothing is audited")
+    fun unknownChild() = runBlocking {
+        every { source.countRows(childId) } returns 0
+        every { source.load(childId) } returns rows.copy(profile = null)
+
+        assertEquals(ChildDataExportResult.ChildNotFound, service(inlineMaxRows = 100).requestExport(childId, parentId))
+        verify(exactly = 0) { auditLog.recordExport(any(), any(), any()) }
+    }
+}
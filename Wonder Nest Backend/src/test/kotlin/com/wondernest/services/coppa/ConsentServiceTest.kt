@@ -0,0 +1,105 @@
+package com.wondernest.services.coppa
+
+import com.wondernest.domain.model.ChildProfile
+import com.wondernest.domain.model.ConsentRecord
+import com.wondernest.domain.model.ConsentVerificationStatus
+import com.wondernest.domain.repository.ConsentRepository
+import com.wondernest.domain.repository.FamilyRepository
+import io.mockk.coEvery
+import io.mockk.every
+import io.mockk.mockk
+import kotlinx.coroutines.runBlocking
+import kotlinx.datetime.Clock
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.util.UUID
+import kotlin.time.Duration.Companion.days
+import kotlin.test.assertEquals
+import kotlin.test.assertFalse
+import kotlin.test.assertTrue
+
+@DisplayName("COPPA Consent Dashboard Tests")
+class ConsentServiceTest {
+
+    private val familyRepository = mockk<FamilyRepository>()
+    private val consentRepository = mockk<ConsentRepository>()
+    private val service = ConsentService(familyRepository, consentRepository)
+
+    private val familyId = UUID.randomUUID()
+    private val parentId = UUID.randomUUID()
+    private val now = Clock.System.now()
+
+    @Test
+    @DisplayName("Two-child family gets accurate per-child consent states")
+    fun perChildConsentStates() = runBlocking {
+        val emma = child("Emma", age = 6)
+        val leo = child("Leo", age = 9)
+        coEvery { familyRepository.getChildrenByFamily(familyId) } returns listOf(emma, leo)
+        coEvery { consentRepository.getConsentRecordsByFamily(familyId) } returns listOf(
+            record(emma.id, "data_collection", granted = true),
+            record(emma.id, "audio_monitoring", granted = true),
+            // Leo's data collection consent was granted, then withdrawn
+            record(leo.id, "data_collection", granted = true, daysAgo = 10),
+            record(leo.id, "data_collection", granted = false, daysAgo = 1),
+            record(leo.id, "audio_monitoring", granted = true)
+        )
+
+        val dashboard = service.getDashboard(familyId)
+        val byName = dashboard.children.associateBy { it.childName }
+
+        assertEquals(2, dashboard.children.size)
+
+        val emmaSummary = byName.getValue("Emma")
+        assertEquals(ConsentStatus.GRANTED, emmaSummary.consentStatus)
+        assertEquals(listOf("audio_monitoring", "data_collection"), emmaSummary.grantedPermissions)
+        assertTrue(emmaSummary.dataCollectionAllowed)
+        assertTrue(emmaSummary.warnings.isEmpty())
+
+        val leoSummary = byName.getValue("Leo")
+        assertEquals(ConsentStatus.LIMITED, leoSummary.consentStatus)
+        assertEquals(listOf("audio_monitoring"), leoSummary.grantedPermissions)
+        assertFalse(leoSummary.dataCollectionAllowed)
+        assertTrue(leoSummary.nextSteps.isNotEmpty())
+    }
+
+    @Test
+    @DisplayName("Children without records or with revoked consent are flagged")
+    fun missingAndRevokedConsent() = runBlocking {
+        val mia = child("Mia", age = 4)
+        val noah = child("Noah", age = 7)
+        coEvery { familyRepository.getChildrenByFamily(familyId) } returns listOf(mia, noah)
+        coEvery { consentRepository.getConsentRecordsByFamily(familyId) } returns listOf(
+            record(noah.id, "data_collection", granted = true, revoked = true)
+        )
+
+        val byName = service.getDashboard(familyId).children.associateBy { it.childName }
+
+        assertEquals(ConsentStatus.NOT_PROVIDED, byName.getValue("Mia").consentStatus)
+        assertEquals(ConsentStatus.REVOKED, byName.getValue("Noah").consentStatus)
+        assertFalse(byName.getValue("Noah").dataCollectionAllowed)
+    }
+
+    private fun child(name: String, age: Int): ChildProfile = mockk {
+        every { id } returns UUID.randomUUID()
+        every { this@mockk.name } returns name
+        every { this@mockk.age } returns age
+    }
+
+    private fun record(
+        childId: UUID,
+        type: String,
+        granted: Boolean,
+        daysAgo: Int = 0,
+        revoked: Boolean = false
+    ) = ConsentRecord(
+        id = UUID.randomUUID(),
+        familyId = familyId,
+        childId = childId,
+        parentId = parentId,
+        consentType = type,
+        granted = granted,
+        createdAt = now - daysAgo.days,
+        verificationStatus = ConsentVerificationStatus.VERIFIED,
+        revokedAt = if (revoked) now else null
+    )
+}
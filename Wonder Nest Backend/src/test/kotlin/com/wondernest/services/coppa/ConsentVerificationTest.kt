@@ -0,0 +1,133 @@
+package com.wondernest.services.coppa
+
+import com.wondernest.domain.model.ConsentRecord
+import com.wondernest.domain.model.ConsentVerificationStatus
+import com.wondernest.domain.repository.ConsentRepository
+import com.wondernest.domain.repository.FamilyRepository
+import io.mockk.coEvery
+import io.mockk.coVerify
+import io.mockk.firstArg
+import io.mockk.mockk
+import kotlinx.coroutines.runBlocking
+import kotlinx.datetime.Instant
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import org.junit.jupiter.api.assertThrows
+import java.util.UUID
+import kotlin.time.Duration.Companion.days
+import kotlin.test.assertEquals
+import kotlin.test.assertNull
+import kotlin.test.assertTrue
+
+@DisplayName("COPPA Verifiable Consent Tests")
+class ConsentVerificationTest {
+
+    private val familyRepository = mockk<FamilyRepository>()
+    private val consentRepository = mockk<ConsentRepository>()
+    private val now = Instant.parse("2025-09-01T12:00:00Z")
+    private val service = ConsentService(familyRepository, consentRepository, ConsentVerifiers(allowStubs = true)) { now }
+
+    private val familyId = UUID.randomUUID()
+    private val parentId = UUID.randomUUID()
+    private val childId = UUID.randomUUID()
+    private val permissions = mapOf("data_collection" to true)
+
+    init {
+        coEvery { consentRepository.recordConsent(any()) } answers { firstArg() }
+    }
+
+    private suspend fun submit(method: String, data: Map<String, String> = emptyMap()) =
+        service.submitConsent(familyId, parentId, childId, permissions, method, data)
+
+    @Test
+    @DisplayName("Email-only and checkbox consent are rejected without recording anything")
+    fun nonVerifiableMethodsRejected() = runBlocking {
+        listOf("email_only", "email", "checkbox", "Checkbox_Only", "carrier_pigeon").forEach { method ->
+            assertThrows<UnsupportedVerificationMethodException> { runBlocking { submit(method) } }
+        }
+        coVerify(exactly = 0) { consentRepository.recordConsent(any()) }
+    }
+
+    @Test
+    @DisplayName("Stub verifiers are refused unless explicitly enabled in development")
+    fun stubsRefusedByDefault() = runBlocking {
+        val production = ConsentService(familyRepository, consentRepository, ConsentVerifiers()) { now }
+
+        listOf("credit_card", "phone").forEach { method ->
+            assertThrows<UnsupportedVerificationMethodException> {
+                runBlocking { production.submitConsent(familyId, parentId, childId, permissions, method) }
+            }
+        }
+        assertThrows<IllegalStateException> {
+            ConsentVerifiers.fromEnvironment(mapOf("COPPA_ALLOW_STUB_VERIFIERS" to "true", "KTOR_ENV" to "production"))
+        }
+        assertTrue(ConsentVerifiers.fromEnvironment(mapOf("KTOR_ENV" to "development")).supportedMethods.isEmpty())
+        coVerify(exactly = 0) { consentRepository.recordConsent(any()) }
+    }
+
+    @Test
+    @DisplayName("Verified credit card consent is granted and expires after a year")
+    fun creditCardVerified() = runBlocking {
+        val submission = submit("credit_card", mapOf("paymentToken" to "tok_test"))
+
+        assertEquals(ConsentVerificationStatus.VERIFIED, submission.verificationStatus)
+        assertEquals(now + ConsentService.CONSENT_VALIDITY_DAYS.days, submission.expiresAt)
+        assertEquals(listOf("data_collection"), ConsentService.grantedPermissions(submission.records, now))
+        assertTrue(submission.complianceWarnings.any { "stub" in it })
+    }
+
+    @Test
+    @DisplayName("Phone consent stays pending and grants nothing until verified")
+    fun phonePending() = runBlocking {
+        val submission = submit("phone", mapOf("phoneNumber" to "+1 (555) 010-0199"))
+
+        assertEquals(ConsentVerificationStatus.PENDING, submission.verificationStatus)
+        assertNull(submission.expiresAt)
+        assertTrue(ConsentService.grantedPermissions(submission.records, now).isEmpty())
+        assertTrue(submission.complianceWarnings.any { "pending" in it })
+    }
+
+    @Test
+    @DisplayName("Completing the staff call verifies pending phone consent")
+    fun phoneCompleted() = runBlocking {
+        val pending = submit("phone", mapOf("phoneNumber" to "+1 (555) 010-0199")).records
+        coEvery { consentRepository.getConsentRecordsByChild(childId) } returns pending
+        coEvery { consentRepository.updateVerification(any(), any(), any()) } returns Unit
+
+        val completed = service.completeVerification(childId, ConsentVerificationStatus.VERIFIED)
+
+        assertEquals(listOf("data_collection"), ConsentService.grantedPermissions(completed, now))
+        coVerify {
+            consentRepository.updateVerification(
+                pending.single().id, ConsentVerificationStatus.VERIFIED, now + ConsentService.CONSENT_VALIDITY_DAYS.days
+            )
+        }
+    }
+
+    @Test
+    @DisplayName("Credit card consent without a payment token fails verification")
+    fun creditCardMissingToken() = runBlocking {
+        val submission = submit("credit_card")
+
+        assertEquals(ConsentVerificationStatus.FAILED, submission.verificationStatus)
+        assertTrue(ConsentService.grantedPermissions(submission.records, now).isEmpty())
+    }
+
+    @Test
+    @DisplayName("Expired consent no longer grants data collection")
+    fun expiredConsent() {
+        val record = ConsentRecord(
+            id = UUID.randomUUID(),
+            familyId = familyId,
+            childId = childId,
+            parentId = parentId,
+            consentType = "data_collection",
+            granted = true,
+            createdAt = now - 400.days,
+            verificationStatus = ConsentVerificationStatus.VERIFIED,
+            expiresAt = now - 35.days
+        )
+
+        assertTrue(ConsentService.grantedPermissions(listOf(record), now).isEmpty())
+    }
+}
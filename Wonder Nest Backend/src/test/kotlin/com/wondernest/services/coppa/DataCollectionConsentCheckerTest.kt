@@ -0,0 +1,87 @@
+package com.wondernest.services.coppa
+
+import com.wondernest.domain.model.ConsentRecord
+import com.wondernest.domain.model.ConsentVerificationStatus
+import com.wondernest.domain.repository.ConsentRepository
+import com.wondernest.services.resilience.RedisGuard
+import com.wondernest.services.resilience.RedisGuardConfig
+import io.micrometer.core.instrument.simple.SimpleMeterRegistry
+import io.mockk.coEvery
+import io.mockk.coVerify
+import io.mockk.mockk
+import kotlinx.coroutines.runBlocking
+import kotlinx.datetime.Clock
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.util.UUID
+import kotlin.test.assertFalse
+import kotlin.test.assertTrue
+
+@DisplayName("Data Collection Consent Enforcement Tests")
+class DataCollectionConsentCheckerTest {
+
+    private val childId = UUID.randomUUID()
+    private val consentRepository = mockk<ConsentRepository>()
+    private val cache = InMemoryConsentCache()
+    private val checker = DataCollectionConsentChecker(
+        consentRepository,
+        cache,
+        RedisGuard(RedisGuardConfig(), SimpleMeterRegistry())
+    )
+
+    @Test
+    @DisplayName("A child without consent is blocked")
+    fun childWithoutConsentBlocked() = runBlocking {
+        coEvery { consentRepository.getConsentRecordsByChild(childId) } returns listOf(
+            record("audio_monitoring", granted = true)
+        )
+
+        assertFalse(checker.isDataCollectionAllowed(childId))
+    }
+
+    @Test
+    @DisplayName("A child with data collection consent is allowed, and the answer is cached")
+    fun childWithConsentAllowed() = runBlocking {
+        coEvery { consentRepository.getConsentRecordsByChild(childId) } returns listOf(
+            record("data_collection", granted = true)
+        )
+
+        assertTrue(checker.isDataCollectionAllowed(childId))
+        assertTrue(checker.isDataCollectionAllowed(childId))
+        coVerify(exactly = 1) { consentRepository.getConsentRecordsByChild(childId) }
+    }
+
+    @Test
+    @DisplayName("Invalidating after a revocation blocks the next write")
+    fun revocationAppliesAfterInvalidate() = runBlocking {
+        coEvery { consentRepository.getConsentRecordsByChild(childId) } returns listOf(
+            record("data_collection", granted = true)
+        )
+        assertTrue(checker.isDataCollectionAllowed(childId))
+
+        coEvery { consentRepository.getConsentRecordsByChild(childId) } returns listOf(
+            record("data_collection", granted = false)
+        )
+        checker.invalidate(childId)
+
+        assertFalse(checker.isDataCollectionAllowed(childId))
+    }
+
+    private fun record(type: String, granted: Boolean) = ConsentRecord(
+        id = UUID.randomUUID(),
+        familyId = UUID.randomUUID(),
+        childId = childId,
+        parentId = UUID.randomUUID(),
+        consentType = type,
+        granted = granted,
+        createdAt = Clock.System.now(),
+        verificationStatus = ConsentVerificationStatus.VERIFIED
+    )
+
+    private class InMemoryConsentCache : ConsentCache {
+        private val entries = mutableMapOf<UUID, Boolean>()
+        override suspend fun get(childId: UUID): Boolean? = entries[childId]
+        override suspend fun put(childId: UUID, allowed: Boolean, ttlSeconds: Long) { entries[childId] = allowed }
+        override suspend fun evict(childId: UUID) { entries.remove(childId) }
+    }
+}
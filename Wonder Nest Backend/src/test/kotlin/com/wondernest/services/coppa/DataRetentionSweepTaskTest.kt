@@ -0,0 +1,154 @@
+package com.wondernest.services.coppa
+
+import com.wondernest.services.analytics.AnalyticsEventService
+import com.wondernest.services.analytics.AnalyticsSamplingConfig
+import com.wondernest.services.analytics.InMemoryAnalyticsEventStore
+import com.wondernest.services.audio.AudioMetricsRequest
+import com.wondernest.services.audio.AudioMetricsService
+import kotlinx.coroutines.CoroutineScope
+import kotlinx.coroutines.Dispatchers
+import kotlinx.datetime.Instant
+import kotlinx.datetime.toJavaInstant
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertFalse
+import kotlin.test.assertNull
+import kotlin.test.assertTrue
+import kotlin.time.Duration.Companion.days
+import kotlin.time.Duration.Companion.hours
+
+@DisplayName("Data Retention Sweep Tests")
+class DataRetentionSweepTaskTest {
+
+    private val now = Instant.parse("2025-09-01T12:00:00Z")
+    private val config = DataRetentionConfig()
+
+    private class RecordingStore(private val remaining: MutableMap<RetentionDataType, Int>) : DataRetentionStore {
+        val batches = mutableListOf<Pair<RetentionDataType, Int>>()
+
+        override fun deleteExpiredBatch(type: RetentionDataType, cutoff: Instant, limit: Int): Int {
+            val deleted = minOf(limit, remaining[type] ?: 0)
+            remaining[type] = (remaining[type] ?: 0) - deleted
+            batches.add(type to deleted)
+            return deleted
+        }
+    }
+
+    @Test
+    @DisplayName("Session logs expire after 7 days")
+    fun sessionLogsExpire() {
+        assertTrue(config.isExpired(RetentionDataType.SESSION_LOGS, now - 7.days - 1.hours, now))
+        assertFalse(config.isExpired(RetentionDataType.SESSION_LOGS, now - 6.days, now))
+    }
+
+    @Test
+    @DisplayName("Learning analytics expire after 30 days")
+    fun learningAnalyticsExpire() {
+        assertTrue(config.isExpired(RetentionDataType.LEARNING_ANALYTICS, now - 31.days, now))
+        assertFalse(config.isExpired(RetentionDataType.LEARNING_ANALYTICS, now - 29.days, now))
+    }
+
+    @Test
+    @DisplayName("Speech metrics expire after 30 days")
+    fun audioDataExpires() {
+        assertTrue(config.isExpired(RetentionDataType.SPEECH_METRICS, now - 31.days, now))
+        assertFalse(config.isExpired(RetentionDataType.SPEECH_METRICS, now - 29.days, now))
+    }
+
+    @Test
+    @DisplayName("Daily metrics expire after a year")
+    fun dailyMetricsExpire() {
+        assertTrue(config.isExpired(RetentionDataType.DAILY_METRICS, now - 366.days, now))
+        assertFalse(config.isExpired(RetentionDataType.DAILY_METRICS, now - 300.days, now))
+    }
+
+    @Test
+    @DisplayName("Retention periods and the switch come from the environment")
+    fun configFromEnvironment() {
+        val fromEnv = DataRetentionConfig.fromEnvironment(
+            mapOf(
+                "DATA_RETENTION_SWEEP_ENABLED" to "false",
+                "DATA_RETENTION_SESSION_LOGS_DAYS" to "14",
+                "DATA_RETENTION_LEARNING_ANALYTICS_DAYS" to "nonsense",
+                "DATA_RETENTION_BATCH_SIZE" to "50"
+            )
+        )
+
+        assertFalse(fromEnv.enabled)
+        assertEquals(14, fromEnv.daysFor(RetentionDataType.SESSION_LOGS))
+        assertEquals(30, fromEnv.daysFor(RetentionDataType.LEARNING_ANALYTICS))
+        assertEquals(50, fromEnv.batchSize)
+        assertFalse(fromEnv.isExpired(RetentionDataType.SESSION_LOGS, now - 10.days, now))
+    }
+
+    @Test
+    @DisplayName("A sweep deletes in batches until a batch comes back short")
+    fun sweepsInBatches() {
+        val store = RecordingStore(mutableMapOf(RetentionDataType.LEARNING_ANALYTICS to 250))
+        val task = DataRetentionSweepTask(DataRetentionConfig(batchSize = 100), store) { now }
+
+        val deleted = task.runOnce()
+
+        assertEquals(250, deleted[RetentionDataType.LEARNING_ANALYTICS])
+        assertEquals(0, deleted[RetentionDataType.SESSION_LOGS])
+        assertEquals(
+            listOf(100, 100, 50),
+            store.batches.filter { it.first == RetentionDataType.LEARNING_ANALYTICS }.map { it.second }
+        )
+    }
+
+    @Test
+    @DisplayName("A failing type is skipped and the others are still swept")
+    fun failureIsolated() {
+        val store = object : DataRetentionStore {
+            override fun deleteExpiredBatch(type: RetentionDataType, cutoff: Instant, limit: Int): Int {
+                if (type == RetentionDataType.SESSION_LOGS) error("relation does not exist")
+                return 3
+            }
+        }
+
+        val deleted = DataRetentionSweepTask(config, store, { now }).runOnce()
+
+        assertNull(deleted[RetentionDataType.SESSION_LOGS])
+        assertEquals(3, deleted[RetentionDataType.LEARNING_ANALYTICS])
+        assertEquals(3, deleted[RetentionDataType.DAILY_METRICS])
+    }
+
+    @Test
+    @DisplayName("Expired events and speech metrics are pruned from the in-memory stores")
+    fun prunesInMemoryStores() {
+        var recordedAt = (now - 40.days).toJavaInstant()
+        val analytics = AnalyticsEventService(AnalyticsSamplingConfig(emptyMap()), store = InMemoryAnalyticsEventStore()) { recordedAt }
+        val audio = AudioMetricsService { recordedAt }
+        val childId = UUID.randomUUID()
+        analytics.record(childId.toString(), "content_view")
+        audio.record(childId, AudioMetricsRequest(childId.toString(), speechClarity = 0.8, engagementLevel = 0.7, sessionDuration = 60))
+        recordedAt = (now - 1.days).toJavaInstant()
+        analytics.record(childId.toString(), "content_view")
+        audio.record(childId, AudioMetricsRequest(childId.toString(), speechClarity = 0.9, engagementLevel = 0.8, sessionDuration = 60))
+
+        val task = DataRetentionSweepTask(
+            config, RecordingStore(mutableMapOf()), { now }, analyticsEventService = analytics, audioMetricsService = audio
+        )
+        val deleted = task.runOnce()
+
+        assertEquals(1, deleted[RetentionDataType.LEARNING_ANALYTICS])
+        assertEquals(1, deleted[RetentionDataType.SPEECH_METRICS])
+        assertEquals(1, analytics.getRawEvents(childId.toString()).size)
+        assertEquals(1, audio.sessions(childId).size)
+        // Daily totals are kept for a year
+        assertEquals(2, analytics.getAggregates(childId.toString()).size)
+    }
+
+    @Test
+    @DisplayName("A disabled sweeper never starts")
+    fun disabledSweeperDoesNotStart() {
+        val store = RecordingStore(mutableMapOf())
+        val task = DataRetentionSweepTask(DataRetentionConfig(enabled = false), store) { now }
+
+        assertNull(task.start(CoroutineScope(Dispatchers.Unconfined)))
+        assertTrue(store.batches.isEmpty())
+    }
+}
@@ -0,0 +1,136 @@
+package com.wondernest.services.coppa
+
+import kotlinx.datetime.Instant
+import org.jetbrains.exposed.sql.Database
+import org.jetbrains.exposed.sql.Transaction
+import org.jetbrains.exposed.sql.transactions.transaction
+import org.junit.jupiter.api.AfterAll
+import org.junit.jupiter.api.BeforeAll
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import org.junit.jupiter.api.TestInstance
+import org.testcontainers.containers.PostgreSQLContainer
+import org.testcontainers.junit.jupiter.Testcontainers
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertFalse
+import kotlin.test.assertTrue
+
+@Testcontainers(disabledWithoutDocker = true)
+@TestInstance(TestInstance.Lifecycle.PER_CLASS)
+@DisplayName("Child Data Deletion Store Tests")
+class DatabaseChildDataDeletionStoreTest {
+
+    private val postgres = PostgreSQLContainer("postgres:15-alpine")
+    private val now = Instant.parse("2025-09-01T12:00:00Z")
+
+    // The tables the store touches, with the columns it reads or writes, under the names and
+    // schemas the migrations create them with
+    private val schema = """
+        CREATE SCHEMA family; CREATE SCHEMA games; CREATE SCHEMA compliance;
+        CREATE SCHEMA analytics; CREATE SCHEMA core; CREATE SCHEMA content;
+        CREATE TABLE family.child_profiles (
+            id UUID PRIMARY KEY, is_active BOOLEAN NOT NULL DEFAULT TRUE,
+            created_at TIMESTAMPTZ DEFAULT now(), updated_at TIMESTAMPTZ DEFAULT now(), archived_at TIMESTAMPTZ,
+            deleted_at TIMESTAMPTZ);
+        CREATE TABLE games.simple_game_data (id UUID PRIMARY KEY DEFAULT gen_random_uuid(), child_id UUID NOT NULL);
+        CREATE TABLE games.simple_game_data_history (id UUID PRIMARY KEY DEFAULT gen_random_uuid(), child_id UUID NOT NULL);
+        CREATE TABLE games.game_sessions (id UUID PRIMARY KEY DEFAULT gen_random_uuid(), child_id UUID NOT NULL);
+        CREATE TABLE games.virtual_currency (id UUID PRIMARY KEY DEFAULT gen_random_uuid(), child_id UUID NOT NULL UNIQUE);
+        CREATE TABLE games.currency_transactions (id UUID PRIMARY KEY DEFAULT gen_random_uuid(), child_id UUID NOT NULL);
+        CREATE TABLE compliance.pii_review_queue (id UUID PRIMARY KEY DEFAULT gen_random_uuid(), child_id UUID NOT NULL);
+        CREATE TABLE analytics.analytics_events (id UUID PRIMARY KEY DEFAULT gen_random_uuid(), child_id UUID);
+        CREATE TABLE analytics.analytics_event_daily_totals (child_id UUID NOT NULL, event_type VARCHAR(50), event_date DATE);
+        CREATE TABLE analytics.analytics_client_events (child_id UUID NOT NULL, client_event_id VARCHAR(100));
+        CREATE TABLE analytics.daily_child_metrics (id UUID PRIMARY KEY DEFAULT gen_random_uuid(), child_id UUID NOT NULL);
+        CREATE TABLE analytics.learning_insights (id UUID PRIMARY KEY DEFAULT gen_random_uuid(), child_id UUID NOT NULL);
+        CREATE TABLE core.milestones (id UUID PRIMARY KEY DEFAULT gen_random_uuid(), child_id UUID NOT NULL);
+        CREATE TABLE family.child_pseudonyms (child_id UUID PRIMARY KEY, external_id VARCHAR(40) UNIQUE);
+        CREATE TABLE core.uploaded_files (
+            id UUID PRIMARY KEY, child_id UUID, file_key VARCHAR(500) NOT NULL,
+            is_deleted BOOLEAN NOT NULL DEFAULT FALSE, deleted_at TIMESTAMPTZ);
+        CREATE TABLE content.file_references (id UUID PRIMARY KEY DEFAULT gen_random_uuid(), file_id UUID NOT NULL);
+    """.trimIndent()
+
+    private val childTables = listOf(
+        "games.simple_game_data", "games.simple_game_data_history", "games.game_sessions", "games.virtual_currency",
+        "games.currency_transactions",
+        "compliance.pii_review_queue", "analytics.analytics_events", "analytics.analytics_event_daily_totals",
+        "analytics.analytics_client_events", "analytics.daily_child_metrics",
+        "analytics.learning_insights", "core.milestones", "family.child_pseudonyms"
+    )
+
+    @BeforeAll
+    fun connect() {
+        postgres.start()
+        Database.connect(postgres.jdbcUrl, "org.postgresql.Driver", postgres.username, postgres.password)
+        transaction { schema.split(";").filter { it.isNotBlank() }.forEach { exec(it) } }
+    }
+
+    @AfterAll
+    fun close() {
+        postgres.stop()
+    }
+
+    private fun Transaction.count(table: String, childId: UUID): Int =
+        exec("SELECT count(*) FROM $table WHERE child_id = '$childId'") { rs -> rs.next(); rs.getInt(1) }!!
+
+    private fun seedChild(childId: UUID) = transaction {
+        exec("INSERT INTO family.child_profiles (id) VALUES ('$childId')")
+        childTables.forEach { exec("INSERT INTO $it (child_id) VALUES ('$childId')") }
+    }
+
+    @Test
+    @DisplayName("Every child-keyed table is emptied for the child and left alone for siblings")
+    fun deletesFromRealTables() {
+        val childId = UUID.randomUUID()
+        val siblingId = UUID.randomUUID()
+        seedChild(childId)
+        seedChild(siblingId)
+
+        val stored = DatabaseChildDataDeletionStore.deleteChildData(childId, now)
+
+        transaction {
+            childTables.forEach { table ->
+                assertEquals(0, count(table, childId), "$table still has rows for the child")
+                assertEquals(1, count(table, siblingId), "$table lost the sibling's rows")
+            }
+            // Deleted, not archived, so restore can't bring the profile back
+            val deleted = exec(
+                "SELECT is_active, deleted_at IS NOT NULL, archived_at IS NULL FROM family.child_profiles WHERE id = '$childId'"
+            ) { rs ->
+                rs.next(); !rs.getBoolean(1) && rs.getBoolean(2) && rs.getBoolean(3)
+            }!!
+            assertTrue(deleted)
+        }
+        assertEquals(1, stored.gameDataRecords)
+        assertEquals(1, stored.analyticsEvents)
+        assertTrue(stored.profileDeleted)
+    }
+
+    @Test
+    @DisplayName("Unreferenced files are soft-deleted and files used by content are only detached")
+    fun handlesFiles() {
+        val childId = UUID.randomUUID()
+        seedChild(childId)
+        val ownFile = UUID.randomUUID()
+        val sharedFile = UUID.randomUUID()
+        transaction {
+            exec("INSERT INTO core.uploaded_files (id, child_id, file_key) VALUES ('$ownFile', '$childId', 'own.png')")
+            exec("INSERT INTO core.uploaded_files (id, child_id, file_key) VALUES ('$sharedFile', '$childId', 'shared.png')")
+            exec("INSERT INTO content.file_references (file_id) VALUES ('$sharedFile')")
+        }
+
+        val stored = DatabaseChildDataDeletionStore.deleteChildData(childId, now)
+
+        assertEquals(listOf(ownFile to "own.png"), stored.deletedFiles)
+        assertEquals(1, stored.filesDetached)
+        transaction {
+            val shared = exec("SELECT child_id IS NULL, is_deleted FROM core.uploaded_files WHERE id = '$sharedFile'") { rs ->
+                rs.next(); rs.getBoolean(1) to rs.getBoolean(2)
+            }!!
+            assertTrue(shared.first)
+            assertFalse(shared.second)
+        }
+    }
+}
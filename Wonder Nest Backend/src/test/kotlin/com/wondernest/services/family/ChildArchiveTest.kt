@@ -0,0 +1,78 @@
+package com.wondernest.services.family
+
+import com.wondernest.domain.model.ChildProfile
+import com.wondernest.domain.repository.FamilyRepository
+import com.wondernest.utils.TestUtils
+import io.mockk.coEvery
+import io.mockk.coVerify
+import io.mockk.mockk
+import kotlinx.coroutines.runBlocking
+import kotlinx.datetime.Clock
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertNotNull
+import kotlin.test.assertNull
+
+@DisplayName("Child Archive Tests")
+class ChildArchiveTest {
+
+    private val familyId = UUID.randomUUID()
+    private val emma = TestUtils.createTestChild(familyId = familyId, name = "Emma")
+    private val leo = TestUtils.createTestChild(familyId = familyId, name = "Leo")
+
+    // Stands in for core.child_profiles
+    private val children = mutableMapOf(emma.id to emma, leo.id to leo)
+
+    private val familyRepository = mockk<FamilyRepository>(relaxed = true).also { repository ->
+        coEvery { repository.getChildrenByFamily(any(), any()) } answers {
+            val includeArchived = secondArg<Boolean>()
+            children.values.filter { it.familyId == firstArg() && (includeArchived || it.archivedAt == null) }
+        }
+        coEvery { repository.archiveChildProfile(any()) } answers { setArchivedAt(firstArg(), Clock.System.now()) }
+        coEvery { repository.restoreChildProfile(any()) } answers { setArchivedAt(firstArg(), null) }
+    }
+
+    private val familyService = FamilyService(familyRepository)
+
+    private fun setArchivedAt(childId: UUID, archivedAt: kotlinx.datetime.Instant?): Boolean {
+        val child: ChildProfile = children[childId] ?: return false
+        children[childId] = child.copy(archivedAt = archivedAt)
+        return true
+    }
+
+    @Test
+    @DisplayName("An archived child drops out of the default list but is still listed with includeArchived")
+    fun archiveHidesChild() = runBlocking {
+        assertNotNull(familyService.archiveChild(familyId, leo.id))
+
+        assertEquals(listOf(emma.id), familyService.getChildren(familyId).map { it.id })
+        assertEquals(setOf(emma.id, leo.id), familyService.getChildren(familyId, includeArchived = true).map { it.id }.toSet())
+        // The profile is flagged, never deleted, so its data stays attached
+        coVerify(exactly = 0) { familyRepository.deleteChildProfile(any()) }
+    }
+
+    @Test
+    @DisplayName("Restoring brings the child back into the default list")
+    fun restoreBringsChildBack() = runBlocking {
+        familyService.archiveChild(familyId, leo.id)
+
+        val restored = familyService.restoreChild(familyId, leo.id)
+
+        assertNull(restored?.archivedAt)
+        assertEquals(setOf(emma.id, leo.id), familyService.getChildren(familyId).map { it.id }.toSet())
+    }
+
+    @Test
+    @DisplayName("Another family's child can't be archived or restored")
+    fun scopedToFamily() = runBlocking {
+        val otherFamily = UUID.randomUUID()
+
+        assertNull(familyService.archiveChild(otherFamily, leo.id))
+        familyService.archiveChild(familyId, leo.id)
+        assertNull(familyService.restoreChild(otherFamily, leo.id))
+        // Restoring a child that isn't archived is a no-op
+        assertNull(familyService.restoreChild(familyId, emma.id))
+    }
+}
@@ -0,0 +1,52 @@
+package com.wondernest.services.family
+
+import com.wondernest.services.analytics.AnalyticsEventService
+import com.wondernest.services.analytics.AnalyticsSamplingConfig
+import com.wondernest.services.analytics.InMemoryAnalyticsEventStore
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertNotEquals
+import kotlin.test.assertNull
+import kotlin.test.assertTrue
+
+@DisplayName("Child Pseudonym Tests")
+class ChildPseudonymServiceTest {
+
+    private val childId = UUID.randomUUID()
+
+    @Test
+    @DisplayName("Rotation retires the old external ID and keeps data reachable through the new one")
+    fun rotationKeepsDataReachable() {
+        val pseudonyms = ChildPseudonymService(InMemoryChildPseudonymStore())
+        val analytics = AnalyticsEventService(AnalyticsSamplingConfig(emptyMap()), store = InMemoryAnalyticsEventStore())
+
+        val oldId = pseudonyms.externalIdFor(childId)
+        assertEquals(oldId, pseudonyms.externalIdFor(childId))
+        analytics.record(childId = pseudonyms.resolve(oldId)!!.toString(), eventType = "game_played")
+
+        val rotated = pseudonyms.rotate(childId)
+
+        assertEquals(oldId, rotated.previousExternalId)
+        assertNotEquals(oldId, rotated.externalId)
+        assertNull(pseudonyms.resolve(oldId))
+
+        val internalId = pseudonyms.resolve(rotated.externalId)
+        assertEquals(childId, internalId)
+        assertEquals(1, analytics.getRawEvents(internalId.toString()).size)
+    }
+
+    @Test
+    @DisplayName("External IDs are opaque and internal UUIDs don't resolve")
+    fun externalIdsAreOpaque() {
+        val pseudonyms = ChildPseudonymService(InMemoryChildPseudonymStore())
+        val externalId = pseudonyms.externalIdFor(childId)
+
+        assertTrue(externalId.matches(Regex("cx_[0-9a-f]{24}")))
+        assertTrue(childId.toString() !in externalId)
+        assertEquals(childId, pseudonyms.resolve(externalId))
+        assertNull(pseudonyms.resolve(childId.toString()))
+        assertNull(pseudonyms.resolve("cx_unknown"))
+    }
+}
@@ -0,0 +1,77 @@
+package com.wondernest.services.family
+
+import com.wondernest.api.analytics.DailyAnalytics
+import com.wondernest.api.content.ContentRecommendationService
+import com.wondernest.domain.repository.AnalyticsRepository
+import com.wondernest.domain.repository.FamilyRepository
+import com.wondernest.utils.TestUtils
+import io.mockk.coEvery
+import io.mockk.coVerify
+import io.mockk.mockk
+import kotlinx.coroutines.runBlocking
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertNull
+import kotlin.test.assertTrue
+
+@DisplayName("Family Dashboard Tests")
+class FamilyDashboardServiceTest {
+
+    private val familyId = UUID.randomUUID()
+    private val emma = TestUtils.createTestChild(familyId = familyId, name = "Emma", age = 6)
+    private val leo = TestUtils.createTestChild(familyId = familyId, name = "Leo", age = 4)
+    private val newborn = TestUtils.createTestChild(familyId = familyId, name = "Mia", age = 1)
+
+    private val familyRepository = mockk<FamilyRepository>(relaxed = true)
+    private val analyticsRepository = mockk<AnalyticsRepository>()
+    private val service = FamilyDashboardService(
+        familyRepository, analyticsRepository, ContentRecommendationService(familyRepository)
+    )
+
+    private fun analytics(childId: UUID, date: String) = DailyAnalytics(
+        date = date,
+        childId = childId.toString(),
+        totalScreenTime = 40,
+        contentConsumed = 4,
+        educationalTime = 25,
+        averageSessionLength = 10,
+        mostEngagedCategory = "stories",
+        completedActivities = 3,
+        learningProgress = 0.6
+    )
+
+    @Test
+    @DisplayName("Every child in the caller's family is listed with their latest analytics")
+    fun includesAllChildren() = runBlocking {
+        coEvery { familyRepository.getChildrenByFamily(familyId) } returns listOf(emma, leo, newborn)
+        coEvery { analyticsRepository.getLatestDailyAnalyticsByFamily(familyId) } returns mapOf(
+            emma.id to analytics(emma.id, "2026-10-16"),
+            leo.id to analytics(leo.id, "2026-10-15")
+        )
+
+        val dashboard = service.dashboardFor(familyId)
+
+        assertEquals(familyId.toString(), dashboard.familyId)
+        assertEquals(listOf(emma.id, leo.id, newborn.id).map { it.toString() }, dashboard.children.map { it.childId })
+        assertEquals("2026-10-16", dashboard.children[0].latestAnalytics?.date)
+        assertEquals("2026-10-15", dashboard.children[1].latestAnalytics?.date)
+        // No metrics recorded yet
+        assertNull(dashboard.children[2].latestAnalytics)
+        assertTrue(dashboard.children[0].recommendations.all { it.ageRating <= emma.age })
+    }
+
+    @Test
+    @DisplayName("Analytics are fetched once for the family, not per child")
+    fun noPerChildQueries() = runBlocking {
+        coEvery { familyRepository.getChildrenByFamily(familyId) } returns listOf(emma, leo, newborn)
+        coEvery { analyticsRepository.getLatestDailyAnalyticsByFamily(familyId) } returns emptyMap()
+
+        service.dashboardFor(familyId)
+
+        coVerify(exactly = 1) { familyRepository.getChildrenByFamily(familyId) }
+        coVerify(exactly = 1) { analyticsRepository.getLatestDailyAnalyticsByFamily(familyId) }
+        coVerify(exactly = 0) { familyRepository.getChildProfile(any()) }
+    }
+}
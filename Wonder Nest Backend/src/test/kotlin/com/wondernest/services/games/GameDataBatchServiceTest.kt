@@ -0,0 +1,85 @@
+package com.wondernest.services.games
+
+import com.wondernest.services.moderation.ChildDataPiiGuard
+import com.wondernest.services.moderation.HeldDataTarget
+import com.wondernest.services.moderation.PiiAction
+import com.wondernest.services.moderation.PiiReviewQueueStore
+import com.wondernest.services.moderation.PiiScannerConfig
+import io.mockk.every
+import io.mockk.mockk
+import io.mockk.verify
+import kotlinx.datetime.Instant
+import kotlinx.serialization.json.JsonElement
+import kotlinx.serialization.json.JsonPrimitive
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertFalse
+import kotlin.test.assertTrue
+
+@DisplayName("Game Data Batch Tests")
+class GameDataBatchServiceTest {
+
+    private val childId = UUID.randomUUID()
+    private val now = Instant.parse("2025-09-01T12:00:00Z")
+    private val store = mockk<GameDataBatchStore>(relaxed = true)
+    private val service = GameDataBatchService(store, GameDataValidator(GameDataSchemaSource { null })) { now }
+
+    private fun sticker(index: Int, value: JsonElement = JsonPrimitive(index)) =
+        GameDataBatchItem("sticker_book", "sticker_$index", mapOf("position" to value))
+
+    @Test
+    @DisplayName("A 50-item batch is written in one call")
+    fun fiftyItemBatch() {
+        val items = List(50) { sticker(it) }
+
+        val result = service.saveBatch(childId, "sticker_book", items)
+
+        assertTrue(result.success)
+        assertEquals(50, result.savedCount)
+        verify(exactly = 1) { store.saveAll(childId, "sticker_book", items, now) }
+    }
+
+    @Test
+    @DisplayName("One oversized payload rejects the whole batch and names the failing key")
+    fun oversizedItemRejectsBatch() {
+        val huge = JsonPrimitive("x".repeat(GameDataValidator.MAX_BYTES))
+        val items = List(50) { if (it == 30) sticker(it, huge) else sticker(it) }
+
+        val result = service.saveBatch(childId, "sticker_book", items)
+
+        assertFalse(result.success)
+        assertEquals(0, result.savedCount)
+        assertEquals("sticker_30", result.failedKey)
+        verify(exactly = 0) { store.saveAll(any(), any(), any(), any()) }
+    }
+
+    @Test
+    @DisplayName("With the PII queue on, every flagged item is held and the rest are saved")
+    fun flaggedItemsHeldForReview() {
+        val queue = mockk<PiiReviewQueueStore>()
+        every { queue.enqueue(any()) } returns UUID.randomUUID()
+        val guard = ChildDataPiiGuard(PiiScannerConfig(action = PiiAction.QUEUE), queue) { now }
+        val service = GameDataBatchService(store, GameDataValidator(GameDataSchemaSource { null }), guard) { now }
+        val items = List(5) { if (it % 2 == 0) sticker(it) else sticker(it, JsonPrimitive("call me on 555-123-4567")) }
+
+        val result = service.saveBatch(childId, "sticker_book", items)
+
+        assertTrue(result.success)
+        assertEquals(3, result.savedCount)
+        assertEquals(listOf("sticker_1", "sticker_3"), result.heldKeys)
+        verify(exactly = 2) { queue.enqueue(match { it.target == HeldDataTarget.SIMPLE }) }
+        verify(exactly = 1) { store.saveAll(childId, "sticker_book", listOf(items[0], items[2], items[4]), now) }
+    }
+
+    @Test
+    @DisplayName("Batch load asks only for the requested keys")
+    fun loadRequestedKeys() {
+        every { store.load(childId, "sticker_book", listOf("sticker_1", "sticker_2")) } returns emptyList()
+
+        service.loadBatch(childId, "sticker_book", listOf("sticker_1", "sticker_2", "sticker_1", " "))
+
+        verify(exactly = 1) { store.load(childId, "sticker_book", listOf("sticker_1", "sticker_2")) }
+    }
+}
@@ -0,0 +1,99 @@
+package com.wondernest.services.games
+
+import io.mockk.Runs
+import io.mockk.every
+import io.mockk.just
+import io.mockk.mockk
+import io.mockk.verify
+import kotlinx.datetime.Instant
+import kotlinx.serialization.json.JsonPrimitive
+import kotlinx.serialization.json.buildJsonObject
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertFalse
+import kotlin.test.assertTrue
+
+@DisplayName("Game Data History Tests")
+class GameDataHistoryServiceTest {
+
+    private val childId = UUID.randomUUID()
+    private val now = Instant.parse("2025-09-01T12:00:00Z")
+    private val gameDataService = mockk<GameDataService>()
+    private val store = mockk<GameDataHistoryStore>()
+    private val config = GameDataHistoryConfig(retentionDays = 30, maxVersionsPerKey = 20)
+    private val historyService = GameDataHistoryService(gameDataService, store, config) { now }
+
+    private val pageOne = buildJsonObject { put("page", JsonPrimitive(1)) }
+
+    @Test
+    @DisplayName("Restoring simple game data writes the historical value back as a new version")
+    fun restoreSavesOldValue() {
+        every { store.findVersion(childId, "sticker_book", "project_1", 3) } returns
+            GameDataHistoryEntry("project_1", 3, pageOne, "2025-08-30T10:00:00Z")
+        every { gameDataService.rejectSave(childId, "sticker_book", "project_1", pageOne) } returns null
+        every { store.saveSimple(childId, "sticker_book", "project_1", pageOne, now) } just Runs
+
+        assertTrue(historyService.restore(childId, "sticker_book", "project_1", 3).success)
+        verify(exactly = 1) { store.saveSimple(childId, "sticker_book", "project_1", pageOne, now) }
+    }
+
+    @Test
+    @DisplayName("A simple game data restore is refused when the game has since been disabled")
+    fun restoreDisabledGame() {
+        every { store.findVersion(childId, "sticker_book", "project_1", 3) } returns
+            GameDataHistoryEntry("project_1", 3, pageOne, "2025-08-30T10:00:00Z")
+        every { gameDataService.rejectSave(childId, "sticker_book", "project_1", pageOne) } returns
+            GameDataOperationResult.disabled("sticker_book")
+
+        assertTrue(historyService.restore(childId, "sticker_book", "project_1", 3).gameDisabled)
+        verify(exactly = 0) { store.saveSimple(any(), any(), any(), any(), any()) }
+    }
+
+    @Test
+    @DisplayName("Restoring instance game data goes through the instance save path")
+    fun restoreInstanceData() {
+        every { store.findVersion(childId, "sticker_book", "project_1", 3) } returns
+            GameDataHistoryEntry("project_1", 3, pageOne, "2025-08-30T10:00:00Z", GameDataHistorySource.INSTANCE)
+        every { gameDataService.saveGameData(childId, "sticker_book", "project_1", pageOne) } returns
+            GameDataOperationResult.success("Game data saved successfully", null)
+
+        assertTrue(historyService.restore(childId, "sticker_book", "project_1", 3).success)
+        verify(exactly = 1) { gameDataService.saveGameData(childId, "sticker_book", "project_1", pageOne) }
+    }
+
+    @Test
+    @DisplayName("Restoring a version that isn't in history writes nothing")
+    fun restoreUnknownVersion() {
+        every { store.findVersion(childId, "sticker_book", "project_1", 9) } returns null
+
+        assertFalse(historyService.restore(childId, "sticker_book", "project_1", 9).success)
+        verify(exactly = 0) { gameDataService.saveGameData(any(), any(), any(), any(), any()) }
+        verify(exactly = 0) { store.saveSimple(any(), any(), any(), any(), any()) }
+    }
+
+    @Test
+    @DisplayName("History reads are capped by the retained versions and pruning uses the retention window")
+    fun limitsAndRetention() {
+        every { store.find(childId, "sticker_book", "project_1", any()) } returns emptyList()
+        every { store.prune(any(), any()) } returns 4
+
+        historyService.getHistory(childId, "sticker_book", "project_1", limit = 500)
+        verify { store.find(childId, "sticker_book", "project_1", 20) }
+
+        assertEquals(4, historyService.prune())
+        verify { store.prune(Instant.parse("2025-08-02T12:00:00Z"), 20) }
+    }
+
+    @Test
+    @DisplayName("Retention settings fall back to defaults when unset or invalid")
+    fun configFromEnvironment() {
+        val config = GameDataHistoryConfig.fromEnvironment(
+            mapOf("GAME_DATA_HISTORY_RETENTION_DAYS" to "45", "GAME_DATA_HISTORY_MAX_VERSIONS" to "-1")
+        )
+
+        assertEquals(45, config.retentionDays)
+        assertEquals(GameDataHistoryConfig.DEFAULT_MAX_VERSIONS, config.maxVersionsPerKey)
+    }
+}
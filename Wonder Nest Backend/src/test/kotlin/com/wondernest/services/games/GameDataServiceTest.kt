@@ -0,0 +1,36 @@
+package com.wondernest.services.games
+
+import io.mockk.every
+import io.mockk.mockk
+import io.mockk.verify
+import kotlinx.serialization.json.JsonPrimitive
+import kotlinx.serialization.json.buildJsonObject
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.util.UUID
+import kotlin.test.assertFalse
+import kotlin.test.assertTrue
+
+@DisplayName("Game Data Parental Control Tests")
+class GameDataServiceTest {
+
+    private val childId = UUID.randomUUID()
+    private val instanceService = mockk<ChildGameInstanceService>()
+    private val registryService = mockk<GameRegistryService>()
+    private val gameDataService = GameDataService(instanceService, registryService)
+
+    private val stickerData = buildJsonObject { put("page", JsonPrimitive(3)) }
+
+    @Test
+    @DisplayName("Disabling a game blocks its data writes for that child")
+    fun disabledGameBlocksWrites() {
+        every { instanceService.isGameEnabled(childId, "sticker_book") } returns false
+
+        val result = gameDataService.saveGameData(childId, "sticker_book", "project_1", stickerData)
+
+        assertFalse(result.success)
+        assertTrue(result.gameDisabled)
+        verify(exactly = 0) { instanceService.getOrCreateInstance(any(), any()) }
+        verify(exactly = 0) { registryService.getGameByKey(any()) }
+    }
+}
@@ -0,0 +1,75 @@
+package com.wondernest.services.games
+
+import kotlinx.serialization.json.JsonArray
+import kotlinx.serialization.json.JsonElement
+import kotlinx.serialization.json.JsonPrimitive
+import kotlinx.serialization.json.buildJsonArray
+import kotlinx.serialization.json.buildJsonObject
+import kotlinx.serialization.json.jsonObject
+import kotlinx.serialization.json.Json
+import kotlinx.serialization.json.put
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import kotlin.test.assertEquals
+import kotlin.test.assertTrue
+
+@DisplayName("Game Data Validation Tests")
+class GameDataValidatorTest {
+
+    private val stickerSchema = Json.parseToJsonElement(
+        """
+        {
+          "type": "object",
+          "required": ["pages"],
+          "properties": {
+            "pages": {
+              "type": "array",
+              "items": {
+                "type": "object",
+                "properties": {
+                  "stickers": { "type": "integer", "minimum": 0 },
+                  "title": { "type": "string", "maxLength": 40 }
+                }
+              }
+            }
+          }
+        }
+        """
+    ).jsonObject
+
+    private val validator = GameDataValidator({ key -> stickerSchema.takeIf { key == "sticker_book" } })
+
+    @Test
+    @DisplayName("Schema violations are reported with the failing path")
+    fun schemaViolation() {
+        val data = buildJsonObject {
+            put("pages", buildJsonArray {
+                add(buildJsonObject { put("stickers", 3) })
+                add(buildJsonObject { put("stickers", "lots") })
+            })
+        }
+
+        assertEquals(listOf("$.pages[1].stickers: expected integer"), validator.validate("sticker_book", data))
+        assertEquals(listOf("$.pages: is required"), validator.validate("sticker_book", buildJsonObject { }))
+    }
+
+    @Test
+    @DisplayName("Games without a schema accept free-form data")
+    fun noSchemaIsFreeForm() {
+        val data = buildJsonObject { put("anything", JsonPrimitive("goes")) }
+
+        assertTrue(validator.validate("drawing", data).isEmpty())
+    }
+
+    @Test
+    @DisplayName("Over-deep payloads are rejected even without a schema")
+    fun overDeepPayload() {
+        var data: JsonElement = JsonPrimitive(1)
+        repeat(GameDataValidator.MAX_DEPTH + 1) { data = JsonArray(listOf(data)) }
+
+        val errors = validator.validate("drawing", data)
+
+        assertEquals(1, errors.size)
+        assertTrue(errors.single().endsWith("nesting deeper than ${GameDataValidator.MAX_DEPTH} levels"))
+    }
+}
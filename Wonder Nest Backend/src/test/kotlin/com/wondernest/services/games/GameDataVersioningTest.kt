@@ -0,0 +1,130 @@
+package com.wondernest.services.games
+
+import io.mockk.every
+import io.mockk.mockk
+import kotlinx.datetime.Instant
+import kotlinx.serialization.json.JsonPrimitive
+import kotlinx.serialization.json.buildJsonObject
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.util.UUID
+import java.util.concurrent.CountDownLatch
+import java.util.concurrent.Executors
+import java.util.concurrent.TimeUnit
+import kotlin.test.assertEquals
+import kotlin.test.assertNotNull
+import kotlin.test.assertTrue
+
+@DisplayName("Game Data Optimistic Concurrency Tests")
+class GameDataVersioningTest {
+
+    private val childId = UUID.randomUUID()
+    private val gameId = UUID.randomUUID()
+    private val instanceId = UUID.randomUUID()
+    private val instanceService = mockk<ChildGameInstanceService>()
+    private val registryService = mockk<GameRegistryService>()
+    private val store = InMemoryVersionStore()
+    private val gameDataService = GameDataService(instanceService, registryService, store, GameDataValidator(GameDataSchemaSource { null }))
+
+    init {
+        every { instanceService.isGameEnabled(childId, "sticker_book") } returns true
+        every { registryService.getGameByKey("sticker_book") } returns GameInfo(
+            gameId.toString(), "sticker_book", "Sticker Book", "", "creative", "art", 24, 96, true
+        )
+        every { instanceService.getOrCreateInstance(childId, gameId) } returns ChildGameInstanceInfo(
+            id = instanceId.toString(),
+            childId = childId.toString(),
+            gameId = gameId.toString(),
+            settings = buildJsonObject { },
+            preferences = buildJsonObject { },
+            isUnlocked = true,
+            totalPlayTimeMinutes = 0,
+            sessionCount = 0,
+            lastPlayedAt = null,
+            createdAt = "",
+            updatedAt = ""
+        )
+        every { instanceService.updatePlayTime(instanceId, 0) } returns true
+    }
+
+    private fun page(number: Int) = buildJsonObject { put("page", JsonPrimitive(number)) }
+
+    private fun save(page: Int, expectedVersion: Int?) =
+        gameDataService.saveGameData(childId, "sticker_book", "project_1", page(page), expectedVersion)
+
+    @Test
+    @DisplayName("Of two tablets saving from the same version, the stale one gets a conflict with the current state")
+    fun staleConcurrentSaveRejected() {
+        assertEquals(1, save(1, expectedVersion = 0).data?.dataVersion)
+
+        val executor = Executors.newFixedThreadPool(2)
+        val start = CountDownLatch(1)
+        val saves = listOf(2, 3).map { page ->
+            executor.submit<GameDataOperationResult> {
+                start.await()
+                save(page, expectedVersion = 1)
+            }
+        }
+        start.countDown()
+        val results = saves.map { it.get(10, TimeUnit.SECONDS) }
+        executor.shutdown()
+
+        val (winners, losers) = results.partition { it.success }
+        assertEquals(1, winners.size)
+        val stale = losers.single()
+        assertTrue(stale.conflict)
+
+        // The rejected save sees the winner's data at version 2
+        val current = assertNotNull(stale.data)
+        assertEquals(2, current.dataVersion)
+        assertEquals(winners.single().data?.dataValue, current.dataValue)
+        assertEquals(2, store.find(instanceId, "project_1")?.dataVersion)
+    }
+
+    @Test
+    @DisplayName("Creating an entry that already exists conflicts and retrying from the returned version succeeds")
+    fun retryFromConflictVersion() {
+        save(1, expectedVersion = 0)
+
+        val conflict = save(2, expectedVersion = 0)
+        assertTrue(conflict.conflict)
+
+        val retried = save(2, expectedVersion = conflict.data!!.dataVersion)
+        assertTrue(retried.success)
+        assertEquals(2, retried.data?.dataVersion)
+    }
+
+    private class InMemoryVersionStore : GameDataVersionStore {
+        private val rows = mutableMapOf<String, StoredGameData>()
+
+        @Synchronized
+        override fun find(instanceId: UUID, dataKey: String): StoredGameData? = rows["$instanceId/$dataKey"]
+
+        @Synchronized
+        override fun insertIfAbsent(
+            instanceId: UUID,
+            dataKey: String,
+            dataValue: Map<String, String>,
+            now: Instant
+        ): StoredGameData? {
+            if (rows.containsKey("$instanceId/$dataKey")) return null
+            val row = StoredGameData(UUID.randomUUID(), instanceId, dataKey, dataValue, 1, now, now)
+            rows["$instanceId/$dataKey"] = row
+            return row
+        }
+
+        @Synchronized
+        override fun updateIfVersion(
+            instanceId: UUID,
+            dataKey: String,
+            expectedVersion: Int,
+            dataValue: Map<String, String>,
+            now: Instant
+        ): StoredGameData? {
+            val row = rows["$instanceId/$dataKey"]?.takeIf { it.dataVersion == expectedVersion } ?: return null
+            val updated = row.copy(dataValue = dataValue, dataVersion = expectedVersion + 1, updatedAt = now)
+            rows["$instanceId/$dataKey"] = updated
+            return updated
+        }
+    }
+}
@@ -0,0 +1,68 @@
+package com.wondernest.services.marketplace
+
+import kotlinx.coroutines.runBlocking
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.math.BigDecimal
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+import kotlin.test.assertFalse
+import kotlin.test.assertTrue
+
+@DisplayName("Age Range Validation Tests")
+class AgeRangeValidationTest {
+
+    private fun fields(ageRange: String) = AgeRangeValidation.validate(ageRange).findings.map { it.field }
+
+    @Test
+    @DisplayName("A 3-8 range passes")
+    fun validRange() {
+        val result = AgeRangeValidation.validate("3-8")
+        assertTrue(result.passed)
+        assertTrue(result.findings.isEmpty())
+        assertTrue(AgeRangeValidation.validate(" 0 - 2 ").passed)
+    }
+
+    @Test
+    @DisplayName("Inverted ranges are rejected")
+    fun invertedRange() {
+        val result = AgeRangeValidation.validate("9-4")
+        assertFalse(result.passed)
+        assertEquals(listOf("ageRangeMin"), result.findings.map { it.field })
+        assertEquals("age_range", result.findings.single().check)
+    }
+
+    @Test
+    @DisplayName("Ages outside 0-18, overly wide spans and malformed ranges are rejected")
+    fun outOfBounds() {
+        assertEquals(listOf("ageRangeMax"), fields("12-21"))
+        assertEquals(listOf("ageRangeMin", "ageRangeMax"), fields("19-40"))
+        assertEquals(listOf("ageRange"), fields("1-17"))
+        assertEquals(listOf("ageRange"), fields("3 to 8"))
+        assertEquals(listOf("ageRange"), fields("-2-5"))
+    }
+
+    @Test
+    @DisplayName("Drafts and submissions with an invalid age range are refused")
+    fun creatorServiceRejects() = runBlocking {
+        val creatorService = CreatorService(PublishingLimitsConfig())
+        val request = PublishContentRequest(
+            title = "Shapes",
+            description = "Learn your shapes",
+            contentType = ContentType.ACTIVITY,
+            ageRange = "8-3",
+            price = BigDecimal("0.99"),
+            licensingModel = LicensingModel.FAMILY,
+            tags = emptyList(),
+            educationalGoals = emptyList(),
+            contentData = emptyMap()
+        )
+
+        val error = assertFailsWith<InvalidAgeRangeException> { creatorService.publishContent(UUID.randomUUID(), request) }
+        assertEquals(listOf("ageRangeMin"), error.validation.findings.map { it.field })
+        assertFailsWith<InvalidAgeRangeException> { creatorService.saveDraft(UUID.randomUUID(), request) }
+
+        assertTrue(creatorService.publishContent(UUID.randomUUID(), request.copy(ageRange = "3-8")).success)
+    }
+}
@@ -0,0 +1,137 @@
+package com.wondernest.services.marketplace
+
+import com.wondernest.data.database.repository.marketplace.MarketplaceRepository
+import com.wondernest.services.moderation.ContentFlagService
+import com.wondernest.services.moderation.ModerationDecisionRequest
+import io.mockk.coEvery
+import io.mockk.mockk
+import kotlinx.coroutines.runBlocking
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.math.BigDecimal
+import java.time.Instant
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+import kotlin.test.assertNotNull
+import kotlin.test.assertNull
+import kotlin.test.assertTrue
+
+@DisplayName("Content Embargo Tests")
+class ContentEmbargoTest {
+
+    private var now = Instant.parse("2025-06-01T09:00:00Z")
+    private val launch = Instant.parse("2025-06-03T16:00:00Z")
+    private val creatorService = CreatorService(PublishingLimitsConfig(), clock = { now })
+    private val embargoReleaseTask = EmbargoReleaseTask(creatorService)
+    private val creatorId = UUID.randomUUID()
+    private val familyCreatorId = UUID.randomUUID()
+
+    private val draftRequest = PublishContentRequest(
+        title = "Ocean Launch",
+        description = "A partner launch",
+        contentType = ContentType.STORY,
+        ageRange = "5-7",
+        price = BigDecimal("2.99"),
+        licensingModel = LicensingModel.FAMILY,
+        tags = listOf("ocean"),
+        educationalGoals = listOf("science"),
+        contentData = mapOf("text" to "Deep under the waves")
+    )
+
+    private fun approvedUnderEmbargo(): UUID {
+        val itemId = creatorService.saveDraft(creatorId, draftRequest).itemId!!
+        creatorService.setEmbargo(creatorId, itemId, launch)
+        runBlocking { creatorService.submitForReview(creatorId, itemId) }
+        creatorService.moderateSubmission(UUID.randomUUID(), itemId, ModerationDecisionRequest("approve"))
+        return itemId
+    }
+
+    @Test
+    @DisplayName("Embargoed content is invisible to families until a task run past the embargo")
+    fun embargoedUntilTaskRun() {
+        val itemId = approvedUnderEmbargo()
+
+        assertTrue(creatorService.getPublishedSubmissions().isEmpty())
+        assertNull(creatorService.getSubmissionForViewer(itemId, viewerCreatorId = familyCreatorId))
+        assertNotNull(creatorService.getSubmissionForViewer(itemId, viewerCreatorId = creatorId))
+        assertNotNull(creatorService.getSubmissionForViewer(itemId, viewerCreatorId = null, isAdmin = true))
+
+        // Before the embargo the task leaves it alone
+        assertEquals(0, embargoReleaseTask.runOnce())
+
+        now = launch.plusSeconds(30)
+        assertEquals(1, embargoReleaseTask.runOnce())
+
+        val published = creatorService.getPublishedSubmissions().single()
+        assertEquals(itemId, published.itemId)
+        assertEquals(PublishStatus.PUBLISHED, published.status)
+        assertNotNull(creatorService.getSubmissionForViewer(itemId, viewerCreatorId = familyCreatorId))
+    }
+
+    @Test
+    @DisplayName("Lifting an embargo early publishes approved content immediately")
+    fun liftEarly() {
+        val itemId = approvedUnderEmbargo()
+
+        val status = creatorService.liftEmbargo(itemId)
+
+        assertEquals(PublishStatus.PUBLISHED, status.status)
+        assertNull(status.embargoUntil)
+        assertEquals(listOf(itemId), creatorService.getPublishedSubmissions().map { it.itemId })
+    }
+
+    @Test
+    @DisplayName("Embargoes must end in the future and can only be lifted by the owning creator")
+    fun embargoValidation() {
+        val itemId = creatorService.saveDraft(creatorId, draftRequest).itemId!!
+
+        assertFailsWith<IllegalArgumentException> {
+            creatorService.setEmbargo(creatorId, itemId, now.minusSeconds(60))
+        }
+        creatorService.setEmbargo(creatorId, itemId, launch)
+        assertFailsWith<NoSuchElementException> {
+            creatorService.liftEmbargo(itemId, creatorId = UUID.randomUUID())
+        }
+    }
+
+    @Test
+    @DisplayName("Marketplace browsing hides embargoed items until they are released")
+    fun marketplaceHidesEmbargoedItems() = runBlocking {
+        val itemId = approvedUnderEmbargo()
+        val listed = listing(itemId)
+        val other = listing(UUID.randomUUID())
+        val repository = mockk<MarketplaceRepository>()
+        coEvery { repository.getRecommendations(any(), any()) } returns listOf(listed, other)
+        coEvery { repository.getListingById(itemId) } returns mockk(relaxed = true)
+        val marketplaceService = MarketplaceService(repository, ContentFlagService(), creatorService)
+
+        assertEquals(listOf(other.id), marketplaceService.getRecommendations(null, UUID.randomUUID()).map { it.id })
+        assertNull(marketplaceService.getItemDetails(itemId))
+
+        now = launch.plusSeconds(30)
+        embargoReleaseTask.runOnce()
+
+        assertEquals(2, marketplaceService.getRecommendations(null, UUID.randomUUID()).size)
+        assertNotNull(marketplaceService.getItemDetails(itemId))
+    }
+
+    private fun listing(id: UUID) = MarketplaceItem(
+        id = id,
+        title = "Ocean Launch",
+        description = "A partner launch",
+        contentType = ContentType.STORY,
+        creatorId = creatorId,
+        creatorName = "Creator",
+        price = BigDecimal("2.99"),
+        rating = 0.0,
+        ratingCount = 0,
+        ageRange = "5-7",
+        tags = emptyList(),
+        thumbnailUrl = null,
+        previewAvailable = false,
+        isAIGenerated = false,
+        purchaseCount = 0,
+        createdAt = now
+    )
+}
@@ -0,0 +1,110 @@
+package com.wondernest.services.marketplace
+
+import kotlinx.serialization.json.JsonPrimitive
+import org.junit.jupiter.api.BeforeEach
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.math.BigDecimal
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+import kotlin.test.assertFalse
+
+@DisplayName("Content JSON Patch Tests")
+class ContentPatchTest {
+
+    private lateinit var creatorService: CreatorService
+    private val creatorId = UUID.randomUUID()
+
+    private val draftRequest = PublishContentRequest(
+        title = "Night Sky",
+        description = "Stories about the stars",
+        contentType = ContentType.STORY,
+        ageRange = "5-7",
+        price = BigDecimal("1.99"),
+        licensingModel = LicensingModel.FAMILY,
+        tags = listOf("space"),
+        educationalGoals = listOf("science"),
+        contentData = mapOf("text" to "Once upon a time", "narrator" to "Luna")
+    )
+
+    @BeforeEach
+    fun setup() {
+        creatorService = CreatorService(PublishingLimitsConfig())
+    }
+
+    private fun draft(): UUID = creatorService.saveDraft(creatorId, draftRequest).itemId!!
+
+    @Test
+    @DisplayName("Add, replace and remove operations update only the targeted fields")
+    fun appliesOperations() {
+        val itemId = draft()
+
+        val result = creatorService.patchDraftContent(
+            creatorId, itemId, ContentPatchRequest(
+                expectedRevision = 1,
+                operations = listOf(
+                    JsonPatchOperation("add", "/coverImage", JsonPrimitive("stars.png")),
+                    JsonPatchOperation("replace", "/text", JsonPrimitive("Once upon a starry night")),
+                    JsonPatchOperation("remove", "/narrator")
+                )
+            )
+        )
+
+        assertEquals(2, result.revision)
+        assertEquals(mapOf("text" to "Once upon a starry night", "coverImage" to "stars.png"), result.contentData)
+        assertEquals(result, creatorService.getDraftContent(itemId))
+    }
+
+    @Test
+    @DisplayName("A patch producing an invalid document is rejected and leaves the draft unchanged")
+    fun rejectsInvalidDocument() {
+        val itemId = draft()
+
+        assertFailsWith<JsonPatchException> {
+            creatorService.patchDraftContent(
+                creatorId, itemId, ContentPatchRequest(1, listOf(JsonPatchOperation("remove", "/text")))
+            )
+        }
+        assertFailsWith<JsonPatchException> {
+            creatorService.patchDraftContent(
+                creatorId, itemId, ContentPatchRequest(1, listOf(JsonPatchOperation("replace", "/text", JsonPrimitive(42))))
+            )
+        }
+
+        val unchanged = creatorService.getDraftContent(itemId)!!
+        assertEquals(1, unchanged.revision)
+        assertEquals(draftRequest.contentData, unchanged.contentData)
+    }
+
+    @Test
+    @DisplayName("Patches against a stale revision conflict")
+    fun staleRevisionConflicts() {
+        val itemId = draft()
+        val rename = listOf(JsonPatchOperation("replace", "/narrator", JsonPrimitive("Orion")))
+
+        creatorService.patchDraftContent(creatorId, itemId, ContentPatchRequest(1, rename))
+        val conflict = assertFailsWith<ContentVersionConflictException> {
+            creatorService.patchDraftContent(creatorId, itemId, ContentPatchRequest(1, rename))
+        }
+        assertEquals(2, conflict.currentRevision)
+    }
+
+    @Test
+    @DisplayName("A failing test operation aborts the whole patch")
+    fun failingTestOperationAborts() {
+        val itemId = draft()
+
+        assertFailsWith<JsonPatchException> {
+            creatorService.patchDraftContent(
+                creatorId, itemId, ContentPatchRequest(
+                    1, listOf(
+                        JsonPatchOperation("replace", "/narrator", JsonPrimitive("Orion")),
+                        JsonPatchOperation("test", "/text", JsonPrimitive("Something else"))
+                    )
+                )
+            )
+        }
+        assertFalse(creatorService.getDraftContent(itemId)!!.contentData["narrator"] == "Orion")
+    }
+}
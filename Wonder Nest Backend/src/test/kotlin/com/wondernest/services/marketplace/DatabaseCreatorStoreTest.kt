@@ -0,0 +1,139 @@
+package com.wondernest.services.marketplace
+
+import com.wondernest.services.moderation.ModerationDecisionRequest
+import kotlinx.coroutines.runBlocking
+import org.jetbrains.exposed.sql.Database
+import org.jetbrains.exposed.sql.transactions.transaction
+import org.junit.jupiter.api.AfterAll
+import org.junit.jupiter.api.BeforeAll
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import org.junit.jupiter.api.TestInstance
+import org.testcontainers.containers.PostgreSQLContainer
+import org.testcontainers.junit.jupiter.Testcontainers
+import java.math.BigDecimal
+import java.time.Instant
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertNotNull
+
+@Testcontainers(disabledWithoutDocker = true)
+@TestInstance(TestInstance.Lifecycle.PER_CLASS)
+@DisplayName("Database Creator Store Tests")
+class DatabaseCreatorStoreTest {
+
+    private val postgres = PostgreSQLContainer("postgres:15-alpine")
+    private var now = Instant.parse("2025-10-01T09:00:00Z")
+
+    // The columns the store reads or writes, as V25 and V64 create them
+    private val schema = """
+        CREATE SCHEMA marketplace;
+        CREATE TABLE marketplace.creator_profiles (
+            id UUID PRIMARY KEY, user_id UUID NOT NULL UNIQUE, display_name VARCHAR(100) NOT NULL, bio TEXT,
+            avatar_url TEXT, social_links JSONB DEFAULT '{}', content_specialties JSONB NOT NULL DEFAULT '[]',
+            tier VARCHAR(30) DEFAULT 'HOBBYIST', verified BOOLEAN DEFAULT FALSE, total_sales INTEGER DEFAULT 0,
+            total_revenue DECIMAL(10,2) DEFAULT 0, average_rating DECIMAL(3,2) DEFAULT 0, content_count INTEGER DEFAULT 0,
+            follower_count INTEGER DEFAULT 0, account_status VARCHAR(30) DEFAULT 'PENDING_VERIFICATION',
+            created_at TIMESTAMP WITH TIME ZONE, updated_at TIMESTAMP WITH TIME ZONE);
+        CREATE TABLE marketplace.creator_submissions (
+            item_id UUID PRIMARY KEY, creator_id UUID NOT NULL, title VARCHAR(200) NOT NULL, status VARCHAR(30) NOT NULL,
+            content_type VARCHAR(50), submitted_at TIMESTAMP WITH TIME ZONE, published_at TIMESTAMP WITH TIME ZONE,
+            embargo_until TIMESTAMP WITH TIME ZONE, claimed_by UUID, claimed_at TIMESTAMP WITH TIME ZONE,
+            rejection_category VARCHAR(50), rejection_guidance TEXT, rejection_notes TEXT, rejected_by UUID,
+            rejected_at TIMESTAMP WITH TIME ZONE, possible_duplicates JSONB NOT NULL DEFAULT '[]',
+            automated_findings JSONB NOT NULL DEFAULT '[]', updated_at TIMESTAMP WITH TIME ZONE NOT NULL);
+        CREATE TABLE marketplace.creator_drafts (
+            item_id UUID PRIMARY KEY REFERENCES marketplace.creator_submissions(item_id) ON DELETE CASCADE,
+            revision INTEGER NOT NULL, title VARCHAR(200) NOT NULL, description TEXT NOT NULL,
+            content_type VARCHAR(50) NOT NULL, age_range VARCHAR(20) NOT NULL, price DECIMAL(8,2) NOT NULL,
+            licensing_model VARCHAR(30) NOT NULL, tags JSONB NOT NULL DEFAULT '[]', educational_goals JSONB NOT NULL DEFAULT '[]',
+            content_data JSONB NOT NULL DEFAULT '{}', version VARCHAR(50), changelog TEXT,
+            primary_language VARCHAR(10) NOT NULL DEFAULT 'en', languages_supported JSONB NOT NULL DEFAULT '[]',
+            localizations JSONB NOT NULL DEFAULT '{}', drop_incomplete_languages BOOLEAN NOT NULL DEFAULT FALSE,
+            updated_at TIMESTAMP WITH TIME ZONE NOT NULL);
+        CREATE TABLE marketplace.creator_content_versions (
+            item_id UUID NOT NULL, version VARCHAR(50) NOT NULL, changelog TEXT NOT NULL,
+            released_at TIMESTAMP WITH TIME ZONE NOT NULL, PRIMARY KEY (item_id, version))
+    """.trimIndent()
+
+    @BeforeAll
+    fun connect() {
+        postgres.start()
+        Database.connect(postgres.jdbcUrl, "org.postgresql.Driver", postgres.username, postgres.password)
+        transaction { schema.split(";").filter { it.isNotBlank() }.forEach { exec(it) } }
+    }
+
+    @AfterAll
+    fun close() {
+        postgres.stop()
+    }
+
+    // A new service over the same store stands in for a restart
+    private fun service() = CreatorService(PublishingLimitsConfig(), clock = { now }, store = DatabaseCreatorStore)
+
+    private fun request(title: String = "Night Sky") = PublishContentRequest(
+        title = title,
+        description = "Counting stars with an owl",
+        contentType = ContentType.STORY,
+        ageRange = "4-6",
+        price = BigDecimal("0.99"),
+        licensingModel = LicensingModel.FAMILY,
+        tags = listOf("space"),
+        educationalGoals = emptyList(),
+        contentData = mapOf("text" to "The owl counts the stars")
+    )
+
+    @Test
+    @DisplayName("Profiles, tiers and drafts survive a restart")
+    fun profilesAndDraftsPersist() = runBlocking {
+        val userId = UUID.randomUUID()
+        val profile = service().registerCreator(
+            userId,
+            CreatorRegistrationRequest("Owl Books", "Stories", null, listOf("STORY"), listOf("en"))
+        )
+        service().updateCreatorTier(profile.id, CreatorTier.PARTNER_STUDIO)
+        val itemId = service().saveDraft(profile.id, request()).itemId!!
+
+        val restarted = service()
+        assertEquals(profile.id, restarted.findCreatorIdForUser(userId))
+        assertEquals(CreatorTier.PARTNER_STUDIO, restarted.getCreatorTier(profile.id))
+        val draft = assertNotNull(restarted.getDraftContent(itemId))
+        assertEquals(1, draft.revision)
+        assertEquals(PublishStatus.DRAFT, restarted.getSubmissionForViewer(itemId, profile.id)?.status)
+    }
+
+    @Test
+    @DisplayName("The review queue, claims and decisions survive a restart")
+    fun reviewPersists() = runBlocking {
+        val creatorId = UUID.randomUUID()
+        val moderatorId = UUID.randomUUID()
+        val itemId = service().saveDraft(creatorId, request("Moon Walk")).itemId!!
+        service().submitForReview(creatorId, itemId)
+        service().claimSubmission(moderatorId, itemId)
+
+        val queue = service().getModerationQueue(moderatorId).items.single { it.itemId == itemId.toString() }
+        assertEquals(moderatorId.toString(), queue.claimedBy)
+
+        service().moderateSubmission(moderatorId, itemId, ModerationDecisionRequest(decision = "approve"))
+        assertEquals(PublishStatus.APPROVED, service().getSubmissionForViewer(itemId, creatorId)?.status)
+    }
+
+    @Test
+    @DisplayName("Embargoes release and version history is kept after a restart")
+    fun embargoAndVersions() = runBlocking {
+        val creatorId = UUID.randomUUID()
+        val moderatorId = UUID.randomUUID()
+        val itemId = service().publishContent(creatorId, request("Sun Song")).itemId!!
+        service().setEmbargo(creatorId, itemId, now.plusSeconds(3_600))
+        service().moderateSubmission(moderatorId, itemId, ModerationDecisionRequest(decision = "approve"))
+
+        now = now.plusSeconds(7_200)
+        assertEquals(listOf(itemId), service().releaseExpiredEmbargoes().filter { it == itemId })
+        assertEquals(PublishStatus.PUBLISHED, service().getSubmissionForViewer(itemId, null)?.status)
+
+        service().publishContent(creatorId, request("Sun Song").copy(itemId = itemId, version = "1.1.0", changelog = "New verse"))
+        val history = assertNotNull(service().getVersionHistory(itemId))
+        assertEquals("1.1.0", history.currentVersion)
+        assertEquals(listOf("1.1.0", "1.0.0"), history.versions.map { it.version })
+    }
+}
@@ -0,0 +1,92 @@
+package com.wondernest.services.marketplace
+
+import com.wondernest.data.database.repository.marketplace.MarketplaceRepository
+import com.wondernest.services.moderation.ContentFlagConfig
+import com.wondernest.services.moderation.ContentFlagService
+import io.mockk.coEvery
+import io.mockk.mockk
+import kotlinx.coroutines.runBlocking
+import org.junit.jupiter.api.BeforeEach
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.math.BigDecimal
+import java.time.Instant
+import java.util.UUID
+import kotlin.test.assertEquals
+
+@DisplayName("Flagged Content Exclusion Tests")
+class FlaggedContentExclusionTest {
+
+    private val repository = mockk<MarketplaceRepository>()
+    private lateinit var flagService: ContentFlagService
+    private lateinit var marketplaceService: MarketplaceService
+
+    private val flagged = item("Flagged Story")
+    private val clean = item("Clean Story")
+
+    @BeforeEach
+    fun setup() {
+        flagService = ContentFlagService(ContentFlagConfig(reportThreshold = 2))
+        marketplaceService = MarketplaceService(repository, flagService, CreatorService(PublishingLimitsConfig()))
+        coEvery { repository.getRecommendations(any(), any()) } returns listOf(flagged, clean)
+        coEvery { repository.searchListings(any()) } returns SearchResult(
+            items = listOf(flagged, clean),
+            totalCount = 2,
+            page = 0,
+            pageSize = 20,
+            facets = SearchFacets(emptyMap(), emptyMap(), emptyMap())
+        )
+    }
+
+    @Test
+    @DisplayName("A flagged-but-published item is excluded from recommendations")
+    fun reportedItemExcludedFromRecommendations() = runBlocking {
+        marketplaceService.reportItem(UUID.randomUUID(), flagged.id, "Scary imagery")
+        assertEquals(2, marketplaceService.getRecommendations(null, UUID.randomUUID()).size)
+
+        marketplaceService.reportItem(UUID.randomUUID(), flagged.id, "Not age appropriate")
+
+        assertEquals(listOf(clean.id), marketplaceService.getRecommendations(null, UUID.randomUUID()).map { it.id })
+    }
+
+    @Test
+    @DisplayName("Repeat reports from one family count once")
+    fun repeatReportsCountOnce() = runBlocking {
+        val familyId = UUID.randomUUID()
+        repeat(3) { marketplaceService.reportItem(familyId, flagged.id, "Scary imagery") }
+
+        assertEquals(2, marketplaceService.getRecommendations(null, familyId).size)
+    }
+
+    @Test
+    @DisplayName("Reported items are excluded from search until cleared")
+    fun reportedExcludedFromSearch() = runBlocking {
+        repeat(2) { marketplaceService.reportItem(UUID.randomUUID(), flagged.id, "Scary imagery") }
+
+        val result = marketplaceService.searchContent(SearchRequest(query = "story"))
+        assertEquals(listOf(clean.id), result.items.map { it.id })
+        assertEquals(1, result.totalCount)
+
+        flagService.clear(flagged.id)
+        assertEquals(2, marketplaceService.searchContent(SearchRequest(query = "story")).items.size)
+    }
+
+    private fun item(title: String) = MarketplaceItem(
+        id = UUID.randomUUID(),
+        title = title,
+        description = "A story",
+        contentType = ContentType.STORY,
+        creatorId = UUID.randomUUID(),
+        creatorName = "Creator",
+        price = BigDecimal("1.99"),
+        rating = 4.5,
+        ratingCount = 10,
+        ageRange = "4-6",
+        tags = emptyList(),
+        thumbnailUrl = null,
+        previewAvailable = false,
+        isAIGenerated = false,
+        purchaseCount = 0,
+        createdAt = Instant.now()
+    )
+}
@@ -0,0 +1,87 @@
+package com.wondernest.services.marketplace
+
+import kotlinx.coroutines.runBlocking
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.math.BigDecimal
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+import kotlin.test.assertTrue
+
+@DisplayName("Localization Completeness Tests")
+class LocalizationCompletenessTest {
+
+    private val request = PublishContentRequest(
+        title = "Garden Friends",
+        description = "Meet the animals in the garden",
+        contentType = ContentType.STORY,
+        ageRange = "3-5",
+        price = BigDecimal("1.99"),
+        licensingModel = LicensingModel.FAMILY,
+        tags = listOf("animals"),
+        educationalGoals = listOf("vocabulary"),
+        contentData = mapOf(
+            "text" to "The snail says hello",
+            "moral" to "Be kind to small creatures",
+            "coverImage" to "https://cdn.example.com/garden.png"
+        ),
+        languagesSupported = listOf("en", "es")
+    )
+
+    @Test
+    @DisplayName("Declaring es without Spanish fields fails with the missing fields listed")
+    fun missingSpanishFails() = runBlocking {
+        val creatorService = CreatorService(PublishingLimitsConfig())
+
+        val error = assertFailsWith<LocalizationIncompleteException> {
+            creatorService.publishContent(UUID.randomUUID(), request)
+        }
+
+        assertEquals(
+            listOf(MissingTranslations("es", listOf("title", "description", "contentData.moral", "contentData.text"))),
+            error.missing
+        )
+    }
+
+    @Test
+    @DisplayName("Partially translated languages list only the fields still missing")
+    fun partialTranslation() {
+        val partial = request.copy(
+            localizations = mapOf(
+                "es" to LocalizedContent(
+                    title = "Amigos del Jardín",
+                    description = "Conoce a los animales del jardín",
+                    contentData = mapOf("text" to "El caracol dice hola")
+                )
+            )
+        )
+
+        assertEquals(
+            listOf(MissingTranslations("es", listOf("contentData.moral"))),
+            LocalizationCompleteness.missingTranslations(partial)
+        )
+    }
+
+    @Test
+    @DisplayName("Complete translations publish, and incomplete languages can be dropped instead")
+    fun completeOrDropped() = runBlocking {
+        val creatorService = CreatorService(PublishingLimitsConfig())
+        val complete = request.copy(
+            localizations = mapOf(
+                "es" to LocalizedContent(
+                    title = "Amigos del Jardín",
+                    description = "Conoce a los animales del jardín",
+                    contentData = mapOf("text" to "El caracol dice hola", "moral" to "Sé amable con las criaturas pequeñas")
+                )
+            )
+        )
+        assertTrue(creatorService.publishContent(UUID.randomUUID(), complete).success)
+
+        val reduced = creatorService.publishContent(
+            UUID.randomUUID(),
+            request.copy(title = "Garden Friends Too", dropIncompleteLanguages = true)
+        )
+        assertEquals(listOf("en"), reduced.languagesSupported)
+    }
+}
@@ -0,0 +1,128 @@
+package com.wondernest.services.marketplace
+
+import com.wondernest.services.moderation.ModerationDecisionRequest
+import kotlinx.coroutines.runBlocking
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.math.BigDecimal
+import java.time.Duration
+import java.time.Instant
+import java.util.UUID
+import java.util.concurrent.Callable
+import java.util.concurrent.CountDownLatch
+import java.util.concurrent.Executors
+import java.util.concurrent.TimeUnit
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+import kotlin.test.assertNull
+
+@DisplayName("Moderation Queue Tests")
+class ModerationQueueTest {
+
+    private var now = Instant.parse("2025-09-01T08:00:00Z")
+    private val creatorService = CreatorService(
+        PublishingLimitsConfig(),
+        queueConfig = ModerationQueueConfig(claimTtl = Duration.ofMinutes(30)),
+        clock = { now }
+    )
+    private val moderatorA = UUID.randomUUID()
+    private val moderatorB = UUID.randomUUID()
+
+    private fun submit(tier: CreatorTier, title: String, type: ContentType = ContentType.GAME): UUID = runBlocking {
+        val creatorId = UUID.randomUUID()
+        creatorService.updateCreatorTier(creatorId, tier)
+        creatorService.publishContent(
+            creatorId,
+            PublishContentRequest(
+                title = title,
+                description = "Queue test",
+                contentType = type,
+                ageRange = "5-7",
+                price = BigDecimal("1.99"),
+                licensingModel = LicensingModel.FAMILY,
+                tags = emptyList(),
+                educationalGoals = emptyList(),
+                contentData = emptyMap()
+            )
+        ).itemId!!
+    }
+
+    private fun queueTitles(moderatorId: UUID = moderatorA) =
+        creatorService.getModerationQueue(moderatorId).items.map { it.title }
+
+    @Test
+    @DisplayName("A partner submission outranks an older hobbyist one")
+    fun priorityOrdering() {
+        submit(CreatorTier.HOBBYIST, "Old hobby game")
+        now = now.plus(Duration.ofHours(6))
+        submit(CreatorTier.PARTNER_STUDIO, "New partner game")
+
+        assertEquals(listOf("New partner game", "Old hobby game"), queueTitles())
+        assertEquals(listOf(40, 12), creatorService.getModerationQueue(moderatorA).items.map { it.priority })
+    }
+
+    @Test
+    @DisplayName("Items waiting long enough surface ahead of new partner submissions")
+    fun agingItemsSurface() {
+        submit(CreatorTier.HOBBYIST, "Old hobby game")
+        now = now.plus(Duration.ofHours(21))
+        submit(CreatorTier.PARTNER_STUDIO, "New partner game")
+
+        assertEquals(listOf("Old hobby game", "New partner game"), queueTitles())
+    }
+
+    @Test
+    @DisplayName("A claimed submission can't be claimed or decided by another moderator")
+    fun claimPreventsDoubleAssignment() {
+        val itemId = submit(CreatorTier.EMERGING, "Counting game")
+
+        creatorService.claimSubmission(moderatorA, itemId)
+        assertFailsWith<SubmissionClaimedException> { creatorService.claimSubmission(moderatorB, itemId) }
+        assertFailsWith<SubmissionClaimedException> {
+            creatorService.moderateSubmission(moderatorB, itemId, ModerationDecisionRequest("approve"))
+        }
+        // Hidden from other moderators, still listed for the one holding it
+        assertEquals(emptyList(), queueTitles(moderatorB))
+        assertEquals(listOf("Counting game"), queueTitles(moderatorA))
+
+        val result = creatorService.moderateSubmission(moderatorA, itemId, ModerationDecisionRequest("approve"))
+        assertEquals(PublishStatus.APPROVED, result.status)
+    }
+
+    @Test
+    @DisplayName("Concurrent claims assign the submission to exactly one moderator")
+    fun concurrentClaims() {
+        val itemId = submit(CreatorTier.PROFESSIONAL, "Shapes game")
+        val moderators = List(16) { UUID.randomUUID() }
+        val executor = Executors.newFixedThreadPool(8)
+        val start = CountDownLatch(1)
+
+        val results = moderators.map { moderator ->
+            executor.submit(Callable {
+                start.await()
+                runCatching { creatorService.claimSubmission(moderator, itemId) }.isSuccess
+            })
+        }
+        start.countDown()
+        val claimed = results.map { it.get(10, TimeUnit.SECONDS) }
+        executor.shutdown()
+
+        assertEquals(1, claimed.count { it })
+    }
+
+    @Test
+    @DisplayName("Abandoned claims expire and the item returns to the queue")
+    fun claimExpiry() {
+        val itemId = submit(CreatorTier.HOBBYIST, "Colour game")
+        creatorService.claimSubmission(moderatorA, itemId)
+        assertEquals(emptyList(), queueTitles(moderatorB))
+
+        now = now.plus(Duration.ofMinutes(31))
+        assertEquals(listOf("Colour game"), queueTitles(moderatorB))
+        val claimed = creatorService.claimSubmission(moderatorB, itemId)
+        assertEquals(moderatorB.toString(), claimed.claimedBy)
+
+        creatorService.releaseClaim(moderatorB, itemId)
+        assertNull(creatorService.getModerationQueue(moderatorA).items.single().claimedBy)
+    }
+}
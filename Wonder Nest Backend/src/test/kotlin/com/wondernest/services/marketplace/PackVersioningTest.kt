@@ -0,0 +1,82 @@
+package com.wondernest.services.marketplace
+
+import kotlinx.coroutines.runBlocking
+import org.junit.jupiter.api.BeforeEach
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.math.BigDecimal
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+import kotlin.test.assertNull
+
+@DisplayName("Pack Version History Tests")
+class PackVersioningTest {
+
+    private lateinit var creatorService: CreatorService
+    private val creatorId = UUID.randomUUID()
+
+    private val publishRequest = PublishContentRequest(
+        title = "Ocean Animals",
+        description = "Stickers of sea creatures",
+        contentType = ContentType.ACTIVITY,
+        ageRange = "3-5",
+        price = BigDecimal("2.99"),
+        licensingModel = LicensingModel.FAMILY,
+        tags = listOf("animals"),
+        educationalGoals = listOf("vocabulary"),
+        contentData = emptyMap()
+    )
+
+    @BeforeEach
+    fun setup() {
+        creatorService = CreatorService(PublishingLimitsConfig())
+        creatorService.updateCreatorTier(creatorId, CreatorTier.PARTNER_STUDIO)
+    }
+
+    @Test
+    @DisplayName("Publishing a new version appends to the history")
+    fun newVersionAppendsToHistory() = runBlocking {
+        val packId = creatorService.publishContent(creatorId, publishRequest).itemId!!
+
+        creatorService.publishContent(
+            creatorId,
+            publishRequest.copy(itemId = packId, version = "1.1.0", changelog = "Added 10 new fish stickers")
+        )
+
+        val history = creatorService.getVersionHistory(packId)!!
+        assertEquals("1.1.0", history.currentVersion)
+        assertEquals(listOf("1.1.0", "1.0.0"), history.versions.map { it.version })
+        assertEquals("Added 10 new fish stickers", history.versions.first().changelog)
+    }
+
+    @Test
+    @DisplayName("A version bump without a changelog is rejected")
+    fun versionBumpRequiresChangelog() = runBlocking {
+        val packId = creatorService.publishContent(creatorId, publishRequest).itemId!!
+
+        assertFailsWith<ChangelogRequiredException> {
+            creatorService.publishContent(creatorId, publishRequest.copy(itemId = packId, version = "1.0.1", changelog = " "))
+        }
+        assertEquals(1, creatorService.getVersionHistory(packId)!!.versions.size)
+    }
+
+    @Test
+    @DisplayName("A new version must be higher than the current one")
+    fun versionMustIncrease() = runBlocking<Unit> {
+        val packId = creatorService.publishContent(creatorId, publishRequest.copy(version = "2.0.0")).itemId!!
+
+        assertFailsWith<IllegalArgumentException> {
+            creatorService.publishContent(
+                creatorId,
+                publishRequest.copy(itemId = packId, version = "1.9.9", changelog = "Downgrade")
+            )
+        }
+    }
+
+    @Test
+    @DisplayName("Unknown packs have no history")
+    fun unknownPackHasNoHistory() {
+        assertNull(creatorService.getVersionHistory(UUID.randomUUID()))
+    }
+}
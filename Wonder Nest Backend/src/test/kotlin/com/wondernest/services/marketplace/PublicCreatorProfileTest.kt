@@ -0,0 +1,95 @@
+package com.wondernest.services.marketplace
+
+import com.wondernest.services.moderation.ModerationDecisionRequest
+import kotlinx.coroutines.runBlocking
+import kotlinx.serialization.encodeToString
+import kotlinx.serialization.json.Json
+import kotlinx.serialization.json.jsonObject
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.math.BigDecimal
+import java.time.Duration
+import java.time.Instant
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertFalse
+import kotlin.test.assertNotNull
+import kotlin.test.assertNull
+import kotlin.test.assertTrue
+
+@DisplayName("Public Creator Profile Tests")
+class PublicCreatorProfileTest {
+
+    private val now = Instant.parse("2025-10-01T12:00:00Z")
+    private val creatorService = CreatorService(PublishingLimitsConfig(), clock = { now })
+
+    private fun register(): UUID = runBlocking {
+        creatorService.registerCreator(
+            UUID.randomUUID(),
+            CreatorRegistrationRequest(
+                displayName = "Maple Tree Studio",
+                bio = "Picture books about the forest",
+                avatarUrl = null,
+                contentSpecialties = listOf("stories", "nature"),
+                languagesSupported = listOf("en"),
+                educatorCredentials = mapOf("licence" to "EDU-1234"),
+                socialLinks = mapOf("website" to "https://maple.example.com", "bad" to "javascript:alert(1)")
+            )
+        ).id
+    }
+
+    private fun publish(creatorId: UUID) = runBlocking {
+        val itemId = creatorService.publishContent(
+            creatorId,
+            PublishContentRequest(
+                title = "Forest Friends",
+                description = "Meet the animals of the forest",
+                contentType = ContentType.STORY,
+                ageRange = "3-6",
+                price = BigDecimal("1.99"),
+                licensingModel = LicensingModel.FAMILY,
+                tags = emptyList(),
+                educationalGoals = emptyList(),
+                contentData = emptyMap()
+            )
+        ).itemId!!
+        creatorService.moderateSubmission(UUID.randomUUID(), itemId, ModerationDecisionRequest("approve"))
+        creatorService.setEmbargo(creatorId, itemId, now.plus(Duration.ofDays(1)))
+        creatorService.liftEmbargo(itemId)
+    }
+
+    @Test
+    @DisplayName("The public profile omits account and earnings fields")
+    fun sensitiveFieldsOmitted() {
+        val creatorId = register()
+        publish(creatorId)
+
+        val profile = assertNotNull(creatorService.getPublicProfile(creatorId))
+        assertEquals(1, profile.publishedContentCount)
+        assertEquals(listOf("stories", "nature"), profile.contentSpecialties)
+        assertEquals(mapOf("website" to "https://maple.example.com"), profile.socialLinks)
+
+        val keys = Json.parseToJsonElement(Json.encodeToString(profile)).jsonObject.keys
+        for (field in listOf("userId", "email", "accountStatus", "totalRevenue", "totalSales", "educatorCredentials")) {
+            assertFalse(field in keys, "$field should not be public")
+        }
+        assertTrue("displayName" in keys)
+    }
+
+    @Test
+    @DisplayName("Suspended and banned creators are not exposed")
+    fun hiddenStatuses() {
+        val creatorId = register()
+        assertNull(creatorService.getPublicProfile(creatorId)!!.averageRating)
+
+        creatorService.updateAccountStatus(creatorId, CreatorAccountStatus.BANNED)
+        assertNull(creatorService.getPublicProfile(creatorId))
+
+        creatorService.updateAccountStatus(creatorId, CreatorAccountStatus.SUSPENDED)
+        assertNull(creatorService.getPublicProfile(creatorId))
+
+        creatorService.updateAccountStatus(creatorId, CreatorAccountStatus.ACTIVE)
+        assertNotNull(creatorService.getPublicProfile(creatorId))
+        assertNull(creatorService.getPublicProfile(UUID.randomUUID()))
+    }
+}
@@ -0,0 +1,95 @@
+package com.wondernest.services.marketplace
+
+import kotlinx.coroutines.runBlocking
+import org.junit.jupiter.api.BeforeEach
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.math.BigDecimal
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+import kotlin.test.assertNull
+
+@DisplayName("Creator Publishing Limits Tests")
+class PublishingLimitsTest {
+
+    private lateinit var creatorService: CreatorService
+
+    private val draftRequest = PublishContentRequest(
+        title = "My Story",
+        description = "A short story",
+        contentType = ContentType.STORY,
+        ageRange = "3-5",
+        price = BigDecimal("1.99"),
+        licensingModel = LicensingModel.FAMILY,
+        tags = listOf("animals"),
+        educationalGoals = listOf("reading"),
+        contentData = emptyMap()
+    )
+
+    @BeforeEach
+    fun setup() {
+        creatorService = CreatorService(PublishingLimitsConfig())
+    }
+
+    @Test
+    @DisplayName("Tier1 creator hits the concurrent draft limit")
+    fun tier1CreatorHitsDraftLimit() {
+        val creatorId = UUID.randomUUID()
+        creatorService.updateCreatorTier(creatorId, CreatorTier.HOBBYIST)
+        val limit = PublishingLimitsConfig.DEFAULT_LIMITS.getValue(PublishingTier.TIER_1).maxConcurrentDrafts!!
+
+        repeat(limit) { creatorService.saveDraft(creatorId, draftRequest) }
+
+        val error = assertFailsWith<PublishingLimitExceededException> {
+            creatorService.saveDraft(creatorId, draftRequest)
+        }
+        assertEquals(PublishingLimitType.CONCURRENT_DRAFTS, error.limitType)
+        assertEquals(limit, error.limit)
+    }
+
+    @Test
+    @DisplayName("Tier4 creator is not limited on drafts")
+    fun tier4CreatorHasNoDraftLimit() {
+        val creatorId = UUID.randomUUID()
+        creatorService.updateCreatorTier(creatorId, CreatorTier.PARTNER_STUDIO)
+
+        repeat(100) {
+            val result = creatorService.saveDraft(creatorId, draftRequest)
+            assertEquals(PublishStatus.DRAFT, result.status)
+        }
+    }
+
+    @Test
+    @DisplayName("Monthly publish limit applies when submitting drafts for review")
+    fun monthlyLimitAppliesOnSubmitForReview() = runBlocking {
+        val config = PublishingLimitsConfig(
+            mapOf(PublishingTier.TIER_1 to TierPublishingLimit(maxConcurrentDrafts = 10, maxMonthlyPublishes = 1))
+        )
+        val service = CreatorService(config)
+        val creatorId = UUID.randomUUID()
+
+        val first = service.saveDraft(creatorId, draftRequest)
+        val second = service.saveDraft(creatorId, draftRequest)
+        service.submitForReview(creatorId, first.itemId!!)
+
+        val error = assertFailsWith<PublishingLimitExceededException> {
+            service.submitForReview(creatorId, second.itemId!!)
+        }
+        assertEquals(PublishingLimitType.MONTHLY_PUBLISHES, error.limitType)
+    }
+
+    @Test
+    @DisplayName("Limits can be overridden from the environment")
+    fun limitsFromEnvironment() {
+        val env = mapOf(
+            "PUBLISH_LIMIT_TIER1_DRAFTS" to "7",
+            "PUBLISH_LIMIT_TIER2_MONTHLY" to "unlimited"
+        )
+        val config = PublishingLimitsConfig.fromEnvironment { env[it] }
+
+        assertEquals(7, config.limitFor(PublishingTier.TIER_1).maxConcurrentDrafts)
+        assertNull(config.limitFor(PublishingTier.TIER_2).maxMonthlyPublishes)
+        assertNull(config.limitFor(PublishingTier.TIER_4).maxConcurrentDrafts)
+    }
+}
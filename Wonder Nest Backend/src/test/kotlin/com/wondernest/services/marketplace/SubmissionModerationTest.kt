@@ -0,0 +1,87 @@
+package com.wondernest.services.marketplace
+
+import com.wondernest.services.moderation.ModerationDecisionRequest
+import kotlinx.coroutines.runBlocking
+import org.junit.jupiter.api.BeforeEach
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.math.BigDecimal
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+import kotlin.test.assertNull
+import kotlin.test.assertTrue
+
+@DisplayName("Submission Moderation Tests")
+class SubmissionModerationTest {
+
+    private lateinit var creatorService: CreatorService
+    private val creatorId = UUID.randomUUID()
+    private val moderatorId = UUID.randomUUID()
+
+    private val draftRequest = PublishContentRequest(
+        title = "Night Sky",
+        description = "Stories about the stars",
+        contentType = ContentType.STORY,
+        ageRange = "5-7",
+        price = BigDecimal("1.99"),
+        licensingModel = LicensingModel.FAMILY,
+        tags = listOf("space"),
+        educationalGoals = listOf("science"),
+        contentData = emptyMap()
+    )
+
+    @BeforeEach
+    fun setup() {
+        creatorService = CreatorService(PublishingLimitsConfig())
+    }
+
+    private fun pendingSubmission(): UUID {
+        val itemId = creatorService.saveDraft(creatorId, draftRequest).itemId!!
+        runBlocking { creatorService.submitForReview(creatorId, itemId) }
+        return itemId
+    }
+
+    @Test
+    @DisplayName("Rejection without a note is refused for note-mandatory categories")
+    fun noteRequiredForSafetyRejection() {
+        val itemId = pendingSubmission()
+
+        val error = assertFailsWith<IllegalArgumentException> {
+            creatorService.moderateSubmission(moderatorId, itemId, ModerationDecisionRequest("reject", "safety", "  "))
+        }
+        assertTrue(error.message!!.contains("safety"))
+
+        // The submission is left untouched and can still be decided
+        val result = creatorService.moderateSubmission(
+            moderatorId, itemId, ModerationDecisionRequest("reject", "safety", "Page 3 shows a character climbing onto a roof")
+        )
+        assertEquals(PublishStatus.REJECTED, result.status)
+        assertEquals("safety", result.rejection?.category)
+    }
+
+    @Test
+    @DisplayName("Rejection must select a known category")
+    fun categoryRequired() {
+        val itemId = pendingSubmission()
+
+        assertFailsWith<IllegalArgumentException> {
+            creatorService.moderateSubmission(moderatorId, itemId, ModerationDecisionRequest("reject", notes = "Not good"))
+        }
+        assertFailsWith<IllegalArgumentException> {
+            creatorService.moderateSubmission(moderatorId, itemId, ModerationDecisionRequest("reject", "boring", "Not good"))
+        }
+    }
+
+    @Test
+    @DisplayName("Categories without mandatory notes and approvals don't need notes")
+    fun optionalNotes() {
+        val rejected = creatorService.moderateSubmission(moderatorId, pendingSubmission(), ModerationDecisionRequest("reject", "metadata"))
+        assertEquals(PublishStatus.REJECTED, rejected.status)
+        assertNull(rejected.rejection?.notes)
+
+        val approved = creatorService.moderateSubmission(moderatorId, pendingSubmission(), ModerationDecisionRequest("approve"))
+        assertEquals(PublishStatus.APPROVED, approved.status)
+        assertNull(approved.rejection)
+    }
+}
@@ -0,0 +1,109 @@
+package com.wondernest.services.marketplace
+
+import com.wondernest.services.moderation.ModerationDecisionRequest
+import kotlinx.coroutines.runBlocking
+import kotlinx.serialization.json.JsonPrimitive
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.math.BigDecimal
+import java.time.Duration
+import java.time.Instant
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+import kotlin.test.assertTrue
+
+@DisplayName("Submission Withdrawal Tests")
+class SubmissionWithdrawalTest {
+
+    private var now = Instant.parse("2025-10-01T09:00:00Z")
+    private val creatorService = CreatorService(PublishingLimitsConfig(), clock = { now })
+    private val creatorId = UUID.randomUUID()
+    private val moderatorId = UUID.randomUUID()
+
+    private fun request(text: String = "The owl counts the stars") = PublishContentRequest(
+        title = "Night Sky",
+        description = "Counting stars with an owl",
+        contentType = ContentType.STORY,
+        ageRange = "4-6",
+        price = BigDecimal("0.99"),
+        licensingModel = LicensingModel.FAMILY,
+        tags = emptyList(),
+        educationalGoals = emptyList(),
+        contentData = mapOf("text" to text)
+    )
+
+    private fun submitted(): UUID {
+        val itemId = creatorService.saveDraft(creatorId, request()).itemId!!
+        runBlocking { creatorService.submitForReview(creatorId, itemId) }
+        return itemId
+    }
+
+    private fun statusOf(itemId: UUID) = creatorService.getSubmissionForViewer(itemId, creatorId)!!.status
+
+    @Test
+    @DisplayName("A withdrawn submission leaves the moderation queue and its claim")
+    fun withdrawLeavesQueue() {
+        val itemId = submitted()
+        creatorService.claimSubmission(moderatorId, itemId)
+
+        val result = creatorService.withdrawSubmission(creatorId, itemId)
+
+        assertEquals(PublishStatus.WITHDRAWN, result.status)
+        assertEquals(PublishStatus.WITHDRAWN, statusOf(itemId))
+        assertTrue(creatorService.getModerationQueue(moderatorId).items.isEmpty())
+        assertFailsWith<IllegalArgumentException> {
+            creatorService.moderateSubmission(moderatorId, itemId, ModerationDecisionRequest(decision = "approve"))
+        }
+    }
+
+    @Test
+    @DisplayName("Resubmitting queues the submission again with a fresh submission time")
+    fun resubmitRequeues() = runBlocking {
+        val itemId = submitted()
+        creatorService.withdrawSubmission(creatorId, itemId)
+        now = now.plus(Duration.ofDays(2))
+
+        val result = creatorService.resubmitSubmission(creatorId, itemId)
+
+        assertEquals(PublishStatus.PENDING_REVIEW, result.status)
+        val queued = creatorService.getModerationQueue(moderatorId).items.single()
+        assertEquals(itemId.toString(), queued.itemId)
+        assertEquals(now.toString(), queued.submittedAt)
+    }
+
+    @Test
+    @DisplayName("Submissions needing changes can be resubmitted once fixed")
+    fun resubmitAfterChanges() = runBlocking {
+        val itemId = creatorService.saveDraft(creatorId, request("What the hell")).itemId!!
+        creatorService.submitForReview(creatorId, itemId)
+        assertEquals(PublishStatus.PENDING_CHANGES, statusOf(itemId))
+
+        // Still flagged, so it stays with the creator
+        assertEquals(PublishStatus.PENDING_CHANGES, creatorService.resubmitSubmission(creatorId, itemId).status)
+
+        creatorService.patchDraftContent(
+            creatorId, itemId,
+            ContentPatchRequest(1, listOf(JsonPatchOperation("replace", "/text", JsonPrimitive("What the owl saw"))))
+        )
+        assertEquals(PublishStatus.PENDING_REVIEW, creatorService.resubmitSubmission(creatorId, itemId).status)
+    }
+
+    @Test
+    @DisplayName("Only submissions in review can be withdrawn, and only withdrawn ones resubmitted")
+    fun invalidTransitions() {
+        val draftId = creatorService.saveDraft(creatorId, request()).itemId!!
+        assertFailsWith<IllegalArgumentException> { creatorService.withdrawSubmission(creatorId, draftId) }
+        assertFailsWith<IllegalArgumentException> { runBlocking { creatorService.resubmitSubmission(creatorId, draftId) } }
+
+        val itemId = submitted()
+        assertFailsWith<IllegalArgumentException> { runBlocking { creatorService.resubmitSubmission(creatorId, itemId) } }
+
+        creatorService.claimSubmission(moderatorId, itemId)
+        creatorService.moderateSubmission(moderatorId, itemId, ModerationDecisionRequest(decision = "approve"))
+        assertFailsWith<IllegalArgumentException> { creatorService.withdrawSubmission(creatorId, itemId) }
+
+        assertFailsWith<NoSuchElementException> { creatorService.withdrawSubmission(creatorId, UUID.randomUUID()) }
+        assertFailsWith<NoSuchElementException> { creatorService.withdrawSubmission(UUID.randomUUID(), itemId) }
+    }
+}
@@ -0,0 +1,133 @@
+package com.wondernest.services.moderation
+
+import com.wondernest.services.marketplace.ContentType
+import com.wondernest.services.marketplace.CreatorService
+import com.wondernest.services.marketplace.LicensingModel
+import com.wondernest.services.marketplace.PublishContentRequest
+import com.wondernest.services.marketplace.PublishStatus
+import com.wondernest.services.marketplace.PublishingLimitsConfig
+import com.wondernest.utils.ContentModeration
+import kotlinx.coroutines.runBlocking
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.math.BigDecimal
+import java.net.InetAddress
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+import kotlin.test.assertFalse
+import kotlin.test.assertTrue
+
+@DisplayName("Automated Content Scanner Tests")
+class AutomatedContentScannerTest {
+
+    private val scanner = AutomatedContentScanner()
+
+    private fun request(contentData: Map<String, String>) = PublishContentRequest(
+        title = "Garden Friends",
+        description = "Meet the creatures in the garden",
+        contentType = ContentType.STORY,
+        ageRange = "4-6",
+        price = BigDecimal("0.99"),
+        licensingModel = LicensingModel.FAMILY,
+        tags = listOf("nature"),
+        educationalGoals = listOf("science"),
+        contentData = contentData
+    )
+
+    @Test
+    @DisplayName("Clean content passes every check")
+    fun cleanContent() = runBlocking {
+        val result = scanner.scan(mapOf("text" to "The snail says hello to the class", "moral" to "Be kind"))
+
+        assertTrue(result.passed)
+        assertTrue(result.findings.isEmpty())
+    }
+
+    @Test
+    @DisplayName("Banned words and personal information are reported per field")
+    fun flaggedContent() = runBlocking {
+        val result = scanner.scan(
+            mapOf(
+                "text" to "What the HELL is that snail doing",
+                "moral" to "Be kind",
+                "credits" to "Write to grownup@example.com for more"
+            )
+        )
+
+        assertFalse(result.passed)
+        assertEquals(
+            listOf("profanity" to "text", "pii" to "credits"),
+            result.findings.map { it.check to it.field }
+        )
+        // Flagged words aren't echoed back in the findings
+        assertFalse(result.findings.any { it.message.contains("hell", ignoreCase = true) })
+    }
+
+    @Test
+    @DisplayName("Image links are only fetched from public addresses")
+    fun privateAddressesRefused() {
+        listOf("127.0.0.1", "10.1.2.3", "172.16.0.1", "192.168.1.1", "169.254.169.254", "100.64.0.1", "0.0.0.0", "::1", "fd00::1", "fe80::1")
+            .forEach { assertFalse(ImageLinkCheck.isPublicAddress(InetAddress.getByName(it)), it) }
+        listOf("93.184.216.34", "2606:4700::1111")
+            .forEach { assertTrue(ImageLinkCheck.isPublicAddress(InetAddress.getByName(it)), it) }
+
+        // Resolves to loopback, so the default probe reports it without making a request
+        val result = runBlocking { ImageLinkCheck().scan(mapOf("cover" to "http://localhost/cover.png")) }
+        assertEquals(listOf("cover"), result.map { it.field })
+    }
+
+    @Test
+    @DisplayName("Extra banned words and image link checks come from configuration")
+    fun configuredChecks() = runBlocking {
+        val config = ContentScanConfig.fromEnvironment(
+            mapOf("CONTENT_SCAN_CHECKS" to "profanity, image_links", "CONTENT_SCAN_EXTRA_BANNED_WORDS" to "Gloop")
+        )
+        assertEquals(setOf(ContentScanCheck.PROFANITY, ContentScanCheck.IMAGE_LINKS), config.checks)
+        assertEquals(ContentScanConfig.DEFAULT_CHECKS, ContentScanConfig.fromEnvironment(emptyMap()).checks)
+        assertFailsWith<IllegalArgumentException> {
+            ContentScanConfig.fromEnvironment(mapOf("CONTENT_SCAN_CHECKS" to "virus"))
+        }
+
+        val probed = mutableListOf<String>()
+        val custom = AutomatedContentScanner(
+            listOf(
+                BannedWordsCheck(ContentModeration.PROFANITY + config.extraBannedWords),
+                ImageLinkCheck { uri -> probed.add(uri.toString()); !uri.path.endsWith("missing.png") }
+            )
+        )
+        val result = custom.scan(
+            mapOf(
+                "text" to "A gloop of mud",
+                "coverImage" to "https://cdn.example.com/garden.png",
+                "pageImage" to "https://cdn.example.com/missing.png",
+                "link" to "https://example.com/about"
+            )
+        )
+
+        assertEquals(listOf("text", "pageImage"), result.findings.map { it.field })
+        assertEquals(listOf("https://cdn.example.com/garden.png", "https://cdn.example.com/missing.png"), probed)
+    }
+
+    @Test
+    @DisplayName("Submissions advance to human review only when the scan passes")
+    fun submissionRouting() = runBlocking {
+        val creatorService = CreatorService(PublishingLimitsConfig())
+
+        val clean = creatorService.publishContent(UUID.randomUUID(), request(mapOf("text" to "The snail says hello")))
+        assertEquals(PublishStatus.PENDING_REVIEW, clean.status)
+        assertTrue(clean.validation!!.passed)
+
+        val flagged = creatorService.publishContent(UUID.randomUUID(), request(mapOf("text" to "The snail says damn")))
+        assertFalse(flagged.success)
+        assertEquals(PublishStatus.PENDING_CHANGES, flagged.status)
+        assertEquals(listOf("text"), flagged.validation!!.findings.map { it.field })
+
+        // A draft that fails goes back to the creator, who can resubmit once it's fixed
+        val creatorId = UUID.randomUUID()
+        val draftId = creatorService.saveDraft(creatorId, request(mapOf("text" to "What the hell"))).itemId!!
+        val result = creatorService.submitForReview(creatorId, draftId)
+        assertEquals(PublishStatus.PENDING_CHANGES, result.status)
+        assertEquals(PublishStatus.PENDING_CHANGES, creatorService.getSubmissionForViewer(draftId, creatorId)!!.status)
+    }
+}
@@ -0,0 +1,56 @@
+package com.wondernest.services.moderation
+
+import org.jetbrains.exposed.sql.Database
+import org.jetbrains.exposed.sql.transactions.transaction
+import org.junit.jupiter.api.AfterAll
+import org.junit.jupiter.api.BeforeAll
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import org.junit.jupiter.api.TestInstance
+import org.testcontainers.containers.PostgreSQLContainer
+import org.testcontainers.junit.jupiter.Testcontainers
+import java.util.UUID
+import kotlin.test.assertEquals
+
+@Testcontainers(disabledWithoutDocker = true)
+@TestInstance(TestInstance.Lifecycle.PER_CLASS)
+@DisplayName("Database Content Fingerprint Index Tests")
+class DatabaseContentFingerprintIndexTest {
+
+    private val postgres = PostgreSQLContainer("postgres:15-alpine")
+
+    // marketplace.content_fingerprints as V65 creates it
+    private val schema = """
+        CREATE SCHEMA marketplace;
+        CREATE TABLE marketplace.content_fingerprints (
+            item_id UUID PRIMARY KEY, normalized_title TEXT NOT NULL, content_hash VARCHAR(64) NOT NULL,
+            vocabulary JSONB NOT NULL DEFAULT '[]', registered_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP)
+    """.trimIndent()
+
+    @BeforeAll
+    fun connect() {
+        postgres.start()
+        Database.connect(postgres.jdbcUrl, "org.postgresql.Driver", postgres.username, postgres.password)
+        transaction { schema.split(";").filter { it.isNotBlank() }.forEach { exec(it) } }
+    }
+
+    @AfterAll
+    fun close() {
+        postgres.stop()
+    }
+
+    @Test
+    @DisplayName("Content registered before a restart is still matched after it")
+    fun matchesAcrossRestart() {
+        val body = "A little turtle leaves the pond to find the ocean, meeting a heron, a crab and a friendly otter."
+        val original = ContentFingerprint.of(UUID.randomUUID(), "The Brave Little Turtle", body)
+        DuplicateDetector(DuplicateDetectionConfig(), DatabaseContentFingerprintIndex).checkAndRegister(original)
+
+        // A new detector over the same table stands in for a restart
+        val restarted = DuplicateDetector(DuplicateDetectionConfig(), DatabaseContentFingerprintIndex)
+        val copy = ContentFingerprint.of(UUID.randomUUID(), "Brave Turtle", body)
+
+        assertEquals(listOf(original.itemId.toString()), restarted.findDuplicates(copy).map { it.itemId })
+        assertEquals(original, DatabaseContentFingerprintIndex.all().single { it.itemId == original.itemId })
+    }
+}
@@ -0,0 +1,82 @@
+package com.wondernest.services.moderation
+
+import org.jetbrains.exposed.sql.Database
+import org.jetbrains.exposed.sql.transactions.transaction
+import org.junit.jupiter.api.AfterAll
+import org.junit.jupiter.api.BeforeAll
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import org.junit.jupiter.api.TestInstance
+import org.testcontainers.containers.PostgreSQLContainer
+import org.testcontainers.junit.jupiter.Testcontainers
+import java.time.Instant
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertFalse
+import kotlin.test.assertTrue
+
+@Testcontainers(disabledWithoutDocker = true)
+@TestInstance(TestInstance.Lifecycle.PER_CLASS)
+@DisplayName("Database Content Report Store Tests")
+class DatabaseContentReportStoreTest {
+
+    private val postgres = PostgreSQLContainer("postgres:15-alpine")
+
+    // games.content_reports as V63 creates it
+    private val schema = """
+        CREATE SCHEMA games;
+        CREATE TABLE games.content_reports (
+            content_id UUID NOT NULL, reporter_family_id UUID NOT NULL, reason TEXT NOT NULL,
+            reported_at TIMESTAMP WITH TIME ZONE NOT NULL,
+            CONSTRAINT uq_content_reports_content_reporter UNIQUE (content_id, reporter_family_id))
+    """.trimIndent()
+
+    @BeforeAll
+    fun connect() {
+        postgres.start()
+        Database.connect(postgres.jdbcUrl, "org.postgresql.Driver", postgres.username, postgres.password)
+        transaction { schema.split(";").filter { it.isNotBlank() }.forEach { exec(it) } }
+    }
+
+    @AfterAll
+    fun close() {
+        postgres.stop()
+    }
+
+    @Test
+    @DisplayName("Reports outlive the service and each family counts once")
+    fun reportsPersist() {
+        val contentId = UUID.randomUUID()
+        val family = UUID.randomUUID()
+        val config = ContentFlagConfig(reportThreshold = 2)
+
+        val first = ContentFlagService(config, DatabaseContentReportStore)
+        assertEquals(1, first.reportContent(contentId, family, "Scary imagery"))
+        assertEquals(1, first.reportContent(contentId, family, "Still scary"))
+
+        // A new instance stands in for a restart
+        val restarted = ContentFlagService(config, DatabaseContentReportStore)
+        assertFalse(restarted.isExcluded(contentId))
+        assertEquals(2, restarted.reportContent(contentId, UUID.randomUUID(), "Not age appropriate"))
+        assertTrue(restarted.isExcluded(contentId))
+
+        val clean = UUID.randomUUID()
+        assertEquals(listOf(clean), restarted.filterVisible(listOf(contentId, clean)) { it })
+
+        restarted.clear(contentId)
+        assertEquals(0, restarted.activeReportCount(contentId))
+    }
+
+    @Test
+    @DisplayName("Counts cover only the items asked for")
+    fun countsPerItem() {
+        val a = UUID.randomUUID()
+        val b = UUID.randomUUID()
+        val report = { ContentReport(UUID.randomUUID(), "Spam", Instant.parse("2025-09-01T12:00:00Z")) }
+        DatabaseContentReportStore.upsert(a, report())
+        DatabaseContentReportStore.upsert(a, report())
+        DatabaseContentReportStore.upsert(b, report())
+
+        assertEquals(mapOf(a to 2), DatabaseContentReportStore.counts(listOf(a, UUID.randomUUID())))
+    }
+}
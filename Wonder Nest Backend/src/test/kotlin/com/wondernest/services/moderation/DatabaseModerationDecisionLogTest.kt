@@ -0,0 +1,66 @@
+package com.wondernest.services.moderation
+
+import org.jetbrains.exposed.sql.Database
+import org.jetbrains.exposed.sql.transactions.transaction
+import org.junit.jupiter.api.AfterAll
+import org.junit.jupiter.api.BeforeAll
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import org.junit.jupiter.api.TestInstance
+import org.testcontainers.containers.PostgreSQLContainer
+import org.testcontainers.junit.jupiter.Testcontainers
+import java.time.Instant
+import java.util.UUID
+import kotlin.test.assertEquals
+
+@Testcontainers(disabledWithoutDocker = true)
+@TestInstance(TestInstance.Lifecycle.PER_CLASS)
+@DisplayName("Database Moderation Decision Log Tests")
+class DatabaseModerationDecisionLogTest {
+
+    private val postgres = PostgreSQLContainer("postgres:15-alpine")
+
+    // games.moderation_decisions as V61 creates it
+    private val schema = """
+        CREATE SCHEMA games;
+        CREATE TABLE games.moderation_decisions (
+            id UUID PRIMARY KEY, item_id UUID NOT NULL, moderator_id UUID NOT NULL, outcome VARCHAR(20) NOT NULL,
+            submitted_at TIMESTAMP WITH TIME ZONE NOT NULL, decided_at TIMESTAMP WITH TIME ZONE NOT NULL,
+            appealed BOOLEAN NOT NULL DEFAULT FALSE, overturned_on_appeal BOOLEAN NOT NULL DEFAULT FALSE)
+    """.trimIndent()
+
+    @BeforeAll
+    fun connect() {
+        postgres.start()
+        Database.connect(postgres.jdbcUrl, "org.postgresql.Driver", postgres.username, postgres.password)
+        transaction { schema.split(";").filter { it.isNotBlank() }.forEach { exec(it) } }
+    }
+
+    @AfterAll
+    fun close() {
+        postgres.stop()
+    }
+
+    @Test
+    @DisplayName("Decisions read back by the day they were made")
+    fun decisionsRoundTrip() {
+        val moderatorId = UUID.randomUUID()
+        fun decision(decidedAt: String, outcome: ModerationOutcome) = ContentModerationDecision(
+            itemId = UUID.randomUUID(),
+            moderatorId = moderatorId,
+            outcome = outcome,
+            submittedAt = Instant.parse("2025-03-01T08:00:00Z"),
+            decidedAt = Instant.parse(decidedAt)
+        )
+        val inRange = decision("2025-03-02T09:00:00Z", ModerationOutcome.REJECT)
+        DatabaseModerationDecisionLog.record(inRange)
+        DatabaseModerationDecisionLog.record(decision("2025-03-03T00:00:00Z", ModerationOutcome.APPROVE))
+
+        val found = DatabaseModerationDecisionLog.decidedBetween(
+            Instant.parse("2025-03-02T00:00:00Z"),
+            Instant.parse("2025-03-03T00:00:00Z")
+        )
+
+        assertEquals(listOf(inRange), found)
+    }
+}
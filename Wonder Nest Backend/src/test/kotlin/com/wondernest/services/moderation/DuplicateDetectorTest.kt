@@ -0,0 +1,105 @@
+package com.wondernest.services.moderation
+
+import com.wondernest.services.marketplace.ContentType
+import com.wondernest.services.marketplace.CreatorService
+import com.wondernest.services.marketplace.LicensingModel
+import com.wondernest.services.marketplace.PublishContentRequest
+import com.wondernest.services.marketplace.PublishingLimitsConfig
+import kotlinx.coroutines.runBlocking
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.math.BigDecimal
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertTrue
+
+@DisplayName("Duplicate Detection Tests")
+class DuplicateDetectorTest {
+
+    private val original = ContentFingerprint.of(
+        UUID.randomUUID(),
+        "The Brave Little Turtle",
+        "A little turtle leaves the pond to find the ocean, meeting a heron, a crab and a friendly otter along the way."
+    )
+
+    @Test
+    @DisplayName("A near-identical submission is flagged while a distinct one isn't")
+    fun nearIdenticalFlagged() {
+        val detector = DuplicateDetector(DuplicateDetectionConfig())
+        detector.checkAndRegister(original)
+
+        val nearCopy = ContentFingerprint.of(
+            UUID.randomUUID(),
+            "The Brave Little Turtle!",
+            "A little turtle leaves the pond to find the ocean, meeting a heron, a crab and a friendly otter on the way."
+        )
+        val distinct = ContentFingerprint.of(
+            UUID.randomUUID(),
+            "Counting Stars",
+            "Count the stars in the night sky with Luna the owl, from one to ten, before bedtime."
+        )
+
+        val matches = detector.findDuplicates(nearCopy)
+        assertEquals(listOf(original.itemId.toString()), matches.map { it.itemId })
+        assertTrue(matches.single().score >= 0.8)
+        assertTrue(detector.findDuplicates(distinct).isEmpty())
+    }
+
+    @Test
+    @DisplayName("Identical content is flagged even under a different title")
+    fun identicalContentFlagged() {
+        val detector = DuplicateDetector(DuplicateDetectionConfig())
+        detector.checkAndRegister(original)
+
+        val renamed = ContentFingerprint.of(
+            UUID.randomUUID(),
+            "Shelly's Big Journey",
+            "A little turtle leaves the pond to find the ocean, meeting a heron, a crab and a friendly otter along the way."
+        )
+
+        val match = detector.findDuplicates(renamed).single()
+        assertTrue(match.identicalContent)
+        assertEquals(1.0, match.score)
+    }
+
+    @Test
+    @DisplayName("Thresholds are configurable")
+    fun configurableThresholds() {
+        val strict = DuplicateDetector(DuplicateDetectionConfig(titleThreshold = 1.0, vocabularyThreshold = 1.0, scoreThreshold = 1.0))
+        strict.checkAndRegister(original)
+
+        val nearCopy = ContentFingerprint.of(
+            UUID.randomUUID(),
+            "The Brave Little Turtle!",
+            "A little turtle leaves the pond to find the sea, meeting a heron, a crab and a friendly otter along the way."
+        )
+        assertTrue(strict.findDuplicates(nearCopy).isEmpty())
+
+        val config = DuplicateDetectionConfig.fromEnvironment(mapOf("DUPLICATE_SCORE_THRESHOLD" to "0.95"))
+        assertEquals(0.95, config.scoreThreshold)
+    }
+
+    @Test
+    @DisplayName("Creator submissions carry duplicate flags for review")
+    fun submissionsFlagged() = runBlocking {
+        val creatorService = CreatorService(PublishingLimitsConfig(), DuplicateDetector(DuplicateDetectionConfig()))
+        val request = PublishContentRequest(
+            title = "The Brave Little Turtle",
+            description = "A little turtle leaves the pond to find the ocean",
+            contentType = ContentType.STORY,
+            ageRange = "3-5",
+            price = BigDecimal("1.99"),
+            licensingModel = LicensingModel.FAMILY,
+            tags = listOf("animals"),
+            educationalGoals = listOf("reading"),
+            contentData = mapOf("text" to "meeting a heron, a crab and a friendly otter along the way")
+        )
+
+        val first = creatorService.publishContent(UUID.randomUUID(), request)
+        val second = creatorService.publishContent(UUID.randomUUID(), request.copy(title = "The Brave Little Turtle 2"))
+
+        assertTrue(first.possibleDuplicates.isEmpty())
+        assertEquals(listOf(first.itemId.toString()), second.possibleDuplicates.map { it.itemId })
+        assertEquals(second.possibleDuplicates, creatorService.getPossibleDuplicates(second.itemId!!))
+    }
+}
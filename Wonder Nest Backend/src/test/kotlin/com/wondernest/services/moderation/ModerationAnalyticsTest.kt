@@ -0,0 +1,83 @@
+package com.wondernest.services.moderation
+
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.time.Instant
+import java.time.LocalDate
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+import kotlin.test.assertNull
+
+@DisplayName("Moderation Analytics Tests")
+class ModerationAnalyticsTest {
+
+    private val alice = UUID.randomUUID()
+    private val bob = UUID.randomUUID()
+    private val log = InMemoryModerationDecisionLog()
+    private val service = ModerationAnalyticsService(log)
+
+    private fun decide(
+        moderatorId: UUID,
+        outcome: ModerationOutcome,
+        submitted: String,
+        decided: String,
+        overturnedOnAppeal: Boolean? = null
+    ) {
+        log.record(
+            ContentModerationDecision(
+                itemId = UUID.randomUUID(),
+                moderatorId = moderatorId,
+                outcome = outcome,
+                submittedAt = Instant.parse(submitted),
+                decidedAt = Instant.parse(decided),
+                appealed = overturnedOnAppeal != null,
+                overturnedOnAppeal = overturnedOnAppeal == true
+            )
+        )
+    }
+
+    @Test
+    @DisplayName("Throughput, time-to-decision and ratios are computed per moderator")
+    fun computesWorkload() {
+        decide(alice, ModerationOutcome.APPROVE, "2025-03-01T10:00:00Z", "2025-03-01T10:30:00Z")
+        decide(alice, ModerationOutcome.APPROVE, "2025-03-02T09:00:00Z", "2025-03-02T10:00:00Z")
+        decide(alice, ModerationOutcome.REJECT, "2025-03-03T08:00:00Z", "2025-03-03T09:30:00Z", overturnedOnAppeal = true)
+        decide(alice, ModerationOutcome.REJECT, "2025-03-04T08:00:00Z", "2025-03-04T08:20:00Z")
+        decide(bob, ModerationOutcome.APPROVE, "2025-03-05T12:00:00Z", "2025-03-05T14:00:00Z")
+        // Outside the range
+        decide(bob, ModerationOutcome.APPROVE, "2025-02-27T12:00:00Z", "2025-02-28T12:00:00Z")
+
+        val report = service.workload(LocalDate.parse("2025-03-01"), LocalDate.parse("2025-03-10"))
+        assertEquals(10, report.days)
+        assertEquals(5, report.totalDecisions)
+
+        val aliceStats = report.moderators.first()
+        assertEquals(alice.toString(), aliceStats.moderatorId)
+        assertEquals(4, aliceStats.decisions)
+        assertEquals(0.4, aliceStats.decisionsPerDay)
+        // (30 + 60 + 90 + 20) / 4
+        assertEquals(50.0, aliceStats.averageTimeToDecisionMinutes)
+        assertEquals(0.5, aliceStats.approvalRatio)
+        assertEquals(0.5, aliceStats.rejectionRatio)
+        assertEquals(1, aliceStats.appealed)
+        assertEquals(1.0, aliceStats.overturnRate)
+
+        val bobStats = report.moderators.last()
+        assertEquals(1, bobStats.decisions)
+        assertEquals(120.0, bobStats.averageTimeToDecisionMinutes)
+        assertEquals(1.0, bobStats.approvalRatio)
+        assertNull(bobStats.overturnRate)
+    }
+
+    @Test
+    @DisplayName("Invalid date ranges are rejected")
+    fun rejectsInvalidRanges() {
+        assertFailsWith<IllegalArgumentException> {
+            service.workload(LocalDate.parse("2025-03-10"), LocalDate.parse("2025-03-01"))
+        }
+        assertFailsWith<IllegalArgumentException> {
+            service.workload(LocalDate.parse("2023-01-01"), LocalDate.parse("2025-01-01"))
+        }
+    }
+}
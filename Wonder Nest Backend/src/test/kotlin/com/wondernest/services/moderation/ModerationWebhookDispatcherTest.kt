@@ -0,0 +1,177 @@
+package com.wondernest.services.moderation
+
+import com.sun.net.httpserver.HttpServer
+import kotlinx.coroutines.runBlocking
+import kotlinx.serialization.json.Json
+import kotlinx.serialization.json.jsonObject
+import kotlinx.serialization.json.jsonPrimitive
+import org.junit.jupiter.api.AfterEach
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.net.InetSocketAddress
+import java.net.URI
+import java.time.Instant
+import java.util.UUID
+import java.util.concurrent.CopyOnWriteArrayList
+import javax.crypto.Mac
+import javax.crypto.spec.SecretKeySpec
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+import kotlin.test.assertFalse
+import kotlin.test.assertTrue
+
+@DisplayName("Moderation Webhook Tests")
+class ModerationWebhookDispatcherTest {
+
+    private class Received(val headers: Map<String, String?>, val body: String)
+
+    private val received = CopyOnWriteArrayList<Received>()
+    private val statuses = ArrayDeque<Int>()
+    private val server = HttpServer.create(InetSocketAddress("127.0.0.1", 0), 0).apply {
+        createContext("/hooks") { exchange ->
+            val body = exchange.requestBody.use { it.readBytes().decodeToString() }
+            received.add(
+                Received(
+                    listOf(
+                        ModerationWebhookDispatcher.EVENT_HEADER,
+                        ModerationWebhookDispatcher.TIMESTAMP_HEADER,
+                        ModerationWebhookDispatcher.SIGNATURE_HEADER,
+                        "Content-Type"
+                    ).associateWith { exchange.requestHeaders.getFirst(it) },
+                    body
+                )
+            )
+            val status = synchronized(statuses) { statuses.removeFirstOrNull() } ?: 200
+            exchange.sendResponseHeaders(status, -1)
+            exchange.close()
+        }
+        start()
+    }
+    private val endpoint = URI("http://127.0.0.1:${server.address.port}/hooks")
+
+    private val secret = "webhook-test-secret"
+    private val now = Instant.parse("2026-05-01T12:00:00Z")
+    private val deadLetters = InMemoryModerationWebhookDeadLetterLog()
+    private val sleeps = CopyOnWriteArrayList<Long>()
+
+    private fun dispatcher(maxRetries: Int = 3) = ModerationWebhookDispatcher(
+        ModerationWebhookConfig(listOf(endpoint), secret, maxRetries = maxRetries, baseDelayMillis = 100),
+        deadLetters,
+        sleep = { sleeps.add(it) },
+        clock = { now }
+    )
+
+    private val event = ModerationWebhookEvent.of(
+        type = ModerationEventType.REJECTED,
+        itemId = UUID.randomUUID(),
+        title = "Safari Stickers",
+        occurredAt = now,
+        moderatorId = UUID.randomUUID(),
+        reasonCategory = RejectionReasonCategory.QUALITY
+    )
+
+    @AfterEach
+    fun stopServer() {
+        server.stop(0)
+    }
+
+    private fun hmac(payload: String): String {
+        val mac = Mac.getInstance("HmacSHA256")
+        mac.init(SecretKeySpec(secret.toByteArray(), "HmacSHA256"))
+        return mac.doFinal(payload.toByteArray()).joinToString("") { "%02x".format(it) }
+    }
+
+    @Test
+    @DisplayName("Events are posted as signed JSON")
+    fun signedPayload() = runBlocking {
+        assertTrue(dispatcher().deliver(endpoint, event))
+
+        val request = received.single()
+        val timestamp = request.headers.getValue(ModerationWebhookDispatcher.TIMESTAMP_HEADER)!!
+        assertEquals(now.epochSecond.toString(), timestamp)
+        assertEquals("submission.rejected", request.headers[ModerationWebhookDispatcher.EVENT_HEADER])
+        assertEquals(
+            "sha256=${hmac("$timestamp.${request.body}")}",
+            request.headers[ModerationWebhookDispatcher.SIGNATURE_HEADER]
+        )
+        assertTrue(request.headers["Content-Type"]!!.startsWith("application/json"))
+
+        val payload = Json.parseToJsonElement(request.body).jsonObject
+        assertEquals(
+            setOf("id", "type", "itemId", "title", "occurredAt", "moderatorId", "reasonCategory"),
+            payload.keys
+        )
+        assertEquals(event.itemId, payload.getValue("itemId").jsonPrimitive.content)
+        assertEquals("quality", payload.getValue("reasonCategory").jsonPrimitive.content)
+        assertEquals("2026-05-01T12:00:00Z", payload.getValue("occurredAt").jsonPrimitive.content)
+    }
+
+    @Test
+    @DisplayName("Server errors are retried with exponential backoff")
+    fun retriesServerErrors() = runBlocking {
+        statuses.addAll(listOf(503, 500))
+
+        assertTrue(dispatcher().deliver(endpoint, event))
+        assertEquals(3, received.size)
+        assertEquals(listOf(100L, 200L), sleeps.toList())
+        assertTrue(deadLetters.pending(10).isEmpty())
+    }
+
+    @Test
+    @DisplayName("Deliveries that exhaust their retries go to the dead-letter log")
+    fun deadLettersAfterRetries() = runBlocking {
+        statuses.addAll(List(5) { 502 })
+
+        assertFalse(dispatcher(maxRetries = 2).deliver(endpoint, event))
+        assertEquals(3, received.size)
+        val failed = deadLetters.pending(10).single()
+        assertEquals(3, failed.attempts)
+        assertEquals("HTTP 502", failed.lastError)
+        assertEquals(event, failed.event)
+    }
+
+    @Test
+    @DisplayName("Replayed dead letters leave the log once they go through")
+    fun replaysDeadLetters() = runBlocking {
+        statuses.addAll(listOf(410, 503, 200))
+        val dispatcher = dispatcher(maxRetries = 0)
+        assertFalse(dispatcher.deliver(endpoint, event))
+
+        assertEquals(0, dispatcher.replayDeadLetters())
+        assertEquals(1, deadLetters.pending(10).size)
+
+        assertEquals(1, dispatcher.replayDeadLetters())
+        assertTrue(deadLetters.pending(10).isEmpty())
+        assertEquals(3, received.size)
+        assertTrue(received.all { Json.parseToJsonElement(it.body).jsonObject["id"]?.jsonPrimitive?.content == event.id })
+    }
+
+    @Test
+    @DisplayName("Client errors are not retried")
+    fun clientErrorsFailFast() = runBlocking {
+        statuses.add(410)
+
+        assertFalse(dispatcher().deliver(endpoint, event))
+        assertEquals(1, received.size)
+        assertEquals(1, deadLetters.pending(10).single().attempts)
+    }
+
+    @Test
+    @DisplayName("Webhook URLs need a signing secret")
+    fun configRequiresSecret() {
+        val config = ModerationWebhookConfig.fromEnvironment(
+            mapOf(
+                "MODERATION_WEBHOOK_URLS" to "https://review.example.com/hook, http://localhost:9000/hook",
+                "MODERATION_WEBHOOK_SECRET" to secret
+            )
+        )
+        assertEquals(2, config.endpoints.size)
+        assertTrue(ModerationWebhookConfig.fromEnvironment(emptyMap()).endpoints.isEmpty())
+        assertFailsWith<IllegalArgumentException> {
+            ModerationWebhookConfig.fromEnvironment(mapOf("MODERATION_WEBHOOK_URLS" to "https://review.example.com/hook"))
+        }
+        assertFailsWith<IllegalArgumentException> {
+            ModerationWebhookConfig.parseEndpoint("ftp://review.example.com/hook")
+        }
+    }
+}
@@ -0,0 +1,106 @@
+package com.wondernest.services.moderation
+
+import com.wondernest.services.games.GameDataBatchItem
+import com.wondernest.services.games.GameDataBatchStore
+import com.wondernest.services.games.GameDataOperationResult
+import com.wondernest.services.games.GameDataService
+import io.mockk.every
+import io.mockk.mockk
+import io.mockk.verify
+import kotlinx.datetime.Instant
+import kotlinx.serialization.json.Json
+import kotlinx.serialization.json.jsonObject
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+import kotlin.test.assertNotNull
+import kotlin.test.assertNull
+
+@DisplayName("PII Review Queue Tests")
+class PiiReviewServiceTest {
+
+    private val now = Instant.parse("2025-09-01T12:00:00Z")
+    private val childId = UUID.randomUUID()
+    private val reviewerId = UUID.randomUUID()
+    private val dataValue = Json.parseToJsonElement("""{"caption": "Come over to 42 Maple Avenue"}""").jsonObject
+
+    // Mirrors the database store: an entry is resolved once, and a failed apply leaves it pending
+    private class FakeQueue : PiiReviewQueueStore {
+        val entries = linkedMapOf<UUID, Pair<HeldChildData, PiiReviewStatus>>()
+
+        override fun enqueue(item: HeldChildData): UUID =
+            UUID.randomUUID().also { entries[it] = item to PiiReviewStatus.PENDING }
+
+        override fun pending(limit: Int): List<PendingPiiReview> =
+            entries.filterValues { it.second == PiiReviewStatus.PENDING }
+                .map { (id, entry) -> PendingPiiReview(id, entry.first) }
+                .take(limit)
+
+        override fun resolve(
+            id: UUID,
+            status: PiiReviewStatus,
+            reviewerId: UUID,
+            now: Instant,
+            apply: (HeldChildData) -> Unit
+        ): HeldChildData? {
+            val (held, current) = entries[id] ?: return null
+            if (current != PiiReviewStatus.PENDING) return null
+            apply(held)
+            entries[id] = held to status
+            return held
+        }
+    }
+
+    private val queue = FakeQueue()
+    private val simpleStore = mockk<GameDataBatchStore>(relaxed = true)
+    private val gameDataService = mockk<GameDataService>()
+    private val service = PiiReviewService(queue, simpleStore, gameDataService) { now }
+
+    private fun hold(target: HeldDataTarget) =
+        queue.enqueue(HeldChildData(childId, "sticker_book", "project_1", dataValue, listOf("$.caption"), now, target))
+
+    @Test
+    @DisplayName("Releasing a held simple save writes it once and takes it off the queue")
+    fun releaseSimpleSave() {
+        val id = hold(HeldDataTarget.SIMPLE)
+        assertEquals(listOf(id), service.pending().map { it.id })
+
+        assertNotNull(service.release(id, reviewerId))
+        assertNull(service.release(id, reviewerId))
+
+        verify(exactly = 1) {
+            simpleStore.saveAll(childId, "sticker_book", listOf(GameDataBatchItem("sticker_book", "project_1", dataValue)), now)
+        }
+        assertEquals(emptyList<PendingPiiReview>(), service.pending())
+    }
+
+    @Test
+    @DisplayName("Game instance saves are released through the game data service and stay pending if it refuses")
+    fun releaseGameInstanceSave() {
+        val id = hold(HeldDataTarget.GAME_INSTANCE)
+        every { gameDataService.saveReviewedGameData(childId, "sticker_book", "project_1", dataValue) } returns
+            GameDataOperationResult.disabled("sticker_book")
+
+        assertFailsWith<IllegalStateException> { service.release(id, reviewerId) }
+        assertEquals(listOf(id), service.pending().map { it.id })
+
+        every { gameDataService.saveReviewedGameData(childId, "sticker_book", "project_1", dataValue) } returns
+            GameDataOperationResult.success("saved", null)
+        assertNotNull(service.release(id, reviewerId))
+        assertEquals(PiiReviewStatus.RELEASED, queue.entries[id]?.second)
+    }
+
+    @Test
+    @DisplayName("Discarded saves are never written")
+    fun discardDropsData() {
+        val id = hold(HeldDataTarget.SIMPLE)
+
+        assertNotNull(service.discard(id, reviewerId))
+
+        assertNull(service.release(id, reviewerId))
+        verify(exactly = 0) { simpleStore.saveAll(any(), any(), any(), any()) }
+        assertEquals(PiiReviewStatus.DISCARDED, queue.entries[id]?.second)
+    }
+}
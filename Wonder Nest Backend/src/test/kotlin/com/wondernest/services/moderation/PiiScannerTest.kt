@@ -0,0 +1,115 @@
+package com.wondernest.services.moderation
+
+import io.mockk.every
+import io.mockk.mockk
+import io.mockk.verify
+import kotlinx.datetime.Instant
+import kotlinx.serialization.json.Json
+import kotlinx.serialization.json.JsonElement
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertIs
+import kotlin.test.assertTrue
+
+@DisplayName("Child Data PII Scanner Tests")
+class PiiScannerTest {
+
+    private val scanner = PiiScanner(PiiScannerConfig())
+
+    private val problematicData: JsonElement = Json.parseToJsonElement(
+        """
+        {
+          "problematic_data": {
+            "home_address": "123 Main Street",
+            "phone_number": "555-123-4567",
+            "chat_messages": ["hi", "where do you live?"],
+            "notes": "email me at sam.parent@example.com",
+            "pages": [{ "caption": "Come over to 42 Maple Avenue", "stickers": 3 }],
+            "friend": { "name": "Emma Johnson" },
+            "backup": "call +1 (555) 010-0199 later"
+          }
+        }
+        """
+    )
+
+    @Test
+    @DisplayName("Problematic data is flagged by key and by value")
+    fun flagsProblematicData() {
+        val findings = scanner.scan(problematicData).associate { it.path to it.category }
+
+        assertEquals(
+            mapOf(
+                "$.problematic_data.home_address" to PiiCategory.SUSPICIOUS_KEY,
+                "$.problematic_data.phone_number" to PiiCategory.SUSPICIOUS_KEY,
+                "$.problematic_data.chat_messages" to PiiCategory.SUSPICIOUS_KEY,
+                "$.problematic_data.notes" to PiiCategory.EMAIL,
+                "$.problematic_data.pages[0].caption" to PiiCategory.STREET_ADDRESS,
+                "$.problematic_data.friend.name" to PiiCategory.FULL_NAME,
+                "$.problematic_data.backup" to PiiCategory.PHONE_NUMBER
+            ),
+            findings
+        )
+    }
+
+    @Test
+    @DisplayName("Ordinary game data is not flagged")
+    fun ordinaryDataClean() {
+        val projectData = Json.parseToJsonElement(
+            """
+            {
+              "name": "My Zoo Trip",
+              "projectId": "550e8400-e29b-41d4-a716-446655440000",
+              "savedAt": "2025-09-01 12:30:00",
+              "score": 1234567890,
+              "pages": [{ "caption": "The lion was 3 metres long", "stickers": ["lion", "zebra"] }],
+              "character": { "name": "Captain" }
+            }
+            """
+        )
+
+        assertTrue(scanner.scan(projectData).isEmpty())
+    }
+
+    @Test
+    @DisplayName("Extra keys and the PII action come from the environment")
+    fun configFromEnvironment() {
+        val config = PiiScannerConfig.fromEnvironment(
+            mapOf("GAME_DATA_PII_ACTION" to "queue", "GAME_DATA_PII_EXTRA_KEYS" to "Pet-Name, ,locker_code")
+        )
+
+        assertEquals(PiiAction.QUEUE, config.action)
+        assertTrue("petname" in config.suspiciousKeys && "lockercode" in config.suspiciousKeys)
+        assertEquals(PiiAction.REJECT, PiiScannerConfig.fromEnvironment(mapOf("GAME_DATA_PII_ACTION" to "ignore")).action)
+    }
+
+    @Test
+    @DisplayName("Flagged saves are rejected or held for review depending on config")
+    fun guardActions() {
+        val childId = UUID.randomUUID()
+        val reviewId = UUID.randomUUID()
+        val queue = mockk<PiiReviewQueueStore>()
+        every { queue.enqueue(any()) } returns reviewId
+        val now = { Instant.parse("2025-09-01T12:00:00Z") }
+
+        val rejected = ChildDataPiiGuard(PiiScannerConfig(action = PiiAction.REJECT), queue, now)
+            .screen(childId, "sticker_book", "project_1", problematicData, HeldDataTarget.SIMPLE)
+        assertIs<PiiScreening.Rejected>(rejected)
+        verify(exactly = 0) { queue.enqueue(any()) }
+
+        val held = ChildDataPiiGuard(PiiScannerConfig(action = PiiAction.QUEUE), queue, now)
+            .screen(childId, "sticker_book", "project_1", problematicData, HeldDataTarget.SIMPLE)
+        assertEquals(reviewId, assertIs<PiiScreening.Held>(held).reviewId)
+        verify(exactly = 1) {
+            queue.enqueue(match {
+                it.childId == childId && it.target == HeldDataTarget.SIMPLE &&
+                    "$.problematic_data.home_address" in it.flaggedPaths
+            })
+        }
+
+        val clean = ChildDataPiiGuard(PiiScannerConfig(action = PiiAction.QUEUE), queue, now)
+            .screen(childId, "sticker_book", "project_1", Json.parseToJsonElement("""{"stickers": 4}"""), HeldDataTarget.SIMPLE)
+        assertEquals(PiiScreening.Clean, clean)
+    }
+}
@@ -0,0 +1,62 @@
+package com.wondernest.services.resilience
+
+import io.micrometer.core.instrument.simple.SimpleMeterRegistry
+import kotlinx.coroutines.runBlocking
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+
+@DisplayName("Idempotency Key Tests")
+class IdempotencyServiceTest {
+
+    private val scope = "game-data:child-1"
+    private val requestBody = """{"gameType":"sticker_book","dataKey":"project_1","dataValue":{"stickers":3}}"""
+
+    private var writes = 0
+    private val write: suspend () -> Pair<Int, String> = {
+        writes++
+        200 to """{"success":true,"message":"Game data saved successfully","write":$writes}"""
+    }
+
+    private val service = IdempotencyService(
+        InMemoryIdempotencyStore(),
+        RedisGuard(RedisGuardConfig(), SimpleMeterRegistry())
+    )
+
+    @Test
+    @DisplayName("A retried save with the same key replays the identical response without writing again")
+    fun sameKeyReplays() = runBlocking {
+        val first = service.execute(scope, "key-1", requestBody, write)
+        val retry = service.execute(scope, "key-1", requestBody, write)
+
+        assertEquals(1, writes)
+        assertEquals(first, retry)
+        assertEquals(200, retry.status)
+    }
+
+    @Test
+    @DisplayName("A different key performs a fresh write")
+    fun differentKeyWrites() = runBlocking {
+        val first = service.execute(scope, "key-1", requestBody, write)
+        val second = service.execute(scope, "key-2", requestBody, write)
+
+        assertEquals(2, writes)
+        assertEquals(false, first.body == second.body)
+    }
+
+    @Test
+    @DisplayName("Reusing a key for a different body is rejected and failed writes aren't recorded")
+    fun reusedKeyRejected() = runBlocking {
+        service.execute(scope, "key-1", requestBody, write)
+
+        assertFailsWith<IdempotencyKeyReusedException> {
+            service.execute(scope, "key-1", requestBody.replace("3", "4"), write)
+        }
+
+        val failing: suspend () -> Pair<Int, String> = { writes++; 500 to "boom" }
+        service.execute(scope, "key-3", requestBody, failing)
+        service.execute(scope, "key-3", requestBody, write)
+        assertEquals(3, writes)
+    }
+}
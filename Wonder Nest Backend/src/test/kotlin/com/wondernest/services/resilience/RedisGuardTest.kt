@@ -0,0 +1,122 @@
+package com.wondernest.services.resilience
+
+import io.micrometer.core.instrument.simple.SimpleMeterRegistry
+import io.lettuce.core.RedisCommandTimeoutException
+import kotlinx.coroutines.delay
+import kotlinx.coroutines.runBlocking
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.net.ConnectException
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+import kotlin.test.assertFalse
+import kotlin.test.assertTrue
+
+@DisplayName("Redis Degradation Tests")
+class RedisGuardTest {
+
+    private var now = 0L
+    private val registry = SimpleMeterRegistry()
+
+    private fun guard(config: RedisGuardConfig = RedisGuardConfig(failureThreshold = 3, openDurationMillis = 1_000)) =
+        RedisGuard(config, registry) { now }
+
+    private val redisDown: suspend () -> Boolean = { throw ConnectException("Connection refused") }
+
+    @Test
+    @DisplayName("Cache lookups fall back when Redis is down")
+    fun cacheFailsOpen() = runBlocking {
+        val result = guard().execute(RedisFeature.CACHE, fallback = { "miss" }) { throw ConnectException("down") }
+        assertEquals("miss", result)
+    }
+
+    @Test
+    @DisplayName("Token blocklist rejects when configured to fail closed")
+    fun blocklistFailsClosedWhenConfigured() {
+        val config = RedisGuardConfig.fromEnvironment(mapOf("REDIS_FAILURE_MODE_TOKEN_BLOCKLIST" to "closed"))
+        val redisGuard = guard(config)
+
+        assertFailsWith<RedisUnavailableException> {
+            runBlocking { redisGuard.execute(RedisFeature.TOKEN_BLOCKLIST, fallback = { false }, block = redisDown) }
+        }
+        // Other features keep their defaults
+        assertEquals(RedisFailureMode.FAIL_OPEN, config.modeFor(RedisFeature.RATE_LIMIT))
+    }
+
+    @Test
+    @DisplayName("Token blocklist fails open by default so auth keeps working")
+    fun blocklistFailsOpenByDefault() = runBlocking {
+        val revoked = guard().execute(RedisFeature.TOKEN_BLOCKLIST, fallback = { false }, block = redisDown)
+        assertFalse(revoked)
+    }
+
+    @Test
+    @DisplayName("Repeated failures open the circuit and skip Redis")
+    fun circuitOpensAfterThreshold() = runBlocking {
+        val redisGuard = guard()
+        var attempts = 0
+        val failing: suspend () -> Boolean = {
+            attempts++
+            throw ConnectException("down")
+        }
+
+        repeat(5) { redisGuard.execute(RedisFeature.RATE_LIMIT, fallback = { true }, block = failing) }
+
+        assertEquals(3, attempts)
+        assertTrue(redisGuard.isDegraded())
+        assertEquals(1.0, registry.get("redis.degraded").gauge().value())
+    }
+
+    @Test
+    @DisplayName("Circuit closes after a successful trial call")
+    fun circuitRecovers() = runBlocking {
+        val redisGuard = guard()
+        repeat(3) { redisGuard.execute(RedisFeature.SESSIONS, fallback = { null }, block = redisDown) }
+        assertTrue(redisGuard.isDegraded())
+
+        now += 1_000
+        val value = redisGuard.execute(RedisFeature.SESSIONS, fallback = { false }) { true }
+
+        assertTrue(value)
+        assertFalse(redisGuard.isDegraded())
+    }
+
+    @Test
+    @DisplayName("A command timeout falls back instead of failing the request")
+    fun commandTimeoutIsRecoverable() = runBlocking {
+        val redisGuard = guard(RedisGuardConfig(callTimeoutMillis = 50))
+
+        val stalled = redisGuard.execute(RedisFeature.RATE_LIMIT, fallback = { true }) {
+            delay(10_000)
+            false
+        }
+        val lettuceTimeout = redisGuard.execute(RedisFeature.TOKEN_BLOCKLIST, fallback = { false }) {
+            throw RedisCommandTimeoutException("Command timed out after 2 second(s)")
+        }
+
+        assertTrue(stalled)
+        assertFalse(lettuceTimeout)
+    }
+
+    @Test
+    @DisplayName("A timeout on a fail-closed feature is a RedisUnavailableException")
+    fun commandTimeoutFailsClosed() {
+        val redisGuard = guard(RedisGuardConfig(callTimeoutMillis = 50))
+
+        assertFailsWith<RedisUnavailableException> {
+            runBlocking { redisGuard.execute(RedisFeature.SIGNED_URL_NONCES, fallback = { false }) { delay(10_000); true } }
+        }
+    }
+
+    @Test
+    @DisplayName("Connection timeouts and reconnect are read from the environment")
+    fun connectionConfig() {
+        val config = RedisConnectionConfig.fromEnvironment(
+            mapOf("REDIS_COMMAND_TIMEOUT_MS" to "500", "REDIS_AUTO_RECONNECT" to "false")
+        )
+
+        assertEquals(500, config.commandTimeoutMillis)
+        assertFalse(config.clientOptions().isAutoReconnect)
+        assertEquals(500, RedisGuardConfig.fromEnvironment(mapOf("REDIS_COMMAND_TIMEOUT_MS" to "500")).callTimeoutMillis)
+    }
+}
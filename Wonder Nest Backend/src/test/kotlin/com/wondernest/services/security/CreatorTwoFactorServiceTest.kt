@@ -0,0 +1,139 @@
+package com.wondernest.services.security
+
+import com.wondernest.data.database.table.UserStatus
+import com.wondernest.domain.model.User
+import com.wondernest.domain.repository.FamilyRepository
+import com.wondernest.domain.repository.UserRepository
+import com.wondernest.services.auth.AuthService
+import com.wondernest.services.auth.JwtService
+import com.wondernest.services.auth.LoginRequest
+import com.wondernest.services.auth.TokenPair
+import dev.samstevens.totp.code.DefaultCodeGenerator
+import dev.samstevens.totp.code.HashingAlgorithm
+import io.mockk.coEvery
+import io.mockk.every
+import io.mockk.mockk
+import kotlinx.coroutines.runBlocking
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import org.springframework.security.crypto.bcrypt.BCryptPasswordEncoder
+import java.security.SecureRandom
+import java.time.Instant
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+import kotlin.test.assertFalse
+import kotlin.test.assertNotEquals
+import kotlin.test.assertTrue
+
+@DisplayName("Creator Two-Factor Authentication Tests")
+class CreatorTwoFactorServiceTest {
+
+    private val now = Instant.parse("2025-09-01T12:00:00Z")
+    private val userId = UUID.randomUUID()
+
+    private class InMemoryCreatorTwoFactorStore : CreatorTwoFactorStore {
+        val records = mutableMapOf<UUID, CreatorTwoFactorRecord>()
+        override suspend fun find(userId: UUID) = records[userId]
+        override suspend fun saveEnrollment(record: CreatorTwoFactorRecord) {
+            records[record.userId] = record.copy(enabled = false)
+        }
+        override suspend fun enable(userId: UUID): Boolean {
+            val record = records[userId] ?: return false
+            records[userId] = record.copy(enabled = true)
+            return true
+        }
+        override suspend fun replaceBackupCodes(userId: UUID, expected: List<String>, remaining: List<String>): Boolean {
+            val record = records[userId]?.takeIf { it.backupCodeHashes == expected } ?: return false
+            records[userId] = record.copy(backupCodeHashes = remaining)
+            return true
+        }
+    }
+
+    private val store = InMemoryCreatorTwoFactorStore()
+    private val cipher = SecretCipher(ByteArray(32).also { SecureRandom().nextBytes(it) })
+    private val service = CreatorTwoFactorService(TwoFactorService(clock = { now }), cipher, store)
+
+    private fun currentCode(secret: String) =
+        DefaultCodeGenerator(HashingAlgorithm.SHA1, 6).generate(secret, now.epochSecond / 30)
+
+    private suspend fun enrollAndConfirm(): CreatorTwoFactorEnrollment {
+        val enrollment = service.beginEnrollment(userId, "creator@example.com")
+        assertTrue(service.confirmEnrollment(userId, currentCode(enrollment.secret)))
+        return enrollment
+    }
+
+    @Test
+    @DisplayName("Enrollment stores the secret encrypted and only enables 2FA once a code is confirmed")
+    fun enrollment() = runBlocking {
+        val enrollment = service.beginEnrollment(userId, "creator@example.com")
+
+        assertEquals(10, enrollment.backupCodes.size)
+        assertTrue(enrollment.qrCode.startsWith("otpauth://totp/"))
+        val stored = store.records.getValue(userId)
+        assertNotEquals(enrollment.secret, stored.encryptedSecret)
+        assertEquals(enrollment.secret, cipher.decrypt(stored.encryptedSecret))
+        assertFalse(stored.backupCodeHashes.any { it in enrollment.backupCodes })
+        assertFalse(service.isEnabled(userId))
+
+        assertFalse(service.confirmEnrollment(userId, "000000"))
+        assertTrue(service.confirmEnrollment(userId, currentCode(enrollment.secret)))
+        assertTrue(service.isEnabled(userId))
+        assertFailsWith<IllegalStateException> { service.beginEnrollment(userId, "creator@example.com") }
+        Unit
+    }
+
+    @Test
+    @DisplayName("Each backup code works once")
+    fun backupCodeSingleUse() = runBlocking {
+        val backupCode = enrollAndConfirm().backupCodes.first()
+
+        assertTrue(service.verify(userId, backupCode))
+        assertFalse(service.verify(userId, backupCode))
+        assertEquals(9, store.records.getValue(userId).backupCodeHashes.size)
+    }
+
+    @Test
+    @DisplayName("Login asks for a code, then issues tokens once a valid code is sent")
+    fun loginWithSecondFactor() = runBlocking {
+        val secret = enrollAndConfirm().secret
+        val password = "Correct-Horse-9"
+        val user = User(
+            id = userId,
+            email = "creator@example.com",
+            status = UserStatus.ACTIVE,
+            createdAt = kotlinx.datetime.Instant.parse("2025-09-01T12:00:00Z"),
+            updatedAt = kotlinx.datetime.Instant.parse("2025-09-01T12:00:00Z")
+        )
+        val userRepository = mockk<UserRepository>(relaxed = true)
+        coEvery { userRepository.getUserByEmail(user.email) } returns user
+        coEvery { userRepository.getUserPasswordHash(user.id) } returns BCryptPasswordEncoder(4).encode(password)
+        val jwtService = mockk<JwtService>()
+        every { jwtService.generateToken(any()) } returns TokenPair("access", "refresh", 3600)
+        val authService = AuthService(
+            userRepository, mockk<FamilyRepository>(), jwtService, creatorTwoFactor = service
+        )
+
+        val challenge = authService.login(LoginRequest(user.email, password)).data
+        assertTrue(challenge.requires2FA)
+        assertEquals("", challenge.accessToken)
+
+        assertFailsWith<SecurityException> { authService.login(LoginRequest(user.email, password, "000000")) }
+
+        val signedIn = authService.login(LoginRequest(user.email, password, currentCode(secret))).data
+        assertFalse(signedIn.requires2FA)
+        assertEquals("access", signedIn.accessToken)
+    }
+
+    @Test
+    @DisplayName("The encryption key is required outside development")
+    fun encryptionKeyRequired() {
+        assertFailsWith<IllegalStateException> { SecretCipher.fromEnvironment(mapOf("KTOR_ENV" to "production")) }
+        assertFailsWith<IllegalStateException> { SecretCipher.fromEnvironment(emptyMap()) }
+
+        val key = java.util.Base64.getEncoder().encodeToString(ByteArray(32))
+        val configured = SecretCipher.fromEnvironment(mapOf("TWO_FACTOR_ENCRYPTION_KEY" to key))
+        assertEquals("secret", configured.decrypt(configured.encrypt("secret")))
+        SecretCipher.fromEnvironment(mapOf("KTOR_ENV" to "development"))
+    }
+}
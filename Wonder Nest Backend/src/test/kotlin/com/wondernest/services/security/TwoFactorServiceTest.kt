@@ -0,0 +1,60 @@
+package com.wondernest.services.security
+
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.time.Instant
+import kotlin.test.assertEquals
+import kotlin.test.assertFalse
+import kotlin.test.assertNull
+import kotlin.test.assertTrue
+
+@DisplayName("Two-Factor Service Tests")
+class TwoFactorServiceTest {
+
+    // RFC 6238 SHA-1 test key "12345678901234567890" in Base32
+    private val rfcSecret = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ"
+    private var now = Instant.ofEpochSecond(59)
+    private val service = TwoFactorService(clock = { now })
+
+    @Test
+    @DisplayName("Codes match the RFC 6238 vectors within one time step of drift")
+    fun validatesWithinWindow() {
+        assertTrue(service.validateCode(rfcSecret, "287082"))
+
+        now = Instant.ofEpochSecond(59 + 30)
+        assertTrue(service.validateCode(rfcSecret, "287082"))
+
+        now = Instant.ofEpochSecond(59 + 90)
+        assertFalse(service.validateCode(rfcSecret, "287082"))
+
+        now = Instant.ofEpochSecond(1111111109)
+        assertTrue(service.validateCode(rfcSecret, "081804"))
+        assertFalse(service.validateCode(rfcSecret, "81804"))
+        assertFalse(service.validateCode(rfcSecret, "abcdef"))
+    }
+
+    @Test
+    @DisplayName("Enrollment returns an otpauth URI for the new secret")
+    fun enrollmentUri() {
+        val secret = service.generateSecret()
+        val enrollment = service.enrollment("admin@wondernest.app", secret)
+
+        assertEquals(32, secret.length)
+        assertTrue(enrollment.otpauthUri.startsWith("otpauth://totp/"))
+        assertTrue("secret=$secret" in enrollment.otpauthUri)
+        assertTrue("issuer=WonderNest" in enrollment.otpauthUri)
+    }
+
+    @Test
+    @DisplayName("Backup codes are stored hashed and can each be used once")
+    fun backupCodesSingleUse() {
+        val codes = service.generateBackupCodes()
+        val hashes = codes.map { service.hashBackupCode(it) }
+        assertEquals(TwoFactorService.BACKUP_CODE_COUNT, hashes.toSet().size)
+        assertFalse(hashes.any { it in codes })
+
+        val remaining = service.consumeBackupCode(hashes, codes[3].uppercase())!!
+        assertEquals(hashes.size - 1, remaining.size)
+        assertNull(service.consumeBackupCode(remaining, codes[3]))
+    }
+}
@@ -0,0 +1,48 @@
+package com.wondernest.services.storage
+
+import kotlinx.coroutines.runBlocking
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import org.junit.jupiter.api.io.TempDir
+import java.io.ByteArrayInputStream
+import java.nio.file.Path
+import kotlin.test.assertEquals
+import kotlin.test.assertNotNull
+
+@DisplayName("Byte Range Tests")
+class ByteRangeTest {
+
+    @Test
+    @DisplayName("Closed, open-ended and suffix ranges resolve to inclusive offsets")
+    fun parsesRanges() {
+        assertEquals(ByteRangeResult.Satisfiable(0, 499), ByteRange.parse("bytes=0-499", 1000))
+        assertEquals(ByteRangeResult.Satisfiable(500, 999), ByteRange.parse("bytes=500-", 1000))
+        assertEquals(ByteRangeResult.Satisfiable(900, 999), ByteRange.parse("bytes=-100", 1000))
+        // Ends past the file and oversized suffixes are clamped
+        assertEquals(ByteRangeResult.Satisfiable(900, 999), ByteRange.parse("bytes=900-5000", 1000))
+        assertEquals(ByteRangeResult.Satisfiable(0, 999), ByteRange.parse("bytes=-5000", 1000))
+        assertEquals("bytes 500-999/1000", ByteRangeResult.Satisfiable(500, 999).contentRange(1000))
+    }
+
+    @Test
+    @DisplayName("Ranges past the end are unsatisfiable and malformed headers are ignored")
+    fun rejectsInvalidRanges() {
+        assertEquals(ByteRangeResult.Unsatisfiable, ByteRange.parse("bytes=1000-", 1000))
+        assertEquals(ByteRangeResult.Unsatisfiable, ByteRange.parse("bytes=-0", 1000))
+        assertEquals(ByteRangeResult.Ignored, ByteRange.parse("bytes=500-100", 1000))
+        assertEquals(ByteRangeResult.Ignored, ByteRange.parse("bytes=0-1,5-9", 1000))
+        assertEquals(ByteRangeResult.Ignored, ByteRange.parse("items=0-1", 1000))
+    }
+
+    @Test
+    @DisplayName("The local provider streams only the requested slice")
+    fun localProviderRange(@TempDir dir: Path) = runBlocking {
+        val provider = LocalStorageProvider(basePath = dir.toString())
+        val stored = provider.upload("story.mp3", "audio/mpeg", ByteArrayInputStream("0123456789".toByteArray()))
+
+        val slice = provider.downloadRange(stored.key, 3, 6)
+        assertNotNull(slice)
+        assertEquals(4L, slice.size)
+        assertEquals("3456", slice.stream.use { String(it.readBytes()) })
+    }
+}
@@ -0,0 +1,66 @@
+package com.wondernest.services.storage
+
+import com.wondernest.domain.model.FileCategory
+import com.wondernest.domain.model.UploadedFile
+import kotlinx.datetime.Instant
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertFalse
+import kotlin.test.assertTrue
+
+@DisplayName("Conditional Download Tests")
+class ConditionalGetTest {
+
+    private val uploadedAt = Instant.parse("2026-03-01T10:15:30.250Z")
+
+    private fun file(contentHash: String?) = UploadedFile(
+        id = UUID.randomUUID(),
+        userId = UUID.randomUUID(),
+        fileKey = "uploads/lion.png",
+        originalName = "lion.png",
+        mimeType = "image/png",
+        fileSize = 2048,
+        contentHash = contentHash,
+        storageProvider = "local",
+        category = FileCategory.CONTENT,
+        uploadedAt = uploadedAt
+    )
+
+    @Test
+    @DisplayName("A matching ETag is not modified (304) and a different one is (200)")
+    fun etagMatching() {
+        val etag = file("abc123").etag()
+        assertEquals("\"abc123\"", etag)
+
+        assertTrue(ConditionalGet.isNotModified("\"abc123\"", null, etag, uploadedAt))
+        assertTrue(ConditionalGet.isNotModified("\"zzz\", W/\"abc123\"", null, etag, uploadedAt))
+        assertTrue(ConditionalGet.isNotModified("*", null, etag, uploadedAt))
+        assertFalse(ConditionalGet.isNotModified("\"def456\"", null, etag, uploadedAt))
+        // If-None-Match takes precedence over a date that would match
+        assertFalse(ConditionalGet.isNotModified("\"def456\"", "Sun, 01 Mar 2026 10:15:30 GMT", etag, uploadedAt))
+    }
+
+    @Test
+    @DisplayName("If-Modified-Since compares at second precision")
+    fun modifiedSince() {
+        val etag = file("abc123").etag()
+        val lastModified = ConditionalGet.formatHttpDate(uploadedAt)
+        assertEquals("Sun, 1 Mar 2026 10:15:30 GMT", lastModified)
+
+        assertTrue(ConditionalGet.isNotModified(null, lastModified, etag, uploadedAt))
+        assertFalse(ConditionalGet.isNotModified(null, "Sun, 1 Mar 2026 10:15:29 GMT", etag, uploadedAt))
+        assertFalse(ConditionalGet.isNotModified(null, "yesterday", etag, uploadedAt))
+        assertFalse(ConditionalGet.isNotModified(null, null, etag, uploadedAt))
+    }
+
+    @Test
+    @DisplayName("Files without a content hash get a stable weak ETag")
+    fun weakEtagFallback() {
+        val etag = file(null).etag()
+        assertTrue(etag.startsWith("W/\""))
+        assertEquals(etag, file(null).etag())
+        assertTrue(ConditionalGet.isNotModified(etag, null, etag, uploadedAt))
+    }
+}
@@ -0,0 +1,101 @@
+package com.wondernest.services.storage
+
+import io.micrometer.core.instrument.simple.SimpleMeterRegistry
+import kotlinx.coroutines.runBlocking
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.util.UUID
+import java.util.concurrent.ConcurrentHashMap
+import java.util.concurrent.atomic.AtomicInteger
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+import kotlin.test.assertFalse
+import kotlin.test.assertTrue
+
+@DisplayName("File Access Controller Tests")
+class FileAccessControllerTest {
+
+    private val ownerId = UUID.randomUUID()
+    private val viewerId = UUID.randomUUID()
+    private val fileId = UUID.randomUUID()
+
+    private val files = ConcurrentHashMap<UUID, FileAccessInfo>()
+    private val family = UUID.randomUUID()
+    private val families = ConcurrentHashMap(mapOf(ownerId to setOf(family), viewerId to setOf(family)))
+    private val loads = AtomicInteger()
+    private val source = object : FileAccessSource {
+        override suspend fun load(fileId: UUID): FileAccessInfo? {
+            loads.incrementAndGet()
+            return files[fileId]
+        }
+
+        override suspend fun familiesOf(userId: UUID): Set<UUID> = families[userId].orEmpty()
+    }
+    private val registry = SimpleMeterRegistry()
+    private var now = 0L
+
+    private fun controller() = FileAccessController(source, ttlMillis = 30_000, meterRegistry = registry, clock = { now })
+
+    @Test
+    @DisplayName("Visibility change invalidates a cached allow decision")
+    fun visibilityChangeInvalidatesCachedAllow() = runBlocking {
+        val controller = controller()
+        files[fileId] = FileAccessInfo(fileId, ownerId, isPublic = true, deleted = false)
+
+        assertTrue(controller.canViewFile(viewerId, fileId))
+        assertTrue(controller.canViewFile(viewerId, fileId))
+        assertEquals(1, loads.get())
+
+        // Owner makes the file private
+        files[fileId] = files.getValue(fileId).copy(isPublic = false)
+        controller.invalidateFile(fileId)
+
+        assertFalse(controller.canViewFile(viewerId, fileId))
+        assertFailsWith<FileAccessDeniedException> { controller.validateFileAccess(viewerId, fileId) }
+        assertTrue(controller.canViewFile(ownerId, fileId))
+    }
+
+    @Test
+    @DisplayName("Decisions are cached until the TTL passes and hits are counted")
+    fun decisionsCachedWithinTtl() = runBlocking {
+        val controller = controller()
+        files[fileId] = FileAccessInfo(fileId, ownerId, isPublic = false, deleted = false)
+
+        assertTrue(controller.canViewFile(ownerId, fileId))
+        assertTrue(controller.canViewFile(ownerId, fileId))
+        assertEquals(1.0, registry.counter("file.access.cache", "result", "hit").count())
+        assertEquals(1.0, registry.counter("file.access.cache", "result", "miss").count())
+
+        now += 30_001
+        assertTrue(controller.canViewFile(ownerId, fileId))
+        assertEquals(2, loads.get())
+    }
+
+    @Test
+    @DisplayName("Deleted and missing files are never viewable")
+    fun deletedFilesDenied() = runBlocking {
+        val controller = controller()
+        files[fileId] = FileAccessInfo(fileId, ownerId, isPublic = true, deleted = true)
+
+        assertFalse(controller.canViewFile(ownerId, fileId))
+        assertFalse(controller.canViewFile(ownerId, UUID.randomUUID()))
+    }
+
+    @Test
+    @DisplayName("Public files are only visible within the owner's family, and leaving it invalidates the cache")
+    fun publicFilesScopedToFamily() = runBlocking {
+        val controller = controller()
+        val outsiderId = UUID.randomUUID()
+        families[outsiderId] = setOf(UUID.randomUUID())
+        files[fileId] = FileAccessInfo(fileId, ownerId, isPublic = true, deleted = false)
+
+        assertFalse(controller.canViewFile(outsiderId, fileId))
+        assertTrue(controller.canViewFile(viewerId, fileId))
+
+        // The viewer is removed from the family
+        families[viewerId] = emptySet()
+        controller.invalidateUser(viewerId)
+
+        assertFalse(controller.canViewFile(viewerId, fileId))
+    }
+}
@@ -0,0 +1,73 @@
+package com.wondernest.services.storage
+
+import com.wondernest.api.dto.ContentUsageInfo
+import com.wondernest.api.dto.StoryUsageInfo
+import com.wondernest.api.dto.toUsageResponse
+import kotlinx.coroutines.runBlocking
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertFalse
+import kotlin.test.assertTrue
+
+@DisplayName("File Reference Service Tests")
+class FileReferenceServiceTest {
+
+    private val fileId = UUID.randomUUID()
+    private val references = mutableMapOf<UUID, List<FileReference>>()
+    private val service = FileReferenceService { id -> references[id].orEmpty() }
+
+    @Test
+    @DisplayName("A file referenced by several stories reports every story")
+    fun referencedByMultipleStories() = runBlocking {
+        val firstStory = UUID.randomUUID()
+        val secondStory = UUID.randomUUID()
+        val profile = UUID.randomUUID()
+        references[fileId] = listOf(
+            FileReference(FileReferenceService.STORY, firstStory, "The Brave Fox", 12),
+            FileReference(FileReferenceService.STORY, secondStory, "Moon Garden", 8),
+            // Same story using the image on two pages
+            FileReference(FileReferenceService.STORY, firstStory, "The Brave Fox", 12),
+            FileReference("profile_picture", profile)
+        )
+
+        val summary = service.getComprehensiveReferences(fileId)
+
+        assertTrue(summary.isUsed)
+        assertEquals(3, summary.referenceCount)
+        assertEquals(listOf(firstStory, secondStory), summary.stories.map { it.referenceId })
+        assertEquals(listOf(profile), summary.other.map { it.referenceId })
+    }
+
+    @Test
+    @DisplayName("A file with no references is safe to delete")
+    fun unreferencedFile() = runBlocking {
+        val summary = service.getComprehensiveReferences(fileId)
+
+        assertFalse(summary.isUsed)
+        assertEquals(0, summary.referenceCount)
+
+        val response = summary.toUsageResponse()
+        assertFalse(response.isUsed)
+        assertTrue(response.stories.isEmpty())
+    }
+
+    @Test
+    @DisplayName("Usage response carries story ids and other content references")
+    fun usageResponseMapping() = runBlocking {
+        val storyId = UUID.randomUUID()
+        val otherId = UUID.randomUUID()
+        references[fileId] = listOf(
+            FileReference(FileReferenceService.STORY, storyId),
+            FileReference("game_asset", otherId)
+        )
+
+        val response = service.getComprehensiveReferences(fileId).toUsageResponse()
+
+        assertTrue(response.isUsed)
+        assertEquals(2, response.referenceCount)
+        assertEquals(listOf(StoryUsageInfo(storyId.toString(), "Untitled story", 0)), response.stories)
+        assertEquals(listOf(ContentUsageInfo("game_asset", otherId.toString())), response.otherReferences)
+    }
+}
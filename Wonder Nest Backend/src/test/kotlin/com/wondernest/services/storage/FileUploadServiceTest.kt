@@ -2,6 +2,7 @@ package com.wondernest.services.storage
 
 import com.wondernest.data.database.table.UserRole
 import com.wondernest.domain.model.FileCategory
+import com.wondernest.domain.model.UploadedFile
 import com.wondernest.domain.model.User
 import kotlinx.coroutines.runBlocking
 import kotlinx.datetime.Clock
@@ -66,6 +67,9 @@ class FileUploadServiceTest {
         assertEquals("image/png", result.mimeType)
         assertEquals(FileCategory.CONTENT, result.category)
         assertEquals(testUser.id, result.userId)
+        val expectedHash = java.security.MessageDigest.getInstance("SHA-256").digest(pngHeader)
+            .joinToString("") { "%02x".format(it) }
+        assertEquals(expectedHash, result.contentHash)
     }
     
     @Test
@@ -101,6 +105,28 @@ class FileUploadServiceTest {
         }
     }
     
+    @Test
+    fun `test size limit is enforced on the bytes received, not the reported size`() = runBlocking {
+        // Network streams report nothing available up front, so available() can't be trusted
+        val body = byteArrayOf(0xFF.toByte(), 0xD8.toByte(), 0xFF.toByte()) + ByteArray(11 * 1024 * 1024)
+        val streamed = object : java.io.FilterInputStream(ByteArrayInputStream(body)) {
+            override fun available() = 0
+        }
+        
+        val error = assertFailsWith<FileValidationException> {
+            fileUploadService.uploadFile(
+                user = testUser,
+                fileName = "large.jpg",
+                contentType = "image/jpeg",
+                inputStream = streamed,
+                category = FileCategory.CONTENT
+            )
+        }
+        assertEquals(FileValidationService.FILE_TOO_LARGE, error.code)
+        // Rejected before anything was written to storage
+        assertEquals(0L, java.nio.file.Files.list(tempDir).use { it.count() })
+    }
+    
     @Test
     fun `test file validation service`() {
         val validationService = FileValidationService()
@@ -153,6 +179,12 @@ class FileUploadServiceTest {
         assertNotNull(downloadedContent)
         assertEquals(content, String(downloadedContent))
         
+        // Test streaming download
+        val streamed = storageProvider.downloadStream(uploadResult.key)
+        assertNotNull(streamed)
+        assertEquals(content.length.toLong(), streamed.size)
+        assertEquals(content, streamed.stream.use { String(it.readBytes()) })
+        
         // Test delete
         val deleted = storageProvider.delete(uploadResult.key)
         assertTrue(deleted)
@@ -160,8 +192,31 @@ class FileUploadServiceTest {
         // Verify file is deleted
         val afterDelete = storageProvider.download(uploadResult.key)
         assertEquals(null, afterDelete)
+        assertEquals(null, storageProvider.downloadStream(uploadResult.key))
     }
     
+    @Test
+    fun `test uploaded objects carry lifecycle tags`() = runBlocking {
+        val familyId = UUID.randomUUID()
+        val uploadResult = storageProvider.upload(
+            fileName = "drawing.png",
+            contentType = "image/png",
+            inputStream = ByteArrayInputStream("png".toByteArray()),
+            tags = StorageTags.forUpload(FileCategory.ARTWORK, familyId, lifecycle = StorageLifecycle.TEMP)
+        )
+
+        val tags = assertNotNull(storageProvider.getMetadata(uploadResult.key)).tags
+        assertEquals("artwork", tags[StorageTags.CATEGORY])
+        assertEquals(familyId.toString(), tags[StorageTags.OWNER_FAMILY])
+        assertEquals("temp", tags[StorageTags.LIFECYCLE])
+
+        // Saving the upload promotes the object so lifecycle rules keep it
+        assertTrue(storageProvider.updateTags(uploadResult.key, StorageTags.forUpload(FileCategory.ARTWORK, familyId)))
+        val promoted = assertNotNull(storageProvider.getMetadata(uploadResult.key)).tags
+        assertEquals("permanent", promoted[StorageTags.LIFECYCLE])
+        assertEquals(familyId.toString(), promoted[StorageTags.OWNER_FAMILY])
+    }
+
     @Test
     fun `test file content validation with magic bytes`() {
         val validationService = FileValidationService()
@@ -218,4 +273,61 @@ class FileUploadServiceTest {
         assertEquals("Child artwork", result.metadata["description"])
         assertEquals("drawing,creative", result.metadata["tags"])
     }
-}
\ No newline at end of file
+    
+    @Test
+    fun `test file dto uses the authenticated download url for private files`() {
+        val file = UploadedFile(
+            id = UUID.randomUUID(),
+            userId = testUser.id,
+            fileKey = "content/2025/01/01/photo.jpg",
+            originalName = "photo.jpg",
+            mimeType = "image/jpeg",
+            fileSize = 2048,
+            storageProvider = "local",
+            url = "http://localhost:8080/files/content/2025/01/01/photo.jpg",
+            isPublic = false,
+            category = FileCategory.ARTWORK,
+            metadata = mapOf("width" to "640"),
+            uploadedAt = Clock.System.now()
+        )
+        
+        val dto = file.toDto()
+        assertEquals("/api/v1/files/${file.id}/download", dto.url)
+        assertEquals("artwork", dto.category)
+        assertEquals(2048, dto.fileSize)
+        assertEquals(mapOf("width" to "640"), dto.metadata)
+        assertEquals(file.url, file.copy(isPublic = true).toDto().url)
+    }
+    
+    @Test
+    fun `test png content named as jpg is accepted and stored as png`() {
+        val pngHeader = byteArrayOf(
+            0x89.toByte(), 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A
+        )
+        
+        assertTrue(validationService.validateFile("photo.jpg", "image/jpeg", 1024, FileCategory.ARTWORK).isValid)
+        assertEquals("image/png", validationService.verifyContentType(pngHeader, "image/jpeg", FileCategory.ARTWORK))
+    }
+    
+    @Test
+    fun `test executables labelled as images are rejected`() = runBlocking {
+        val peHeader = byteArrayOf(0x4D, 0x5A, 0x90.toByte(), 0x00, 0x03, 0x00, 0x00, 0x00)
+        val elfHeader = byteArrayOf(0x7F, 0x45, 0x4C, 0x46, 0x02, 0x01, 0x01, 0x00)
+        
+        for (header in listOf(peHeader, elfHeader)) {
+            val error = assertFailsWith<FileValidationException> {
+                fileUploadService.uploadFile(
+                    user = testUser,
+                    fileName = "cute-cat.png",
+                    contentType = "image/png",
+                    inputStream = ByteArrayInputStream(header),
+                    category = FileCategory.ARTWORK
+                )
+            }
+            assertEquals(FileValidationService.INVALID_MIME_TYPE, error.code)
+        }
+        
+        // Content that doesn't match a declared type we can recognise is rejected too
+        assertEquals(null, validationService.verifyContentType("just text".toByteArray(), "image/png"))
+    }
+}
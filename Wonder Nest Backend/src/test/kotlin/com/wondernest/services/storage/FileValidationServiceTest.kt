@@ -0,0 +1,85 @@
+package com.wondernest.services.storage
+
+import com.wondernest.domain.model.FileCategory
+import io.ktor.server.config.*
+import org.junit.jupiter.api.Test
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+import kotlin.test.assertFalse
+import kotlin.test.assertTrue
+
+class FileValidationServiceTest {
+
+    @Test
+    fun `unknown upload category is rejected`() {
+        val validationService = FileValidationService()
+
+        val error = assertFailsWith<UnknownFileCategoryException> {
+            validationService.resolveCategory("game_assett")
+        }
+        assertTrue(error.message!!.contains("game_assett"))
+    }
+
+    @Test
+    fun `valid upload category is accepted`() {
+        val validationService = FileValidationService()
+
+        assertEquals(FileCategory.GAME_ASSET, validationService.resolveCategory("game_asset"))
+        assertEquals(FileCategory.ARTWORK, validationService.resolveCategory("child_artwork"))
+        assertEquals(FileCategory.CONTENT, validationService.resolveCategory(null))
+    }
+
+    @Test
+    fun `category outside the configured allow-list is rejected`() {
+        val config = MapApplicationConfig("storage.categories.allowed" to "artwork,audio")
+        val validationService = FileValidationService(config)
+
+        assertEquals(FileCategory.AUDIO, validationService.resolveCategory("audio"))
+        assertFailsWith<UnknownFileCategoryException> {
+            validationService.resolveCategory("document")
+        }
+    }
+
+    @Test
+    fun `category policy restricts mime types and size`() {
+        val config = MapApplicationConfig(
+            "storage.categories.profile_picture.max-file-size" to "1024"
+        )
+        val validationService = FileValidationService(config)
+
+        val pdfAsAvatar = validationService.validateFile("doc.pdf", "application/pdf", 100, FileCategory.PROFILE_PICTURE)
+        assertFalse(pdfAsAvatar.isValid)
+
+        val largeAvatar = validationService.validateFile("me.png", "image/png", 2048, FileCategory.PROFILE_PICTURE)
+        assertFalse(largeAvatar.isValid)
+
+        val avatar = validationService.validateFile("me.png", "image/png", 512, FileCategory.PROFILE_PICTURE)
+        assertTrue(avatar.isValid)
+    }
+
+    @Test
+    fun `size tiers differ between images audio and documents`() {
+        val validationService = FileValidationService()
+        val sixMegabytes = 6L * 1024 * 1024
+
+        val image = validationService.validateFile("drawing.png", "image/png", sixMegabytes, FileCategory.ARTWORK)
+        assertFalse(image.isValid)
+        assertEquals(FileValidationService.FILE_TOO_LARGE, image.errorCode)
+
+        val audio = validationService.validateFile("song.mp3", "audio/mpeg", sixMegabytes, FileCategory.AUDIO)
+        assertTrue(audio.isValid)
+
+        val document = validationService.validateFile("book.pdf", "application/pdf", 40L * 1024 * 1024, FileCategory.DOCUMENT)
+        assertTrue(document.isValid)
+    }
+
+    @Test
+    fun `disallowed mime type carries the invalid mime type code`() {
+        val validationService = FileValidationService()
+
+        val result = validationService.validateFile("song.mp3", "audio/mpeg", 1024, FileCategory.PROFILE_PICTURE)
+
+        assertFalse(result.isValid)
+        assertEquals(FileValidationService.INVALID_MIME_TYPE, result.errorCode)
+    }
+}
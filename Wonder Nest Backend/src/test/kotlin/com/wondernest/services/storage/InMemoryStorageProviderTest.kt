@@ -0,0 +1,54 @@
+package com.wondernest.services.storage
+
+import kotlinx.coroutines.runBlocking
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.io.ByteArrayInputStream
+import kotlin.test.assertContentEquals
+import kotlin.test.assertEquals
+import kotlin.test.assertFalse
+import kotlin.test.assertNotNull
+import kotlin.test.assertNull
+import kotlin.test.assertTrue
+
+@DisplayName("In-Memory Storage Provider Tests")
+class InMemoryStorageProviderTest {
+
+    private val provider = InMemoryStorageProvider()
+    private val content = "hello, wondernest".toByteArray()
+
+    @Test
+    @DisplayName("Uploaded files round-trip with their metadata")
+    fun roundTrip() = runBlocking {
+        val result = provider.upload(
+            "drawing.png", "image/png", ByteArrayInputStream(content), metadata = mapOf("owner" to "child-1")
+        )
+
+        assertTrue(result.key.endsWith(".png"))
+        assertContentEquals(content, provider.download(result.key))
+        assertContentEquals(content, provider.downloadStream(result.key)?.stream?.use { it.readBytes() })
+        assertContentEquals("wonder".toByteArray(), provider.downloadRange(result.key, 7, 12)?.stream?.readBytes())
+
+        val metadata = assertNotNull(provider.getMetadata(result.key))
+        assertEquals(content.size.toLong(), metadata.size)
+        assertEquals("image/png", metadata.contentType)
+        assertEquals("child-1", metadata.metadata["owner"])
+        assertEquals(listOf(result.key), provider.listFiles(result.key.substringBefore("/")).map { it.key })
+        assertEquals(
+            "memory://storage/${result.key}?expires=60",
+            provider.getPresignedUrl(result.key, expirationSeconds = 60)
+        )
+    }
+
+    @Test
+    @DisplayName("Deleting removes the object and deleting a missing key reports not found")
+    fun deleteMissing() = runBlocking {
+        val key = provider.upload("notes.txt", "text/plain", ByteArrayInputStream(content)).key
+
+        assertTrue(provider.delete(key))
+        assertNull(provider.download(key))
+        assertNull(provider.getPresignedUrl(key))
+        assertFalse(provider.delete(key))
+        assertFalse(provider.delete("missing/key"))
+    }
+}
@@ -0,0 +1,68 @@
+package com.wondernest.services.storage
+
+import kotlinx.coroutines.runBlocking
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import org.junit.jupiter.api.io.TempDir
+import java.io.ByteArrayInputStream
+import java.nio.file.Files
+import java.nio.file.Path
+import kotlin.test.assertContentEquals
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+import kotlin.test.assertFalse
+import kotlin.test.assertNull
+import kotlin.test.assertTrue
+
+@DisplayName("Filesystem Storage Provider Tests")
+class LocalStorageProviderTest {
+
+    @TempDir
+    lateinit var tempDir: Path
+
+    private val content = "hello, wondernest".toByteArray()
+
+    private fun provider() = LocalStorageProvider(tempDir.resolve("storage").toString(), "http://localhost:8080")
+
+    @Test
+    @DisplayName("Files are stored under the root with metadata sidecars and can be deleted")
+    fun uploadDownloadDelete() = runBlocking {
+        val provider = provider()
+        val key = provider.upload("story.txt", "text/plain", ByteArrayInputStream(content), mapOf("owner" to "a")).key
+
+        val stored = tempDir.resolve("storage").resolve(key)
+        assertContentEquals(content, Files.readAllBytes(stored))
+        assertTrue(Files.exists(tempDir.resolve("storage").resolve("$key.metadata")))
+        assertContentEquals(content, provider.download(key))
+        assertEquals("text/plain", provider.getMetadata(key)?.contentType)
+
+        assertTrue(provider.delete(key))
+        assertFalse(Files.exists(stored))
+        assertNull(provider.download(key))
+        assertFalse(provider.delete(key))
+    }
+
+    @Test
+    @DisplayName("Keys that could escape the storage root are rejected")
+    fun traversalRejected() = runBlocking {
+        val provider = provider()
+        Files.writeString(tempDir.resolve("secret.txt"), "secret")
+
+        for (key in listOf("../secret.txt", "a/../../secret.txt", "/etc/passwd", "..\\secret.txt", "./x", "")) {
+            assertFailsWith<IllegalArgumentException>(key) { provider.download(key) }
+            assertFailsWith<IllegalArgumentException>(key) { provider.delete(key) }
+            assertFailsWith<IllegalArgumentException>(key) { provider.exists(key) }
+        }
+        assertTrue(Files.exists(tempDir.resolve("secret.txt")))
+    }
+
+    @Test
+    @DisplayName("The health check fails once the storage root is gone")
+    fun healthCheck() = runBlocking {
+        val provider = provider()
+        assertTrue(provider.healthCheck())
+
+        Files.delete(tempDir.resolve("storage"))
+        assertFalse(provider.healthCheck())
+    }
+}
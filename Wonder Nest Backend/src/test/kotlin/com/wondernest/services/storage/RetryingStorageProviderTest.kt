@@ -0,0 +1,80 @@
+package com.wondernest.services.storage
+
+import io.mockk.coEvery
+import io.mockk.coVerify
+import io.mockk.mockk
+import kotlinx.coroutines.runBlocking
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.io.ByteArrayInputStream
+import java.io.InputStream
+import java.net.SocketTimeoutException
+import kotlin.random.Random
+import kotlin.test.assertContentEquals
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+import kotlin.test.assertNull
+import kotlin.test.assertTrue
+
+@DisplayName("Storage Retry Tests")
+class RetryingStorageProviderTest {
+
+    private val delegate = mockk<StorageProvider>()
+    private val sleeps = mutableListOf<Long>()
+    private val provider = RetryingStorageProvider(
+        delegate,
+        StorageRetryConfig(maxRetries = 3, baseDelayMillis = 100, maxDelayMillis = 1_000),
+        Random(42)
+    ) { sleeps.add(it) }
+
+    @Test
+    @DisplayName("A call that fails twice then succeeds succeeds within the retry budget")
+    fun failsTwiceThenSucceeds() = runBlocking {
+        var calls = 0
+        coEvery { delegate.download("k") } answers {
+            if (++calls <= 2) throw SocketTimeoutException("timed out") else "data".toByteArray()
+        }
+
+        assertContentEquals("data".toByteArray(), provider.download("k"))
+        assertEquals(3, calls)
+        assertEquals(2, sleeps.size)
+        assertTrue(sleeps[0] <= 100 && sleeps[1] <= 200)
+    }
+
+    @Test
+    @DisplayName("Missing objects and non-retryable errors are not retried")
+    fun nonRetryable() = runBlocking {
+        coEvery { delegate.download("missing") } returns null
+        coEvery { delegate.delete("forbidden") } throws StorageException("403 Forbidden")
+
+        assertNull(provider.download("missing"))
+        assertFailsWith<StorageException> { provider.delete("forbidden") }
+        coVerify(exactly = 1) { delegate.download("missing") }
+        coVerify(exactly = 1) { delegate.delete("forbidden") }
+        assertTrue(sleeps.isEmpty())
+    }
+
+    @Test
+    @DisplayName("Retries stop once the budget is spent")
+    fun budgetExhausted() {
+        coEvery { delegate.exists("k") } throws StorageException("503 Slow Down", retryable = true)
+
+        assertFailsWith<StorageException> { runBlocking { provider.exists("k") } }
+        coVerify(exactly = 4) { delegate.exists("k") }
+    }
+
+    @Test
+    @DisplayName("Rewindable uploads resend the whole stream on retry")
+    fun uploadRewinds() = runBlocking {
+        val received = mutableListOf<String>()
+        coEvery { delegate.upload(any(), any(), any(), any(), any()) } answers {
+            received.add(thirdArg<InputStream>().readBytes().decodeToString())
+            if (received.size == 1) throw StorageException("500", retryable = true)
+            StorageResult(key = "1/a.txt", size = 5, contentType = "text/plain")
+        }
+
+        provider.upload("a.txt", "text/plain", ByteArrayInputStream("hello".toByteArray()))
+
+        assertEquals(listOf("hello", "hello"), received)
+    }
+}
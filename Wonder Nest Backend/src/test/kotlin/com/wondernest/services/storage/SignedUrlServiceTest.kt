@@ -0,0 +1,152 @@
+package com.wondernest.services.storage
+
+import com.wondernest.services.resilience.RedisGuard
+import com.wondernest.services.resilience.RedisUnavailableException
+import io.ktor.http.*
+import kotlinx.coroutines.runBlocking
+import kotlinx.datetime.Instant
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.util.UUID
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+import kotlin.test.assertFalse
+import kotlin.test.assertTrue
+
+@DisplayName("Signed URL Tests")
+class SignedUrlServiceTest {
+
+    private var now = Instant.parse("2025-09-01T12:00:00Z")
+    private val service = SignedUrlService(
+        "test-secret-test-secret-test-sec".toByteArray(),
+        SignedUrlConfig(defaultExpirySeconds = 86_400, maxExpirySeconds = 3_600 * 48)
+    ) { now }
+    private val fileId = UUID.randomUUID()
+
+    private suspend fun validate(url: String, id: UUID = fileId): SignedUrlValidation {
+        val params = Url("http://localhost$url").parameters
+        return service.validateSignedUrl(id, params["expires"]?.toLongOrNull(), params["signature"], params["nonce"])
+    }
+
+    @Test
+    @DisplayName("The requested expiry is honoured when validating")
+    fun expiryHonoured() = runBlocking {
+        val url = service.generateSignedUrl(fileId, expiresInSeconds = 300)
+        assertTrue(url.startsWith("/api/v1/files/$fileId/signed-download?"))
+
+        now = Instant.parse("2025-09-01T12:04:59Z")
+        assertEquals(SignedUrlValidation.VALID, validate(url))
+        now = Instant.parse("2025-09-01T12:05:00Z")
+        assertEquals(SignedUrlValidation.EXPIRED, validate(url))
+    }
+
+    @Test
+    @DisplayName("Expired signatures are rejected while the default lasts a day")
+    fun expiredRejected() = runBlocking {
+        val url = service.generateSignedUrl(fileId)
+
+        now = Instant.parse("2025-09-02T11:59:59Z")
+        assertEquals(SignedUrlValidation.VALID, validate(url))
+        now = Instant.parse("2025-09-03T00:00:00Z")
+        assertEquals(SignedUrlValidation.EXPIRED, validate(url))
+    }
+
+    @Test
+    @DisplayName("Tampered links and links for other files are invalid")
+    fun tamperedRejected() = runBlocking {
+        val url = service.generateSignedUrl(fileId, expiresInSeconds = 300)
+
+        assertEquals(SignedUrlValidation.INVALID, validate(url, UUID.randomUUID()))
+        assertEquals(SignedUrlValidation.INVALID, validate(url.replace(Regex("expires=\\d+"), "expires=9999999999")))
+        assertEquals(SignedUrlValidation.INVALID, service.validateSignedUrl(fileId, null, null))
+    }
+
+    @Test
+    @DisplayName("Expiries above the configured maximum are refused")
+    fun maximumEnforced() {
+        assertFailsWith<IllegalArgumentException> {
+            runBlocking { service.generateSignedUrl(fileId, expiresInSeconds = 3_600 * 48 + 1) }
+        }
+        assertFailsWith<IllegalArgumentException> { runBlocking { service.generateSignedUrl(fileId, expiresInSeconds = 0) } }
+    }
+
+    @Test
+    @DisplayName("A single-use link works once and is rejected on the second request")
+    fun singleUse() = runBlocking {
+        val url = service.generateSignedUrl(fileId, expiresInSeconds = 300, singleUse = true)
+
+        assertEquals(SignedUrlValidation.VALID, validate(url))
+        assertEquals(SignedUrlValidation.ALREADY_USED, validate(url))
+
+        // Multi-use stays the default, and dropping the nonce breaks the signature
+        val reusable = service.generateSignedUrl(fileId, expiresInSeconds = 300)
+        assertEquals(SignedUrlValidation.VALID, validate(reusable))
+        assertEquals(SignedUrlValidation.VALID, validate(reusable))
+        assertEquals(SignedUrlValidation.INVALID, validate(url.replace(Regex("&nonce=[^&]+"), "")))
+    }
+
+    @Test
+    @DisplayName("Checking a single-use link without consuming it leaves it usable")
+    fun singleUseDeferredConsume() = runBlocking {
+        val url = service.generateSignedUrl(fileId, expiresInSeconds = 300, singleUse = true)
+        val params = Url("http://localhost$url").parameters
+        val nonce = params["nonce"]!!
+
+        repeat(2) {
+            assertEquals(
+                SignedUrlValidation.VALID,
+                service.validateSignedUrl(fileId, params["expires"]?.toLongOrNull(), params["signature"], nonce, consume = false)
+            )
+        }
+        assertTrue(service.consumeNonce(nonce))
+        assertFalse(service.consumeNonce(nonce))
+        assertEquals(SignedUrlValidation.ALREADY_USED, validate(url))
+    }
+
+    @Test
+    @DisplayName("Single-use links are refused while the nonce store is down")
+    fun singleUseFailsClosed() {
+        val brokenStore = object : SignedUrlNonceStore {
+            override suspend fun register(nonce: String, ttlSeconds: Long) = throw IllegalStateException("down")
+            override suspend fun consume(nonce: String): Boolean = throw IllegalStateException("down")
+        }
+        val service = SignedUrlService(
+            "test-secret-test-secret-test-sec".toByteArray(), nonceStore = brokenStore, redisGuard = RedisGuard()
+        ) { now }
+
+        assertFailsWith<RedisUnavailableException> {
+            runBlocking { service.generateSignedUrl(fileId, expiresInSeconds = 300, singleUse = true) }
+        }
+    }
+
+    @Test
+    @DisplayName("Links use the configured public base URL rather than the request host")
+    fun configuredBaseUrl() = runBlocking {
+        val config = SignedUrlConfig.fromEnvironment(mapOf("PUBLIC_BASE_URL" to "https://api.wondernest.app/"))
+        val service = SignedUrlService("test-secret-test-secret-test-sec".toByteArray(), config) { now }
+
+        val url = service.generateSignedUrl(fileId, 300, requestBaseUrl = "http://localhost:8080")
+        assertTrue(url.startsWith("https://api.wondernest.app/api/v1/files/$fileId/signed-download?"), url)
+
+        // Without a configured base the request's own host is used
+        val fallback = this@SignedUrlServiceTest.service.generateSignedUrl(fileId, 300, requestBaseUrl = "https://staging.wondernest.app")
+        assertTrue(fallback.startsWith("https://staging.wondernest.app/api/v1/files/"), fallback)
+    }
+
+    @Test
+    @DisplayName("A public base URL that isn't absolute fails fast")
+    fun invalidBaseUrl() {
+        listOf("api.wondernest.app", "/relative", "ftp://files.wondernest.app", "https://api.wondernest.app?x=1").forEach {
+            assertFailsWith<IllegalArgumentException>(it) { SignedUrlConfig.fromEnvironment(mapOf("PUBLIC_BASE_URL" to it)) }
+        }
+    }
+
+    @Test
+    @DisplayName("The signing secret is required outside development")
+    fun secretRequired() {
+        assertFailsWith<IllegalStateException> {
+            SignedUrlService.fromEnvironment(InMemorySignedUrlNonceStore(), RedisGuard(), mapOf("KTOR_ENV" to "production"))
+        }
+        SignedUrlService.fromEnvironment(InMemorySignedUrlNonceStore(), RedisGuard(), mapOf("KTOR_ENV" to "development"))
+    }
+}
@@ -0,0 +1,179 @@
+package com.wondernest.services.web.admin
+
+import com.wondernest.data.database.repository.web.AdminAuditEntry
+import com.wondernest.data.database.repository.web.AdminAuditRepository
+import com.wondernest.data.database.repository.web.AdminSessionRepository
+import com.wondernest.data.database.repository.web.AdminUserRepository
+import com.wondernest.domain.web.AdminLoginRequest
+import com.wondernest.domain.web.AdminRole
+import com.wondernest.domain.web.AdminSession
+import com.wondernest.domain.web.AdminUser
+import com.wondernest.domain.web.AllowedHours
+import com.wondernest.domain.web.AllowedHoursWindow
+import com.wondernest.domain.web.AuditLogEntry
+import com.wondernest.domain.web.AuditLogFilter
+import com.wondernest.services.auth.JwtService
+import io.mockk.coEvery
+import io.mockk.mockk
+import kotlinx.coroutines.runBlocking
+import org.junit.jupiter.api.BeforeEach
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import org.springframework.security.crypto.bcrypt.BCryptPasswordEncoder
+import java.time.Instant
+import java.util.*
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+import kotlin.test.assertTrue
+
+@DisplayName("Admin Allowed Hours Tests")
+class AdminAllowedHoursTest {
+
+    private class RecordingAuditRepository : AdminAuditRepository {
+        val entries = mutableListOf<AdminAuditEntry>()
+        override suspend fun record(entry: AdminAuditEntry) {
+            entries.add(entry)
+        }
+        override suspend fun query(filter: AuditLogFilter, limit: Int, offset: Long) = emptyList<AuditLogEntry>()
+    }
+
+    private val password = "correct horse battery"
+    private var now = Instant.parse("2025-09-01T14:00:00Z")
+
+    // Weekdays 09:00-17:00 New York time (EDT, UTC-4, in September)
+    private val officeHours = AllowedHours(
+        timezone = "America/New_York",
+        windows = listOf(
+            AllowedHoursWindow(listOf("MONDAY", "TUESDAY", "WEDNESDAY", "THURSDAY", "FRIDAY"), "09:00", "17:00")
+        )
+    )
+
+    // Friday night shift, crossing into Saturday
+    private val nightShift = AllowedHours(
+        timezone = "UTC",
+        windows = listOf(AllowedHoursWindow(listOf("FRIDAY"), "22:00", "06:00"))
+    )
+
+    private lateinit var adminUserRepository: AdminUserRepository
+    private lateinit var sessionRepository: AdminSessionRepository
+    private lateinit var auditRepository: RecordingAuditRepository
+    private lateinit var service: AdminAuthService
+    private val sessions = mutableMapOf<UUID, AdminSession>()
+
+    @BeforeEach
+    fun setup() {
+        adminUserRepository = mockk(relaxed = true)
+        sessionRepository = mockk(relaxed = true)
+        coEvery { sessionRepository.create(any()) } answers { firstArg<AdminSession>().also { sessions[it.id] = it } }
+        coEvery { sessionRepository.findById(any()) } answers { sessions[firstArg()] }
+        auditRepository = RecordingAuditRepository()
+        service = AdminAuthService(
+            adminUserRepository, sessionRepository, JwtService(), mockk(), auditRepository, clock = { now }
+        )
+    }
+
+    private fun admin(allowedHours: AllowedHours?): AdminUser {
+        val created = Instant.parse("2025-01-01T00:00:00Z")
+        val user = AdminUser(
+            id = UUID.randomUUID(),
+            email = "admin@wondernest.app",
+            passwordHash = BCryptPasswordEncoder(4).encode(password),
+            salt = "",
+            firstName = "Ada",
+            lastName = "Admin",
+            role = AdminRole.CONTENT_MODERATOR,
+            permissions = emptyList(),
+            allowedHours = allowedHours,
+            createdAt = created,
+            updatedAt = created
+        )
+        coEvery { adminUserRepository.findByEmail(user.email) } returns user
+        coEvery { adminUserRepository.findById(user.id) } returns user
+        return user
+    }
+
+    private suspend fun login() =
+        service.authenticateAdmin(AdminLoginRequest("admin@wondernest.app", password), "10.0.0.1")
+
+    @Test
+    @DisplayName("Logins inside the window succeed")
+    fun loginInsideWindow() = runBlocking {
+        admin(officeHours)
+        now = Instant.parse("2025-09-01T14:00:00Z") // Monday 10:00 in New York
+
+        assertTrue(login().accessToken.isNotEmpty())
+        assertTrue(auditRepository.entries.isEmpty())
+    }
+
+    @Test
+    @DisplayName("Logins outside the window are rejected and audited")
+    fun loginOutsideWindow() {
+        val adminUser = admin(officeHours)
+        now = Instant.parse("2025-09-01T23:00:00Z") // Monday 19:00 in New York
+
+        assertFailsWith<AuthenticationException> { runBlocking { login() } }
+
+        val entry = auditRepository.entries.single()
+        assertEquals("auth.login_outside_allowed_hours", entry.action)
+        assertEquals(adminUser.id, entry.adminId)
+    }
+
+    @Test
+    @DisplayName("A window crossing midnight covers the early hours of the next day only")
+    fun windowCrossingMidnight() = runBlocking {
+        admin(nightShift)
+
+        now = Instant.parse("2025-09-05T23:30:00Z") // Friday 23:30
+        assertTrue(login().accessToken.isNotEmpty())
+        now = Instant.parse("2025-09-06T03:00:00Z") // Saturday 03:00
+        assertTrue(login().accessToken.isNotEmpty())
+
+        for (outside in listOf("2025-09-05T21:00:00Z", "2025-09-06T07:00:00Z", "2025-09-06T23:00:00Z")) {
+            now = Instant.parse(outside)
+            assertFailsWith<AuthenticationException>(outside) { login() }
+        }
+    }
+
+    @Test
+    @DisplayName("Token refresh is re-checked against the window")
+    fun refreshOutsideWindow() {
+        admin(officeHours)
+        now = Instant.parse("2025-09-05T14:00:00Z") // Friday 10:00 in New York
+        val refreshToken = runBlocking { login() }.refreshToken
+
+        // The refresh token resolves to the session login created
+        assertTrue(runBlocking { service.refreshAdminToken(refreshToken) }.accessToken.isNotEmpty())
+
+        now = Instant.parse("2025-09-06T14:00:00Z") // Saturday
+        assertFailsWith<AuthenticationException> { runBlocking { service.refreshAdminToken(refreshToken) } }
+        assertEquals("auth.refresh_outside_allowed_hours", auditRepository.entries.single().action)
+    }
+
+    @Test
+    @DisplayName("Refresh tokens without a live session are rejected")
+    fun refreshWithoutSession() = runBlocking {
+        admin(null)
+        val refreshToken = login().refreshToken
+        sessions.clear()
+
+        assertFailsWith<AuthenticationException> { service.refreshAdminToken(refreshToken) }
+        assertFailsWith<AuthenticationException> { service.refreshAdminToken("not-a-token") }
+        Unit
+    }
+
+    @Test
+    @DisplayName("Invalid schedules are rejected on write")
+    fun validation() {
+        officeHours.validate()
+        nightShift.validate()
+
+        val invalid = listOf(
+            officeHours.copy(timezone = "Mars/Olympus"),
+            officeHours.copy(windows = emptyList()),
+            officeHours.copy(windows = listOf(AllowedHoursWindow(listOf("FUNDAY"), "09:00", "17:00"))),
+            officeHours.copy(windows = listOf(AllowedHoursWindow(listOf("MONDAY"), "9am", "17:00"))),
+            officeHours.copy(windows = listOf(AllowedHoursWindow(listOf("MONDAY"), "09:00", "09:00")))
+        )
+        invalid.forEach { assertFailsWith<IllegalArgumentException>(it.toString()) { it.validate() } }
+    }
+}
@@ -0,0 +1,113 @@
+package com.wondernest.services.web.admin
+
+import com.wondernest.data.database.repository.web.AdminAuditEntry
+import com.wondernest.data.database.repository.web.AdminAuditRepository
+import com.wondernest.domain.web.AdminRole
+import com.wondernest.domain.web.AuditLogEntry
+import com.wondernest.domain.web.AuditLogFilter
+import com.wondernest.domain.web.AuditSeverity
+import kotlinx.coroutines.runBlocking
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.time.Instant
+import java.util.*
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+import kotlin.test.assertFalse
+import kotlin.test.assertTrue
+
+@DisplayName("Admin Audit Log Query Tests")
+class AdminAuditServiceTest {
+
+    // Applies filters and ordering the way the SQL query does
+    private class InMemoryAuditRepository(private val rows: List<AuditLogEntry>) : AdminAuditRepository {
+        var lastLimit = 0
+
+        override suspend fun record(entry: AdminAuditEntry) = Unit
+
+        override suspend fun query(filter: AuditLogFilter, limit: Int, offset: Long): List<AuditLogEntry> {
+            lastLimit = limit
+            return rows
+                .filter { filter.adminId == null || it.adminId == filter.adminId }
+                .filter { filter.action == null || it.action == filter.action }
+                .filter { filter.severity == null || it.severity == filter.severity }
+                .filter { row -> filter.from?.let { row.createdAt >= it } ?: true }
+                .filter { row -> filter.to?.let { row.createdAt < it } ?: true }
+                .sortedByDescending { it.createdAt }
+                .drop(offset.toInt())
+                .take(limit)
+        }
+    }
+
+    private val adminId = UUID.randomUUID()
+
+    private fun entry(minute: Long, severity: AuditSeverity, action: String = "content.status_changed") = AuditLogEntry(
+        id = UUID.randomUUID(),
+        adminId = adminId,
+        action = action,
+        resourceType = null,
+        resourceId = null,
+        details = emptyMap(),
+        severity = severity,
+        success = severity != AuditSeverity.ERROR,
+        errorMessage = null,
+        userAgent = null,
+        createdAt = Instant.parse("2025-09-01T12:00:00Z").plusSeconds(minute * 60)
+    )
+
+    private val rows = (1..7L).map { entry(it, AuditSeverity.INFO) } +
+        listOf(entry(8, AuditSeverity.ERROR), entry(9, AuditSeverity.WARNING), entry(10, AuditSeverity.ERROR))
+    private val repository = InMemoryAuditRepository(rows)
+    private val service = AdminAuditService(repository)
+
+    @Test
+    @DisplayName("Filtering by severity ERROR returns only error events, newest first")
+    fun severityFilter() = runBlocking {
+        val page = service.queryLogs(AdminRole.SUPER_ADMIN, AuditLogFilter(severity = AuditSeverity.ERROR))
+
+        assertEquals(listOf("error", "error"), page.entries.map { it.severity })
+        assertEquals(
+            listOf(rows[9].id.toString(), rows[7].id.toString()),
+            page.entries.map { it.id }
+        )
+        assertFalse(page.hasMore)
+    }
+
+    @Test
+    @DisplayName("Pages split exactly at the page size and the last page reports no more results")
+    fun paginationBoundaries() = runBlocking {
+        val first = service.queryLogs(AdminRole.SUPER_ADMIN, AuditLogFilter(), page = 1, pageSize = 5)
+        val second = service.queryLogs(AdminRole.SUPER_ADMIN, AuditLogFilter(), page = 2, pageSize = 5)
+        val third = service.queryLogs(AdminRole.SUPER_ADMIN, AuditLogFilter(), page = 3, pageSize = 5)
+
+        assertEquals(5, first.entries.size)
+        assertTrue(first.hasMore)
+        assertEquals(5, second.entries.size)
+        assertFalse(second.hasMore)
+        assertTrue(third.entries.isEmpty())
+        assertEquals(
+            rows.sortedByDescending { it.createdAt }.map { it.id.toString() },
+            first.entries.map { it.id } + second.entries.map { it.id }
+        )
+    }
+
+    @Test
+    @DisplayName("Page size is capped")
+    fun pageSizeCapped() = runBlocking {
+        val page = service.queryLogs(AdminRole.SUPER_ADMIN, AuditLogFilter(), pageSize = 10_000)
+
+        assertEquals(AdminAuditService.MAX_PAGE_SIZE, page.pageSize)
+        assertEquals(AdminAuditService.MAX_PAGE_SIZE + 1, repository.lastLimit)
+    }
+
+    @Test
+    @DisplayName("Roles below content moderator are refused")
+    fun roleLevelRequired() {
+        assertFailsWith<SecurityException> {
+            runBlocking { service.queryLogs(AdminRole.ANALYTICS_VIEWER, AuditLogFilter()) }
+        }
+        assertFailsWith<SecurityException> {
+            runBlocking { service.queryLogs(null, AuditLogFilter()) }
+        }
+    }
+}
@@ -0,0 +1,193 @@
+package com.wondernest.services.web.admin
+
+import com.wondernest.data.database.repository.web.AdminAuditEntry
+import com.wondernest.data.database.repository.web.AdminAuditRepository
+import com.wondernest.data.database.repository.web.AdminSessionRepository
+import com.wondernest.data.database.repository.web.AdminUserRepository
+import com.wondernest.domain.web.AdminLoginRequest
+import com.wondernest.domain.web.AdminRole
+import com.wondernest.domain.web.AdminUser
+import com.wondernest.domain.web.AuditLogEntry
+import com.wondernest.domain.web.AuditLogFilter
+import com.wondernest.services.auth.JwtService
+import com.wondernest.services.auth.TokenPair
+import com.wondernest.services.security.TwoFactorService
+import io.mockk.coEvery
+import io.mockk.coVerify
+import io.mockk.every
+import io.mockk.mockk
+import io.mockk.slot
+import kotlinx.coroutines.runBlocking
+import org.junit.jupiter.api.BeforeEach
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import org.springframework.security.crypto.bcrypt.BCryptPasswordEncoder
+import java.time.Instant
+import java.util.*
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+import kotlin.test.assertFalse
+import kotlin.test.assertTrue
+
+@DisplayName("Admin Authentication Tests")
+class AdminAuthServiceTest {
+
+    private class RecordingAuditRepository : AdminAuditRepository {
+        val entries = mutableListOf<AdminAuditEntry>()
+        override suspend fun record(entry: AdminAuditEntry) {
+            entries.add(entry)
+        }
+        override suspend fun query(filter: AuditLogFilter, limit: Int, offset: Long) = emptyList<AuditLogEntry>()
+    }
+
+    // RFC 6238 SHA-1 test key; "287082" is the code at t=59s
+    private val secret = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ"
+    private val twoFactorService = TwoFactorService(clock = { Instant.ofEpochSecond(59) })
+    private val password = "correct horse battery"
+
+    private lateinit var adminUserRepository: AdminUserRepository
+    private lateinit var auditRepository: RecordingAuditRepository
+    private lateinit var sessionRepository: AdminSessionRepository
+    private lateinit var jwtService: JwtService
+    private lateinit var service: AdminAuthService
+
+    @BeforeEach
+    fun setup() {
+        adminUserRepository = mockk(relaxed = true)
+        auditRepository = RecordingAuditRepository()
+        sessionRepository = mockk(relaxed = true)
+        jwtService = mockk()
+        every { jwtService.generateAdminToken(any(), any()) } returns TokenPair("access", "refresh", 3600)
+        coEvery { adminUserRepository.recordTotpStep(any(), any()) } returns true
+        service = AdminAuthService(adminUserRepository, sessionRepository, jwtService, twoFactorService, auditRepository)
+    }
+
+    private fun admin(twoFactorEnabled: Boolean, backupCodes: List<String> = emptyList()): AdminUser {
+        val now = Instant.now()
+        val user = AdminUser(
+            id = UUID.randomUUID(),
+            email = "admin@wondernest.app",
+            passwordHash = BCryptPasswordEncoder(4).encode(password),
+            salt = "",
+            firstName = "Ada",
+            lastName = "Admin",
+            role = AdminRole.SUPER_ADMIN,
+            permissions = emptyList(),
+            twoFactorEnabled = twoFactorEnabled,
+            twoFactorSecret = secret.takeIf { twoFactorEnabled },
+            twoFactorBackupCodes = backupCodes,
+            createdAt = now,
+            updatedAt = now
+        )
+        coEvery { adminUserRepository.findByEmail(user.email) } returns user
+        return user
+    }
+
+    private suspend fun login(code: String?) =
+        service.authenticateAdmin(AdminLoginRequest("admin@wondernest.app", password, code), "10.0.0.1")
+
+    @Test
+    @DisplayName("A valid TOTP code completes login for accounts with 2FA enabled")
+    fun validCodeLogsIn() = runBlocking {
+        admin(twoFactorEnabled = true)
+
+        assertTrue(login(null).requiresTwoFactor)
+        assertEquals("access", login("287082").accessToken)
+    }
+
+    @Test
+    @DisplayName("An invalid TOTP code is rejected and audited as a warning")
+    fun invalidCodeRejected() = runBlocking {
+        val user = admin(twoFactorEnabled = true)
+
+        assertFailsWith<InvalidTwoFactorCodeException> { login("123456") }
+
+        val entry = auditRepository.entries.single()
+        assertEquals("auth.two_factor_failed", entry.action)
+        assertEquals("warning", entry.details["severity"])
+        assertFalse(entry.success)
+        coVerify(exactly = 1) { adminUserRepository.incrementFailedLoginAttempts(user.id, any()) }
+    }
+
+    @Test
+    @DisplayName("A TOTP code is refused the second time it is presented")
+    fun replayedCodeRejected() = runBlocking {
+        val user = admin(twoFactorEnabled = true)
+        var lastStep: Long? = null
+        coEvery { adminUserRepository.recordTotpStep(user.id, any()) } answers {
+            val step = secondArg<Long>()
+            (lastStep?.let { step > it } ?: true).also { accepted -> if (accepted) lastStep = step }
+        }
+
+        assertEquals("access", login("287082").accessToken)
+        assertFailsWith<InvalidTwoFactorCodeException> { login("287082") }
+        assertEquals(1L, lastStep)
+    }
+
+    @Test
+    @DisplayName("A backup code works once in place of a TOTP code")
+    fun backupCodeConsumed() = runBlocking {
+        val user = admin(twoFactorEnabled = true, backupCodes = listOf(twoFactorService.hashBackupCode("aaaa-bbbb")))
+        val stored = mutableListOf(twoFactorService.hashBackupCode("aaaa-bbbb"))
+        coEvery { adminUserRepository.consumeBackupCode(user.id, any(), any()) } answers {
+            if (stored.remove(secondArg<String>())) stored.size else null
+        }
+
+        assertEquals("access", login("aaaa-bbbb").accessToken)
+        assertFailsWith<InvalidTwoFactorCodeException> { login("AAAA-BBBB") }
+        coVerify(exactly = 0) { adminUserRepository.update(any()) }
+    }
+
+    @Test
+    @DisplayName("Accounts without 2FA skip verification")
+    fun twoFactorDisabledSkipsVerification() = runBlocking {
+        admin(twoFactorEnabled = false)
+
+        assertEquals("access", login("000000").accessToken)
+        assertTrue(auditRepository.entries.isEmpty())
+    }
+
+    @Test
+    @DisplayName("A configured threshold of 3 locks on the third failure and resets on success")
+    fun configurableLockoutThreshold() = runBlocking {
+        service = AdminAuthService(
+            adminUserRepository, sessionRepository, jwtService, twoFactorService, auditRepository,
+            AdminLockoutConfig(maxFailedAttempts = 3, lockoutMinutes = 15)
+        )
+        val user = admin(twoFactorEnabled = false)
+        var attempts = 0
+        val lockedUntil = slot<Instant>()
+        coEvery { adminUserRepository.findByEmail(user.email) } answers { user.copy(failedLoginAttempts = attempts) }
+        coEvery { adminUserRepository.incrementFailedLoginAttempts(user.id, any()) } answers { ++attempts }
+        coEvery { adminUserRepository.updateFailedLoginAttempts(user.id, any()) } answers {
+            attempts = secondArg()
+            true
+        }
+        coEvery { adminUserRepository.lockUser(user.id, capture(lockedUntil)) } returns true
+        val wrongPassword = AdminLoginRequest(user.email, "wrong password")
+
+        repeat(2) { assertFailsWith<AuthenticationException> { service.authenticateAdmin(wrongPassword, "10.0.0.1") } }
+        assertFalse(lockedUntil.isCaptured)
+
+        // A successful login before the threshold resets the counter
+        login(null)
+        assertEquals(0, attempts)
+
+        repeat(2) { assertFailsWith<AuthenticationException> { service.authenticateAdmin(wrongPassword, "10.0.0.1") } }
+        assertFalse(lockedUntil.isCaptured)
+
+        assertFailsWith<AuthenticationException> { service.authenticateAdmin(wrongPassword, "10.0.0.1") }
+        assertEquals(3, attempts)
+        assertTrue(lockedUntil.captured.isAfter(Instant.now().plusSeconds(14 * 60)))
+    }
+
+    @Test
+    @DisplayName("Lockout settings fall back to defaults for missing or invalid values")
+    fun lockoutConfigFromEnvironment() {
+        assertEquals(AdminLockoutConfig(5, 30), AdminLockoutConfig.fromEnvironment(emptyMap()))
+        assertEquals(
+            AdminLockoutConfig(3, 30),
+            AdminLockoutConfig.fromEnvironment(mapOf("ADMIN_MAX_FAILED_ATTEMPTS" to "3", "ADMIN_LOCKOUT_MINUTES" to "0"))
+        )
+    }
+}
@@ -0,0 +1,204 @@
+package com.wondernest.services.web.admin
+
+import com.wondernest.data.database.repository.web.AdminAuditEntry
+import com.wondernest.data.database.repository.web.AdminAuditRepository
+import com.wondernest.data.database.repository.web.AdminUserRepository
+import com.wondernest.data.database.repository.web.ContentItemRepository
+import com.wondernest.domain.web.AdminRole
+import com.wondernest.domain.web.AdminUser
+import com.wondernest.domain.web.AuditLogEntry
+import com.wondernest.domain.web.AuditLogFilter
+import com.wondernest.domain.web.BulkStatusTransitionRequest
+import com.wondernest.domain.web.ContentItem
+import com.wondernest.domain.web.ContentItemFilter
+import com.wondernest.domain.web.ContentStatus
+import io.mockk.coEvery
+import io.mockk.mockk
+import kotlinx.coroutines.runBlocking
+import org.junit.jupiter.api.BeforeEach
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.time.Instant
+import java.util.*
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+import kotlin.test.assertTrue
+
+@DisplayName("Admin Bulk Content Transition Tests")
+class AdminContentServiceTest {
+
+    private class InMemoryContentItemRepository : ContentItemRepository {
+        val items = sortedMapOf<UUID, ContentItem>()
+
+        override suspend fun findByFilter(filter: ContentItemFilter, afterId: UUID?, limit: Int): List<ContentItem> =
+            items.values
+                .filter { afterId == null || it.id > afterId }
+                .filter { filter.creatorId == null || it.creatorId == filter.creatorId }
+                .filter { filter.contentType == null || it.contentType == filter.contentType }
+                .filter { filter.status == null || it.status == filter.status }
+                .take(limit)
+
+        override suspend fun updateStatusBatch(ids: List<UUID>, status: ContentStatus, actorId: UUID): Int {
+            ids.forEach { id -> items[id]?.let { items[id] = it.copy(status = status) } }
+            return ids.size
+        }
+    }
+
+    private class RecordingAuditRepository : AdminAuditRepository {
+        val entries = mutableListOf<AdminAuditEntry>()
+        override suspend fun record(entry: AdminAuditEntry) {
+            entries.add(entry)
+        }
+        override suspend fun query(filter: AuditLogFilter, limit: Int, offset: Long) = emptyList<AuditLogEntry>()
+    }
+
+    private lateinit var contentRepository: InMemoryContentItemRepository
+    private lateinit var auditRepository: RecordingAuditRepository
+    private lateinit var service: AdminContentService
+
+    private val adminId = UUID.randomUUID()
+    private val creatorA = UUID.randomUUID()
+    private val creatorB = UUID.randomUUID()
+    private val moderator = listOf("moderate_content")
+    private var storedAdmin = AdminUser(
+        id = adminId,
+        email = "mod@wondernest.app",
+        passwordHash = "",
+        salt = "",
+        firstName = "Mo",
+        lastName = "Derator",
+        role = AdminRole.ANALYTICS_VIEWER,
+        permissions = moderator,
+        createdAt = Instant.now(),
+        updatedAt = Instant.now()
+    )
+
+    @BeforeEach
+    fun setup() {
+        contentRepository = InMemoryContentItemRepository()
+        auditRepository = RecordingAuditRepository()
+        val adminUsers = mockk<AdminUserRepository>()
+        coEvery { adminUsers.findById(adminId) } answers { storedAdmin }
+        service = AdminContentService(contentRepository, auditRepository, adminUsers)
+    }
+
+    private fun addItem(creatorId: UUID, status: ContentStatus): ContentItem {
+        val now = Instant.now()
+        val item = ContentItem(
+            id = UUID.randomUUID(),
+            contentType = "story",
+            title = "Story",
+            creatorId = creatorId,
+            status = status,
+            createdAt = now,
+            updatedAt = now
+        )
+        contentRepository.items[item.id] = item
+        return item
+    }
+
+    @Test
+    @DisplayName("Unpublishing by creator only affects that creator's content")
+    fun unpublishByCreator() = runBlocking {
+        repeat(5) { addItem(creatorA, ContentStatus.PUBLISHED) }
+        repeat(3) { addItem(creatorB, ContentStatus.PUBLISHED) }
+
+        val result = service.bulkTransition(
+            adminId, moderator,
+            BulkStatusTransitionRequest(targetStatus = "archived", creatorId = creatorA.toString(), batchSize = 2)
+        )
+
+        assertEquals(5, result.matched)
+        assertEquals(5, result.updated)
+        assertEquals(0, result.failed)
+        assertEquals(3, result.batches)
+        val items = contentRepository.items.values
+        assertTrue(items.filter { it.creatorId == creatorA }.all { it.status == ContentStatus.ARCHIVED })
+        assertTrue(items.filter { it.creatorId == creatorB }.all { it.status == ContentStatus.PUBLISHED })
+    }
+
+    @Test
+    @DisplayName("Items that cannot make the transition are reported as failures")
+    fun invalidTransitionsReported() = runBlocking {
+        val published = addItem(creatorA, ContentStatus.PUBLISHED)
+        addItem(creatorA, ContentStatus.DRAFT)
+
+        val result = service.bulkTransition(
+            adminId, moderator,
+            BulkStatusTransitionRequest(targetStatus = "in_review", creatorId = creatorA.toString())
+        )
+
+        assertEquals(2, result.matched)
+        assertEquals(1, result.updated)
+        assertEquals(1, result.failed)
+        assertEquals(published.id.toString(), result.failures.single().contentId)
+        assertEquals(ContentStatus.PUBLISHED, contentRepository.items[published.id]?.status)
+    }
+
+    @Test
+    @DisplayName("Publishing requires the publish permission")
+    fun publishRequiresPermission() {
+        addItem(creatorA, ContentStatus.APPROVED)
+
+        assertFailsWith<SecurityException> {
+            runBlocking {
+                service.bulkTransition(
+                    adminId, moderator,
+                    BulkStatusTransitionRequest(targetStatus = "published", creatorId = creatorA.toString())
+                )
+            }
+        }
+        assertTrue(auditRepository.entries.isEmpty())
+    }
+
+    @Test
+    @DisplayName("Batch updates are refused once the stored account loses the permission")
+    fun storedPermissionsGuardUpdates() {
+        val item = addItem(creatorA, ContentStatus.PUBLISHED)
+        // The token still claims moderate_content, but the account no longer has it
+        storedAdmin = storedAdmin.copy(permissions = emptyList())
+
+        assertFailsWith<SecurityException> {
+            runBlocking {
+                service.bulkTransition(
+                    adminId, moderator,
+                    BulkStatusTransitionRequest(targetStatus = "archived", creatorId = creatorA.toString())
+                )
+            }
+        }
+        assertEquals(ContentStatus.PUBLISHED, contentRepository.items[item.id]?.status)
+    }
+
+    @Test
+    @DisplayName("Bulk transitions are audited with the filter used")
+    fun auditIncludesFilter() = runBlocking {
+        addItem(creatorA, ContentStatus.PUBLISHED)
+
+        service.bulkTransition(
+            adminId, moderator,
+            BulkStatusTransitionRequest(
+                targetStatus = "archived",
+                creatorId = creatorA.toString(),
+                contentType = "story",
+                reason = "Creator offboarded"
+            )
+        )
+
+        val entry = auditRepository.entries.single()
+        assertEquals(adminId, entry.adminId)
+        assertEquals("content.bulk_status_transition", entry.action)
+        assertEquals(creatorA.toString(), entry.details["filter.creatorId"])
+        assertEquals("story", entry.details["filter.contentType"])
+        assertEquals("1", entry.details["updated"])
+    }
+
+    @Test
+    @DisplayName("A filter is required")
+    fun filterRequired() {
+        assertFailsWith<IllegalArgumentException> {
+            runBlocking {
+                service.bulkTransition(adminId, moderator, BulkStatusTransitionRequest(targetStatus = "archived"))
+            }
+        }
+    }
+}
@@ -0,0 +1,125 @@
+package com.wondernest.services.web.admin
+
+import com.auth0.jwt.JWT
+import com.wondernest.data.database.repository.web.AdminAuditRepository
+import com.wondernest.data.database.repository.web.AdminSessionRepository
+import com.wondernest.data.database.repository.web.AdminUserRepository
+import com.wondernest.domain.web.AdminPermission
+import com.wondernest.domain.web.AdminRole
+import com.wondernest.domain.web.AdminUser
+import com.wondernest.domain.web.CustomPermissions
+import com.wondernest.services.auth.JwtService
+import io.mockk.coEvery
+import io.mockk.coVerify
+import io.mockk.mockk
+import kotlinx.coroutines.runBlocking
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.time.Instant
+import java.util.*
+import kotlin.test.assertFailsWith
+import kotlin.test.assertFalse
+import kotlin.test.assertTrue
+
+@DisplayName("Admin Custom Permission Tests")
+class AdminCustomPermissionsTest {
+
+    private val manageSystem = AdminPermission.MANAGE_SYSTEM_SETTINGS.code
+
+    private fun admin(role: AdminRole, customPermissions: CustomPermissions = CustomPermissions()): AdminUser {
+        val now = Instant.now()
+        return AdminUser(
+            id = UUID.randomUUID(),
+            email = "admin@wondernest.app",
+            passwordHash = "",
+            salt = "",
+            firstName = "Ada",
+            lastName = "Admin",
+            role = role,
+            permissions = emptyList(),
+            customPermissions = customPermissions,
+            createdAt = now,
+            updatedAt = now
+        )
+    }
+
+    // Permissions as a route sees them: read back from the admin's access token
+    private fun tokenPermissions(adminUser: AdminUser): List<String> =
+        JWT.decode(JwtService().generateAdminToken(adminUser).accessToken)
+            .getClaim("permissions").asList(String::class.java)
+
+    // Backfill endpoints require manage_system_settings
+    private val backfillService = BackfillService(emptyList(), mockk(relaxed = true), mockk(relaxed = true))
+
+    @Test
+    @DisplayName("A custom grant passes the permission check even when the role lacks it")
+    fun customGrantPasses() = runBlocking {
+        val creator = admin(AdminRole.CONTENT_CREATOR)
+        assertFailsWith<SecurityException> { backfillService.listJobs(tokenPermissions(creator)) }
+
+        val granted = admin(AdminRole.CONTENT_CREATOR, CustomPermissions(granted = listOf(manageSystem)))
+        assertTrue(manageSystem in granted.effectivePermissions())
+        backfillService.listJobs(tokenPermissions(granted))
+        Unit
+    }
+
+    @Test
+    @DisplayName("A custom denial overrides a permission the role grants")
+    fun denialOverridesRole() {
+        val superAdmin = admin(AdminRole.SUPER_ADMIN, CustomPermissions(denied = listOf(manageSystem)))
+
+        assertFalse(manageSystem in superAdmin.effectivePermissions())
+        assertTrue(AdminPermission.MANAGE_ADMIN_USERS.code in superAdmin.effectivePermissions())
+        assertFailsWith<SecurityException> {
+            runBlocking { backfillService.listJobs(tokenPermissions(superAdmin)) }
+        }
+    }
+
+    @Test
+    @DisplayName("Unknown permission names are rejected when custom permissions are saved")
+    fun unknownPermissionsRejected() {
+        val target = admin(AdminRole.SUPPORT_AGENT)
+        val adminUserRepository = mockk<AdminUserRepository>()
+        coEvery { adminUserRepository.findById(target.id) } returns target
+        val service = AdminAccountService(
+            adminUserRepository, mockk<AdminAuditRepository>(relaxed = true), mockk(), mockk(), mockk()
+        )
+
+        assertFailsWith<IllegalArgumentException> {
+            runBlocking {
+                service.updateCustomPermissions(
+                    listOf(AdminPermission.MANAGE_ADMIN_USERS.code),
+                    UUID.randomUUID(),
+                    target.id,
+                    CustomPermissions(granted = listOf("launch_rockets"))
+                )
+            }
+        }
+        coVerify(exactly = 0) { adminUserRepository.update(any()) }
+    }
+
+    @Test
+    @DisplayName("Saving custom permissions ends the admin's sessions so old tokens stop working")
+    fun permissionChangeRevokesSessions() = runBlocking {
+        val target = admin(AdminRole.SUPPORT_AGENT)
+        val adminUserRepository = mockk<AdminUserRepository>()
+        val sessionRepository = mockk<AdminSessionRepository>()
+        coEvery { adminUserRepository.findById(target.id) } returns target
+        coEvery { adminUserRepository.update(any()) } answers { firstArg() }
+        coEvery { sessionRepository.deactivateAllUserSessions(target.id) } returns 2
+        val service = AdminAccountService(
+            adminUserRepository, mockk<AdminAuditRepository>(relaxed = true), mockk(), mockk(), sessionRepository
+        )
+
+        val profile = service.updateCustomPermissions(
+            listOf(AdminPermission.MANAGE_ADMIN_USERS.code),
+            UUID.randomUUID(),
+            target.id,
+            CustomPermissions(granted = listOf(manageSystem))
+        )
+
+        assertTrue(manageSystem in profile.permissions)
+        coVerify { sessionRepository.deactivateAllUserSessions(target.id) }
+        coVerify { adminUserRepository.update(match { it.customPermissions.granted == listOf(manageSystem) }) }
+    }
+}
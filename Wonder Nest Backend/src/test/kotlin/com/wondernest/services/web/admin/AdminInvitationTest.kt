@@ -0,0 +1,121 @@
+package com.wondernest.services.web.admin
+
+import com.wondernest.data.database.repository.web.AdminInvitationRepository
+import com.wondernest.data.database.repository.web.AdminUserRepository
+import com.wondernest.domain.web.AdminInvitation
+import com.wondernest.domain.web.AdminInvitationRequest
+import com.wondernest.domain.web.AdminPermission
+import com.wondernest.domain.web.AdminRole
+import com.wondernest.domain.web.AdminUser
+import com.wondernest.services.email.EmailMessage
+import com.wondernest.services.email.EmailSender
+import com.wondernest.services.email.EmailService
+import io.mockk.coEvery
+import io.mockk.mockk
+import kotlinx.coroutines.runBlocking
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.time.Instant
+import java.util.*
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+import kotlin.test.assertFalse
+import kotlin.test.assertNotNull
+import kotlin.test.assertTrue
+
+@DisplayName("Admin Invitation Tests")
+class AdminInvitationTest {
+
+    private class RecordingEmailSender(private val fail: Boolean = false) : EmailSender {
+        val sent = mutableListOf<EmailMessage>()
+        override suspend fun send(message: EmailMessage) {
+            if (fail) throw IllegalStateException("SMTP unavailable")
+            sent.add(message)
+        }
+    }
+
+    private val now = Instant.parse("2025-09-01T12:00:00Z")
+    private val inviter = AdminUser(
+        id = UUID.randomUUID(),
+        email = "ada@wondernest.app",
+        passwordHash = "",
+        salt = "",
+        firstName = "Ada",
+        lastName = "Lovelace",
+        role = AdminRole.SUPER_ADMIN,
+        permissions = emptyList(),
+        createdAt = now,
+        updatedAt = now
+    )
+    private val invitations = mutableListOf<AdminInvitation>()
+
+    private fun service(sender: EmailSender, inviter: AdminUser = this.inviter): AdminAccountService {
+        val adminUserRepository = mockk<AdminUserRepository>()
+        coEvery { adminUserRepository.findById(inviter.id) } returns inviter
+        val invitationRepository = mockk<AdminInvitationRepository>()
+        coEvery { invitationRepository.create(any()) } answers { firstArg<AdminInvitation>().also { invitations.add(it) } }
+        return AdminAccountService(
+            adminUserRepository,
+            mockk(relaxed = true),
+            invitationRepository,
+            EmailService(sender, "https://admin.wondernest.app/"),
+            mockk(relaxed = true),
+            clock = { now }
+        )
+    }
+
+    private val canManage = listOf(AdminPermission.MANAGE_ADMIN_USERS.code)
+
+    @Test
+    @DisplayName("The invitee is emailed a link carrying the invitation token")
+    fun invitationEmailSent() = runBlocking {
+        val sender = RecordingEmailSender()
+
+        val response = service(sender).inviteAdmin(
+            canManage, inviter.id, AdminInvitationRequest(" New.Moderator@Example.com ", "content_moderator")
+        )
+
+        assertTrue(response.emailSent)
+        val message = sender.sent.single()
+        assertEquals("new.moderator@example.com", message.to)
+
+        val token = Regex("https://admin\\.wondernest\\.app/admin/invitations/accept\\?token=([A-Za-z0-9_-]+)")
+            .find(message.body)?.groupValues?.get(1)
+        assertNotNull(token)
+        val invitation = invitations.single()
+        assertEquals(AdminAccountService.hashInvitationToken(token), invitation.tokenHash)
+        assertFalse(token in invitation.tokenHash)
+
+        assertTrue("Ada Lovelace" in message.body)
+        assertTrue(AdminRole.CONTENT_MODERATOR.displayName in message.body)
+        assertTrue("8 September 2025" in message.body)
+    }
+
+    @Test
+    @DisplayName("A failed delivery keeps the invitation and reports that no email went out")
+    fun deliveryFailureIsNotFatal() = runBlocking {
+        val response = service(RecordingEmailSender(fail = true)).inviteAdmin(
+            canManage, inviter.id, AdminInvitationRequest("new.moderator@example.com", "content_moderator")
+        )
+
+        assertFalse(response.emailSent)
+        assertEquals(1, invitations.size)
+    }
+
+    @Test
+    @DisplayName("Admins can't invite a role above their own")
+    fun noEscalation() {
+        val moderator = inviter.copy(role = AdminRole.CONTENT_MODERATOR)
+        val sender = RecordingEmailSender()
+
+        assertFailsWith<SecurityException> {
+            runBlocking {
+                service(sender, moderator).inviteAdmin(
+                    canManage, moderator.id, AdminInvitationRequest("boss@example.com", "super_admin")
+                )
+            }
+        }
+        assertTrue(sender.sent.isEmpty())
+        assertTrue(invitations.isEmpty())
+    }
+}
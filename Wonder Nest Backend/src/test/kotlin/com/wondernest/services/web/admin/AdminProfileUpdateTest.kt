@@ -0,0 +1,111 @@
+package com.wondernest.services.web.admin
+
+import com.wondernest.data.database.repository.web.AdminAuditEntry
+import com.wondernest.data.database.repository.web.AdminAuditRepository
+import com.wondernest.data.database.repository.web.AdminUserRepository
+import com.wondernest.domain.web.AdminRole
+import com.wondernest.domain.web.AdminUser
+import com.wondernest.domain.web.AuditLogEntry
+import com.wondernest.domain.web.AuditLogFilter
+import com.wondernest.domain.web.UpdateAdminProfileRequest
+import io.mockk.coEvery
+import io.mockk.mockk
+import kotlinx.coroutines.runBlocking
+import org.junit.jupiter.api.BeforeEach
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.time.Instant
+import java.util.*
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+import kotlin.test.assertTrue
+
+@DisplayName("Admin Profile Update Tests")
+class AdminProfileUpdateTest {
+
+    private class RecordingAuditRepository : AdminAuditRepository {
+        val entries = mutableListOf<AdminAuditEntry>()
+        override suspend fun record(entry: AdminAuditEntry) {
+            entries.add(entry)
+        }
+        override suspend fun query(filter: AuditLogFilter, limit: Int, offset: Long) = emptyList<AuditLogEntry>()
+    }
+
+    private val now = Instant.parse("2025-09-01T12:00:00Z")
+    private val admins = mutableMapOf<UUID, AdminUser>()
+    private lateinit var auditRepository: RecordingAuditRepository
+    private lateinit var service: AdminAuthService
+    private lateinit var adminUser: AdminUser
+
+    @BeforeEach
+    fun setup() {
+        val adminUserRepository = mockk<AdminUserRepository>()
+        coEvery { adminUserRepository.findById(any()) } answers { admins[firstArg()] }
+        coEvery { adminUserRepository.update(any()) } answers {
+            firstArg<AdminUser>().also { admins[it.id] = it }
+        }
+        auditRepository = RecordingAuditRepository()
+        service = AdminAuthService(
+            adminUserRepository, mockk(relaxed = true), mockk(), mockk(), auditRepository, clock = { now }
+        )
+
+        val created = Instant.parse("2025-01-01T00:00:00Z")
+        adminUser = AdminUser(
+            id = UUID.randomUUID(),
+            email = "admin@wondernest.app",
+            passwordHash = "",
+            salt = "",
+            firstName = "Ada",
+            lastName = "Admin",
+            role = AdminRole.SUPPORT_AGENT,
+            permissions = emptyList(),
+            createdAt = created,
+            updatedAt = created
+        )
+        admins[adminUser.id] = adminUser
+    }
+
+    @Test
+    @DisplayName("A name change is visible on the next profile read")
+    fun nameChangePersists() = runBlocking {
+        val updated = service.updateProfile(adminUser.id, UpdateAdminProfileRequest(firstName = "  Grace "))
+
+        assertEquals("Grace", updated.firstName)
+        assertEquals("Grace", service.getProfile(adminUser.id)?.firstName)
+        assertEquals("Admin", service.getProfile(adminUser.id)?.lastName)
+        assertEquals(now, admins.getValue(adminUser.id).updatedAt)
+    }
+
+    @Test
+    @DisplayName("Blank and overlong names are rejected")
+    fun invalidNamesRejected() {
+        val invalid = listOf(
+            UpdateAdminProfileRequest(firstName = "   "),
+            UpdateAdminProfileRequest(lastName = "x".repeat(AdminAuthService.MAX_NAME_LENGTH + 1)),
+            UpdateAdminProfileRequest(phoneNumber = "1".repeat(AdminAuthService.MAX_PHONE_LENGTH + 1))
+        )
+        invalid.forEach { request ->
+            assertFailsWith<IllegalArgumentException>(request.toString()) {
+                runBlocking { service.updateProfile(adminUser.id, request) }
+            }
+        }
+        assertEquals(adminUser, admins[adminUser.id])
+        assertTrue(auditRepository.entries.isEmpty())
+    }
+
+    @Test
+    @DisplayName("The audit entry lists the fields that changed")
+    fun changedFieldsAudited() = runBlocking {
+        service.updateProfile(
+            adminUser.id,
+            UpdateAdminProfileRequest(firstName = "Ada", lastName = "Lovelace", phoneNumber = "+44 20 7946 0000"),
+            ipAddress = "10.0.0.1"
+        )
+
+        val entry = auditRepository.entries.single()
+        assertEquals("admin_user.profile_updated", entry.action)
+        assertEquals(adminUser.id, entry.adminId)
+        assertEquals("lastName,phoneNumber", entry.details["changedFields"])
+        assertEquals("10.0.0.1", entry.ipAddress)
+    }
+}
@@ -0,0 +1,144 @@
+package com.wondernest.services.web.admin
+
+import com.wondernest.data.database.repository.web.AdminAuditEntry
+import com.wondernest.data.database.repository.web.AdminAuditRepository
+import com.wondernest.data.database.repository.web.AdminSessionRepository
+import com.wondernest.domain.web.AdminPermission
+import com.wondernest.domain.web.AdminSession
+import com.wondernest.domain.web.AuditLogEntry
+import com.wondernest.domain.web.AuditLogFilter
+import io.mockk.coEvery
+import io.mockk.mockk
+import kotlinx.coroutines.runBlocking
+import org.junit.jupiter.api.BeforeEach
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.time.Instant
+import java.time.temporal.ChronoUnit
+import java.util.*
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+import kotlin.test.assertFalse
+import kotlin.test.assertTrue
+
+@DisplayName("Admin Session Management Tests")
+class AdminSessionManagementTest {
+
+    private class RecordingAuditRepository : AdminAuditRepository {
+        val entries = mutableListOf<AdminAuditEntry>()
+        override suspend fun record(entry: AdminAuditEntry) {
+            entries.add(entry)
+        }
+        override suspend fun query(filter: AuditLogFilter, limit: Int, offset: Long) = emptyList<AuditLogEntry>()
+    }
+
+    private val alice = UUID.randomUUID()
+    private val bob = UUID.randomUUID()
+    private val sessions = mutableMapOf<UUID, AdminSession>()
+
+    private lateinit var auditRepository: RecordingAuditRepository
+    private lateinit var service: AdminAuthService
+
+    @BeforeEach
+    fun setup() {
+        val sessionRepository = mockk<AdminSessionRepository>()
+        coEvery { sessionRepository.findById(any()) } answers { sessions[firstArg()] }
+        coEvery { sessionRepository.findActiveSessionsForUser(any()) } answers {
+            sessions.values.filter { it.adminUserId == firstArg() && it.isActive }
+        }
+        coEvery { sessionRepository.deactivateSession(any()) } answers {
+            val session = sessions[firstArg()] ?: return@answers false
+            sessions[session.id] = session.copy(isActive = false)
+            true
+        }
+        auditRepository = RecordingAuditRepository()
+        service = AdminAuthService(mockk(), sessionRepository, mockk(), mockk(), auditRepository)
+    }
+
+    private fun session(adminId: UUID): UUID {
+        val now = Instant.now()
+        val session = AdminSession(
+            id = UUID.randomUUID(),
+            adminUserId = adminId,
+            sessionToken = "hash",
+            ipAddress = "10.0.0.1",
+            userAgent = "Firefox",
+            expiresAt = now.plus(4, ChronoUnit.HOURS),
+            lastActivity = now,
+            createdAt = now
+        )
+        sessions[session.id] = session
+        return session.id
+    }
+
+    private fun caller(adminId: UUID, sessionId: UUID?, vararg permissions: AdminPermission) =
+        AdminCaller(adminId, sessionId, permissions.map { it.code }, ipAddress = "10.0.0.9")
+
+    @Test
+    @DisplayName("Listing marks the current session")
+    fun listFlagsCurrentSession() = runBlocking {
+        val current = session(alice)
+        val other = session(alice)
+        session(bob)
+
+        val listed = service.listSessions(caller(alice, current))
+
+        assertEquals(setOf(current.toString(), other.toString()), listed.map { it.id }.toSet())
+        assertEquals(listOf(current.toString()), listed.filter { it.current }.map { it.id })
+    }
+
+    @Test
+    @DisplayName("An admin can revoke their own session and the revocation is audited")
+    fun selfRevocation() = runBlocking {
+        val current = session(alice)
+        val other = session(alice)
+
+        assertTrue(service.revokeSession(caller(alice, current), other))
+
+        assertFalse(sessions.getValue(other).isActive)
+        val entry = auditRepository.entries.single()
+        assertEquals("auth.session_revoked", entry.action)
+        assertEquals(other, entry.resourceId)
+        assertEquals(alice, entry.adminId)
+    }
+
+    @Test
+    @DisplayName("Revoking all sessions keeps the current one")
+    fun revokeOthersKeepsCurrent() = runBlocking {
+        val current = session(alice)
+        repeat(2) { session(alice) }
+        val bobSession = session(bob)
+
+        assertEquals(2, service.revokeOtherSessions(caller(alice, current)))
+
+        assertTrue(sessions.getValue(current).isActive)
+        assertTrue(sessions.getValue(bobSession).isActive)
+        assertEquals(2, auditRepository.entries.size)
+    }
+
+    @Test
+    @DisplayName("Admins can't see or revoke other admins' sessions without the permission")
+    fun crossAdminIsolation() = runBlocking {
+        val current = session(alice)
+        val bobSession = session(bob)
+
+        assertFalse(service.revokeSession(caller(alice, current), bobSession))
+        assertTrue(sessions.getValue(bobSession).isActive)
+        assertFailsWith<InsufficientPermissionsException> {
+            service.listSessions(caller(alice, current), bob)
+        }
+        assertTrue(auditRepository.entries.isEmpty())
+    }
+
+    @Test
+    @DisplayName("The session-management permission allows managing other admins' sessions")
+    fun permissionAllowsCrossAdmin() = runBlocking {
+        val current = session(alice)
+        val bobSession = session(bob)
+        val manager = caller(alice, current, AdminPermission.MANAGE_SECURITY_SETTINGS)
+
+        assertEquals(listOf(bobSession.toString()), service.listSessions(manager, bob).map { it.id })
+        assertTrue(service.revokeSession(manager, bobSession))
+        assertEquals(bob.toString(), auditRepository.entries.single().details["sessionOwnerId"])
+    }
+}
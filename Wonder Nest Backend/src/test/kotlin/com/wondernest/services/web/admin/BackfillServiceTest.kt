@@ -0,0 +1,198 @@
+package com.wondernest.services.web.admin
+
+import com.wondernest.data.database.repository.web.AdminAuditEntry
+import com.wondernest.data.database.repository.web.AdminAuditRepository
+import com.wondernest.data.database.repository.web.BackfillProgressRepository
+import com.wondernest.domain.web.AuditLogEntry
+import com.wondernest.domain.web.AuditLogFilter
+import com.wondernest.domain.web.BackfillProgress
+import com.wondernest.domain.web.BackfillRunRequest
+import com.wondernest.domain.web.BackfillStatus
+import kotlinx.coroutines.CoroutineScope
+import kotlinx.coroutines.Dispatchers
+import kotlinx.coroutines.runBlocking
+import org.junit.jupiter.api.BeforeEach
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import java.time.Duration
+import java.time.Instant
+import java.util.*
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+import kotlin.test.assertFalse
+
+@DisplayName("Admin Backfill Tests")
+class BackfillServiceTest {
+
+    private class InMemoryJob(rowCount: Int) : BackfillJob {
+        override val name = "test-job"
+        override val description = "Uppercases test rows"
+
+        val rows = (1..rowCount).associate { "row-%03d".format(it) to "value $it" }.toSortedMap()
+        val processedIds = mutableListOf<String>()
+        var failOn: String? = null
+
+        override suspend fun nextBatch(afterId: String?, limit: Int): List<String> =
+            rows.keys.filter { afterId == null || it > afterId }.take(limit)
+
+        override suspend fun process(ids: List<String>): Int {
+            failOn?.let { if (it in ids) throw IllegalStateException("boom on $it") }
+            processedIds.addAll(ids)
+            return ids.count { id ->
+                val value = rows.getValue(id)
+                (value != value.uppercase()).also { rows[id] = value.uppercase() }
+            }
+        }
+    }
+
+    private class InMemoryProgressRepository : BackfillProgressRepository {
+        val saved = mutableMapOf<String, BackfillProgress>()
+        override suspend fun find(jobName: String): BackfillProgress? = saved[jobName]
+
+        override suspend fun claim(jobName: String, runId: UUID, now: Instant): BackfillProgress? {
+            val current = saved[jobName] ?: BackfillProgress(jobName, updatedAt = now)
+            if (current.status == BackfillStatus.RUNNING) return null
+            saved[jobName] = current.copy(status = BackfillStatus.RUNNING, runId = runId, updatedAt = now)
+            return current
+        }
+
+        override suspend fun save(progress: BackfillProgress): Boolean {
+            if (saved[progress.jobName]?.runId != progress.runId) return false
+            saved[progress.jobName] = progress
+            return true
+        }
+
+        override suspend fun failStale(staleBefore: Instant, now: Instant): Int {
+            val stale = saved.values.filter { it.status == BackfillStatus.RUNNING && it.updatedAt < staleBefore }
+            stale.forEach {
+                saved[it.jobName] = it.copy(status = BackfillStatus.FAILED, lastError = "Interrupted", runId = null, updatedAt = now)
+            }
+            return stale.size
+        }
+    }
+
+    private class RecordingAuditRepository : AdminAuditRepository {
+        val entries = mutableListOf<AdminAuditEntry>()
+        override suspend fun record(entry: AdminAuditEntry) {
+            entries.add(entry)
+        }
+        override suspend fun query(filter: AuditLogFilter, limit: Int, offset: Long) = emptyList<AuditLogEntry>()
+    }
+
+    private lateinit var job: InMemoryJob
+    private lateinit var progressRepository: InMemoryProgressRepository
+    private lateinit var auditRepository: RecordingAuditRepository
+    private lateinit var service: BackfillService
+
+    private val adminId = UUID.randomUUID()
+    private var now = Instant.parse("2025-09-01T12:00:00Z")
+    private val systemAdmin = listOf("manage_system_settings")
+
+    @BeforeEach
+    fun setup() {
+        job = InMemoryJob(rowCount = 25)
+        progressRepository = InMemoryProgressRepository()
+        auditRepository = RecordingAuditRepository()
+        // Unconfined runs the background batches to completion before start() returns
+        service = BackfillService(
+            listOf(job),
+            progressRepository,
+            auditRepository,
+            CoroutineScope(Dispatchers.Unconfined),
+            clock = { now }
+        )
+    }
+
+    @Test
+    @DisplayName("A paused run resumes from its cursor without reprocessing rows")
+    fun resumesFromCursor() = runBlocking {
+        val started = service.start(adminId, systemAdmin, "test-job", BackfillRunRequest(batchSize = 10, maxBatches = 1))
+        assertEquals("running", started.status)
+        val first = service.status(systemAdmin, "test-job")
+        assertEquals("paused", first.status)
+        assertEquals("row-010", first.cursor)
+        assertEquals(10, first.processed)
+
+        service.start(adminId, systemAdmin, "test-job", BackfillRunRequest(batchSize = 10))
+        val second = service.status(systemAdmin, "test-job")
+        assertEquals("completed", second.status)
+        assertEquals(25, second.processed)
+        assertEquals(25, second.updated)
+        assertEquals(3, second.batches)
+
+        assertEquals(job.rows.keys.toList(), job.processedIds)
+        assertEquals(2, auditRepository.entries.size)
+    }
+
+    @Test
+    @DisplayName("A failed batch keeps the last good cursor and is retried on the next run")
+    fun failedBatchRetried() = runBlocking {
+        job.failOn = "row-015"
+
+        service.start(adminId, systemAdmin, "test-job", BackfillRunRequest(batchSize = 10))
+        val failed = service.status(systemAdmin, "test-job")
+        assertEquals("failed", failed.status)
+        assertEquals("row-010", failed.cursor)
+        assertEquals("boom on row-015", failed.lastError)
+
+        job.failOn = null
+        service.start(adminId, systemAdmin, "test-job", BackfillRunRequest(batchSize = 10))
+        assertEquals("completed", service.status(systemAdmin, "test-job").status)
+        assertEquals(job.rows.keys.toList(), job.processedIds)
+    }
+
+    @Test
+    @DisplayName("Completed jobs are not re-run unless restarted, and restarts change nothing")
+    fun completedJobIsIdempotent() = runBlocking {
+        service.start(adminId, systemAdmin, "test-job", BackfillRunRequest(batchSize = 10))
+        job.processedIds.clear()
+
+        val again = service.start(adminId, systemAdmin, "test-job", BackfillRunRequest(batchSize = 10))
+        assertEquals("completed", again.status)
+        assertEquals(emptyList(), job.processedIds)
+
+        service.start(adminId, systemAdmin, "test-job", BackfillRunRequest(batchSize = 10, restart = true))
+        val restarted = service.status(systemAdmin, "test-job")
+        assertEquals(25, restarted.processed)
+        assertEquals(0, restarted.updated)
+    }
+
+    @Test
+    @DisplayName("Running backfills requires system settings permission and a known job")
+    fun requiresPermissionAndKnownJob() = runBlocking {
+        assertFailsWith<SecurityException> {
+            service.start(adminId, listOf("moderate_content"), "test-job", BackfillRunRequest())
+        }
+        assertFailsWith<NoSuchElementException> {
+            service.start(adminId, systemAdmin, "missing-job", BackfillRunRequest())
+        }
+        assertEquals(emptyList(), job.processedIds)
+    }
+
+    @Test
+    @DisplayName("A job held by another run is refused until that run goes stale, then resumes from its cursor")
+    fun staleRunIsRecovered() = runBlocking {
+        // Another instance crashed after saving its first batch
+        val crashedRun = UUID.randomUUID()
+        progressRepository.saved["test-job"] = BackfillProgress(
+            "test-job", cursor = "row-010", status = BackfillStatus.RUNNING, processed = 10, batches = 1,
+            startedAt = now, updatedAt = now, runId = crashedRun
+        )
+
+        assertFailsWith<BackfillAlreadyRunningException> {
+            service.start(adminId, systemAdmin, "test-job", BackfillRunRequest(batchSize = 10))
+        }
+
+        now = now.plus(Duration.ofMinutes(16))
+        assertEquals("failed", service.status(systemAdmin, "test-job").status)
+
+        service.start(adminId, systemAdmin, "test-job", BackfillRunRequest(batchSize = 10))
+        val resumed = service.status(systemAdmin, "test-job")
+        assertEquals("completed", resumed.status)
+        assertEquals(25, resumed.processed)
+        assertEquals(job.rows.keys.drop(10), job.processedIds)
+        // The crashed run can no longer overwrite the resumed progress
+        val latest = progressRepository.saved.getValue("test-job")
+        assertFalse(progressRepository.save(latest.copy(runId = crashedRun, status = BackfillStatus.RUNNING)))
+    }
+}
@@ -0,0 +1,42 @@
+package com.wondernest.utils
+
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import kotlin.test.assertEquals
+import kotlin.test.assertTrue
+
+@DisplayName("Password Strength Tests")
+class PasswordStrengthTest {
+
+    @Test
+    @DisplayName("Common passwords and short single-class passwords are very weak")
+    fun weakPasswords() {
+        assertEquals(PasswordStrength.VERY_WEAK, ValidationUtils.scorePassword("password").strength)
+        assertEquals(PasswordStrength.VERY_WEAK, ValidationUtils.scorePassword("aaaaaaaa").strength)
+
+        val short = ValidationUtils.scorePassword("aB3$")
+        assertEquals(PasswordStrength.WEAK, short.strength)
+        assertTrue("Use at least 8 characters" in short.suggestions)
+    }
+
+    @Test
+    @DisplayName("Dictionary words and sequences are penalized despite character variety")
+    fun penalizesPatterns() {
+        val result = ValidationUtils.scorePassword("P@ssword123!")
+
+        assertEquals(PasswordStrength.WEAK, result.strength)
+        assertTrue("Avoid common words and names" in result.suggestions)
+        assertTrue("Avoid sequences like abc, 123 or qwerty" in result.suggestions)
+    }
+
+    @Test
+    @DisplayName("Long or varied passwords score strong, with suggestions for what is missing")
+    fun strongPasswords() {
+        val varied = ValidationUtils.scorePassword("Tr0ub4dor&3")
+        assertEquals(PasswordStrength.STRONG, varied.strength)
+        assertEquals(listOf("Use 12 or more characters"), varied.suggestions)
+
+        assertEquals(PasswordStrength.STRONG, ValidationUtils.scorePassword("correcthorsebatterystaple").strength)
+        assertEquals(PasswordStrength.VERY_STRONG, ValidationUtils.scorePassword("Xk9#mQ2vL7!pR4&z").strength)
+    }
+}
@@ -0,0 +1,44 @@
+package com.wondernest.utils
+
+import org.junit.jupiter.api.DisplayName
+import org.junit.jupiter.api.Test
+import kotlin.test.assertFalse
+import kotlin.test.assertTrue
+
+@DisplayName("Rich Text Sanitization Tests")
+class RichTextSanitizationTest {
+
+    @Test
+    @DisplayName("Safe formatting survives while scripts and event handlers are removed")
+    fun keepsSafeTags() {
+        val sanitized = ValidationUtils.sanitizeRichText(
+            "<p onclick=\"steal()\">A <b>bold</b> adventure</p><script>alert('x')</script>"
+        )!!
+
+        assertTrue(sanitized.contains("<b>bold</b>"))
+        assertTrue(sanitized.contains("<p>"))
+        assertFalse(sanitized.contains("<script"))
+        assertFalse(sanitized.contains("alert"))
+        assertFalse(sanitized.contains("onclick"))
+    }
+
+    @Test
+    @DisplayName("Dangerous links and attributes are stripped")
+    fun stripsDangerousLinks() {
+        val sanitized = ValidationUtils.sanitizeRichText(
+            "<a href=\"javascript:alert(1)\">bad</a> <a href=\"https://wondernest.app\" style=\"color:red\">good</a><img src=x onerror=alert(1)>"
+        )!!
+
+        assertFalse(sanitized.contains("javascript:"))
+        assertFalse(sanitized.contains("style="))
+        assertFalse(sanitized.contains("<img"))
+        assertTrue(sanitized.contains("href=\"https://wondernest.app\""))
+        assertTrue(sanitized.contains("nofollow"))
+    }
+
+    @Test
+    @DisplayName("Plain-text sanitization still escapes everything")
+    fun plainTextStillEscaped() {
+        assertFalse(ValidationUtils.sanitizeString("<b>bold</b>")!!.contains("<b>"))
+    }
+}